@@ -233,6 +233,61 @@ impl UnicodeString {
         &self.string[string_start_byte_index..string_end_byte_index]
     }
 
+    /// [clip_to_width](Self::clip_to_width) leaves out a wide grapheme cluster that
+    /// straddles either edge of the cols that it clips to, since half of it can't be
+    /// painted. Returns the number of cols (at the start, at the end) that are left blank
+    /// because of this, so that they can be painted over w/ spaces & the rest of the line
+    /// stays in its cols.
+    pub fn get_clip_to_width_padding(
+        &self,
+        /* index */ start_display_col_index: ChUnit,
+        /* width */ max_display_col_count: ChUnit,
+    ) -> (ChUnit, ChUnit) {
+        let end_display_col_index = start_display_col_index + max_display_col_count;
+        let mut start_padding = ch!(0);
+        let mut end_padding = ch!(0);
+        for segment in self.iter() {
+            let segment_start = segment.display_col_offset;
+            let segment_end = segment_start + segment.unicode_width;
+            if segment_start < start_display_col_index
+                && segment_end > start_display_col_index
+            {
+                start_padding =
+                    segment_end.min(end_display_col_index) - start_display_col_index;
+            }
+            if segment_start >= start_display_col_index
+                && segment_start < end_display_col_index
+                && segment_end > end_display_col_index
+            {
+                end_padding = end_display_col_index - segment_start;
+            }
+        }
+        (start_padding, end_padding)
+    }
+
+    /// Same as [clip_to_width](Self::clip_to_width), except that the cols of a wide
+    /// grapheme cluster that straddles either edge are painted as spaces. More info in
+    /// [get_clip_to_width_padding](Self::get_clip_to_width_padding).
+    pub fn clip_to_width_padded(
+        &self,
+        /* index */ start_display_col_index: ChUnit,
+        /* width */ max_display_col_count: ChUnit,
+    ) -> String {
+        let (start_padding, end_padding) = self
+            .get_clip_to_width_padding(start_display_col_index, max_display_col_count);
+        // The text starts after the wide grapheme cluster at the start.
+        let clipped = self.clip_to_width(
+            start_display_col_index + start_padding,
+            max_display_col_count - start_padding,
+        );
+        format!(
+            "{}{}{}",
+            " ".repeat(ch!(@to_usize start_padding)),
+            clipped,
+            " ".repeat(ch!(@to_usize end_padding))
+        )
+    }
+
     /// If `self.string` is shorter than `max_display_col_count` then a padding string is
     /// returned (that is comprised of the `pad_char` repeated).
     pub fn try_get_postfix_padding_for(
@@ -334,44 +389,52 @@ impl UnicodeString {
         None
     }
 
+    /// Return the first grapheme cluster segment to the right of the one at the given
+    /// `display_col` that takes up at least 1 column. Zero width segments (eg: a
+    /// zero width space) can never have the caret placed on them, so they are skipped.
     pub fn get_string_at_right_of_display_col_index(
         &self,
         display_col: ChUnit,
     ) -> Option<UnicodeStringSegmentSliceResult> {
         let segment_at_col = self.at_display_col_index(display_col)?;
-        if segment_at_col.logical_index < self.len() - 1 {
-            let segment_right_of_col =
-                self.at_logical_index(segment_at_col.logical_index + 1)?;
-            Some(UnicodeStringSegmentSliceResult::new(
-                &segment_right_of_col.string,
-                segment_right_of_col.unicode_width,
-                segment_right_of_col.display_col_offset,
-            ))
-        } else {
-            None
-        }
+        let segment_right_of_col = self
+            .iter()
+            .skip(segment_at_col.logical_index + 1)
+            .find(|segment| segment.unicode_width > ch!(0))?;
+        Some(UnicodeStringSegmentSliceResult::new(
+            &segment_right_of_col.string,
+            segment_right_of_col.unicode_width,
+            segment_right_of_col.display_col_offset,
+        ))
     }
 
+    /// Return the first grapheme cluster segment to the left of the one at the given
+    /// `display_col` that takes up at least 1 column. Zero width segments are skipped, so
+    /// that moving the caret left always moves it by a non zero display width.
     pub fn get_string_at_left_of_display_col_index(
         &self,
         display_col: ChUnit,
     ) -> Option<UnicodeStringSegmentSliceResult> {
         let segment_at_col = self.at_display_col_index(display_col)?;
-        if segment_at_col.logical_index > 0 {
-            let segment_left_of_col =
-                self.at_logical_index(segment_at_col.logical_index - 1)?;
-            Some(UnicodeStringSegmentSliceResult::new(
-                &segment_left_of_col.string,
-                segment_left_of_col.unicode_width,
-                segment_left_of_col.display_col_offset,
-            ))
-        } else {
-            None
-        }
+        let segment_left_of_col = self
+            .iter()
+            .take(segment_at_col.logical_index)
+            .rev()
+            .find(|segment| segment.unicode_width > ch!(0))?;
+        Some(UnicodeStringSegmentSliceResult::new(
+            &segment_left_of_col.string,
+            segment_left_of_col.unicode_width,
+            segment_left_of_col.display_col_offset,
+        ))
     }
 
+    /// Return the last grapheme cluster segment that takes up at least 1 column. Any
+    /// trailing zero width segments are skipped.
     pub fn get_string_at_end(&self) -> Option<UnicodeStringSegmentSliceResult> {
-        let segment = self.last()?;
+        let segment = self
+            .iter()
+            .rev()
+            .find(|segment| segment.unicode_width > ch!(0))?;
         Some(UnicodeStringSegmentSliceResult::new(
             &segment.string,
             segment.unicode_width,
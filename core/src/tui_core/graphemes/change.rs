@@ -47,8 +47,12 @@ impl UnicodeString {
         let new_string = acc.join("");
 
         // In the caller - update the caret position based on the unicode width of the character.
+        // This is the amount by which the display width of the line grew, rather than the
+        // display width of the chunk by itself, since the chunk may combine w/ the grapheme
+        // cluster next to it (eg: a combining mark, or a ZWJ sequence).
         let new_unicode_string = UnicodeString::from(new_string);
-        let chunk_display_width: ChUnit = ch!(UnicodeString::str_display_width(chunk));
+        let chunk_display_width: ChUnit =
+            new_unicode_string.display_width - self.display_width;
         Some((new_unicode_string, chunk_display_width))
     }
 
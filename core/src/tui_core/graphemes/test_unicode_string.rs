@@ -280,4 +280,74 @@ mod tests {
         assert_eq2! {acc[0].string, "Hi "};
         assert_eq2! {acc[1].string, "😃 📦 🙏🏽 👨🏾‍🤝‍👨🏿."};
     }
+
+    #[test]
+    fn test_unicode_string_counts_for_single_multibyte_grapheme() {
+        let u_s = UnicodeString::from("中");
+        assert_eq2!(u_s.grapheme_cluster_segment_count, 1);
+        assert_eq2!(u_s.byte_size, "中".len());
+        assert_eq2!(u_s.display_width, ch!(2));
+
+        let u_s = UnicodeString::from("ae\u{301}中");
+        assert_eq2!(u_s.grapheme_cluster_segment_count, 3);
+        assert_eq2!(u_s.byte_size, "ae\u{301}中".len());
+        assert_eq2!(u_s.display_width, ch!(4));
+    }
+
+    #[test]
+    fn test_unicode_string_skips_zero_width_segments() {
+        // The zero width space between `x` & `y` can't have the caret placed on it.
+        let u_s = UnicodeString::from("x\u{200B}y\u{200B}");
+        assert_eq2!(u_s.display_width, ch!(2));
+
+        let left = u_s.get_string_at_left_of_display_col_index(1.into()).unwrap();
+        assert_eq2!(left.unicode_string_seg.string, "x");
+        assert_eq2!(left.unicode_width, ch!(1));
+
+        let right = u_s.get_string_at_right_of_display_col_index(0.into()).unwrap();
+        assert_eq2!(right.unicode_string_seg.string, "y");
+
+        let end = u_s.get_string_at_end().unwrap();
+        assert_eq2!(end.unicode_string_seg.string, "y");
+        assert_eq2!(end.display_col_at_which_seg_starts, ch!(1));
+    }
+
+    #[test]
+    fn test_unicode_string_clip_to_width_padded() {
+        // `中` & `文` each take up 2 cols: `a` is at col 0, `中` at 1-2, `文` at 3-4, `b`
+        // at 5.
+        let u_s = UnicodeString::from("a中文b");
+
+        // Starting in the middle of `中` used to shift `文b` left by a col.
+        assert_eq2!(u_s.clip_to_width(ch!(2), ch!(4)), "文b");
+        assert_eq2!(
+            u_s.get_clip_to_width_padding(ch!(2), ch!(4)),
+            (ch!(1), ch!(0))
+        );
+        assert_eq2!(u_s.clip_to_width_padded(ch!(2), ch!(4)), " 文b");
+
+        // `文` is cut off by the end.
+        assert_eq2!(u_s.clip_to_width_padded(ch!(0), ch!(4)), "a中 ");
+
+        // Cut off at both ends.
+        assert_eq2!(u_s.clip_to_width_padded(ch!(2), ch!(2)), "  ");
+
+        // No wide chars at the edges.
+        assert_eq2!(u_s.clip_to_width_padded(ch!(1), ch!(4)), "中文");
+        assert_eq2!(
+            u_s.get_clip_to_width_padding(ch!(1), ch!(4)),
+            (ch!(0), ch!(0))
+        );
+        for start_display_col_index in 0..6 {
+            for max_display_col_count in 0..6 {
+                let clipped = UnicodeString::from(u_s.clip_to_width_padded(
+                    ch!(start_display_col_index),
+                    ch!(max_display_col_count),
+                ));
+                let expected_width =
+                    (6 - start_display_col_index).min(max_display_col_count);
+                assert_eq2!(clipped.display_width, ch!(expected_width));
+            }
+        }
+    }
 }
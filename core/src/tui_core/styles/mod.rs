@@ -19,10 +19,12 @@
 pub mod hex_color_parser;
 pub mod style;
 pub mod stylesheet;
+pub mod stylesheet_loader;
 pub mod tui_color;
 
 // Re-export.
 pub use hex_color_parser::*;
 pub use style::*;
 pub use stylesheet::*;
+pub use stylesheet_loader::*;
 pub use tui_color::*;
@@ -14,6 +14,10 @@
  *   See the License for the specific language governing permissions and
  *   limitations under the License.
  */
+use std::{any::Any,
+          collections::HashMap,
+          fmt::{Debug, Formatter},
+          sync::Arc};
 
 use serde::{Deserialize, Serialize};
 
@@ -22,6 +26,37 @@ use crate::*;
 #[derive(Default, Debug, Clone, Serialize, Deserialize)]
 pub struct Stylesheet {
     pub styles: Vec<Style>,
+    /// Maps the names of the styles (in a stylesheet definition file) to their ids. More
+    /// info in [Stylesheet::try_load_from_file].
+    #[serde(default)]
+    pub style_ids_by_name: HashMap<String, u8>,
+    /// Styles that are only added to a style (w/ the same id) when their predicate
+    /// matches. They can't be loaded from a file. More info in
+    /// [Stylesheet::add_conditional_style].
+    #[serde(skip)]
+    pub conditional_styles: Vec<ConditionalStyle>,
+}
+
+/// Evaluated against the context that is passed to [get_styles!] w/ `@when`, eg: the
+/// app's state. Use [Stylesheet::add_conditional_style] to create one w/ a typed context.
+pub type StylePredicate = Arc<dyn Fn(&dyn Any) -> bool + Send + Sync>;
+
+/// A [Style] that is added on top of the style w/ the same `id` in the [Stylesheet], when
+/// its `predicate` matches.
+#[derive(Clone)]
+pub struct ConditionalStyle {
+    pub id: u8,
+    pub style: Style,
+    pub predicate: StylePredicate,
+}
+
+impl Debug for ConditionalStyle {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ConditionalStyle")
+            .field("id", &self.id)
+            .field("style", &self.style)
+            .finish_non_exhaustive()
+    }
 }
 
 #[macro_export]
@@ -45,8 +80,30 @@ macro_rules! get_style {
     };
 }
 
+/// Pass `@when: &state` to also get the [ConditionalStyle]s whose predicates match the
+/// `state`. More info in [Stylesheet::find_styles_by_ids_when].
 #[macro_export]
 macro_rules! get_styles {
+    (
+        @from_result: $arg_stylesheet_result : expr, // Eg: from: stylesheet,
+        [$($args:tt)*],                             // Eg: ["style1", "style2"]
+        @when: $arg_context : expr                  // Eg: @when: &state
+    ) => {
+        if let Ok(ref it) = $arg_stylesheet_result {
+            it.find_styles_by_ids_when(vec![$($args)*], $arg_context)
+        } else {
+            None
+        }
+    };
+
+    (
+        @from: $arg_stylesheet : expr, // Eg: from: stylesheet,
+        [$($args:tt)*],               // Eg: ["style1", "style2"]
+        @when: $arg_context : expr    // Eg: @when: &state
+    ) => {
+        $arg_stylesheet.find_styles_by_ids_when(vec![$($args)*], $arg_context)
+    };
+
     (
         @from_result: $arg_stylesheet_result : expr, // Eg: from: stylesheet,
         [$($args:tt)*]                              // Eg: ["style1", "style2"]
@@ -86,10 +143,47 @@ impl Stylesheet {
         });
     }
 
+    /// Add a `style` that is only used when `predicate` returns `true` for the context
+    /// passed to [get_styles!] w/ `@when` (or [Stylesheet::find_styles_by_ids_when]), eg:
+    /// a red border for a box when the app's state has an error. The `predicate` never
+    /// matches a context that isn't a `C`. Only the attributes that are set in `style`
+    /// override the ones of the style w/ the same `id`.
+    ///
+    /// ```ignore
+    /// stylesheet.add_conditional_style(
+    ///     Id::Input as u8,
+    ///     style! { color_fg: TuiColor::Basic(ANSIBasicColor::Red) },
+    ///     |state: &State| state.maybe_error.is_some(),
+    /// )?;
+    /// ```
+    pub fn add_conditional_style<C: Any>(
+        &mut self,
+        id: u8,
+        style: Style,
+        predicate: impl Fn(&C) -> bool + Send + Sync + 'static,
+    ) -> CommonResult<()> {
+        throws!({
+            if id == u8::MAX {
+                return CommonError::new_err_with_only_msg("Style id must be defined");
+            }
+            self.conditional_styles.push(ConditionalStyle {
+                id,
+                style,
+                predicate: Arc::new(move |context: &dyn Any| {
+                    context.downcast_ref::<C>().is_some_and(&predicate)
+                }),
+            });
+        });
+    }
+
     pub fn find_style_by_id(&self, id: u8) -> Option<Style> {
         self.styles.iter().find(|style| style.id == id).cloned()
     }
 
+    pub fn find_style_by_name(&self, name: &str) -> Option<Style> {
+        self.find_style_by_id(*self.style_ids_by_name.get(name)?)
+    }
+
     /// Returns [None] if no style in `ids` [Vec] is found.
     pub fn find_styles_by_ids(&self, ids: Vec<u8>) -> Option<Vec<Style>> {
         let mut styles = Vec::new();
@@ -107,6 +201,41 @@ impl Stylesheet {
         }
     }
 
+    /// Same as [Stylesheet::find_styles_by_ids], but each style is followed by the
+    /// [ConditionalStyle]s w/ its id whose predicates match the `context`. When the
+    /// returned styles are merged (eg: by [Stylesheet::compute]), the later ones override
+    /// the attributes that they set, so the precedence (from lowest to highest) is:
+    /// 1. The styles in the order of `ids`, ie: the styles of later ids win.
+    /// 2. For each id, its style, then its conditional styles in the order they were added,
+    ///    ie: a matching conditional style wins over its style & the ones added before it.
+    ///
+    /// The conditional styles for an id are used even if the stylesheet has no style w/
+    /// that id. Returns [None] if nothing is found.
+    pub fn find_styles_by_ids_when<C: Any>(
+        &self,
+        ids: Vec<u8>,
+        context: &C,
+    ) -> Option<Vec<Style>> {
+        let mut styles = Vec::new();
+
+        for id in ids {
+            if let Some(style) = self.find_style_by_id(id) {
+                styles.push(style);
+            }
+            for conditional_style in &self.conditional_styles {
+                if conditional_style.id == id && (conditional_style.predicate)(context) {
+                    styles.push(conditional_style.style);
+                }
+            }
+        }
+
+        if styles.is_empty() {
+            None
+        } else {
+            styles.into()
+        }
+    }
+
     pub fn compute(styles: &Option<Vec<Style>>) -> Option<Style> {
         if let Some(styles) = styles {
             let mut computed = Style::default();
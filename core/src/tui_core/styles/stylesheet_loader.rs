@@ -0,0 +1,277 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+//! Load a [Stylesheet] from a definition file (TOML or JSON), so that apps can be themed
+//! w/out recompiling. Each style is a named entry, eg in TOML:
+//!
+//! ```toml
+//! [editor]
+//! id = 4
+//! fg = "#ff00ff"
+//! bg = "dark_blue"
+//! bold = true
+//! padding = 1
+//! ```
+//!
+//! And in JSON:
+//!
+//! ```json
+//! { "editor": { "id": 4, "fg": "#ff00ff", "bg": "dark_blue", "bold": true } }
+//! ```
+//!
+//! - The `id` is required, so that [get_style!] & [get_styles!] can find the style. The
+//!   style can also be found by its name using [Stylesheet::find_style_by_name].
+//! - Colors are either hex (`#RRGGBB`) or one of the [ANSIBasicColor] names in snake case
+//!   (eg: `dark_blue`), or `reset`.
+//! - The attributes are `bold`, `italic`, `underline`, `dim`, `reverse`, `strikethrough`
+//!   and `lolcat`.
+//! - Unknown keys & bad colors are rejected w/ an error that names the offending style.
+
+use std::{collections::BTreeMap, path::Path};
+
+use serde::Deserialize;
+
+use crate::*;
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct StyleDefinition {
+    id: u8,
+    fg: Option<String>,
+    bg: Option<String>,
+    #[serde(default)]
+    bold: bool,
+    #[serde(default)]
+    italic: bool,
+    #[serde(default)]
+    underline: bool,
+    #[serde(default)]
+    dim: bool,
+    #[serde(default)]
+    reverse: bool,
+    #[serde(default)]
+    strikethrough: bool,
+    #[serde(default)]
+    lolcat: bool,
+    padding: Option<u16>,
+}
+
+type StylesheetDefinition = BTreeMap<String, StyleDefinition>;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StylesheetFormat {
+    Toml,
+    Json,
+}
+
+impl Stylesheet {
+    /// The [StylesheetFormat] is picked using the file extension (`.toml` or `.json`).
+    pub fn try_load_from_file(path: impl AsRef<Path>) -> CommonResult<Stylesheet> {
+        let path = path.as_ref();
+        let format = match path.extension().and_then(|it| it.to_str()) {
+            Some("toml") => StylesheetFormat::Toml,
+            Some("json") => StylesheetFormat::Json,
+            _ => {
+                return CommonError::new(
+                    CommonErrorType::InvalidArguments,
+                    &format!(
+                        "Stylesheet file must have a .toml or .json extension: {}",
+                        path.display()
+                    ),
+                )
+            }
+        };
+        let Ok(input) = std::fs::read_to_string(path) else {
+            return CommonError::new(
+                CommonErrorType::IOError,
+                &format!("Failed to read stylesheet file: {}", path.display()),
+            );
+        };
+        Self::try_from_str(&input, format)
+    }
+
+    pub fn try_from_str(
+        input: &str,
+        format: StylesheetFormat,
+    ) -> CommonResult<Stylesheet> {
+        let result_definition: Result<StylesheetDefinition, String> = match format {
+            StylesheetFormat::Toml => toml::from_str(input).map_err(|it| it.to_string()),
+            StylesheetFormat::Json => {
+                serde_json::from_str(input).map_err(|it| it.to_string())
+            }
+        };
+
+        let definition = match result_definition {
+            Ok(it) => it,
+            Err(msg) => {
+                return CommonError::new(
+                    CommonErrorType::ParsingError,
+                    &format!("Invalid stylesheet: {msg}"),
+                )
+            }
+        };
+
+        let mut stylesheet = Stylesheet::new();
+        for (name, style_definition) in definition {
+            let style = convert(&name, style_definition)?;
+            stylesheet.add_style(style)?;
+            stylesheet.style_ids_by_name.insert(name, style.id);
+        }
+        Ok(stylesheet)
+    }
+}
+
+fn convert(name: &str, it: StyleDefinition) -> CommonResult<Style> {
+    let get_color = |maybe_color: Option<String>| -> CommonResult<Option<TuiColor>> {
+        match maybe_color {
+            Some(color) => match try_parse_color(&color) {
+                Ok(it) => Ok(Some(it)),
+                Err(_) => CommonError::new(
+                    CommonErrorType::InvalidValue,
+                    &format!("Invalid color `{color}` in style `{name}`"),
+                ),
+            },
+            None => Ok(None),
+        }
+    };
+
+    Ok(Style {
+        id: it.id,
+        bold: it.bold,
+        italic: it.italic,
+        dim: it.dim,
+        underline: it.underline,
+        reverse: it.reverse,
+        strikethrough: it.strikethrough,
+        lolcat: it.lolcat,
+        color_fg: get_color(it.fg)?,
+        color_bg: get_color(it.bg)?,
+        padding: it.padding.map(|it| ch!(it)),
+        ..Default::default()
+    })
+}
+
+/// Parse a hex color (`#RRGGBB`), or a named color (eg: `dark_blue`, or `reset`).
+pub fn try_parse_color(input: &str) -> CommonResult<TuiColor> {
+    if input.starts_with('#') {
+        return match parse_hex_color(input) {
+            Ok(("", rgb_value)) => Ok(TuiColor::Rgb(rgb_value)),
+            _ => CommonError::new_err_with_only_type(
+                CommonErrorType::InvalidHexColorFormat,
+            ),
+        };
+    }
+
+    let color = match input.to_lowercase().as_str() {
+        "reset" => TuiColor::Reset,
+        "black" => TuiColor::Basic(ANSIBasicColor::Black),
+        "white" => TuiColor::Basic(ANSIBasicColor::White),
+        "grey" | "gray" => TuiColor::Basic(ANSIBasicColor::Grey),
+        "dark_grey" | "dark_gray" => TuiColor::Basic(ANSIBasicColor::DarkGrey),
+        "red" => TuiColor::Basic(ANSIBasicColor::Red),
+        "dark_red" => TuiColor::Basic(ANSIBasicColor::DarkRed),
+        "green" => TuiColor::Basic(ANSIBasicColor::Green),
+        "dark_green" => TuiColor::Basic(ANSIBasicColor::DarkGreen),
+        "yellow" => TuiColor::Basic(ANSIBasicColor::Yellow),
+        "dark_yellow" => TuiColor::Basic(ANSIBasicColor::DarkYellow),
+        "blue" => TuiColor::Basic(ANSIBasicColor::Blue),
+        "dark_blue" => TuiColor::Basic(ANSIBasicColor::DarkBlue),
+        "magenta" => TuiColor::Basic(ANSIBasicColor::Magenta),
+        "dark_magenta" => TuiColor::Basic(ANSIBasicColor::DarkMagenta),
+        "cyan" => TuiColor::Basic(ANSIBasicColor::Cyan),
+        "dark_cyan" => TuiColor::Basic(ANSIBasicColor::DarkCyan),
+        _ => return CommonError::new_err_with_only_type(CommonErrorType::InvalidValue),
+    };
+    Ok(color)
+}
+
+#[cfg(test)]
+mod test_stylesheet_loader {
+    use super::*;
+
+    fn get_err_msg(result: CommonResult<Stylesheet>) -> String {
+        let err = result.err().unwrap();
+        let common_error = err.downcast_ref::<CommonError>().unwrap();
+        common_error.err_msg.clone().unwrap()
+    }
+
+    #[test]
+    fn test_load_toml() {
+        let input = r##"
+            [editor]
+            id = 4
+            fg = "#ff00ff"
+            bg = "dark_blue"
+            bold = true
+            padding = 1
+
+            [title]
+            id = 5
+            lolcat = true
+        "##;
+        let stylesheet = Stylesheet::try_from_str(input, StylesheetFormat::Toml).unwrap();
+
+        let style = stylesheet.find_style_by_id(4).unwrap();
+        assert_eq2!(
+            style.color_fg,
+            Some(TuiColor::Rgb(RgbValue::from_u8(255, 0, 255)))
+        );
+        assert_eq2!(
+            style.color_bg,
+            Some(TuiColor::Basic(ANSIBasicColor::DarkBlue))
+        );
+        assert_eq2!(style.bold, true);
+        assert_eq2!(style.padding, Some(ch!(1)));
+        assert_eq2!(stylesheet.find_style_by_name("editor"), Some(style));
+
+        assert_eq2!(stylesheet.find_style_by_name("title").unwrap().lolcat, true);
+        assert_eq2!(get_styles!(@from: stylesheet, [4, 5]).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_load_json() {
+        let input = r##"{ "editor": { "id": 4, "fg": "red", "italic": true } }"##;
+        let stylesheet = Stylesheet::try_from_str(input, StylesheetFormat::Json).unwrap();
+        let style = stylesheet.find_style_by_name("editor").unwrap();
+        assert_eq2!(style.id, 4);
+        assert_eq2!(style.color_fg, Some(TuiColor::Basic(ANSIBasicColor::Red)));
+        assert_eq2!(style.italic, true);
+    }
+
+    #[test]
+    fn test_errors() {
+        // Unknown key.
+        let input = r#"
+            [editor]
+            id = 4
+            colour = "red"
+        "#;
+        let msg = get_err_msg(Stylesheet::try_from_str(input, StylesheetFormat::Toml));
+        assert!(msg.contains("unknown field `colour`"), "{msg}");
+
+        // Bad colors.
+        for color in ["#ff00", "#gg0000", "not_a_color"] {
+            let input = format!("[editor]\nid = 4\nfg = \"{color}\"");
+            let msg =
+                get_err_msg(Stylesheet::try_from_str(&input, StylesheetFormat::Toml));
+            assert_eq2!(msg, format!("Invalid color `{color}` in style `editor`"));
+        }
+
+        // Unsupported file extension.
+        assert!(Stylesheet::try_load_from_file("styles.yaml").is_err());
+    }
+}
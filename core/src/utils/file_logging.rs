@@ -24,7 +24,7 @@
 use std::{fs::File, sync::Once};
 
 use chrono::Local;
-use log::LevelFilter;
+use log::{Level, LevelFilter};
 use simplelog::*;
 use time::UtcOffset;
 
@@ -79,111 +79,48 @@ pub fn try_to_set_log_level(level: LevelFilter) -> CommonResult<String> {
     }
 }
 
-/// Log the message to the `INFO` log level using a file logger. There could be issues w/ accessing
-/// this file; if it fails this function will not propagate the log error.
-pub fn log_info(arg: String) {
-    if init_file_logger_once().is_err() {
-        eprintln!(
-            "Error initializing file logger due to {}",
-            init_file_logger_once().unwrap_err()
-        );
-    } else {
-        match ENABLE_MULTITHREADED_LOG_WRITING {
-            true => {
-                std::thread::spawn(move || {
-                    log::info!("{}", arg);
-                });
-            }
-            false => {
-                log::info!("{}", arg);
-            }
-        }
-    }
-}
-
-/// Log the message to the `DEBUG` log level using a file logger. There could be issues w/ accessing
-/// this file; if it fails this function will not propagate the log error.
-pub fn log_debug(arg: String) {
-    if init_file_logger_once().is_err() {
-        eprintln!(
-            "Error initializing file logger due to {}",
-            init_file_logger_once().unwrap_err()
-        );
-    } else {
-        match ENABLE_MULTITHREADED_LOG_WRITING {
-            true => {
-                std::thread::spawn(move || {
-                    log::debug!("{}", arg);
-                });
-            }
-            false => {
-                log::debug!("{}", arg);
-            }
-        }
-    }
-}
-
-/// Log the message to the `WARN` log level using a file logger. There could be issues w/ accessing
-/// this file; if it fails this function will not propagate the log error.
-pub fn log_warn(arg: String) {
-    if init_file_logger_once().is_err() {
-        eprintln!(
-            "Error initializing file logger due to {}",
-            init_file_logger_once().unwrap_err()
-        );
-    } else {
-        match ENABLE_MULTITHREADED_LOG_WRITING {
-            true => {
-                std::thread::spawn(move || {
-                    log::warn!("{}", arg);
-                });
-            }
-            false => {
-                log::warn!("{}", arg);
-            }
-        }
-    }
-}
-
-/// Log the message to the `TRACE` log level using a file logger. There could be issues w/ accessing
-/// this file; if it fails this function will not propagate the log error.
-pub fn log_trace(arg: String) {
-    if init_file_logger_once().is_err() {
-        eprintln!(
-            "Error initializing file logger due to {}",
-            init_file_logger_once().unwrap_err()
-        );
-    } else {
-        match ENABLE_MULTITHREADED_LOG_WRITING {
-            true => {
-                std::thread::spawn(move || {
-                    log::trace!("{}", arg);
-                });
-            }
-            false => {
-                log::trace!("{}", arg);
-            }
-        }
-    }
-}
-
-/// Log the message to the `ERROR` log level using a file logger. There could be issues w/ accessing
-/// this file; if it fails this function will not propagate the log error.
-pub fn log_error(arg: String) {
-    if init_file_logger_once().is_err() {
-        eprintln!(
-            "Error initializing file logger due to {}",
-            init_file_logger_once().unwrap_err()
-        );
-    } else {
-        match ENABLE_MULTITHREADED_LOG_WRITING {
-            true => {
-                std::thread::spawn(move || {
-                    log::error!("{}", arg);
-                });
-            }
-            false => {
-                log::error!("{}", arg);
+/// Log the message to the `INFO` log level using the [LogSink] that is set w/ [set_log_sink]
+/// (the [FileLogSink] by default).
+pub fn log_info(arg: String) { log_to_sink(Level::Info, arg); }
+
+/// Log the message to the `DEBUG` log level using the [LogSink] that is set w/ [set_log_sink]
+/// (the [FileLogSink] by default).
+pub fn log_debug(arg: String) { log_to_sink(Level::Debug, arg); }
+
+/// Log the message to the `WARN` log level using the [LogSink] that is set w/ [set_log_sink]
+/// (the [FileLogSink] by default).
+pub fn log_warn(arg: String) { log_to_sink(Level::Warn, arg); }
+
+/// Log the message to the `TRACE` log level using the [LogSink] that is set w/ [set_log_sink]
+/// (the [FileLogSink] by default).
+pub fn log_trace(arg: String) { log_to_sink(Level::Trace, arg); }
+
+/// Log the message to the `ERROR` log level using the [LogSink] that is set w/ [set_log_sink]
+/// (the [FileLogSink] by default).
+pub fn log_error(arg: String) { log_to_sink(Level::Error, arg); }
+
+/// The default [LogSink], which writes to a file logger. There could be issues w/ accessing
+/// this file; if it fails the log error is not propagated.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct FileLogSink;
+
+impl LogSink for FileLogSink {
+    fn log(&self, level: Level, message: String) {
+        if init_file_logger_once().is_err() {
+            eprintln!(
+                "Error initializing file logger due to {}",
+                init_file_logger_once().unwrap_err()
+            );
+        } else {
+            match ENABLE_MULTITHREADED_LOG_WRITING {
+                true => {
+                    std::thread::spawn(move || {
+                        log::log!(level, "{}", message);
+                    });
+                }
+                false => {
+                    log::log!(level, "{}", message);
+                }
             }
         }
     }
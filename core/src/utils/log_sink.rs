@@ -0,0 +1,128 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+//! All the logging functions (eg: [log_debug], [log_info]) write to the [LogSink] that is
+//! set w/ [set_log_sink]. By default this is the [FileLogSink], which writes to the file
+//! in [FILE_PATH].
+//!
+//! Apps that have their own logging (eg: w/ `tracing`) can capture these messages into it
+//! by setting a sink of their own, or the [LogFacadeSink] which forwards them to the `log`
+//! facade (w/out installing a file logger).
+//!
+//! The debug logging in the `tui` crate is wrapped in `call_if_true!(DEBUG_TUI_MOD, ...)`,
+//! where `DEBUG_TUI_MOD` is a `const`, so when it is disabled the messages aren't even
+//! formatted, no matter which sink is set.
+
+use std::sync::RwLock;
+
+use log::Level;
+
+use crate::*;
+
+/// Receives all the messages logged by [log_info], [log_debug], [log_warn], [log_trace] &
+/// [log_error]. More info in the [module docs](self).
+pub trait LogSink: Send + Sync {
+    fn log(&self, level: Level, message: String);
+}
+
+/// Forwards the messages to the `log` facade, so that they end up in whichever logger the
+/// app has installed (eg: `tracing` w/ the `tracing-log` bridge). Unlike [FileLogSink],
+/// this doesn't install a logger of its own.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct LogFacadeSink;
+
+impl LogSink for LogFacadeSink {
+    fn log(&self, level: Level, message: String) {
+        log::log!(level, "{}", message);
+    }
+}
+
+/// [None] means that the [FileLogSink] is used.
+static LOG_SINK: RwLock<Option<Box<dyn LogSink>>> = RwLock::new(None);
+
+/// Replace the [LogSink] that all the messages are written to. More info in the [module
+/// docs](self).
+pub fn set_log_sink(log_sink: impl LogSink + 'static) {
+    if let Ok(mut it) = LOG_SINK.write() {
+        *it = Some(Box::new(log_sink));
+    }
+}
+
+/// Go back to the default [FileLogSink].
+pub fn reset_log_sink() {
+    if let Ok(mut it) = LOG_SINK.write() {
+        *it = None;
+    }
+}
+
+/// Write the message to the [LogSink] that is currently set.
+pub fn log_to_sink(level: Level, message: String) {
+    match LOG_SINK.read() {
+        Ok(it) => match it.as_ref() {
+            Some(log_sink) => log_sink.log(level, message),
+            None => FileLogSink.log(level, message),
+        },
+        // The lock is only poisoned if a sink panicked, so don't use it again.
+        Err(_) => FileLogSink.log(level, message),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+
+    struct TestLogSink {
+        messages: Arc<Mutex<Vec<(Level, String)>>>,
+    }
+
+    impl LogSink for TestLogSink {
+        fn log(&self, level: Level, message: String) {
+            self.messages.lock().unwrap().push((level, message));
+        }
+    }
+
+    #[test]
+    fn test_set_log_sink() {
+        let messages = Arc::new(Mutex::new(vec![]));
+        set_log_sink(TestLogSink {
+            messages: messages.clone(),
+        });
+
+        log_debug("test_set_log_sink debug".to_string());
+        log_error("test_set_log_sink error".to_string());
+        reset_log_sink();
+        log_info("test_set_log_sink info".to_string());
+
+        // Other tests may log at the same time, so only look at the messages from this one.
+        let messages = messages
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(_, message)| message.starts_with("test_set_log_sink"))
+            .cloned()
+            .collect::<Vec<_>>();
+        assert_eq2!(
+            messages,
+            vec![
+                (Level::Debug, "test_set_log_sink debug".to_string()),
+                (Level::Error, "test_set_log_sink error".to_string())
+            ]
+        );
+    }
+}
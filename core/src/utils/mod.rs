@@ -17,6 +17,8 @@
 
 // Attach.
 pub mod file_logging;
+pub mod log_sink;
 
 // Re-export.
 pub use file_logging::*;
+pub use log_sink::*;
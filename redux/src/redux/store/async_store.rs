@@ -18,6 +18,9 @@
 use core::fmt::Debug;
 use std::sync::Arc;
 
+use r3bl_rs_utils_core::{CommonError, CommonErrorType, CommonResult};
+#[cfg(feature = "persistence")]
+use serde::{de::DeserializeOwned, Serialize};
 use tokio::sync::RwLock;
 
 use crate::{redux::{AsyncMiddlewareSpawnsVec,
@@ -27,7 +30,8 @@ use crate::{redux::{AsyncMiddlewareSpawnsVec,
             AsyncMiddleware,
             AsyncMiddlewareSpawns,
             AsyncReducer,
-            AsyncSubscriber};
+            AsyncSubscriber,
+            StoreHistory};
 
 pub type SharedStore<S, A> = Arc<RwLock<Store<S, A>>>;
 
@@ -52,6 +56,8 @@ where
     pub middleware_spawns_vec: AsyncMiddlewareSpawnsVec<S, A>,
     pub subscriber_vec: AsyncSubscriberVec<S>,
     pub reducer_vec: AsyncReducerVec<S, A>,
+    /// This is `None` unless [Store::enable_history] is called.
+    pub history: Option<StoreHistory<S, A>>,
 }
 
 impl<S, A> Default for Store<S, A>
@@ -66,6 +72,7 @@ where
             middleware_spawns_vec: Default::default(),
             reducer_vec: Default::default(),
             subscriber_vec: Default::default(),
+            history: None,
         }
     }
 }
@@ -148,6 +155,9 @@ where
 
     async fn actually_dispatch_action(&mut self, action: &A) {
         self.run_reducers(action).await;
+        if let Some(history) = self.history.as_mut() {
+            history.push(action.clone(), self.state.clone());
+        }
         self.run_subscribers().await;
     }
 
@@ -215,3 +225,112 @@ where
         }
     }
 }
+
+// Handle time travel debugging.
+impl<S, A> Store<S, A>
+where
+    S: Clone + Default + PartialEq + Debug + Sync + Send,
+    A: Clone + Default + Send + Sync,
+{
+    /// Start recording every action that is dispatched (including the ones returned by
+    /// middlewares) along w/ a snapshot of the state after the reducers have run. At most
+    /// `capacity` of these are kept (oldest ones are dropped first). Calling this again
+    /// discards anything that was previously recorded.
+    pub async fn enable_history(&mut self, capacity: usize) -> &mut Store<S, A> {
+        self.history = Some(StoreHistory::new(capacity));
+        self
+    }
+
+    pub async fn disable_history(&mut self) -> &mut Store<S, A> {
+        self.history = None;
+        self
+    }
+
+    /// Returns an empty vec if history isn't enabled.
+    pub fn get_history_actions(&self) -> Vec<A> {
+        match &self.history {
+            Some(history) => history
+                .entries
+                .iter()
+                .map(|entry| entry.action.clone())
+                .collect(),
+            None => vec![],
+        }
+    }
+
+    /// Index of the recorded state that the store currently has.
+    pub fn get_history_index(&self) -> Option<usize> {
+        self.history
+            .as_ref()
+            .and_then(|history| history.current_index)
+    }
+
+    /// Replace the current state w/ the one that was recorded at `index` and then run the
+    /// subscribers. Reducers and middlewares are not run. If an action is dispatched after
+    /// jumping back, then all the entries after `index` are discarded.
+    pub async fn jump_to(&mut self, index: usize) -> CommonResult<()> {
+        let Some(history) = self.history.as_mut() else {
+            return CommonError::new(
+                CommonErrorType::InvalidState,
+                "History is not enabled for this store",
+            );
+        };
+
+        let Some(entry) = history.get(index) else {
+            return CommonError::new(
+                CommonErrorType::IndexOutOfBounds,
+                &format!("No history entry at index: {index}"),
+            );
+        };
+
+        self.state = entry.state.clone();
+        history.current_index = Some(index);
+        self.run_subscribers().await;
+        Ok(())
+    }
+}
+
+// Handle persistence.
+#[cfg(feature = "persistence")]
+impl<S, A> Store<S, A>
+where
+    S: Clone + Default + PartialEq + Debug + Sync + Send + Serialize + DeserializeOwned,
+    A: Clone + Default + Send + Sync,
+{
+    /// Serialize the current state (as JSON) to the given `writer`. Nothing else in the
+    /// store (reducers, subscribers, middlewares) is saved.
+    pub fn save_snapshot(&self, writer: impl std::io::Write) -> CommonResult<()> {
+        match serde_json::to_writer(writer, &self.state) {
+            Ok(_) => Ok(()),
+            Err(error) => CommonError::new(CommonErrorType::IOError, &error.to_string()),
+        }
+    }
+
+    /// Deserialize a state (as JSON) from the given `reader`, replace the current state w/
+    /// it, and then run the subscribers so that the UI can re-render.
+    ///
+    /// 1. If the snapshot is partial or corrupt then an error is returned and the current
+    ///    state is left untouched.
+    /// 2. Since this takes `&mut self`, it can't run while an action is being dispatched
+    ///    (on a [SharedStore] the write lock is held for the entire duration of
+    ///    [Store::dispatch_action]). However, actions that are still waiting for the
+    ///    lock (eg: ones kicked off using [crate::spawn_dispatch_action!]) will be applied
+    ///    on top of the restored state, once this returns.
+    pub async fn restore_snapshot(
+        &mut self,
+        reader: impl std::io::Read,
+    ) -> CommonResult<()> {
+        let new_state: S = match serde_json::from_reader(reader) {
+            Ok(it) => it,
+            Err(error) => {
+                return CommonError::new(
+                    CommonErrorType::ParsingError,
+                    &error.to_string(),
+                )
+            }
+        };
+        self.state = new_state;
+        self.run_subscribers().await;
+        Ok(())
+    }
+}
@@ -17,6 +17,8 @@
 
 // Attach sources.
 pub mod async_store;
+pub mod store_history;
 
 // Re-export.
 pub use async_store::*;
+pub use store_history::*;
@@ -0,0 +1,75 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::collections::VecDeque;
+
+/// Bounded record of dispatched actions, each paired w/ a snapshot of the state right after
+/// the reducers ran for it. This is used by [crate::Store] for time travel debugging, and is
+/// only allocated when [crate::Store::enable_history] is called.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StoreHistory<S, A> {
+    pub capacity: usize,
+    pub entries: VecDeque<StoreHistoryEntry<S, A>>,
+    /// Index into `entries` of the state that is currently in the store. This is `None`
+    /// if nothing has been recorded yet.
+    pub current_index: Option<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct StoreHistoryEntry<S, A> {
+    pub action: A,
+    pub state: S,
+}
+
+impl<S, A> StoreHistory<S, A> {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: VecDeque::with_capacity(capacity),
+            current_index: None,
+        }
+    }
+
+    /// If the store was jumped back to an older entry, then all the entries after it are
+    /// dropped before recording this one (just like redo in an undo stack). The oldest
+    /// entry is evicted when `capacity` is exceeded.
+    pub fn push(&mut self, action: A, state: S) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        if let Some(current_index) = self.current_index {
+            self.entries.truncate(current_index + 1);
+        }
+
+        self.entries.push_back(StoreHistoryEntry { action, state });
+
+        while self.entries.len() > self.capacity {
+            self.entries.pop_front();
+        }
+
+        self.current_index = Some(self.entries.len() - 1);
+    }
+
+    pub fn get(&self, index: usize) -> Option<&StoreHistoryEntry<S, A>> {
+        self.entries.get(index)
+    }
+
+    pub fn len(&self) -> usize { self.entries.len() }
+
+    pub fn is_empty(&self) -> bool { self.entries.is_empty() }
+}
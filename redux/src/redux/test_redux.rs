@@ -60,7 +60,9 @@ mod tests {
     /// │ State struct.                                        │
     /// ╰──────────────────────────────────────────────────────╯
     /// ```
-    #[derive(Clone, Default, PartialEq, Eq, Debug)]
+    #[derive(
+        Clone, Default, PartialEq, Eq, Debug, serde::Serialize, serde::Deserialize,
+    )]
     pub struct State {
         pub stack: Vec<i32>,
     }
@@ -85,6 +87,99 @@ mod tests {
         run_mw_example_spawns(&shared_vec, &shared_store.clone()).await;
     }
 
+    /// ```text
+    /// ╭──────────────────────────────────────────────────────╮
+    /// │ Persistence test runner.                             │
+    /// ╰──────────────────────────────────────────────────────╯
+    /// ```
+    #[cfg(feature = "persistence")]
+    #[tokio::test]
+    async fn test_redux_store_save_and_restore_snapshot() {
+        let shared_vec = Arc::new(RwLock::new(Vec::<i32>::new()));
+
+        let mut store = Store::<State, Action>::default();
+        store
+            .add_reducer(MyReducer::new())
+            .await
+            .add_subscriber(Box::new(MySubscriber {
+                shared_vec: shared_vec.clone(),
+            }))
+            .await;
+
+        store.dispatch_action(Action::Add(1, 2)).await;
+
+        // Save snapshot.
+        let mut snapshot: Vec<u8> = vec![];
+        store.save_snapshot(&mut snapshot).unwrap();
+
+        // Change the state, then restore it.
+        store.dispatch_action(Action::Reset).await;
+        assert_eq2!(store.get_state().stack, vec![-100]);
+        shared_vec.write().await.clear();
+
+        store.restore_snapshot(snapshot.as_slice()).await.unwrap();
+        assert_eq2!(store.get_state().stack, vec![3]);
+
+        // Subscribers are notified w/ the restored state.
+        assert_eq2!(shared_vec.write().await.pop(), Some(3));
+
+        // Corrupt snapshot returns an error & leaves the state untouched.
+        let corrupt_snapshot = &snapshot[..snapshot.len() / 2];
+        assert!(store.restore_snapshot(corrupt_snapshot).await.is_err());
+        assert_eq2!(store.get_state().stack, vec![3]);
+    }
+
+    /// ```text
+    /// ╭──────────────────────────────────────────────────────╮
+    /// │ History test runner.                                 │
+    /// ╰──────────────────────────────────────────────────────╯
+    /// ```
+    #[tokio::test]
+    async fn test_redux_store_history_and_jump_to() {
+        let shared_vec = Arc::new(RwLock::new(Vec::<i32>::new()));
+
+        let mut store = Store::<State, Action>::default();
+        store
+            .add_reducer(MyReducer::new())
+            .await
+            .add_subscriber(Box::new(MySubscriber {
+                shared_vec: shared_vec.clone(),
+            }))
+            .await;
+
+        // History is disabled by default.
+        store.dispatch_action(Action::Add(1, 1)).await;
+        assert_eq2!(store.get_history_actions().len(), 0);
+        assert!(store.jump_to(0).await.is_err());
+
+        // Capacity bounds the number of recorded entries.
+        store.enable_history(2).await;
+        store.dispatch_action(Action::Add(1, 2)).await;
+        store.dispatch_action(Action::AddPop(1)).await;
+        store.dispatch_action(Action::AddPop(10)).await;
+        assert_eq2!(
+            store.get_history_actions(),
+            vec![Action::AddPop(1), Action::AddPop(10)]
+        );
+        assert_eq2!(store.get_history_index(), Some(1));
+        assert_eq2!(store.get_state().stack, vec![14]);
+
+        // Jump back & subscribers are notified w/ the restored state.
+        shared_vec.write().await.clear();
+        store.jump_to(0).await.unwrap();
+        assert_eq2!(store.get_state().stack, vec![4]);
+        assert_eq2!(shared_vec.write().await.pop(), Some(4));
+        assert!(store.jump_to(2).await.is_err());
+
+        // Dispatching after jumping back discards the entries after it.
+        store.dispatch_action(Action::Reset).await;
+        assert_eq2!(
+            store.get_history_actions(),
+            vec![Action::AddPop(1), Action::Reset]
+        );
+        assert_eq2!(store.get_history_index(), Some(1));
+    }
+
     /// ```text
     /// ╭──────────────────────────────────────────────────────╮
     /// │ Test helpers: Reset shared object.                   │
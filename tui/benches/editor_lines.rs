@@ -0,0 +1,91 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+//! Benchmarks for the [EditorLines] rope vs a [Vec] of lines (which is what the
+//! [EditorBuffer] used to store its lines in), on a 50K line buffer. Run them w/
+//! `cargo bench --bench editor_lines`.
+
+#![feature(test)]
+
+extern crate test;
+
+use r3bl_rs_utils_core::*;
+use r3bl_tui::*;
+use test::{black_box, Bencher};
+
+const LINE_COUNT: usize = 50_000;
+
+fn make_lines() -> Vec<UnicodeString> {
+    (0..LINE_COUNT)
+        .map(|it| UnicodeString::from(format!("fn line_{it}() {{ println!(\"😃\"); }}")))
+        .collect()
+}
+
+#[bench]
+fn bench_vec_insert_at_start(bencher: &mut Bencher) {
+    let mut lines = make_lines();
+    bencher.iter(|| {
+        lines.insert(0, UnicodeString::from("new line"));
+        black_box(lines.remove(0))
+    });
+}
+
+#[bench]
+fn bench_editor_lines_insert_at_start(bencher: &mut Bencher) {
+    let mut lines = EditorLines::from(make_lines());
+    bencher.iter(|| {
+        lines.insert(0, UnicodeString::from("new line"));
+        black_box(lines.remove(0))
+    });
+}
+
+/// This is what happens each time the [EditorBuffer] is saved in the store (eg: as an
+/// undo / redo snapshot) & then edited.
+#[bench]
+fn bench_vec_snapshot_and_edit(bencher: &mut Bencher) {
+    let lines = make_lines();
+    bencher.iter(|| {
+        let mut snapshot = lines.clone();
+        snapshot[LINE_COUNT / 2] = UnicodeString::from("edited line");
+        black_box(snapshot)
+    });
+}
+
+#[bench]
+fn bench_editor_lines_snapshot_and_edit(bencher: &mut Bencher) {
+    let lines = EditorLines::from(make_lines());
+    bencher.iter(|| {
+        let mut snapshot = lines.clone();
+        snapshot[LINE_COUNT / 2] = UnicodeString::from("edited line");
+        black_box(snapshot)
+    });
+}
+
+/// Type a newline at the start of the buffer, using the [EditorBuffer] API.
+#[bench]
+fn bench_editor_buffer_insert_at_start(bencher: &mut Bencher) {
+    let mut editor_buffer = EditorBuffer::new_empty(None);
+    editor_buffer.set_lines(make_lines().into_iter().map(|it| it.string).collect());
+    let viewport = size!(col_count: 80, row_count: 24);
+    bencher.iter(|| {
+        black_box(editor_buffer.insert_at(
+            position!(col_index: 0, row_index: 0),
+            "\n",
+            viewport,
+        ))
+    });
+}
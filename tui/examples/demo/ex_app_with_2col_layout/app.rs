@@ -38,6 +38,8 @@ pub enum Id {
 #[derive(Default)]
 pub struct AppWithLayout {
     pub component_registry: ComponentRegistry<State, Action>,
+    /// Owned by the app so that the split is kept across renders.
+    pub split_pane: SplitPane,
 }
 
 mod app_with_layout_impl_trait_app {
@@ -105,6 +107,12 @@ mod app_with_layout_impl_trait_app {
                 window_size,
             } = args;
 
+            // Try to resize the columns & return if handled.
+            let event_propagation = self.split_pane.handle_event(input_event);
+            if event_propagation != EventPropagation::Propagate {
+                return Ok(event_propagation);
+            }
+
             // Try to handle left and right arrow key input events & return if handled.
             if let Continuation::Return = self.handle_focus_switch(input_event) {
                 return Ok(EventPropagation::ConsumedRender);
@@ -162,13 +170,15 @@ mod perform_layout {
 
                 // Col1.
                 {
-                    box_start!(
-                      in:                     surface,
-                      id:                     FlexBoxId::from(Id::Col1 as u8),
-                      dir:                    LayoutDirection::Vertical,
-                      requested_size_percent: requested_size_percent!(width: 50, height: 100),
-                      styles:                 [Id::Col1 as u8],
-                    );
+                    let maybe_styles =
+                        get_styles! { @from: surface.stylesheet, [Id::Col1 as u8] };
+                    self.0.split_pane.pane_box_start(
+                        surface,
+                        SplitPaneSide::First,
+                        FlexBoxId::from(Id::Col1 as u8),
+                        LayoutDirection::Vertical,
+                        maybe_styles,
+                    )?;
                     render_component_in_current_box!(
                         in:                 surface,
                         component_id:       FlexBoxId::from(Id::Col1 as u8),
@@ -183,13 +193,15 @@ mod perform_layout {
 
                 // Col2.
                 {
-                    box_start!(
-                      in:                     surface,
-                      id:                     FlexBoxId::from(Id::Col2 as u8),
-                      dir:                    LayoutDirection::Vertical,
-                      requested_size_percent: requested_size_percent!(width: 50, height: 100),
-                      styles:                 [Id::Col2 as u8],
-                    );
+                    let maybe_styles =
+                        get_styles! { @from: surface.stylesheet, [Id::Col2 as u8] };
+                    self.0.split_pane.pane_box_start(
+                        surface,
+                        SplitPaneSide::Second,
+                        FlexBoxId::from(Id::Col2 as u8),
+                        LayoutDirection::Vertical,
+                        maybe_styles,
+                    )?;
                     render_component_in_current_box!(
                         in:                 surface,
                         component_id:       FlexBoxId::from(Id::Col2 as u8),
@@ -202,6 +214,8 @@ mod perform_layout {
                     box_end!(in: surface);
                 }
 
+                self.0.split_pane.render_divider(surface)?;
+
                 box_end!(in: surface);
             });
         }
@@ -306,6 +320,7 @@ mod pretty_print {
         fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
             f.debug_struct("AppWithLayout")
                 .field("component_registry", &self.component_registry)
+                .field("split_pane", &self.split_pane)
                 .finish()
         }
     }
@@ -349,7 +364,9 @@ mod status_bar {
             styled_text! { @style: style!(attrib: [dim]),       @text: " … " },
             styled_text! { @style: style!(attrib: [underline]), @text: " ↓ / - : dec " },
             styled_text! { @style: style!(attrib: [dim]),       @text: " … " },
-            styled_text! { @style: style!(attrib: [underline]), @text: " ← / → : focus " }
+            styled_text! { @style: style!(attrib: [underline]), @text: " ← / → : focus " },
+            styled_text! { @style: style!(attrib: [dim]),       @text: " … " },
+            styled_text! { @style: style!(attrib: [underline]), @text: " Alt+Shift+← / → : resize " }
         };
 
         let display_width = styled_texts.display_width();
@@ -32,6 +32,7 @@ pub enum ComponentId {
     Editor = 1,
     SimpleDialog = 2,
     AutocompleteDialog = 3,
+    GotoLineDialog = 9,
 }
 
 #[repr(u8)]
@@ -40,15 +41,6 @@ pub enum EditorStyleName {
     Default = 4,
 }
 
-#[repr(u8)]
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
-pub enum DialogStyleName {
-    Border = 5,
-    Title = 6,
-    Editor = 7,
-    ResultsPanel = 8,
-}
-
 /// Async trait object that implements the [App] trait.
 pub struct AppWithLayout {
     pub component_registry: ComponentRegistry<State, Action>,
@@ -72,8 +64,14 @@ mod app_with_layout_impl_trait_app {
                 } = args;
 
                 // Create a surface and then run the SurfaceRenderer (ContainerSurfaceRender) on it.
+                // The styles of the active theme are layered over the app's styles.
                 let mut surface = {
-                    let mut it = surface!(stylesheet: stylesheet::create_stylesheet()?);
+                    let stylesheet = shared_global_data
+                        .read()
+                        .await
+                        .get_active_theme()
+                        .get_surface_stylesheet(stylesheet::create_stylesheet()?);
+                    let mut it = surface!(stylesheet: stylesheet);
 
                     it.surface_start(SurfaceProps {
                         pos: position!(col_index: 0, row_index: 0),
@@ -124,6 +122,23 @@ mod app_with_layout_impl_trait_app {
                 log_debug(msg);
             });
 
+            // F2 => switch to the next theme (recolors everything).
+            if input_event.matches_keypress(keypress!(@fn FunctionKey::F2)) {
+                let next_theme_id = shared_global_data
+                    .read()
+                    .await
+                    .theme_registry
+                    .get_next_theme_id()
+                    .to_string();
+                if let Err(err) =
+                    set_active_theme(shared_global_data, shared_store, &next_theme_id)
+                        .await
+                {
+                    log_error(format!("📣 Error switching theme: {err:?}"));
+                }
+                return Ok(EventPropagation::Consumed);
+            }
+
             // Check to see if the modal dialog should be activated.
             if let EventPropagation::Consumed =
                 self.try_input_event_activate_modal(args, input_event).await
@@ -162,14 +177,15 @@ mod detect_modal_dialog_activation_from_input_event {
     use super::*;
 
     impl AppWithLayout {
-        /// If `input_event` matches <kbd>Ctrl+l</kbd> or <kbd>Ctrl+k</kbd>, then toggle the modal
-        /// dialog.
+        /// If `input_event` matches <kbd>Ctrl+l</kbd>, <kbd>Ctrl+k</kbd> or <kbd>Ctrl+g</kbd>, then
+        /// toggle the modal dialog.
         ///
         /// Note that this returns a [EventPropagation::Consumed] and not
         /// [EventPropagation::ConsumedRender] because both the following dispatched to the store &
         /// that will cause a rerender:
         /// 1. [Action::SimpleDialogComponentInitializeFocused].
         /// 2. [Action::AutocompleteDialogComponentInitializeFocused].
+        /// 3. [Action::GotoLineDialogComponentInitializeFocused].
         pub async fn try_input_event_activate_modal(
             &mut self,
             args: GlobalScopeArgs<'_, State, Action>,
@@ -237,6 +253,37 @@ mod detect_modal_dialog_activation_from_input_event {
                 };
             };
 
+            // Ctrl + g => activate Go to line.
+            if let DialogEvent::ActivateModal = DialogEvent::should_activate_modal(
+                input_event,
+                KeyPress::WithModifiers {
+                    key: Key::Character('g'),
+                    mask: ModifierKeysMask::CTRL,
+                },
+            ) {
+                // Reset the dialog component prior to activating / showing it.
+                ComponentRegistry::reset_component(
+                    &self.component_registry,
+                    FlexBoxId::from(ComponentId::GotoLineDialog as u8),
+                )
+                .await;
+                return match activate_goto_line_modal(self, args) {
+                    Ok(_) => EventPropagation::Consumed,
+                    Err(err) => {
+                        if let Some(CommonError {
+                            err_type: _,
+                            err_msg: msg,
+                        }) = err.downcast_ref::<CommonError>()
+                        {
+                            log_error(format!(
+                                "📣 Error activating go to line modal: {msg:?}"
+                            ));
+                        }
+                        EventPropagation::Propagate
+                    }
+                };
+            };
+
             return EventPropagation::Propagate;
 
             fn activate_simple_modal(
@@ -259,8 +306,7 @@ mod detect_modal_dialog_activation_from_input_event {
                 // Setting the has_focus to Id::Dialog will cause the dialog to appear on the next
                 // render.
                 this.component_registry
-                    .has_focus
-                    .try_set_modal_id(FlexBoxId::from(ComponentId::SimpleDialog as u8))?;
+                    .push_modal(FlexBoxId::from(ComponentId::SimpleDialog as u8))?;
 
                 // Change the state so that it will trigger a render. This will show the title &
                 // text on the next render.
@@ -304,10 +350,7 @@ mod detect_modal_dialog_activation_from_input_event {
                 // Setting the has_focus to Id::Dialog will cause the dialog to appear on the next
                 // render.
                 this.component_registry
-                    .has_focus
-                    .try_set_modal_id(FlexBoxId::from(
-                        ComponentId::AutocompleteDialog as u8,
-                    ))?;
+                    .push_modal(FlexBoxId::from(ComponentId::AutocompleteDialog as u8))?;
 
                 // Change the state so that it will trigger a render. This will show the title &
                 // text on the next render.
@@ -330,6 +373,43 @@ mod detect_modal_dialog_activation_from_input_event {
 
                 Ok(())
             }
+
+            fn activate_goto_line_modal(
+                this: &mut AppWithLayout,
+                args: GlobalScopeArgs<State, Action>,
+            ) -> CommonResult<()> {
+                // The editor component fills the window (except for the status bar), and it
+                // has a padding of 1.
+                let editor_viewport = size!(
+                    col_count: args.window_size.col_count - 2,
+                    row_count: args.window_size.row_count - 3
+                );
+
+                // Setting the has_focus to Id::Dialog will cause the dialog to appear on the next
+                // render.
+                this.component_registry
+                    .push_modal(FlexBoxId::from(ComponentId::GotoLineDialog as u8))?;
+
+                // Change the state so that it will trigger a render. This will show the title on
+                // the next render.
+                spawn_dispatch_action!(
+                    args.shared_store,
+                    Action::GotoLineDialogComponentInitializeFocused(
+                        FlexBoxId::from(ComponentId::GotoLineDialog as u8),
+                        editor_viewport
+                    )
+                );
+
+                call_if_true!(DEBUG_TUI_MOD, {
+                    let msg = format!(
+                        "📣 activate modal go to line: {:?}",
+                        this.component_registry.has_focus
+                    );
+                    log_debug(msg);
+                });
+
+                Ok(())
+            }
         }
     }
 }
@@ -414,6 +494,26 @@ mod perform_layout {
                       window_size:        window_size
                     };
                 }
+
+                // Or, render go to line modal dialog (if it is active, on top of the editor
+                // component).
+                if self
+                    .0
+                    .component_registry
+                    .has_focus
+                    .is_modal_id(FlexBoxId::from(ComponentId::GotoLineDialog as u8))
+                {
+                    render_component_in_given_box! {
+                      in:                 surface,
+                      box:                FlexBox::default(), /* This is not used as the modal breaks out of its box. */
+                      component_id:       FlexBoxId::from(ComponentId::GotoLineDialog as u8),
+                      from:               self.0.component_registry,
+                      state:              state,
+                      shared_store:       shared_store,
+                      shared_global_data: shared_global_data,
+                      window_size:        window_size
+                    };
+                }
             });
         }
     }
@@ -422,12 +522,12 @@ mod perform_layout {
 mod populate_component_registry {
 
     use super::*;
-    use crate::ex_editor::app::stylesheet::create_stylesheet;
 
     pub fn init(this: &mut AppWithLayout) {
         insert_editor_component(this);
         insert_dialog_component_simple(this);
         insert_dialog_component_autocomplete(this);
+        insert_dialog_component_goto_line(this);
 
         // Switch focus to the editor component if focus is not set.
         this.component_registry
@@ -447,14 +547,10 @@ mod populate_component_registry {
 
     /// Insert autocomplete dialog component into registry if it's not already there.
     fn insert_dialog_component_autocomplete(this: &mut AppWithLayout) {
-        let result_stylesheet = create_stylesheet();
-
         let dialog_options = DialogEngineConfigOptions {
             mode: DialogEngineMode::ModalAutocomplete,
-            maybe_style_border: get_style! { @from_result: result_stylesheet , DialogStyleName::Border as u8 },
-            maybe_style_title: get_style! { @from_result: result_stylesheet , DialogStyleName::Title as u8 },
-            maybe_style_editor: get_style! { @from_result: result_stylesheet , DialogStyleName::Editor as u8 },
-            maybe_style_results_panel: get_style! { @from_result: result_stylesheet , DialogStyleName::ResultsPanel as u8 },
+            title: Some("Autocomplete".to_string()),
+            show_close_hint: true,
             ..Default::default()
         };
 
@@ -462,6 +558,7 @@ mod populate_component_registry {
             multiline_mode: LineMode::SingleLine,
             syntax_highlight: SyntaxHighlightMode::Disable,
             edit_mode: EditMode::ReadWrite,
+            ..Default::default()
         };
 
         let shared_dialog_component = {
@@ -531,16 +628,86 @@ mod populate_component_registry {
         });
     }
 
+    /// Insert go to line dialog component into registry if it's not already there.
+    fn insert_dialog_component_goto_line(this: &mut AppWithLayout) {
+        let dialog_options = DialogEngineConfigOptions {
+            mode: DialogEngineMode::ModalSimple,
+            title: Some("Go to line".to_string()),
+            show_close_hint: true,
+            ..Default::default()
+        };
+
+        let editor_options = EditorEngineConfig {
+            multiline_mode: LineMode::SingleLine,
+            syntax_highlight: SyntaxHighlightMode::Disable,
+            edit_mode: EditMode::ReadWrite,
+            ..Default::default()
+        };
+
+        let shared_dialog_component = {
+            let it = DialogComponent::new_shared(
+                FlexBoxId::from(ComponentId::GotoLineDialog as u8),
+                dialog_options,
+                editor_options,
+                on_dialog_press_handler,
+                on_dialog_editor_change_handler,
+            );
+
+            fn on_dialog_press_handler(
+                dialog_choice: DialogChoice,
+                shared_store: &SharedStore<State, Action>,
+            ) {
+                // Ignore anything that isn't a line number.
+                if let DialogChoice::Yes(text) = dialog_choice {
+                    if let Ok(line_number) = text.trim().parse::<usize>() {
+                        spawn_dispatch_action!(
+                            shared_store,
+                            Action::EditorComponentGotoLine(
+                                FlexBoxId::from(ComponentId::Editor as u8),
+                                line_number
+                            )
+                        );
+                    }
+                }
+            }
+
+            fn on_dialog_editor_change_handler(
+                editor_buffer: EditorBuffer,
+                shared_store: &SharedStore<State, Action>,
+            ) {
+                spawn_dispatch_action!(
+                    shared_store,
+                    Action::GotoLineDialogComponentUpdateContent(
+                        FlexBoxId::from(ComponentId::GotoLineDialog as u8),
+                        editor_buffer
+                    )
+                );
+            }
+
+            it
+        };
+
+        this.component_registry.put(
+            FlexBoxId::from(ComponentId::GotoLineDialog as u8),
+            shared_dialog_component,
+        );
+
+        call_if_true!(DEBUG_TUI_MOD, {
+            let msg = format!(
+                "🪙 {}",
+                "construct DialogComponent (go to line) { on_dialog_press }"
+            );
+            log_debug(msg);
+        });
+    }
+
     /// Insert simple dialog component into registry if it's not already there.
     fn insert_dialog_component_simple(this: &mut AppWithLayout) {
-        let result_stylesheet = create_stylesheet();
-
         let dialog_options = DialogEngineConfigOptions {
             mode: DialogEngineMode::ModalSimple,
-            maybe_style_border: get_style! { @from_result: result_stylesheet , DialogStyleName::Border as u8 },
-            maybe_style_title: get_style! { @from_result: result_stylesheet , DialogStyleName::Title as u8 },
-            maybe_style_editor: get_style! { @from_result: result_stylesheet , DialogStyleName::Editor as u8 },
-            maybe_style_results_panel: get_style! { @from_result: result_stylesheet , DialogStyleName::ResultsPanel as u8 },
+            title: Some("Simple".to_string()),
+            show_close_hint: true,
+            animation: Some(DialogAnimation::Grow { frame_count: 6 }),
             ..Default::default()
         };
 
@@ -548,6 +715,7 @@ mod populate_component_registry {
             multiline_mode: LineMode::SingleLine,
             syntax_highlight: SyntaxHighlightMode::Disable,
             edit_mode: EditMode::ReadWrite,
+            ..Default::default()
         };
 
         let shared_dialog_component = {
@@ -632,7 +800,11 @@ mod populate_component_registry {
                 );
             }
 
-            let config_options = EditorEngineConfig::default();
+            let config_options = EditorEngineConfig {
+                scrollbars: ScrollbarMode::Both,
+                render_clip_markers: true,
+                ..Default::default()
+            };
             EditorComponent::new_shared(id, config_options, on_buffer_change)
         };
 
@@ -686,30 +858,6 @@ mod stylesheet {
                 // These are ignored due to syntax highlighting.
                 // attrib: [bold]
                 // color_fg: TuiColor::Blue
-              },
-              style! {
-                id: DialogStyleName::Title as u8
-                lolcat: true
-                // These are ignored due to lolcat: true.
-                // attrib: [bold]
-                // color_fg: TuiColor::Yellow
-              },
-              style! {
-                id: DialogStyleName::Border as u8
-                lolcat: true
-                // These are ignored due to lolcat: true.
-                // attrib: [dim]
-                // color_fg: TuiColor::Green
-              },
-              style! {
-                id: DialogStyleName::Editor as u8
-                attrib: [bold]
-                color_fg: TuiColor::Basic(ANSIBasicColor::Magenta)
-              },
-              style! {
-                id: DialogStyleName::ResultsPanel as u8
-                // attrib: [bold]
-                color_fg: TuiColor::Basic(ANSIBasicColor::Blue)
               }
             }
         })
@@ -732,6 +880,12 @@ mod status_bar {
             styled_text! { @style: style!(attrib: [dim, underline]) , @text: "Ctrl + k"},
             styled_text! { @style: style!(attrib: [bold]) ,           @text: " : Autocomplete 🤖"},
             styled_text! { @style: style!(attrib: [dim]) ,            @text: " … "},
+            styled_text! { @style: style!(attrib: [dim, underline]) , @text: "Ctrl + g"},
+            styled_text! { @style: style!(attrib: [bold]) ,           @text: " : Go to line 🧭"},
+            styled_text! { @style: style!(attrib: [dim]) ,            @text: " … "},
+            styled_text! { @style: style!(attrib: [dim, underline]) , @text: "F2"},
+            styled_text! { @style: style!(attrib: [bold]) ,           @text: " : Theme 🎨"},
+            styled_text! { @style: style!(attrib: [dim]) ,            @text: " … "},
             styled_text! { @style: style!(attrib: [underline]) ,      @text: "Type content 🌊"},
         };
 
@@ -20,8 +20,34 @@ use std::{collections::HashMap,
 
 use async_trait::async_trait;
 use r3bl_redux::*;
+use r3bl_rs_utils_core::*;
 use r3bl_tui::{DialogBuffer, *};
 
+/// The autocomplete dialog shows the ones that fuzzy match what the user types.
+const AUTOCOMPLETE_CANDIDATES: &[&str] = &[
+    "apple",
+    "apricot",
+    "avocado",
+    "banana",
+    "blackberry",
+    "blueberry",
+    "cherry",
+    "coconut",
+    "grape",
+    "grapefruit",
+    "lemon",
+    "lime",
+    "mango",
+    "orange",
+    "papaya",
+    "peach",
+    "pear",
+    "pineapple",
+    "plum",
+    "strawberry",
+    "watermelon",
+];
+
 pub async fn create_store() -> Store<State, Action> {
     let mut store: Store<State, Action> = Store::default();
     store.add_reducer(Reducer::new()).await;
@@ -59,6 +85,17 @@ pub enum Action {
 
     /// Domain: AutocompleteDialogComponent, Event: SetResults.
     AutocompleteDialogComponentSetResults(FlexBoxId /* id */, Vec<String>),
+
+    /// Domain: GotoLineDialogComponent, Event: InitializeFocused.
+    GotoLineDialogComponentInitializeFocused(
+        FlexBoxId, /* id */
+        Size,      /* editor viewport */
+    ),
+    /// Domain: GotoLineDialogComponent, Event: UpdateContent.
+    GotoLineDialogComponentUpdateContent(FlexBoxId /* id */, EditorBuffer),
+
+    /// Domain: EditorComponent, Event: GotoLine.
+    EditorComponentGotoLine(FlexBoxId /* id */, usize /* line number */),
 }
 
 mod action_impl {
@@ -153,6 +190,7 @@ mod reducer_impl {
         State {
             editor_buffers,
             dialog_buffers: Default::default(),
+            goto_line_viewport: Default::default(),
         }
     }
 
@@ -185,6 +223,24 @@ mod reducer_impl {
                 Action::AutocompleteDialogComponentSetResults(id, results) => {
                     Self::dialog_component_set_results(state, id, results)
                 }
+
+                Action::GotoLineDialogComponentInitializeFocused(id, editor_viewport) => {
+                    state.goto_line_viewport = *editor_viewport;
+                    Self::dialog_component_initialize_focused(
+                        state,
+                        id,
+                        &"Go to line".to_string(),
+                        &"".to_string(),
+                    )
+                }
+
+                Action::GotoLineDialogComponentUpdateContent(id, editor_buffer) => {
+                    Self::dialog_component_update_content(state, id, editor_buffer)
+                }
+
+                Action::EditorComponentGotoLine(id, line_number) => {
+                    Self::editor_component_goto_line(state, id, *line_number)
+                }
             };
         }
     }
@@ -221,57 +277,25 @@ mod reducer_impl {
             id: &FlexBoxId,
             editor_buffer: &EditorBuffer,
         ) {
-            // This is Some only if the content has changed (ignoring caret movements).
-            let results_have_changed: Option<Vec<String>> = {
-                match state.dialog_buffers.get_mut(id) {
-                    Some(dialog_buffer)
-                        if dialog_buffer.editor_buffer.get_lines()
-                            != editor_buffer.get_lines() =>
-                    {
-                        Some({
-                            let editor_buffer_str = editor_buffer.get_as_string();
-                            let start_rand_num = rand::random::<u8>() as usize;
-                            let max = 10;
-                            let mut it = Vec::with_capacity(max);
-                            for index in start_rand_num..(start_rand_num + max) {
-                                it.push(format!("{editor_buffer_str}{index}"));
-                            }
-                            it
-                        })
-                    }
-                    _ => None,
-                }
-            };
-
-            state
-                .dialog_buffers
-                .entry(*id)
-                .and_modify(|it| {
-                    it.editor_buffer = editor_buffer.clone();
-                    if let Some(results) = results_have_changed.clone() {
-                        it.maybe_results = Some(results);
-                    }
-                })
-                .or_insert_with(
-                    // This code path should never execute, since to update the buffer given an id,
-                    // it should have already existed in the first place, which is created by:
-                    // 1. [Action::SimpleDialogComponentInitializeFocused].
-                    // 2. [Action::AutocompleteDialogComponentInitializeFocused].
-                    || {
-                        let mut it = DialogBuffer::new_empty();
-                        it.editor_buffer = editor_buffer.clone();
-                        if let Some(results) = results_have_changed {
-                            it.maybe_results = Some(results);
-                        }
-                        it
-                    },
-                );
+            let dialog_buffer = state.dialog_buffers.entry(*id).or_insert_with(
+                // This code path should never execute, since to update the buffer given an id,
+                // it should have already existed in the first place, which is created by:
+                // 1. [Action::SimpleDialogComponentInitializeFocused].
+                // 2. [Action::AutocompleteDialogComponentInitializeFocused].
+                DialogBuffer::new_empty,
+            );
+
+            // The results only change if the content has changed (ignoring caret movements).
+            let has_content_changed =
+                dialog_buffer.editor_buffer.get_lines() != editor_buffer.get_lines();
+            dialog_buffer.editor_buffer = editor_buffer.clone();
+            if has_content_changed {
+                dialog_buffer.set_results_matching_query(AUTOCOMPLETE_CANDIDATES);
+            }
 
             // Content is empty.
             if editor_buffer.get_as_string() == "" {
-                if let Some(it) = state.dialog_buffers.get_mut(id) {
-                    it.maybe_results = None;
-                }
+                dialog_buffer.maybe_results = None;
             }
         }
 
@@ -305,6 +329,17 @@ mod reducer_impl {
         ) {
             state.editor_buffers.insert(*id, buffer.clone());
         }
+
+        fn editor_component_goto_line(
+            state: &mut State,
+            id: &FlexBoxId,
+            line_number: usize,
+        ) {
+            let viewport = state.goto_line_viewport;
+            if let Some(buffer) = state.editor_buffers.get_mut(id) {
+                buffer.goto_line(line_number, viewport);
+            }
+        }
     }
 }
 
@@ -312,6 +347,8 @@ mod reducer_impl {
 pub struct State {
     pub editor_buffers: HashMap<FlexBoxId, EditorBuffer>,
     pub dialog_buffers: HashMap<FlexBoxId, DialogBuffer>,
+    /// The size of the editor's viewport when the go to line dialog was activated.
+    pub goto_line_viewport: Size,
 }
 
 mod state_impl {
@@ -17,6 +17,8 @@
 
 // Attach.
 pub mod animator_struct;
+pub mod smooth_scroll;
 
 // Re-export.
 pub use animator_struct::*;
+pub use smooth_scroll::*;
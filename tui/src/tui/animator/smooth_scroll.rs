@@ -0,0 +1,113 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+/// A large scroll (eg: a page) never takes more than this many frames to animate, so
+/// that it still feels snappy. Small scrolls take fewer frames (1 row per frame).
+pub const SMOOTH_SCROLL_MAX_FRAME_COUNT: usize = 4;
+
+/// The delay between the frames of a smooth scroll (about 60 fps).
+pub const SMOOTH_SCROLL_FRAME_INTERVAL: Duration = Duration::from_millis(16);
+
+/// Animates the scroll offset (in rows) that is painted towards the actual scroll offset
+/// of a component, over a few renders. This is used when the `smooth_scroll` option of
+/// the [EditorEngineConfig](crate::EditorEngineConfig) or the
+/// [ListComponentConfig](crate::ListComponentConfig) is enabled:
+/// 1. Each time the component is rendered it calls [advance](SmoothScroll::advance) w/
+///    its actual scroll offset, & paints the offset that is returned.
+/// 2. While [is_animating](SmoothScroll::is_animating) is `true`, the component requests
+///    a [RenderTick](crate::RenderTick) of [SMOOTH_SCROLL_FRAME_INTERVAL], so that the
+///    next frame is painted even if nothing else happens.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SmoothScroll {
+    pub displayed_offset: usize,
+    pub target_offset: usize,
+    /// The number of rows to move each frame. It is calculated when the target changes,
+    /// so that the animation takes at most [SMOOTH_SCROLL_MAX_FRAME_COUNT] frames.
+    pub step_size: usize,
+}
+
+impl SmoothScroll {
+    /// Move the displayed offset 1 step towards `target_offset` & return it.
+    pub fn advance(&mut self, target_offset: usize) -> usize {
+        if target_offset != self.target_offset {
+            self.target_offset = target_offset;
+            let distance = self.displayed_offset.abs_diff(target_offset);
+            self.step_size = distance.div_ceil(SMOOTH_SCROLL_MAX_FRAME_COUNT).max(1);
+        }
+
+        self.displayed_offset = if self.displayed_offset < target_offset {
+            (self.displayed_offset + self.step_size).min(target_offset)
+        } else {
+            self.displayed_offset
+                .saturating_sub(self.step_size)
+                .max(target_offset)
+        };
+        self.displayed_offset
+    }
+
+    /// Skip the animation, eg: when smooth scrolling is disabled.
+    pub fn jump_to(&mut self, offset: usize) {
+        self.displayed_offset = offset;
+        self.target_offset = offset;
+    }
+
+    pub fn is_animating(&self) -> bool { self.displayed_offset != self.target_offset }
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_rs_utils_core::*;
+
+    use super::*;
+
+    #[test]
+    fn test_smooth_scroll_takes_at_most_max_frame_count() {
+        let mut smooth_scroll = SmoothScroll::default();
+
+        // A large jump is split into SMOOTH_SCROLL_MAX_FRAME_COUNT frames.
+        let frames: Vec<usize> = (0..SMOOTH_SCROLL_MAX_FRAME_COUNT)
+            .map(|_| smooth_scroll.advance(100))
+            .collect();
+        assert_eq2!(frames, vec![25, 50, 75, 100]);
+        assert!(!smooth_scroll.is_animating());
+
+        // A small jump moves 1 row per frame.
+        assert_eq2!(smooth_scroll.advance(98), 99);
+        assert!(smooth_scroll.is_animating());
+        assert_eq2!(smooth_scroll.advance(98), 98);
+        assert!(!smooth_scroll.is_animating());
+    }
+
+    #[test]
+    fn test_smooth_scroll_retarget_and_jump() {
+        let mut smooth_scroll = SmoothScroll::default();
+        assert_eq2!(smooth_scroll.advance(40), 10);
+
+        // The target changed mid animation, the steps are recalculated from the
+        // displayed offset.
+        assert_eq2!(smooth_scroll.advance(2), 8);
+        assert_eq2!(smooth_scroll.advance(2), 6);
+
+        smooth_scroll.jump_to(30);
+        assert!(!smooth_scroll.is_animating());
+        assert_eq2!(smooth_scroll.advance(30), 30);
+    }
+}
@@ -0,0 +1,319 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::{fmt::Debug, sync::Arc};
+
+use async_trait::async_trait;
+use r3bl_redux::*;
+use r3bl_rs_utils_core::*;
+use tokio::sync::RwLock;
+
+use crate::*;
+
+/// This is a reusable modal [Component] that runs one of the registered [PaletteCommand]s,
+/// which the user finds by typing part of its name.
+/// - It reuses the [DialogEngine] (in [DialogEngineMode::ModalAutocomplete]) to paint the
+///   dialog box & to handle the input. Unlike the [DialogComponent], the [DialogBuffer] lives
+///   in this component (and not in the store), since the results are computed from the
+///   registered commands.
+/// - As the user types, the commands are filtered & ranked w/ fuzzy matching (more info in
+///   [fuzzy_match()]). <kbd>Enter</kbd> runs the handler of the selected command,
+///   and <kbd>Esc</kbd> closes the palette.
+/// - To open it, the app pushes its `id` w/ [ComponentRegistry::push_modal] (eg: when
+///   <kbd>Ctrl+Shift+P</kbd> is pressed). Focus is restored when it is closed.
+#[derive(Clone)]
+pub struct CommandPaletteComponent<S, A>
+where
+    S: Debug + Default + Clone + PartialEq + Sync + Send,
+    A: Debug + Default + Clone + Sync + Send,
+{
+    pub id: FlexBoxId,
+    pub dialog_engine: DialogEngine,
+    pub dialog_buffer: DialogBuffer,
+    pub commands: Vec<PaletteCommand<S, A>>,
+}
+
+/// A command that can be run from the [CommandPaletteComponent]. The `display_name` is what
+/// the user searches for, so it should be unique.
+#[derive(Clone)]
+pub struct PaletteCommand<S, A>
+where
+    S: Debug + Default + Clone + PartialEq + Sync + Send,
+    A: Debug + Default + Clone + Sync + Send,
+{
+    pub id: String,
+    pub display_name: String,
+    pub handler: OnPaletteCommandFn<S, A>,
+}
+
+/// Called w/ the `id` of the [PaletteCommand] that the user picked.
+pub type OnPaletteCommandFn<S, A> = fn(&SharedStore<S, A>, &str);
+
+pub mod command_palette_component_impl {
+    use super::*;
+
+    #[async_trait]
+    impl<S, A> Component<S, A> for CommandPaletteComponent<S, A>
+    where
+        S: HasDialogBuffers + Default + Clone + PartialEq + Debug + Sync + Send + 'static,
+        A: Debug + Default + Clone + Sync + Send + 'static,
+    {
+        fn reset(&mut self) {
+            self.dialog_engine.reset();
+            self.clear_query();
+        }
+
+        fn get_id(&self) -> FlexBoxId { self.id }
+
+        /// Like the [DialogComponent], the `_current_box` is ignored since the palette
+        /// paints itself over the entire screen.
+        async fn render(
+            &mut self,
+            args: ComponentScopeArgs<'_, S, A>,
+            _current_box: &FlexBox,
+            surface_bounds: SurfaceBounds,
+        ) -> CommonResult<RenderPipeline> {
+            self.dialog_engine.maybe_surface_bounds = Some(surface_bounds);
+
+            let ComponentScopeArgs {
+                state,
+                shared_store,
+                shared_global_data,
+                component_registry,
+                window_size,
+            } = args;
+
+            let pipeline = DialogEngineApi::render_engine(DialogEngineArgs {
+                shared_global_data,
+                shared_store,
+                state,
+                component_registry: &mut *component_registry,
+                self_id: self.id,
+                dialog_engine: &mut self.dialog_engine,
+                dialog_buffer: &self.dialog_buffer,
+                window_size,
+            })
+            .await?;
+
+            // Run the selected command (if the close animation is done).
+            if let Some(dialog_choice) = self.dialog_engine.take_closed_dialog_choice() {
+                component_registry.has_focus.reset_modal_id();
+                self.run_command_for_dialog_choice(dialog_choice, shared_store);
+            }
+
+            Ok(pipeline)
+        }
+
+        async fn handle_event(
+            &mut self,
+            args: ComponentScopeArgs<'_, S, A>,
+            input_event: &InputEvent,
+        ) -> CommonResult<EventPropagation> {
+            let ComponentScopeArgs {
+                state,
+                shared_store,
+                shared_global_data,
+                component_registry,
+                window_size,
+            } = args;
+
+            let response = DialogEngineApi::apply_event(
+                DialogEngineArgs {
+                    shared_global_data,
+                    shared_store,
+                    state,
+                    component_registry: &mut *component_registry,
+                    self_id: self.id,
+                    dialog_engine: &mut self.dialog_engine,
+                    dialog_buffer: &self.dialog_buffer,
+                    window_size,
+                },
+                input_event,
+            )
+            .await;
+
+            let response = match response {
+                Ok(it) => it,
+                // Clicks before the palette is rendered are ignored.
+                Err(DialogError::NoSurfaceBounds) => {
+                    return Ok(EventPropagation::Propagate);
+                }
+                Err(dialog_error) => {
+                    return Err(Box::new(CommonError::from(dialog_error)));
+                }
+            };
+
+            match response {
+                // Run the selected command (if any) & restore focus.
+                DialogEngineApplyResponse::DialogChoice(dialog_choice) => {
+                    component_registry.has_focus.reset_modal_id();
+                    self.run_command_for_dialog_choice(dialog_choice, shared_store);
+                    Ok(EventPropagation::ConsumedRender)
+                }
+
+                // The query changed, so filter the commands again.
+                DialogEngineApplyResponse::UpdateEditorBuffer(new_editor_buffer) => {
+                    self.dialog_buffer.editor_buffer = new_editor_buffer;
+                    self.update_results();
+                    Ok(EventPropagation::ConsumedRender)
+                }
+
+                DialogEngineApplyResponse::SelectScrollResultsPanel
+                | DialogEngineApplyResponse::AnimationInProgress => {
+                    Ok(EventPropagation::ConsumedRender)
+                }
+
+                DialogEngineApplyResponse::Unchanged => {
+                    Ok(EventPropagation::ConsumedNoRender)
+                }
+                DialogEngineApplyResponse::Noop => Ok(EventPropagation::Propagate),
+            }
+        }
+    }
+
+    impl<S, A> CommandPaletteComponent<S, A>
+    where
+        S: Debug + Default + Clone + PartialEq + Sync + Send,
+        A: Debug + Default + Clone + Sync + Send,
+    {
+        /// Add a command, or replace the one that has the same `id`.
+        pub fn register_command(
+            &mut self,
+            id: &str,
+            display_name: &str,
+            handler: OnPaletteCommandFn<S, A>,
+        ) {
+            let command = PaletteCommand {
+                id: id.to_string(),
+                display_name: display_name.to_string(),
+                handler,
+            };
+            match self.commands.iter_mut().find(|it| it.id == id) {
+                Some(existing_command) => *existing_command = command,
+                None => self.commands.push(command),
+            }
+            self.update_results();
+        }
+
+        /// The text that the user has typed so far.
+        pub fn get_query(&self) -> String {
+            self.dialog_buffer.editor_buffer.get_as_string()
+        }
+
+        /// The commands that match the query, best match first. More info in
+        /// [rank_fuzzy_matches].
+        pub fn get_matching_commands(&self) -> Vec<&PaletteCommand<S, A>> {
+            let display_names: Vec<&str> = self
+                .commands
+                .iter()
+                .map(|it| it.display_name.as_str())
+                .collect();
+            rank_fuzzy_matches(&self.get_query(), &display_names)
+                .into_iter()
+                .map(|index| &self.commands[index])
+                .collect()
+        }
+
+        /// Show the commands that match the query in the results panel, & select the best
+        /// match.
+        pub fn update_results(&mut self) {
+            let results = self
+                .get_matching_commands()
+                .iter()
+                .map(|it| it.display_name.clone())
+                .collect();
+            self.dialog_buffer.maybe_results = Some(results);
+            self.dialog_engine.selected_row_index = ch!(0);
+            self.dialog_engine.scroll_offset_row_index = ch!(0);
+        }
+
+        pub fn clear_query(&mut self) {
+            self.dialog_buffer.editor_buffer =
+                EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT));
+            self.update_results();
+        }
+
+        /// [DialogChoice::Yes] holds the display name of the selected command, which is run.
+        /// Either way, the query is cleared for the next time the palette is opened.
+        pub fn run_command_for_dialog_choice(
+            &mut self,
+            dialog_choice: DialogChoice,
+            shared_store: &SharedStore<S, A>,
+        ) {
+            if let DialogChoice::Yes(display_name) = dialog_choice {
+                let maybe_command = self
+                    .get_matching_commands()
+                    .into_iter()
+                    .find(|it| it.display_name == display_name)
+                    .map(|it| (it.handler, it.id.clone()));
+                if let Some((handler, id)) = maybe_command {
+                    handler(shared_store, &id);
+                }
+            }
+            self.clear_query();
+        }
+    }
+}
+pub use command_palette_component_impl::*;
+
+mod constructor {
+    use super::*;
+
+    impl<S, A> CommandPaletteComponent<S, A>
+    where
+        S: Debug + Default + Clone + PartialEq + Sync + Send,
+        A: Debug + Default + Clone + Sync + Send,
+    {
+        /// The `title` is painted above the query. The `dialog_options` are always used in
+        /// [DialogEngineMode::ModalAutocomplete]. Use
+        /// [register_command](CommandPaletteComponent::register_command) to add commands.
+        pub fn new(
+            id: FlexBoxId,
+            title: &str,
+            dialog_options: DialogEngineConfigOptions,
+            editor_options: EditorEngineConfig,
+        ) -> Self {
+            let dialog_options = DialogEngineConfigOptions {
+                mode: DialogEngineMode::ModalAutocomplete,
+                ..dialog_options
+            };
+            let mut it = Self {
+                id,
+                dialog_engine: DialogEngine::new(dialog_options, editor_options),
+                dialog_buffer: DialogBuffer::new_empty(),
+                commands: vec![],
+            };
+            it.dialog_buffer.title = title.to_string();
+            it.update_results();
+            it
+        }
+
+        pub fn new_shared(
+            id: FlexBoxId,
+            title: &str,
+            dialog_options: DialogEngineConfigOptions,
+            editor_options: EditorEngineConfig,
+        ) -> Arc<RwLock<Self>> {
+            Arc::new(RwLock::new(CommandPaletteComponent::new(
+                id,
+                title,
+                dialog_options,
+                editor_options,
+            )))
+        }
+    }
+}
@@ -0,0 +1,82 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+#[cfg(test)]
+mod test_command_palette {
+    use std::sync::Mutex;
+
+    use r3bl_redux::*;
+    use r3bl_rs_utils_core::*;
+
+    use crate::{test_dialog::mock_real_objects_for_dialog, *};
+
+    type State = mock_real_objects_for_dialog::State;
+
+    static RAN_COMMAND_IDS: Mutex<Vec<String>> = Mutex::new(vec![]);
+
+    fn on_command(_: &SharedStore<State, String>, id: &str) {
+        RAN_COMMAND_IDS.lock().unwrap().push(id.to_string());
+    }
+
+    #[test]
+    fn test_register_filter_and_run_commands() {
+        let shared_store = mock_real_objects_for_dialog::create_store();
+        let mut palette = CommandPaletteComponent::<State, String>::new(
+            FlexBoxId::from(0),
+            "Commands",
+            Default::default(),
+            Default::default(),
+        );
+        assert_eq2!(
+            palette.dialog_engine.dialog_options.mode,
+            DialogEngineMode::ModalAutocomplete
+        );
+
+        palette.register_command("file.open", "Open File", on_command);
+        palette.register_command("file.close", "Close File", on_command);
+        palette.register_command("editor.goto", "Go To Line", on_command);
+        assert_eq2!(palette.dialog_buffer.get_results_count(), ch!(3));
+
+        // Registering the same id again replaces the command.
+        palette.register_command("file.close", "Close Current File", on_command);
+        assert_eq2!(palette.commands.len(), 3);
+
+        // Typing filters the results.
+        palette.dialog_buffer.editor_buffer.set_content("opfi");
+        palette.update_results();
+        assert_eq2!(
+            palette.dialog_buffer.maybe_results,
+            Some(vec!["Open File".to_string()])
+        );
+
+        // Enter runs the selected command & clears the query.
+        palette.run_command_for_dialog_choice(
+            DialogChoice::Yes("Open File".into()),
+            &shared_store,
+        );
+        assert_eq2!(
+            *RAN_COMMAND_IDS.lock().unwrap(),
+            vec!["file.open".to_string()]
+        );
+        assert_eq2!(palette.get_query(), "");
+        assert_eq2!(palette.dialog_buffer.get_results_count(), ch!(3));
+
+        // Esc doesn't run anything.
+        palette.run_command_for_dialog_choice(DialogChoice::No, &shared_store);
+        assert_eq2!(RAN_COMMAND_IDS.lock().unwrap().len(), 1);
+    }
+}
@@ -43,6 +43,26 @@ impl DialogBuffer {
             ch!(0)
         }
     }
+
+    pub fn get_result(&self, index: usize) -> Option<&str> {
+        self.maybe_results
+            .as_ref()
+            .and_then(|results| results.get(index))
+            .map(String::as_str)
+    }
+}
+
+impl DialogBuffer {
+    /// Set the results to the `candidates` that fuzzy match the text in the editor, best
+    /// match first. More info in [rank_fuzzy_matches].
+    pub fn set_results_matching_query(&mut self, candidates: &[&str]) {
+        let query = self.editor_buffer.get_as_string();
+        let results = rank_fuzzy_matches(&query, candidates)
+            .into_iter()
+            .map(|index| candidates[index].to_string())
+            .collect();
+        self.maybe_results = Some(results);
+    }
 }
 
 impl DialogBuffer {
@@ -38,13 +38,17 @@ where
     pub on_dialog_press_handler: Option<OnDialogPressFn<S, A>>,
     /// Make sure to dispatch an action to update the dialog buffer's editor buffer.
     pub on_dialog_editor_change_handler: Option<OnDialogEditorChangeFn<S, A>>,
+    /// Called when the user selects a different result in the results panel (eg: to
+    /// show a preview of it). Not set by [new](DialogComponent::new).
+    pub on_dialog_results_selection_change_handler:
+        Option<OnDialogResultsSelectionChangeFn<S, A>>,
 }
 
 #[async_trait]
 impl<S, A> Component<S, A> for DialogComponent<S, A>
 where
-    S: HasDialogBuffers + Default + Clone + PartialEq + Debug + Sync + Send,
-    A: Debug + Default + Clone + Sync + Send,
+    S: HasDialogBuffers + Default + Clone + PartialEq + Debug + Sync + Send + 'static,
+    A: Debug + Default + Clone + Sync + Send + 'static,
 {
     fn reset(&mut self) { self.dialog_engine.reset(); }
 
@@ -62,6 +66,8 @@ where
     ///    the entire screen.
     /// 2. However, `SurfaceBounds` is saved for later use. And it is used to restrict where the
     ///    dialog can be placed on the screen.
+    /// 3. If the dialog is animated, the user's choice is only handled once the close
+    ///    animation is done (which happens during a render).
     async fn render(
         &mut self,
         args: ComponentScopeArgs<'_, S, A>,
@@ -98,7 +104,17 @@ where
             }
         };
 
-        DialogEngineApi::render_engine(dialog_engine_args).await
+        let pipeline = DialogEngineApi::render_engine(dialog_engine_args).await?;
+
+        // Handle user's choice (if the close animation is done).
+        if let Some(dialog_choice) = self.dialog_engine.take_closed_dialog_choice() {
+            component_registry.has_focus.reset_modal_id();
+            if let Some(it) = &self.on_dialog_press_handler {
+                it(dialog_choice, shared_store);
+            };
+        }
+
+        Ok(pipeline)
     }
 
     /// This shim simply calls [DialogEngineApi::apply_event](DialogEngineApi::apply_event) w/ all
@@ -143,7 +159,23 @@ where
             }
         };
 
-        match DialogEngineApi::apply_event(dialog_engine_args, input_event).await? {
+        let response =
+            match DialogEngineApi::apply_event(dialog_engine_args, input_event).await {
+                Ok(it) => it,
+                // The dialog box paints the error, & stays open so the user can fix it.
+                Err(dialog_error) if dialog_error.is_input_error() => {
+                    return Ok(EventPropagation::ConsumedRender);
+                }
+                // Clicks before the dialog box is rendered are ignored.
+                Err(DialogError::NoSurfaceBounds) => {
+                    return Ok(EventPropagation::Propagate);
+                }
+                Err(dialog_error) => {
+                    return Err(Box::new(CommonError::from(dialog_error)));
+                }
+            };
+
+        match response {
             // Handler user's choice.
             DialogEngineApplyResponse::DialogChoice(dialog_choice) => {
                 component_registry.has_focus.reset_modal_id();
@@ -179,9 +211,32 @@ where
 
             // Handle user input that has updated the results panel.
             DialogEngineApplyResponse::SelectScrollResultsPanel => {
+                // Run the handler (if any) w/ the selected result.
+                if let Some(it) = &self.on_dialog_results_selection_change_handler {
+                    if let Some(result) =
+                        self.dialog_engine.get_selected_result(&dialog_buffer)
+                    {
+                        it(
+                            self.dialog_engine.get_selected_result_index(),
+                            result.to_string(),
+                            shared_store,
+                        );
+                    }
+                };
+                Ok(EventPropagation::ConsumedRender)
+            }
+
+            // Input is ignored while the dialog box is animating. A render is needed to
+            // start the close animation (if the user just made a choice).
+            DialogEngineApplyResponse::AnimationInProgress => {
                 Ok(EventPropagation::ConsumedRender)
             }
 
+            // Eg: up pressed w/ the first result already selected.
+            DialogEngineApplyResponse::Unchanged => {
+                Ok(EventPropagation::ConsumedNoRender)
+            }
+
             // All else.
             _ => Ok(EventPropagation::Propagate),
         }
@@ -208,6 +263,7 @@ where
             id,
             on_dialog_press_handler: Some(on_dialog_press_handler),
             on_dialog_editor_change_handler: Some(on_dialog_editor_change_handler),
+            on_dialog_results_selection_change_handler: None,
         }
     }
 
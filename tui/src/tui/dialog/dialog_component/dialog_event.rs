@@ -26,6 +26,10 @@ use crate::*;
 pub enum DialogEvent {
     ActivateModal,
     EnterPressed,
+    /// Used to accept the input of a
+    /// [multiline](DialogEngineConfigOptions::multiline) dialog, since
+    /// <kbd>Enter</kbd> inserts a new line.
+    CtrlEnterPressed,
     EscPressed,
     None,
 }
@@ -49,7 +53,8 @@ mod dialog_event_impl {
         }
 
         /// Tries to convert the given [InputEvent] into a [DialogEvent].
-        /// - Enter and Esc are also matched against to return [DialogEvent::EnterPressed] and
+        /// - Enter, Ctrl+Enter and Esc are also matched against to return
+        ///   [DialogEvent::EnterPressed], [DialogEvent::CtrlEnterPressed] and
         ///   [DialogEvent::EscPressed]
         /// - Otherwise, [Err] is returned.
         pub fn from(input_event: &InputEvent) -> Self {
@@ -62,6 +67,14 @@ mod dialog_event_impl {
                         return Self::EnterPressed;
                     }
 
+                    // Compare to `Ctrl+Enter`.
+                    KeyPress::WithModifiers {
+                        key: Key::SpecialKey(SpecialKey::Enter),
+                        mask: ModifierKeysMask::CTRL,
+                    } => {
+                        return Self::CtrlEnterPressed;
+                    }
+
                     // Compare to `Esc`.
                     KeyPress::Plain {
                         key: Key::SpecialKey(SpecialKey::Esc),
@@ -91,6 +104,15 @@ mod test_dialog_event {
         assert_eq2!(dialog_event, DialogEvent::EnterPressed);
     }
 
+    #[test]
+    fn dialog_event_handles_ctrl_enter() {
+        let input_event = InputEvent::Keyboard(
+            keypress!(@special ModifierKeysMask::CTRL, SpecialKey::Enter),
+        );
+        let dialog_event = DialogEvent::from(&input_event);
+        assert_eq2!(dialog_event, DialogEvent::CtrlEnterPressed);
+    }
+
     #[test]
     fn dialog_event_handles_esc() {
         let input_event = InputEvent::Keyboard(keypress!(@special SpecialKey::Esc));
@@ -15,9 +15,11 @@
  *   limitations under the License.
  */
 
-use std::fmt::Debug;
+use std::{fmt::Debug, time::Duration};
 
+use r3bl_redux::*;
 use r3bl_rs_utils_core::*;
+use r3bl_rs_utils_macro::style;
 
 use crate::*;
 
@@ -26,6 +28,12 @@ pub enum DialogEngineApplyResponse {
     UpdateEditorBuffer(EditorBuffer),
     DialogChoice(DialogChoice),
     SelectScrollResultsPanel,
+    /// The dialog box is being animated, and input is ignored until it is done. More info
+    /// in [DialogEngineConfigOptions::animation].
+    AnimationInProgress,
+    /// The event was handled, but nothing changed, eg: up pressed w/ the first result
+    /// already selected.
+    Unchanged,
     Noop,
 }
 
@@ -37,9 +45,29 @@ impl DialogEngineApi {
         args: DialogEngineArgs<'_, S, A>,
     ) -> CommonResult<RenderPipeline>
     where
-        S: HasDialogBuffers + Default + Clone + PartialEq + Debug + Sync + Send,
-        A: Debug + Default + Clone + Sync + Send,
+        S: HasDialogBuffers + Default + Clone + PartialEq + Debug + Sync + Send + 'static,
+        A: Debug + Default + Clone + Sync + Send + 'static,
     {
+        // Always use the active theme (it can be switched at runtime).
+        args.dialog_engine.theme_stylesheet = args
+            .shared_global_data
+            .read()
+            .await
+            .get_active_theme()
+            .stylesheet
+            .clone();
+
+        // Paint a compact message instead of a garbled dialog box if there's no room.
+        if internal_impl::is_window_too_small(
+            args.window_size,
+            &args.dialog_engine.dialog_options,
+        ) {
+            return Ok(internal_impl::render_window_too_small(
+                args.window_size,
+                args.dialog_engine.maybe_surface_bounds,
+            ));
+        }
+
         let mode = args.dialog_engine.dialog_options.mode;
         let overlay_flex_box: PartialFlexBox = {
             match &args.dialog_engine.maybe_flex_box {
@@ -71,6 +99,44 @@ impl DialogEngineApi {
         let (origin_pos, bounds_size) =
             overlay_flex_box.get_style_adjusted_position_and_size();
 
+        // The dialog box is clamped to the surface, which might leave no room inside the
+        // border.
+        if !internal_impl::has_interior(&bounds_size) {
+            return Ok(internal_impl::render_window_too_small(
+                args.window_size,
+                args.dialog_engine.maybe_surface_bounds,
+            ));
+        }
+
+        // Each render paints one frame of the open / close animation (if any). Only the
+        // border is painted until the dialog box settles.
+        if let Some(fraction) = internal_impl::advance_animation(args.dialog_engine) {
+            // Get painted again for the next frame, w/out dispatching an action.
+            if args.dialog_engine.is_animating() {
+                args.shared_global_data.write().await.request_render_tick(
+                    Duration::from_millis(DIALOG_ANIMATION_FRAME_INTERVAL_MS),
+                );
+            }
+
+            let mut it = render_pipeline!();
+            if let Some((origin_pos, bounds_size)) = internal_impl::get_animated_bounds(
+                &origin_pos,
+                &bounds_size,
+                fraction,
+                args.dialog_engine,
+            ) {
+                it.push(
+                    ZOrder::Glass,
+                    internal_impl::render_border(
+                        &origin_pos,
+                        &bounds_size,
+                        args.dialog_engine,
+                    ),
+                );
+            }
+            return Ok(it);
+        }
+
         let pipeline = {
             let mut it = render_pipeline!();
 
@@ -83,6 +149,26 @@ impl DialogEngineApi {
                 ),
             );
 
+            it.push(
+                ZOrder::Glass,
+                internal_impl::render_border_title(
+                    &origin_pos,
+                    &bounds_size,
+                    args.dialog_engine,
+                ),
+            );
+
+            if args.dialog_engine.maybe_input_error.is_some() {
+                it.push(
+                    ZOrder::Glass,
+                    internal_impl::render_input_error(
+                        &origin_pos,
+                        &bounds_size,
+                        args.dialog_engine,
+                    ),
+                );
+            }
+
             it.push(
                 ZOrder::Glass,
                 internal_impl::render_title(
@@ -98,12 +184,14 @@ impl DialogEngineApi {
                 args.dialog_engine.dialog_options.mode,
                 DialogEngineMode::ModalAutocomplete
             ) {
+                // The results might have changed since the last render.
+                args.dialog_engine
+                    .clamp_selected_result_index(args.dialog_buffer.get_results_count());
                 let results_panel_ops = internal_impl::render_results_panel(
                     &origin_pos,
                     &bounds_size,
                     args.dialog_engine,
-                    args.self_id,
-                    args.state,
+                    args.dialog_buffer,
                 )?;
                 if !results_panel_ops.is_empty() {
                     it.push(ZOrder::Glass, results_panel_ops);
@@ -123,11 +211,21 @@ impl DialogEngineApi {
     /// - [DialogEngineApplyResponse::DialogChoice] => <kbd>Enter</kbd> or <kbd>Esc</kbd> was
     ///   pressed.
     /// - [DialogEngineApplyResponse::UpdateEditorBuffer] => the editor buffer was updated.
+    /// - [DialogEngineApplyResponse::SelectScrollResultsPanel] => the selected result
+    ///   changed, see [get_selected_result](DialogEngine::get_selected_result).
+    /// - [DialogEngineApplyResponse::AnimationInProgress] => the dialog box is opening or
+    ///   closing, in which case the input is ignored. If a dialog choice starts the close
+    ///   animation, it is available from
+    ///   [take_closed_dialog_choice](DialogEngine::take_closed_dialog_choice) once the
+    ///   animation is done.
     /// - [DialogEngineApplyResponse::Noop] => otherwise.
+    ///
+    /// If the input is rejected (see [DialogError::is_input_error]), the [DialogError] is
+    /// saved in [DialogEngine::maybe_input_error] so that it is painted on the next render.
     pub async fn apply_event<S, A>(
         args: DialogEngineArgs<'_, S, A>,
         input_event: &InputEvent,
-    ) -> CommonResult<DialogEngineApplyResponse>
+    ) -> DialogResult<DialogEngineApplyResponse>
     where
         S: HasDialogBuffers + Default + Clone + PartialEq + Debug + Sync + Send,
         A: Debug + Default + Clone + Sync + Send,
@@ -143,21 +241,59 @@ impl DialogEngineApi {
             ..
         } = args;
 
+        // Ignore input while the dialog box is opening or closing.
+        if dialog_engine.is_animating() {
+            return Ok(DialogEngineApplyResponse::AnimationInProgress);
+        }
+
+        // The input error (if any) is only shown until the next event.
+        dialog_engine.maybe_input_error = None;
+
+        // The results might have changed since the last render.
+        dialog_engine.clamp_selected_result_index(dialog_buffer.get_results_count());
+
+        // The close hint can't be clicked before the dialog box is rendered.
+        if let InputEvent::Mouse(_) = input_event {
+            if dialog_engine.dialog_options.show_close_hint
+                && dialog_engine.maybe_flex_box.is_none()
+            {
+                return Err(DialogError::NoSurfaceBounds);
+            }
+        }
+
         // Was a dialog choice made?
         if let Some(choice) = internal_impl::try_handle_dialog_choice(
             input_event,
             dialog_buffer,
             dialog_engine,
         ) {
+            if let Err(dialog_error) =
+                internal_impl::validate_dialog_choice(&choice, dialog_engine)
+            {
+                dialog_engine.maybe_input_error = Some(dialog_error.clone());
+                return Err(dialog_error);
+            }
+            if dialog_engine.get_animation().is_some() {
+                dialog_engine.animation_progress = DialogAnimationProgress::Closing {
+                    frame_index: 0,
+                    dialog_choice: choice,
+                };
+                return Ok(DialogEngineApplyResponse::AnimationInProgress);
+            }
             dialog_engine.reset();
             return Ok(DialogEngineApplyResponse::DialogChoice(choice));
         }
 
         // Was up / down pressed to select autocomplete results & vert scroll the results panel?
-        if let EventPropagation::ConsumedRender =
-            internal_impl::try_handle_up_down(input_event, dialog_buffer, dialog_engine)
+        match internal_impl::try_handle_up_down(input_event, dialog_buffer, dialog_engine)
         {
-            return Ok(DialogEngineApplyResponse::SelectScrollResultsPanel);
+            EventPropagation::ConsumedRender => {
+                return Ok(DialogEngineApplyResponse::SelectScrollResultsPanel);
+            }
+            EventPropagation::ConsumedNoRender => {
+                return Ok(DialogEngineApplyResponse::Unchanged);
+            }
+            _ => {}
         }
 
         // Otherwise, pass the event to the editor engine.
@@ -173,7 +309,9 @@ impl DialogEngineApi {
 
         // If the editor engine applied the event, return the new editor buffer.
         if let EditorEngineApplyEventResult::Applied(new_editor_buffer) =
-            EditorEngineApi::apply_event(editor_engine_args, input_event).await?
+            EditorEngineApi::apply_event(editor_engine_args, input_event)
+                .await
+                .map_err(|err| DialogError::EditorEngineFailed(err.to_string()))?
         {
             return Ok(DialogEngineApplyResponse::UpdateEditorBuffer(
                 new_editor_buffer,
@@ -198,6 +336,112 @@ pub enum DisplayConstants {
 mod internal_impl {
     use super::*;
 
+    /// Moves the animation forward by one frame. Returns how much of the dialog box
+    /// should be shown in this frame (from `0.0` to `1.0`), or [None] if it isn't
+    /// animating & should be rendered normally.
+    pub fn advance_animation(dialog_engine: &mut DialogEngine) -> Option<f64> {
+        let frame_count = dialog_engine.get_animation()?.get_frame_count();
+        let get_fraction = |frame_index: u16| frame_index as f64 / frame_count as f64;
+
+        let (new_progress, maybe_fraction) =
+            match std::mem::take(&mut dialog_engine.animation_progress) {
+                DialogAnimationProgress::NotStarted => {
+                    open_step(1, frame_count, get_fraction)
+                }
+                DialogAnimationProgress::Opening { frame_index } => {
+                    open_step(frame_index + 1, frame_count, get_fraction)
+                }
+                DialogAnimationProgress::Settled => {
+                    (DialogAnimationProgress::Settled, None)
+                }
+                DialogAnimationProgress::Closing {
+                    frame_index,
+                    dialog_choice,
+                } => {
+                    let frame_index = frame_index + 1;
+                    if frame_index >= frame_count {
+                        (DialogAnimationProgress::Closed(dialog_choice), Some(0.0))
+                    } else {
+                        (
+                            DialogAnimationProgress::Closing {
+                                frame_index,
+                                dialog_choice,
+                            },
+                            Some(1.0 - get_fraction(frame_index)),
+                        )
+                    }
+                }
+                DialogAnimationProgress::Closed(dialog_choice) => {
+                    (DialogAnimationProgress::Closed(dialog_choice), Some(0.0))
+                }
+            };
+
+        dialog_engine.animation_progress = new_progress;
+        maybe_fraction
+    }
+
+    fn open_step(
+        frame_index: u16,
+        frame_count: u16,
+        get_fraction: impl Fn(u16) -> f64,
+    ) -> (DialogAnimationProgress, Option<f64>) {
+        if frame_index >= frame_count {
+            (DialogAnimationProgress::Settled, None)
+        } else {
+            (
+                DialogAnimationProgress::Opening { frame_index },
+                Some(get_fraction(frame_index)),
+            )
+        }
+    }
+
+    /// Returns the bounds of the dialog box for the given animation `fraction`, or [None]
+    /// if nothing should be painted.
+    pub fn get_animated_bounds(
+        origin_pos: &Position,
+        bounds_size: &Size,
+        fraction: f64,
+        dialog_engine: &DialogEngine,
+    ) -> Option<(Position, Size)> {
+        if fraction <= 0.0 {
+            return None;
+        }
+
+        let scale = |value: ChUnit| ch!((*value as f64 * fraction).ceil() as u16);
+
+        match dialog_engine.get_animation()? {
+            DialogAnimation::Grow { .. } => {
+                // Grow from the center, and keep the border intact (min size is 2x2).
+                let col_count = std::cmp::max(scale(bounds_size.col_count), ch!(2))
+                    .min(bounds_size.col_count);
+                let row_count = std::cmp::max(scale(bounds_size.row_count), ch!(2))
+                    .min(bounds_size.row_count);
+                let origin_pos = position!(
+                    col_index: origin_pos.col_index + (bounds_size.col_count - col_count) / 2,
+                    row_index: origin_pos.row_index + (bounds_size.row_count - row_count) / 2
+                );
+                Some((
+                    origin_pos,
+                    size!(col_count: col_count, row_count: row_count),
+                ))
+            }
+            DialogAnimation::SlideIn { .. } => {
+                // Slide down from the top of the surface.
+                let top_row_index = match dialog_engine.maybe_surface_bounds {
+                    Some(surface_bounds) => surface_bounds.origin_pos.row_index,
+                    None => ch!(0),
+                };
+                let row_index =
+                    top_row_index + scale(origin_pos.row_index - top_row_index);
+                let origin_pos = position!(
+                    col_index: origin_pos.col_index,
+                    row_index: row_index
+                );
+                Some((origin_pos, *bounds_size))
+            }
+        }
+    }
+
     /// Return the [FlexBox] for the dialog to be rendered in.
     ///
     /// - In non-modal contexts (which this is not), this is determined by the layout engine.
@@ -236,80 +480,75 @@ mod internal_impl {
         };
 
         // Check to ensure that the dialog box has enough space to be displayed.
-        if window_size.col_count < ch!(MinSize::Col as u8)
-            || window_size.row_count < ch!(MinSize::Row as u8)
-        {
+        if is_window_too_small(window_size, dialog_options) {
             return CommonError::new(
                 CommonErrorType::DisplaySizeTooSmall,
                 &format!(
                     "Window size is too small. Min size is {} cols x {} rows",
-                    MinSize::Col as u8,
-                    MinSize::Row as u8
+                    dialog_options.min_size.col_count, dialog_options.min_size.row_count
                 ),
             );
         }
 
-        let (origin_pos, bounds_size) = match dialog_options.mode {
+        let default_bounds_size = match dialog_options.mode {
             DialogEngineMode::ModalSimple => {
-                let simple_dialog_size = {
-                    // Calc dialog bounds size based on window size.
-                    let col_count = {
-                        let percent = percent!(
-                            DisplayConstants::DialogComponentBorderWidthPercent as u16
-                        )?;
-                        percent.calc_percentage(surface_size.col_count)
-                    };
-                    let row_count = ch!(DisplayConstants::SimpleModalRowCount as u16);
-                    let size = size! { col_count: col_count, row_count: row_count };
-                    assert!(size.row_count < ch!(MinSize::Row as u8));
-                    size
-                };
-
-                let origin_pos = {
-                    // Calc origin position based on window size & dialog size.
-                    let origin_col =
-                        surface_size.col_count / 2 - simple_dialog_size.col_count / 2;
-                    let origin_row =
-                        surface_size.row_count / 2 - simple_dialog_size.row_count / 2;
-                    let mut it = position!(col_index: origin_col, row_index: origin_row);
-                    it += surface_origin_pos;
-                    it
+                // Calc dialog bounds size based on window size.
+                let col_count = {
+                    let percent = percent!(
+                        DisplayConstants::DialogComponentBorderWidthPercent as u16
+                    )?;
+                    percent.calc_percentage(surface_size.col_count)
                 };
-
-                (origin_pos, simple_dialog_size)
+                let row_count = ch!(DisplayConstants::SimpleModalRowCount as u16)
+                    + dialog_options.get_editor_row_count()
+                    - ch!(1);
+                if dialog_options.is_multiline() {
+                    // Shrink the editor to fit the surface (if needed).
+                    let max_row_count = std::cmp::max(
+                        surface_size.row_count,
+                        ch!(DisplayConstants::SimpleModalRowCount as u16),
+                    );
+                    let row_count = std::cmp::min(row_count, max_row_count);
+                    size! { col_count: col_count, row_count: row_count }
+                } else {
+                    size! { col_count: col_count, row_count: row_count }
+                }
             }
             DialogEngineMode::ModalAutocomplete => {
-                let autocomplete_dialog_size = {
-                    // Calc dialog bounds size based on window size.
-                    let row_count = ch!(DisplayConstants::SimpleModalRowCount as u16)
-                        + ch!(DisplayConstants::EmptyLine as u16)
-                        + dialog_options.result_panel_display_row_count;
-                    let col_count = {
-                        let percent = percent!(
-                            DisplayConstants::DialogComponentBorderWidthPercent as u16
-                        )?;
-                        percent.calc_percentage(surface_size.col_count)
-                    };
-                    let size = size!(col_count: col_count, row_count: row_count);
-                    assert!(size.row_count < ch!(MinSize::Row as u8));
-                    size
-                };
-
-                let origin_pos = {
-                    // Calc origin position based on window size & dialog size.
-                    let origin_col = surface_size.col_count / 2
-                        - autocomplete_dialog_size.col_count / 2;
-                    let origin_row = surface_size.row_count / 2
-                        - autocomplete_dialog_size.row_count / 2;
-                    let mut it = position!(col_index: origin_col, row_index: origin_row);
-                    it += surface_origin_pos;
-                    it
+                // Calc dialog bounds size based on window size.
+                let row_count = ch!(DisplayConstants::SimpleModalRowCount as u16)
+                    + ch!(DisplayConstants::EmptyLine as u16)
+                    + dialog_options.result_panel_display_row_count;
+                let col_count = {
+                    let percent = percent!(
+                        DisplayConstants::DialogComponentBorderWidthPercent as u16
+                    )?;
+                    percent.calc_percentage(surface_size.col_count)
                 };
-
-                (origin_pos, autocomplete_dialog_size)
+                size!(col_count: col_count, row_count: row_count)
             }
         };
 
+        // The width & height in the options (if any) take precedence.
+        let bounds_size = size!(
+            col_count: match dialog_options.maybe_width {
+                Some(width) => width.resolve(surface_size.col_count),
+                None => default_bounds_size.col_count,
+            },
+            row_count: match dialog_options.maybe_height {
+                Some(height) => height.resolve(surface_size.row_count),
+                None => default_bounds_size.row_count,
+            }
+        );
+        let origin_pos = dialog_options.anchor.get_origin_pos(
+            bounds_size,
+            surface_origin_pos,
+            surface_size,
+        );
+
+        let (origin_pos, bounds_size) =
+            clamp_to_surface(origin_pos, bounds_size, surface_origin_pos, surface_size);
+
         throws_with_return!({
             PartialFlexBox {
                 id: *dialog_id,
@@ -329,12 +568,15 @@ mod internal_impl {
         S: Debug + Default + Clone + PartialEq + Sync + Send,
         A: Debug + Default + Clone + Sync + Send,
     {
-        let maybe_style = args.dialog_engine.dialog_options.maybe_style_editor;
+        let maybe_style = args.dialog_engine.get_style_editor();
 
         let flex_box: FlexBox = PartialFlexBox {
             id: args.self_id,
             style_adjusted_origin_pos: position! {col_index: origin_pos.col_index + 1, row_index: origin_pos.row_index + 2},
-            style_adjusted_bounds_size: size! {col_count: bounds_size.col_count - 2, row_count: 1},
+            style_adjusted_bounds_size: size! {
+                col_count: bounds_size.col_count - 2,
+                row_count: get_editor_row_count(&args.dialog_engine.dialog_options, bounds_size)
+            },
             maybe_computed_style: maybe_style,
         }
         .into();
@@ -358,7 +600,11 @@ mod internal_impl {
             || args.dialog_buffer.editor_buffer.get_as_string() == ""
         {
             let mut ops = render_ops!();
-            let msg = "Press <Esc> to close, or <Enter> to accept".to_string();
+            let msg = if args.dialog_engine.dialog_options.is_multiline() {
+                "Press <Esc> to close, or <Ctrl+Enter> to accept".to_string()
+            } else {
+                "Press <Esc> to close, or <Enter> to accept".to_string()
+            };
 
             ops.push(RenderOp::ResetColor);
             ops.push(RenderOp::MoveCursorPositionAbs(
@@ -383,31 +629,27 @@ mod internal_impl {
         Ok(pipeline)
     }
 
-    pub fn render_results_panel<S>(
+    pub fn render_results_panel(
         origin_pos: &Position,
         bounds_size: &Size,
         dialog_engine: &DialogEngine,
-        self_id: FlexBoxId,
-        state: &S,
-    ) -> CommonResult<RenderOps>
-    where
-        S: HasDialogBuffers + Default + Clone + PartialEq + Debug + Sync + Send,
-    {
+        dialog_buffer: &DialogBuffer,
+    ) -> CommonResult<RenderOps> {
         let mut it = render_ops!();
 
-        if let Some(dialog_buffer) = state.get_dialog_buffer(self_id) {
-            if let Some(results) = dialog_buffer.maybe_results.as_ref() {
-                if !results.is_empty() {
-                    paint_results(
-                        &mut it,
-                        origin_pos,
-                        bounds_size,
-                        results,
-                        dialog_engine,
-                    );
-                };
-            }
-        };
+        if let Some(results) = dialog_buffer.maybe_results.as_ref() {
+            if !results.is_empty() {
+                let query = dialog_buffer.editor_buffer.get_as_string();
+                paint_results(
+                    &mut it,
+                    origin_pos,
+                    bounds_size,
+                    results,
+                    &query,
+                    dialog_engine,
+                );
+            };
+        }
 
         return Ok(it);
 
@@ -416,6 +658,7 @@ mod internal_impl {
             origin_pos: &Position,
             bounds_size: &Size,
             results: &[String],
+            query: &str,
             dialog_engine: &DialogEngine,
         ) {
             let col_start_index = ch!(1);
@@ -441,25 +684,9 @@ mod internal_impl {
 
                 let text = UnicodeString::from(item.as_str());
                 let max_display_col_count = bounds_size.col_count - 2;
-                let clipped_text = if text.display_width > max_display_col_count {
-                    let snip_len = ch!(2); /* `..` */
-                    let postfix_len = ch!(5); /* last 5 characters */
-
-                    let lhs_start_index = ch!(0);
-                    let lhs_end_index = max_display_col_count - postfix_len - snip_len;
-                    let lhs = text.clip_to_width(lhs_start_index, lhs_end_index);
-
-                    let rhs_start_index = text.display_width - postfix_len;
-                    let rhs_end_index = text.display_width;
-                    let rhs = text.clip_to_width(rhs_start_index, rhs_end_index);
-
-                    format!("{lhs}..{rhs}")
-                } else {
-                    text.string
-                };
 
                 let max_display_row_count =
-                    /* Viewport height: */ dialog_engine.dialog_options.result_panel_display_row_count +
+                    /* Viewport height: */ dialog_engine.get_results_panel_row_count() +
                     /* Scroll offset: */ scroll_offset_row_index;
                 if row_index >= max_display_row_count {
                     break;
@@ -471,49 +698,104 @@ mod internal_impl {
                     rel_insertion_pos,
                 ));
 
-                // Set style to underline if selected row & paint.
-                match selected_row_index.eq(&row_index) {
+                // Set style to underline if selected row.
+                let row_style = match selected_row_index.eq(&row_index) {
                     // This is the selected row.
-                    true => {
-                        let my_selected_style = match dialog_engine
-                            .dialog_options
-                            .maybe_style_results_panel
-                        {
-                            // Update existing style.
-                            Some(style) => Style {
-                                underline: true,
-                                ..style
-                            },
-                            // No existing style, so create a new style w/ only underline.
-                            _ => Style {
-                                underline: true,
-                                ..Default::default()
-                            },
-                        }
-                        .into();
-                        // Paint the text for the row.
-                        ops.push(RenderOp::ApplyColors(my_selected_style));
-                        ops.push(RenderOp::PaintTextWithAttributes(
-                            clipped_text,
-                            my_selected_style,
-                        ));
+                    true => match dialog_engine.get_style_results_panel() {
+                        // Update existing style.
+                        Some(style) => Style {
+                            underline: true,
+                            ..style
+                        },
+                        // No existing style, so create a new style w/ only underline.
+                        _ => Style {
+                            underline: true,
+                            ..Default::default()
+                        },
                     }
+                    .into(),
                     // Regular row, not selected.
-                    false => {
-                        // Paint the text for the row.
-                        ops.push(RenderOp::ApplyColors(
-                            dialog_engine.dialog_options.maybe_style_results_panel,
-                        ));
-                        ops.push(RenderOp::PaintTextWithAttributes(
-                            clipped_text,
-                            dialog_engine.dialog_options.maybe_style_results_panel,
-                        ));
-                    }
+                    false => dialog_engine.get_style_results_panel(),
+                };
+
+                // The graphemes that match the query are painted w/ the match style.
+                let matched_grapheme_indices =
+                    match dialog_engine.dialog_options.highlight_matches {
+                        true => fuzzy_match(query, &text.string)
+                            .map(|it| it.matched_grapheme_indices)
+                            .unwrap_or_default(),
+                        false => vec![],
+                    };
+                let match_style = Some(
+                    row_style.unwrap_or_default()
+                        + dialog_engine.get_style_results_panel_match().unwrap_or(
+                            Style {
+                                bold: true,
+                                ..Default::default()
+                            },
+                        ),
+                );
+
+                // Paint the text for the row.
+                for (run, is_matched) in clip_results_panel_row(
+                    &text,
+                    max_display_col_count,
+                    &matched_grapheme_indices,
+                ) {
+                    let run_style = match is_matched {
+                        true => match_style,
+                        false => row_style,
+                    };
+                    ops.push(RenderOp::ApplyColors(run_style));
+                    ops.push(RenderOp::PaintTextWithAttributes(run, run_style));
                 }
             }
         }
     }
 
+    /// Fits `text` in `max_display_col_count` cols. If it doesn't fit, then the middle
+    /// is snipped out, eg: `Some long res..esult`. Returns `(run, is_matched)` where the
+    /// matched runs are made of the graphemes in `matched_grapheme_indices`. The
+    /// graphemes are laid out by their display width, so wide ones are never split.
+    pub fn clip_results_panel_row(
+        text: &UnicodeString,
+        max_display_col_count: ChUnit,
+        matched_grapheme_indices: &[usize],
+    ) -> Vec<(String, bool)> {
+        let is_matched = |segment: &GraphemeClusterSegment| {
+            matched_grapheme_indices.contains(&segment.logical_index)
+        };
+        let mut runs: Vec<(String, bool)> = vec![];
+
+        if text.display_width <= max_display_col_count {
+            for segment in text.iter() {
+                push_to_fuzzy_match_runs(&mut runs, &segment.string, is_matched(segment));
+            }
+            return runs;
+        }
+
+        let snip_len = ch!(2); /* `..` */
+        let postfix_len = ch!(5); /* last 5 characters */
+        let lhs_end_index = max_display_col_count - postfix_len - snip_len;
+        let rhs_start_index = text.display_width - postfix_len;
+
+        for segment in text
+            .iter()
+            .filter(|it| it.display_col_offset + it.unicode_width <= lhs_end_index)
+        {
+            push_to_fuzzy_match_runs(&mut runs, &segment.string, is_matched(segment));
+        }
+        push_to_fuzzy_match_runs(&mut runs, "..", false);
+        for segment in text
+            .iter()
+            .filter(|it| it.display_col_offset >= rhs_start_index)
+        {
+            push_to_fuzzy_match_runs(&mut runs, &segment.string, is_matched(segment));
+        }
+
+        runs
+    }
+
     pub fn render_title(
         origin_pos: &Position,
         bounds_size: &Size,
@@ -531,21 +813,278 @@ mod internal_impl {
 
         ops.push(RenderOp::ResetColor);
         ops.push(RenderOp::MoveCursorPositionAbs(row_pos));
-        ops.push(RenderOp::ApplyColors(
-            dialog_engine.dialog_options.maybe_style_title,
-        ));
+        let maybe_style = dialog_engine.get_style_title();
+        ops.push(RenderOp::ApplyColors(maybe_style));
 
         // Apply lolcat override (if enabled) to the fg_color of text_content.
         lolcat_from_style(
             &mut ops,
             &mut dialog_engine.color_wheel,
-            &dialog_engine.dialog_options.maybe_style_title,
+            &maybe_style,
             text_content,
         );
 
         ops
     }
 
+    /// Paint [DialogEngineConfigOptions::title] & the close hint (if enabled) on top of the
+    /// top border painted by [render_border].
+    ///
+    /// ```text
+    /// ┌─ Title ───────────[x]┐
+    /// ```
+    pub fn render_border_title(
+        origin_pos: &Position,
+        bounds_size: &Size,
+        dialog_engine: &mut DialogEngine,
+    ) -> RenderOps {
+        let mut ops = render_ops!();
+        let maybe_style = dialog_engine.get_style_border_title();
+        let show_close_hint = dialog_engine.dialog_options.show_close_hint;
+        let close_hint_width = UnicodeString::from(DIALOG_CLOSE_HINT).display_width;
+
+        if let Some(title) = dialog_engine.dialog_options.title.clone() {
+            // Leave room for the corners, the line before the title, the spaces around it, &
+            // the close hint.
+            let reserved_col_count = if show_close_hint {
+                ch!(5) + close_hint_width + 1
+            } else {
+                ch!(5)
+            };
+            let title = truncate_with_ellipsis(
+                &title,
+                bounds_size.col_count - reserved_col_count,
+            );
+            if !title.is_empty() {
+                ops.push(RenderOp::ResetColor);
+                ops.push(RenderOp::MoveCursorPositionAbs(position!(
+                    col_index: origin_pos.col_index + 2,
+                    row_index: origin_pos.row_index
+                )));
+                ops.push(RenderOp::ApplyColors(maybe_style));
+                lolcat_from_style(
+                    &mut ops,
+                    &mut dialog_engine.color_wheel,
+                    &maybe_style,
+                    &format!(" {title} "),
+                );
+            }
+        }
+
+        if show_close_hint && bounds_size.col_count > close_hint_width + 2 {
+            ops.push(RenderOp::ResetColor);
+            ops.push(RenderOp::MoveCursorPositionAbs(get_close_hint_pos(
+                origin_pos,
+                bounds_size,
+            )));
+            ops.push(RenderOp::ApplyColors(maybe_style));
+            lolcat_from_style(
+                &mut ops,
+                &mut dialog_engine.color_wheel,
+                &maybe_style,
+                DIALOG_CLOSE_HINT,
+            );
+        }
+
+        ops
+    }
+
+    /// The close hint is right aligned in the top border, just before the corner.
+    pub fn get_close_hint_pos(origin_pos: &Position, bounds_size: &Size) -> Position {
+        let close_hint_width = UnicodeString::from(DIALOG_CLOSE_HINT).display_width;
+        position!(
+            col_index: origin_pos.col_index + bounds_size.col_count - 1 - close_hint_width,
+            row_index: origin_pos.row_index
+        )
+    }
+
+    pub fn is_window_too_small(
+        window_size: &Size,
+        dialog_options: &DialogEngineConfigOptions,
+    ) -> bool {
+        window_size.col_count < dialog_options.min_size.col_count
+            || window_size.row_count < dialog_options.min_size.row_count
+    }
+
+    /// The rows below the title that the editor takes up, in a dialog box that is
+    /// `bounds_size` big. A [multiline](DialogEngineConfigOptions::multiline) editor
+    /// fills them if the [height](DialogEngineConfigOptions::maybe_height) is set.
+    pub fn get_editor_row_count(
+        dialog_options: &DialogEngineConfigOptions,
+        bounds_size: &Size,
+    ) -> ChUnit {
+        let max_row_count =
+            bounds_size.row_count - ch!(DisplayConstants::SimpleModalRowCount as u16 - 1);
+        match dialog_options.maybe_height {
+            Some(_) if dialog_options.is_multiline() => max_row_count,
+            _ => std::cmp::min(dialog_options.get_editor_row_count(), max_row_count),
+        }
+    }
+
+    /// A dialog box needs at least 1 row & col inside its border.
+    pub fn has_interior(bounds_size: &Size) -> bool {
+        bounds_size.col_count > ch!(2) && bounds_size.row_count > ch!(2)
+    }
+
+    /// Shrink the dialog box to fit in the surface (if needed), & then move it so that it
+    /// doesn't stick out of the surface.
+    pub fn clamp_to_surface(
+        origin_pos: Position,
+        bounds_size: Size,
+        surface_origin_pos: Position,
+        surface_size: Size,
+    ) -> (Position, Size) {
+        let bounds_size = size!(
+            col_count: std::cmp::min(bounds_size.col_count, surface_size.col_count),
+            row_count: std::cmp::min(bounds_size.row_count, surface_size.row_count)
+        );
+
+        let clamp = |index: ChUnit,
+                     count: ChUnit,
+                     surface_index: ChUnit,
+                     surface_count: ChUnit| {
+            let max_index = surface_index + surface_count - count;
+            std::cmp::min(std::cmp::max(index, surface_index), max_index)
+        };
+        let origin_pos = position!(
+            col_index: clamp(
+                origin_pos.col_index,
+                bounds_size.col_count,
+                surface_origin_pos.col_index,
+                surface_size.col_count
+            ),
+            row_index: clamp(
+                origin_pos.row_index,
+                bounds_size.row_count,
+                surface_origin_pos.row_index,
+                surface_size.row_count
+            )
+        );
+
+        (origin_pos, bounds_size)
+    }
+
+    /// Paint [DIALOG_WINDOW_TOO_SMALL_MSG] (truncated to fit) in the middle of the
+    /// surface, or the window if there is no surface.
+    pub fn render_window_too_small(
+        window_size: &Size,
+        maybe_surface_bounds: Option<SurfaceBounds>,
+    ) -> RenderPipeline {
+        let SurfaceBounds {
+            origin_pos,
+            box_size,
+        } = maybe_surface_bounds.unwrap_or(SurfaceBounds {
+            origin_pos: position!(col_index: 0, row_index: 0),
+            box_size: *window_size,
+        });
+
+        let mut pipeline = render_pipeline!();
+        if box_size.col_count == ch!(0) || box_size.row_count == ch!(0) {
+            return pipeline;
+        }
+
+        let msg = truncate_with_ellipsis(DIALOG_WINDOW_TOO_SMALL_MSG, box_size.col_count);
+        let msg_width = UnicodeString::from(msg.as_str()).display_width;
+
+        let mut ops = render_ops!();
+        ops.push(RenderOp::ResetColor);
+        ops.push(RenderOp::MoveCursorPositionAbs(position!(
+            col_index: origin_pos.col_index + (box_size.col_count - msg_width) / 2,
+            row_index: origin_pos.row_index + box_size.row_count / 2
+        )));
+        ops.push(RenderOp::PaintTextWithAttributes(
+            msg,
+            Some(get_dialog_error_style()),
+        ));
+        ops.push(RenderOp::ResetColor);
+        pipeline.push(ZOrder::Glass, ops);
+
+        pipeline
+    }
+
+    pub fn get_dialog_error_style() -> Style {
+        style! {
+            attrib: [bold]
+            color_fg: TuiColor::Basic(ANSIBasicColor::Red)
+        }
+    }
+
+    /// Paint the [DialogEngine::maybe_input_error] (if any) in the bottom border.
+    pub fn render_input_error(
+        origin_pos: &Position,
+        bounds_size: &Size,
+        dialog_engine: &DialogEngine,
+    ) -> RenderOps {
+        let mut ops = render_ops!();
+        let Some(dialog_error) = &dialog_engine.maybe_input_error else {
+            return ops;
+        };
+
+        // Leave room for the corners & the line before the message.
+        let msg = truncate_with_ellipsis(
+            &format!(" {dialog_error} "),
+            bounds_size.col_count - 4,
+        );
+        if msg.is_empty() {
+            return ops;
+        }
+
+        let style = get_dialog_error_style();
+        ops.push(RenderOp::ResetColor);
+        ops.push(RenderOp::MoveCursorPositionAbs(position!(
+            col_index: origin_pos.col_index + 2,
+            row_index: origin_pos.row_index + bounds_size.row_count - 1
+        )));
+        ops.push(RenderOp::PaintTextWithAttributes(msg, Some(style)));
+        ops.push(RenderOp::ResetColor);
+
+        ops
+    }
+
+    pub fn truncate_with_ellipsis(text: &str, max_display_col_count: ChUnit) -> String {
+        let text_us = UnicodeString::from(text);
+        if text_us.display_width <= max_display_col_count {
+            return text.to_string();
+        }
+        if max_display_col_count == ch!(0) {
+            return String::new();
+        }
+        let ellipsis_width = UnicodeString::from(ELLIPSIS).display_width;
+        format!(
+            "{}{ELLIPSIS}",
+            text_us.truncate_end_to_fit_width(max_display_col_count - ellipsis_width)
+        )
+    }
+
+    /// Returns `true` if the `input_event` is a click on the close hint painted by
+    /// [render_border_title].
+    pub fn is_close_hint_clicked(
+        input_event: &InputEvent,
+        dialog_engine: &DialogEngine,
+    ) -> bool {
+        if !dialog_engine.dialog_options.show_close_hint {
+            return false;
+        }
+        let (
+            InputEvent::Mouse(MouseInput {
+                pos,
+                kind: MouseInputKind::MouseDown(_),
+                ..
+            }),
+            Some((_, _, flex_box)),
+        ) = (input_event, &dialog_engine.maybe_flex_box)
+        else {
+            return false;
+        };
+
+        let (origin_pos, bounds_size) = flex_box.get_style_adjusted_position_and_size();
+        let close_hint_pos = get_close_hint_pos(&origin_pos, &bounds_size);
+        let close_hint_width = UnicodeString::from(DIALOG_CLOSE_HINT).display_width;
+        pos.row_index == close_hint_pos.row_index
+            && pos.col_index >= close_hint_pos.col_index
+            && pos.col_index < close_hint_pos.col_index + close_hint_width
+    }
+
     /// Only Colorizes text in-place if [Style]'s `lolcat` field is true. Otherwise leaves `text`
     /// alone.
     fn lolcat_from_style(
@@ -579,7 +1118,7 @@ mod internal_impl {
     ) -> RenderOps {
         let mut ops = render_ops!();
         let inner_spaces = SPACER.repeat(ch!(@to_usize bounds_size.col_count - 2));
-        let maybe_style = dialog_engine.dialog_options.maybe_style_border;
+        let maybe_style = dialog_engine.get_style_border();
 
         for row_idx in 0..*bounds_size.row_count {
             let row_pos = position!(col_index: origin_pos.col_index, row_index: origin_pos.row_index + row_idx);
@@ -693,13 +1232,54 @@ mod internal_impl {
         ops
     }
 
+    /// Only [DialogChoice::Yes] is checked, against
+    /// [DialogEngineConfigOptions::reject_empty_input] (in
+    /// [DialogEngineMode::ModalSimple]) & [DialogEngine::maybe_input_validator].
+    pub fn validate_dialog_choice(
+        choice: &DialogChoice,
+        dialog_engine: &DialogEngine,
+    ) -> DialogResult<()> {
+        let DialogChoice::Yes(text) = choice else {
+            return Ok(());
+        };
+
+        if dialog_engine.dialog_options.reject_empty_input
+            && dialog_engine.dialog_options.mode == DialogEngineMode::ModalSimple
+            && text.trim().is_empty()
+        {
+            return Err(DialogError::EmptyBuffer);
+        }
+
+        if let Some(validator) = dialog_engine.maybe_input_validator {
+            validator(text).map_err(DialogError::ValidationFailed)?;
+        }
+
+        Ok(())
+    }
+
     pub fn try_handle_dialog_choice(
         input_event: &InputEvent,
         dialog_buffer: &DialogBuffer,
         dialog_engine: &DialogEngine,
     ) -> Option<DialogChoice> {
+        // Handle click on the close hint.
+        if is_close_hint_clicked(input_event, dialog_engine) {
+            return Some(DialogChoice::No);
+        }
+
         match DialogEvent::from(input_event) {
             // Handle Enter.
+            // Enter inserts a new line in multiline mode (handled by the editor).
+            DialogEvent::EnterPressed if dialog_engine.dialog_options.is_multiline() => {}
+
+            // Handle Ctrl+Enter (multiline mode only).
+            DialogEvent::CtrlEnterPressed
+                if dialog_engine.dialog_options.is_multiline() =>
+            {
+                let text = dialog_buffer.editor_buffer.get_as_string_with_newlines();
+                return Some(DialogChoice::Yes(text));
+            }
+
             DialogEvent::EnterPressed => match dialog_engine.dialog_options.mode {
                 DialogEngineMode::ModalSimple => {
                     let text = dialog_buffer.editor_buffer.get_as_string();
@@ -731,14 +1311,22 @@ mod internal_impl {
         dialog_buffer: &DialogBuffer,
         dialog_engine: &mut DialogEngine,
     ) -> EventPropagation {
+        // Up & down move the caret in multiline mode (handled by the editor).
+        if dialog_engine.dialog_options.is_multiline() {
+            return EventPropagation::Propagate;
+        }
+
         // Handle up arrow?
         if input_event.matches(&[InputEvent::Keyboard(KeyPress::Plain {
             key: Key::SpecialKey(SpecialKey::Up),
         })]) {
-            if dialog_engine.selected_row_index > ch!(0) {
-                dialog_engine.selected_row_index -= 1;
+            // Already at the first result.
+            if dialog_engine.selected_row_index == ch!(0) {
+                return EventPropagation::ConsumedNoRender;
             }
 
+            dialog_engine.selected_row_index -= 1;
+
             if dialog_engine.selected_row_index < dialog_engine.scroll_offset_row_index {
                 dialog_engine.scroll_offset_row_index -= 1;
             }
@@ -753,12 +1341,15 @@ mod internal_impl {
             let max_abs_row_index = dialog_buffer.get_results_count() - ch!(1);
 
             let results_panel_viewport_height_row_count =
-                dialog_engine.dialog_options.result_panel_display_row_count;
+                dialog_engine.get_results_panel_row_count();
 
-            if dialog_engine.selected_row_index < max_abs_row_index {
-                dialog_engine.selected_row_index += 1;
+            // Already at the last result (or there are none).
+            if dialog_engine.selected_row_index >= max_abs_row_index {
+                return EventPropagation::ConsumedNoRender;
             }
 
+            dialog_engine.selected_row_index += 1;
+
             if dialog_engine.selected_row_index
                 >= dialog_engine.scroll_offset_row_index
                     + results_panel_viewport_height_row_count
@@ -810,23 +1401,185 @@ mod test_dialog_engine_api_render_engine {
         let render_ops = pipeline.get(&ZOrder::Glass).unwrap();
         assert!(!render_ops.is_empty());
     }
-}
-
-#[cfg(test)]
-mod test_dialog_api_make_flex_box_for_dialog {
-    use std::error::Error;
-
-    use r3bl_rs_utils_core::*;
 
-    use crate::{dialog_engine_api::internal_impl, *};
+    fn get_painted_texts(pipeline: &RenderPipeline) -> Vec<String> {
+        pipeline
+            .get_all_render_op_in(ZOrder::Glass)
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|op| match op {
+                RenderOp::PaintTextWithAttributes(text, _) => Some(text),
+                _ => None,
+            })
+            .collect()
+    }
 
-    /// More info on `is` and downcasting:
-    /// - https://stackoverflow.com/questions/71409337/rust-how-to-match-against-any
-    /// - https://ysantos.com/blog/downcast-rust
-    #[test]
-    fn make_flex_box_for_dialog_simple_display_size_too_small() {
-        let surface = Surface::default();
-        let window_size = Size::default();
+    #[tokio::test]
+    async fn render_engine_window_too_small() {
+        let self_id: FlexBoxId = FlexBoxId::from(0);
+        let window_size = &size!( col_count: 10, row_count: 3 );
+        let dialog_buffer = &mut DialogBuffer::new_empty();
+        let dialog_engine = &mut mock_real_objects_for_dialog::make_dialog_engine();
+        let shared_store = &mock_real_objects_for_dialog::create_store();
+        let shared_global_data =
+            &test_editor::mock_real_objects_for_editor::make_shared_global_data(
+                (*window_size).into(),
+            );
+        let component_registry =
+            &mut test_editor::mock_real_objects_for_editor::make_component_registry();
+        let state = &shared_store.read().await.state.clone();
+        let args = DialogEngineArgs {
+            shared_global_data,
+            shared_store,
+            state,
+            component_registry,
+            window_size,
+            self_id,
+            dialog_buffer,
+            dialog_engine,
+        };
+
+        // A compact message is painted (instead of an error), truncated to fit.
+        let pipeline = DialogEngineApi::render_engine(args).await.unwrap();
+        assert_eq2!(get_painted_texts(&pipeline), vec!["window to…".to_string()]);
+        assert_eq2!(
+            pipeline.get_all_render_op_in(ZOrder::Glass).unwrap()[1],
+            RenderOp::MoveCursorPositionAbs(position!(col_index: 0, row_index: 1))
+        );
+        assert!(dialog_engine.maybe_flex_box.is_none());
+    }
+
+    #[tokio::test]
+    async fn render_engine_surface_too_small() {
+        let self_id: FlexBoxId = FlexBoxId::from(0);
+        let window_size = &size!( col_count: 70, row_count: 15 );
+        let dialog_buffer = &mut DialogBuffer::new_empty();
+        let dialog_engine = &mut mock_real_objects_for_dialog::make_dialog_engine();
+        dialog_engine.maybe_surface_bounds = Some(SurfaceBounds {
+            origin_pos: position!(col_index: 5, row_index: 5),
+            box_size: size!(col_count: 40, row_count: 2),
+        });
+        let shared_store = &mock_real_objects_for_dialog::create_store();
+        let shared_global_data =
+            &test_editor::mock_real_objects_for_editor::make_shared_global_data(
+                (*window_size).into(),
+            );
+        let component_registry =
+            &mut test_editor::mock_real_objects_for_editor::make_component_registry();
+        let state = &shared_store.read().await.state.clone();
+        let args = DialogEngineArgs {
+            shared_global_data,
+            shared_store,
+            state,
+            component_registry,
+            window_size,
+            self_id,
+            dialog_buffer,
+            dialog_engine,
+        };
+
+        // The window is big enough, but the dialog box has no room inside its border once
+        // it is clamped to the 2 rows of the surface.
+        let pipeline = DialogEngineApi::render_engine(args).await.unwrap();
+        assert_eq2!(
+            get_painted_texts(&pipeline),
+            vec![DIALOG_WINDOW_TOO_SMALL_MSG.to_string()]
+        );
+        assert_eq2!(
+            pipeline.get_all_render_op_in(ZOrder::Glass).unwrap()[1],
+            RenderOp::MoveCursorPositionAbs(position!(col_index: 17, row_index: 6))
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_dialog_engine_api_clip_results_panel_row {
+    use r3bl_rs_utils_core::*;
+
+    use crate::dialog_engine_api::internal_impl;
+
+    fn run(text: &str, is_matched: bool) -> (String, bool) {
+        (text.to_string(), is_matched)
+    }
+
+    #[test]
+    fn test_clip_results_panel_row_fits() {
+        let runs = internal_impl::clip_results_panel_row(
+            &UnicodeString::from("Open File"),
+            ch!(20),
+            &[0, 5, 6],
+        );
+        assert_eq2!(
+            runs,
+            vec![
+                run("O", true),
+                run("pen ", false),
+                run("Fi", true),
+                run("le", false)
+            ]
+        );
+
+        // Nothing matched.
+        let runs = internal_impl::clip_results_panel_row(
+            &UnicodeString::from("abc"),
+            ch!(20),
+            &[],
+        );
+        assert_eq2!(runs, vec![run("abc", false)]);
+    }
+
+    #[test]
+    fn test_clip_results_panel_row_wide_chars() {
+        let runs = internal_impl::clip_results_panel_row(
+            &UnicodeString::from("学中文abc"),
+            ch!(20),
+            &[1, 2],
+        );
+        assert_eq2!(
+            runs,
+            vec![run("学", false), run("中文", true), run("abc", false)]
+        );
+    }
+
+    #[test]
+    fn test_clip_results_panel_row_clipped() {
+        // 17 cols wide, clipped to 12 cols: 5 for the lhs, 2 for `..`, and 5 for the rhs. The
+        // 3rd wide char doesn't fit in the lhs, so it is dropped (not split in half).
+        let runs = internal_impl::clip_results_panel_row(
+            &UnicodeString::from("中文中文中文abcde"),
+            ch!(12),
+            &[1, 7],
+        );
+        assert_eq2!(
+            runs,
+            vec![
+                run("中", false),
+                run("文", true),
+                run("..a", false),
+                run("b", true),
+                run("cde", false)
+            ]
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_dialog_api_make_flex_box_for_dialog {
+    use std::error::Error;
+
+    use r3bl_rs_utils_core::*;
+
+    use crate::{dialog_engine_api::internal_impl,
+                test_dialog::mock_real_objects_for_dialog,
+                *};
+
+    /// More info on `is` and downcasting:
+    /// - https://stackoverflow.com/questions/71409337/rust-how-to-match-against-any
+    /// - https://ysantos.com/blog/downcast-rust
+    #[test]
+    fn make_flex_box_for_dialog_simple_display_size_too_small() {
+        let surface = Surface::default();
+        let window_size = Size::default();
         let dialog_id: FlexBoxId = FlexBoxId::from(0);
 
         // The window size is too small and will result in this error.
@@ -906,6 +1659,64 @@ mod test_dialog_api_make_flex_box_for_dialog {
         assert_eq2!(result, true);
     }
 
+    #[test]
+    fn make_flex_box_for_dialog_custom_min_size() {
+        let window_size = size!( col_count: 40, row_count: 8 );
+        let dialog_options = DialogEngineConfigOptions {
+            mode: DialogEngineMode::ModalSimple,
+            min_size: size!( col_count: 30, row_count: 6 ),
+            ..Default::default()
+        };
+
+        let result_flex_box = internal_impl::make_flex_box_for_dialog(
+            &FlexBoxId::from(0),
+            &dialog_options,
+            &window_size,
+            None,
+        );
+        assert_eq2!(result_flex_box.is_ok(), true);
+
+        let window_size = size!( col_count: 29, row_count: 8 );
+        let result_flex_box = internal_impl::make_flex_box_for_dialog(
+            &FlexBoxId::from(0),
+            &dialog_options,
+            &window_size,
+            None,
+        );
+        assert_eq2!(result_flex_box.is_err(), true);
+    }
+
+    #[test]
+    fn make_flex_box_for_dialog_clamped_to_surface() {
+        // The autocomplete dialog box needs 10 rows, but the surface only has 3.
+        let surface = Surface {
+            origin_pos: position! { col_index: 2, row_index: 2 },
+            box_size: size!( col_count: 30, row_count: 3 ),
+            ..Default::default()
+        };
+        let window_size = size!( col_count: 70, row_count: 15 );
+
+        let flex_box = internal_impl::make_flex_box_for_dialog(
+            &FlexBoxId::from(0),
+            &DialogEngineConfigOptions {
+                mode: DialogEngineMode::ModalAutocomplete,
+                ..Default::default()
+            },
+            &window_size,
+            Some(SurfaceBounds::from(&surface)),
+        )
+        .unwrap();
+
+        assert_eq2!(
+            flex_box.style_adjusted_bounds_size,
+            size!( col_count: 27, row_count: 3 )
+        );
+        assert_eq2!(
+            flex_box.style_adjusted_origin_pos,
+            position!( col_index: 4, row_index: 2 )
+        );
+    }
+
     #[test]
     fn make_flex_box_for_dialog_simple() {
         // 1. The surface and window_size are not the same width and height.
@@ -980,6 +1791,179 @@ mod test_dialog_api_make_flex_box_for_dialog {
             position!( col_index: 5, row_index: 2 )
         );
     }
+
+    #[test]
+    fn make_flex_box_for_dialog_custom_dimensions() {
+        let surface = Surface {
+            origin_pos: position! { col_index: 2, row_index: 2 },
+            box_size: size!( col_count: 65, row_count: 10 ),
+            ..Default::default()
+        };
+        let window_size = size!( col_count: 70, row_count: 15 );
+        let self_id: FlexBoxId = FlexBoxId::from(0);
+
+        // A narrow dialog box at the top of the surface.
+        let dialog_options = DialogEngineConfigOptions {
+            mode: DialogEngineMode::ModalAutocomplete,
+            maybe_width: Some(DialogDimension::Fixed(ch!(30))),
+            maybe_height: Some(DialogDimension::Percent(percent!(80).unwrap())),
+            anchor: DialogAnchor::Top,
+            ..Default::default()
+        };
+        let flex_box = internal_impl::make_flex_box_for_dialog(
+            &self_id,
+            &dialog_options,
+            &window_size,
+            Some(SurfaceBounds::from(&surface)),
+        )
+        .unwrap();
+        assert_eq2!(
+            flex_box.style_adjusted_bounds_size,
+            size!( col_count: 30, row_count: 8 )
+        );
+        assert_eq2!(
+            flex_box.style_adjusted_origin_pos,
+            position!( col_index: 19, row_index: 2 )
+        );
+
+        // The results panel fills the rows below the editor.
+        let dialog_engine = DialogEngine {
+            dialog_options,
+            maybe_flex_box: Some((
+                window_size,
+                DialogEngineMode::ModalAutocomplete,
+                flex_box,
+            )),
+            ..Default::default()
+        };
+        assert_eq2!(dialog_engine.get_results_panel_row_count(), ch!(3));
+
+        // A dialog box that is too big is clamped to the surface.
+        let flex_box = internal_impl::make_flex_box_for_dialog(
+            &self_id,
+            &DialogEngineConfigOptions {
+                maybe_width: Some(DialogDimension::Percent(percent!(50).unwrap())),
+                maybe_height: Some(DialogDimension::Fixed(ch!(20))),
+                anchor: DialogAnchor::Bottom,
+                ..Default::default()
+            },
+            &window_size,
+            Some(SurfaceBounds::from(&surface)),
+        )
+        .unwrap();
+        assert_eq2!(
+            flex_box.style_adjusted_bounds_size,
+            size!( col_count: 32, row_count: 10 )
+        );
+        assert_eq2!(
+            flex_box.style_adjusted_origin_pos,
+            position!( col_index: 18, row_index: 2 )
+        );
+    }
+
+    #[test]
+    fn make_flex_box_for_dialog_multiline() {
+        let surface = Surface {
+            origin_pos: position! { col_index: 2, row_index: 2 },
+            box_size: size!( col_count: 65, row_count: 10 ),
+            ..Default::default()
+        };
+        let window_size = size!( col_count: 70, row_count: 15 );
+        let self_id: FlexBoxId = FlexBoxId::from(0);
+
+        // border-top, title, 5 editor rows, border-bottom.
+        let flex_box = internal_impl::make_flex_box_for_dialog(
+            &self_id,
+            &DialogEngineConfigOptions {
+                mode: DialogEngineMode::ModalSimple,
+                multiline: true,
+                ..Default::default()
+            },
+            &window_size,
+            Some(SurfaceBounds::from(&surface)),
+        )
+        .unwrap();
+        assert_eq2!(
+            flex_box.style_adjusted_bounds_size,
+            size!( col_count: 58, row_count: 8 )
+        );
+
+        // The editor shrinks to fit the surface.
+        let flex_box = internal_impl::make_flex_box_for_dialog(
+            &self_id,
+            &DialogEngineConfigOptions {
+                mode: DialogEngineMode::ModalSimple,
+                multiline: true,
+                multiline_row_count: ch!(20),
+                ..Default::default()
+            },
+            &window_size,
+            Some(SurfaceBounds::from(&surface)),
+        )
+        .unwrap();
+        assert_eq2!(
+            flex_box.style_adjusted_bounds_size,
+            size!( col_count: 58, row_count: 10 )
+        );
+    }
+
+    #[test]
+    fn render_border_title_and_close_hint() {
+        let mut dialog_engine = mock_real_objects_for_dialog::make_dialog_engine();
+        dialog_engine.dialog_options.title = Some("A very long dialog title".into());
+        dialog_engine.dialog_options.show_close_hint = true;
+        let origin_pos = position!(col_index: 0, row_index: 0);
+        let bounds_size = size!(col_count: 20, row_count: 4);
+
+        // 20 cols - 5 (corners, line, spaces) - 3 (close hint) - 1 = 11 cols.
+        let ops = internal_impl::render_border_title(
+            &origin_pos,
+            &bounds_size,
+            &mut dialog_engine,
+        );
+        let painted_texts = ops
+            .iter()
+            .filter_map(|op| match op {
+                RenderOp::PaintTextWithAttributes(text, _) => Some(text.as_str()),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq2!(painted_texts, vec![" A very lon… ", DIALOG_CLOSE_HINT]);
+
+        // Clicking on the close hint is the same as pressing Esc.
+        dialog_engine.maybe_flex_box = Some((
+            bounds_size,
+            DialogEngineMode::ModalSimple,
+            PartialFlexBox {
+                style_adjusted_origin_pos: origin_pos,
+                style_adjusted_bounds_size: bounds_size,
+                ..Default::default()
+            },
+        ));
+        let click = |col_index: u16| {
+            InputEvent::Mouse(MouseInput {
+                pos: position!(col_index: col_index, row_index: 0),
+                kind: MouseInputKind::MouseDown(Button::Left),
+                maybe_modifier_keys: None,
+            })
+        };
+        assert!(internal_impl::is_close_hint_clicked(
+            &click(16),
+            &dialog_engine
+        ));
+        assert!(!internal_impl::is_close_hint_clicked(
+            &click(15),
+            &dialog_engine
+        ));
+        assert!(matches!(
+            internal_impl::try_handle_dialog_choice(
+                &click(18),
+                &DialogBuffer::new_empty(),
+                &dialog_engine
+            ),
+            Some(DialogChoice::No)
+        ));
+    }
 }
 
 #[cfg(test)]
@@ -1097,4 +2081,420 @@ mod test_dialog_engine_api_apply_event {
             assert_eq2!(editor_buffer.get_as_string(), "a");
         }
     }
+
+    #[tokio::test]
+    async fn apply_event_enter_rejected() {
+        let self_id: FlexBoxId = FlexBoxId::from(0);
+        let window_size = &size!( col_count: 70, row_count: 15 );
+        let dialog_buffer = &mut DialogBuffer::new_empty();
+        let dialog_engine = &mut mock_real_objects_for_dialog::make_dialog_engine();
+        dialog_engine.dialog_options.reject_empty_input = true;
+        fn validator(text: &str) -> Result<(), String> {
+            match text {
+                "taken" => Err("Name is taken".to_string()),
+                _ => Ok(()),
+            }
+        }
+        dialog_engine.maybe_input_validator = Some(validator);
+        let shared_store = &mock_real_objects_for_dialog::create_store();
+        let shared_global_data =
+            &test_editor::mock_real_objects_for_editor::make_shared_global_data(
+                (*window_size).into(),
+            );
+        let component_registry =
+            &mut test_editor::mock_real_objects_for_editor::make_component_registry();
+        let state = &shared_store.read().await.state.clone();
+        let input_event = InputEvent::Keyboard(keypress!(@special SpecialKey::Enter));
+
+        // Empty input.
+        let args = DialogEngineArgs {
+            shared_global_data,
+            shared_store,
+            state,
+            component_registry,
+            window_size,
+            self_id,
+            dialog_buffer,
+            dialog_engine,
+        };
+        let result = DialogEngineApi::apply_event(args, &input_event).await;
+        assert_eq2!(result.unwrap_err(), DialogError::EmptyBuffer);
+        assert_eq2!(
+            dialog_engine.maybe_input_error,
+            Some(DialogError::EmptyBuffer)
+        );
+
+        // Input rejected by the validator.
+        dialog_buffer
+            .editor_buffer
+            .set_lines(vec!["taken".to_string()]);
+        let args = DialogEngineArgs {
+            shared_global_data,
+            shared_store,
+            state,
+            component_registry,
+            window_size,
+            self_id,
+            dialog_buffer,
+            dialog_engine,
+        };
+        let result = DialogEngineApi::apply_event(args, &input_event).await;
+        let expected = DialogError::ValidationFailed("Name is taken".to_string());
+        assert_eq2!(result.unwrap_err(), expected.clone());
+        assert_eq2!(dialog_engine.maybe_input_error, Some(expected));
+
+        // Valid input is accepted, & the error is cleared.
+        dialog_buffer
+            .editor_buffer
+            .set_lines(vec!["free".to_string()]);
+        let args = DialogEngineArgs {
+            shared_global_data,
+            shared_store,
+            state,
+            component_registry,
+            window_size,
+            self_id,
+            dialog_buffer,
+            dialog_engine,
+        };
+        let response = DialogEngineApi::apply_event(args, &input_event)
+            .await
+            .unwrap();
+        assert!(matches!(
+            response,
+            DialogEngineApplyResponse::DialogChoice(DialogChoice::Yes(_))
+        ));
+        assert_eq2!(dialog_engine.maybe_input_error, None);
+    }
+
+    #[tokio::test]
+    async fn apply_event_selected_result() {
+        let self_id: FlexBoxId = FlexBoxId::from(0);
+        let window_size = &size!( col_count: 70, row_count: 15 );
+        let dialog_buffer = &mut DialogBuffer::new_empty();
+        dialog_buffer.maybe_results =
+            Some(vec!["a".to_string(), "b".to_string(), "c".to_string()]);
+        let dialog_engine = &mut mock_real_objects_for_dialog::make_dialog_engine();
+        dialog_engine.dialog_options.mode = DialogEngineMode::ModalAutocomplete;
+        dialog_engine.dialog_options.result_panel_display_row_count = ch!(2);
+        let shared_store = &mock_real_objects_for_dialog::create_store();
+        let shared_global_data =
+            &test_editor::mock_real_objects_for_editor::make_shared_global_data(
+                (*window_size).into(),
+            );
+        let component_registry =
+            &mut test_editor::mock_real_objects_for_editor::make_component_registry();
+        let state = &shared_store.read().await.state.clone();
+
+        // Preselect a result that is out of range, it is clamped to the last one, and
+        // scrolled into view.
+        dialog_engine.set_selected_result_index(5);
+        let args = DialogEngineArgs {
+            shared_global_data,
+            shared_store,
+            state,
+            component_registry,
+            window_size,
+            self_id,
+            dialog_buffer,
+            dialog_engine,
+        };
+        let input_event = InputEvent::Keyboard(keypress!(@special SpecialKey::Up));
+        let response = DialogEngineApi::apply_event(args, &input_event)
+            .await
+            .unwrap();
+        assert!(matches!(
+            response,
+            DialogEngineApplyResponse::SelectScrollResultsPanel
+        ));
+        assert_eq2!(dialog_engine.get_selected_result_index(), 1);
+        assert_eq2!(dialog_engine.get_selected_result(dialog_buffer), Some("b"));
+        assert_eq2!(dialog_engine.scroll_offset_row_index, ch!(1));
+
+        // The results shrink.
+        dialog_buffer.maybe_results = Some(vec!["z".to_string()]);
+        dialog_engine.clamp_selected_result_index(dialog_buffer.get_results_count());
+        assert_eq2!(dialog_engine.get_selected_result(dialog_buffer), Some("z"));
+        assert_eq2!(dialog_engine.scroll_offset_row_index, ch!(0));
+
+        // No results.
+        dialog_buffer.maybe_results = None;
+        dialog_engine.clamp_selected_result_index(dialog_buffer.get_results_count());
+        assert_eq2!(dialog_engine.get_selected_result_index(), 0);
+        assert_eq2!(dialog_engine.get_selected_result(dialog_buffer), None);
+    }
+
+    #[tokio::test]
+    async fn apply_event_mouse_before_render() {
+        let self_id: FlexBoxId = FlexBoxId::from(0);
+        let window_size = &size!( col_count: 70, row_count: 15 );
+        let dialog_buffer = &mut DialogBuffer::new_empty();
+        let dialog_engine = &mut mock_real_objects_for_dialog::make_dialog_engine();
+        dialog_engine.dialog_options.show_close_hint = true;
+        let shared_store = &mock_real_objects_for_dialog::create_store();
+        let shared_global_data =
+            &test_editor::mock_real_objects_for_editor::make_shared_global_data(
+                (*window_size).into(),
+            );
+        let component_registry =
+            &mut test_editor::mock_real_objects_for_editor::make_component_registry();
+        let state = &shared_store.read().await.state.clone();
+        let args = DialogEngineArgs {
+            shared_global_data,
+            shared_store,
+            state,
+            component_registry,
+            window_size,
+            self_id,
+            dialog_buffer,
+            dialog_engine,
+        };
+
+        let input_event = InputEvent::Mouse(MouseInput {
+            pos: position!(col_index: 0, row_index: 0),
+            kind: MouseInputKind::MouseDown(Button::Left),
+            maybe_modifier_keys: None,
+        });
+        let result = DialogEngineApi::apply_event(args, &input_event).await;
+        assert_eq2!(result.unwrap_err(), DialogError::NoSurfaceBounds);
+    }
+
+    #[tokio::test]
+    async fn apply_event_ignored_while_animating() {
+        let self_id: FlexBoxId = FlexBoxId::from(0);
+        let window_size = &size!( col_count: 70, row_count: 15 );
+        let dialog_buffer = &mut DialogBuffer::new_empty();
+        let dialog_engine = &mut mock_real_objects_for_dialog::make_dialog_engine();
+        dialog_engine.dialog_options.animation =
+            Some(DialogAnimation::Grow { frame_count: 3 });
+        let shared_store = &mock_real_objects_for_dialog::create_store();
+        let shared_global_data =
+            &test_editor::mock_real_objects_for_editor::make_shared_global_data(
+                (*window_size).into(),
+            );
+        let component_registry =
+            &mut test_editor::mock_real_objects_for_editor::make_component_registry();
+        let state = &shared_store.read().await.state.clone();
+        let input_event = InputEvent::Keyboard(keypress!(@special SpecialKey::Esc));
+
+        // Input is ignored until the open animation is done (takes 3 renders).
+        for frame_index in 0..3 {
+            let args = DialogEngineArgs {
+                shared_global_data,
+                shared_store,
+                state,
+                component_registry,
+                window_size,
+                self_id,
+                dialog_buffer,
+                dialog_engine,
+            };
+            let response = DialogEngineApi::apply_event(args, &input_event)
+                .await
+                .unwrap();
+            assert!(matches!(
+                response,
+                DialogEngineApplyResponse::AnimationInProgress
+            ));
+
+            let args = DialogEngineArgs {
+                shared_global_data,
+                shared_store,
+                state,
+                component_registry,
+                window_size,
+                self_id,
+                dialog_buffer,
+                dialog_engine,
+            };
+            DialogEngineApi::render_engine(args).await.unwrap();
+
+            // Each frame (but the last) requests a render tick for the next one.
+            let maybe_requested_delay = shared_global_data
+                .write()
+                .await
+                .render_tick
+                .maybe_requested_delay
+                .take();
+            assert_eq2!(maybe_requested_delay.is_some(), frame_index < 2);
+        }
+        assert_eq2!(
+            dialog_engine.animation_progress,
+            DialogAnimationProgress::Settled
+        );
+
+        // Esc starts the close animation, the choice is available once it is done.
+        let args = DialogEngineArgs {
+            shared_global_data,
+            shared_store,
+            state,
+            component_registry,
+            window_size,
+            self_id,
+            dialog_buffer,
+            dialog_engine,
+        };
+        let response = DialogEngineApi::apply_event(args, &input_event)
+            .await
+            .unwrap();
+        assert!(matches!(
+            response,
+            DialogEngineApplyResponse::AnimationInProgress
+        ));
+        for _ in 0..3 {
+            assert_eq2!(dialog_engine.take_closed_dialog_choice(), None);
+            let args = DialogEngineArgs {
+                shared_global_data,
+                shared_store,
+                state,
+                component_registry,
+                window_size,
+                self_id,
+                dialog_buffer,
+                dialog_engine,
+            };
+            DialogEngineApi::render_engine(args).await.unwrap();
+        }
+        assert_eq2!(
+            dialog_engine.take_closed_dialog_choice(),
+            Some(DialogChoice::No)
+        );
+        assert_eq2!(
+            dialog_engine.animation_progress,
+            DialogAnimationProgress::NotStarted
+        );
+
+        // Reduce motion disables the animation.
+        dialog_engine.dialog_options.reduce_motion = true;
+        let args = DialogEngineArgs {
+            shared_global_data,
+            shared_store,
+            state,
+            component_registry,
+            window_size,
+            self_id,
+            dialog_buffer,
+            dialog_engine,
+        };
+        let response = DialogEngineApi::apply_event(args, &input_event)
+            .await
+            .unwrap();
+        assert!(matches!(
+            response,
+            DialogEngineApplyResponse::DialogChoice(DialogChoice::No)
+        ));
+    }
+
+    #[tokio::test]
+    async fn apply_event_multiline() {
+        let self_id: FlexBoxId = FlexBoxId::from(0);
+        let window_size = &size!( col_count: 70, row_count: 15 );
+        let dialog_buffer = &mut DialogBuffer::new_empty();
+        let dialog_engine = &mut mock_real_objects_for_dialog::make_dialog_engine();
+        dialog_engine.dialog_options.multiline = true;
+        let shared_store = &mock_real_objects_for_dialog::create_store();
+        let shared_global_data =
+            &test_editor::mock_real_objects_for_editor::make_shared_global_data(
+                (*window_size).into(),
+            );
+        let component_registry =
+            &mut test_editor::mock_real_objects_for_editor::make_component_registry();
+        let state = &shared_store.read().await.state.clone();
+
+        // Enter inserts a new line, instead of accepting the input.
+        for input_event in [
+            InputEvent::Keyboard(keypress!(@char 'a')),
+            InputEvent::Keyboard(keypress!(@special SpecialKey::Enter)),
+            InputEvent::Keyboard(keypress!(@char 'b')),
+        ] {
+            let args = DialogEngineArgs {
+                shared_global_data,
+                shared_store,
+                state,
+                component_registry,
+                window_size,
+                self_id,
+                dialog_buffer,
+                dialog_engine,
+            };
+            let response = DialogEngineApi::apply_event(args, &input_event)
+                .await
+                .unwrap();
+            match response {
+                DialogEngineApplyResponse::UpdateEditorBuffer(editor_buffer) => {
+                    dialog_buffer.editor_buffer = editor_buffer;
+                }
+                _ => panic!("Expected UpdateEditorBuffer, got {response:?}"),
+            }
+        }
+        assert_eq2!(dialog_buffer.editor_buffer.line_count(), 2);
+
+        // Ctrl+Enter accepts the input, w/ the lines joined by new lines.
+        let args = DialogEngineArgs {
+            shared_global_data,
+            shared_store,
+            state,
+            component_registry,
+            window_size,
+            self_id,
+            dialog_buffer,
+            dialog_engine,
+        };
+        let input_event = InputEvent::Keyboard(
+            keypress!(@special ModifierKeysMask::CTRL, SpecialKey::Enter),
+        );
+        let response = DialogEngineApi::apply_event(args, &input_event)
+            .await
+            .unwrap();
+        assert!(matches!(
+            response,
+            DialogEngineApplyResponse::DialogChoice(DialogChoice::Yes(text)) if text == "a\nb"
+        ));
+    }
+
+    #[tokio::test]
+    async fn apply_event_up_down_at_boundary() {
+        let self_id: FlexBoxId = FlexBoxId::from(0);
+        let window_size = &size!( col_count: 70, row_count: 15 );
+        let dialog_buffer = &mut DialogBuffer::new_empty();
+        dialog_buffer.maybe_results = Some(vec!["a".to_string(), "b".to_string()]);
+        let dialog_engine = &mut mock_real_objects_for_dialog::make_dialog_engine();
+        let shared_store = &mock_real_objects_for_dialog::create_store();
+        let shared_global_data =
+            &test_editor::mock_real_objects_for_editor::make_shared_global_data(
+                (*window_size).into(),
+            );
+        let component_registry =
+            &mut test_editor::mock_real_objects_for_editor::make_component_registry();
+        let state = &shared_store.read().await.state.clone();
+
+        let mut responses = vec![];
+        for input_event in [
+            InputEvent::Keyboard(keypress!(@special SpecialKey::Up)),
+            InputEvent::Keyboard(keypress!(@special SpecialKey::Down)),
+            InputEvent::Keyboard(keypress!(@special SpecialKey::Down)),
+        ] {
+            let args = DialogEngineArgs {
+                shared_global_data,
+                shared_store,
+                state,
+                component_registry,
+                window_size,
+                self_id,
+                dialog_buffer,
+                dialog_engine,
+            };
+            let response = DialogEngineApi::apply_event(args, &input_event)
+                .await
+                .unwrap();
+            responses.push(format!("{response:?}"));
+        }
+
+        // Nothing changes at the first & last results.
+        assert_eq2!(
+            responses,
+            vec!["Unchanged", "SelectScrollResultsPanel", "Unchanged"]
+        );
+        assert_eq2!(dialog_engine.selected_row_index, ch!(1));
+    }
 }
@@ -57,13 +57,36 @@ pub struct DialogEngine {
     pub maybe_surface_bounds: Option<SurfaceBounds>,
     pub selected_row_index: ChUnit,
     pub scroll_offset_row_index: ChUnit,
+    /// Advanced by one frame each time
+    /// [DialogEngineApi::render_engine](DialogEngineApi::render_engine) is called, if
+    /// [DialogEngineConfigOptions::animation] is set.
+    pub animation_progress: DialogAnimationProgress,
+    /// The [Stylesheet] of the active [TuiTheme]. It is replaced each time
+    /// [DialogEngineApi::render_engine](DialogEngineApi::render_engine) is called, so
+    /// that switching themes at runtime takes effect on the next render.
+    pub theme_stylesheet: Stylesheet,
+    /// Called w/ the text of the editor buffer when the input is accepted. If it returns
+    /// an error message, then the dialog box stays open, and
+    /// [DialogEngineApi::apply_event] returns [DialogError::ValidationFailed].
+    #[serde(skip)]
+    pub maybe_input_validator: Option<DialogInputValidator>,
+    /// The last [DialogError::is_input_error] returned by [DialogEngineApi::apply_event].
+    /// It is painted in the bottom border until the next event is applied.
+    pub maybe_input_error: Option<DialogError>,
 }
 
+pub type DialogInputValidator = fn(&str) -> Result<(), String>;
+
 impl DialogEngine {
     pub fn new(
         dialog_options: DialogEngineConfigOptions,
-        editor_options: EditorEngineConfig,
+        mut editor_options: EditorEngineConfig,
     ) -> Self {
+        // The editor has to accept new lines for a multiline dialog.
+        if dialog_options.multiline {
+            editor_options.multiline_mode = LineMode::MultiLine;
+        }
+
         // The col_count has to be large enough to fit the terminal width so that the gradient
         // doesn't flicker. If for some reason the terminal width is not available, then we default
         // to 250.
@@ -103,9 +126,255 @@ impl DialogEngine {
     pub fn reset(&mut self) {
         self.selected_row_index = ch!(0);
         self.scroll_offset_row_index = ch!(0);
+        self.animation_progress = DialogAnimationProgress::NotStarted;
+        self.maybe_input_error = None;
+    }
+
+    /// Index of the result (in [DialogBuffer::maybe_results]) that is highlighted in the
+    /// results panel. Only for [DialogEngineMode::ModalAutocomplete].
+    pub fn get_selected_result_index(&self) -> usize {
+        ch!(@to_usize self.selected_row_index)
+    }
+
+    /// The result that is highlighted in the results panel, if there are any results.
+    pub fn get_selected_result<'a>(
+        &self,
+        dialog_buffer: &'a DialogBuffer,
+    ) -> Option<&'a str> {
+        dialog_buffer.get_result(self.get_selected_result_index())
+    }
+
+    /// Highlight the result at `index`, eg: to preselect a result when the dialog box is
+    /// opened (call it after [reset](DialogEngine::reset)). It is clamped to the results
+    /// (and scrolled into view) on the next render, so it can be called before the
+    /// results are in the [DialogBuffer].
+    pub fn set_selected_result_index(&mut self, index: usize) {
+        self.selected_row_index = ch!(index);
+    }
+
+    /// Keep the selection in range when the results change, and make sure that it is
+    /// visible in the results panel.
+    pub fn clamp_selected_result_index(&mut self, results_count: ChUnit) {
+        let results_count = ch!(@to_usize results_count);
+        let viewport_height = ch!(@to_usize self.get_results_panel_row_count()).max(1);
+
+        let selected_index = std::cmp::min(
+            self.get_selected_result_index(),
+            results_count.saturating_sub(1),
+        );
+        let mut scroll_offset = std::cmp::min(
+            ch!(@to_usize self.scroll_offset_row_index),
+            results_count.saturating_sub(viewport_height),
+        );
+        if selected_index < scroll_offset {
+            scroll_offset = selected_index;
+        } else if selected_index >= scroll_offset + viewport_height {
+            scroll_offset = selected_index + 1 - viewport_height;
+        }
+
+        self.selected_row_index = ch!(selected_index);
+        self.scroll_offset_row_index = ch!(scroll_offset);
+    }
+
+    /// Number of results that are shown in the results panel. This is
+    /// [result_panel_display_row_count](DialogEngineConfigOptions::result_panel_display_row_count),
+    /// unless the [height](DialogEngineConfigOptions::maybe_height) of the dialog box is
+    /// set, in which case the results panel fills the rows below the editor.
+    pub fn get_results_panel_row_count(&self) -> ChUnit {
+        match (self.dialog_options.maybe_height, self.maybe_flex_box) {
+            (Some(_), Some((_, _, flex_box))) => {
+                flex_box.style_adjusted_bounds_size.row_count
+                    - ch!(DisplayConstants::SimpleModalRowCount as u16)
+                    - ch!(DisplayConstants::EmptyLine as u16)
+            }
+            _ => self.dialog_options.result_panel_display_row_count,
+        }
+    }
+
+    /// The styles in [DialogEngineConfigOptions] (if any) take precedence over the ones
+    /// in the active [TuiTheme].
+    fn get_style(
+        &self,
+        maybe_style_override: Option<Style>,
+        id: ThemeStyleId,
+    ) -> Option<Style> {
+        maybe_style_override.or_else(|| self.theme_stylesheet.find_style_by_id(id as u8))
+    }
+
+    pub fn get_style_border(&self) -> Option<Style> {
+        self.get_style(
+            self.dialog_options.maybe_style_border,
+            ThemeStyleId::DialogBorder,
+        )
+    }
+
+    pub fn get_style_title(&self) -> Option<Style> {
+        self.get_style(
+            self.dialog_options.maybe_style_title,
+            ThemeStyleId::DialogTitle,
+        )
+    }
+
+    pub fn get_style_editor(&self) -> Option<Style> {
+        self.get_style(
+            self.dialog_options.maybe_style_editor,
+            ThemeStyleId::DialogEditor,
+        )
+    }
+
+    pub fn get_style_results_panel(&self) -> Option<Style> {
+        self.get_style(
+            self.dialog_options.maybe_style_results_panel,
+            ThemeStyleId::DialogResultsPanel,
+        )
+    }
+
+    pub fn get_style_border_title(&self) -> Option<Style> {
+        self.get_style(
+            self.dialog_options.maybe_style_border_title,
+            ThemeStyleId::DialogBorderTitle,
+        )
+    }
+
+    pub fn get_style_results_panel_match(&self) -> Option<Style> {
+        self.get_style(
+            self.dialog_options.maybe_style_results_panel_match,
+            ThemeStyleId::DialogResultsPanelMatch,
+        )
+    }
+
+    /// Returns [None] if there is no animation, or if
+    /// [reduce_motion](DialogEngineConfigOptions::reduce_motion) is enabled.
+    pub fn get_animation(&self) -> Option<DialogAnimation> {
+        if self.dialog_options.reduce_motion {
+            None
+        } else {
+            self.dialog_options.animation
+        }
+    }
+
+    /// Input is ignored while this is `true`.
+    pub fn is_animating(&self) -> bool {
+        self.get_animation().is_some()
+            && !matches!(self.animation_progress, DialogAnimationProgress::Settled)
+    }
+
+    /// Once the close animation is done, the [DialogChoice] that started it is returned
+    /// (only once) so that it can be handed to the app.
+    pub fn take_closed_dialog_choice(&mut self) -> Option<DialogChoice> {
+        match std::mem::take(&mut self.animation_progress) {
+            DialogAnimationProgress::Closed(dialog_choice) => {
+                self.reset();
+                Some(dialog_choice)
+            }
+            it => {
+                self.animation_progress = it;
+                None
+            }
+        }
+    }
+}
+
+/// How the dialog box is animated when it is opened or closed. Each frame is painted by a
+/// render, so `frame_count` renders are needed for the dialog box to settle.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DialogAnimation {
+    /// Grow from the center of the dialog box to its full size (and shrink back on close).
+    Grow { frame_count: u16 },
+    /// Slide down from the top of the surface to the dialog box's position (and slide
+    /// back up on close).
+    SlideIn { frame_count: u16 },
+}
+
+impl DialogAnimation {
+    pub fn get_frame_count(&self) -> u16 {
+        match self {
+            DialogAnimation::Grow { frame_count } => (*frame_count).max(1),
+            DialogAnimation::SlideIn { frame_count } => (*frame_count).max(1),
+        }
     }
 }
 
+/// The width or height of the dialog box, eg: a narrow command input, or a large file
+/// picker. The dialog box is always clamped to fit in the surface (or the window if there
+/// is no surface).
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DialogDimension {
+    /// Percent of the width or height of the surface.
+    Percent(Percent),
+    /// Number of cols or rows.
+    Fixed(ChUnit),
+}
+
+impl DialogDimension {
+    /// Returns the number of cols or rows, given the `surface_count` (its width or
+    /// height).
+    pub fn resolve(&self, surface_count: ChUnit) -> ChUnit {
+        match self {
+            DialogDimension::Percent(percent) => percent.calc_percentage(surface_count),
+            DialogDimension::Fixed(count) => *count,
+        }
+    }
+}
+
+/// Where the dialog box is placed in the surface (or the window if there is no surface).
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DialogAnchor {
+    #[default]
+    Center,
+    /// At the top of the surface, centered horizontally.
+    Top,
+    /// At the bottom of the surface, centered horizontally.
+    Bottom,
+    /// At the given position, which is relative to the origin of the surface.
+    At(Position),
+}
+
+impl DialogAnchor {
+    /// Returns the origin of a dialog box that is `bounds_size` big. It might stick out of
+    /// the surface, in which case the dialog box is moved back in when it is rendered.
+    pub fn get_origin_pos(
+        &self,
+        bounds_size: Size,
+        surface_origin_pos: Position,
+        surface_size: Size,
+    ) -> Position {
+        let center_col_index = surface_size.col_count / 2 - bounds_size.col_count / 2;
+        let mut it = match self {
+            DialogAnchor::Center => position!(
+                col_index: center_col_index,
+                row_index: surface_size.row_count / 2 - bounds_size.row_count / 2
+            ),
+            DialogAnchor::Top => position!(col_index: center_col_index, row_index: 0),
+            DialogAnchor::Bottom => position!(
+                col_index: center_col_index,
+                row_index: surface_size.row_count - bounds_size.row_count
+            ),
+            DialogAnchor::At(position) => *position,
+        };
+        it += surface_origin_pos;
+        it
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DialogAnimationProgress {
+    /// The dialog hasn't been rendered since it was [reset](DialogEngine::reset).
+    #[default]
+    NotStarted,
+    Opening {
+        frame_index: u16,
+    },
+    Settled,
+    Closing {
+        frame_index: u16,
+        dialog_choice: DialogChoice,
+    },
+    /// The close animation is done. More info in
+    /// [take_closed_dialog_choice](DialogEngine::take_closed_dialog_choice).
+    Closed(DialogChoice),
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct DialogEngineConfigOptions {
     pub mode: DialogEngineMode,
@@ -115,6 +384,63 @@ pub struct DialogEngineConfigOptions {
     pub maybe_style_title: Option<Style>,
     pub maybe_style_editor: Option<Style>,
     pub maybe_style_results_panel: Option<Style>,
+    /// Painted in the top border of the dialog box (truncated w/ an ellipsis if it doesn't
+    /// fit). This is different from [DialogBuffer::title] which is painted inside the box.
+    pub title: Option<String>,
+    /// Paint a [DIALOG_CLOSE_HINT] at the right end of the top border. Clicking on it
+    /// is the same as pressing <kbd>Esc</kbd>, ie: it results in [DialogChoice::No].
+    pub show_close_hint: bool,
+    /// Used for [title](DialogEngineConfigOptions::title) and the close hint.
+    pub maybe_style_border_title: Option<Style>,
+    /// Animate the dialog box when it is opened & closed. Input is ignored until the
+    /// animation is done.
+    pub animation: Option<DialogAnimation>,
+    /// Disables [animation](DialogEngineConfigOptions::animation) (eg: for users who
+    /// prefer reduced motion).
+    pub reduce_motion: bool,
+    /// Only for [DialogEngineMode::ModalSimple]. Turns the editor into a text area that
+    /// is [multiline_row_count](DialogEngineConfigOptions::multiline_row_count) rows
+    /// tall (and scrolls vertically). <kbd>Enter</kbd> inserts a new line, and
+    /// <kbd>Ctrl+Enter</kbd> accepts the input, which is returned in [DialogChoice::Yes]
+    /// w/ the lines joined by `\n`.
+    pub multiline: bool,
+    pub multiline_row_count: ChUnit,
+    /// Only for [DialogEngineMode::ModalAutocomplete]. The graphemes of each result that
+    /// fuzzy match the text in the editor are painted w/
+    /// [maybe_style_results_panel_match](DialogEngineConfigOptions::maybe_style_results_panel_match)
+    /// (bold if there is none). More info in [fuzzy_match()].
+    pub highlight_matches: bool,
+    pub maybe_style_results_panel_match: Option<Style>,
+    /// Only for [DialogEngineMode::ModalSimple]. Don't accept empty (or whitespace only)
+    /// input, and return [DialogError::EmptyBuffer] instead.
+    pub reject_empty_input: bool,
+    /// If the window is smaller than this, then [DIALOG_WINDOW_TOO_SMALL_MSG] is painted
+    /// instead of the dialog box. The default is [MinSize].
+    pub min_size: Size,
+    /// The width of the dialog box. When it is [None], the dialog box is
+    /// [DisplayConstants::DialogComponentBorderWidthPercent] of the surface wide.
+    pub maybe_width: Option<DialogDimension>,
+    /// The height of the dialog box. When it is [None], the dialog box is just tall
+    /// enough for its content. Otherwise the editor (when it is
+    /// [multiline](DialogEngineConfigOptions::multiline)) or the results panel fills
+    /// the rows that are left.
+    pub maybe_height: Option<DialogDimension>,
+    /// Where the dialog box is placed in the surface.
+    pub anchor: DialogAnchor,
+}
+
+/// Builds a [DialogEngineConfigOptions], starting from its [Default], so that only the
+/// options that differ from it have to be set, eg:
+///
+/// ```ignore
+/// let config_options = DialogEngineConfigOptions::builder()
+///     .set_mode(DialogEngineMode::ModalAutocomplete)
+///     .set_title(Some("Search".to_string()))
+///     .build();
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DialogEngineConfigOptionsBuilder {
+    config_options: DialogEngineConfigOptions,
 }
 
 mod dialog_engine_config_options_impl {
@@ -131,13 +457,212 @@ mod dialog_engine_config_options_impl {
                 maybe_style_editor: None,
                 maybe_style_title: None,
                 maybe_style_results_panel: None,
+                title: None,
+                show_close_hint: false,
+                maybe_style_border_title: None,
+                animation: None,
+                reduce_motion: false,
+                multiline: false,
+                multiline_row_count: ch!(DEFAULT_DIALOG_MULTILINE_ROW_COUNT),
+                highlight_matches: true,
+                maybe_style_results_panel_match: None,
+                reject_empty_input: false,
+                min_size: size!(
+                    col_count: MinSize::Col as u8,
+                    row_count: MinSize::Row as u8
+                ),
+                maybe_width: None,
+                maybe_height: None,
+                anchor: DialogAnchor::Center,
+            }
+        }
+    }
+
+    impl DialogEngineConfigOptions {
+        pub fn is_multiline(&self) -> bool {
+            self.multiline && self.mode == DialogEngineMode::ModalSimple
+        }
+
+        /// Number of rows that the editor takes up in the dialog box.
+        pub fn get_editor_row_count(&self) -> ChUnit {
+            if self.is_multiline() {
+                std::cmp::max(self.multiline_row_count, ch!(1))
+            } else {
+                ch!(1)
             }
         }
     }
 }
 
+mod dialog_engine_config_options_builder_impl {
+    use super::*;
+
+    impl DialogEngineConfigOptions {
+        pub fn builder() -> DialogEngineConfigOptionsBuilder {
+            DialogEngineConfigOptionsBuilder::new()
+        }
+    }
+
+    impl DialogEngineConfigOptionsBuilder {
+        pub fn new() -> Self { Self::default() }
+
+        pub fn set_mode(mut self, mode: DialogEngineMode) -> Self {
+            self.config_options.mode = mode;
+            self
+        }
+
+        pub fn set_result_panel_display_row_count(
+            mut self,
+            result_panel_display_row_count: ChUnit,
+        ) -> Self {
+            self.config_options.result_panel_display_row_count =
+                result_panel_display_row_count;
+            self
+        }
+
+        pub fn set_maybe_style_border(
+            mut self,
+            maybe_style_border: Option<Style>,
+        ) -> Self {
+            self.config_options.maybe_style_border = maybe_style_border;
+            self
+        }
+
+        pub fn set_maybe_style_title(mut self, maybe_style_title: Option<Style>) -> Self {
+            self.config_options.maybe_style_title = maybe_style_title;
+            self
+        }
+
+        pub fn set_maybe_style_editor(
+            mut self,
+            maybe_style_editor: Option<Style>,
+        ) -> Self {
+            self.config_options.maybe_style_editor = maybe_style_editor;
+            self
+        }
+
+        pub fn set_maybe_style_results_panel(
+            mut self,
+            maybe_style_results_panel: Option<Style>,
+        ) -> Self {
+            self.config_options.maybe_style_results_panel = maybe_style_results_panel;
+            self
+        }
+
+        pub fn set_title(mut self, title: Option<String>) -> Self {
+            self.config_options.title = title;
+            self
+        }
+
+        pub fn set_show_close_hint(mut self, show_close_hint: bool) -> Self {
+            self.config_options.show_close_hint = show_close_hint;
+            self
+        }
+
+        pub fn set_maybe_style_border_title(
+            mut self,
+            maybe_style_border_title: Option<Style>,
+        ) -> Self {
+            self.config_options.maybe_style_border_title = maybe_style_border_title;
+            self
+        }
+
+        pub fn set_animation(mut self, animation: Option<DialogAnimation>) -> Self {
+            self.config_options.animation = animation;
+            self
+        }
+
+        pub fn set_reduce_motion(mut self, reduce_motion: bool) -> Self {
+            self.config_options.reduce_motion = reduce_motion;
+            self
+        }
+
+        pub fn set_multiline(mut self, multiline: bool) -> Self {
+            self.config_options.multiline = multiline;
+            self
+        }
+
+        pub fn set_multiline_row_count(mut self, multiline_row_count: ChUnit) -> Self {
+            self.config_options.multiline_row_count = multiline_row_count;
+            self
+        }
+
+        pub fn set_highlight_matches(mut self, highlight_matches: bool) -> Self {
+            self.config_options.highlight_matches = highlight_matches;
+            self
+        }
+
+        pub fn set_maybe_style_results_panel_match(
+            mut self,
+            maybe_style_results_panel_match: Option<Style>,
+        ) -> Self {
+            self.config_options.maybe_style_results_panel_match =
+                maybe_style_results_panel_match;
+            self
+        }
+
+        pub fn set_reject_empty_input(mut self, reject_empty_input: bool) -> Self {
+            self.config_options.reject_empty_input = reject_empty_input;
+            self
+        }
+
+        pub fn set_min_size(mut self, min_size: Size) -> Self {
+            self.config_options.min_size = min_size;
+            self
+        }
+
+        pub fn set_maybe_width(mut self, maybe_width: Option<DialogDimension>) -> Self {
+            self.config_options.maybe_width = maybe_width;
+            self
+        }
+
+        pub fn set_maybe_height(mut self, maybe_height: Option<DialogDimension>) -> Self {
+            self.config_options.maybe_height = maybe_height;
+            self
+        }
+
+        pub fn set_anchor(mut self, anchor: DialogAnchor) -> Self {
+            self.config_options.anchor = anchor;
+            self
+        }
+
+        pub fn build(self) -> DialogEngineConfigOptions { self.config_options }
+    }
+}
+
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum DialogEngineMode {
     ModalSimple,
     ModalAutocomplete,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_config_options_builder() {
+        // Nothing set, same as default.
+        assert_eq2!(
+            DialogEngineConfigOptions::builder().build(),
+            DialogEngineConfigOptions::default()
+        );
+
+        // Only the options that are set differ from the default.
+        let config_options = DialogEngineConfigOptions::builder()
+            .set_multiline(true)
+            .set_multiline_row_count(ch!(3))
+            .set_title(Some("Notes".to_string()))
+            .build();
+        assert_eq2!(
+            config_options,
+            DialogEngineConfigOptions {
+                multiline: true,
+                multiline_row_count: ch!(3),
+                title: Some("Notes".to_string()),
+                ..Default::default()
+            }
+        );
+        assert_eq2!(config_options.get_editor_row_count(), ch!(3));
+    }
+}
@@ -0,0 +1,115 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::{error::Error,
+          fmt::{Display, Result}};
+
+use r3bl_rs_utils_core::*;
+use serde::*;
+
+use crate::*;
+
+/// Result type of [DialogEngineApi::apply_event]. Use `?` to turn a [DialogError] into
+/// a [CommonResult] error, or [CommonError::from] to convert it explicitly.
+pub type DialogResult<T> = std::result::Result<T, DialogError>;
+
+/// The ways in which [DialogEngineApi::apply_event] can fail. Callers can match on these
+/// to decide what to show the user.
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DialogError {
+    /// A mouse event arrived before the dialog box was rendered, so there are no bounds
+    /// to map it to.
+    NoSurfaceBounds,
+    /// The input was accepted while the editor buffer was empty, and
+    /// [DialogEngineConfigOptions::reject_empty_input] is set.
+    EmptyBuffer,
+    /// The input was rejected by [DialogEngine::maybe_input_validator], w/ its message.
+    ValidationFailed(String),
+    /// The [EditorEngineApi] could not apply the event, w/ the message of its error.
+    EditorEngineFailed(String),
+}
+
+/// Implement [`Error`] trait.
+impl Error for DialogError {}
+
+/// Implement [`Display`] trait (needed by [`Error`] trait). These messages are meant to be
+/// shown to the user.
+impl Display for DialogError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result {
+        match self {
+            DialogError::NoSurfaceBounds => write!(f, "Dialog has not been rendered yet"),
+            DialogError::EmptyBuffer => write!(f, "Input can't be empty"),
+            DialogError::ValidationFailed(msg) => write!(f, "{msg}"),
+            DialogError::EditorEngineFailed(msg) => write!(f, "Editor error: {msg}"),
+        }
+    }
+}
+
+impl DialogError {
+    /// Returns `true` if the user's input was rejected, & they can fix it & try again. In
+    /// this case the dialog box stays open and paints the error.
+    pub fn is_input_error(&self) -> bool {
+        matches!(
+            self,
+            DialogError::EmptyBuffer | DialogError::ValidationFailed(_)
+        )
+    }
+
+    pub fn get_common_error_type(&self) -> CommonErrorType {
+        match self {
+            DialogError::NoSurfaceBounds => CommonErrorType::InvalidState,
+            DialogError::EmptyBuffer => CommonErrorType::InvalidValue,
+            DialogError::ValidationFailed(_) => CommonErrorType::InvalidValue,
+            DialogError::EditorEngineFailed(_) => CommonErrorType::General,
+        }
+    }
+}
+
+impl From<DialogError> for CommonError {
+    fn from(dialog_error: DialogError) -> Self {
+        CommonError {
+            err_type: dialog_error.get_common_error_type(),
+            err_msg: Some(dialog_error.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dialog_error_into_common_error() {
+        let common_error: CommonError =
+            DialogError::ValidationFailed("Name is taken".into()).into();
+        assert!(matches!(
+            common_error.err_type,
+            CommonErrorType::InvalidValue
+        ));
+        assert_eq2!(common_error.err_msg, Some("Name is taken".to_string()));
+
+        let common_error: CommonError = DialogError::NoSurfaceBounds.into();
+        assert!(matches!(
+            common_error.err_type,
+            CommonErrorType::InvalidState
+        ));
+
+        assert!(DialogError::EmptyBuffer.is_input_error());
+        assert!(!DialogError::NoSurfaceBounds.is_input_error());
+    }
+}
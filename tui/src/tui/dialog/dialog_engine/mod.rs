@@ -18,7 +18,9 @@
 // Attach.
 pub mod dialog_engine_api;
 pub mod dialog_engine_struct;
+pub mod dialog_error;
 
 // Re-export.
 pub use dialog_engine_api::*;
 pub use dialog_engine_struct::*;
+pub use dialog_error::*;
@@ -15,7 +15,9 @@
  *   limitations under the License.
  */
 
-use std::fmt::{Debug, Formatter, Result};
+use std::{collections::HashMap,
+          fmt::{Debug, Formatter, Result},
+          ops::RangeInclusive};
 
 use get_size::GetSize;
 use r3bl_rs_utils_core::*;
@@ -165,13 +167,64 @@ use crate::*;
 /// in the map represents a row of text in the buffer.
 /// - The row index is the key.
 /// - The value is the [SelectionRange].
+///
+/// ## `fold_map`
+///
+/// The [FoldMap] is used to keep track of the folded regions in the buffer. This is only
+/// used for display and caret navigation, it does not change `lines`.
+///
+/// ## `marks`
+///
+/// Named positions (eg: `a`) in the buffer that the caret can jump back to. The
+/// positions are [scroll adjusted](CaretKind::ScrollAdjusted). They are kept in sync w/
+/// lines that are inserted or removed above them by
+/// [EditorEngineInternalApi::shift_marks_after_edit], and marks on lines that are removed
+/// are dropped.
+///
+/// ## `secondary_carets`
+///
+/// Extra carets (in addition to the primary one, which is `caret_display_position`) that
+/// text is inserted at and deleted from at the same time as the primary caret. Unlike the
+/// primary caret, these positions are [scroll adjusted](CaretKind::ScrollAdjusted).
+///
+/// ## `line_ending`
+///
+/// The [LineEnding] that is used the most in the content that was loaded by
+/// [load_content](EditorBuffer::load_content), eg: to display `CRLF` in a status bar. New
+/// lines get this line ending when the buffer is saved.
+///
+/// ## `maybe_mixed_line_breaks`
+///
+/// Only set if the loaded content has mixed line endings. It holds the line break after
+/// each line, so that [get_content_for_save](EditorBuffer::get_content_for_save) can
+/// write them back unchanged. It is kept in sync w/ lines that are inserted or removed by
+/// [EditorEngineInternalApi::shift_marks_after_edit], and is cleared by
+/// [convert_line_endings](EditorBuffer::convert_line_endings).
+///
+/// ## `is_dirty`
+///
+/// Set when an [EditorEvent] that changes the content is applied to the buffer, and
+/// cleared by [set_lines](EditorBuffer::set_lines) (eg: when a file is loaded) or by
+/// [mark_clean](EditorBuffer::mark_clean) (eg: after the app saves the buffer).
+///
+/// ## `maybe_snippet_session`
+///
+/// Set by [insert_snippet](EditorBuffer::insert_snippet) while the caret moves between
+/// the tab stops of the snippet. More info in [SnippetSession].
 #[derive(Clone, PartialEq, Serialize, Deserialize, GetSize)]
 pub struct EditorBuffer {
-    lines: Vec<UnicodeString>,
+    lines: EditorLines,
     caret_display_position: Position,
     scroll_offset: ScrollOffset,
     maybe_file_extension: Option<String>,
     selection_map: SelectionMap,
+    fold_map: FoldMap,
+    marks: HashMap<char, Position>,
+    secondary_carets: Vec<Position>,
+    line_ending: LineEnding,
+    maybe_mixed_line_breaks: Option<Vec<LineEnding>>,
+    is_dirty: bool,
+    maybe_snippet_session: Option<SnippetSession>,
 }
 
 mod constructor {
@@ -190,11 +243,18 @@ mod constructor {
             });
 
             Self {
-                lines: vec![UnicodeString::default()],
+                lines: EditorLines::from(vec![UnicodeString::default()]),
                 caret_display_position: Position::default(),
                 scroll_offset: ScrollOffset::default(),
                 maybe_file_extension: file_extension.map(|s| s.to_string()),
                 selection_map: Default::default(),
+                fold_map: Default::default(),
+                marks: Default::default(),
+                secondary_carets: Default::default(),
+                line_ending: Default::default(),
+                maybe_mixed_line_breaks: None,
+                is_dirty: false,
+                maybe_snippet_session: None,
             }
         }
     }
@@ -230,7 +290,7 @@ pub mod access_and_mutate {
             }
         }
 
-        pub fn get_lines(&self) -> &Vec<UnicodeString> { &self.lines }
+        pub fn get_lines(&self) -> &EditorLines { &self.lines }
 
         pub fn get_as_string(&self) -> String {
             self.get_lines()
@@ -240,6 +300,16 @@ pub mod access_and_mutate {
                 .join(", ")
         }
 
+        /// Unlike [get_as_string](EditorBuffer::get_as_string), the lines are joined w/
+        /// `\n`, ie: this is the text exactly as the user typed it.
+        pub fn get_as_string_with_newlines(&self) -> String {
+            self.get_lines()
+                .iter()
+                .map(|l| l.string.clone())
+                .collect::<Vec<String>>()
+                .join("\n")
+        }
+
         pub fn set_lines(&mut self, lines: Vec<String>) {
             // Set lines.
             self.lines = lines.into_iter().map(UnicodeString::from).collect();
@@ -247,8 +317,46 @@ pub mod access_and_mutate {
             self.caret_display_position = Position::default();
             // Reset scroll_offset.
             self.scroll_offset = ScrollOffset::default();
+            // Reset folds.
+            self.fold_map.clear();
+            // Reset marks.
+            self.marks.clear();
+            // Reset secondary carets.
+            self.secondary_carets.clear();
+            // End the snippet session.
+            self.maybe_snippet_session = None;
+            // Freshly loaded content has no unsaved changes.
+            self.is_dirty = false;
+        }
+
+        /// Returns the 1 based `(line, column)` of the caret, eg: to display `Ln 12, Col 4`
+        /// in a status bar. The column is a display column, so wide characters (eg: emoji)
+        /// count as more than one column.
+        pub fn caret_display_position(&self) -> (usize, usize) {
+            let caret = self.get_caret(CaretKind::ScrollAdjusted);
+            (
+                ch!(@to_usize caret.row_index) + 1,
+                ch!(@to_usize caret.col_index) + 1,
+            )
         }
 
+        pub fn line_count(&self) -> usize { self.lines.len() }
+
+        /// Number of grapheme clusters in the buffer, not counting line breaks. This counts
+        /// the segments that are cached in each [UnicodeString], so it doesn't re-scan the
+        /// text.
+        pub fn char_count(&self) -> usize {
+            self.lines.iter().map(|line| line.len()).sum()
+        }
+
+        /// Returns `true` if the content has changed since it was loaded or last saved.
+        pub fn is_dirty(&self) -> bool { self.is_dirty }
+
+        pub fn mark_dirty(&mut self) { self.is_dirty = true; }
+
+        /// Call this after the buffer has been persisted.
+        pub fn mark_clean(&mut self) { self.is_dirty = false; }
+
         /// Returns the current caret position in two variants:
         /// 1. [CaretKind::Raw] -> The raw caret position not adjusted for scrolling.
         /// 2. [CaretKind::ScrollAdjusted] -> The caret position adjusted for scrolling using
@@ -283,8 +391,106 @@ pub mod access_and_mutate {
 
         pub fn get_scroll_offset(&self) -> ScrollOffset { self.scroll_offset }
 
+        /// The largest scroll offset row index for the given `viewport_height`, ie: when
+        /// the last line is at the bottom of the viewport, or `overscroll` rows above it.
+        /// The `overscroll` is capped so that the last line stays in the viewport. More
+        /// info in [EditorEngineConfig::overscroll].
+        pub fn get_max_scroll_row_index(
+            &self,
+            viewport_height: ChUnit,
+            overscroll: usize,
+        ) -> ChUnit {
+            let overscroll = overscroll.min(ch!(@to_usize viewport_height - 1));
+            self.len() + ch!(overscroll) - viewport_height
+        }
+
+        /// Scroll so that the given [scroll adjusted](CaretKind::ScrollAdjusted)
+        /// `row_index` & `col_index` are at the top left of the `viewport` (the size of the
+        /// content area of the editor), eg: to scroll 2 editors together.
+        /// - The offset is clamped so that it doesn't scroll past the end of the buffer
+        ///   (plus the `overscroll` rows, see [EditorEngineConfig::overscroll]) or of its
+        ///   widest line.
+        /// - The caret stays on the same text if it is still in the viewport, otherwise it
+        ///   is moved to the nearest row (& col) in it. This way the caret following logic
+        ///   (that runs when the next [EditorEvent] is applied) doesn't scroll back to it.
+        ///   If the caret row is shorter than the col offset, then the col offset is
+        ///   reduced so that the caret (at the end of its row) is in the viewport.
+        ///
+        /// The col offset is moved right (if needed) so that the caret row doesn't start
+        /// in the middle of a wide grapheme cluster.
+        ///
+        /// [EditorEngineConfig::scroll_off] (&
+        /// [side_scroll_off](EditorEngineConfig::side_scroll_off)) are not applied here,
+        /// so the caret can end up in the margin. The margin is applied by the next
+        /// [EditorEvent] that moves the caret or changes the content, which can then scroll
+        /// the viewport by up to that many rows (or cols). To keep the offset exactly as
+        /// it is set, keep the caret out of the margin.
+        pub fn set_scroll_offset(
+            &mut self,
+            row_index: ChUnit,
+            col_index: ChUnit,
+            viewport: Size,
+            overscroll: usize,
+        ) {
+            if self.lines.is_empty() {
+                return;
+            }
+
+            let max_scroll_row_index =
+                self.get_max_scroll_row_index(viewport.row_count, overscroll);
+            let widest_line_display_width = self
+                .lines
+                .iter()
+                .map(|line| line.display_width)
+                .max()
+                .unwrap_or_default();
+            // The caret can be just past the end of the line.
+            let max_scroll_col_index = widest_line_display_width + 1 - viewport.col_count;
+            let mut scroll_offset = position! {
+                col_index: col_index.min(max_scroll_col_index),
+                row_index: row_index.min(max_scroll_row_index)
+            };
+
+            // Move the caret into the viewport.
+            let caret = self.get_caret(CaretKind::ScrollAdjusted);
+            let caret_row_index = caret
+                .row_index
+                .max(scroll_offset.row_index)
+                .min(scroll_offset.row_index + viewport.row_count - 1)
+                .min(self.len() - 1);
+            let caret_line = &self.lines[ch!(@to_usize caret_row_index)];
+
+            // Don't start painting the caret row in the middle of a wide grapheme cluster.
+            if let Some(segment) = caret_line
+                .is_display_col_index_in_middle_of_grapheme_cluster(
+                    scroll_offset.col_index,
+                )
+            {
+                scroll_offset.col_index =
+                    segment.display_col_offset + segment.unicode_width;
+            }
+
+            let mut caret_col_index = caret
+                .col_index
+                .max(scroll_offset.col_index)
+                .min(scroll_offset.col_index + viewport.col_count - 1)
+                .min(caret_line.display_width);
+            if let Some(segment) = caret_line
+                .is_display_col_index_in_middle_of_grapheme_cluster(caret_col_index)
+            {
+                caret_col_index = segment.display_col_offset;
+            }
+            scroll_offset.col_index = scroll_offset.col_index.min(caret_col_index);
+
+            self.scroll_offset = scroll_offset;
+            self.caret_display_position = position! {
+                col_index: caret_col_index - scroll_offset.col_index,
+                row_index: caret_row_index - scroll_offset.row_index
+            };
+        }
+
         /// Returns:
-        /// 1. /* lines */ &mut `Vec<UnicodeString>`,
+        /// 1. /* lines */ &mut [EditorLines],
         /// 2. /* caret */ &mut Position,
         /// 3. /* scroll_offset */ &mut ScrollOffset,
         ///
@@ -295,7 +501,7 @@ pub mod access_and_mutate {
         pub fn get_mut(
             &mut self,
         ) -> (
-            /* lines */ &mut Vec<UnicodeString>,
+            /* lines */ &mut EditorLines,
             /* caret */ &mut Position,
             /* scroll_offset */ &mut ScrollOffset,
             /* selection_map */ &mut SelectionMap,
@@ -313,8 +519,1740 @@ pub mod access_and_mutate {
         pub fn clear_selection(&mut self) { self.selection_map.clear(); }
 
         pub fn get_selection_map(&self) -> &SelectionMap { &self.selection_map }
+
+        pub fn get_fold_map(&self) -> &FoldMap { &self.fold_map }
+
+        pub fn get_fold_map_mut(&mut self) -> &mut FoldMap { &mut self.fold_map }
+
+        /// Collapse the given `range` of rows. See [FoldMap::fold] for when this returns
+        /// `false`.
+        pub fn fold(&mut self, range: FoldRange) -> bool {
+            let max_row_index = ch!(self.lines.len(), @dec);
+            if range.end_row_index > max_row_index {
+                return false;
+            }
+            self.fold_map.fold(range)
+        }
+
+        /// Expand the innermost fold that contains `row_index`.
+        pub fn unfold(&mut self, row_index: ChUnit) -> bool {
+            self.fold_map.unfold(row_index)
+        }
+
+        /// Move the caret to the first non-whitespace column of `line_number` (which is 1
+        /// based) & scroll so that this line is in the center of the given `viewport`.
+        /// - Out of range line numbers are clamped to the first or last line.
+        /// - Any folds that hide the line are expanded.
+        pub fn goto_line(&mut self, line_number: usize, viewport: Size) {
+            if self.lines.is_empty() {
+                return;
+            }
+
+            let row_index = ch!(line_number.clamp(1, self.lines.len()) - 1);
+            if self.fold_map.get_fold_hiding_row(row_index).is_some() {
+                self.fold_map.unfold_all_containing(row_index);
+            }
+            self.selection_map.clear();
+
+            let first_non_whitespace_col_index = self.lines[ch!(@to_usize row_index)]
+                .vec_segment
+                .iter()
+                .take_while(|segment| segment.string.trim().is_empty())
+                .fold(ch!(0), |acc, segment| acc + segment.unicode_width);
+
+            self.center_caret_at(row_index, first_non_whitespace_col_index, viewport);
+        }
+
+        /// Scroll vertically so that the caret row is in the center of the given
+        /// `viewport` (like Vim's `zz`), w/out scrolling past the end of the buffer (plus
+        /// the `overscroll` rows). The caret stays on the same text.
+        pub fn recenter_caret(&mut self, viewport: Size, overscroll: usize) {
+            if self.lines.is_empty() {
+                return;
+            }
+            let row_index = self.get_caret(CaretKind::ScrollAdjusted).row_index;
+            let max_scroll_row_index =
+                self.get_max_scroll_row_index(viewport.row_count, overscroll);
+            let scroll_row_index =
+                (row_index - viewport.row_count / 2).min(max_scroll_row_index);
+            self.scroll_offset.row_index = scroll_row_index;
+            self.caret_display_position.row_index = row_index - scroll_row_index;
+        }
+
+        pub fn has_secondary_carets(&self) -> bool { !self.secondary_carets.is_empty() }
+
+        pub fn get_secondary_carets(&self) -> &Vec<Position> { &self.secondary_carets }
+
+        /// The positions must be [scroll adjusted](CaretKind::ScrollAdjusted). Positions
+        /// that are the same as the primary caret (or each other) are dropped.
+        pub fn set_secondary_carets(&mut self, secondary_carets: Vec<Position>) {
+            let primary_caret = self.get_caret(CaretKind::ScrollAdjusted);
+            let mut it = secondary_carets;
+            it.retain(|caret| *caret != primary_caret);
+            it.sort_by_key(|caret| (caret.row_index, caret.col_index));
+            it.dedup();
+            self.secondary_carets = it;
+        }
+
+        /// Collapse back to just the primary caret.
+        pub fn clear_secondary_carets(&mut self) { self.secondary_carets.clear(); }
+
+        /// Returns all the marks, so that they can be displayed (eg: in a marks panel).
+        pub fn get_marks(&self) -> &HashMap<char, Position> { &self.marks }
+
+        pub fn get_marks_mut(&mut self) -> &mut HashMap<char, Position> {
+            &mut self.marks
+        }
+
+        /// Save the [scroll adjusted](CaretKind::ScrollAdjusted) `position` as the mark
+        /// named `name`. An existing mark w/ the same name is replaced.
+        pub fn set_mark(&mut self, name: char, position: Position) {
+            self.marks.insert(name, position);
+        }
+
+        /// Move the caret to the mark named `name` & center it in the given `viewport`.
+        /// Returns `false` if there is no such mark.
+        pub fn goto_mark(&mut self, name: char, viewport: Size) -> bool {
+            let Some(position) = self.marks.get(&name).copied() else {
+                return false;
+            };
+            if self.lines.is_empty() {
+                return false;
+            }
+
+            let row_index = position.row_index.min(self.len() - 1);
+            let col_index = position
+                .col_index
+                .min(self.get_line_display_width(row_index));
+            if self.fold_map.get_fold_hiding_row(row_index).is_some() {
+                self.fold_map.unfold_all_containing(row_index);
+            }
+            self.selection_map.clear();
+
+            self.center_caret_at(row_index, col_index, viewport);
+            true
+        }
+
+        /// Replace all the lines w/ `content` (which is split on newlines), eg: to open a
+        /// file or to format the document from app code. Unlike
+        /// [set_lines](EditorBuffer::set_lines) this is an edit, so the buffer is marked
+        /// dirty. The caret & scroll offset move to the top, and folds, marks, selections &
+        /// secondary carets are cleared.
+        pub fn set_content(&mut self, content: &str) {
+            self.set_lines(split_into_lines(content));
+            self.selection_map.clear();
+            self.maybe_mixed_line_breaks = None;
+            self.is_dirty = true;
+        }
+
+        /// Replace all the lines w/ `content` that was just read from a file. Unlike
+        /// [set_content](EditorBuffer::set_content) this isn't an edit, so the buffer is
+        /// clean afterwards. The line ending that is used the most in `content` becomes
+        /// the [line_ending](EditorBuffer::get_line_ending) of the buffer, and if the line
+        /// endings are mixed, they are preserved by
+        /// [get_content_for_save](EditorBuffer::get_content_for_save).
+        pub fn load_content(&mut self, content: &str) {
+            let detected = LineEnding::detect(content);
+            self.set_lines(split_into_lines(content));
+            self.selection_map.clear();
+            self.line_ending = detected.dominant;
+            self.maybe_mixed_line_breaks = detected
+                .line_breaks
+                .iter()
+                .any(|it| *it != detected.dominant)
+                .then_some(detected.line_breaks);
+        }
+
+        pub fn get_line_ending(&self) -> LineEnding { self.line_ending }
+
+        pub fn has_mixed_line_endings(&self) -> bool {
+            self.maybe_mixed_line_breaks.is_some()
+        }
+
+        /// Returns the text to write to a file. The lines are joined w/ the
+        /// [line_ending](EditorBuffer::get_line_ending) of the buffer, except for mixed
+        /// line endings that were loaded (& not converted), which are kept as they were.
+        pub fn get_content_for_save(&self) -> String {
+            let mut content = String::new();
+            for (row_index, line) in self.lines.iter().enumerate() {
+                if row_index > 0 {
+                    let line_ending = self
+                        .maybe_mixed_line_breaks
+                        .as_ref()
+                        .and_then(|line_breaks| line_breaks.get(row_index - 1))
+                        .unwrap_or(&self.line_ending);
+                    content.push_str(line_ending.as_str());
+                }
+                content.push_str(&line.string);
+            }
+            content
+        }
+
+        /// Use `line_ending` after every line when the buffer is saved, including lines
+        /// that had a different one (in a file w/ mixed line endings). This is an edit, so
+        /// the buffer is marked dirty. Returns `false` if nothing changed.
+        pub fn convert_line_endings(&mut self, line_ending: LineEnding) -> bool {
+            if self.line_ending == line_ending && self.maybe_mixed_line_breaks.is_none() {
+                return false;
+            }
+            self.line_ending = line_ending;
+            self.maybe_mixed_line_breaks = None;
+            self.is_dirty = true;
+            true
+        }
+
+        /// Keep the mixed line breaks in sync after `delta` lines are inserted or removed.
+        /// The line breaks are inserted (w/ the buffer's line ending) or removed starting
+        /// at the line break after the row at `row_index`.
+        pub fn shift_mixed_line_breaks(&mut self, row_index: usize, delta: isize) {
+            let Some(line_breaks) = self.maybe_mixed_line_breaks.as_mut() else {
+                return;
+            };
+            let start_index = row_index.min(line_breaks.len());
+            let amount = delta.unsigned_abs();
+            if delta > 0 {
+                let inserted = std::iter::repeat(self.line_ending).take(amount);
+                line_breaks.splice(start_index..start_index, inserted);
+            } else {
+                let end_index = (start_index + amount).min(line_breaks.len());
+                line_breaks.drain(start_index..end_index);
+            }
+        }
+
+        /// Same as [set_content](EditorBuffer::set_content), except that the caret moves to
+        /// the given [scroll adjusted](CaretKind::ScrollAdjusted) `caret` (clamped to the
+        /// new content), eg: to keep it in place after formatting the document. The scroll
+        /// offset is kept if the caret is still in the `viewport`.
+        pub fn set_content_and_clamp_caret(
+            &mut self,
+            content: &str,
+            caret: Position,
+            viewport: Size,
+        ) {
+            let scroll_offset = self.scroll_offset;
+            self.set_content(content);
+            self.scroll_offset = scroll_offset;
+
+            let row_index = caret.row_index.min(self.len() - 1);
+            let col_index = caret.col_index.min(self.get_line_display_width(row_index));
+            self.move_caret_into_view(row_index, col_index, viewport);
+        }
+
+        /// Insert `text` (which can contain newlines) at the given [scroll
+        /// adjusted](CaretKind::ScrollAdjusted) `position` (clamped to the content), eg: to
+        /// accept a completion. Returns the position right after the inserted text.
+        /// - The carets, marks & folds at or after `position` shift, so they stay on the
+        ///   same text. So a caret at `position` ends up after the inserted text.
+        /// - The caret is scrolled into the `viewport` if it isn't in it anymore.
+        pub fn insert_at(
+            &mut self,
+            position: Position,
+            text: &str,
+            viewport: Size,
+        ) -> Position {
+            if self.lines.is_empty() {
+                self.lines.push(UnicodeString::default());
+            }
+
+            let row_index = position.row_index.min(self.len() - 1);
+            let col_index = position
+                .col_index
+                .min(self.get_line_display_width(row_index));
+            let line = &self.lines[ch!(@to_usize row_index)];
+            let left = line.clip_to_width(ch!(0), col_index).to_string();
+            let right = line
+                .clip_to_width(col_index, line.display_width - col_index)
+                .to_string();
+
+            // Splice the inserted lines in between the left & right of the line.
+            let mut new_lines = split_into_lines(text);
+            let added_row_count = new_lines.len() - 1;
+            let last_inserted_line = new_lines.pop().unwrap_or_default();
+            let end_position = match added_row_count {
+                0 => position! {
+                    col_index: col_index
+                        + ch!(UnicodeString::str_display_width(&last_inserted_line)),
+                    row_index: row_index
+                },
+                _ => position! {
+                    col_index: ch!(UnicodeString::str_display_width(&last_inserted_line)),
+                    row_index: row_index + ch!(added_row_count)
+                },
+            };
+            new_lines.push(last_inserted_line);
+            if let Some(first_line) = new_lines.first_mut() {
+                first_line.insert_str(0, &left);
+            }
+            if let Some(last_line) = new_lines.last_mut() {
+                last_line.push_str(&right);
+            }
+            let row = ch!(@to_usize row_index);
+            self.lines
+                .splice(row..=row, new_lines.into_iter().map(UnicodeString::from));
+
+            // Keep the carets, marks & folds on the same text.
+            let shift = |it: Position| -> Position {
+                if it.row_index == row_index && it.col_index >= col_index {
+                    position! {
+                        col_index: end_position.col_index + (it.col_index - col_index),
+                        row_index: end_position.row_index
+                    }
+                } else if it.row_index > row_index {
+                    position! {
+                        col_index: it.col_index,
+                        row_index: it.row_index + ch!(added_row_count)
+                    }
+                } else {
+                    it
+                }
+            };
+            let caret = shift(self.get_caret(CaretKind::ScrollAdjusted));
+            self.secondary_carets =
+                self.secondary_carets.iter().copied().map(shift).collect();
+            for mark in self.marks.values_mut() {
+                *mark = shift(*mark);
+            }
+            if added_row_count > 0 {
+                self.fold_map.unfold_all_containing(row_index);
+                self.fold_map
+                    .shift_rows_after(row_index, added_row_count as isize);
+                self.shift_mixed_line_breaks(
+                    ch!(@to_usize row_index),
+                    added_row_count as isize,
+                );
+            }
+            self.selection_map.clear();
+            self.is_dirty = true;
+
+            self.move_caret_into_view(caret.row_index, caret.col_index, viewport);
+            end_position
+        }
+
+        /// Delete `col_count` display cols from the line, starting at the given [scroll
+        /// adjusted](CaretKind::ScrollAdjusted) `position` (clamped to the content), eg: to
+        /// remove the word prefix that a completion replaces. This doesn't join lines.
+        /// - The carets & marks in the deleted range move to `position`, and the ones after
+        ///   it shift left.
+        /// - The caret is scrolled into the `viewport` if it isn't in it anymore.
+        pub fn delete_at(
+            &mut self,
+            position: Position,
+            col_count: ChUnit,
+            viewport: Size,
+        ) {
+            if self.lines.is_empty() {
+                return;
+            }
+
+            let row_index = position.row_index.min(self.len() - 1);
+            let line_display_width = self.get_line_display_width(row_index);
+            let start_col_index = position.col_index.min(line_display_width);
+            let end_col_index = (start_col_index + col_count).min(line_display_width);
+            let line = &self.lines[ch!(@to_usize row_index)];
+            let new_line = format!(
+                "{}{}",
+                line.clip_to_width(ch!(0), start_col_index),
+                line.clip_to_width(end_col_index, line_display_width - end_col_index)
+            );
+            self.lines[ch!(@to_usize row_index)] = UnicodeString::from(new_line);
+
+            // Keep the carets & marks on the same text.
+            let shift = |it: Position| -> Position {
+                if it.row_index != row_index || it.col_index <= start_col_index {
+                    it
+                } else if it.col_index < end_col_index {
+                    position! { col_index: start_col_index, row_index: row_index }
+                } else {
+                    position! {
+                        col_index: it.col_index - (end_col_index - start_col_index),
+                        row_index: row_index
+                    }
+                }
+            };
+            let caret = shift(self.get_caret(CaretKind::ScrollAdjusted));
+            self.secondary_carets =
+                self.secondary_carets.iter().copied().map(shift).collect();
+            for mark in self.marks.values_mut() {
+                *mark = shift(*mark);
+            }
+            self.selection_map.clear();
+            self.is_dirty = true;
+
+            self.move_caret_into_view(caret.row_index, caret.col_index, viewport);
+        }
+
+        /// Move the caret to the given [scroll adjusted](CaretKind::ScrollAdjusted)
+        /// position. If it is not in the `viewport` w/ the current scroll offset, then
+        /// scroll so that the row is in the center of it.
+        pub(super) fn move_caret_into_view(
+            &mut self,
+            row_index: ChUnit,
+            col_index: ChUnit,
+            viewport: Size,
+        ) {
+            let scroll_offset = self.scroll_offset;
+            let is_row_in_view = row_index >= scroll_offset.row_index
+                && row_index < scroll_offset.row_index + viewport.row_count
+                && scroll_offset.row_index < self.len();
+            let is_col_in_view = col_index >= scroll_offset.col_index
+                && col_index < scroll_offset.col_index + viewport.col_count;
+
+            if is_row_in_view && is_col_in_view {
+                self.caret_display_position = position! {
+                    col_index: col_index - scroll_offset.col_index,
+                    row_index: row_index - scroll_offset.row_index
+                };
+            } else {
+                self.center_caret_at(row_index, col_index, viewport);
+            }
+        }
+
+        /// Move the caret to the given [scroll adjusted](CaretKind::ScrollAdjusted)
+        /// position & scroll so that the row is in the center of the `viewport`.
+        fn center_caret_at(
+            &mut self,
+            row_index: ChUnit,
+            col_index: ChUnit,
+            viewport: Size,
+        ) {
+            // Center the row vertically, w/out scrolling past the end of the buffer.
+            let max_scroll_row_index = self.len() - viewport.row_count;
+            let scroll_row_index =
+                (row_index - viewport.row_count / 2).min(max_scroll_row_index);
+            self.scroll_offset.row_index = scroll_row_index;
+            self.caret_display_position.row_index = row_index - scroll_row_index;
+
+            // Only scroll horizontally if the column isn't in the viewport.
+            if col_index < viewport.col_count {
+                self.scroll_offset.col_index = ch!(0);
+                self.caret_display_position.col_index = col_index;
+            } else {
+                let caret_col_index = viewport.col_count - 1;
+                self.scroll_offset.col_index = col_index - caret_col_index;
+                self.caret_display_position.col_index = caret_col_index;
+            }
+        }
+    }
+}
+
+/// Split `content` into lines, on `\n` or `\r\n`. There is always at least one line.
+fn split_into_lines(content: &str) -> Vec<String> {
+    content
+        .split('\n')
+        .map(|line| line.strip_suffix('\r').unwrap_or(line).to_string())
+        .collect()
+}
+
+pub mod line_ops {
+    use super::*;
+
+    impl EditorBuffer {
+        /// Returns the first & last rows (inclusive) that are selected, or the caret row if
+        /// there is no selection.
+        pub fn get_selected_or_caret_rows(&self) -> (usize, usize) {
+            let caret_row_index =
+                ch!(@to_usize self.get_caret(CaretKind::ScrollAdjusted).row_index);
+            let selected_row_indices: Vec<usize> = self
+                .selection_map
+                .iter()
+                .map(|(row_index, _)| ch!(@to_usize *row_index))
+                .collect();
+            match (
+                selected_row_indices.iter().min(),
+                selected_row_indices.iter().max(),
+            ) {
+                (Some(start_row_index), Some(end_row_index)) => {
+                    (*start_row_index, *end_row_index)
+                }
+                _ => (caret_row_index, caret_row_index),
+            }
+        }
+
+        /// Insert a copy of the line at `row_index` below it. More info in
+        /// [duplicate_lines](EditorBuffer::duplicate_lines).
+        pub fn duplicate_line(&mut self, row_index: usize) -> bool {
+            self.duplicate_lines(row_index, row_index)
+        }
+
+        /// Insert a copy of the lines from `start_row_index` to `end_row_index` (inclusive)
+        /// right below them. The caret and selection move to the copy, & the caret col is
+        /// preserved. Returns `false` if the range is out of bounds.
+        pub fn duplicate_lines(
+            &mut self,
+            start_row_index: usize,
+            end_row_index: usize,
+        ) -> bool {
+            if start_row_index > end_row_index || end_row_index >= self.lines.len() {
+                return false;
+            }
+
+            let copied_lines = self.lines.clone_range(start_row_index..=end_row_index);
+            let count = copied_lines.len();
+            let insert_index = end_row_index + 1;
+            self.lines.splice(insert_index..insert_index, copied_lines);
+
+            for row_index in start_row_index..=end_row_index {
+                self.fold_map.unfold_all_containing(ch!(row_index));
+            }
+            self.fold_map
+                .shift_rows_after(ch!(end_row_index), count as isize);
+            for position in self.marks.values_mut() {
+                if position.row_index > ch!(end_row_index) {
+                    position.row_index += ch!(count);
+                }
+            }
+
+            self.shift_caret_and_selection_rows(count as isize);
+            self.secondary_carets.clear();
+            self.is_dirty = true;
+            true
+        }
+
+        /// Swap the line at `row_index` w/ the one above or below it. More info in
+        /// [move_lines](EditorBuffer::move_lines).
+        pub fn move_line(&mut self, row_index: usize, direction: CaretDirection) -> bool {
+            self.move_lines(row_index, row_index, direction)
+        }
+
+        /// Move the lines from `start_row_index` to `end_row_index` (inclusive) up or down
+        /// by one line. The caret and selection move w/ the lines, & the caret col is
+        /// preserved. Returns `false` (and does nothing) if the lines are already at the
+        /// top or bottom of the buffer, or if `direction` is not up or down.
+        pub fn move_lines(
+            &mut self,
+            start_row_index: usize,
+            end_row_index: usize,
+            direction: CaretDirection,
+        ) -> bool {
+            if start_row_index > end_row_index || end_row_index >= self.lines.len() {
+                return false;
+            }
+
+            // The line that is displaced by the move, and where it ends up.
+            let (delta, displaced_row_index, new_displaced_row_index) = match direction {
+                CaretDirection::Up if start_row_index > 0 => {
+                    let line = self.lines.remove(start_row_index - 1);
+                    self.lines.insert(end_row_index, line);
+                    (-1, start_row_index - 1, end_row_index)
+                }
+                CaretDirection::Down if end_row_index + 1 < self.lines.len() => {
+                    let line = self.lines.remove(end_row_index + 1);
+                    self.lines.insert(start_row_index, line);
+                    (1, end_row_index + 1, start_row_index)
+                }
+                _ => return false,
+            };
+
+            let first_touched_row_index = start_row_index.min(displaced_row_index);
+            let last_touched_row_index = end_row_index.max(displaced_row_index);
+            for row_index in first_touched_row_index..=last_touched_row_index {
+                self.fold_map.unfold_all_containing(ch!(row_index));
+            }
+            for position in self.marks.values_mut() {
+                let row_index = ch!(@to_usize position.row_index);
+                if (start_row_index..=end_row_index).contains(&row_index) {
+                    position.row_index = ch!(row_index.saturating_add_signed(delta));
+                } else if row_index == displaced_row_index {
+                    position.row_index = ch!(new_displaced_row_index);
+                }
+            }
+
+            self.shift_caret_and_selection_rows(delta);
+            self.secondary_carets.clear();
+            self.is_dirty = true;
+            true
+        }
+
+        /// Comment out the lines in `range` by inserting `prefix` (followed by a space)
+        /// after the leading whitespace of each line. If all of them are already commented
+        /// out, then the prefix (and the space after it) is removed instead. Blank lines
+        /// are left alone. Returns `false` if nothing changed.
+        pub fn toggle_line_comment(
+            &mut self,
+            range: RangeInclusive<usize>,
+            prefix: &str,
+        ) -> bool {
+            if prefix.is_empty() || self.lines.is_empty() {
+                return false;
+            }
+            let last_row_index = self.lines.len() - 1;
+            let range = *range.start()..=(*range.end()).min(last_row_index);
+
+            fn split_indent(line: &str) -> (&str, &str) {
+                let indent_len = line.len() - line.trim_start().len();
+                line.split_at(indent_len)
+            }
+            let non_blank_row_indices = range
+                .filter(|row_index| !self.lines[*row_index].string.trim().is_empty())
+                .collect::<Vec<_>>();
+            if non_blank_row_indices.is_empty() {
+                return false;
+            }
+            let is_commented_out = non_blank_row_indices.iter().all(|row_index| {
+                let (_, text) = split_indent(&self.lines[*row_index].string);
+                text.starts_with(prefix)
+            });
+
+            let caret = self.get_caret(CaretKind::ScrollAdjusted);
+            for row_index in non_blank_row_indices {
+                let line = &self.lines[row_index].string;
+                let (indent, text) = split_indent(line);
+                let new_text = if is_commented_out {
+                    let text = &text[prefix.len()..];
+                    text.strip_prefix(' ').unwrap_or(text).to_string()
+                } else {
+                    format!("{prefix} {text}")
+                };
+                let old_width = UnicodeString::from(text).display_width;
+                let new_width = UnicodeString::from(new_text.as_str()).display_width;
+                let indent_width = UnicodeString::from(indent).display_width;
+                self.lines[row_index] =
+                    UnicodeString::from(format!("{indent}{new_text}"));
+
+                // Keep the caret on the same char (or at the start of the text).
+                if caret.row_index == ch!(row_index) && caret.col_index > indent_width {
+                    let col_index = if new_width > old_width {
+                        caret.col_index + (new_width - old_width)
+                    } else {
+                        (caret.col_index - (old_width - new_width)).max(indent_width)
+                    };
+                    self.set_caret_col_scroll_adjusted(col_index);
+                }
+                if let Some(range) = self.selection_map.map.get_mut(&ch!(row_index)) {
+                    let line_width = self.lines[row_index].display_width;
+                    range.end_display_col_index =
+                        range.end_display_col_index.min(line_width);
+                }
+            }
+
+            self.secondary_carets.clear();
+            self.is_dirty = true;
+            true
+        }
+
+        /// Insert `indent` (eg: 4 spaces or a tab, see
+        /// [EditorEngineConfig::get_indent_unit]) at the start of each line in `range`.
+        /// Blank lines are left alone. The caret and selection move w/ the text. Returns
+        /// `false` if nothing changed.
+        pub fn indent_lines(
+            &mut self,
+            range: RangeInclusive<usize>,
+            indent: &str,
+        ) -> bool {
+            if indent.is_empty() || self.lines.is_empty() {
+                return false;
+            }
+            let last_row_index = self.lines.len() - 1;
+            let range = *range.start()..=(*range.end()).min(last_row_index);
+            let indent_width = UnicodeString::from(indent).display_width;
+
+            let mut is_changed = false;
+            for row_index in range {
+                let line = &self.lines[row_index].string;
+                if line.trim().is_empty() {
+                    continue;
+                }
+                self.lines[row_index] = UnicodeString::from(format!("{indent}{line}"));
+                self.shift_cols_in_row(row_index, indent_width, true);
+                is_changed = true;
+            }
+
+            if is_changed {
+                self.end_line_indent_change();
+            }
+            is_changed
+        }
+
+        /// Remove up to `tab_width` cols of leading whitespace from each line in `range`,
+        /// where a tab counts as `tab_width` cols. The text after the whitespace is never
+        /// removed. The caret and selection move w/ the text. Returns `false` if nothing
+        /// changed.
+        pub fn dedent_lines(
+            &mut self,
+            range: RangeInclusive<usize>,
+            tab_width: usize,
+        ) -> bool {
+            if tab_width == 0 || self.lines.is_empty() {
+                return false;
+            }
+            let last_row_index = self.lines.len() - 1;
+            let range = *range.start()..=(*range.end()).min(last_row_index);
+
+            let mut is_changed = false;
+            for row_index in range {
+                let line = &self.lines[row_index].string;
+                let mut byte_count = 0;
+                let mut col_count = 0;
+                for character in line.chars() {
+                    match character {
+                        ' ' if col_count < tab_width => col_count += 1,
+                        '\t' if col_count == 0 => col_count = tab_width,
+                        _ => break,
+                    }
+                    byte_count += 1;
+                }
+                if byte_count == 0 {
+                    continue;
+                }
+                let removed_width =
+                    UnicodeString::from(&line[..byte_count]).display_width;
+                self.lines[row_index] = UnicodeString::from(&line[byte_count..]);
+                self.shift_cols_in_row(row_index, removed_width, false);
+                is_changed = true;
+            }
+
+            if is_changed {
+                self.end_line_indent_change();
+            }
+            is_changed
+        }
+
+        /// Move the caret & selection in the row at `row_index` by `width` cols, to the
+        /// right if `is_added`, otherwise to the left. A selection that starts at the
+        /// start of the line keeps starting there.
+        fn shift_cols_in_row(&mut self, row_index: usize, width: ChUnit, is_added: bool) {
+            let shift = |col_index: ChUnit| match is_added {
+                true => col_index + width,
+                false => col_index - width,
+            };
+
+            let caret = self.get_caret(CaretKind::ScrollAdjusted);
+            if caret.row_index == ch!(row_index) {
+                self.set_caret_col_scroll_adjusted(shift(caret.col_index));
+            }
+            if let Some(range) = self.selection_map.map.get_mut(&ch!(row_index)) {
+                if range.start_display_col_index > ch!(0) {
+                    range.start_display_col_index = shift(range.start_display_col_index);
+                }
+                range.end_display_col_index = shift(range.end_display_col_index);
+            }
+        }
+
+        /// The snippet ranges aren't shifted, so the session ends.
+        fn end_line_indent_change(&mut self) {
+            self.maybe_snippet_session = None;
+            self.secondary_carets.clear();
+            self.is_dirty = true;
+        }
+
+        fn set_caret_col_scroll_adjusted(&mut self, col_index: ChUnit) {
+            if col_index < self.scroll_offset.col_index {
+                self.scroll_offset.col_index = col_index;
+            }
+            self.caret_display_position.col_index =
+                col_index - self.scroll_offset.col_index;
+        }
+
+        /// Move the caret & selected rows by `delta` rows. If the caret moves above the
+        /// viewport then it is scrolled up. Scrolling down is left to the caller (it
+        /// requires the viewport height).
+        fn shift_caret_and_selection_rows(&mut self, delta: isize) {
+            let shift = |row_index: ChUnit| {
+                ch!(ch!(@to_usize row_index).saturating_add_signed(delta))
+            };
+
+            let caret_row_index =
+                shift(self.get_caret(CaretKind::ScrollAdjusted).row_index);
+            if caret_row_index < self.scroll_offset.row_index {
+                self.scroll_offset.row_index = caret_row_index;
+            }
+            self.caret_display_position.row_index =
+                caret_row_index - self.scroll_offset.row_index;
+
+            let selection = std::mem::take(&mut self.selection_map.map);
+            self.selection_map.map = selection
+                .into_iter()
+                .map(|(row_index, range)| (shift(row_index), range))
+                .collect();
+        }
+    }
+}
+
+pub mod transpose_ops {
+    use super::*;
+
+    impl EditorBuffer {
+        /// Swap the grapheme clusters on either side of the [scroll
+        /// adjusted](CaretKind::ScrollAdjusted) `position`, & move the caret past both of
+        /// them (like `transpose-chars` in Emacs). This is a single change to the buffer,
+        /// so one undo reverts it. Returns `false` if there's nothing to swap.
+        /// - At the end of a line, the last 2 clusters are swapped & the caret stays put.
+        /// - At the start of a line, the new line before it counts as a char, so the first
+        ///   cluster moves to the end of the line above. A line w/ 1 cluster is handled the
+        ///   same way when the caret is at its end.
+        /// - Nothing happens at the start of the buffer, or on an empty line.
+        pub fn transpose_chars(&mut self, position: Position, viewport: Size) -> bool {
+            let Some(position) = self.clamp_to_content(position) else {
+                return false;
+            };
+            let row_index = ch!(@to_usize position.row_index);
+            let line = &self.lines[row_index];
+            let segments = line.iter().collect::<Vec<_>>();
+            let index = segments
+                .iter()
+                .position(|it| it.display_col_offset >= position.col_index)
+                .unwrap_or(segments.len());
+
+            match (index, segments.len()) {
+                (_, 0) => false,
+                // Swap the new line before the line w/ its first cluster.
+                (0, _) | (1, 1) => {
+                    if row_index == 0 {
+                        return false;
+                    }
+                    let first = segments[0];
+                    let new_prev_line =
+                        format!("{}{}", self.lines[row_index - 1].string, first.string);
+                    let new_line = line.string[first.byte_size..].to_string();
+                    self.lines[row_index - 1] = UnicodeString::from(new_prev_line);
+                    self.lines[row_index] = UnicodeString::from(new_line);
+                    self.finish_transpose(
+                        row_index - 1..=row_index,
+                        position! { col_index: 0, row_index: row_index },
+                        viewport,
+                    );
+                    true
+                }
+                // At the end of the line, swap the last 2 clusters.
+                (index, count) if index == count => {
+                    let new_line =
+                        swap_segments(line, segments[count - 2], segments[count - 1]);
+                    self.lines[row_index] = UnicodeString::from(new_line);
+                    self.finish_transpose(row_index..=row_index, position, viewport);
+                    true
+                }
+                (index, _) => {
+                    let (left, right) = (segments[index - 1], segments[index]);
+                    let col_index = right.display_col_offset + right.unicode_width;
+                    let new_line = swap_segments(line, left, right);
+                    self.lines[row_index] = UnicodeString::from(new_line);
+                    self.finish_transpose(
+                        row_index..=row_index,
+                        position! { col_index: col_index, row_index: row_index },
+                        viewport,
+                    );
+                    true
+                }
+            }
+        }
+
+        /// Swap the word that the [scroll adjusted](CaretKind::ScrollAdjusted) `position`
+        /// is in (or the one before it) w/ the word after it, & move the caret to the end
+        /// of both of them (like `transpose-words` in Emacs). If there's no word after it,
+        /// then the last 2 words of the line are swapped. The chars that make up a word
+        /// come from the `word_char_class`, and the punctuation & whitespace between the
+        /// words stays put. Only words on the same line are swapped. This is a single
+        /// change to the buffer, so one undo reverts it. Returns `false` if the line has
+        /// fewer than 2 words.
+        pub fn transpose_words(
+            &mut self,
+            position: Position,
+            word_char_class: &WordCharClass,
+            viewport: Size,
+        ) -> bool {
+            let Some(position) = self.clamp_to_content(position) else {
+                return false;
+            };
+            let row_index = ch!(@to_usize position.row_index);
+            let line = &self.lines[row_index];
+
+            // The (first, last) cluster of each word.
+            let mut words: Vec<(&GraphemeClusterSegment, &GraphemeClusterSegment)> =
+                vec![];
+            let mut maybe_word_start = None;
+            let segments = line.iter().collect::<Vec<_>>();
+            for (index, segment) in segments.iter().enumerate() {
+                let is_word = word_char_class.get_char_class(segment) == CharClass::Word;
+                match (is_word, maybe_word_start) {
+                    (true, None) => maybe_word_start = Some(*segment),
+                    (false, Some(start)) => {
+                        words.push((start, segments[index - 1]));
+                        maybe_word_start = None;
+                    }
+                    _ => {}
+                }
+            }
+            if let (Some(start), Some(end)) = (maybe_word_start, segments.last().copied())
+            {
+                words.push((start, end));
+            }
+
+            let index = words
+                .iter()
+                .rposition(|(start, _)| start.display_col_offset < position.col_index)
+                .unwrap_or(0);
+            let (left, right) = if index + 1 < words.len() {
+                (words[index], words[index + 1])
+            } else if index >= 1 {
+                (words[index - 1], words[index])
+            } else {
+                return false;
+            };
+
+            let byte_range =
+                |(start, end): (&GraphemeClusterSegment, &GraphemeClusterSegment)| {
+                    start.byte_offset..end.byte_offset + end.byte_size
+                };
+            let (left_bytes, right_bytes) = (byte_range(left), byte_range(right));
+            let string = &line.string;
+            let new_line = format!(
+                "{}{}{}{}{}",
+                &string[..left_bytes.start],
+                &string[right_bytes.clone()],
+                &string[left_bytes.end..right_bytes.start],
+                &string[left_bytes],
+                &string[right_bytes.end..],
+            );
+            let col_index = right.1.display_col_offset + right.1.unicode_width;
+            self.lines[row_index] = UnicodeString::from(new_line);
+            self.finish_transpose(
+                row_index..=row_index,
+                position! { col_index: col_index, row_index: row_index },
+                viewport,
+            );
+            true
+        }
+
+        /// Clean up after the lines in `row_indices` have been changed: unfold them, keep
+        /// the marks in them inside their lines, & move the caret to `caret`.
+        fn finish_transpose(
+            &mut self,
+            row_indices: RangeInclusive<usize>,
+            caret: Position,
+            viewport: Size,
+        ) {
+            for row_index in row_indices {
+                self.fold_map.unfold_all_containing(ch!(row_index));
+                let line_width = self.lines[row_index].display_width;
+                for mark in self.marks.values_mut() {
+                    if mark.row_index == ch!(row_index) {
+                        mark.col_index = mark.col_index.min(line_width);
+                    }
+                }
+            }
+            self.selection_map.clear();
+            self.secondary_carets.clear();
+            self.is_dirty = true;
+            self.move_caret_into_view(caret.row_index, caret.col_index, viewport);
+        }
+    }
+
+    /// Returns `line` w/ the adjacent `left` & `right` clusters swapped.
+    fn swap_segments(
+        line: &UnicodeString,
+        left: &GraphemeClusterSegment,
+        right: &GraphemeClusterSegment,
+    ) -> String {
+        format!(
+            "{}{}{}{}",
+            &line.string[..left.byte_offset],
+            right.string,
+            left.string,
+            &line.string[right.byte_offset + right.byte_size..],
+        )
+    }
+}
+
+pub mod select_ops {
+    use super::*;
+
+    impl EditorBuffer {
+        /// Move the caret to the given [scroll adjusted](CaretKind::ScrollAdjusted)
+        /// `position` (clamped to the content), eg: when the editor is clicked. This
+        /// clears the selection & the secondary carets. Returns `false` if the buffer is
+        /// empty.
+        pub fn click_at(&mut self, position: Position, viewport: Size) -> bool {
+            let Some(position) = self.clamp_to_content(position) else {
+                return false;
+            };
+            self.select_range_in_row(
+                position.row_index,
+                SelectionRange::new(position.col_index, position.col_index),
+                viewport,
+            );
+            true
+        }
+
+        /// Select the word at the given [scroll adjusted](CaretKind::ScrollAdjusted)
+        /// `position` (eg: on a double click), & put the caret at its end. The chars that
+        /// make up a word come from the `word_char_class`, & a run of whitespace or
+        /// punctuation is selected the same way. Returns `false` if the line is empty.
+        pub fn select_word_at(
+            &mut self,
+            position: Position,
+            word_char_class: &WordCharClass,
+            viewport: Size,
+        ) -> bool {
+            let Some(position) = self.clamp_to_content(position) else {
+                return false;
+            };
+            let Some(range) = word_char_class.get_word_range_at(
+                &self.lines[ch!(@to_usize position.row_index)],
+                position.col_index,
+            ) else {
+                return false;
+            };
+            self.select_range_in_row(position.row_index, range, viewport);
+            true
+        }
+
+        /// Select the whole line at `row_index` (eg: on a triple click), & put the caret
+        /// at its end. Returns `false` if the line is empty.
+        pub fn select_line_at(&mut self, row_index: ChUnit, viewport: Size) -> bool {
+            let Some(position) =
+                self.clamp_to_content(position! { col_index: 0, row_index: row_index })
+            else {
+                return false;
+            };
+            let display_width = self.get_line_display_width(position.row_index);
+            if display_width == ch!(0) {
+                return false;
+            }
+            self.select_range_in_row(
+                position.row_index,
+                SelectionRange::new(ch!(0), display_width),
+                viewport,
+            );
+            true
+        }
+
+        /// Returns the text in the selection (lines are separated by `\n`) as plain text,
+        /// ie: w/out any escape sequences that are in the content (eg: a log file w/
+        /// colors). The styles from syntax highlighting are never in the content, so
+        /// they aren't in this text either. Returns [None] if there's no selection.
+        pub fn get_selected_text(&self) -> Option<String> {
+            let mut row_indices = self
+                .selection_map
+                .iter()
+                .map(|(row_index, _)| *row_index)
+                .collect::<Vec<_>>();
+            if row_indices.is_empty() {
+                return None;
+            }
+            row_indices.sort();
+
+            let selected_text = row_indices
+                .iter()
+                .filter_map(|row_index| {
+                    let line = self.lines.get(ch!(@to_usize *row_index))?;
+                    let range = self.selection_map.get(*row_index)?;
+                    Some(line.clip_to_range(*range))
+                })
+                .collect::<Vec<_>>()
+                .join("\n");
+            Some(strip_ansi_escape_sequences(&selected_text))
+        }
+
+        /// Returns the whole content (lines are separated by `\n`) as plain text. More
+        /// info in [get_selected_text](EditorBuffer::get_selected_text).
+        pub fn get_plain_text(&self) -> String {
+            strip_ansi_escape_sequences(&self.get_as_string_with_newlines())
+        }
+
+        /// [Copy](copy_to_clipboard) the [selected
+        /// text](EditorBuffer::get_selected_text) to the clipboard. Returns `false` if
+        /// there's no selection.
+        pub fn copy_selected_text_to_clipboard(&self) -> bool {
+            match self.get_selected_text() {
+                Some(selected_text) => {
+                    copy_to_clipboard(&selected_text);
+                    true
+                }
+                None => false,
+            }
+        }
+
+        /// [Copy](copy_to_clipboard) the whole content (as [plain
+        /// text](EditorBuffer::get_plain_text)) to the clipboard, eg: in a log viewer.
+        pub fn copy_all_to_clipboard(&self) { copy_to_clipboard(&self.get_plain_text()); }
+
+        /// The row is clamped to the last line, & the col to the end of the line (or the
+        /// start of the grapheme cluster that it is in the middle of).
+        pub(super) fn clamp_to_content(&self, position: Position) -> Option<Position> {
+            if self.lines.is_empty() {
+                return None;
+            }
+            let row_index = position.row_index.min(self.len() - 1);
+            let line = &self.lines[ch!(@to_usize row_index)];
+            let col_index = line
+                .iter()
+                .find(|it| position.col_index < it.display_col_offset + it.unicode_width)
+                .map(|it| it.display_col_offset)
+                .unwrap_or(line.display_width);
+            Some(position! { col_index: col_index, row_index: row_index })
+        }
+
+        fn select_range_in_row(
+            &mut self,
+            row_index: ChUnit,
+            range: SelectionRange,
+            viewport: Size,
+        ) {
+            self.secondary_carets.clear();
+            self.selection_map.clear();
+            if range.start_display_col_index < range.end_display_col_index {
+                self.selection_map.insert(
+                    row_index,
+                    range,
+                    CaretMovementDirection::Right,
+                );
+            }
+            self.move_caret_into_view(row_index, range.end_display_col_index, viewport);
+        }
+    }
+}
+
+pub mod search_replace {
+    use super::*;
+
+    /// How [EditorBuffer::find_matches] compares the query to the text.
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+    pub struct SearchOptions {
+        pub case_insensitive: bool,
+        /// Only match the query when it isn't part of a longer word (letters, digits &
+        /// `_`).
+        pub whole_word: bool,
+        /// The query is a regular expression, and `$1` (or `${name}`) in the replacement
+        /// is replaced w/ the text of that capture group. More info in
+        /// [build_search_regex]. This needs the `regex` feature, w/out it the query is
+        /// rejected like an invalid regex (it doesn't match anything).
+        pub is_regex: bool,
+    }
+
+    /// A match of the query in a line of the buffer. The cols are display cols, and they
+    /// aren't adjusted for scrolling.
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+    pub struct SearchMatch {
+        pub row_index: ChUnit,
+        pub start_col_index: ChUnit,
+        /// Exclusive.
+        pub end_col_index: ChUnit,
+    }
+
+    impl SearchMatch {
+        pub fn get_start(&self) -> Position {
+            position! { col_index: self.start_col_index, row_index: self.row_index }
+        }
+
+        pub fn get_col_count(&self) -> ChUnit {
+            self.end_col_index - self.start_col_index
+        }
+    }
+
+    impl EditorBuffer {
+        /// Returns all the (non overlapping) matches of `query`, from the top of the
+        /// buffer to the bottom. Queries never match across lines, and an invalid regex
+        /// doesn't match anything.
+        pub fn find_matches(
+            &self,
+            query: &str,
+            options: &SearchOptions,
+        ) -> Vec<SearchMatch> {
+            if options.is_regex {
+                #[cfg(feature = "regex")]
+                {
+                    return match build_search_regex(query, options) {
+                        Ok(regex) => self.find_regex_matches(&regex),
+                        Err(_) => vec![],
+                    };
+                }
+                #[cfg(not(feature = "regex"))]
+                {
+                    return vec![];
+                }
+            }
+
+            self.find_matches_w(|line| find_byte_ranges_in_line(line, query, options))
+        }
+
+        /// Move the caret to the start of `search_match` & scroll it into the `viewport`.
+        pub fn goto_match(&mut self, search_match: &SearchMatch, viewport: Size) {
+            self.move_caret_into_view(
+                search_match.row_index,
+                search_match.start_col_index,
+                viewport,
+            );
+        }
+
+        /// Replace the text of `search_match` (as returned by
+        /// [find_matches](EditorBuffer::find_matches)) w/ `replacement`, & move the caret
+        /// right after it. Returns `false` if the match isn't in the buffer, or if the
+        /// `replacement` has newlines.
+        pub fn replace_match(
+            &mut self,
+            search_match: &SearchMatch,
+            replacement: &str,
+            viewport: Size,
+        ) -> bool {
+            if replacement.contains('\n')
+                || ch!(@to_usize search_match.row_index) >= self.lines.len()
+                || search_match.end_col_index
+                    > self.get_line_display_width(search_match.row_index)
+            {
+                return false;
+            }
+
+            self.delete_at(
+                search_match.get_start(),
+                search_match.get_col_count(),
+                viewport,
+            );
+            let end_position =
+                self.insert_at(search_match.get_start(), replacement, viewport);
+            self.clear_secondary_carets();
+            self.move_caret_into_view(
+                end_position.row_index,
+                end_position.col_index,
+                viewport,
+            );
+            true
+        }
+
+        /// Replace all the matches of `query` w/ `replacement` in one edit, so that a single
+        /// undo reverts all of them. Returns the number of replacements, which is `0` if the
+        /// `query` is empty (or an invalid regex) or the `replacement` has newlines.
+        /// - The caret stays on the same row, and its col is clamped to the new line.
+        /// - Selections & secondary carets are cleared.
+        pub fn replace_all(
+            &mut self,
+            query: &str,
+            replacement: &str,
+            options: &SearchOptions,
+        ) -> usize {
+            if options.is_regex {
+                #[cfg(feature = "regex")]
+                {
+                    return match build_search_regex(query, options) {
+                        Ok(regex) => self.replace_all_regex(&regex, replacement),
+                        Err(_) => 0,
+                    };
+                }
+                #[cfg(not(feature = "regex"))]
+                {
+                    return 0;
+                }
+            }
+
+            if replacement.contains('\n') {
+                return 0;
+            }
+            self.replace_in_lines(|line| {
+                let byte_ranges = find_byte_ranges_in_line(line, query, options);
+                if byte_ranges.is_empty() {
+                    return None;
+                }
+
+                let mut new_line = String::with_capacity(line.len());
+                let mut last_byte_index = 0;
+                for (start_byte_index, end_byte_index) in &byte_ranges {
+                    new_line.push_str(&line[last_byte_index..*start_byte_index]);
+                    new_line.push_str(replacement);
+                    last_byte_index = *end_byte_index;
+                }
+                new_line.push_str(&line[last_byte_index..]);
+                Some((new_line, byte_ranges.len()))
+            })
+        }
+
+        /// `find_byte_ranges` returns the `(start, end)` byte ranges of the matches in a
+        /// line.
+        fn find_matches_w(
+            &self,
+            find_byte_ranges: impl Fn(&str) -> Vec<(usize, usize)>,
+        ) -> Vec<SearchMatch> {
+            self.lines
+                .iter()
+                .enumerate()
+                .flat_map(|(row_index, line)| {
+                    find_byte_ranges(&line.string).into_iter().map(
+                        move |(start_byte_index, end_byte_index)| SearchMatch {
+                            row_index: ch!(row_index),
+                            start_col_index: ch!(UnicodeString::str_display_width(
+                                &line.string[..start_byte_index]
+                            )),
+                            end_col_index: ch!(UnicodeString::str_display_width(
+                                &line.string[..end_byte_index]
+                            )),
+                        },
+                    )
+                })
+                .collect()
+        }
+
+        /// `replace_line` returns the new line & the number of replacements in it, or
+        /// [None] if there are none. Returns the total number of replacements.
+        fn replace_in_lines(
+            &mut self,
+            replace_line: impl Fn(&str) -> Option<(String, usize)>,
+        ) -> usize {
+            // Only the lines that change are replaced, so the rest are still shared w/ the
+            // previous versions of the buffer (eg: in the undo history).
+            let mut replacement_count = 0;
+            let mut new_lines = vec![];
+            for (row_index, line) in self.lines.iter().enumerate() {
+                if let Some((new_line, count)) = replace_line(&line.string) {
+                    replacement_count += count;
+                    new_lines.push((row_index, UnicodeString::from(new_line)));
+                }
+            }
+            if replacement_count == 0 {
+                return 0;
+            }
+            for (row_index, new_line) in new_lines {
+                self.lines[row_index] = new_line;
+            }
+
+            // Keep the caret & marks inside their (possibly shorter) lines.
+            let caret = self.get_caret(CaretKind::ScrollAdjusted);
+            let caret_col_index = caret
+                .col_index
+                .min(self.get_line_display_width(caret.row_index));
+            if caret_col_index < self.scroll_offset.col_index {
+                self.scroll_offset.col_index = caret_col_index;
+            }
+            self.caret_display_position.col_index =
+                caret_col_index - self.scroll_offset.col_index;
+            let line_widths = self
+                .lines
+                .iter()
+                .map(|line| line.display_width)
+                .collect::<Vec<_>>();
+            for mark in self.marks.values_mut() {
+                if let Some(line_width) = line_widths.get(ch!(@to_usize mark.row_index)) {
+                    mark.col_index = mark.col_index.min(*line_width);
+                }
+            }
+
+            self.selection_map.clear();
+            self.secondary_carets.clear();
+            self.is_dirty = true;
+            replacement_count
+        }
+    }
+
+    /// Returns the `(start, end)` byte ranges of the (non overlapping) matches of `query`
+    /// in `line`.
+    fn find_byte_ranges_in_line(
+        line: &str,
+        query: &str,
+        options: &SearchOptions,
+    ) -> Vec<(usize, usize)> {
+        let mut byte_ranges = vec![];
+        if query.is_empty() {
+            return byte_ranges;
+        }
+
+        let mut search_start_byte_index = 0;
+        while search_start_byte_index < line.len() {
+            let Some((start_byte_index, end_byte_index)) = line
+                [search_start_byte_index..]
+                .char_indices()
+                .map(|(byte_index, _)| search_start_byte_index + byte_index)
+                .find_map(|start_byte_index| {
+                    match_at(line, start_byte_index, query, options)
+                        .map(|end_byte_index| (start_byte_index, end_byte_index))
+                })
+            else {
+                break;
+            };
+            byte_ranges.push((start_byte_index, end_byte_index));
+            search_start_byte_index = end_byte_index;
+        }
+
+        byte_ranges
+    }
+
+    /// Returns the end byte index if `query` matches `line` at `start_byte_index`.
+    fn match_at(
+        line: &str,
+        start_byte_index: usize,
+        query: &str,
+        options: &SearchOptions,
+    ) -> Option<usize> {
+        let is_same_char = |lhs: char, rhs: char| match options.case_insensitive {
+            true => lhs.to_lowercase().eq(rhs.to_lowercase()),
+            false => lhs == rhs,
+        };
+
+        let mut line_chars = line[start_byte_index..].char_indices();
+        let mut end_byte_index = start_byte_index;
+        for query_char in query.chars() {
+            let (byte_index, line_char) = line_chars.next()?;
+            if !is_same_char(line_char, query_char) {
+                return None;
+            }
+            end_byte_index = start_byte_index + byte_index + line_char.len_utf8();
+        }
+
+        if options.whole_word {
+            let is_word_char = |it: char| it.is_alphanumeric() || it == '_';
+            let is_word_before = line[..start_byte_index]
+                .chars()
+                .next_back()
+                .is_some_and(is_word_char);
+            let is_word_after = line[end_byte_index..]
+                .chars()
+                .next()
+                .is_some_and(is_word_char);
+            if is_word_before || is_word_after {
+                return None;
+            }
+        }
+
+        Some(end_byte_index)
+    }
+
+    /// Only compiled w/ the `regex` feature.
+    #[cfg(feature = "regex")]
+    pub mod regex_search {
+        use std::result::Result;
+
+        use regex::{Regex, RegexBuilder};
+
+        use super::*;
+
+        /// Compile `query` (which must have the [SearchOptions::is_regex] syntax of the
+        /// `regex` crate), w/ the other `options`. Matches that are empty (eg: of `a*`)
+        /// are skipped. Returns the (one line) message of the error if the `query` is
+        /// invalid, so that it can be shown to the user.
+        pub fn build_search_regex(
+            query: &str,
+            options: &SearchOptions,
+        ) -> Result<Regex, String> {
+            let pattern = match options.whole_word {
+                true => format!(r"\b(?:{query})\b"),
+                false => query.to_string(),
+            };
+            RegexBuilder::new(&pattern)
+                .case_insensitive(options.case_insensitive)
+                .build()
+                .map_err(|error| {
+                    let message = error.to_string();
+                    message
+                        .lines()
+                        .last()
+                        .unwrap_or_default()
+                        .trim()
+                        .to_string()
+                })
+        }
+
+        impl EditorBuffer {
+            /// Same as [find_matches](EditorBuffer::find_matches), w/ a `regex` that is
+            /// already compiled (eg: by [build_search_regex]).
+            pub fn find_regex_matches(&self, regex: &Regex) -> Vec<SearchMatch> {
+                self.find_matches_w(|line| {
+                    regex
+                        .find_iter(line)
+                        .filter(|it| it.start() < it.end())
+                        .map(|it| (it.start(), it.end()))
+                        .collect()
+                })
+            }
+
+            /// Same as [replace_all](EditorBuffer::replace_all), w/ a `regex` that is
+            /// already compiled (eg: by [build_search_regex]).
+            pub fn replace_all_regex(
+                &mut self,
+                regex: &Regex,
+                replacement: &str,
+            ) -> usize {
+                if replacement.contains('\n') {
+                    return 0;
+                }
+                self.replace_in_lines(|line| {
+                    let mut new_line = String::with_capacity(line.len());
+                    let mut last_byte_index = 0;
+                    let mut count = 0;
+                    for captures in regex.captures_iter(line) {
+                        let Some(it) = captures.get(0).filter(|it| it.start() < it.end())
+                        else {
+                            continue;
+                        };
+                        new_line.push_str(&line[last_byte_index..it.start()]);
+                        captures.expand(replacement, &mut new_line);
+                        last_byte_index = it.end();
+                        count += 1;
+                    }
+                    if count == 0 {
+                        return None;
+                    }
+                    new_line.push_str(&line[last_byte_index..]);
+                    Some((new_line, count))
+                })
+            }
+
+            /// Returns the `replacement` w/ the capture group references (eg: `$1`)
+            /// replaced by the text that `regex` captures for `search_match`. Pass the
+            /// result to [replace_match](EditorBuffer::replace_match).
+            pub fn expand_regex_replacement(
+                &self,
+                regex: &Regex,
+                search_match: &SearchMatch,
+                replacement: &str,
+            ) -> Option<String> {
+                let line = self.lines.get(ch!(@to_usize search_match.row_index))?;
+                let start_byte_index = line
+                    .clip_to_width(ch!(0), search_match.start_col_index)
+                    .len();
+                let captures = regex.captures_at(&line.string, start_byte_index)?;
+                if captures.get(0)?.start() != start_byte_index {
+                    return None;
+                }
+
+                let mut expanded = String::new();
+                captures.expand(replacement, &mut expanded);
+                Some(expanded)
+            }
+        }
+    }
+    #[cfg(feature = "regex")]
+    pub use regex_search::*;
+}
+pub use search_replace::*;
+
+pub mod snippet_ops {
+    use super::*;
+
+    impl EditorBuffer {
+        /// Insert the snippet `template` at the given [scroll
+        /// adjusted](CaretKind::ScrollAdjusted) `position` (clamped to the content), and
+        /// start a [SnippetSession] w/ the caret at the 1st tab stop (& its placeholder
+        /// selected). If the snippet only has `$0`, the caret goes there & there is no
+        /// session. More info on the syntax in [parse_snippet].
+        ///
+        /// This is a single change to the buffer, so the whole expansion is undone at
+        /// once.
+        pub fn insert_snippet(
+            &mut self,
+            template: &str,
+            position: Position,
+            viewport: Size,
+        ) {
+            let ParsedSnippet { text, tab_stops } = parse_snippet(template);
+
+            let start = if self.lines.is_empty() {
+                Position::default()
+            } else {
+                let row_index = position.row_index.min(self.len() - 1);
+                position! {
+                    col_index: position.col_index.min(self.get_line_display_width(row_index)),
+                    row_index: row_index
+                }
+            };
+            self.insert_at(start, &text, viewport);
+            self.start_snippet_session(tab_stops, start, viewport);
+        }
+
+        /// Start a [SnippetSession] for the `tab_stops` of a snippet (from [parse_snippet])
+        /// whose text was just inserted at `start`, w/ the caret at the 1st tab stop.
+        pub fn start_snippet_session(
+            &mut self,
+            tab_stops: Vec<SnippetTabStop>,
+            start: Position,
+            viewport: Size,
+        ) {
+            let tab_stops = tab_stops
+                .into_iter()
+                .map(|tab_stop| SnippetTabStop {
+                    number: tab_stop.number,
+                    ranges: tab_stop
+                        .ranges
+                        .iter()
+                        .map(|it| it.offset_by(start))
+                        .collect(),
+                })
+                .collect();
+            self.maybe_snippet_session = Some(SnippetSession {
+                tab_stops,
+                current_index: 0,
+            });
+            self.select_current_snippet_tab_stop(viewport);
+        }
+
+        pub fn get_snippet_session(&self) -> Option<&SnippetSession> {
+            self.maybe_snippet_session.as_ref()
+        }
+
+        /// The caret & text stay where they are, only the tab stops are forgotten.
+        pub fn end_snippet_session(&mut self) { self.maybe_snippet_session = None; }
+
+        /// Returns `false` if there is no [SnippetSession]. When the caret gets to `$0` the
+        /// session ends.
+        pub fn goto_next_snippet_tab_stop(&mut self, viewport: Size) -> bool {
+            let Some(session) = self.maybe_snippet_session.as_mut() else {
+                return false;
+            };
+            session.current_index =
+                (session.current_index + 1).min(session.tab_stops.len() - 1);
+            self.select_current_snippet_tab_stop(viewport);
+            true
+        }
+
+        /// Returns `false` if there is no [SnippetSession]. The caret stays at the 1st tab
+        /// stop if it is already there.
+        pub fn goto_prev_snippet_tab_stop(&mut self, viewport: Size) -> bool {
+            let Some(session) = self.maybe_snippet_session.as_mut() else {
+                return false;
+            };
+            session.current_index = session.current_index.saturating_sub(1);
+            self.select_current_snippet_tab_stop(viewport);
+            true
+        }
+
+        /// Move the caret to the end of the current tab stop & select its placeholder, so
+        /// that typing replaces it. The session ends at `$0`.
+        fn select_current_snippet_tab_stop(&mut self, viewport: Size) {
+            let Some(session) = self.maybe_snippet_session.as_ref() else {
+                return;
+            };
+            let is_last = session.get_current().map(|it| it.number) == Some(0);
+            let Some(range) = session.get_current_range() else {
+                self.maybe_snippet_session = None;
+                return;
+            };
+
+            self.selection_map.clear();
+            if range.get_col_count() > ch!(0) && !is_last {
+                self.selection_map.insert(
+                    range.row_index,
+                    SelectionRange {
+                        start_display_col_index: range.start_col_index,
+                        end_display_col_index: range.end_col_index,
+                    },
+                    CaretMovementDirection::Right,
+                );
+            }
+            self.move_caret_into_view(range.row_index, range.end_col_index, viewport);
+            if is_last {
+                self.maybe_snippet_session = None;
+            }
+        }
+
+        /// If the selection is exactly the placeholder of the current tab stop, delete it
+        /// (& return `true`), so that the text that is typed (or pasted) replaces it. This
+        /// is called before the [EditorEvent] that changes the content is applied.
+        pub fn delete_selected_snippet_placeholder(&mut self, viewport: Size) -> bool {
+            let Some(range) = self
+                .maybe_snippet_session
+                .as_ref()
+                .and_then(|it| it.get_current_range())
+            else {
+                return false;
+            };
+            let caret = self.get_caret(CaretKind::ScrollAdjusted);
+            let is_placeholder_selected = caret.row_index == range.row_index
+                && caret.col_index == range.end_col_index
+                && self.selection_map.map.len() == 1
+                && self.selection_map.get(range.row_index)
+                    == Some(&SelectionRange {
+                        start_display_col_index: range.start_col_index,
+                        end_display_col_index: range.end_col_index,
+                    });
+            if !is_placeholder_selected || range.get_col_count() == ch!(0) {
+                return false;
+            }
+
+            let row_width_before_edit = self.get_line_display_width(range.row_index);
+            let start = position! {
+                col_index: range.start_col_index,
+                row_index: range.row_index
+            };
+            self.delete_at(start, range.get_col_count(), viewport);
+            self.update_snippet_session_after_edit(
+                range.row_index,
+                row_width_before_edit,
+                self.len(),
+            );
+            true
+        }
+
+        /// Called after an [EditorEvent] changed the content, w/ the caret row, its width
+        /// & the line count from before the edit. The edit has to be in the current tab
+        /// stop (& not add or remove lines), otherwise the session ends. The current tab
+        /// stop grows (or shrinks) to fit the text that was typed, & its mirrors are
+        /// updated to match it.
+        pub fn update_snippet_session_after_edit(
+            &mut self,
+            caret_row_before_edit: ChUnit,
+            caret_row_width_before_edit: ChUnit,
+            line_count_before_edit: ChUnit,
+        ) {
+            let Some(mut session) = self.maybe_snippet_session.take() else {
+                return;
+            };
+            let Some(range) = session.get_current_range() else {
+                return;
+            };
+            let caret = self.get_caret(CaretKind::ScrollAdjusted);
+            let delta = ch!(@to_usize self.get_line_display_width(range.row_index))
+                as isize
+                - ch!(@to_usize caret_row_width_before_edit) as isize;
+            let new_end_col_index = shift_col_index(range.end_col_index, delta);
+            let is_edit_in_range = self.len() == line_count_before_edit
+                && caret_row_before_edit == range.row_index
+                && caret.row_index == range.row_index
+                && caret.col_index >= range.start_col_index
+                && caret.col_index <= new_end_col_index
+                && new_end_col_index >= range.start_col_index;
+            if !is_edit_in_range {
+                return;
+            }
+
+            // Grow the current tab stop, & shift the ranges after it.
+            let current_index = session.current_index;
+            session.shift_ranges_after(
+                range.row_index,
+                range.end_col_index,
+                delta,
+                (current_index, 0),
+            );
+            session.tab_stops[current_index].ranges[0].end_col_index = new_end_col_index;
+
+            // Copy its text to the mirrors, which are all after it (so the caret doesn't
+            // move).
+            let text = self.lines[ch!(@to_usize range.row_index)]
+                .clip_to_width(
+                    range.start_col_index,
+                    new_end_col_index - range.start_col_index,
+                )
+                .to_string();
+            let text_width = ch!(UnicodeString::str_display_width(&text));
+            let mirror_count = session.tab_stops[current_index].ranges.len();
+            for mirror_index in 1..mirror_count {
+                let mirror = session.tab_stops[current_index].ranges[mirror_index];
+                let Some(line) = self.lines.get(ch!(@to_usize mirror.row_index)) else {
+                    continue;
+                };
+                let new_line = format!(
+                    "{}{}{}",
+                    line.clip_to_width(ch!(0), mirror.start_col_index),
+                    text,
+                    line.clip_to_width(
+                        mirror.end_col_index,
+                        line.display_width - mirror.end_col_index
+                    )
+                );
+                self.lines[ch!(@to_usize mirror.row_index)] =
+                    UnicodeString::from(new_line);
+
+                let mirror_delta = ch!(@to_usize text_width) as isize
+                    - ch!(@to_usize mirror.get_col_count()) as isize;
+                session.shift_ranges_after(
+                    mirror.row_index,
+                    mirror.end_col_index,
+                    mirror_delta,
+                    (current_index, mirror_index),
+                );
+                session.tab_stops[current_index].ranges[mirror_index].end_col_index =
+                    mirror.start_col_index + text_width;
+            }
+
+            self.maybe_snippet_session = Some(session);
+        }
     }
 }
+pub use snippet_ops::*;
 
 mod debug_format_helpers {
     use super::*;
@@ -0,0 +1,261 @@
+/*
+ *   Copyright (c) 2022 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+//! [EditorLines] is the storage for the lines in an [EditorBuffer]. It is a rope, ie, a
+//! balanced tree of lines (an RRB tree from the [im] crate), instead of a [Vec]. This
+//! means that:
+//! 1. Inserting or removing lines is `O(log n)` anywhere in the buffer, and doesn't shift
+//!    all the lines after it (which is what makes a [Vec] slow at the top of a large
+//!    buffer).
+//! 2. Cloning is `O(1)`, and the clones share all the lines that aren't changed after
+//!    that. The [EditorBuffer] is cloned into the [r3bl_redux::Store] (& for undo / redo
+//!    snapshots), so this keeps snapshots cheap.
+//!
+//! The lines are still indexed by row, so the caret & scroll math (which is in terms of
+//! row & display col indices) doesn't have to change. The API mirrors the parts of [Vec]
+//! that the editor uses.
+
+use std::ops::{Bound, Index, IndexMut, Range, RangeBounds};
+
+use get_size::GetSize;
+use r3bl_rs_utils_core::*;
+use serde::{Deserialize, Serialize};
+
+/// The lines in an [EditorBuffer](crate::EditorBuffer). More info in the [module
+/// docs](self).
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct EditorLines {
+    lines: im::Vector<UnicodeString>,
+}
+
+mod constructor {
+    use super::*;
+
+    impl EditorLines {
+        pub fn new() -> Self { Self::default() }
+    }
+
+    impl From<Vec<UnicodeString>> for EditorLines {
+        fn from(lines: Vec<UnicodeString>) -> Self { lines.into_iter().collect() }
+    }
+
+    impl FromIterator<UnicodeString> for EditorLines {
+        fn from_iter<I: IntoIterator<Item = UnicodeString>>(iter: I) -> Self {
+            Self {
+                lines: iter.into_iter().collect(),
+            }
+        }
+    }
+}
+
+mod access_and_mutate {
+    use super::*;
+
+    impl EditorLines {
+        pub fn len(&self) -> usize { self.lines.len() }
+
+        pub fn is_empty(&self) -> bool { self.lines.is_empty() }
+
+        pub fn get(&self, row_index: usize) -> Option<&UnicodeString> {
+            self.lines.get(row_index)
+        }
+
+        pub fn get_mut(&mut self, row_index: usize) -> Option<&mut UnicodeString> {
+            self.lines.get_mut(row_index)
+        }
+
+        pub fn first(&self) -> Option<&UnicodeString> { self.lines.front() }
+
+        pub fn last(&self) -> Option<&UnicodeString> { self.lines.back() }
+
+        pub fn iter(&self) -> im::vector::Iter<'_, UnicodeString> { self.lines.iter() }
+
+        pub fn iter_mut(&mut self) -> im::vector::IterMut<'_, UnicodeString> {
+            self.lines.iter_mut()
+        }
+
+        pub fn push(&mut self, line: UnicodeString) { self.lines.push_back(line); }
+
+        pub fn pop(&mut self) -> Option<UnicodeString> { self.lines.pop_back() }
+
+        /// Panics if `row_index > len`, just like [Vec::insert].
+        pub fn insert(&mut self, row_index: usize, line: UnicodeString) {
+            self.lines.insert(row_index, line);
+        }
+
+        /// Panics if `row_index` is out of bounds, just like [Vec::remove].
+        pub fn remove(&mut self, row_index: usize) -> UnicodeString {
+            self.lines.remove(row_index)
+        }
+
+        /// Replace the lines in `range` w/ `replace_with`. Unlike [Vec::splice] this is
+        /// `O(log n)` (plus the number of lines that are inserted), and the removed lines
+        /// aren't returned.
+        pub fn splice(
+            &mut self,
+            range: impl RangeBounds<usize>,
+            replace_with: impl IntoIterator<Item = UnicodeString>,
+        ) {
+            let Range { start, end } = self.to_range(range);
+            let tail = self.lines.split_off(end);
+            self.lines.truncate(start);
+            self.lines.extend(replace_with);
+            self.lines.append(tail);
+        }
+
+        /// Remove the lines in `range` & return them.
+        pub fn drain(&mut self, range: impl RangeBounds<usize>) -> Vec<UnicodeString> {
+            let range = self.to_range(range);
+            self.lines.slice(range).into_iter().collect()
+        }
+
+        /// Returns a copy of the lines in `range`, eg: `&lines[range].to_vec()` for a [Vec].
+        pub fn clone_range(&self, range: impl RangeBounds<usize>) -> Vec<UnicodeString> {
+            let Range { start, end } = self.to_range(range);
+            self.lines
+                .iter()
+                .skip(start)
+                .take(end - start)
+                .cloned()
+                .collect()
+        }
+
+        fn to_range(&self, range: impl RangeBounds<usize>) -> Range<usize> {
+            let start = match range.start_bound() {
+                Bound::Included(it) => *it,
+                Bound::Excluded(it) => *it + 1,
+                Bound::Unbounded => 0,
+            };
+            let end = match range.end_bound() {
+                Bound::Included(it) => *it + 1,
+                Bound::Excluded(it) => *it,
+                Bound::Unbounded => self.len(),
+            };
+            start..end
+        }
+    }
+
+    impl Index<usize> for EditorLines {
+        type Output = UnicodeString;
+
+        fn index(&self, row_index: usize) -> &Self::Output { &self.lines[row_index] }
+    }
+
+    impl IndexMut<usize> for EditorLines {
+        fn index_mut(&mut self, row_index: usize) -> &mut Self::Output {
+            &mut self.lines[row_index]
+        }
+    }
+
+    impl<'a> IntoIterator for &'a EditorLines {
+        type Item = &'a UnicodeString;
+        type IntoIter = im::vector::Iter<'a, UnicodeString>;
+
+        fn into_iter(self) -> Self::IntoIter { self.lines.iter() }
+    }
+
+    impl PartialEq<Vec<UnicodeString>> for EditorLines {
+        fn eq(&self, other: &Vec<UnicodeString>) -> bool {
+            self.len() == other.len() && self.iter().eq(other.iter())
+        }
+    }
+
+    /// The lines that are shared w/ other clones are counted in each of them.
+    impl GetSize for EditorLines {
+        fn get_heap_size(&self) -> usize {
+            self.lines.iter().map(GetSize::get_size).sum()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_lines(texts: &[&str]) -> EditorLines {
+        texts.iter().map(|it| UnicodeString::from(*it)).collect()
+    }
+
+    fn to_strings(lines: &EditorLines) -> Vec<&str> {
+        lines.iter().map(|it| it.string.as_str()).collect()
+    }
+
+    #[test]
+    fn test_insert_and_remove() {
+        let mut lines = make_lines(&["a", "b", "c"]);
+        lines.insert(0, UnicodeString::from("x"));
+        lines.insert(4, UnicodeString::from("y"));
+        assert_eq2!(to_strings(&lines), vec!["x", "a", "b", "c", "y"]);
+        assert_eq2!(lines.remove(1).string, "a");
+        lines.push(UnicodeString::from("z"));
+        assert_eq2!(lines.pop().map(|it| it.string), Some("z".to_string()));
+        assert_eq2!(to_strings(&lines), vec!["x", "b", "c", "y"]);
+        lines[0] = UnicodeString::from("w");
+        assert_eq2!(lines[0].string, "w");
+        assert_eq2!(lines.first().map(|it| it.display_width), Some(ch!(1)));
+        assert_eq2!(lines.get(4), None);
+    }
+
+    #[test]
+    fn test_splice_drain_and_clone_range() {
+        let mut lines = make_lines(&["a", "b", "c", "d"]);
+
+        lines.splice(1..=2, vec![UnicodeString::from("x")]);
+        assert_eq2!(to_strings(&lines), vec!["a", "x", "d"]);
+
+        lines.splice(3..3, make_lines(&["e", "f"]).iter().cloned());
+        assert_eq2!(to_strings(&lines), vec!["a", "x", "d", "e", "f"]);
+
+        assert_eq2!(
+            lines.clone_range(1..=2),
+            vec![UnicodeString::from("x"), UnicodeString::from("d")]
+        );
+
+        let drained = lines.drain(..2);
+        assert_eq2!(drained.len(), 2);
+        assert_eq2!(to_strings(&lines), vec!["d", "e", "f"]);
+        assert_eq2!(
+            lines.drain(1..),
+            vec![UnicodeString::from("e"), UnicodeString::from("f")]
+        );
+        assert_eq2!(lines, vec![UnicodeString::from("d")]);
+    }
+
+    #[test]
+    fn test_clones_are_independent() {
+        let lines = make_lines(&["a", "b", "c"]);
+        let mut snapshot = lines.clone();
+        snapshot.insert(0, UnicodeString::from("x"));
+        snapshot[1] = UnicodeString::from("y");
+        assert_eq2!(to_strings(&lines), vec!["a", "b", "c"]);
+        assert_eq2!(to_strings(&snapshot), vec!["x", "y", "b", "c"]);
+    }
+
+    #[test]
+    fn test_serde_is_same_as_vec() {
+        let lines = make_lines(&["a", "b"]);
+        let vec = vec![UnicodeString::from("a"), UnicodeString::from("b")];
+        assert_eq2!(
+            serde_json::to_string(&lines).unwrap(),
+            serde_json::to_string(&vec).unwrap()
+        );
+        let lines_from_json: EditorLines =
+            serde_json::from_str(&serde_json::to_string(&vec).unwrap()).unwrap();
+        assert_eq2!(lines_from_json, lines);
+    }
+}
@@ -0,0 +1,317 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use get_size::GetSize;
+use r3bl_rs_utils_core::*;
+use serde::{Deserialize, Serialize};
+
+use crate::*;
+
+/// Range of rows (inclusive on both ends) in the buffer that are collapsed into a single
+/// summary line when rendered.
+///
+/// Note that both row indices are:
+/// - [Scroll adjusted](crate::editor_buffer_struct::CaretKind::ScrollAdjusted).
+/// - And not [raw](crate::editor_buffer_struct::CaretKind::Raw).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, GetSize)]
+pub struct FoldRange {
+    pub start_row_index: RowIndex,
+    pub end_row_index: RowIndex,
+}
+
+/// Keeps track of all the folded regions in the buffer. Folds can be nested (one fold
+/// fully contains another) but they can't partially overlap. When folds are nested the
+/// outermost one is used for rendering and caret navigation.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, GetSize)]
+pub struct FoldMap {
+    /// Sorted by `start_row_index` (ascending), then by `end_row_index` (descending), so
+    /// that outer folds come before the ones nested inside them.
+    pub folds: Vec<FoldRange>,
+}
+
+/// A single row in the viewport, which is either a line from the buffer, or the summary
+/// line for a folded region.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VisibleRow {
+    Line(RowIndex),
+    Folded(FoldRange),
+}
+
+pub mod fold_range_impl {
+    use super::*;
+
+    impl FoldRange {
+        pub fn new(start_row_index: RowIndex, end_row_index: RowIndex) -> Self {
+            Self {
+                start_row_index,
+                end_row_index,
+            }
+        }
+
+        pub fn contains(&self, row_index: RowIndex) -> bool {
+            row_index >= self.start_row_index && row_index <= self.end_row_index
+        }
+
+        /// The first row of the range is where the summary line is painted, all the rows
+        /// after it are hidden.
+        pub fn hides(&self, row_index: RowIndex) -> bool {
+            row_index > self.start_row_index && row_index <= self.end_row_index
+        }
+
+        pub fn contains_range(&self, other: &FoldRange) -> bool {
+            self.contains(other.start_row_index) && self.contains(other.end_row_index)
+        }
+
+        pub fn intersects(&self, other: &FoldRange) -> bool {
+            self.start_row_index <= other.end_row_index
+                && other.start_row_index <= self.end_row_index
+        }
+
+        pub fn row_count(&self) -> ChUnit {
+            self.end_row_index - self.start_row_index + 1
+        }
+    }
+}
+
+pub mod fold_map_impl {
+    use super::*;
+
+    impl FoldMap {
+        pub fn is_empty(&self) -> bool { self.folds.is_empty() }
+
+        pub fn clear(&mut self) { self.folds.clear(); }
+
+        /// Returns `false` if the `range` spans less than 2 rows, or if it already exists,
+        /// or if it partially overlaps an existing fold.
+        pub fn fold(&mut self, range: FoldRange) -> bool {
+            if range.end_row_index <= range.start_row_index {
+                return false;
+            }
+
+            for existing in &self.folds {
+                let is_nested =
+                    existing.contains_range(&range) || range.contains_range(existing);
+                if *existing == range || (existing.intersects(&range) && !is_nested) {
+                    return false;
+                }
+            }
+
+            self.folds.push(range);
+            self.folds.sort_by(|a, b| {
+                a.start_row_index
+                    .cmp(&b.start_row_index)
+                    .then(b.end_row_index.cmp(&a.end_row_index))
+            });
+
+            true
+        }
+
+        /// Remove the innermost fold that contains `row_index`. Returns `false` if there
+        /// isn't one.
+        pub fn unfold(&mut self, row_index: RowIndex) -> bool {
+            let maybe_innermost = self
+                .folds
+                .iter()
+                .enumerate()
+                .filter(|(_, fold)| fold.contains(row_index))
+                .min_by_key(|(_, fold)| fold.row_count())
+                .map(|(index, _)| index);
+
+            match maybe_innermost {
+                Some(index) => {
+                    self.folds.remove(index);
+                    true
+                }
+                None => false,
+            }
+        }
+
+        /// Remove all the folds that contain `row_index` (including nested ones).
+        pub fn unfold_all_containing(&mut self, row_index: RowIndex) {
+            self.folds.retain(|fold| !fold.contains(row_index));
+        }
+
+        pub fn get_outermost_fold_containing(
+            &self,
+            row_index: RowIndex,
+        ) -> Option<FoldRange> {
+            self.folds
+                .iter()
+                .find(|fold| fold.contains(row_index))
+                .copied()
+        }
+
+        /// Returns the outermost fold that hides `row_index` from view. The first row of a
+        /// fold isn't hidden, since the summary line is painted there.
+        pub fn get_fold_hiding_row(&self, row_index: RowIndex) -> Option<FoldRange> {
+            self.folds
+                .iter()
+                .find(|fold| fold.contains(row_index))
+                .filter(|fold| fold.hides(row_index))
+                .copied()
+        }
+
+        /// Lines have been inserted or removed right after `row_index` so shift all the
+        /// folds that start after it by `delta`.
+        pub fn shift_rows_after(&mut self, row_index: RowIndex, delta: isize) {
+            if delta == 0 {
+                return;
+            }
+            let amount = ch!(delta.unsigned_abs());
+            for fold in self.folds.iter_mut() {
+                if fold.start_row_index > row_index {
+                    if delta > 0 {
+                        fold.start_row_index += amount;
+                        fold.end_row_index += amount;
+                    } else {
+                        fold.start_row_index -= amount;
+                        fold.end_row_index -= amount;
+                    }
+                }
+            }
+        }
+
+        /// Iterate the rows that are visible in the viewport, starting w/ the buffer row at
+        /// `start_row_index` (the scroll offset row) and ending at `line_count`.
+        pub fn visible_rows(
+            &self,
+            start_row_index: RowIndex,
+            line_count: usize,
+        ) -> impl Iterator<Item = VisibleRow> + '_ {
+            let mut row_index = start_row_index;
+            let line_count = ch!(line_count);
+            std::iter::from_fn(move || {
+                if row_index >= line_count {
+                    return None;
+                }
+                match self.get_outermost_fold_containing(row_index) {
+                    Some(fold) => {
+                        row_index = fold.end_row_index + 1;
+                        Some(VisibleRow::Folded(fold))
+                    }
+                    None => {
+                        let it = VisibleRow::Line(row_index);
+                        row_index += 1;
+                        Some(it)
+                    }
+                }
+            })
+        }
+
+        /// Convert a buffer row index into a row index in the viewport, taking into account
+        /// the rows that are hidden by folds. Returns [None] if the row is hidden or above
+        /// `start_row_index` (the scroll offset row).
+        pub fn get_display_row_index(
+            &self,
+            start_row_index: RowIndex,
+            line_count: usize,
+            row_index: RowIndex,
+        ) -> Option<ChUnit> {
+            if row_index < start_row_index {
+                return None;
+            }
+
+            if self.is_empty() {
+                return Some(row_index - start_row_index);
+            }
+
+            for (display_row_index, visible_row) in
+                self.visible_rows(start_row_index, line_count).enumerate()
+            {
+                match visible_row {
+                    VisibleRow::Line(it) if it == row_index => {
+                        return Some(ch!(display_row_index))
+                    }
+                    VisibleRow::Folded(fold) if fold.start_row_index == row_index => {
+                        return Some(ch!(display_row_index))
+                    }
+                    VisibleRow::Folded(fold) if fold.hides(row_index) => return None,
+                    _ => {}
+                }
+            }
+
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_fold_map {
+    use super::*;
+
+    #[test]
+    fn test_fold_nested_and_overlapping() {
+        let mut fold_map = FoldMap::default();
+
+        // Single row ranges are rejected.
+        assert!(!fold_map.fold(FoldRange::new(ch!(1), ch!(1))));
+
+        assert!(fold_map.fold(FoldRange::new(ch!(2), ch!(10))));
+        // Duplicate.
+        assert!(!fold_map.fold(FoldRange::new(ch!(2), ch!(10))));
+        // Nested.
+        assert!(fold_map.fold(FoldRange::new(ch!(4), ch!(6))));
+        // Partial overlap.
+        assert!(!fold_map.fold(FoldRange::new(ch!(8), ch!(12))));
+
+        assert_eq2!(
+            fold_map.get_outermost_fold_containing(ch!(5)),
+            Some(FoldRange::new(ch!(2), ch!(10)))
+        );
+        assert_eq2!(fold_map.get_fold_hiding_row(ch!(2)), None);
+
+        // Unfold removes the innermost one first.
+        assert!(fold_map.unfold(ch!(5)));
+        assert_eq2!(fold_map.folds, vec![FoldRange::new(ch!(2), ch!(10))]);
+        assert!(fold_map.unfold(ch!(5)));
+        assert!(!fold_map.unfold(ch!(5)));
+    }
+
+    #[test]
+    fn test_fold_visible_rows() {
+        let mut fold_map = FoldMap::default();
+        fold_map.fold(FoldRange::new(ch!(1), ch!(3)));
+        fold_map.fold(FoldRange::new(ch!(1), ch!(2)));
+
+        let visible_rows = fold_map.visible_rows(ch!(0), 6).collect::<Vec<_>>();
+        assert_eq2!(
+            visible_rows,
+            vec![
+                VisibleRow::Line(ch!(0)),
+                VisibleRow::Folded(FoldRange::new(ch!(1), ch!(3))),
+                VisibleRow::Line(ch!(4)),
+                VisibleRow::Line(ch!(5)),
+            ]
+        );
+
+        assert_eq2!(
+            fold_map.get_display_row_index(ch!(0), 6, ch!(1)),
+            Some(ch!(1))
+        );
+        assert_eq2!(fold_map.get_display_row_index(ch!(0), 6, ch!(2)), None);
+        assert_eq2!(
+            fold_map.get_display_row_index(ch!(0), 6, ch!(5)),
+            Some(ch!(3))
+        );
+
+        fold_map.shift_rows_after(ch!(0), 2);
+        assert_eq2!(
+            fold_map.get_outermost_fold_containing(ch!(5)),
+            Some(FoldRange::new(ch!(3), ch!(5)))
+        );
+    }
+}
@@ -0,0 +1,130 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::fmt::{Display, Formatter, Result};
+
+use get_size::GetSize;
+use serde::{Deserialize, Serialize};
+
+/// The line break that is written between lines when the [crate::EditorBuffer] is saved.
+/// The lines themselves never contain the line break.
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize, GetSize,
+)]
+pub enum LineEnding {
+    /// `\n` (Unix, macOS).
+    #[default]
+    Lf,
+    /// `\r\n` (Windows).
+    CrLf,
+}
+
+/// The line endings found in some content by [LineEnding::detect].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DetectedLineEndings {
+    /// The majority of the line breaks. Ties (& content w/ no line breaks) pick
+    /// [LineEnding::Lf].
+    pub dominant: LineEnding,
+    /// The line break after each line, in order. There is one less of these than there
+    /// are lines.
+    pub line_breaks: Vec<LineEnding>,
+}
+
+pub mod line_ending_impl {
+    use super::*;
+
+    impl LineEnding {
+        pub fn as_str(&self) -> &'static str {
+            match self {
+                LineEnding::Lf => "\n",
+                LineEnding::CrLf => "\r\n",
+            }
+        }
+
+        /// Find the line break after each line in `content`, and the one that is used
+        /// the most.
+        pub fn detect(content: &str) -> DetectedLineEndings {
+            let line_breaks = content
+                .split_inclusive('\n')
+                .filter(|line| line.ends_with('\n'))
+                .map(|line| {
+                    if line.ends_with("\r\n") {
+                        LineEnding::CrLf
+                    } else {
+                        LineEnding::Lf
+                    }
+                })
+                .collect::<Vec<_>>();
+
+            let crlf_count = line_breaks
+                .iter()
+                .filter(|it| **it == LineEnding::CrLf)
+                .count();
+            let dominant = if crlf_count * 2 > line_breaks.len() {
+                LineEnding::CrLf
+            } else {
+                LineEnding::Lf
+            };
+
+            DetectedLineEndings {
+                dominant,
+                line_breaks,
+            }
+        }
+    }
+
+    /// Short name that can be displayed in a status bar, eg: `CRLF`.
+    impl Display for LineEnding {
+        fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+            match self {
+                LineEnding::Lf => write!(f, "LF"),
+                LineEnding::CrLf => write!(f, "CRLF"),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_rs_utils_core::*;
+
+    use super::*;
+
+    #[test]
+    fn test_detect() {
+        let it = LineEnding::detect("abc");
+        assert_eq2!(it.dominant, LineEnding::Lf);
+        assert!(it.line_breaks.is_empty());
+
+        let it = LineEnding::detect("a\r\nb\r\nc\n");
+        assert_eq2!(it.dominant, LineEnding::CrLf);
+        assert_eq2!(
+            it.line_breaks,
+            vec![LineEnding::CrLf, LineEnding::CrLf, LineEnding::Lf]
+        );
+
+        // Ties pick LF.
+        let it = LineEnding::detect("a\r\nb\n");
+        assert_eq2!(it.dominant, LineEnding::Lf);
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq2!(LineEnding::Lf.to_string(), "LF");
+        assert_eq2!(LineEnding::CrLf.to_string(), "CRLF");
+    }
+}
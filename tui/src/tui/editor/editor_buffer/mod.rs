@@ -18,9 +18,17 @@
 // Attach.
 pub mod editor_buffer_selection_support;
 pub mod editor_buffer_struct;
+pub mod editor_lines;
+pub mod fold_map;
+pub mod line_ending;
 pub mod selection_map;
+pub mod snippet;
 
 // Re-export.
 pub use editor_buffer_selection_support::*;
 pub use editor_buffer_struct::*;
+pub use editor_lines::*;
+pub use fold_map::*;
+pub use line_ending::*;
 pub use selection_map::*;
+pub use snippet::*;
@@ -0,0 +1,416 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+//! Snippets are templates w/ tab stops, eg: `fn ${1:name}($2) {\n    $0\n}`, that are
+//! inserted by [EditorBuffer::insert_snippet] (or [EditorEvent::InsertSnippet]).
+//!
+//! # Template syntax
+//!
+//! - `$1`, `$2`, ... are tab stops, & `$0` is the final caret position. If there is no
+//!   `$0`, it is at the end of the snippet.
+//! - `${1:placeholder}` is a tab stop w/ a placeholder, which is selected when the caret
+//!   gets to it (typing replaces it). Placeholders can't span lines.
+//! - A tab stop that is used more than once is mirrored, ie: the other occurrences are
+//!   updated as you type in the 1st one. They all start w/ the first placeholder that is
+//!   given for the tab stop.
+//! - `\$`, `\}` & `\\` are a literal `$`, `}` & `\`.
+//!
+//! # Snippet session
+//!
+//! After the snippet is inserted, the [SnippetSession] (which lives in the
+//! [EditorBuffer]) keeps track of the tab stops, so that Tab & Shift+Tab move between them
+//! (more info in [try_handle_snippet_input_event]). The session ends when:
+//! 1. The caret gets to `$0`.
+//! 2. Esc is pressed.
+//! 3. The buffer is edited outside of the current tab stop, or lines are added / removed.
+
+use get_size::GetSize;
+use r3bl_rs_utils_core::*;
+use serde::{Deserialize, Serialize};
+
+use crate::*;
+
+/// A range of display cols in a single row, where a tab stop is. `end_col_index` is
+/// exclusive, so the range is empty if the tab stop has no placeholder.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize, GetSize)]
+pub struct SnippetRange {
+    pub row_index: ChUnit,
+    pub start_col_index: ChUnit,
+    pub end_col_index: ChUnit,
+}
+
+/// All the occurrences of a tab stop. The 1st range is the one that the caret goes to,
+/// & the rest mirror its text.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, GetSize)]
+pub struct SnippetTabStop {
+    pub number: usize,
+    pub ranges: Vec<SnippetRange>,
+}
+
+/// The text of a snippet (w/out the tab stop syntax), & its tab stops in the order that
+/// they are visited. The ranges are relative to the start of the `text`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ParsedSnippet {
+    pub text: String,
+    pub tab_stops: Vec<SnippetTabStop>,
+}
+
+/// More info in the [module docs](self). The ranges are [scroll
+/// adjusted](CaretKind::ScrollAdjusted).
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, GetSize)]
+pub struct SnippetSession {
+    /// Sorted by number, except for `$0`, which is always last.
+    pub tab_stops: Vec<SnippetTabStop>,
+    pub current_index: usize,
+}
+
+mod snippet_impl {
+    use super::*;
+
+    impl SnippetRange {
+        pub fn get_col_count(&self) -> ChUnit {
+            self.end_col_index - self.start_col_index
+        }
+
+        /// Move a range that is relative to the start of a snippet to where the snippet
+        /// was inserted.
+        pub fn offset_by(&self, start: Position) -> Self {
+            let start_col_index = if self.row_index == ch!(0) {
+                start.col_index
+            } else {
+                ch!(0)
+            };
+            Self {
+                row_index: start.row_index + self.row_index,
+                start_col_index: start_col_index + self.start_col_index,
+                end_col_index: start_col_index + self.end_col_index,
+            }
+        }
+    }
+
+    impl SnippetSession {
+        pub fn get_current(&self) -> Option<&SnippetTabStop> {
+            self.tab_stops.get(self.current_index)
+        }
+
+        /// The range that the caret is in, while the current tab stop is edited.
+        pub fn get_current_range(&self) -> Option<SnippetRange> {
+            self.get_current()
+                .and_then(|tab_stop| tab_stop.ranges.first())
+                .copied()
+        }
+
+        /// Shift the ranges (other than `except`) that start at or after `col_index` in
+        /// `row_index` by `delta` cols, after the text there has changed width.
+        pub fn shift_ranges_after(
+            &mut self,
+            row_index: ChUnit,
+            col_index: ChUnit,
+            delta: isize,
+            except: (usize, usize),
+        ) {
+            for (tab_stop_index, tab_stop) in self.tab_stops.iter_mut().enumerate() {
+                for (range_index, range) in tab_stop.ranges.iter_mut().enumerate() {
+                    if (tab_stop_index, range_index) == except
+                        || range.row_index != row_index
+                        || range.start_col_index < col_index
+                    {
+                        continue;
+                    }
+                    range.start_col_index = shift_col_index(range.start_col_index, delta);
+                    range.end_col_index = shift_col_index(range.end_col_index, delta);
+                }
+            }
+        }
+    }
+
+    pub fn shift_col_index(col_index: ChUnit, delta: isize) -> ChUnit {
+        ch!((ch!(@to_usize col_index) as isize + delta).max(0) as usize)
+    }
+}
+pub use snippet_impl::*;
+
+/// Split the `template` into its text & tab stops. More info on the syntax in the [module
+/// docs](self).
+pub fn parse_snippet(template: &str) -> ParsedSnippet {
+    // The tab stop numbers & placeholders, in the order that they appear.
+    let mut chunks: Vec<SnippetChunk> = vec![];
+    let mut text = String::new();
+    let mut chars = template.chars().peekable();
+
+    while let Some(character) = chars.next() {
+        match character {
+            '\\' if matches!(chars.peek(), Some('$' | '}' | '\\')) => {
+                text.push(chars.next().unwrap_or_default());
+            }
+            '$' => match parse_tab_stop(&mut chars.clone()) {
+                Some((number, maybe_placeholder, consumed_char_count)) => {
+                    for _ in 0..consumed_char_count {
+                        chars.next();
+                    }
+                    chunks.push(SnippetChunk::Text(std::mem::take(&mut text)));
+                    chunks.push(SnippetChunk::TabStop(number, maybe_placeholder));
+                }
+                None => text.push(character),
+            },
+            _ => text.push(character),
+        }
+    }
+    chunks.push(SnippetChunk::Text(text));
+
+    // The 1st placeholder of each tab stop is used for all of its occurrences.
+    let get_placeholder = |number: usize| -> String {
+        chunks
+            .iter()
+            .find_map(|chunk| match chunk {
+                SnippetChunk::TabStop(it, Some(placeholder)) if *it == number => {
+                    Some(placeholder.clone())
+                }
+                _ => None,
+            })
+            .unwrap_or_default()
+    };
+
+    let mut parsed_snippet = ParsedSnippet::default();
+    let mut row_index = ch!(0);
+    let mut col_index = ch!(0);
+    for chunk in &chunks {
+        match chunk {
+            SnippetChunk::Text(text) => {
+                for (index, line) in text.split('\n').enumerate() {
+                    if index > 0 {
+                        row_index += 1;
+                        col_index = ch!(0);
+                    }
+                    col_index += ch!(UnicodeString::str_display_width(line));
+                }
+                parsed_snippet.text.push_str(text);
+            }
+            SnippetChunk::TabStop(number, _) => {
+                let placeholder = get_placeholder(*number);
+                let range = SnippetRange {
+                    row_index,
+                    start_col_index: col_index,
+                    end_col_index: col_index
+                        + ch!(UnicodeString::str_display_width(&placeholder)),
+                };
+                col_index = range.end_col_index;
+                parsed_snippet.text.push_str(&placeholder);
+
+                let tab_stops = &mut parsed_snippet.tab_stops;
+                match tab_stops.iter_mut().find(|it| it.number == *number) {
+                    Some(tab_stop) => tab_stop.ranges.push(range),
+                    None => tab_stops.push(SnippetTabStop {
+                        number: *number,
+                        ranges: vec![range],
+                    }),
+                }
+            }
+        }
+    }
+
+    // `$0` is visited last, & it is at the end of the snippet if it isn't in it.
+    let tab_stops = &mut parsed_snippet.tab_stops;
+    if !tab_stops.iter().any(|it| it.number == 0) {
+        tab_stops.push(SnippetTabStop {
+            number: 0,
+            ranges: vec![SnippetRange {
+                row_index,
+                start_col_index: col_index,
+                end_col_index: col_index,
+            }],
+        });
+    }
+    tab_stops.sort_by_key(|it| {
+        if it.number == 0 {
+            usize::MAX
+        } else {
+            it.number
+        }
+    });
+
+    parsed_snippet
+}
+
+enum SnippetChunk {
+    Text(String),
+    TabStop(usize, Option<String>),
+}
+
+/// `chars` starts right after a `$`. Returns the tab stop number, placeholder, and the
+/// number of chars (after the `$`) that make up the tab stop, or [None] if it isn't one
+/// (so the `$` is literal).
+fn parse_tab_stop(
+    chars: &mut std::iter::Peekable<std::str::Chars<'_>>,
+) -> Option<(usize, Option<String>, usize)> {
+    let mut consumed_char_count = 0;
+    let is_braced = chars.peek() == Some(&'{');
+    if is_braced {
+        chars.next();
+        consumed_char_count += 1;
+    }
+
+    let mut digits = String::new();
+    while let Some(character) = chars.peek().filter(|it| it.is_ascii_digit()) {
+        digits.push(*character);
+        chars.next();
+        consumed_char_count += 1;
+    }
+    let number = digits.parse::<usize>().ok()?;
+    if !is_braced {
+        return Some((number, None, consumed_char_count));
+    }
+
+    let mut maybe_placeholder = None;
+    if chars.peek() == Some(&':') {
+        chars.next();
+        consumed_char_count += 1;
+        let mut placeholder = String::new();
+        loop {
+            match chars.next()? {
+                '\n' => return None,
+                '}' => break,
+                '\\' if matches!(chars.peek(), Some('$' | '}' | '\\')) => {
+                    placeholder.push(chars.next()?);
+                    consumed_char_count += 2;
+                    continue;
+                }
+                character => placeholder.push(character),
+            }
+            consumed_char_count += 1;
+        }
+        maybe_placeholder = Some(placeholder);
+    } else if chars.next()? != '}' {
+        return None;
+    }
+    // The closing `}`.
+    consumed_char_count += 1;
+
+    Some((number, maybe_placeholder, consumed_char_count))
+}
+
+/// While there is a [SnippetSession], it gets a chance to handle the `input_event` (after
+/// the completion popup):
+/// - Tab moves to the next tab stop, & Shift+Tab to the previous one.
+/// - Esc ends the session (so in Vim's insert mode, a 2nd Esc is needed to go back to
+///   normal mode).
+///
+/// Returns the new buffer if the event was handled.
+pub fn try_handle_snippet_input_event(
+    editor_engine: &EditorEngine,
+    editor_buffer: &EditorBuffer,
+    input_event: &InputEvent,
+) -> Option<EditorBuffer> {
+    editor_buffer.get_snippet_session()?;
+
+    let InputEvent::Keyboard(key_press) = input_event else {
+        return None;
+    };
+    let special_key = match key_press {
+        KeyPress::Plain {
+            key: Key::SpecialKey(special_key),
+        } => *special_key,
+        // Some terminals report Shift+Tab as BackTab w/ the shift modifier.
+        KeyPress::WithModifiers {
+            key: Key::SpecialKey(SpecialKey::BackTab),
+            ..
+        } => SpecialKey::BackTab,
+        _ => return None,
+    };
+
+    let viewport = size! {
+        col_count: editor_engine.viewport_width(),
+        row_count: editor_engine.viewport_height()
+    };
+    let mut new_editor_buffer = editor_buffer.clone();
+    match special_key {
+        SpecialKey::Tab => {
+            new_editor_buffer.goto_next_snippet_tab_stop(viewport);
+        }
+        SpecialKey::BackTab => {
+            new_editor_buffer.goto_prev_snippet_tab_stop(viewport);
+        }
+        SpecialKey::Esc => new_editor_buffer.end_snippet_session(),
+        _ => return None,
+    }
+    Some(new_editor_buffer)
+}
+
+#[cfg(test)]
+mod test_snippet {
+    use super::*;
+
+    fn range(
+        row_index: usize,
+        start_col_index: usize,
+        end_col_index: usize,
+    ) -> SnippetRange {
+        SnippetRange {
+            row_index: ch!(row_index),
+            start_col_index: ch!(start_col_index),
+            end_col_index: ch!(end_col_index),
+        }
+    }
+
+    #[test]
+    fn test_parse_snippet() {
+        let parsed_snippet = parse_snippet("fn ${1:name}($2) {\n    $0\n}");
+        assert_eq2!(parsed_snippet.text, "fn name() {\n    \n}");
+        assert_eq2!(
+            parsed_snippet.tab_stops,
+            vec![
+                SnippetTabStop {
+                    number: 1,
+                    ranges: vec![range(0, 3, 7)],
+                },
+                SnippetTabStop {
+                    number: 2,
+                    ranges: vec![range(0, 8, 8)],
+                },
+                SnippetTabStop {
+                    number: 0,
+                    ranges: vec![range(1, 4, 4)],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_snippet_mirrors_and_escapes() {
+        // Mirrors get the placeholder, even if it is given later on.
+        let parsed_snippet = parse_snippet("$1 = ${1:x}; \\$2 ${3} $ ${x}");
+        assert_eq2!(parsed_snippet.text, "x = x; $2  $ ${x}");
+        assert_eq2!(
+            parsed_snippet.tab_stops,
+            vec![
+                SnippetTabStop {
+                    number: 1,
+                    ranges: vec![range(0, 0, 1), range(0, 4, 5)],
+                },
+                SnippetTabStop {
+                    number: 3,
+                    ranges: vec![range(0, 10, 10)],
+                },
+                // There is no `$0`, so it is at the end.
+                SnippetTabStop {
+                    number: 0,
+                    ranges: vec![range(0, 17, 17)],
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,138 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::{fmt::Debug, time::Duration};
+
+use r3bl_redux::*;
+use tokio::task::JoinHandle;
+
+use crate::*;
+
+/// This is called by the [AutoSaveTimer] when the editor has been idle (no content
+/// changes) for [EditorEngineConfig::auto_save]. The app decides where (and if) to persist
+/// the [EditorBuffer], typically by dispatching an action to the store.
+pub type OnEditorAutoSaveFn<S, A> = fn(&SharedStore<S, A>, FlexBoxId, EditorBuffer);
+
+/// Debounce timer used by [EditorComponent] to auto-save its buffer after a period of
+/// inactivity.
+/// - Each content change [restarts](AutoSaveTimer::restart) the timer, so it only fires
+///   once the user stops typing.
+/// - The timer is only running while there are unsaved changes (the buffer is dirty), once
+///   it fires it isn't restarted until the next content change.
+/// - The task is aborted when this struct is dropped, eg: when the [EditorComponent] is
+///   removed from the [ComponentRegistry].
+#[derive(Debug, Default)]
+pub struct AutoSaveTimer {
+    pub task_handle: Option<JoinHandle<()>>,
+}
+
+/// A cloned [EditorComponent] does not share the pending auto-save task of the original.
+impl Clone for AutoSaveTimer {
+    fn clone(&self) -> Self { Self::default() }
+}
+
+impl Drop for AutoSaveTimer {
+    fn drop(&mut self) { self.cancel(); }
+}
+
+impl AutoSaveTimer {
+    /// Cancels the pending task (if any) and starts a new one which calls `on_auto_save`
+    /// w/ the given `buffer` after `delay` has elapsed.
+    pub fn restart<S, A>(
+        &mut self,
+        delay: Duration,
+        shared_store: &SharedStore<S, A>,
+        id: FlexBoxId,
+        buffer: EditorBuffer,
+        on_auto_save: OnEditorAutoSaveFn<S, A>,
+    ) where
+        S: Default + Clone + PartialEq + Debug + Sync + Send + 'static,
+        A: Debug + Default + Clone + Sync + Send + 'static,
+    {
+        self.cancel();
+        let shared_store = shared_store.clone();
+        self.task_handle = Some(tokio::spawn(async move {
+            tokio::time::sleep(delay).await;
+            on_auto_save(&shared_store, id, buffer);
+        }));
+    }
+
+    /// Returns `true` if there are unsaved changes that the timer will fire for.
+    pub fn is_pending(&self) -> bool {
+        matches!(&self.task_handle, Some(handle) if !handle.is_finished())
+    }
+
+    pub fn cancel(&mut self) {
+        if let Some(handle) = self.task_handle.take() {
+            handle.abort();
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_auto_save_timer {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use r3bl_rs_utils_core::*;
+
+    use super::*;
+    use crate::test_dialog::mock_real_objects_for_dialog;
+
+    static SAVE_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+    fn on_auto_save(
+        _shared_store: &SharedStore<mock_real_objects_for_dialog::State, String>,
+        _id: FlexBoxId,
+        _buffer: EditorBuffer,
+    ) {
+        SAVE_COUNT.fetch_add(1, Ordering::SeqCst);
+    }
+
+    #[tokio::test]
+    async fn test_auto_save_debounce_and_cancel() {
+        let shared_store = mock_real_objects_for_dialog::create_store();
+        let delay = Duration::from_millis(20);
+        let mut timer = AutoSaveTimer::default();
+
+        // Restarting several times in a row only fires once.
+        for _ in 0..3 {
+            timer.restart(
+                delay,
+                &shared_store,
+                FlexBoxId::from(0),
+                EditorBuffer::new_empty(None),
+                on_auto_save,
+            );
+        }
+        assert!(timer.is_pending());
+        tokio::time::sleep(delay * 5).await;
+        assert_eq2!(SAVE_COUNT.load(Ordering::SeqCst), 1);
+        assert!(!timer.is_pending());
+
+        // Cancelled timers (eg: on unmount) don't fire.
+        timer.restart(
+            delay,
+            &shared_store,
+            FlexBoxId::from(0),
+            EditorBuffer::new_empty(None),
+            on_auto_save,
+        );
+        drop(timer);
+        tokio::time::sleep(delay * 5).await;
+        assert_eq2!(SAVE_COUNT.load(Ordering::SeqCst), 1);
+    }
+}
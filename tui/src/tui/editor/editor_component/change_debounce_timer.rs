@@ -0,0 +1,200 @@
+/*
+ *   Copyright (c) 2022 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::{fmt::{Debug, Formatter, Result},
+          sync::{Arc, Mutex},
+          time::Duration};
+
+use r3bl_redux::*;
+use tokio::task::JoinHandle;
+
+use crate::*;
+
+type FlushFn = Box<dyn FnOnce() + Send>;
+
+/// Debounce timer used by [EditorComponent] to call its
+/// [on_editor_buffer_change_handler](EditorComponent::on_editor_buffer_change_handler)
+/// only once there have been no changes for [EditorEngineConfig::change_debounce].
+/// - Each change [restarts](ChangeDebounceTimer::restart) the timer w/ the latest
+///   [EditorBuffer], so only the last one is passed to the handler.
+/// - The pending change can be [flushed](ChangeDebounceTimer::flush) right away, eg: when
+///   the editor loses focus or before the app saves. It is also flushed when this struct is
+///   dropped, so the final edit is never lost.
+/// - The handler is called at most once per restart, by whichever happens first.
+#[derive(Default)]
+pub struct ChangeDebounceTimer {
+    pub task_handle: Option<JoinHandle<()>>,
+    /// Shared w/ the task, so that either of them can take it.
+    maybe_flush_fn: Arc<Mutex<Option<FlushFn>>>,
+}
+
+/// A cloned [EditorComponent] does not share the pending change of the original.
+impl Clone for ChangeDebounceTimer {
+    fn clone(&self) -> Self { Self::default() }
+}
+
+impl Debug for ChangeDebounceTimer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_struct("ChangeDebounceTimer")
+            .field("is_pending", &self.is_pending())
+            .finish()
+    }
+}
+
+impl Drop for ChangeDebounceTimer {
+    fn drop(&mut self) { self.flush(); }
+}
+
+impl ChangeDebounceTimer {
+    /// Drops the pending change (if any), since `buffer` supersedes it, and starts a new
+    /// task which calls `on_change` w/ `buffer` after `delay` has elapsed.
+    pub fn restart<S, A>(
+        &mut self,
+        delay: Duration,
+        shared_store: &SharedStore<S, A>,
+        id: FlexBoxId,
+        buffer: EditorBuffer,
+        on_change: OnEditorBufferChangeFn<S, A>,
+    ) where
+        S: Default + Clone + PartialEq + Debug + Sync + Send + 'static,
+        A: Debug + Default + Clone + Sync + Send + 'static,
+    {
+        self.cancel_task();
+        let shared_store = shared_store.clone();
+        self.set_flush_fn(Some(Box::new(move || {
+            on_change(&shared_store, id, buffer);
+        })));
+
+        let maybe_flush_fn = self.maybe_flush_fn.clone();
+        self.task_handle = Some(tokio::spawn(async move {
+            tokio::time::sleep(delay).await;
+            run_flush_fn(&maybe_flush_fn);
+        }));
+    }
+
+    /// Returns `true` if there is a change that hasn't been passed to the handler yet.
+    pub fn is_pending(&self) -> bool {
+        matches!(self.maybe_flush_fn.lock(), Ok(it) if it.is_some())
+    }
+
+    /// Calls the handler w/ the pending change right away (if there is one).
+    pub fn flush(&mut self) {
+        self.cancel_task();
+        run_flush_fn(&self.maybe_flush_fn);
+    }
+
+    fn cancel_task(&mut self) {
+        if let Some(handle) = self.task_handle.take() {
+            handle.abort();
+        }
+    }
+
+    fn set_flush_fn(&self, maybe_flush_fn: Option<FlushFn>) {
+        if let Ok(mut it) = self.maybe_flush_fn.lock() {
+            *it = maybe_flush_fn;
+        }
+    }
+}
+
+/// The lock isn't held while the handler runs.
+fn run_flush_fn(maybe_flush_fn: &Mutex<Option<FlushFn>>) {
+    let maybe_flush_fn = match maybe_flush_fn.lock() {
+        Ok(mut it) => it.take(),
+        Err(_) => None,
+    };
+    if let Some(flush_fn) = maybe_flush_fn {
+        flush_fn();
+    }
+}
+
+#[cfg(test)]
+mod test_change_debounce_timer {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use r3bl_rs_utils_core::*;
+
+    use super::*;
+    use crate::test_dialog::mock_real_objects_for_dialog;
+
+    static CHANGE_COUNT: AtomicUsize = AtomicUsize::new(0);
+    static LAST_LINE_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+    fn on_change(
+        _shared_store: &SharedStore<mock_real_objects_for_dialog::State, String>,
+        _id: FlexBoxId,
+        buffer: EditorBuffer,
+    ) {
+        CHANGE_COUNT.fetch_add(1, Ordering::SeqCst);
+        LAST_LINE_COUNT.store(buffer.line_count(), Ordering::SeqCst);
+    }
+
+    fn make_buffer(line_count: usize) -> EditorBuffer {
+        let mut buffer = EditorBuffer::new_empty(None);
+        buffer.set_lines(vec![String::new(); line_count]);
+        buffer
+    }
+
+    #[tokio::test]
+    async fn test_change_debounce_and_flush() {
+        let shared_store = mock_real_objects_for_dialog::create_store();
+        let delay = Duration::from_millis(20);
+        let mut timer = ChangeDebounceTimer::default();
+
+        // Restarting several times in a row only fires once, w/ the last buffer.
+        for line_count in 1..=3 {
+            timer.restart(
+                delay,
+                &shared_store,
+                FlexBoxId::from(0),
+                make_buffer(line_count),
+                on_change,
+            );
+        }
+        assert!(timer.is_pending());
+        assert_eq2!(CHANGE_COUNT.load(Ordering::SeqCst), 0);
+        tokio::time::sleep(delay * 5).await;
+        assert_eq2!(CHANGE_COUNT.load(Ordering::SeqCst), 1);
+        assert_eq2!(LAST_LINE_COUNT.load(Ordering::SeqCst), 3);
+        assert!(!timer.is_pending());
+
+        // Flushing fires right away, and only once.
+        timer.restart(
+            delay,
+            &shared_store,
+            FlexBoxId::from(0),
+            make_buffer(4),
+            on_change,
+        );
+        timer.flush();
+        assert_eq2!(CHANGE_COUNT.load(Ordering::SeqCst), 2);
+        assert_eq2!(LAST_LINE_COUNT.load(Ordering::SeqCst), 4);
+        tokio::time::sleep(delay * 5).await;
+        assert_eq2!(CHANGE_COUNT.load(Ordering::SeqCst), 2);
+
+        // Dropping flushes the pending change.
+        timer.restart(
+            delay,
+            &shared_store,
+            FlexBoxId::from(0),
+            make_buffer(5),
+            on_change,
+        );
+        drop(timer);
+        assert_eq2!(CHANGE_COUNT.load(Ordering::SeqCst), 3);
+        assert_eq2!(LAST_LINE_COUNT.load(Ordering::SeqCst), 5);
+    }
+}
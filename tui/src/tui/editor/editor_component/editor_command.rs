@@ -0,0 +1,47 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::fmt::{Display, Formatter, Result};
+
+use serde::{Deserialize, Serialize};
+
+/// Something that the user asked the editor to do, which the app has to carry out, since
+/// it owns the IO (eg: where the buffer is saved). Keys are bound to these w/
+/// [EditorEvent::Command](crate::EditorEvent::Command), and then
+/// [EditorEngineApi::apply_event](crate::EditorEngineApi::apply_event) returns
+/// [EditorEngineApplyEventResult::Command](crate::EditorEngineApplyEventResult::Command)
+/// instead of changing the buffer. The [EditorComponent](crate::EditorComponent) passes it
+/// to the app w/ its [OnEditorCommandFn](crate::OnEditorCommandFn).
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum EditorCommand {
+    Save,
+    SaveAs,
+    Open,
+    Quit,
+}
+
+/// Short description that can be displayed in the keyboard help, eg: `Save`.
+impl Display for EditorCommand {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            EditorCommand::Save => write!(f, "Save"),
+            EditorCommand::SaveAs => write!(f, "Save as"),
+            EditorCommand::Open => write!(f, "Open"),
+            EditorCommand::Quit => write!(f, "Quit"),
+        }
+    }
+}
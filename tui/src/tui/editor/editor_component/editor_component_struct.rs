@@ -15,7 +15,7 @@
  *   limitations under the License.
  */
 
-use std::{borrow::Cow, fmt::Debug, sync::Arc};
+use std::{borrow::Cow, fmt::Debug, sync::Arc, time::Instant};
 
 use async_trait::async_trait;
 use r3bl_redux::*;
@@ -35,18 +35,59 @@ where
     pub editor_engine: EditorEngine,
     pub id: FlexBoxId,
     pub on_editor_buffer_change_handler: Option<OnEditorBufferChangeFn<S, A>>,
+    /// Only used if [EditorEngineConfig::auto_save] is set.
+    pub on_auto_save_handler: Option<OnEditorAutoSaveFn<S, A>>,
+    /// Called w/ the [EditorCommand]s (eg: save) that the app has to carry out. If this
+    /// isn't set, the input events that are bound to commands are propagated.
+    pub on_editor_command_handler: Option<OnEditorCommandFn<S, A>>,
+    pub auto_save_timer: AutoSaveTimer,
+    /// Only used if [EditorEngineConfig::change_debounce] is set.
+    pub change_debounce_timer: ChangeDebounceTimer,
+    /// Only set while the change handler is debounced. More info in
+    /// [DebouncedEditorBuffer].
+    pub maybe_debounced_buffer: Option<DebouncedEditorBuffer>,
+}
+
+/// When the change handler is debounced, the store doesn't have the latest buffer until
+/// the [ChangeDebounceTimer] fires (and the action that the handler dispatches is
+/// applied). In the meantime, the [EditorComponent] keeps using `latest_buffer` so that it
+/// stays responsive. This is dropped (and the buffer in the store is used again) once the
+/// latest buffer has been passed to the handler, and then the buffer in the store changes.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DebouncedEditorBuffer {
+    pub latest_buffer: EditorBuffer,
+    /// The buffer in the store the last time it was checked.
+    pub maybe_buffer_in_store: Option<EditorBuffer>,
 }
 
 pub type OnEditorBufferChangeFn<S, A> = fn(&SharedStore<S, A>, FlexBoxId, EditorBuffer);
 
+/// The buffer is passed along, so that the app has the content, eg: to save it.
+pub type OnEditorCommandFn<S, A> =
+    fn(&SharedStore<S, A>, FlexBoxId, EditorCommand, &EditorBuffer);
+
+/// The parts of the [EditorEngine] (outside of the [EditorBuffer]) that change what is
+/// painted. If an event doesn't change these or the buffer, there's nothing to render.
+type EditorEngineRenderState =
+    (InsertMode, KeyBindingState, CompletionState, ReplaceState);
+
+fn get_engine_render_state(editor_engine: &EditorEngine) -> EditorEngineRenderState {
+    (
+        editor_engine.insert_mode,
+        editor_engine.key_binding_state.clone(),
+        editor_engine.completion_state.clone(),
+        editor_engine.replace_state.clone(),
+    )
+}
+
 pub mod editor_component_impl {
     use super::*;
 
     #[async_trait]
     impl<S, A> Component<S, A> for EditorComponent<S, A>
     where
-        S: HasEditorBuffers + Default + Clone + PartialEq + Debug + Sync + Send,
-        A: Debug + Default + Clone + Sync + Send,
+        S: HasEditorBuffers + Default + Clone + PartialEq + Debug + Sync + Send + 'static,
+        A: Debug + Default + Clone + Sync + Send + 'static,
     {
         fn reset(&mut self) {}
 
@@ -75,23 +116,8 @@ pub mod editor_component_impl {
                     ..
                 } = args;
 
-                let cow_buffer: Cow<EditorBuffer> = {
-                    // Either: get existing buffer ref from state.
-                    if let Some(existing_buffer_ref) =
-                        state.get_editor_buffer(self.get_id())
-                    {
-                        Cow::Borrowed(existing_buffer_ref)
-                    }
-                    // Or: create a new owned buffer.
-                    else {
-                        Cow::Owned(EditorBuffer::new_empty(
-                            self.editor_engine
-                                .config_options
-                                .syntax_highlight
-                                .get_file_extension_for_new_empty_buffer(),
-                        ))
-                    }
-                };
+                let cow_buffer: Cow<EditorBuffer> = self.get_latest_editor_buffer(state);
+                let engine_state_before = get_engine_render_state(&self.editor_engine);
 
                 // BM: editor component processes input event here
                 // Try to apply the `input_event` to `editor_engine` to decide whether to
@@ -110,15 +136,51 @@ pub mod editor_component_impl {
                 )
                 .await?;
 
+                // Render again when the pending keys of a partial chord time out, so that
+                // they are flushed even if no other key is pressed.
+                if let (Some(chord_timeout), Some(_)) = (
+                    self.editor_engine.config_options.chord_timeout,
+                    self.editor_engine
+                        .key_binding_state
+                        .maybe_pending_keys_instant,
+                ) {
+                    shared_global_data
+                        .write()
+                        .await
+                        .request_render_tick(chord_timeout);
+                }
+
                 match result {
+                    EditorEngineApplyEventResult::Applied(new_buffer)
+                        if new_buffer == *cow_buffer
+                            && get_engine_render_state(&self.editor_engine)
+                                == engine_state_before =>
+                    {
+                        // Eg: left pressed w/ the caret at the start of the buffer.
+                        EventPropagation::ConsumedNoRender
+                    }
                     EditorEngineApplyEventResult::Applied(new_buffer) => {
-                        if let Some(on_change_handler) =
-                            self.on_editor_buffer_change_handler
-                        {
-                            on_change_handler(shared_store, self.get_id(), new_buffer);
+                        if new_buffer.get_lines() != cow_buffer.get_lines() {
+                            self.restart_auto_save_timer(shared_store, &new_buffer);
                         }
+                        self.call_on_change_handler(state, shared_store, new_buffer);
                         EventPropagation::Consumed
                     }
+                    EditorEngineApplyEventResult::Command(editor_command) => {
+                        match self.on_editor_command_handler {
+                            // The handler should dispatch an action if the state changes.
+                            Some(on_editor_command) => {
+                                on_editor_command(
+                                    shared_store,
+                                    self.id,
+                                    editor_command,
+                                    &cow_buffer,
+                                );
+                                EventPropagation::Consumed
+                            }
+                            None => EventPropagation::Propagate,
+                        }
+                    }
                     EditorEngineApplyEventResult::NotApplied => {
                         // Optional: handle any `input_event` not consumed by `editor_engine`.
                         EventPropagation::Propagate
@@ -146,18 +208,33 @@ pub mod editor_component_impl {
                 ..
             } = args;
 
-            let my_buffer: Cow<EditorBuffer> = {
-                if let Some(buffer) = state.get_editor_buffer(self.get_id()) {
-                    Cow::Borrowed(buffer)
-                } else {
-                    Cow::Owned(EditorBuffer::new_empty(
-                        self.editor_engine
-                            .config_options
-                            .syntax_highlight
-                            .get_file_extension_for_new_empty_buffer(),
-                    ))
-                }
-            };
+            // Don't hold on to the last edit once the user moves on to something else.
+            if !component_registry
+                .has_focus
+                .does_id_have_focus(self.get_id())
+            {
+                self.change_debounce_timer.flush();
+            }
+
+            self.apply_chord_timeout(
+                state,
+                shared_store,
+                shared_global_data,
+                component_registry,
+            );
+
+            // The terminal cursor shows the mode of the editor that has focus.
+            if component_registry
+                .has_focus
+                .does_id_have_focus(self.get_id())
+            {
+                shared_global_data
+                    .write()
+                    .await
+                    .set_cursor_shape(self.editor_engine.get_cursor_shape());
+            }
+
+            let my_buffer: Cow<EditorBuffer> = self.get_latest_editor_buffer(state);
 
             let render_args = EditorEngineArgs {
                 editor_engine: &mut self.editor_engine,
@@ -175,6 +252,164 @@ pub mod editor_component_impl {
 }
 pub use editor_component_impl::*;
 
+pub mod auto_save_impl {
+    use super::*;
+
+    impl<S, A> EditorComponent<S, A>
+    where
+        S: Debug + Default + Clone + PartialEq + Sync + Send + 'static,
+        A: Debug + Default + Clone + Sync + Send + 'static,
+    {
+        /// Called when the content of the buffer changes (which makes it dirty). Does nothing
+        /// if auto-save isn't configured, or if the buffer is read-only.
+        pub(super) fn restart_auto_save_timer(
+            &mut self,
+            shared_store: &SharedStore<S, A>,
+            new_buffer: &EditorBuffer,
+        ) {
+            let config_options = &self.editor_engine.config_options;
+            if config_options.edit_mode == EditMode::ReadOnly {
+                return;
+            }
+            let (Some(delay), Some(on_auto_save)) =
+                (config_options.auto_save, self.on_auto_save_handler)
+            else {
+                return;
+            };
+            self.auto_save_timer.restart(
+                delay,
+                shared_store,
+                self.id,
+                new_buffer.clone(),
+                on_auto_save,
+            );
+        }
+    }
+}
+
+pub mod chord_timeout_impl {
+    use super::*;
+
+    impl<S, A> EditorComponent<S, A>
+    where
+        S: HasEditorBuffers + Default + Clone + PartialEq + Debug + Sync + Send + 'static,
+        A: Debug + Default + Clone + Sync + Send + 'static,
+    {
+        /// Called on each render (there is no input event when the user stops typing). The
+        /// keys of a partial chord that has timed out are applied just like the ones that
+        /// are handled by [handle_event](Component::handle_event).
+        pub(super) fn apply_chord_timeout(
+            &mut self,
+            state: &S,
+            shared_store: &SharedStore<S, A>,
+            shared_global_data: &SharedGlobalData,
+            component_registry: &mut ComponentRegistry<S, A>,
+        ) {
+            let cow_buffer: Cow<EditorBuffer> = self.get_latest_editor_buffer(state);
+            let result = EditorEngineApi::apply_chord_timeout(
+                EditorEngineArgs {
+                    state,
+                    editor_buffer: &cow_buffer,
+                    component_registry,
+                    shared_global_data,
+                    shared_store,
+                    self_id: self.id,
+                    editor_engine: &mut self.editor_engine,
+                },
+                Instant::now(),
+            );
+
+            let EditorEngineApplyEventResult::Applied(new_buffer) = result else {
+                return;
+            };
+            if new_buffer == *cow_buffer {
+                return;
+            }
+            if new_buffer.get_lines() != cow_buffer.get_lines() {
+                self.restart_auto_save_timer(shared_store, &new_buffer);
+            }
+            self.call_on_change_handler(state, shared_store, new_buffer);
+        }
+    }
+}
+
+pub mod change_debounce_impl {
+    use super::*;
+
+    impl<S, A> EditorComponent<S, A>
+    where
+        S: HasEditorBuffers + Default + Clone + PartialEq + Debug + Sync + Send + 'static,
+        A: Debug + Default + Clone + Sync + Send + 'static,
+    {
+        /// Call this to pass the latest buffer to the change handler right away, eg: before
+        /// the app saves the buffer from the store. Does nothing if there is no pending
+        /// change. This is also done when the editor loses focus.
+        pub fn flush_buffer_change(&mut self) { self.change_debounce_timer.flush(); }
+
+        /// Calls the change handler right away, or restarts the [ChangeDebounceTimer] if
+        /// [EditorEngineConfig::change_debounce] is set.
+        pub(super) fn call_on_change_handler(
+            &mut self,
+            state: &S,
+            shared_store: &SharedStore<S, A>,
+            new_buffer: EditorBuffer,
+        ) {
+            let Some(on_change_handler) = self.on_editor_buffer_change_handler else {
+                return;
+            };
+            let Some(delay) = self.editor_engine.config_options.change_debounce else {
+                on_change_handler(shared_store, self.get_id(), new_buffer);
+                return;
+            };
+
+            self.change_debounce_timer.restart(
+                delay,
+                shared_store,
+                self.get_id(),
+                new_buffer.clone(),
+                on_change_handler,
+            );
+            self.maybe_debounced_buffer = Some(DebouncedEditorBuffer {
+                latest_buffer: new_buffer,
+                maybe_buffer_in_store: state.get_editor_buffer(self.get_id()).cloned(),
+            });
+        }
+
+        /// Returns the [DebouncedEditorBuffer::latest_buffer] if the store doesn't have it
+        /// yet. Otherwise returns the buffer in the store (or a new empty one).
+        pub(super) fn get_latest_editor_buffer<'a>(
+            &mut self,
+            state: &'a S,
+        ) -> Cow<'a, EditorBuffer> {
+            let maybe_buffer_in_store = state.get_editor_buffer(self.get_id());
+            let is_change_pending = self.change_debounce_timer.is_pending();
+
+            if let Some(debounced_buffer) = self.maybe_debounced_buffer.as_mut() {
+                let has_store_changed = debounced_buffer.maybe_buffer_in_store.as_ref()
+                    != maybe_buffer_in_store;
+                if is_change_pending || !has_store_changed {
+                    if has_store_changed {
+                        debounced_buffer.maybe_buffer_in_store =
+                            maybe_buffer_in_store.cloned();
+                    }
+                    return Cow::Owned(debounced_buffer.latest_buffer.clone());
+                }
+                self.maybe_debounced_buffer = None;
+            }
+
+            match maybe_buffer_in_store {
+                Some(buffer) => Cow::Borrowed(buffer),
+                None => Cow::Owned(EditorBuffer::new_empty(
+                    self.editor_engine
+                        .config_options
+                        .syntax_highlight
+                        .get_file_extension_for_new_empty_buffer(),
+                )),
+            }
+        }
+    }
+}
+
 pub mod constructor {
     use super::*;
 
@@ -194,9 +429,46 @@ pub mod constructor {
                 editor_engine: EditorEngine::new(config_options),
                 id,
                 on_editor_buffer_change_handler: Some(on_buffer_change),
+                on_auto_save_handler: None,
+                on_editor_command_handler: None,
+                auto_save_timer: Default::default(),
+                change_debounce_timer: Default::default(),
+                maybe_debounced_buffer: None,
             }
         }
 
+        /// The `on_auto_save` lambda is called w/ the latest buffer after the editor has been
+        /// idle for [EditorEngineConfig::auto_save]. The app still decides where to persist
+        /// it.
+        pub fn with_on_auto_save(
+            mut self,
+            on_auto_save: OnEditorAutoSaveFn<S, A>,
+        ) -> Self {
+            self.on_auto_save_handler = Some(on_auto_save);
+            self
+        }
+
+        /// The `on_editor_command` lambda is called w/ the [EditorCommand]s (eg: save,
+        /// open, quit) that the user asks for, so that the app can carry them out.
+        pub fn with_on_editor_command(
+            mut self,
+            on_editor_command: OnEditorCommandFn<S, A>,
+        ) -> Self {
+            self.on_editor_command_handler = Some(on_editor_command);
+            self
+        }
+
+        /// The `completion_provider` is called w/ the buffer & caret each time a word char is
+        /// typed, and the completions that it returns are shown in a popup at the caret. More
+        /// info in [CompletionState].
+        pub fn with_completion_provider(
+            mut self,
+            completion_provider: CompletionProviderFn,
+        ) -> Self {
+            self.editor_engine.maybe_completion_provider = Some(completion_provider);
+            self
+        }
+
         pub fn new_shared(
             id: FlexBoxId,
             config_options: EditorEngineConfig,
@@ -28,10 +28,15 @@ use crate::*;
 ///
 /// By providing a conversion from [InputEvent] to [EditorEvent] it becomes easier to write event
 /// handlers that consume [InputEvent] and then execute [EditorEvent] on an [EditorBuffer].
-#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+///
+/// These are also the commands that keys are bound to in a [KeyMap].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum EditorEvent {
     InsertChar(char),
     InsertString(String),
+    /// Insert a block of text that was pasted into the terminal (using bracketed paste).
+    /// It can span many lines & is inserted as is, in one edit.
+    Paste(String),
     InsertNewLine,
     Delete,
     Backspace,
@@ -42,9 +47,84 @@ pub enum EditorEvent {
     MoveCaret(CaretDirection),
     Resize(Size),
     Select(SelectionScope),
+    /// Fold the region that starts at the caret row, or unfold it if it is already folded.
+    ToggleFold,
+    MoveCaretToNextWord,
+    MoveCaretToPrevWord,
+    /// Delete the line at the caret.
+    DeleteLine,
+    /// Delete from the caret to the start of the next word.
+    DeleteWord,
+    /// Delete from the caret to the end of the line, or join the next line if the caret
+    /// is already at the end.
+    DeleteToEndOfLine,
+    /// Delete the selected text. Like the other delete events, the deleted text is pushed
+    /// onto the [KillRing].
+    DeleteSelection,
+    ClearSelection,
+    /// Insert the most recently deleted text from the [KillRing] at the caret.
+    Yank,
+    /// Move the caret to the given line number (which is 1 based) & center it in the
+    /// viewport. See [EditorBuffer::goto_line] for details.
+    GotoLine(usize),
+    /// Scroll so that the caret row is in the center of the viewport. See
+    /// [EditorBuffer::recenter_caret] for details.
+    RecenterCaret,
+    /// Save the caret position as the mark w/ the given name.
+    SetMark(char),
+    /// Move the caret to the mark w/ the given name (if it exists).
+    GotoMark(char),
+    /// Add a secondary caret on the line below the lowest caret.
+    AddCaretBelow,
+    /// Remove all the secondary carets, leaving just the primary one.
+    CollapseCarets,
+    /// Swap the chars on either side of the caret, & move the caret past them. See
+    /// [EditorBuffer::transpose_chars] for details.
+    TransposeChars,
+    /// Swap the word at the caret w/ the next one, & move the caret past them. See
+    /// [EditorBuffer::transpose_words] for details.
+    TransposeWords,
+    /// Insert a copy of the caret line (or the selected lines) below it. See
+    /// [EditorBuffer::duplicate_lines] for details.
+    DuplicateLine,
+    /// Move the caret line (or the selected lines) up or down by one line. See
+    /// [EditorBuffer::move_lines] for details.
+    MoveLine(CaretDirection),
+    /// Comment out the caret line (or the selected lines) using the line comment prefix
+    /// of the buffer's language, or uncomment them if they are all commented out. See
+    /// [EditorBuffer::toggle_line_comment] for details.
+    ToggleLineComment,
+    /// Indent the caret line (or the selected lines) by
+    /// [EditorEngineConfig::tab_width]. See [EditorBuffer::indent_lines] for details.
+    IndentLines,
+    /// Remove up to [EditorEngineConfig::tab_width] cols of leading whitespace from the
+    /// caret line (or the selected lines). See [EditorBuffer::dedent_lines] for details.
+    DedentLines,
+    /// Indent the selected lines (like [EditorEvent::IndentLines]), or insert
+    /// [EditorEngineConfig::get_indent_unit] at the caret if nothing is selected. This is
+    /// what Tab is bound to.
+    IndentSelectionOrInsertIndent,
+    /// Dedent the selected lines (like [EditorEvent::DedentLines]). If nothing is
+    /// selected the key isn't consumed, so that eg: the app can move the focus to the
+    /// previous component. This is what Shift+Tab is bound to.
+    DedentSelection,
+    /// Switch between [InsertMode::Insert] and [InsertMode::Overwrite].
+    ToggleInsertMode,
+    /// Open the replace bar, to replace the matches of a query one at a time or all at
+    /// once. More info in [try_handle_replace_input_event].
+    OpenReplace,
+    /// Use the given line ending after every line when the buffer is saved. See
+    /// [EditorBuffer::convert_line_endings] for details.
+    ConvertLineEndings(LineEnding),
+    /// Insert the snippet template at the caret & move the caret to its 1st tab stop. See
+    /// [EditorBuffer::insert_snippet] for details.
+    InsertSnippet(String),
+    /// Ask the app to do something that the editor can't, eg: save the buffer. This
+    /// doesn't change the buffer. More info in [EditorCommand].
+    Command(EditorCommand),
 }
 
-#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum SelectionScope {
     OneCharLeft,
     OneCharRight,
@@ -56,7 +136,7 @@ pub enum SelectionScope {
     End,
 }
 
-#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, GetSize)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, GetSize)]
 pub enum CaretDirection {
     Up,
     Down,
@@ -64,6 +144,112 @@ pub enum CaretDirection {
     Right,
 }
 
+/// These are used to show the [KeyMap] to the user in the [KeyboardHelpComponent].
+mod editor_event_help_impl {
+    use super::*;
+
+    impl EditorEvent {
+        /// The heading that this event is grouped under.
+        pub fn get_help_category(&self) -> &'static str {
+            match self {
+                EditorEvent::Home
+                | EditorEvent::End
+                | EditorEvent::PageDown
+                | EditorEvent::PageUp
+                | EditorEvent::MoveCaret(_)
+                | EditorEvent::MoveCaretToNextWord
+                | EditorEvent::MoveCaretToPrevWord
+                | EditorEvent::GotoLine(_)
+                | EditorEvent::SetMark(_)
+                | EditorEvent::GotoMark(_) => "Navigation",
+                EditorEvent::Select(_)
+                | EditorEvent::ClearSelection
+                | EditorEvent::AddCaretBelow
+                | EditorEvent::CollapseCarets => "Selection",
+                EditorEvent::ToggleFold
+                | EditorEvent::RecenterCaret
+                | EditorEvent::Resize(_) => "View",
+                EditorEvent::Command(_) => "File",
+                _ => "Editing",
+            }
+        }
+
+        pub fn get_help_description(&self) -> String {
+            match self {
+                EditorEvent::InsertChar(character) => format!("Insert '{character}'"),
+                EditorEvent::InsertString(text) => format!("Insert \"{text}\""),
+                EditorEvent::Paste(_) => "Paste".to_string(),
+                EditorEvent::InsertNewLine => "Insert new line".to_string(),
+                EditorEvent::Delete => "Delete char at caret".to_string(),
+                EditorEvent::Backspace => "Delete char before caret".to_string(),
+                EditorEvent::Home => "Go to start of line".to_string(),
+                EditorEvent::End => "Go to end of line".to_string(),
+                EditorEvent::PageDown => "Page down".to_string(),
+                EditorEvent::PageUp => "Page up".to_string(),
+                EditorEvent::MoveCaret(direction) => format!("Move caret {direction:?}"),
+                EditorEvent::Resize(_) => "Resize".to_string(),
+                EditorEvent::Select(SelectionScope::OneCharLeft) => {
+                    "Select char left".to_string()
+                }
+                EditorEvent::Select(SelectionScope::OneCharRight) => {
+                    "Select char right".to_string()
+                }
+                EditorEvent::Select(SelectionScope::OneLineUp) => {
+                    "Select line up".to_string()
+                }
+                EditorEvent::Select(SelectionScope::OneLineDown) => {
+                    "Select line down".to_string()
+                }
+                EditorEvent::Select(SelectionScope::PageUp) => {
+                    "Select page up".to_string()
+                }
+                EditorEvent::Select(SelectionScope::PageDown) => {
+                    "Select page down".to_string()
+                }
+                EditorEvent::Select(SelectionScope::Home) => {
+                    "Select to start of line".to_string()
+                }
+                EditorEvent::Select(SelectionScope::End) => {
+                    "Select to end of line".to_string()
+                }
+                EditorEvent::ToggleFold => "Fold / unfold region".to_string(),
+                EditorEvent::MoveCaretToNextWord => "Go to next word".to_string(),
+                EditorEvent::MoveCaretToPrevWord => "Go to previous word".to_string(),
+                EditorEvent::DeleteLine => "Delete line".to_string(),
+                EditorEvent::DeleteWord => "Delete to next word".to_string(),
+                EditorEvent::DeleteToEndOfLine => "Delete to end of line".to_string(),
+                EditorEvent::DeleteSelection => "Delete selection".to_string(),
+                EditorEvent::ClearSelection => "Clear selection".to_string(),
+                EditorEvent::Yank => "Yank (paste last deleted text)".to_string(),
+                EditorEvent::GotoLine(line_number) => format!("Go to line {line_number}"),
+                EditorEvent::RecenterCaret => "Center caret line".to_string(),
+                EditorEvent::SetMark(name) => format!("Set mark '{name}'"),
+                EditorEvent::GotoMark(name) => format!("Go to mark '{name}'"),
+                EditorEvent::AddCaretBelow => "Add caret below".to_string(),
+                EditorEvent::CollapseCarets => "Remove extra carets".to_string(),
+                EditorEvent::TransposeChars => "Transpose chars".to_string(),
+                EditorEvent::TransposeWords => "Transpose words".to_string(),
+                EditorEvent::DuplicateLine => "Duplicate line".to_string(),
+                EditorEvent::MoveLine(direction) => format!("Move line {direction:?}"),
+                EditorEvent::ToggleLineComment => "Comment / uncomment line".to_string(),
+                EditorEvent::IndentLines => "Indent line".to_string(),
+                EditorEvent::DedentLines => "Dedent line".to_string(),
+                EditorEvent::IndentSelectionOrInsertIndent => {
+                    "Indent selection / insert indent".to_string()
+                }
+                EditorEvent::DedentSelection => "Dedent selection".to_string(),
+                EditorEvent::ToggleInsertMode => "Toggle insert / overwrite".to_string(),
+                EditorEvent::OpenReplace => "Find and replace".to_string(),
+                EditorEvent::ConvertLineEndings(line_ending) => {
+                    format!("Convert line endings to {line_ending}")
+                }
+                EditorEvent::InsertSnippet(_) => "Insert snippet".to_string(),
+                EditorEvent::Command(editor_command) => editor_command.to_string(),
+            }
+        }
+    }
+}
+
 impl TryFrom<&InputEvent> for EditorEvent {
     type Error = String;
 
@@ -75,104 +261,72 @@ impl TryFrom<&InputEvent> for EditorEvent {
             ));
         });
 
-        match input_event {
-            // Selection events.
-            InputEvent::Keyboard(KeyPress::WithModifiers {
-                key: Key::SpecialKey(SpecialKey::Right),
-                mask: ModifierKeysMask::SHIFT,
-            }) => Ok(EditorEvent::Select(SelectionScope::OneCharRight)),
-
-            InputEvent::Keyboard(KeyPress::WithModifiers {
-                key: Key::SpecialKey(SpecialKey::Left),
-                mask: ModifierKeysMask::SHIFT,
-            }) => Ok(EditorEvent::Select(SelectionScope::OneCharLeft)),
-
-            InputEvent::Keyboard(KeyPress::WithModifiers {
-                key: Key::SpecialKey(SpecialKey::Down),
-                mask: ModifierKeysMask::SHIFT,
-            }) => Ok(EditorEvent::Select(SelectionScope::OneLineDown)),
-
-            InputEvent::Keyboard(KeyPress::WithModifiers {
-                key: Key::SpecialKey(SpecialKey::Up),
-                mask: ModifierKeysMask::SHIFT,
-            }) => Ok(EditorEvent::Select(SelectionScope::OneLineUp)),
-
-            InputEvent::Keyboard(KeyPress::WithModifiers {
-                key: Key::SpecialKey(SpecialKey::PageUp),
-                mask: ModifierKeysMask::SHIFT,
-            }) => Ok(EditorEvent::Select(SelectionScope::PageUp)),
-
-            InputEvent::Keyboard(KeyPress::WithModifiers {
-                key: Key::SpecialKey(SpecialKey::PageDown),
-                mask: ModifierKeysMask::SHIFT,
-            }) => Ok(EditorEvent::Select(SelectionScope::PageDown)),
-
-            InputEvent::Keyboard(KeyPress::WithModifiers {
-                key: Key::SpecialKey(SpecialKey::Home),
-                mask: ModifierKeysMask::SHIFT,
-            }) => Ok(EditorEvent::Select(SelectionScope::Home)),
-
-            InputEvent::Keyboard(KeyPress::WithModifiers {
-                key: Key::SpecialKey(SpecialKey::End),
-                mask: ModifierKeysMask::SHIFT,
-            }) => Ok(EditorEvent::Select(SelectionScope::End)),
-
-            // Other events.
-            InputEvent::Keyboard(KeyPress::Plain {
-                key: Key::SpecialKey(SpecialKey::PageDown),
-            }) => Ok(EditorEvent::PageDown),
-
-            InputEvent::Keyboard(KeyPress::Plain {
-                key: Key::SpecialKey(SpecialKey::PageUp),
-            }) => Ok(EditorEvent::PageUp),
-
-            InputEvent::Keyboard(KeyPress::Plain {
-                key: Key::SpecialKey(SpecialKey::Home),
-            }) => Ok(EditorEvent::Home),
-
-            InputEvent::Keyboard(KeyPress::Plain {
-                key: Key::SpecialKey(SpecialKey::End),
-            }) => Ok(EditorEvent::End),
-
-            InputEvent::Resize(size) => Ok(EditorEvent::Resize(*size)),
-
-            InputEvent::Keyboard(KeyPress::Plain {
-                key: Key::Character(character),
-            }) => Ok(Self::InsertChar(*character)),
-
-            InputEvent::Keyboard(KeyPress::Plain {
-                key: Key::SpecialKey(SpecialKey::Enter),
-            }) => Ok(Self::InsertNewLine),
-
-            InputEvent::Keyboard(KeyPress::Plain {
-                key: Key::SpecialKey(SpecialKey::Delete),
-            }) => Ok(Self::Delete),
-
-            InputEvent::Keyboard(KeyPress::Plain {
-                key: Key::SpecialKey(SpecialKey::Backspace),
-            }) => Ok(Self::Backspace),
-
-            InputEvent::Keyboard(KeyPress::Plain {
-                key: Key::SpecialKey(SpecialKey::Up),
-            }) => Ok(Self::MoveCaret(CaretDirection::Up)),
-
-            InputEvent::Keyboard(KeyPress::Plain {
-                key: Key::SpecialKey(SpecialKey::Down),
-            }) => Ok(Self::MoveCaret(CaretDirection::Down)),
-
-            InputEvent::Keyboard(KeyPress::Plain {
-                key: Key::SpecialKey(SpecialKey::Left),
-            }) => Ok(Self::MoveCaret(CaretDirection::Left)),
-
-            InputEvent::Keyboard(KeyPress::Plain {
-                key: Key::SpecialKey(SpecialKey::Right),
-            }) => Ok(Self::MoveCaret(CaretDirection::Right)),
-            _ => Err(format!("Invalid input event: {input_event:?}")),
-        }
+        // The keys are looked up in the default key map, use
+        // [EditorEngineConfig::key_map] to apply the user's bindings.
+        KeyMap::default()
+            .translate(input_event)
+            .ok_or_else(|| format!("Invalid input event: {input_event:?}"))
     }
 }
 
 impl EditorEvent {
+    /// Some events depend on the state of the buffer, eg: Tab indents the selected lines
+    /// or inserts an indent at the caret, & [EditorEvent::CollapseCarets] only applies
+    /// when there are secondary carets. Returns [None] if `self` doesn't apply, in which
+    /// case [EditorEngineApi::apply_event] doesn't consume the key, so that it reaches
+    /// the app (eg: Esc to close a dialog, or Shift+Tab to move the focus).
+    pub fn resolve(
+        self,
+        editor_buffer: &EditorBuffer,
+        config_options: &EditorEngineConfig,
+    ) -> Option<EditorEvent> {
+        match self {
+            EditorEvent::CollapseCarets if !editor_buffer.has_secondary_carets() => None,
+            EditorEvent::IndentSelectionOrInsertIndent => {
+                match editor_buffer.has_selection() {
+                    true => Some(EditorEvent::IndentLines),
+                    false => {
+                        Some(EditorEvent::InsertString(config_options.get_indent_unit()))
+                    }
+                }
+            }
+            EditorEvent::DedentSelection => editor_buffer
+                .has_selection()
+                .then_some(EditorEvent::DedentLines),
+            _ => Some(self),
+        }
+    }
+
+    /// Whether `self` changes the content of the buffer, eg: to ignore the keys that edit
+    /// in Vim's normal mode.
+    pub fn is_edit(&self) -> bool {
+        matches!(
+            self,
+            EditorEvent::InsertChar(_)
+                | EditorEvent::InsertString(_)
+                | EditorEvent::Paste(_)
+                | EditorEvent::InsertNewLine
+                | EditorEvent::Delete
+                | EditorEvent::Backspace
+                | EditorEvent::DeleteLine
+                | EditorEvent::DeleteWord
+                | EditorEvent::DeleteToEndOfLine
+                | EditorEvent::DeleteSelection
+                | EditorEvent::Yank
+                | EditorEvent::TransposeChars
+                | EditorEvent::TransposeWords
+                | EditorEvent::DuplicateLine
+                | EditorEvent::MoveLine(_)
+                | EditorEvent::ToggleLineComment
+                | EditorEvent::IndentLines
+                | EditorEvent::DedentLines
+                | EditorEvent::IndentSelectionOrInsertIndent
+                | EditorEvent::DedentSelection
+                | EditorEvent::ConvertLineEndings(_)
+                | EditorEvent::InsertSnippet(_)
+        )
+    }
+
     pub fn apply_editor_event<S, A>(
         editor_engine: &mut EditorEngine,
         editor_buffer: &mut EditorBuffer,
@@ -184,7 +338,84 @@ impl EditorEvent {
         S: Debug + Default + Clone + PartialEq + Sync + Send,
         A: Debug + Default + Clone + Sync + Send,
     {
+        let Some(editor_event) =
+            editor_event.resolve(editor_buffer, &editor_engine.config_options)
+        else {
+            return;
+        };
+        let viewport = size! {
+            col_count: editor_engine.viewport_width(),
+            row_count: editor_engine.viewport_height()
+        };
+
+        let is_content_change = matches!(
+            editor_event,
+            EditorEvent::InsertChar(_)
+                | EditorEvent::InsertString(_)
+                | EditorEvent::Paste(_)
+                | EditorEvent::InsertNewLine
+                | EditorEvent::Delete
+                | EditorEvent::Backspace
+                | EditorEvent::DeleteLine
+                | EditorEvent::DeleteWord
+                | EditorEvent::DeleteToEndOfLine
+                | EditorEvent::DeleteSelection
+                | EditorEvent::Yank
+                | EditorEvent::InsertSnippet(_)
+        );
+        let is_caret_row_removed = matches!(editor_event, EditorEvent::DeleteLine);
+        let is_snippet_inserted = matches!(editor_event, EditorEvent::InsertSnippet(_));
+        let line_count_before_edit = editor_buffer.len();
+        let caret_row_before_edit =
+            editor_buffer.get_caret(CaretKind::ScrollAdjusted).row_index;
+
+        // Typing (or deleting) over the selected placeholder of a snippet tab stop replaces
+        // it, so it is deleted before the caret row width is saved.
+        let is_snippet_placeholder_deleted = matches!(
+            editor_event,
+            EditorEvent::InsertChar(_)
+                | EditorEvent::InsertString(_)
+                | EditorEvent::Paste(_)
+                | EditorEvent::Backspace
+                | EditorEvent::Delete
+        ) && !editor_buffer.has_secondary_carets()
+            && editor_buffer.delete_selected_snippet_placeholder(viewport);
+        let caret_row_width_before_edit =
+            editor_buffer.get_line_display_width(caret_row_before_edit);
+
+        if is_content_change {
+            editor_buffer.mark_dirty();
+            EditorEngineInternalApi::unfold_rows_touched_by_edit(
+                editor_buffer,
+                editor_engine,
+                &editor_event,
+            );
+        }
+
+        // Edits that can be applied at all the carets are. Any other edit collapses the
+        // carets, since it can move lines around.
+        if editor_buffer.has_secondary_carets() && is_content_change {
+            if EditorEngineInternalApi::is_multi_caret_edit(&editor_event) {
+                EditorEngineInternalApi::apply_edit_at_all_carets(
+                    editor_buffer,
+                    editor_engine,
+                    &editor_event,
+                );
+                editor_buffer.end_snippet_session();
+                return;
+            }
+            editor_buffer.clear_secondary_carets();
+        }
+        let maybe_caret_motion = matches!(
+            editor_event,
+            EditorEvent::MoveCaret(_) | EditorEvent::Home | EditorEvent::End
+        )
+        .then(|| editor_event.clone());
+
         match editor_event {
+            // The placeholder that was deleted is all that these delete.
+            EditorEvent::Backspace | EditorEvent::Delete
+                if is_snippet_placeholder_deleted => {}
             EditorEvent::InsertChar(character) => {
                 EditorEngineInternalApi::insert_str_at_caret(
                     EditorArgsMut {
@@ -194,6 +425,15 @@ impl EditorEvent {
                     &String::from(character),
                 )
             }
+            EditorEvent::Paste(text) => {
+                EditorEngineInternalApi::insert_multiline_str_at_caret(
+                    EditorArgsMut {
+                        editor_buffer,
+                        editor_engine,
+                    },
+                    &text.replace("\r\n", "\n").replace('\r', "\n"),
+                );
+            }
             EditorEvent::InsertNewLine => {
                 EditorEngineInternalApi::insert_new_line_at_caret(EditorArgsMut {
                     editor_buffer,
@@ -229,6 +469,11 @@ impl EditorEvent {
                         SelectMode::Disabled,
                     ),
                 };
+                EditorEngineInternalApi::skip_over_folded_rows(
+                    editor_buffer,
+                    editor_engine,
+                    direction,
+                );
             }
             EditorEvent::InsertString(chunk) => {
                 EditorEngineInternalApi::insert_str_at_caret(
@@ -252,6 +497,11 @@ impl EditorEvent {
                     editor_engine,
                     SelectMode::Disabled,
                 );
+                EditorEngineInternalApi::skip_over_folded_rows(
+                    editor_buffer,
+                    editor_engine,
+                    CaretDirection::Up,
+                );
             }
             EditorEvent::End => {
                 EditorEngineInternalApi::end(
@@ -259,6 +509,11 @@ impl EditorEvent {
                     editor_engine,
                     SelectMode::Disabled,
                 );
+                EditorEngineInternalApi::skip_over_folded_rows(
+                    editor_buffer,
+                    editor_engine,
+                    CaretDirection::Down,
+                );
             }
             EditorEvent::PageDown => {
                 EditorEngineInternalApi::page_down(
@@ -266,6 +521,11 @@ impl EditorEvent {
                     editor_engine,
                     SelectMode::Disabled,
                 );
+                EditorEngineInternalApi::skip_over_folded_rows(
+                    editor_buffer,
+                    editor_engine,
+                    CaretDirection::Down,
+                );
             }
             EditorEvent::PageUp => {
                 EditorEngineInternalApi::page_up(
@@ -273,6 +533,11 @@ impl EditorEvent {
                     editor_engine,
                     SelectMode::Disabled,
                 );
+                EditorEngineInternalApi::skip_over_folded_rows(
+                    editor_buffer,
+                    editor_engine,
+                    CaretDirection::Up,
+                );
             }
             EditorEvent::Select(selection_scope) => match selection_scope {
                 SelectionScope::OneCharRight => {
@@ -332,7 +597,225 @@ impl EditorEvent {
                     );
                 }
             },
+            EditorEvent::ToggleFold => {
+                EditorEngineInternalApi::toggle_fold_at_caret(
+                    editor_buffer,
+                    editor_engine,
+                );
+            }
+            EditorEvent::MoveCaretToNextWord => {
+                EditorEngineInternalApi::next_word(editor_buffer, editor_engine);
+                EditorEngineInternalApi::skip_over_folded_rows(
+                    editor_buffer,
+                    editor_engine,
+                    CaretDirection::Right,
+                );
+            }
+            EditorEvent::MoveCaretToPrevWord => {
+                EditorEngineInternalApi::prev_word(editor_buffer, editor_engine);
+                EditorEngineInternalApi::skip_over_folded_rows(
+                    editor_buffer,
+                    editor_engine,
+                    CaretDirection::Left,
+                );
+            }
+            EditorEvent::DeleteLine => {
+                if let Some(text) = EditorEngineInternalApi::delete_line_at_caret(
+                    editor_buffer,
+                    editor_engine,
+                ) {
+                    editor_engine.key_binding_state.kill_ring.push(text);
+                }
+            }
+            EditorEvent::DeleteWord => {
+                if let Some(text) = EditorEngineInternalApi::delete_word_at_caret(
+                    editor_buffer,
+                    editor_engine,
+                ) {
+                    editor_engine.key_binding_state.kill_ring.push(text);
+                }
+            }
+            EditorEvent::DeleteToEndOfLine => {
+                if let Some(text) = EditorEngineInternalApi::delete_to_end_of_line(
+                    editor_buffer,
+                    editor_engine,
+                ) {
+                    editor_engine.key_binding_state.kill_ring.push(text);
+                }
+            }
+            EditorEvent::DeleteSelection => {
+                if let Some(text) =
+                    EditorEngineInternalApi::delete_selected(editor_buffer, editor_engine)
+                {
+                    editor_engine.key_binding_state.kill_ring.push(text);
+                }
+            }
+            EditorEvent::Yank => {
+                if let Some(text) =
+                    editor_engine.key_binding_state.kill_ring.get_top().cloned()
+                {
+                    EditorEngineInternalApi::insert_multiline_str_at_caret(
+                        EditorArgsMut {
+                            editor_buffer,
+                            editor_engine,
+                        },
+                        &text,
+                    );
+                }
+            }
+            EditorEvent::ClearSelection => {
+                editor_buffer.clear_selection();
+            }
+            EditorEvent::GotoLine(line_number) => {
+                editor_buffer.goto_line(line_number, viewport);
+            }
+            EditorEvent::RecenterCaret => {
+                editor_buffer
+                    .recenter_caret(viewport, editor_engine.config_options.overscroll);
+            }
+            EditorEvent::SetMark(name) => {
+                let caret = editor_buffer.get_caret(CaretKind::ScrollAdjusted);
+                editor_buffer.set_mark(name, caret);
+            }
+            EditorEvent::GotoMark(name) => {
+                editor_buffer.goto_mark(name, viewport);
+            }
+            EditorEvent::AddCaretBelow => {
+                EditorEngineInternalApi::add_caret_below(editor_buffer, editor_engine);
+            }
+            EditorEvent::CollapseCarets => {
+                editor_buffer.clear_secondary_carets();
+            }
+            EditorEvent::TransposeChars => {
+                let caret = editor_buffer.get_caret(CaretKind::ScrollAdjusted);
+                editor_buffer.transpose_chars(caret, viewport);
+            }
+            EditorEvent::TransposeWords => {
+                let caret = editor_buffer.get_caret(CaretKind::ScrollAdjusted);
+                editor_buffer.transpose_words(
+                    caret,
+                    &editor_engine.config_options.word_char_class,
+                    viewport,
+                );
+            }
+            EditorEvent::DuplicateLine | EditorEvent::MoveLine(_)
+                if editor_engine.config_options.multiline_mode
+                    == LineMode::SingleLine => {}
+            EditorEvent::DuplicateLine => {
+                let (start_row_index, end_row_index) =
+                    editor_buffer.get_selected_or_caret_rows();
+                if editor_buffer.duplicate_lines(start_row_index, end_row_index) {
+                    EditorEngineInternalApi::validate_scroll(EditorArgsMut {
+                        editor_buffer,
+                        editor_engine,
+                    });
+                }
+            }
+            EditorEvent::MoveLine(direction) => {
+                let (start_row_index, end_row_index) =
+                    editor_buffer.get_selected_or_caret_rows();
+                if editor_buffer.move_lines(start_row_index, end_row_index, direction) {
+                    EditorEngineInternalApi::validate_scroll(EditorArgsMut {
+                        editor_buffer,
+                        editor_engine,
+                    });
+                }
+            }
+            EditorEvent::ToggleLineComment => {
+                let maybe_prefix =
+                    editor_buffer
+                        .get_maybe_file_extension()
+                        .and_then(|file_extension| {
+                            try_get_line_comment_prefix(
+                                &editor_engine.syntax_set,
+                                file_extension,
+                            )
+                        });
+                if let Some(prefix) = maybe_prefix {
+                    let (start_row_index, end_row_index) =
+                        editor_buffer.get_selected_or_caret_rows();
+                    editor_buffer
+                        .toggle_line_comment(start_row_index..=end_row_index, prefix);
+                }
+            }
+            EditorEvent::IndentLines => {
+                let (start_row_index, end_row_index) =
+                    editor_buffer.get_selected_or_caret_rows();
+                let indent = editor_engine.config_options.get_indent_unit();
+                if editor_buffer.indent_lines(start_row_index..=end_row_index, &indent) {
+                    EditorEngineInternalApi::validate_scroll(EditorArgsMut {
+                        editor_buffer,
+                        editor_engine,
+                    });
+                }
+            }
+            EditorEvent::DedentLines => {
+                let (start_row_index, end_row_index) =
+                    editor_buffer.get_selected_or_caret_rows();
+                let tab_width = editor_engine.config_options.tab_width;
+                if editor_buffer.dedent_lines(start_row_index..=end_row_index, tab_width)
+                {
+                    EditorEngineInternalApi::validate_scroll(EditorArgsMut {
+                        editor_buffer,
+                        editor_engine,
+                    });
+                }
+            }
+            EditorEvent::IndentSelectionOrInsertIndent | EditorEvent::DedentSelection => {
+                // These are resolved into other events above.
+            }
+            EditorEvent::ToggleInsertMode => {
+                editor_engine.insert_mode = match editor_engine.insert_mode {
+                    InsertMode::Insert => InsertMode::Overwrite,
+                    InsertMode::Overwrite => InsertMode::Insert,
+                };
+            }
+            EditorEvent::OpenReplace => {
+                editor_engine.replace_state.open();
+            }
+            EditorEvent::ConvertLineEndings(line_ending) => {
+                editor_buffer.convert_line_endings(line_ending);
+            }
+            EditorEvent::InsertSnippet(template) => {
+                let caret = editor_buffer.get_caret(CaretKind::ScrollAdjusted);
+                editor_buffer.insert_snippet(&template, caret, viewport);
+            }
+            // These are returned by [EditorEngineApi::apply_event] instead of being applied.
+            EditorEvent::Command(_) => {}
         };
+
+        if let Some(caret_motion) = maybe_caret_motion {
+            EditorEngineInternalApi::move_secondary_carets(editor_buffer, &caret_motion);
+        }
+
+        if is_content_change {
+            // [EditorBuffer::insert_snippet] already shifts the folds & marks, and its
+            // snippet session is up to date.
+            if !is_snippet_inserted {
+                EditorEngineInternalApi::shift_folds_after_edit(
+                    editor_buffer,
+                    caret_row_before_edit,
+                    line_count_before_edit,
+                );
+                EditorEngineInternalApi::shift_marks_after_edit(
+                    editor_buffer,
+                    is_caret_row_removed,
+                    caret_row_before_edit,
+                    line_count_before_edit,
+                );
+                editor_buffer.update_snippet_session_after_edit(
+                    caret_row_before_edit,
+                    caret_row_width_before_edit,
+                    line_count_before_edit,
+                );
+            }
+            editor_engine.diagnostics.shift_rows_after_edit(
+                editor_buffer,
+                is_caret_row_removed,
+                caret_row_before_edit,
+                line_count_before_edit,
+            );
+        }
     }
 
     pub fn apply_editor_events<S, A>(
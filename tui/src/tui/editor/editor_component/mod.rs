@@ -16,9 +16,15 @@
  */
 
 // Attach.
+pub mod auto_save_timer;
+pub mod change_debounce_timer;
+pub mod editor_command;
 pub mod editor_component_struct;
 pub mod editor_event;
 
 // Re-export.
+pub use auto_save_timer::*;
+pub use change_debounce_timer::*;
+pub use editor_command::*;
 pub use editor_component_struct::*;
 pub use editor_event::*;
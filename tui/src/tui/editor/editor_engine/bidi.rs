@@ -0,0 +1,299 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+//! Partial support for bidirectional (eg: Arabic or Hebrew mixed w/ English) text. The
+//! [EditorBuffer] always holds the text in logical order (the order it is typed in), and
+//! the caret moves in logical order. Only the display is reordered, when
+//! [EditorEngineConfig::bidi_reorder] is enabled:
+//! 1. The base direction of each line comes from its first strong char (a letter). Lines
+//!    w/out any right-to-left text are painted as is.
+//! 2. Neutral chars (eg: spaces & punctuation) take the direction of the text around them
+//!    when it is the same on both sides, otherwise the base direction. Digits are treated
+//!    as left-to-right text, so numbers keep their order.
+//! 3. The runs are reordered like in the Unicode Bidirectional Algorithm (rule L2).
+//!
+//! Explicit embeddings, mirrored brackets & right aligning right-to-left lines are not
+//! supported. The selection, search matches, whitespace markers & diagnostics are
+//! painted at the logical cols, and horizontal scrolling & mouse clicks use the logical
+//! cols too. This is why [EditorEngineConfig::bidi_reorder] is off by default.
+//!
+//! - UAX #9: <https://www.unicode.org/reports/tr9/>
+
+use r3bl_rs_utils_core::*;
+
+use crate::*;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TextDirection {
+    Ltr,
+    Rtl,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum CharClass {
+    Strong(TextDirection),
+    Neutral,
+}
+
+fn get_char_class(character: char) -> CharClass {
+    match character as u32 {
+        // Hebrew, Arabic, Syriac, Thaana, NKo, Samaritan & Mandaic.
+        0x0590..=0x08FF
+        // Hebrew & Arabic presentation forms.
+        | 0xFB1D..=0xFDFF
+        | 0xFE70..=0xFEFC => CharClass::Strong(TextDirection::Rtl),
+        _ if character.is_alphanumeric() => CharClass::Strong(TextDirection::Ltr),
+        _ => CharClass::Neutral,
+    }
+}
+
+/// A grapheme cluster is classified by its first char (the rest are combining marks).
+fn get_segment_class(segment: &str) -> CharClass {
+    segment
+        .chars()
+        .next()
+        .map(get_char_class)
+        .unwrap_or(CharClass::Neutral)
+}
+
+/// Returns the direction of the first strong char in the `text`, or [None] if it doesn't
+/// have any.
+pub fn get_base_text_direction(text: &str) -> Option<TextDirection> {
+    text.chars()
+        .find_map(|character| match get_char_class(character) {
+            CharClass::Strong(direction) => Some(direction),
+            CharClass::Neutral => None,
+        })
+}
+
+/// Returns the (logical) indices of the `segments` (grapheme clusters) of a line in the
+/// order that they are painted, from left to right. Returns [None] if the line doesn't
+/// have any right-to-left text, ie: the segments are painted in logical order. More info
+/// in the [module docs](self).
+pub fn get_bidi_visual_order<'a>(
+    segments: impl IntoIterator<Item = &'a str>,
+) -> Option<Vec<usize>> {
+    let classes: Vec<CharClass> = segments.into_iter().map(get_segment_class).collect();
+    if !classes.contains(&CharClass::Strong(TextDirection::Rtl)) {
+        return None;
+    }
+
+    let base_direction = classes
+        .iter()
+        .find_map(|class| match class {
+            CharClass::Strong(direction) => Some(*direction),
+            CharClass::Neutral => None,
+        })
+        .unwrap_or(TextDirection::Ltr);
+
+    // Resolve the neutrals.
+    let mut directions: Vec<TextDirection> = Vec::with_capacity(classes.len());
+    let mut index = 0;
+    while index < classes.len() {
+        if let CharClass::Strong(direction) = classes[index] {
+            directions.push(direction);
+            index += 1;
+            continue;
+        }
+        let start_index = index;
+        while index < classes.len() && classes[index] == CharClass::Neutral {
+            index += 1;
+        }
+        let prev_direction = directions.last().copied().unwrap_or(base_direction);
+        let next_direction = match classes.get(index) {
+            Some(CharClass::Strong(direction)) => *direction,
+            _ => base_direction,
+        };
+        let direction = if prev_direction == next_direction {
+            prev_direction
+        } else {
+            base_direction
+        };
+        directions.extend(std::iter::repeat(direction).take(index - start_index));
+    }
+
+    // Assign the embedding levels (odd is right-to-left).
+    let levels: Vec<u8> = directions
+        .iter()
+        .map(|direction| match (base_direction, direction) {
+            (TextDirection::Ltr, TextDirection::Ltr) => 0,
+            (_, TextDirection::Rtl) => 1,
+            (TextDirection::Rtl, TextDirection::Ltr) => 2,
+        })
+        .collect();
+
+    // From the highest level down to the lowest odd level, reverse each run of segments
+    // that are at that level or higher.
+    let mut visual_order: Vec<usize> = (0..levels.len()).collect();
+    let max_level = levels.iter().copied().max().unwrap_or(0);
+    for level in (1..=max_level).rev() {
+        let mut index = 0;
+        while index < visual_order.len() {
+            if levels[visual_order[index]] < level {
+                index += 1;
+                continue;
+            }
+            let start_index = index;
+            while index < visual_order.len() && levels[visual_order[index]] >= level {
+                index += 1;
+            }
+            visual_order[start_index..index].reverse();
+        }
+    }
+
+    Some(visual_order)
+}
+
+/// Returns the `line` reordered for display, or [None] if it doesn't have to be.
+pub fn reorder_line_for_display(line: &UnicodeString) -> Option<UnicodeString> {
+    let visual_order =
+        get_bidi_visual_order(line.iter().map(|segment| segment.string.as_str()))?;
+    let it: String = visual_order
+        .into_iter()
+        .map(|index| line.vec_segment[index].string.as_str())
+        .collect();
+    Some(UnicodeString::from(it))
+}
+
+/// Same as [reorder_line_for_display], but each grapheme cluster keeps its [Style].
+pub fn reorder_styled_line_for_display(
+    line: &StyleUSSpanLine,
+) -> Option<StyleUSSpanLine> {
+    let segments: Vec<(Style, &str)> = line
+        .iter()
+        .flat_map(|span| {
+            span.text
+                .iter()
+                .map(|segment| (span.style, segment.string.as_str()))
+        })
+        .collect();
+    let visual_order = get_bidi_visual_order(segments.iter().map(|(_, it)| *it))?;
+
+    // Merge the adjacent segments that have the same style back into spans.
+    let mut spans: Vec<(Style, String)> = vec![];
+    for index in visual_order {
+        let (style, segment) = segments[index];
+        match spans.last_mut() {
+            Some((last_style, last_text)) if *last_style == style => {
+                last_text.push_str(segment)
+            }
+            _ => spans.push((style, segment.to_string())),
+        }
+    }
+
+    let mut it = StyleUSSpanLine::default();
+    for (style, text) in spans {
+        it.push(StyleUSSpan::new(style, US::from(text)));
+    }
+    Some(it)
+}
+
+/// Returns the display col where the segment that starts at the (logical) `col_index` is
+/// painted. The caret past the end of the line stays there.
+pub fn get_bidi_visual_col_index(line: &UnicodeString, col_index: ChUnit) -> ChUnit {
+    let Some(visual_order) =
+        get_bidi_visual_order(line.iter().map(|segment| segment.string.as_str()))
+    else {
+        return col_index;
+    };
+
+    let mut visual_col_index = ch!(0);
+    for index in visual_order {
+        let segment = &line.vec_segment[index];
+        if segment.display_col_offset == col_index {
+            return visual_col_index;
+        }
+        visual_col_index += segment.unicode_width;
+    }
+    col_index
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_rs_utils_core::*;
+
+    use super::*;
+
+    fn reorder(text: &str) -> String {
+        match reorder_line_for_display(&UnicodeString::from(text)) {
+            Some(it) => it.string,
+            None => text.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_base_text_direction() {
+        assert_eq2!(
+            get_base_text_direction("  123 abc שלום"),
+            Some(TextDirection::Ltr)
+        );
+        assert_eq2!(
+            get_base_text_direction("- שלום abc"),
+            Some(TextDirection::Rtl)
+        );
+        assert_eq2!(get_base_text_direction(" - "), None);
+    }
+
+    #[test]
+    fn test_reorder_mixed_lines() {
+        // LTR only lines aren't changed.
+        assert_eq2!(get_bidi_visual_order(["hello", " ", "world"]), None);
+
+        // A RTL run in a LTR line.
+        assert_eq2!(reorder("abc אבג def"), "abc גבא def");
+
+        // A LTR run (& a number) in a RTL line.
+        assert_eq2!(reorder("אבג abc 123 דהו"), "והד abc 123 גבא");
+
+        // RTL only, w/ trailing punctuation.
+        assert_eq2!(reorder("שלום עולם!"), "!םלוע םולש");
+
+        // Arabic.
+        assert_eq2!(reorder("مرحبا abc"), "abc ابحرم");
+    }
+
+    #[test]
+    fn test_reorder_styled_line() {
+        let bold = Style {
+            bold: true,
+            ..Default::default()
+        };
+        let line: StyleUSSpanLine = list![
+            StyleUSSpan::new(Style::default(), US::from("ab ")),
+            StyleUSSpan::new(bold, US::from("אב")),
+        ];
+        let reordered = reorder_styled_line_for_display(&line).unwrap();
+        assert_eq2!(
+            reordered,
+            list![
+                StyleUSSpan::new(Style::default(), US::from("ab ")),
+                StyleUSSpan::new(bold, US::from("בא")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_visual_col_index() {
+        // The caret moves in logical order, but is painted where the char is.
+        let line = UnicodeString::from("ab אבג");
+        assert_eq2!(get_bidi_visual_col_index(&line, ch!(0)), ch!(0));
+        assert_eq2!(get_bidi_visual_col_index(&line, ch!(3)), ch!(5));
+        assert_eq2!(get_bidi_visual_col_index(&line, ch!(5)), ch!(3));
+        // Past the end of the line.
+        assert_eq2!(get_bidi_visual_col_index(&line, ch!(6)), ch!(6));
+    }
+}
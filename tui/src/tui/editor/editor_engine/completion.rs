@@ -0,0 +1,311 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use r3bl_rs_utils_core::*;
+use serde::*;
+
+use crate::*;
+
+/// A candidate that is shown in the completion popup of the editor.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Completion {
+    /// Shown in the popup.
+    pub label: String,
+    /// Replaces the word prefix at the caret when this completion is accepted.
+    pub insert_text: String,
+}
+
+/// Returns the completions for the word prefix at the given [scroll
+/// adjusted](CaretKind::ScrollAdjusted) caret position. The provider is responsible for
+/// filtering & sorting them. More info in [EditorComponent::with_completion_provider].
+pub type CompletionProviderFn = fn(&EditorBuffer, Position) -> Vec<Completion>;
+
+/// The state of the completion popup, which lives in the [EditorEngine]. The popup is open
+/// when there are `items`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CompletionState {
+    pub items: Vec<Completion>,
+    pub selection: ListSelection,
+    pub scroll_offset_row_index: usize,
+    /// Where the word prefix that is replaced by the accepted completion starts. This is
+    /// [scroll adjusted](CaretKind::ScrollAdjusted).
+    pub prefix_start: Position,
+}
+
+mod completion_impl {
+    use super::*;
+
+    impl Completion {
+        /// The `label` is also the text that is inserted.
+        pub fn new(label: &str) -> Self {
+            Self {
+                label: label.to_string(),
+                insert_text: label.to_string(),
+            }
+        }
+    }
+
+    impl CompletionState {
+        pub fn is_active(&self) -> bool { !self.items.is_empty() }
+
+        pub fn dismiss(&mut self) { *self = CompletionState::default(); }
+
+        pub fn get_selected(&self) -> Option<&Completion> {
+            self.items.get(self.selection.focused_index)
+        }
+
+        /// The popup shows at most [COMPLETION_POPUP_MAX_ROW_COUNT] items at a time.
+        pub fn get_popup_row_count(&self) -> usize {
+            self.items.len().min(COMPLETION_POPUP_MAX_ROW_COUNT)
+        }
+
+        pub fn navigate(&mut self, navigation: ListNavigation) {
+            let row_count = self.get_popup_row_count();
+            self.selection
+                .navigate(navigation, self.items.len(), row_count);
+            self.scroll_offset_row_index =
+                self.selection.get_scroll_offset_to_keep_focused_in_view(
+                    self.scroll_offset_row_index,
+                    row_count,
+                );
+        }
+    }
+}
+
+pub mod completion_api {
+    use super::*;
+
+    /// Returns the word (letters, digits & `_`) that ends at the given [scroll
+    /// adjusted](CaretKind::ScrollAdjusted) `position`, and the position where it starts.
+    pub fn get_word_prefix_at(
+        editor_buffer: &EditorBuffer,
+        position: Position,
+    ) -> (Position, String) {
+        let Some(line) = editor_buffer
+            .get_lines()
+            .get(ch!(@to_usize position.row_index))
+        else {
+            return (position, String::new());
+        };
+
+        let left = line.clip_to_width(ch!(0), position.col_index);
+        let prefix_start_byte_index = left
+            .char_indices()
+            .rev()
+            .take_while(|(_, character)| is_word_char(*character))
+            .last()
+            .map(|(byte_index, _)| byte_index)
+            .unwrap_or(left.len());
+        let prefix = &left[prefix_start_byte_index..];
+
+        let prefix_start = position! {
+            col_index: position.col_index - ch!(UnicodeString::str_display_width(prefix)),
+            row_index: position.row_index
+        };
+        (prefix_start, prefix.to_string())
+    }
+
+    fn is_word_char(character: char) -> bool {
+        character.is_alphanumeric() || character == '_'
+    }
+
+    /// Typing a word char (re)opens the popup, & Backspace updates it if it is already
+    /// open. Any other edit or caret movement closes it.
+    pub fn is_completion_trigger(editor_events: &[EditorEvent], is_active: bool) -> bool {
+        editor_events.iter().any(|editor_event| match editor_event {
+            EditorEvent::InsertChar(character) => is_word_char(*character),
+            EditorEvent::Backspace => is_active,
+            _ => false,
+        })
+    }
+
+    /// Call this after the `editor_events` have been applied to the `editor_buffer`. If they
+    /// trigger completion, the popup is filled w/ the completions for the word prefix at the
+    /// caret, otherwise it is closed.
+    pub fn update_completions(
+        editor_engine: &mut EditorEngine,
+        editor_buffer: &EditorBuffer,
+        is_triggered: bool,
+    ) {
+        let Some(completion_provider) = editor_engine.maybe_completion_provider else {
+            return;
+        };
+        if !is_triggered {
+            editor_engine.completion_state.dismiss();
+            return;
+        }
+
+        let caret = editor_buffer.get_caret(CaretKind::ScrollAdjusted);
+        let (prefix_start, prefix) = get_word_prefix_at(editor_buffer, caret);
+        if prefix.is_empty() {
+            editor_engine.completion_state.dismiss();
+            return;
+        }
+
+        editor_engine.completion_state = CompletionState {
+            items: completion_provider(editor_buffer, caret),
+            prefix_start,
+            ..Default::default()
+        };
+    }
+
+    /// When the popup is open, it gets the first chance to handle the `input_event`:
+    /// - Up, Down, PageUp & PageDown move the selection.
+    /// - Tab & Enter accept the selected completion.
+    /// - Esc dismisses the popup.
+    ///
+    /// Returns the new buffer if the event was handled.
+    pub fn try_handle_completion_input_event(
+        editor_engine: &mut EditorEngine,
+        editor_buffer: &EditorBuffer,
+        input_event: &InputEvent,
+    ) -> Option<EditorBuffer> {
+        if !editor_engine.completion_state.is_active() {
+            return None;
+        }
+
+        let InputEvent::Keyboard(KeyPress::Plain {
+            key: Key::SpecialKey(special_key),
+        }) = input_event
+        else {
+            return None;
+        };
+
+        match special_key {
+            SpecialKey::Up => editor_engine.completion_state.navigate(ListNavigation::Up),
+            SpecialKey::Down => editor_engine
+                .completion_state
+                .navigate(ListNavigation::Down),
+            SpecialKey::PageUp => editor_engine
+                .completion_state
+                .navigate(ListNavigation::PageUp),
+            SpecialKey::PageDown => editor_engine
+                .completion_state
+                .navigate(ListNavigation::PageDown),
+            SpecialKey::Tab | SpecialKey::Enter => {
+                return Some(accept_completion(editor_engine, editor_buffer));
+            }
+            SpecialKey::Esc => editor_engine.completion_state.dismiss(),
+            _ => return None,
+        }
+
+        Some(editor_buffer.clone())
+    }
+
+    /// Replace the word prefix w/ the selected completion, & close the popup.
+    pub fn accept_completion(
+        editor_engine: &mut EditorEngine,
+        editor_buffer: &EditorBuffer,
+    ) -> EditorBuffer {
+        let mut new_editor_buffer = editor_buffer.clone();
+        let completion_state = std::mem::take(&mut editor_engine.completion_state);
+        let Some(completion) = completion_state.get_selected() else {
+            return new_editor_buffer;
+        };
+
+        let viewport = size! {
+            col_count: editor_engine.viewport_width(),
+            row_count: editor_engine.viewport_height()
+        };
+        let caret = editor_buffer.get_caret(CaretKind::ScrollAdjusted);
+        let prefix_start = completion_state.prefix_start;
+        new_editor_buffer.delete_at(
+            prefix_start,
+            caret.col_index - prefix_start.col_index,
+            viewport,
+        );
+        new_editor_buffer.insert_at(prefix_start, &completion.insert_text, viewport);
+        new_editor_buffer
+    }
+
+    /// Paint the popup below the caret (or above it if there's no room below), lined up w/
+    /// the start of the word prefix, & clamped to the `window_size`. Just like the results
+    /// panel of the dialog, the selected item is highlighted & the items scroll to keep it
+    /// in view.
+    pub fn render_completion_popup(
+        editor_engine: &EditorEngine,
+        editor_buffer: &EditorBuffer,
+        window_size: Size,
+    ) -> Option<RenderOps> {
+        let completion_state = &editor_engine.completion_state;
+        if !completion_state.is_active() {
+            return None;
+        }
+
+        let origin_pos = editor_engine.current_box.style_adjusted_origin_pos;
+        let scroll_offset = editor_buffer.get_scroll_offset();
+        let caret = editor_buffer.get_caret(CaretKind::Raw);
+
+        let row_count = ch!(completion_state.get_popup_row_count());
+        let max_label_display_width = completion_state
+            .items
+            .iter()
+            .map(|it| UnicodeString::str_display_width(&it.label))
+            .max()
+            .unwrap_or_default();
+        let col_count = ch!(max_label_display_width + 2)
+            .min(ch!(COMPLETION_POPUP_MAX_COL_COUNT))
+            .min(window_size.col_count);
+
+        let caret_row_index = origin_pos.row_index + caret.row_index;
+        let below_row_index = caret_row_index + 1;
+        let top_row_index = if below_row_index + row_count <= window_size.row_count {
+            below_row_index
+        } else {
+            caret_row_index - row_count
+        };
+        let left_col_index = (origin_pos.col_index
+            + (completion_state.prefix_start.col_index - scroll_offset.col_index))
+            .min(window_size.col_count - col_count);
+
+        let mut render_ops = render_ops!();
+        for (row_offset, (index, completion)) in completion_state
+            .items
+            .iter()
+            .enumerate()
+            .skip(completion_state.scroll_offset_row_index)
+            .take(ch!(@to_usize row_count))
+            .enumerate()
+        {
+            let style = match index == completion_state.selection.focused_index {
+                true => get_completion_popup_selected_style(),
+                false => get_completion_popup_style(),
+            };
+            let text = UnicodeString::from(format!(" {} ", completion.label));
+            let clipped_text = text.clip_to_width(ch!(0), col_count);
+            let padding_len =
+                col_count - ch!(UnicodeString::str_display_width(clipped_text));
+
+            render_ops.push(RenderOp::MoveCursorPositionAbs(position! {
+                col_index: left_col_index,
+                row_index: top_row_index + ch!(row_offset)
+            }));
+            render_ops.push(RenderOp::ApplyColors(Some(style)));
+            render_ops.push(RenderOp::PaintTextWithAttributes(
+                format!(
+                    "{clipped_text}{}",
+                    SPACER.repeat(ch!(@to_usize padding_len))
+                ),
+                Some(style),
+            ));
+            render_ops.push(RenderOp::ResetColor);
+        }
+
+        Some(render_ops)
+    }
+}
+pub use completion_api::*;
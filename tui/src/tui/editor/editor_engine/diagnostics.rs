@@ -0,0 +1,325 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::cmp::Ordering;
+
+use r3bl_rs_utils_core::*;
+
+use crate::*;
+
+/// The variants are ordered from the most to the least severe, so the smallest one is
+/// the one that is painted in the gutter when a row has more than one [Diagnostic].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum DiagnosticSeverity {
+    Error,
+    Warning,
+    Info,
+    Hint,
+}
+
+/// A message (eg: from a linter) about a row of the buffer. The cols are display cols,
+/// and they aren't adjusted for scrolling.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub row_index: RowIndex,
+    pub severity: DiagnosticSeverity,
+    pub message: String,
+    /// The cols from here to `end_col_index` are underlined.
+    pub start_col_index: ChUnit,
+    /// Exclusive. When it is the same as `start_col_index` nothing is underlined.
+    pub end_col_index: ChUnit,
+}
+
+/// The diagnostics that are shown in the gutter of the editor (when
+/// [EditorEngineConfig::diagnostics_gutter] is enabled). They live in the [EditorEngine]
+/// and not in the [EditorBuffer], since they come from outside the editor (eg: a linter
+/// that runs after the buffer changes).
+///
+/// 1. They are replaced all at once by [set_all](Diagnostics::set_all) & removed all at
+///    once by [clear](Diagnostics::clear).
+/// 2. When lines are inserted or removed, the rows of the diagnostics below the edit are
+///    shifted by [shift_rows_after_edit](Diagnostics::shift_rows_after_edit), so they
+///    stay on the same text until the linter runs again. The ones on removed rows are
+///    dropped.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Diagnostics {
+    /// Sorted by `row_index`.
+    items: Vec<Diagnostic>,
+}
+
+mod diagnostic_impl {
+    use super::*;
+
+    impl DiagnosticSeverity {
+        /// The glyph that is painted in the gutter.
+        pub fn get_gutter_char(&self) -> char {
+            match self {
+                DiagnosticSeverity::Error => DIAGNOSTIC_ERROR_CHAR,
+                DiagnosticSeverity::Warning => DIAGNOSTIC_WARNING_CHAR,
+                DiagnosticSeverity::Info => DIAGNOSTIC_INFO_CHAR,
+                DiagnosticSeverity::Hint => DIAGNOSTIC_HINT_CHAR,
+            }
+        }
+    }
+
+    impl Diagnostic {
+        /// A diagnostic for the whole row, w/ no underlined cols.
+        pub fn new(
+            row_index: RowIndex,
+            severity: DiagnosticSeverity,
+            message: impl Into<String>,
+        ) -> Self {
+            Self {
+                row_index,
+                severity,
+                message: message.into(),
+                start_col_index: ch!(0),
+                end_col_index: ch!(0),
+            }
+        }
+
+        pub fn has_col_range(&self) -> bool { self.start_col_index < self.end_col_index }
+    }
+
+    impl Diagnostics {
+        /// Replace all the diagnostics, eg: each time the linter runs.
+        pub fn set_all(&mut self, diagnostics: Vec<Diagnostic>) {
+            let mut it = diagnostics;
+            it.sort_by_key(|diagnostic| (diagnostic.row_index, diagnostic.severity));
+            self.items = it;
+        }
+
+        pub fn clear(&mut self) { self.items.clear(); }
+
+        pub fn is_empty(&self) -> bool { self.items.is_empty() }
+
+        pub fn len(&self) -> usize { self.items.len() }
+
+        pub fn iter(&self) -> impl Iterator<Item = &Diagnostic> { self.items.iter() }
+
+        /// Returns all the diagnostics for the rows in the given range (inclusive).
+        pub fn get_for_rows(
+            &self,
+            start_row_index: RowIndex,
+            end_row_index: RowIndex,
+        ) -> impl Iterator<Item = &Diagnostic> {
+            self.items.iter().filter(move |diagnostic| {
+                diagnostic.row_index >= start_row_index
+                    && diagnostic.row_index <= end_row_index
+            })
+        }
+
+        /// Returns the most severe diagnostic for the rows in the given range
+        /// (inclusive), ie: the one that is painted in the gutter.
+        pub fn get_most_severe_for_rows(
+            &self,
+            start_row_index: RowIndex,
+            end_row_index: RowIndex,
+        ) -> Option<&Diagnostic> {
+            self.get_for_rows(start_row_index, end_row_index)
+                .min_by_key(|diagnostic| diagnostic.severity)
+        }
+
+        /// Returns the diagnostic that the caret is on, eg: to show its message in a status
+        /// bar. If there are several on the caret row, the ones whose cols contain the
+        /// caret are preferred, and then the most severe one.
+        pub fn get_at_caret(&self, editor_buffer: &EditorBuffer) -> Option<&Diagnostic> {
+            let caret = editor_buffer.get_caret(CaretKind::ScrollAdjusted);
+            self.get_for_rows(caret.row_index, caret.row_index)
+                .min_by_key(|diagnostic| {
+                    let contains_caret = caret.col_index >= diagnostic.start_col_index
+                        && caret.col_index < diagnostic.end_col_index;
+                    (!contains_caret, diagnostic.severity)
+                })
+        }
+
+        /// This works just like [EditorEngineInternalApi::shift_marks_after_edit], and it
+        /// is called right after it.
+        pub fn shift_rows_after_edit(
+            &mut self,
+            editor_buffer: &EditorBuffer,
+            is_caret_row_removed: bool,
+            caret_row_before_edit: ChUnit,
+            line_count_before_edit: ChUnit,
+        ) {
+            let line_count_after_edit = editor_buffer.len();
+            let caret_row_after_edit =
+                editor_buffer.get_caret(CaretKind::ScrollAdjusted).row_index;
+
+            match line_count_after_edit.cmp(&line_count_before_edit) {
+                Ordering::Equal => {}
+                Ordering::Greater => {
+                    let amount = line_count_after_edit - line_count_before_edit;
+                    for diagnostic in self.items.iter_mut() {
+                        if diagnostic.row_index > caret_row_before_edit {
+                            diagnostic.row_index += amount;
+                        }
+                    }
+                }
+                Ordering::Less => {
+                    let amount = line_count_before_edit - line_count_after_edit;
+                    let first_removed_row_index = if is_caret_row_removed {
+                        caret_row_before_edit
+                    } else {
+                        caret_row_before_edit.min(caret_row_after_edit) + 1
+                    };
+                    let end_removed_row_index = first_removed_row_index + amount;
+                    self.items.retain(|diagnostic| {
+                        diagnostic.row_index < first_removed_row_index
+                            || diagnostic.row_index >= end_removed_row_index
+                    });
+                    for diagnostic in self.items.iter_mut() {
+                        if diagnostic.row_index >= end_removed_row_index {
+                            diagnostic.row_index -= amount;
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Paint the gutter w/ the glyph of the most severe diagnostic for each row in the
+/// viewport (a folded region gets the most severe one in it), and underline the cols of
+/// the diagnostics that have them. The gutter is painted at `gutter_origin_pos`, which is
+/// to the left of the content box in [EditorEngine::current_box].
+pub fn render_diagnostics(
+    editor_engine: &EditorEngine,
+    editor_buffer: &EditorBuffer,
+    gutter_origin_pos: Position,
+    render_ops: &mut RenderOps,
+) {
+    if !editor_engine.config_options.diagnostics_gutter {
+        return;
+    }
+
+    let diagnostics = &editor_engine.diagnostics;
+    let origin_pos = editor_engine.current_box.style_adjusted_origin_pos;
+    let scroll_offset = editor_buffer.get_scroll_offset();
+    let viewport_width = editor_engine.viewport_width();
+    let viewport_height = ch!(@to_usize editor_engine.viewport_height());
+    let lines = editor_buffer.get_lines();
+
+    let visible_rows = editor_buffer
+        .get_fold_map()
+        .visible_rows(scroll_offset.row_index, lines.len())
+        .take(viewport_height);
+    for (display_row_index, visible_row) in visible_rows.enumerate() {
+        let (start_row_index, end_row_index) = match visible_row {
+            VisibleRow::Line(row_index) => (row_index, row_index),
+            VisibleRow::Folded(fold) => (fold.start_row_index, fold.end_row_index),
+        };
+
+        // Paint the gutter.
+        let Some(most_severe) =
+            diagnostics.get_most_severe_for_rows(start_row_index, end_row_index)
+        else {
+            continue;
+        };
+        let style = get_diagnostic_style(most_severe.severity);
+        render_ops.push(RenderOp::MoveCursorPositionRelTo(
+            gutter_origin_pos,
+            position! { col_index: 0, row_index: ch!(display_row_index) },
+        ));
+        render_ops.push(RenderOp::ApplyColors(Some(style)));
+        render_ops.push(RenderOp::PaintTextWithAttributes(
+            most_severe.severity.get_gutter_char().to_string(),
+            Some(style),
+        ));
+        render_ops.push(RenderOp::ResetColor);
+
+        // Underline the cols (the content of folded regions isn't visible).
+        let VisibleRow::Line(row_index) = visible_row else {
+            continue;
+        };
+        let Some(line) = lines.get(ch!(@to_usize row_index)) else {
+            continue;
+        };
+        for diagnostic in diagnostics.get_for_rows(row_index, row_index) {
+            // Clip the cols to the viewport.
+            let start_col_index = diagnostic.start_col_index.max(scroll_offset.col_index);
+            let end_col_index = diagnostic
+                .end_col_index
+                .min(line.display_width)
+                .min(scroll_offset.col_index + viewport_width);
+            if start_col_index >= end_col_index {
+                continue;
+            }
+
+            let style = Style {
+                underline: true,
+                ..get_diagnostic_style(diagnostic.severity)
+            };
+            render_ops.push(RenderOp::MoveCursorPositionRelTo(
+                origin_pos,
+                position! {
+                    col_index: start_col_index - scroll_offset.col_index,
+                    row_index: ch!(display_row_index)
+                },
+            ));
+            render_ops.push(RenderOp::ApplyColors(Some(style)));
+            render_ops.push(RenderOp::PaintTextWithAttributes(
+                line.clip_to_width(start_col_index, end_col_index - start_col_index)
+                    .to_string(),
+                Some(style),
+            ));
+            render_ops.push(RenderOp::ResetColor);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_rs_utils_core::*;
+
+    use super::*;
+
+    #[test]
+    fn test_most_severe_and_at_caret() {
+        let mut diagnostics = Diagnostics::default();
+        diagnostics.set_all(vec![
+            Diagnostic::new(ch!(1), DiagnosticSeverity::Hint, "hint"),
+            Diagnostic {
+                start_col_index: ch!(2),
+                end_col_index: ch!(4),
+                ..Diagnostic::new(ch!(0), DiagnosticSeverity::Warning, "warning")
+            },
+            Diagnostic::new(ch!(0), DiagnosticSeverity::Error, "error"),
+        ]);
+
+        assert_eq2!(
+            diagnostics
+                .get_most_severe_for_rows(ch!(0), ch!(1))
+                .map(|it| it.message.as_str()),
+            Some("error")
+        );
+
+        // The caret is in the cols of the warning.
+        let mut buffer = EditorBuffer::new_empty(None);
+        buffer.set_lines(vec!["abcdef".to_string(), "g".to_string()]);
+        buffer.get_mut().1.col_index = ch!(3);
+        assert_eq2!(
+            diagnostics
+                .get_at_caret(&buffer)
+                .map(|it| it.message.as_str()),
+            Some("warning")
+        );
+
+        diagnostics.clear();
+        assert!(diagnostics.is_empty());
+    }
+}
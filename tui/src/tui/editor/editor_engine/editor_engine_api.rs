@@ -15,12 +15,11 @@
  *   limitations under the License.
  */
 
-use std::fmt::Debug;
+use std::{fmt::Debug, time::Instant};
 
 use crossterm::style::Stylize;
 use r3bl_rs_utils_core::*;
 use r3bl_rs_utils_macro::style;
-use syntect::easy::HighlightLines;
 
 use super::*;
 use crate::*;
@@ -48,6 +47,13 @@ impl EditorEngineApi {
             ..
         } = args;
 
+        // Clicks don't change the content, so they are handled even when it is read only.
+        if let Some(new_editor_buffer) =
+            try_handle_mouse_input_event(editor_engine, editor_buffer, input_event)
+        {
+            return Ok(EditorEngineApplyEventResult::Applied(new_editor_buffer));
+        }
+
         let editor_config = &editor_engine.config_options;
 
         if let EditMode::ReadOnly = editor_config.edit_mode {
@@ -76,26 +82,140 @@ impl EditorEngineApi {
                 KeyPress::Plain {
                     key: Key::SpecialKey(SpecialKey::PageDown),
                 },
+                KeyPress::WithModifiers {
+                    key: Key::Character('z'),
+                    mask: ModifierKeysMask::ALT,
+                },
             ]) {
                 return Ok(EditorEngineApplyEventResult::NotApplied);
             }
         }
 
-        if let Ok(editor_event) = EditorEvent::try_from(input_event) {
-            // REVIEW: editor buffer gets cloned here
-            let mut new_editor_buffer = editor_buffer.clone();
-            EditorEvent::apply_editor_event(
-                editor_engine,
-                &mut new_editor_buffer,
-                editor_event,
-                shared_global_data,
-                component_registry,
-                self_id,
-            );
-            Ok(EditorEngineApplyEventResult::Applied(new_editor_buffer))
+        // The replace bar (if open) gets the first chance to handle the event, then the
+        // completion popup (if open), and then the snippet session (if there is one).
+        if let Some(new_editor_buffer) =
+            try_handle_replace_input_event(editor_engine, editor_buffer, input_event)
+        {
+            return Ok(EditorEngineApplyEventResult::Applied(new_editor_buffer));
+        }
+        if let Some(new_editor_buffer) =
+            try_handle_completion_input_event(editor_engine, editor_buffer, input_event)
+        {
+            return Ok(EditorEngineApplyEventResult::Applied(new_editor_buffer));
+        }
+        if let Some(new_editor_buffer) =
+            try_handle_snippet_input_event(editor_engine, editor_buffer, input_event)
+        {
+            return Ok(EditorEngineApplyEventResult::Applied(new_editor_buffer));
+        }
+
+        let editor_events = if let InputEvent::Paste(text) = input_event {
+            // Pasted text is inserted as is, so it skips the key bindings & the auto
+            // close pairs.
+            vec![EditorEvent::Paste(text.clone())]
         } else {
-            Ok(EditorEngineApplyEventResult::NotApplied)
+            // Try the key bindings (eg: Vim) first & then fall back to the key map.
+            let editor_events =
+                match KeyBindingsApi::translate(editor_engine, input_event) {
+                    KeyBindingResult::Handled(editor_events) => editor_events,
+                    KeyBindingResult::NotHandled => {
+                        match editor_engine.config_options.key_map.translate(input_event)
+                        {
+                            Some(editor_event) => vec![editor_event],
+                            None => return Ok(EditorEngineApplyEventResult::NotApplied),
+                        }
+                    }
+                };
+
+            // Commands are carried out by the app, not applied to the buffer.
+            if let [EditorEvent::Command(editor_command)] = editor_events.as_slice() {
+                return Ok(EditorEngineApplyEventResult::Command(
+                    editor_command.clone(),
+                ));
+            }
+
+            // Keys that don't apply right now (eg: Esc w/ a single caret) are left for
+            // the app.
+            if !editor_events.is_empty()
+                && editor_events.iter().all(|it| {
+                    it.clone()
+                        .resolve(editor_buffer, &editor_engine.config_options)
+                        .is_none()
+                })
+            {
+                return Ok(EditorEngineApplyEventResult::NotApplied);
+            }
+
+            // All these events are applied to the same clone of the buffer below, so a
+            // pair that is auto closed is a single change.
+            EditorEngineInternalApi::translate_auto_close_pairs(
+                editor_buffer,
+                editor_engine,
+                editor_events,
+            )
+        };
+
+        let is_completion_triggered = is_completion_trigger(
+            &editor_events,
+            editor_engine.completion_state.is_active(),
+        );
+
+        // REVIEW: editor buffer gets cloned here
+        let mut new_editor_buffer = editor_buffer.clone();
+        EditorEvent::apply_editor_events(
+            editor_engine,
+            &mut new_editor_buffer,
+            editor_events,
+            shared_global_data,
+            component_registry,
+            self_id,
+        );
+        update_completions(editor_engine, &new_editor_buffer, is_completion_triggered);
+        Ok(EditorEngineApplyEventResult::Applied(new_editor_buffer))
+    }
+
+    /// Applies the keys of a partial chord (eg: a lone `j` when `jk` is bound) once
+    /// [EditorEngineConfig::chord_timeout] has passed, as if each had been pressed on its
+    /// own. Returns [EditorEngineApplyEventResult::NotApplied] if there is nothing to
+    /// flush. More info in [KeyBindingsApi].
+    pub fn apply_chord_timeout<S, A>(
+        args: EditorEngineArgs<'_, S, A>,
+        now: Instant,
+    ) -> EditorEngineApplyEventResult<EditorBuffer>
+    where
+        S: Debug + Default + Clone + PartialEq + Sync + Send,
+        A: Debug + Default + Clone + Sync + Send,
+    {
+        let EditorEngineArgs {
+            editor_buffer,
+            component_registry,
+            shared_global_data,
+            self_id,
+            editor_engine,
+            ..
+        } = args;
+
+        let editor_events =
+            KeyBindingsApi::flush_timed_out_pending_keys(editor_engine, now);
+        if editor_events.is_empty() {
+            return EditorEngineApplyEventResult::NotApplied;
         }
+        let editor_events = EditorEngineInternalApi::translate_auto_close_pairs(
+            editor_buffer,
+            editor_engine,
+            editor_events,
+        );
+
+        let mut new_editor_buffer = editor_buffer.clone();
+        EditorEvent::apply_editor_events(
+            editor_engine,
+            &mut new_editor_buffer,
+            editor_events,
+            shared_global_data,
+            component_registry,
+            self_id,
+        );
+        EditorEngineApplyEventResult::Applied(new_editor_buffer)
     }
 
     pub async fn render_engine<S, A>(
@@ -111,10 +231,75 @@ impl EditorEngineApi {
                 editor_buffer,
                 component_registry,
                 editor_engine,
+                shared_global_data,
                 ..
             } = args;
 
             editor_engine.current_box = current_box.into();
+            // The diagnostics gutter (if enabled) is to the left of the content, so all
+            // the render functions use the box to the right of it.
+            let gutter_origin_pos = editor_engine.current_box.style_adjusted_origin_pos;
+            let gutter_width = editor_engine.gutter_width();
+            editor_engine
+                .current_box
+                .style_adjusted_origin_pos
+                .col_index += gutter_width;
+            editor_engine
+                .current_box
+                .style_adjusted_bounds_size
+                .col_count -= gutter_width;
+            editor_engine.focus_state = component_registry
+                .has_focus
+                .does_id_have_focus(current_box.id)
+                .into();
+
+            // Always use the active theme (it can be switched at runtime).
+            {
+                let global_data = shared_global_data.read().await;
+                let active_theme = global_data.get_active_theme();
+                editor_engine.theme = active_theme.syntect_theme.clone();
+                editor_engine.markdown_style_config = active_theme.markdown_style_config;
+            }
+            let window_size = shared_global_data.read().await.get_size();
+
+            // The scroll offset that is painted lags behind the actual one while smooth
+            // scrolling, so a copy of the buffer w/ the painted offset is rendered. The
+            // carets are hidden until the animation is done.
+            let target_row_index =
+                ch!(@to_usize editor_buffer.get_scroll_offset().row_index);
+            let maybe_animated_buffer = if editor_engine.config_options.smooth_scroll {
+                let displayed_row_index =
+                    editor_engine.smooth_scroll.advance(target_row_index);
+                if editor_engine.smooth_scroll.is_animating() {
+                    shared_global_data
+                        .write()
+                        .await
+                        .request_render_tick(SMOOTH_SCROLL_FRAME_INTERVAL);
+                    let mut animated_buffer = editor_buffer.clone();
+                    let (_, caret, scroll_offset, _) = animated_buffer.get_mut();
+                    caret.row_index = caret.row_index + scroll_offset.row_index
+                        - ch!(displayed_row_index);
+                    scroll_offset.row_index = ch!(displayed_row_index);
+                    Some(animated_buffer)
+                } else {
+                    None
+                }
+            } else {
+                editor_engine.smooth_scroll.jump_to(target_row_index);
+                None
+            };
+            let is_smooth_scrolling = maybe_animated_buffer.is_some();
+            let editor_buffer = maybe_animated_buffer.as_ref().unwrap_or(editor_buffer);
+
+            // The render functions can't mutate the engine, so the visible lines are
+            // highlighted (w/in the budget) before they are painted.
+            if syn_hi_syntect_path::highlight_visible_lines(editor_buffer, editor_engine)
+            {
+                shared_global_data
+                    .write()
+                    .await
+                    .request_render_tick(SMOOTH_SCROLL_FRAME_INTERVAL);
+            }
 
             // Create reusable args for render functions.
             let render_args = RenderArgs {
@@ -130,15 +315,86 @@ impl EditorEngineApi {
 
                 EditorEngineApi::render_content(&render_args, &mut render_ops);
                 EditorEngineApi::render_selection(&render_args, &mut render_ops);
-                EditorEngineApi::render_caret(&render_args, &mut render_ops);
+                render_diagnostics(
+                    render_args.editor_engine,
+                    editor_buffer,
+                    gutter_origin_pos,
+                    &mut render_ops,
+                );
+                EditorEngineApi::render_scrollbars(&render_args, &mut render_ops);
+                if !is_smooth_scrolling {
+                    EditorEngineApi::render_secondary_carets(
+                        &render_args,
+                        &mut render_ops,
+                    );
+                    EditorEngineApi::render_caret(&render_args, &mut render_ops);
+                }
+                EditorEngineApi::render_mode_indicator(&render_args, &mut render_ops);
 
                 let mut render_pipeline = render_pipeline!();
                 render_pipeline.push(ZOrder::Normal, render_ops);
+
+                // The completion popup is painted over the content.
+                if let Some(popup_render_ops) = render_completion_popup(
+                    render_args.editor_engine,
+                    editor_buffer,
+                    window_size,
+                )
+                .filter(|_| !is_smooth_scrolling)
+                {
+                    render_pipeline.push(ZOrder::High, popup_render_ops);
+                }
+
+                // The matches & the replace bar are painted over the content too.
+                if let Some(replace_render_ops) =
+                    render_replace_mode(render_args.editor_engine, editor_buffer)
+                        .filter(|_| !is_smooth_scrolling)
+                {
+                    render_pipeline.push(ZOrder::High, replace_render_ops);
+                }
+
                 render_pipeline
             }
         })
     }
 
+    /// Returns the rows that [render_engine](EditorEngineApi::render_engine) paints in
+    /// `current_box` (top to bottom), where each [StyledText] is a run of text that has
+    /// the same style. This is exactly what is painted, ie: the diagnostics gutter, the
+    /// syntax highlighted content, the current line, the selection & the carets (& any
+    /// popup that is over the box). So custom painters (& snapshot tests) can use this
+    /// instead of interpreting the [RenderOp]s. More info in
+    /// [OffscreenBuffer::get_styled_texts_in_row].
+    pub async fn render_visible_rows<S, A>(
+        args: EditorEngineArgs<'_, S, A>,
+        current_box: &FlexBox,
+    ) -> CommonResult<Vec<StyledTexts>>
+    where
+        S: Debug + Default + Clone + PartialEq + Sync + Send,
+        A: Debug + Default + Clone + Sync + Send,
+    {
+        let render_pipeline = EditorEngineApi::render_engine(args, current_box).await?;
+
+        // Paint into a window that is just big enough for the box.
+        let origin_pos = current_box.style_adjusted_origin_pos;
+        let bounds_size = current_box.style_adjusted_bounds_size;
+        let window_size = size!(
+            col_count: origin_pos.col_index + bounds_size.col_count,
+            row_count: origin_pos.row_index + bounds_size.row_count
+        );
+        let offscreen_buffer = render_pipeline.convert_with_window_size(window_size);
+
+        Ok((0..ch!(@to_usize bounds_size.row_count))
+            .map(|row_index| {
+                offscreen_buffer.get_styled_texts_in_row(
+                    origin_pos.row_index + ch!(row_index),
+                    origin_pos.col_index,
+                    bounds_size.col_count,
+                )
+            })
+            .collect())
+    }
+
     fn render_content<S, A>(
         render_args: &RenderArgs<'_, S, A>,
         render_ops: &mut RenderOps,
@@ -151,16 +407,24 @@ impl EditorEngineApi {
             editor_engine,
             ..
         } = render_args;
-        let Size {
-            col_count: max_display_col_count,
-            row_count: max_display_row_count,
-        } = editor_engine.current_box.style_adjusted_bounds_size;
+        // The scrollbars (if enabled) are not part of the viewport.
+        let max_display_col_count = editor_engine.viewport_width();
+        let max_display_row_count = editor_engine.viewport_height();
 
         let syntax_highlight_enabled = matches!(
             editor_engine.config_options.syntax_highlight,
             SyntaxHighlightMode::Enable(_)
         );
 
+        // Paint the rulers first, so that the content is painted over them.
+        rulers::render_content(
+            editor_buffer,
+            max_display_row_count,
+            render_ops,
+            editor_engine,
+            max_display_col_count,
+        );
+
         if !syntax_highlight_enabled {
             no_syn_hi_path::render_content(
                 editor_buffer,
@@ -169,11 +433,30 @@ impl EditorEngineApi {
                 editor_engine,
                 max_display_col_count,
             );
-            return;
+        } else {
+            let _span = RenderStageSpan::enter(RenderStage::Highlight);
+
+            // Render using syntect first.
+            syn_hi_syntect_path::render_content(
+                editor_buffer,
+                max_display_row_count,
+                render_ops,
+                editor_engine,
+                max_display_col_count,
+            );
+
+            // Any overrides can be applied here.
+            syn_hi_r3bl_path::render_content(
+                editor_buffer,
+                max_display_row_count,
+                render_ops,
+                editor_engine,
+                max_display_col_count,
+            );
         }
 
-        // Render using syntect first.
-        syn_hi_syntect_path::render_content(
+        // Paint indent guides on top of the (leading whitespace of the) content.
+        indent_guides::render_content(
             editor_buffer,
             max_display_row_count,
             render_ops,
@@ -181,14 +464,122 @@ impl EditorEngineApi {
             max_display_col_count,
         );
 
-        // Any overrides can be applied here.
-        syn_hi_r3bl_path::render_content(
+        // Paint whitespace markers on top of the content.
+        whitespace_markers::render_content(
             editor_buffer,
             max_display_row_count,
             render_ops,
             editor_engine,
             max_display_col_count,
         );
+
+        // Paint clip markers on top of the content.
+        clip_markers::render_content(
+            editor_buffer,
+            max_display_row_count,
+            render_ops,
+            editor_engine,
+            max_display_col_count,
+        );
+    }
+
+    /// Paint the scrollbars that are enabled in [EditorEngineConfig::scrollbars] in the
+    /// last col (vertical) & last row (horizontal) of the box, outside the viewport.
+    fn render_scrollbars<S, A>(
+        render_args: &RenderArgs<'_, S, A>,
+        render_ops: &mut RenderOps,
+    ) where
+        S: Debug + Default + Clone + PartialEq + Sync + Send,
+        A: Debug + Default + Clone + Sync + Send,
+    {
+        let RenderArgs {
+            editor_buffer,
+            editor_engine,
+            ..
+        } = render_args;
+
+        let scrollbars = editor_engine.config_options.scrollbars;
+        let markers = &editor_engine.config_options.scrollbar_markers;
+        let viewport_width = editor_engine.viewport_width();
+        let viewport_height = editor_engine.viewport_height();
+        let scroll_offset = editor_buffer.get_scroll_offset();
+        let origin_pos = editor_engine.current_box.style_adjusted_origin_pos;
+
+        if scrollbars.has_vertical() {
+            // The rows that can be scrolled past the end of the buffer are content too.
+            let content_height = editor_buffer.len().max(
+                editor_buffer.get_max_scroll_row_index(
+                    viewport_height,
+                    editor_engine.config_options.overscroll,
+                ) + viewport_height,
+            );
+            let (thumb_start, thumb_len) = ScrollbarMode::get_thumb_range(
+                viewport_height,
+                content_height,
+                scroll_offset.row_index,
+            );
+            for row_index in 0..ch!(@to_usize viewport_height) {
+                let is_thumb = ch!(row_index) >= thumb_start
+                    && ch!(row_index) < thumb_start + thumb_len;
+                let (marker_char, style) = if is_thumb {
+                    (markers.thumb_char, markers.thumb_style)
+                } else {
+                    (markers.track_char, markers.track_style)
+                };
+                render_ops.push(RenderOp::MoveCursorPositionRelTo(
+                    origin_pos,
+                    position! { col_index: viewport_width, row_index: ch!(row_index) },
+                ));
+                render_ops.push(RenderOp::ApplyColors(Some(style)));
+                render_ops.push(RenderOp::PaintTextWithAttributes(
+                    marker_char.to_string(),
+                    None,
+                ));
+                render_ops.push(RenderOp::ResetColor);
+            }
+        }
+
+        if scrollbars.has_horizontal() {
+            let max_line_display_width = editor_buffer
+                .get_lines()
+                .iter()
+                .map(|line| line.display_width)
+                .max()
+                .unwrap_or_default();
+            let (thumb_start, thumb_len) = ScrollbarMode::get_thumb_range(
+                viewport_width,
+                max_line_display_width,
+                scroll_offset.col_index,
+            );
+            let thumb_end = thumb_start + thumb_len;
+            let track_str = |count: ChUnit| {
+                markers.track_char.to_string().repeat(ch!(@to_usize count))
+            };
+
+            render_ops.push(RenderOp::MoveCursorPositionRelTo(
+                origin_pos,
+                position! { col_index: 0, row_index: viewport_height },
+            ));
+            render_ops.push(RenderOp::ApplyColors(Some(markers.track_style)));
+            render_ops.push(RenderOp::PaintTextWithAttributes(
+                track_str(thumb_start),
+                None,
+            ));
+            render_ops.push(RenderOp::ApplyColors(Some(markers.thumb_style)));
+            render_ops.push(RenderOp::PaintTextWithAttributes(
+                markers
+                    .thumb_char
+                    .to_string()
+                    .repeat(ch!(@to_usize thumb_len)),
+                None,
+            ));
+            render_ops.push(RenderOp::ApplyColors(Some(markers.track_style)));
+            render_ops.push(RenderOp::PaintTextWithAttributes(
+                track_str(viewport_width - thumb_end),
+                None,
+            ));
+            render_ops.push(RenderOp::ResetColor);
+        }
     }
 
     // BM: Render selection
@@ -249,13 +640,18 @@ impl EditorEngineApi {
                     ))
                 );
 
-                let position = {
-                    // Convert scroll adjusted to raw.
-                    let raw_row_index = {
-                        let row_scroll_offset = scroll_offset.row_index;
-                        row_index - row_scroll_offset
-                    };
+                // Convert scroll adjusted to raw (skipping rows hidden by folds).
+                let Some(raw_row_index) =
+                    editor_buffer.get_fold_map().get_display_row_index(
+                        scroll_offset.row_index,
+                        lines.len(),
+                        row_index,
+                    )
+                else {
+                    continue;
+                };
 
+                let position = {
                     // Convert scroll adjusted to raw.
                     let raw_col_index = {
                         let col_scroll_offset = scroll_offset.col_index;
@@ -291,53 +687,207 @@ impl EditorEngineApi {
         A: Debug + Default + Clone + Sync + Send,
     {
         let RenderArgs {
-            component_registry,
             editor_buffer,
             editor_engine,
             ..
         } = render_args;
 
-        if component_registry
-            .has_focus
-            .does_id_have_focus(editor_engine.current_box.id)
+        let is_focused = editor_engine.focus_state.is_focused();
+        if !is_focused && !editor_engine.config_options.show_caret_when_blurred {
+            return;
+        }
+
+        let str_at_caret: String = if let Some(UnicodeStringSegmentSliceResult {
+            unicode_string_seg: str_seg,
+            ..
+        }) =
+            EditorEngineInternalApi::string_at_caret(editor_buffer, editor_engine)
         {
-            let str_at_caret: String = if let Some(UnicodeStringSegmentSliceResult {
-                unicode_string_seg: str_seg,
-                ..
-            }) =
-                EditorEngineInternalApi::string_at_caret(editor_buffer, editor_engine)
-            {
-                str_seg.string
-            } else {
-                DEFAULT_CURSOR_CHAR.into()
-            };
+            str_seg.string
+        } else {
+            DEFAULT_CURSOR_CHAR.into()
+        };
+
+        // Take the rows that are hidden by folds into account.
+        let caret_display_position = {
+            let mut raw_caret = editor_buffer.get_caret(CaretKind::Raw);
+
+            // Paint the caret where the char at the caret is displayed, when the line is
+            // reordered for display.
+            if editor_engine.config_options.bidi_reorder {
+                if let Some(line) = EditorEngineInternalApi::line_at_caret_to_string(
+                    editor_buffer,
+                    editor_engine,
+                ) {
+                    let scroll_offset_col_index =
+                        editor_buffer.get_scroll_offset().col_index;
+                    let col_index = get_bidi_visual_col_index(
+                        &line,
+                        editor_buffer.get_caret(CaretKind::ScrollAdjusted).col_index,
+                    );
+                    raw_caret.col_index = col_index - scroll_offset_col_index;
+                }
+            }
 
+            match editor_buffer.get_fold_map().get_display_row_index(
+                editor_buffer.get_scroll_offset().row_index,
+                editor_buffer.get_lines().len(),
+                editor_buffer.get_caret(CaretKind::ScrollAdjusted).row_index,
+            ) {
+                Some(row_index) => position!(
+                    col_index: raw_caret.col_index,
+                    row_index: row_index
+                ),
+                None => raw_caret,
+            }
+        };
+
+        // The caret of an editor that doesn't have focus is hollow, and the terminal
+        // cursor isn't moved back to it.
+        if !is_focused {
             render_ops.push(RenderOp::MoveCursorPositionRelTo(
                 editor_engine.current_box.style_adjusted_origin_pos,
-                editor_buffer.get_caret(CaretKind::Raw),
+                caret_display_position,
             ));
             render_ops.push(RenderOp::PaintTextWithAttributes(
                 str_at_caret,
-                style! { attrib: [reverse] }.into(),
+                get_blurred_caret_style().into(),
             ));
+            render_ops.push(RenderOp::ResetColor);
+            return;
+        }
+
+        render_ops.push(RenderOp::MoveCursorPositionRelTo(
+            editor_engine.current_box.style_adjusted_origin_pos,
+            caret_display_position,
+        ));
+        // In Vim insert mode the caret is a bar, otherwise it is a block. The bar is
+        // approximated by underlining the character at the caret.
+        let caret_style = match KeyBindingsApi::get_vim_mode(editor_engine) {
+            Some(VimMode::Insert) => style! { attrib: [underline] },
+            _ => style! { attrib: [reverse] },
+        };
+
+        render_ops.push(RenderOp::PaintTextWithAttributes(
+            str_at_caret,
+            caret_style.into(),
+        ));
+        render_ops.push(RenderOp::MoveCursorPositionRelTo(
+            editor_engine.current_box.style_adjusted_origin_pos,
+            caret_display_position,
+        ));
+        render_ops.push(RenderOp::ResetColor);
+    }
+
+    /// Paint the secondary carets (that are in the viewport). This has to be done before
+    /// [render_caret](EditorEngineApi::render_caret) so that the terminal cursor ends up
+    /// at the primary caret.
+    fn render_secondary_carets<S, A>(
+        render_args: &RenderArgs<'_, S, A>,
+        render_ops: &mut RenderOps,
+    ) where
+        S: Debug + Default + Clone + PartialEq + Sync + Send,
+        A: Debug + Default + Clone + Sync + Send,
+    {
+        let RenderArgs {
+            editor_buffer,
+            editor_engine,
+            ..
+        } = render_args;
+
+        if !editor_engine.focus_state.is_focused() {
+            return;
+        }
+
+        let scroll_offset = editor_buffer.get_scroll_offset();
+        let viewport_width = editor_engine.viewport_width();
+        let viewport_height = editor_engine.viewport_height();
+
+        for caret in editor_buffer.get_secondary_carets() {
+            // Take the rows that are hidden by folds into account.
+            let Some(row_index) = editor_buffer.get_fold_map().get_display_row_index(
+                scroll_offset.row_index,
+                editor_buffer.get_lines().len(),
+                caret.row_index,
+            ) else {
+                continue;
+            };
+            let is_in_viewport = row_index < viewport_height
+                && caret.col_index >= scroll_offset.col_index
+                && caret.col_index - scroll_offset.col_index < viewport_width;
+            if !is_in_viewport {
+                continue;
+            }
+
+            let str_at_caret: String = editor_buffer
+                .get_lines()
+                .get(ch!(@to_usize caret.row_index))
+                .and_then(|line| line.get_string_at_display_col_index(caret.col_index))
+                .map(|it| it.unicode_string_seg.string)
+                .unwrap_or_else(|| DEFAULT_CURSOR_CHAR.into());
+
             render_ops.push(RenderOp::MoveCursorPositionRelTo(
                 editor_engine.current_box.style_adjusted_origin_pos,
-                editor_buffer.get_caret(CaretKind::Raw),
+                position!(
+                    col_index: caret.col_index - scroll_offset.col_index,
+                    row_index: row_index
+                ),
+            ));
+            render_ops.push(RenderOp::PaintTextWithAttributes(
+                str_at_caret,
+                get_secondary_caret_style().into(),
             ));
             render_ops.push(RenderOp::ResetColor);
         }
     }
 
+    /// Paint the current [VimMode] (when [KeyBindingMode::Vim] is used) and whether
+    /// [InsertMode::Overwrite] is active in the bottom right corner of the editor.
+    fn render_mode_indicator<S, A>(
+        render_args: &RenderArgs<'_, S, A>,
+        render_ops: &mut RenderOps,
+    ) where
+        S: Debug + Default + Clone + PartialEq + Sync + Send,
+        A: Debug + Default + Clone + Sync + Send,
+    {
+        let RenderArgs { editor_engine, .. } = render_args;
+
+        let mut labels = vec![];
+        if let Some(vim_mode) = KeyBindingsApi::get_vim_mode(editor_engine) {
+            labels.push(vim_mode.to_string());
+        }
+        if editor_engine.insert_mode == InsertMode::Overwrite {
+            labels.push(OVERWRITE_MODE_INDICATOR_TEXT.to_string());
+        }
+        if labels.is_empty() {
+            return;
+        }
+
+        let max_display_col_count = editor_engine.viewport_width();
+        let max_display_row_count = editor_engine.viewport_height();
+
+        let indicator = UnicodeString::from(format!(" {} ", labels.join(" ")));
+        let col_index = max_display_col_count - indicator.display_width;
+        let row_index = max_display_row_count - 1;
+
+        render_ops.push(RenderOp::MoveCursorPositionRelTo(
+            editor_engine.current_box.style_adjusted_origin_pos,
+            position! { col_index: col_index, row_index: row_index },
+        ));
+        render_ops.push(RenderOp::ApplyColors(Some(get_vim_mode_indicator_style())));
+        render_ops.push(RenderOp::PaintTextWithAttributes(
+            indicator.string,
+            get_vim_mode_indicator_style().into(),
+        ));
+        render_ops.push(RenderOp::ResetColor);
+    }
+
     pub fn render_empty_state<S, A>(render_args: &RenderArgs<'_, S, A>) -> RenderPipeline
     where
         S: Debug + Default + Clone + PartialEq + Sync + Send,
         A: Debug + Default + Clone + Sync + Send,
     {
-        let RenderArgs {
-            component_registry,
-            editor_engine,
-            ..
-        } = render_args;
+        let RenderArgs { editor_engine, .. } = render_args;
         let mut pipeline = render_pipeline!();
         let mut content_cursor_pos = position! { col_index: 0 , row_index: 0 };
 
@@ -358,10 +908,7 @@ impl EditorEngineApi {
         };
 
         // Paint the emoji.
-        if component_registry
-            .has_focus
-            .does_id_have_focus(editor_engine.current_box.id)
-        {
+        if editor_engine.focus_state.is_focused() {
             render_pipeline! {
               @push_into pipeline
               at ZOrder::Normal
@@ -387,12 +934,15 @@ where
 {
     Applied(T),
     NotApplied,
+    /// The buffer isn't changed, the app has to carry out the command. More info in
+    /// [EditorCommand].
+    Command(EditorCommand),
 }
 
 mod syn_hi_r3bl_path {
     use super::*;
 
-    /// Try convert [Vec] of [US] to [MdDocument]:
+    /// Try convert [EditorLines] to [MdDocument]:
     /// - Step 1: Get the lines from the buffer using
     ///           [editor_buffer.get_lines()](EditorBuffer::get_lines()).
     /// - Step 2: Convert the lines into a [List] of [StyleUSSpanLine] using
@@ -405,7 +955,7 @@ mod syn_hi_r3bl_path {
         editor_engine: &&mut EditorEngine,
         max_display_col_count: ChUnit,
     ) {
-        // Try to parse the Vec<US> into an MDDocument & render it.
+        // Try to parse the EditorLines into an MDDocument & render it.
         try_render_content(
             editor_buffer,
             max_display_row_count,
@@ -432,7 +982,8 @@ mod syn_hi_r3bl_path {
         let lines = try_parse_and_highlight(
             editor_buffer.get_lines(),
             &editor_engine.current_box.get_computed_style(),
-            Some((&editor_engine.syntax_set, &editor_engine.theme)),
+            &editor_engine.markdown_style_config,
+            Some((&editor_engine.syntax_set, editor_engine.theme.as_ref())),
         )?;
 
         call_if_true!(DEBUG_TUI_SYN_HI, {
@@ -445,9 +996,9 @@ mod syn_hi_r3bl_path {
             ));
         });
 
-        for (row_index, line) in lines
-            .iter()
-            .skip(ch!(@to_usize editor_buffer.get_scroll_offset().row_index))
+        for (row_index, visible_row) in editor_buffer
+            .get_fold_map()
+            .visible_rows(editor_buffer.get_scroll_offset().row_index, lines.len())
             .enumerate()
         {
             // Clip the content to max rows.
@@ -455,14 +1006,31 @@ mod syn_hi_r3bl_path {
                 break;
             }
 
-            render_single_line(
-                line,
-                editor_buffer,
-                editor_engine,
-                row_index,
-                max_display_col_count,
-                render_ops,
-            );
+            match visible_row {
+                VisibleRow::Line(line_row_index) => {
+                    let Some(line) = lines.get(ch!(@to_usize line_row_index)) else {
+                        break;
+                    };
+                    render_single_line(
+                        line,
+                        line_row_index,
+                        editor_buffer,
+                        editor_engine,
+                        row_index,
+                        max_display_col_count,
+                        render_ops,
+                    );
+                }
+                VisibleRow::Folded(fold) => {
+                    no_syn_hi_path::render_folded_region_summary(
+                        render_ops,
+                        row_index,
+                        editor_engine,
+                        fold,
+                        max_display_col_count,
+                    );
+                }
+            }
         }
 
         Ok(())
@@ -470,6 +1038,7 @@ mod syn_hi_r3bl_path {
 
     fn render_single_line(
         line: &List<StyleUSSpan>,
+        line_row_index: ChUnit,
         editor_buffer: &&EditorBuffer,
         editor_engine: &&mut EditorEngine,
         row_index: usize,
@@ -481,8 +1050,32 @@ mod syn_hi_r3bl_path {
             position! { col_index: 0 , row_index: ch!(@to_usize row_index) },
         ));
         let scroll_offset_col = editor_buffer.get_scroll_offset().col_index;
-        let styled_texts: StyledTexts =
+        let maybe_reordered_line = if editor_engine.config_options.bidi_reorder {
+            reorder_styled_line_for_display(line)
+        } else {
+            None
+        };
+        let line = maybe_reordered_line.as_ref().unwrap_or(line);
+        let mut styled_texts: StyledTexts =
             line.clip(scroll_offset_col, max_display_col_count);
+        if editor_engine.config_options.extend_bg_to_line_end {
+            if let Some(last_span) = line.last() {
+                extend_bg_to_line_end(
+                    &mut styled_texts,
+                    last_span.style,
+                    max_display_col_count,
+                );
+            }
+        }
+        if let Some(current_line_style) =
+            current_line::get_style_for_line(editor_engine, editor_buffer, line_row_index)
+        {
+            apply_current_line_style(
+                &mut styled_texts,
+                current_line_style,
+                max_display_col_count,
+            );
+        }
         styled_texts.render_into(render_ops);
         render_ops.push(RenderOp::ResetColor);
     }
@@ -491,6 +1084,55 @@ mod syn_hi_r3bl_path {
 mod syn_hi_syntect_path {
     use super::*;
 
+    /// Fill the [HighlightCache] w/ the lines that [render_content] will paint. Returns
+    /// `true` if the [EditorEngineConfig::highlight_budget] ran out before all of them
+    /// were highlighted.
+    pub fn highlight_visible_lines(
+        editor_buffer: &EditorBuffer,
+        editor_engine: &mut EditorEngine,
+    ) -> bool {
+        if !matches!(
+            editor_engine.config_options.syntax_highlight,
+            SyntaxHighlightMode::Enable(_)
+        ) {
+            return false;
+        }
+        let Some(file_ext) = editor_buffer.get_maybe_file_extension() else {
+            return false;
+        };
+
+        let _span = RenderStageSpan::enter(RenderStage::Highlight);
+        let lines = editor_buffer.get_lines();
+        // Same rows as render_content, which paints up to (& including) this row.
+        let max_display_row_count = ch!(@to_usize editor_engine.viewport_height());
+        let visible_lines = editor_buffer
+            .get_fold_map()
+            .visible_rows(editor_buffer.get_scroll_offset().row_index, lines.len())
+            .take(max_display_row_count + 1)
+            .filter_map(|visible_row| match visible_row {
+                VisibleRow::Line(line_row_index) => {
+                    lines.get(ch!(@to_usize line_row_index))
+                }
+                VisibleRow::Folded(_) => None,
+            })
+            .map(|line| line.string.as_str());
+
+        let EditorEngine {
+            syntax_set,
+            theme,
+            config_options,
+            highlight_cache,
+            ..
+        } = editor_engine;
+        highlight_cache.highlight_lines(
+            visible_lines,
+            file_ext,
+            syntax_set,
+            theme,
+            config_options.highlight_budget,
+        )
+    }
+
     pub fn render_content(
         editor_buffer: &&EditorBuffer,
         max_display_row_count: ChUnit,
@@ -500,10 +1142,11 @@ mod syn_hi_syntect_path {
     ) {
         // Paint each line in the buffer (skipping the scroll_offset.row).
         // https://doc.rust-lang.org/std/iter/trait.Iterator.html#method.skip
-        for (row_index, line) in editor_buffer
-            .get_lines()
-            .iter()
-            .skip(ch!(@to_usize editor_buffer.get_scroll_offset().row_index))
+        // Folded regions are collapsed into a single summary line.
+        let lines = editor_buffer.get_lines();
+        for (row_index, visible_row) in editor_buffer
+            .get_fold_map()
+            .visible_rows(editor_buffer.get_scroll_offset().row_index, lines.len())
             .enumerate()
         {
             // Clip the content to max rows.
@@ -511,14 +1154,28 @@ mod syn_hi_syntect_path {
                 break;
             }
 
-            render_single_line(
-                render_ops,
-                row_index,
-                editor_engine,
-                editor_buffer,
-                line,
-                max_display_col_count,
-            );
+            match visible_row {
+                VisibleRow::Line(line_row_index) => {
+                    render_single_line(
+                        render_ops,
+                        row_index,
+                        editor_engine,
+                        editor_buffer,
+                        &lines[ch!(@to_usize line_row_index)],
+                        line_row_index,
+                        max_display_col_count,
+                    );
+                }
+                VisibleRow::Folded(fold) => {
+                    no_syn_hi_path::render_folded_region_summary(
+                        render_ops,
+                        row_index,
+                        editor_engine,
+                        fold,
+                        max_display_col_count,
+                    );
+                }
+            }
         }
     }
 
@@ -528,6 +1185,7 @@ mod syn_hi_syntect_path {
         editor_engine: &&mut EditorEngine,
         editor_buffer: &&EditorBuffer,
         line: &UnicodeString,
+        line_row_index: ChUnit,
         max_display_col_count: ChUnit,
     ) {
         render_ops.push(RenderOp::MoveCursorPositionRelTo(
@@ -535,15 +1193,16 @@ mod syn_hi_syntect_path {
             position! { col_index: 0 , row_index: ch!(@to_usize row_index) },
         ));
 
-        let it =
-            try_get_syntect_highlighted_line(editor_engine, editor_buffer, &line.string);
-
-        match it {
-            // If enabled, and we have a SyntaxReference then try and highlight the line.
-            Some(syntect_highlighted_line) => {
+        // Lines that aren't in the cache yet (the highlight budget ran out) are painted w/out
+        // syntax highlighting in this frame.
+        match editor_engine.highlight_cache.get(&line.string) {
+            // If enabled, and the line has been highlighted, then paint it.
+            Some(styled_line) => {
                 render_line_with_syntect(
-                    syntect_highlighted_line,
+                    styled_line,
+                    line_row_index,
                     editor_buffer,
+                    editor_engine,
                     max_display_col_count,
                     render_ops,
                 );
@@ -552,6 +1211,7 @@ mod syn_hi_syntect_path {
             None => {
                 no_syn_hi_path::render_line_no_syntax_highlight(
                     line,
+                    line_row_index,
                     editor_buffer,
                     max_display_col_count,
                     render_ops,
@@ -562,45 +1222,135 @@ mod syn_hi_syntect_path {
     }
 
     fn render_line_with_syntect(
-        syntect_highlighted_line: Vec<(syntect::highlighting::Style, &str)>,
+        styled_line: &StyleUSSpanLine,
+        line_row_index: ChUnit,
         editor_buffer: &&EditorBuffer,
+        editor_engine: &&mut EditorEngine,
         max_display_col_count: ChUnit,
         render_ops: &mut RenderOps,
     ) {
         let scroll_offset_col = editor_buffer.get_scroll_offset().col_index;
-        let list: List<StyleUSSpan> =
-            syntect_to_styled_text_conversion::from_syntect_to_tui(
-                syntect_highlighted_line,
-            );
-        let styled_texts: StyledTexts =
+        let maybe_reordered_line = if editor_engine.config_options.bidi_reorder {
+            reorder_styled_line_for_display(styled_line)
+        } else {
+            None
+        };
+        let list = maybe_reordered_line.as_ref().unwrap_or(styled_line);
+        let mut styled_texts: StyledTexts =
             list.clip(scroll_offset_col, max_display_col_count);
+        if let Some(current_line_style) =
+            current_line::get_style_for_line(editor_engine, editor_buffer, line_row_index)
+        {
+            apply_current_line_style(
+                &mut styled_texts,
+                current_line_style,
+                max_display_col_count,
+            );
+        }
         styled_texts.render_into(render_ops);
         render_ops.push(RenderOp::ResetColor);
     }
+}
+
+mod whitespace_markers {
+    use super::*;
+
+    /// Paint markers on top of the whitespace in the lines that have already been
+    /// rendered. This is display only, the buffer content & caret are not affected. Tabs
+    /// have no display width in the buffer, so the tab marker is painted at the column
+    /// where the tab starts.
+    pub fn render_content(
+        editor_buffer: &&EditorBuffer,
+        max_display_row_count: ChUnit,
+        render_ops: &mut RenderOps,
+        editor_engine: &&mut EditorEngine,
+        max_display_col_count: ChUnit,
+    ) {
+        if editor_engine.config_options.render_whitespace == WhitespaceMode::None {
+            return;
+        }
+
+        let lines = editor_buffer.get_lines();
+        for (row_index, visible_row) in editor_buffer
+            .get_fold_map()
+            .visible_rows(editor_buffer.get_scroll_offset().row_index, lines.len())
+            .enumerate()
+        {
+            // Clip the content to max rows.
+            if ch!(row_index) > max_display_row_count {
+                break;
+            }
+
+            // Folded regions don't have any whitespace to mark.
+            if let VisibleRow::Line(line_row_index) = visible_row {
+                render_single_line(
+                    render_ops,
+                    row_index,
+                    editor_engine,
+                    editor_buffer,
+                    &lines[ch!(@to_usize line_row_index)],
+                    line_row_index,
+                    max_display_col_count,
+                );
+            }
+        }
+    }
 
-    /// Try and load syntax highlighting for the current line. It might seem lossy to
-    /// create a new [HighlightLines] for each line, but if this struct is re-used then it
-    /// will not be able to highlight the lines correctly in the editor component. This
-    /// struct is mutated when it is used to highlight a line, so it must be re-created
-    /// for each line.
-    fn try_get_syntect_highlighted_line<'a>(
-        editor_engine: &'a &mut EditorEngine,
+    fn render_single_line(
+        render_ops: &mut RenderOps,
+        row_index: usize,
+        editor_engine: &&mut EditorEngine,
         editor_buffer: &&EditorBuffer,
-        line: &'a str,
-    ) -> Option<Vec<(syntect::highlighting::Style, &'a str)>> {
-        let file_ext = editor_buffer.get_maybe_file_extension()?;
-        let syntax_ref = try_get_syntax_ref(&editor_engine.syntax_set, file_ext)?;
-        let theme = &editor_engine.theme;
-        let mut highlighter = HighlightLines::new(syntax_ref, theme);
-        highlighter
-            .highlight_line(line, &editor_engine.syntax_set)
-            .ok()
+        line: &UnicodeString,
+        line_row_index: ChUnit,
+        max_display_col_count: ChUnit,
+    ) {
+        let config_options = &editor_engine.config_options;
+        let scroll_offset_col_index = editor_buffer.get_scroll_offset().col_index;
+        // Keep the background of the current line under the markers.
+        let mut marker_style = config_options.whitespace_markers.style;
+        marker_style += &current_line::get_style_for_line(
+            editor_engine,
+            editor_buffer,
+            line_row_index,
+        );
+
+        for segment in config_options.render_whitespace.get_marked_segments(line) {
+            let Some(marker_char) =
+                config_options.whitespace_markers.get_marker_char(segment)
+            else {
+                continue;
+            };
+
+            // Skip the markers that are outside the viewport.
+            if segment.display_col_offset < scroll_offset_col_index {
+                continue;
+            }
+            let col_index = segment.display_col_offset - scroll_offset_col_index;
+            if col_index >= max_display_col_count {
+                break;
+            }
+
+            render_ops.push(RenderOp::MoveCursorPositionRelTo(
+                editor_engine.current_box.style_adjusted_origin_pos,
+                position! { col_index: col_index , row_index: ch!(@to_usize row_index) },
+            ));
+            render_ops.push(RenderOp::ApplyColors(Some(marker_style)));
+            render_ops.push(RenderOp::PaintTextWithAttributes(
+                marker_char.to_string(),
+                None,
+            ));
+            render_ops.push(RenderOp::ResetColor);
+        }
     }
 }
 
-mod no_syn_hi_path {
+mod clip_markers {
     use super::*;
 
+    /// Paint a marker at the start and/or end of the lines that have content which is
+    /// hidden to the left and/or right of the viewport. This is display only, like the
+    /// whitespace markers.
     pub fn render_content(
         editor_buffer: &&EditorBuffer,
         max_display_row_count: ChUnit,
@@ -608,12 +1358,134 @@ mod no_syn_hi_path {
         editor_engine: &&mut EditorEngine,
         max_display_col_count: ChUnit,
     ) {
-        // Paint each line in the buffer (skipping the scroll_offset.row).
-        // https://doc.rust-lang.org/std/iter/trait.Iterator.html#method.skip
-        for (row_index, line) in editor_buffer
-            .get_lines()
-            .iter()
-            .skip(ch!(@to_usize editor_buffer.get_scroll_offset().row_index))
+        if !editor_engine.config_options.render_clip_markers
+            || max_display_col_count == ch!(0)
+        {
+            return;
+        }
+
+        let markers = &editor_engine.config_options.scrollbar_markers;
+        let scroll_offset_col_index = editor_buffer.get_scroll_offset().col_index;
+        let lines = editor_buffer.get_lines();
+        for (row_index, visible_row) in editor_buffer
+            .get_fold_map()
+            .visible_rows(editor_buffer.get_scroll_offset().row_index, lines.len())
+            .enumerate()
+        {
+            // Clip the content to max rows.
+            if ch!(row_index) >= max_display_row_count {
+                break;
+            }
+
+            let VisibleRow::Line(line_row_index) = visible_row else {
+                continue;
+            };
+            let line_display_width = lines[ch!(@to_usize line_row_index)].display_width;
+            let mut clip_marker_style = markers.clip_marker_style;
+            clip_marker_style += &current_line::get_style_for_line(
+                editor_engine,
+                editor_buffer,
+                line_row_index,
+            );
+
+            let is_clipped_at_start =
+                scroll_offset_col_index > ch!(0) && line_display_width > ch!(0);
+            let is_clipped_at_end =
+                line_display_width > scroll_offset_col_index + max_display_col_count;
+
+            for (is_clipped, col_index, marker_char) in [
+                (is_clipped_at_start, ch!(0), markers.clip_start_char),
+                (
+                    is_clipped_at_end,
+                    max_display_col_count - 1,
+                    markers.clip_end_char,
+                ),
+            ] {
+                if !is_clipped {
+                    continue;
+                }
+                render_ops.push(RenderOp::MoveCursorPositionRelTo(
+                    editor_engine.current_box.style_adjusted_origin_pos,
+                    position! { col_index: col_index, row_index: ch!(row_index) },
+                ));
+                render_ops.push(RenderOp::ApplyColors(Some(clip_marker_style)));
+                render_ops.push(RenderOp::PaintTextWithAttributes(
+                    marker_char.to_string(),
+                    None,
+                ));
+                render_ops.push(RenderOp::ResetColor);
+            }
+        }
+    }
+}
+
+mod rulers {
+    use super::*;
+
+    /// Paint a vertical guide at each of the [EditorEngineConfig::rulers] that are in the
+    /// viewport (after the horizontal scroll), on every row of the viewport. The
+    /// [current_box](EditorEngine::current_box) already starts after the gutter, so the
+    /// guides are painted relative to it.
+    pub fn render_content(
+        editor_buffer: &&EditorBuffer,
+        max_display_row_count: ChUnit,
+        render_ops: &mut RenderOps,
+        editor_engine: &&mut EditorEngine,
+        max_display_col_count: ChUnit,
+    ) {
+        let scroll_offset_col_index = editor_buffer.get_scroll_offset().col_index;
+        for ruler_col_index in &editor_engine.config_options.rulers {
+            let ruler_col_index = ch!(*ruler_col_index);
+            // Skip the rulers that are outside the viewport.
+            if ruler_col_index < scroll_offset_col_index {
+                continue;
+            }
+            let col_index = ruler_col_index - scroll_offset_col_index;
+            if col_index >= max_display_col_count {
+                continue;
+            }
+
+            for row_index in 0..ch!(@to_usize max_display_row_count) {
+                render_ops.push(RenderOp::MoveCursorPositionRelTo(
+                    editor_engine.current_box.style_adjusted_origin_pos,
+                    position! { col_index: col_index, row_index: ch!(row_index) },
+                ));
+                render_ops.push(RenderOp::ApplyColors(Some(get_ruler_style())));
+                render_ops.push(RenderOp::PaintTextWithAttributes(
+                    RULER_CHAR.to_string(),
+                    None,
+                ));
+                render_ops.push(RenderOp::ResetColor);
+            }
+        }
+    }
+}
+
+mod indent_guides {
+    use super::*;
+
+    /// Paint a guide at each [tab_width](EditorEngineConfig::tab_width) col that is in the
+    /// leading whitespace of the visible lines (after the horizontal scroll), on top of the
+    /// content that has already been rendered. This is display only, the buffer content &
+    /// caret are not affected.
+    pub fn render_content(
+        editor_buffer: &&EditorBuffer,
+        max_display_row_count: ChUnit,
+        render_ops: &mut RenderOps,
+        editor_engine: &&mut EditorEngine,
+        max_display_col_count: ChUnit,
+    ) {
+        let config_options = &editor_engine.config_options;
+        if !config_options.indent_guides || config_options.tab_width == 0 {
+            return;
+        }
+        let tab_width = ch!(config_options.tab_width);
+        let scroll_offset_col_index = editor_buffer.get_scroll_offset().col_index;
+
+        let lines = editor_buffer.get_lines();
+        for (row_index, visible_row) in editor_buffer
+            .get_fold_map()
+            .visible_rows(editor_buffer.get_scroll_offset().row_index, lines.len())
             .enumerate()
         {
             // Clip the content to max rows.
@@ -621,14 +1493,141 @@ mod no_syn_hi_path {
                 break;
             }
 
-            render_single_line(
-                render_ops,
-                row_index,
+            // Folded regions are painted as a summary line, w/out guides.
+            let VisibleRow::Line(line_row_index) = visible_row else {
+                continue;
+            };
+            let indent_width = get_indent_width(lines, ch!(@to_usize line_row_index));
+            // Keep the background of the current line under the guides.
+            let mut guide_style = get_indent_guide_style();
+            guide_style += &current_line::get_style_for_line(
                 editor_engine,
                 editor_buffer,
-                line,
-                max_display_col_count,
+                line_row_index,
             );
+
+            let mut guide_col_index = ch!(0);
+            while guide_col_index < indent_width {
+                let is_in_viewport = guide_col_index >= scroll_offset_col_index
+                    && guide_col_index - scroll_offset_col_index < max_display_col_count;
+                if is_in_viewport {
+                    render_ops.push(RenderOp::MoveCursorPositionRelTo(
+                        editor_engine.current_box.style_adjusted_origin_pos,
+                        position! {
+                            col_index: guide_col_index - scroll_offset_col_index,
+                            row_index: ch!(row_index)
+                        },
+                    ));
+                    render_ops.push(RenderOp::ApplyColors(Some(guide_style)));
+                    render_ops.push(RenderOp::PaintTextWithAttributes(
+                        INDENT_GUIDE_CHAR.to_string(),
+                        None,
+                    ));
+                    render_ops.push(RenderOp::ResetColor);
+                }
+                guide_col_index += tab_width;
+            }
+        }
+    }
+
+    /// Returns the display width of the leading whitespace of the line at `row_index`. A
+    /// blank line (empty, or just whitespace) gets the smaller indent of the nearest
+    /// non blank lines above & below it, so that the guides continue through it.
+    fn get_indent_width(lines: &EditorLines, row_index: usize) -> ChUnit {
+        if let Some(indent_width) = get_leading_whitespace_width(&lines[row_index]) {
+            return indent_width;
+        }
+        let above = (0..row_index)
+            .rev()
+            .find_map(|it| get_leading_whitespace_width(&lines[it]));
+        let below = (row_index + 1..lines.len())
+            .find_map(|it| get_leading_whitespace_width(&lines[it]));
+        match (above, below) {
+            (Some(above), Some(below)) => above.min(below),
+            _ => ch!(0),
+        }
+    }
+
+    /// Returns [None] if the `line` is blank.
+    fn get_leading_whitespace_width(line: &UnicodeString) -> Option<ChUnit> {
+        let first_non_whitespace_segment = line
+            .iter()
+            .find(|it| !it.string.chars().all(char::is_whitespace))?;
+        Some(first_non_whitespace_segment.display_col_offset)
+    }
+}
+
+mod current_line {
+    use super::*;
+
+    /// Returns the [current_line_style](EditorEngineConfig::current_line_style) if
+    /// [highlight_current_line](EditorEngineConfig::highlight_current_line) is enabled,
+    /// and the primary caret or any of the secondary carets is on the line at
+    /// `line_row_index` (in the buffer, not the viewport).
+    pub fn get_style_for_line(
+        editor_engine: &EditorEngine,
+        editor_buffer: &EditorBuffer,
+        line_row_index: ChUnit,
+    ) -> Option<Style> {
+        if !editor_engine.config_options.highlight_current_line {
+            return None;
+        }
+        let has_caret = editor_buffer.get_caret(CaretKind::ScrollAdjusted).row_index
+            == line_row_index
+            || editor_buffer
+                .get_secondary_carets()
+                .iter()
+                .any(|caret| caret.row_index == line_row_index);
+        has_caret.then_some(editor_engine.config_options.current_line_style)
+    }
+}
+
+mod no_syn_hi_path {
+    use super::*;
+
+    pub fn render_content(
+        editor_buffer: &&EditorBuffer,
+        max_display_row_count: ChUnit,
+        render_ops: &mut RenderOps,
+        editor_engine: &&mut EditorEngine,
+        max_display_col_count: ChUnit,
+    ) {
+        // Paint each line in the buffer (skipping the scroll_offset.row).
+        // https://doc.rust-lang.org/std/iter/trait.Iterator.html#method.skip
+        // Folded regions are collapsed into a single summary line.
+        let lines = editor_buffer.get_lines();
+        for (row_index, visible_row) in editor_buffer
+            .get_fold_map()
+            .visible_rows(editor_buffer.get_scroll_offset().row_index, lines.len())
+            .enumerate()
+        {
+            // Clip the content to max rows.
+            if ch!(row_index) > max_display_row_count {
+                break;
+            }
+
+            match visible_row {
+                VisibleRow::Line(line_row_index) => {
+                    render_single_line(
+                        render_ops,
+                        row_index,
+                        editor_engine,
+                        editor_buffer,
+                        &lines[ch!(@to_usize line_row_index)],
+                        line_row_index,
+                        max_display_col_count,
+                    );
+                }
+                VisibleRow::Folded(fold) => {
+                    no_syn_hi_path::render_folded_region_summary(
+                        render_ops,
+                        row_index,
+                        editor_engine,
+                        fold,
+                        max_display_col_count,
+                    );
+                }
+            }
         }
     }
 
@@ -638,6 +1637,7 @@ mod no_syn_hi_path {
         editor_engine: &&mut EditorEngine,
         editor_buffer: &&EditorBuffer,
         line: &UnicodeString,
+        line_row_index: ChUnit,
         max_display_col_count: ChUnit,
     ) {
         render_ops.push(RenderOp::MoveCursorPositionRelTo(
@@ -647,6 +1647,7 @@ mod no_syn_hi_path {
 
         no_syn_hi_path::render_line_no_syntax_highlight(
             line,
+            line_row_index,
             editor_buffer,
             max_display_col_count,
             render_ops,
@@ -654,9 +1655,37 @@ mod no_syn_hi_path {
         );
     }
 
+    /// This is used by all the render paths to paint the summary line for a folded region.
+    pub fn render_folded_region_summary(
+        render_ops: &mut RenderOps,
+        row_index: usize,
+        editor_engine: &&mut EditorEngine,
+        fold: FoldRange,
+        max_display_col_count: ChUnit,
+    ) {
+        render_ops.push(RenderOp::MoveCursorPositionRelTo(
+            editor_engine.current_box.style_adjusted_origin_pos,
+            position! { col_index: 0 , row_index: ch!(@to_usize row_index) },
+        ));
+
+        let summary = UnicodeString::from(format!(
+            "{FOLDED_REGION_SUMMARY_PREFIX} {} lines",
+            ch!(@to_usize fold.row_count())
+        ));
+        let truncated_summary = summary.clip_to_width(ch!(0), max_display_col_count);
+
+        render_ops.push(RenderOp::ApplyColors(Some(get_folded_region_style())));
+        render_ops.push(RenderOp::PaintTextWithAttributes(
+            truncated_summary.into(),
+            None,
+        ));
+        render_ops.push(RenderOp::ResetColor);
+    }
+
     /// This is used as a fallback by other render paths.
     pub fn render_line_no_syntax_highlight(
         line: &UnicodeString,
+        line_row_index: ChUnit,
         editor_buffer: &&EditorBuffer,
         max_display_col_count: ChUnit,
         render_ops: &mut RenderOps,
@@ -664,9 +1693,37 @@ mod no_syn_hi_path {
     ) {
         let scroll_offset_col_index = editor_buffer.get_scroll_offset().col_index;
 
-        // Clip the content [scroll_offset.col .. max cols].
+        let maybe_reordered_line = if editor_engine.config_options.bidi_reorder {
+            reorder_line_for_display(line)
+        } else {
+            None
+        };
+        let line = maybe_reordered_line.as_ref().unwrap_or(line);
+
+        // Clip the content [scroll_offset.col .. max cols]. A wide grapheme cluster that
+        // straddles either edge is painted as spaces.
         let truncated_line =
-            line.clip_to_width(scroll_offset_col_index, max_display_col_count);
+            line.clip_to_width_padded(scroll_offset_col_index, max_display_col_count);
+
+        if let Some(current_line_style) =
+            current_line::get_style_for_line(editor_engine, editor_buffer, line_row_index)
+        {
+            let computed_style = editor_engine
+                .current_box
+                .get_computed_style()
+                .unwrap_or_default();
+            let mut styled_texts = styled_texts! {
+                styled_text! { @style: computed_style, @text: truncated_line }
+            };
+            apply_current_line_style(
+                &mut styled_texts,
+                current_line_style,
+                max_display_col_count,
+            );
+            styled_texts.render_into(render_ops);
+            render_ops.push(RenderOp::ResetColor);
+            return;
+        }
 
         render_ops.push(RenderOp::ApplyColors(
             editor_engine.current_box.get_computed_style(),
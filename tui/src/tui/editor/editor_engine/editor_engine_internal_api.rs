@@ -91,6 +91,14 @@ impl EditorEngineInternalApi {
         caret_mut::to_end_of_line(buffer, engine, select_mode)
     }
 
+    pub fn next_word(buffer: &mut EditorBuffer, engine: &mut EditorEngine) -> Option<()> {
+        caret_mut::to_next_word(buffer, engine)
+    }
+
+    pub fn prev_word(buffer: &mut EditorBuffer, engine: &mut EditorEngine) -> Option<()> {
+        caret_mut::to_prev_word(buffer, engine)
+    }
+
     pub fn validate_scroll(args: EditorArgsMut) {
         scroll_editor_buffer::validate_scroll(args);
     }
@@ -134,6 +142,119 @@ impl EditorEngineInternalApi {
     ) -> Option<()> {
         content_mut::backspace_at_caret(buffer, engine)
     }
+
+    /// Returns the text that was deleted.
+    pub fn delete_line_at_caret(
+        buffer: &mut EditorBuffer,
+        engine: &mut EditorEngine,
+    ) -> Option<String> {
+        content_mut::delete_line_at_caret(buffer, engine)
+    }
+
+    /// Returns the text that was deleted.
+    pub fn delete_word_at_caret(
+        buffer: &mut EditorBuffer,
+        engine: &mut EditorEngine,
+    ) -> Option<String> {
+        content_mut::delete_word_at_caret(buffer, engine)
+    }
+
+    /// Returns the text that was deleted.
+    pub fn delete_to_end_of_line(
+        buffer: &mut EditorBuffer,
+        engine: &mut EditorEngine,
+    ) -> Option<String> {
+        content_mut::delete_to_end_of_line(buffer, engine)
+    }
+
+    pub fn insert_multiline_str_at_caret(args: EditorArgsMut<'_>, text: &str) {
+        content_mut::insert_multiline_str_at_caret(args, text)
+    }
+
+    /// Returns the text that was deleted.
+    pub fn delete_selected(
+        buffer: &mut EditorBuffer,
+        engine: &mut EditorEngine,
+    ) -> Option<String> {
+        content_mut::delete_selected(buffer, engine)
+    }
+
+    pub fn toggle_fold_at_caret(
+        buffer: &mut EditorBuffer,
+        engine: &mut EditorEngine,
+    ) -> Option<()> {
+        fold_mut::toggle_fold_at_caret(buffer, engine)
+    }
+
+    pub fn skip_over_folded_rows(
+        buffer: &mut EditorBuffer,
+        engine: &mut EditorEngine,
+        direction: CaretDirection,
+    ) -> Option<()> {
+        fold_mut::skip_over_folded_rows(buffer, engine, direction)
+    }
+
+    pub fn unfold_rows_touched_by_edit(
+        buffer: &mut EditorBuffer,
+        engine: &mut EditorEngine,
+        editor_event: &EditorEvent,
+    ) {
+        fold_mut::unfold_rows_touched_by_edit(buffer, engine, editor_event)
+    }
+
+    pub fn shift_folds_after_edit(
+        buffer: &mut EditorBuffer,
+        caret_row_before_edit: ChUnit,
+        line_count_before_edit: ChUnit,
+    ) {
+        fold_mut::shift_folds_after_edit(
+            buffer,
+            caret_row_before_edit,
+            line_count_before_edit,
+        )
+    }
+
+    pub fn shift_marks_after_edit(
+        buffer: &mut EditorBuffer,
+        is_caret_row_removed: bool,
+        caret_row_before_edit: ChUnit,
+        line_count_before_edit: ChUnit,
+    ) {
+        mark_mut::shift_marks_after_edit(
+            buffer,
+            is_caret_row_removed,
+            caret_row_before_edit,
+            line_count_before_edit,
+        )
+    }
+
+    pub fn add_caret_below(buffer: &mut EditorBuffer, engine: &mut EditorEngine) {
+        multi_caret_mut::add_caret_below(buffer, engine)
+    }
+
+    pub fn is_multi_caret_edit(editor_event: &EditorEvent) -> bool {
+        multi_caret_mut::is_multi_caret_edit(editor_event)
+    }
+
+    pub fn apply_edit_at_all_carets(
+        buffer: &mut EditorBuffer,
+        engine: &mut EditorEngine,
+        editor_event: &EditorEvent,
+    ) {
+        multi_caret_mut::apply_edit_at_all_carets(buffer, engine, editor_event)
+    }
+
+    pub fn move_secondary_carets(buffer: &mut EditorBuffer, editor_event: &EditorEvent) {
+        multi_caret_mut::move_secondary_carets(buffer, editor_event)
+    }
+
+    pub fn translate_auto_close_pairs(
+        buffer: &EditorBuffer,
+        engine: &EditorEngine,
+        editor_events: Vec<EditorEvent>,
+    ) -> Vec<EditorEvent> {
+        auto_close_pairs::translate(buffer, engine, editor_events)
+    }
 }
 
 /// Helper macros just for this module.
@@ -876,6 +997,100 @@ mod caret_mut {
 
         None
     }
+
+    pub fn to_next_word(
+        editor_buffer: &mut EditorBuffer,
+        editor_engine: &mut EditorEngine,
+    ) -> Option<()> {
+        empty_check_early_return!(editor_buffer, @None);
+
+        let line = content_get::line_at_caret_to_string(editor_buffer, editor_engine)?;
+        let caret_col_index =
+            editor_buffer.get_caret(CaretKind::ScrollAdjusted).col_index;
+
+        match content_get::find_next_word_start(
+            &line,
+            caret_col_index,
+            &editor_engine.config_options.word_char_class,
+        ) {
+            Some(col_index) => {
+                to_col(editor_buffer, editor_engine, col_index);
+            }
+            // Wrap around to the start of the next line.
+            None if content_get::next_line_below_caret_exists(
+                editor_buffer,
+                editor_engine,
+            ) =>
+            {
+                down(editor_buffer, editor_engine, SelectMode::Disabled);
+                to_start_of_line(editor_buffer, editor_engine, SelectMode::Disabled);
+            }
+            None => {
+                to_end_of_line(editor_buffer, editor_engine, SelectMode::Disabled);
+            }
+        }
+
+        None
+    }
+
+    pub fn to_prev_word(
+        editor_buffer: &mut EditorBuffer,
+        editor_engine: &mut EditorEngine,
+    ) -> Option<()> {
+        empty_check_early_return!(editor_buffer, @None);
+
+        let line = content_get::line_at_caret_to_string(editor_buffer, editor_engine)?;
+        let caret_col_index =
+            editor_buffer.get_caret(CaretKind::ScrollAdjusted).col_index;
+
+        match content_get::find_prev_word_start(
+            &line,
+            caret_col_index,
+            &editor_engine.config_options.word_char_class,
+        ) {
+            Some(col_index) => {
+                to_col(editor_buffer, editor_engine, col_index);
+            }
+            // Wrap around to the end of the previous line.
+            None if content_get::prev_line_above_caret_exists(
+                editor_buffer,
+                editor_engine,
+            ) =>
+            {
+                up(editor_buffer, editor_engine, SelectMode::Disabled);
+                to_end_of_line(editor_buffer, editor_engine, SelectMode::Disabled);
+            }
+            None => {
+                to_start_of_line(editor_buffer, editor_engine, SelectMode::Disabled);
+            }
+        }
+
+        None
+    }
+
+    /// Move the caret to `col_index` in the line at the caret.
+    pub fn to_col(
+        editor_buffer: &mut EditorBuffer,
+        editor_engine: &mut EditorEngine,
+        col_index: ChUnit,
+    ) {
+        let viewport_width = editor_engine.viewport_width();
+        let line_display_width =
+            content_get::line_display_width_at_caret(editor_buffer, editor_engine);
+        validate_editor_buffer_change::apply_change(
+            editor_buffer,
+            editor_engine,
+            |_, caret, scroll_offset| {
+                scroll_editor_buffer::set_caret_col(
+                    caret,
+                    scroll_offset,
+                    viewport_width,
+                    line_display_width,
+                    col_index,
+                );
+            },
+        );
+    }
 }
 
 mod content_get {
@@ -1020,6 +1235,54 @@ mod content_get {
         }
         None
     }
+
+    /// Returns the display col index of the start of the next word in `line` after
+    /// `display_col_index`. A word is a run of chars that are in the `word_char_class`, or
+    /// a run of punctuation chars.
+    pub fn find_next_word_start(
+        line: &UnicodeString,
+        display_col_index: ChUnit,
+        word_char_class: &WordCharClass,
+    ) -> Option<ChUnit> {
+        let mut iter = line
+            .iter()
+            .filter(|segment| segment.display_col_offset >= display_col_index)
+            .peekable();
+
+        // Skip the rest of the word that the caret is on.
+        let current_class = word_char_class.get_char_class(iter.peek()?);
+        let mut iter = iter.skip_while(|segment| {
+            current_class != CharClass::Whitespace
+                && word_char_class.get_char_class(segment) == current_class
+        });
+
+        iter.find(|segment| {
+            word_char_class.get_char_class(segment) != CharClass::Whitespace
+        })
+        .map(|segment| segment.display_col_offset)
+    }
+
+    /// Returns the display col index of the start of the word in `line` before
+    /// `display_col_index`.
+    pub fn find_prev_word_start(
+        line: &UnicodeString,
+        display_col_index: ChUnit,
+        word_char_class: &WordCharClass,
+    ) -> Option<ChUnit> {
+        let mut iter = line
+            .iter()
+            .rev()
+            .filter(|segment| segment.display_col_offset < display_col_index)
+            .skip_while(|segment| {
+                word_char_class.get_char_class(segment) == CharClass::Whitespace
+            })
+            .peekable();
+
+        let word_class = word_char_class.get_char_class(iter.peek()?);
+        iter.take_while(|segment| word_char_class.get_char_class(segment) == word_class)
+            .last()
+            .map(|segment| segment.display_col_offset)
+    }
 }
 
 mod content_mut {
@@ -1394,6 +1657,223 @@ mod content_mut {
         }
     }
 
+    /// Delete the line at the caret (including its new line) & move the caret to the start
+    /// of the line that takes its place. If it is the only line, then it is cleared
+    /// instead.
+    pub fn delete_line_at_caret(
+        buffer: &mut EditorBuffer,
+        engine: &mut EditorEngine,
+    ) -> Option<String> {
+        empty_check_early_return!(buffer, @None);
+
+        let deleted_line = content_get::line_at_caret_to_string(buffer, engine)?;
+        let is_only_line = buffer.len() == ch!(1);
+        let is_last_line = !content_get::next_line_below_caret_exists(buffer, engine);
+
+        validate_editor_buffer_change::apply_change(
+            buffer,
+            engine,
+            |lines, caret, scroll_offset| {
+                let row_idx =
+                    EditorBuffer::calc_scroll_adj_caret_row(caret, scroll_offset);
+                if is_only_line {
+                    let _ = replace(&mut lines[row_idx], UnicodeString::from(""));
+                } else {
+                    lines.remove(row_idx);
+                }
+                scroll_editor_buffer::reset_caret_col(caret, scroll_offset);
+            },
+        );
+
+        // The caret is now past the end of the buffer, so move it up.
+        if is_last_line && !is_only_line {
+            scroll_editor_buffer::change_caret_row_by(
+                EditorArgsMut {
+                    editor_buffer: buffer,
+                    editor_engine: engine,
+                },
+                ch!(1),
+                CaretDirection::Up,
+            );
+        }
+
+        Some(format!("{}\n", deleted_line.string))
+    }
+
+    /// Delete from the caret to the start of the next word (or the end of the line if
+    /// there isn't one).
+    pub fn delete_word_at_caret(
+        buffer: &mut EditorBuffer,
+        engine: &mut EditorEngine,
+    ) -> Option<String> {
+        empty_check_early_return!(buffer, @None);
+
+        let line = content_get::line_at_caret_to_string(buffer, engine)?;
+        let start_col_index = buffer.get_caret(CaretKind::ScrollAdjusted).col_index;
+        let end_col_index = content_get::find_next_word_start(
+            &line,
+            start_col_index,
+            &engine.config_options.word_char_class,
+        )
+        .unwrap_or(line.display_width);
+
+        delete_to_col_in_line_at_caret(buffer, engine, line, end_col_index)
+    }
+
+    /// Delete from the caret to the end of the line. If the caret is already at the end
+    /// of the line, then the line below is joined to it instead.
+    pub fn delete_to_end_of_line(
+        buffer: &mut EditorBuffer,
+        engine: &mut EditorEngine,
+    ) -> Option<String> {
+        empty_check_early_return!(buffer, @None);
+
+        let line = content_get::line_at_caret_to_string(buffer, engine)?;
+        let start_col_index = buffer.get_caret(CaretKind::ScrollAdjusted).col_index;
+
+        if start_col_index >= line.display_width {
+            if !content_get::next_line_below_caret_exists(buffer, engine) {
+                return None;
+            }
+            delete_at_caret(buffer, engine);
+            return Some("\n".to_string());
+        }
+
+        let end_col_index = line.display_width;
+        delete_to_col_in_line_at_caret(buffer, engine, line, end_col_index)
+    }
+
+    /// Delete the text in `line` (which is the line at the caret) from the caret to
+    /// `end_col_index`. The caret does not move.
+    fn delete_to_col_in_line_at_caret(
+        buffer: &mut EditorBuffer,
+        engine: &mut EditorEngine,
+        line: UnicodeString,
+        end_col_index: ChUnit,
+    ) -> Option<String> {
+        let start_col_index = buffer.get_caret(CaretKind::ScrollAdjusted).col_index;
+        if end_col_index <= start_col_index {
+            return None;
+        }
+
+        let deleted_str = line
+            .clip_to_range(SelectionRange::new(start_col_index, end_col_index))
+            .to_string();
+        let new_line = UnicodeString::from(format!(
+            "{}{}",
+            line.clip_to_width(ch!(0), start_col_index),
+            line.clip_to_width(end_col_index, line.display_width - end_col_index)
+        ));
+
+        validate_editor_buffer_change::apply_change(
+            buffer,
+            engine,
+            |lines, caret, scroll_offset| {
+                let row_idx =
+                    EditorBuffer::calc_scroll_adj_caret_row(caret, scroll_offset);
+                let _ = replace(&mut lines[row_idx], new_line);
+            },
+        );
+
+        Some(deleted_str)
+    }
+
+    /// Unlike [insert_str_at_caret], `text` can contain new lines.
+    pub fn insert_multiline_str_at_caret(args: EditorArgsMut<'_>, text: &str) {
+        let EditorArgsMut {
+            editor_buffer,
+            editor_engine,
+        } = args;
+
+        for (index, line) in text.split('\n').enumerate() {
+            if index > 0 {
+                insert_new_line_at_caret(EditorArgsMut {
+                    editor_buffer,
+                    editor_engine,
+                });
+            }
+            if !line.is_empty() {
+                insert_str_at_caret(
+                    EditorArgsMut {
+                        editor_buffer,
+                        editor_engine,
+                    },
+                    line,
+                );
+            }
+        }
+    }
+
+    /// Delete the text in the selection & move the caret to where the selection started.
+    /// Lines that are partially selected are joined together. Returns the text that was
+    /// deleted (lines are separated by `\n`).
+    pub fn delete_selected(
+        buffer: &mut EditorBuffer,
+        engine: &mut EditorEngine,
+    ) -> Option<String> {
+        empty_check_early_return!(buffer, @None);
+
+        let mut row_indices = buffer
+            .get_selection_map()
+            .iter()
+            .map(|(row_index, _)| *row_index)
+            .collect::<Vec<_>>();
+        row_indices.sort();
+        let first_row_index = *row_indices.first()?;
+        let last_row_index = *row_indices.last()?;
+
+        let selection_map = buffer.get_selection_map();
+        let first_range = *selection_map.get(first_row_index)?;
+        let last_range = *selection_map.get(last_row_index)?;
+        let lines = buffer.get_lines();
+        let first_line = lines.get(ch!(@to_usize first_row_index))?;
+        let last_line = lines.get(ch!(@to_usize last_row_index))?;
+
+        let deleted_str = row_indices
+            .iter()
+            .filter_map(|row_index| {
+                let line = lines.get(ch!(@to_usize *row_index))?;
+                let range = selection_map.get(*row_index)?;
+                Some(line.clip_to_range(*range))
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        let new_line = UnicodeString::from(format!(
+            "{}{}",
+            first_line.clip_to_width(ch!(0), first_range.start_display_col_index),
+            last_line.clip_to_width(
+                last_range.end_display_col_index,
+                last_line.display_width - last_range.end_display_col_index
+            )
+        ));
+
+        let caret_row_index = buffer.get_caret(CaretKind::ScrollAdjusted).row_index;
+
+        validate_editor_buffer_change::apply_change(buffer, engine, |lines, _, _| {
+            let first_row_idx = ch!(@to_usize first_row_index);
+            let last_row_idx = ch!(@to_usize last_row_index);
+            let _ = replace(&mut lines[first_row_idx], new_line);
+            lines.drain(first_row_idx + 1..=last_row_idx);
+        });
+
+        // Move the caret to where the selection started.
+        if caret_row_index > first_row_index {
+            scroll_editor_buffer::change_caret_row_by(
+                EditorArgsMut {
+                    editor_buffer: buffer,
+                    editor_engine: engine,
+                },
+                caret_row_index - first_row_index,
+                CaretDirection::Up,
+            );
+        }
+        caret_mut::to_col(buffer, engine, first_range.start_display_col_index);
+
+        buffer.clear_selection();
+
+        Some(deleted_str)
+    }
+
     fn insert_into_existing_line(
         args: EditorArgsMut<'_>,
         caret_adj: Position,
@@ -1407,8 +1887,12 @@ mod content_mut {
         let row_index = ch!(@to_usize caret_adj.row_index);
         let line = editor_buffer.get_lines().get(row_index)?;
 
-        let (new_line, char_display_width) =
-            line.insert_char_at_display_col(ch!(caret_adj.col_index), chunk)?;
+        let (new_line, char_display_width) = insert_chunk_at_display_col(
+            line,
+            ch!(caret_adj.col_index),
+            chunk,
+            editor_engine.insert_mode,
+        )?;
 
         let viewport_width = editor_engine.viewport_width();
 
@@ -1434,6 +1918,30 @@ mod content_mut {
         None
     }
 
+    /// Returns the new line and the display width by which the caret has to move. In
+    /// [InsertMode::Overwrite] one grapheme cluster under the caret is removed for each
+    /// one in `chunk` before it is inserted, so the edit is a single replace (that
+    /// appends once the end of the line is reached).
+    pub fn insert_chunk_at_display_col(
+        line: &UnicodeString,
+        col_index: ChUnit,
+        chunk: &str,
+        insert_mode: InsertMode,
+    ) -> Option<(UnicodeString, ChUnit)> {
+        if insert_mode == InsertMode::Insert {
+            return line.insert_char_at_display_col(col_index, chunk);
+        }
+
+        let mut overwritten_line = line.clone();
+        for _ in 0..UnicodeString::from(chunk).len() {
+            if overwritten_line.at_display_col_index(col_index).is_none() {
+                break;
+            }
+            overwritten_line = overwritten_line.delete_char_at_display_col(col_index)?;
+        }
+        overwritten_line.insert_char_at_display_col(col_index, chunk)
+    }
+
     fn fill_in_missing_lines_up_to_row(args: EditorArgsMut<'_>, caret_row: usize) {
         let EditorArgsMut {
             editor_buffer,
@@ -1515,12 +2023,13 @@ pub mod validate_editor_buffer_change {
         editor_buffer: &mut EditorBuffer,
         editor_engine: &mut EditorEngine,
         mutator: impl FnOnce(
-            /* EditorBuffer::lines */ &mut Vec<UnicodeString>,
+            /* EditorBuffer::lines */ &mut EditorLines,
             /* EditorBuffer::caret */ &mut Position,
             /* EditorEngine::scroll_offset */ &mut ScrollOffset,
         ),
     ) -> Option<()> {
         let (lines, caret, scroll_offset, _) = editor_buffer.get_mut();
+        let scroll_row_index_before_change = scroll_offset.row_index;
 
         // Run the mutator first.
         mutator(lines, caret, scroll_offset);
@@ -1536,6 +2045,22 @@ pub mod validate_editor_buffer_change {
             editor_buffer,
         });
 
+        // Keep the scroll margins around the caret.
+        scroll_editor_buffer::apply_scroll_off(EditorArgsMut {
+            editor_engine,
+            editor_buffer,
+        });
+
+        // If the caret had to be followed, then move it to where the scroll strategy
+        // wants it.
+        scroll_editor_buffer::apply_scroll_strategy(
+            EditorArgsMut {
+                editor_engine,
+                editor_buffer,
+            },
+            scroll_row_index_before_change,
+        );
+
         // Check scroll_offset validity.
         if let Some(diff) = is_scroll_offset_in_middle_of_grapheme_cluster(EditorArgs {
             editor_engine,
@@ -1593,6 +2118,9 @@ pub mod validate_editor_buffer_change {
         }
     }
 
+    /// Returns how many cols the scroll offset has to move right so that the caret row
+    /// doesn't start painting in the middle of a wide grapheme cluster, ie: the distance
+    /// to the end of the one that straddles the left edge of the viewport.
     pub fn is_scroll_offset_in_middle_of_grapheme_cluster(
         args: EditorArgs<'_>,
     ) -> Option<ChUnit> {
@@ -1601,24 +2129,14 @@ pub mod validate_editor_buffer_change {
             editor_engine,
         } = args;
         let line = content_get::line_at_caret_to_string(editor_buffer, editor_engine)?;
-
-        let unicode_width_at_caret = {
-            match content_get::string_at_caret(editor_buffer, editor_engine) {
-                None => ch!(0),
-                Some(string_at_caret) => string_at_caret.unicode_width,
-            }
-        };
-
         let scroll_offset = editor_buffer.get_scroll_offset();
 
-        if let Some(segment) = line
-            .is_display_col_index_in_middle_of_grapheme_cluster(scroll_offset.col_index)
-        {
-            let diff = segment.unicode_width - unicode_width_at_caret;
-            return Some(diff);
-        };
-
-        None
+        let segment = line.is_display_col_index_in_middle_of_grapheme_cluster(
+            scroll_offset.col_index,
+        )?;
+        let diff =
+            segment.display_col_offset + segment.unicode_width - scroll_offset.col_index;
+        Some(diff)
     }
 
     pub fn adjust_scroll_offset_because_in_middle_of_grapheme_cluster(
@@ -1793,6 +2311,128 @@ mod scroll_editor_buffer {
         }
     }
 
+    /// If the content was scrolled vertically (from `scroll_row_index_before_change`) to
+    /// follow the caret, then scroll it again so that the caret ends up where the
+    /// [EditorEngineConfig::scroll_strategy] wants it. Nothing changes for
+    /// [ScrollStrategy::MinimalEdge].
+    /// - The caret following includes the [scroll_off](EditorEngineConfig::scroll_off)
+    ///   margin, ie: the strategy kicks in when the caret moves into the margin.
+    /// - [ScrollStrategy::Top] leaves the margin above the caret.
+    /// - It won't scroll past the end of the buffer, plus the
+    ///   [overscroll](EditorEngineConfig::overscroll) rows.
+    ///
+    /// This is meant to be called inside [validate::apply_change], after
+    /// [apply_scroll_off].
+    pub fn apply_scroll_strategy(
+        args: EditorArgsMut<'_>,
+        scroll_row_index_before_change: ChUnit,
+    ) {
+        let EditorArgsMut {
+            editor_buffer,
+            editor_engine,
+        } = args;
+
+        let viewport_height = editor_engine.viewport_height();
+        let target_caret_row_index = match editor_engine.config_options.scroll_strategy {
+            ScrollStrategy::MinimalEdge => return,
+            ScrollStrategy::Center => viewport_height / 2,
+            ScrollStrategy::Top => get_margin_that_fits(
+                editor_engine.config_options.scroll_off,
+                viewport_height,
+            ),
+        };
+        let max_scroll_row_index = editor_buffer.get_max_scroll_row_index(
+            viewport_height,
+            editor_engine.config_options.overscroll,
+        );
+
+        let (_, caret, scroll_offset, _) = editor_buffer.get_mut();
+        if scroll_offset.row_index == scroll_row_index_before_change {
+            return;
+        }
+        let caret_row_index = caret.row_index + scroll_offset.row_index;
+        let scroll_row_index =
+            (caret_row_index - target_caret_row_index).min(max_scroll_row_index);
+        scroll_offset.row_index = scroll_row_index;
+        caret.row_index = caret_row_index - scroll_row_index;
+    }
+
+    /// Scroll the viewport so that there are at least
+    /// [scroll_off](EditorEngineConfig::scroll_off) rows above & below the caret, and
+    /// [side_scroll_off](EditorEngineConfig::side_scroll_off) cols to the left & right of it.
+    /// - The caret does not move in the buffer, only the scroll_offset (and the caret's
+    ///   position in the viewport) change.
+    /// - It won't scroll past the top of the buffer or the start of the line, and it won't
+    ///   scroll down (or right) past the end of the buffer (or line) just to show the
+    ///   margin.
+    /// - If a margin doesn't fit in the viewport, it is reduced to the largest one that
+    ///   does (which can be 0, ie: no margin).
+    ///
+    /// This is meant to be called inside [validate::apply_change].
+    pub fn apply_scroll_off(args: EditorArgsMut<'_>) {
+        let EditorArgsMut {
+            editor_buffer,
+            editor_engine,
+        } = args;
+
+        let viewport_height = editor_engine.viewport_height();
+        let viewport_width = editor_engine.viewport_width();
+        let row_margin = get_margin_that_fits(
+            editor_engine.config_options.scroll_off,
+            viewport_height,
+        );
+        let col_margin = get_margin_that_fits(
+            editor_engine.config_options.side_scroll_off,
+            viewport_width,
+        );
+        let line_count = editor_buffer.len();
+        let line_display_width =
+            content_get::line_display_width_at_caret(editor_buffer, editor_engine);
+
+        let (_, caret, scroll_offset, _) = editor_buffer.get_mut();
+
+        // Vertical.
+        if row_margin > ch!(0) {
+            if caret.row_index < row_margin {
+                let diff = (row_margin - caret.row_index).min(scroll_offset.row_index);
+                scroll_offset.row_index -= diff;
+                caret.row_index += diff;
+            }
+            let max_caret_row_index = viewport_height - 1 - row_margin;
+            if caret.row_index > max_caret_row_index {
+                let max_scroll_row_index = line_count - viewport_height;
+                let diff = (caret.row_index - max_caret_row_index)
+                    .min(max_scroll_row_index - scroll_offset.row_index);
+                scroll_offset.row_index += diff;
+                caret.row_index -= diff;
+            }
+        }
+
+        // Horizontal.
+        if col_margin > ch!(0) {
+            if caret.col_index < col_margin {
+                let diff = (col_margin - caret.col_index).min(scroll_offset.col_index);
+                scroll_offset.col_index -= diff;
+                caret.col_index += diff;
+            }
+            let max_caret_col_index = viewport_width - 1 - col_margin;
+            if caret.col_index > max_caret_col_index {
+                // The caret can be just past the end of the line.
+                let max_scroll_col_index = line_display_width + 1 - viewport_width;
+                let diff = (caret.col_index - max_caret_col_index)
+                    .min(max_scroll_col_index - scroll_offset.col_index);
+                scroll_offset.col_index += diff;
+                caret.col_index -= diff;
+            }
+        }
+    }
+
+    /// The caret needs a row (or col) of its own, so the margin on both sides of it can
+    /// be at most `(viewport_size - 1) / 2`.
+    fn get_margin_that_fits(margin: usize, viewport_size: ChUnit) -> ChUnit {
+        ch!(margin).min((viewport_size - 1) / 2)
+    }
+
     /// This is meant to be called inside [validate::apply_change].
     pub fn reset_caret_col(caret: &mut Position, scroll_offset: &mut ScrollOffset) {
         scroll_offset.col_index = ch!(0);
@@ -2139,6 +2779,592 @@ mod scroll_editor_buffer {
     }
 }
 
+mod fold_mut {
+    use super::*;
+
+    /// If the caret is on a folded region, then unfold it. Otherwise try and fold the
+    /// region that starts at the caret row (see [find_foldable_range]).
+    pub fn toggle_fold_at_caret(
+        editor_buffer: &mut EditorBuffer,
+        editor_engine: &mut EditorEngine,
+    ) -> Option<()> {
+        empty_check_early_return!(editor_buffer, @None);
+
+        let caret_row = editor_buffer.get_caret(CaretKind::ScrollAdjusted).row_index;
+
+        if editor_buffer.unfold(caret_row) {
+            return None;
+        }
+
+        let range = find_foldable_range(editor_buffer.get_lines(), caret_row)?;
+        if editor_buffer.fold(range) {
+            // The summary line is painted in place of the first row of the fold.
+            validate_editor_buffer_change::apply_change(
+                editor_buffer,
+                editor_engine,
+                |_, caret, scroll_offset| {
+                    scroll_editor_buffer::reset_caret_col(caret, scroll_offset);
+                },
+            );
+        }
+
+        None
+    }
+
+    /// Try and find a region of rows starting at `row_index` that can be folded:
+    /// 1. The lines below it that are indented more than it is (blank lines in between
+    ///    are included).
+    /// 2. Otherwise, if it is a Markdown heading, then the lines below it until the next
+    ///    heading of the same or higher level.
+    pub fn find_foldable_range(
+        lines: &EditorLines,
+        row_index: ChUnit,
+    ) -> Option<FoldRange> {
+        let start_row_index = ch!(@to_usize row_index);
+        let header_line = lines.get(start_row_index)?;
+        let mut maybe_end_row_index: Option<usize> = None;
+
+        // Indentation based.
+        let header_indent = get_indent_width(&header_line.string);
+        for (index, line) in lines.iter().enumerate().skip(start_row_index + 1) {
+            if line.string.trim().is_empty() {
+                continue;
+            }
+            if get_indent_width(&line.string) > header_indent {
+                maybe_end_row_index = Some(index);
+            } else {
+                break;
+            }
+        }
+
+        // Heading based.
+        if maybe_end_row_index.is_none() {
+            if let Some(level) = get_heading_level(&header_line.string) {
+                let mut end_row_index = start_row_index;
+                for (index, line) in lines.iter().enumerate().skip(start_row_index + 1) {
+                    match get_heading_level(&line.string) {
+                        Some(other_level) if other_level <= level => break,
+                        _ => end_row_index = index,
+                    }
+                }
+                if end_row_index > start_row_index {
+                    maybe_end_row_index = Some(end_row_index);
+                }
+            }
+        }
+
+        Some(FoldRange::new(row_index, ch!(maybe_end_row_index?)))
+    }
+
+    fn get_indent_width(line: &str) -> usize {
+        line.chars().take_while(|it| it.is_whitespace()).count()
+    }
+
+    fn get_heading_level(line: &str) -> Option<usize> {
+        let level = line.chars().take_while(|it| *it == '#').count();
+        if (1..=6).contains(&level) && line[level..].starts_with(' ') {
+            Some(level)
+        } else {
+            None
+        }
+    }
+
+    /// The caret should never end up on a row that is hidden by a fold. If it does, then
+    /// move it past the fold (in the given `direction`), or to the summary line of the
+    /// fold if there is no row after it.
+    pub fn skip_over_folded_rows(
+        editor_buffer: &mut EditorBuffer,
+        editor_engine: &mut EditorEngine,
+        direction: CaretDirection,
+    ) -> Option<()> {
+        let caret_row = editor_buffer.get_caret(CaretKind::ScrollAdjusted).row_index;
+        let fold = editor_buffer
+            .get_fold_map()
+            .get_fold_hiding_row(caret_row)?;
+        let row_after_fold = fold.end_row_index + 1;
+        let is_moving_down =
+            matches!(direction, CaretDirection::Down | CaretDirection::Right);
+
+        if is_moving_down && row_after_fold < editor_buffer.len() {
+            scroll_editor_buffer::change_caret_row_by(
+                EditorArgsMut {
+                    editor_buffer,
+                    editor_engine,
+                },
+                row_after_fold - caret_row,
+                CaretDirection::Down,
+            );
+        } else {
+            scroll_editor_buffer::change_caret_row_by(
+                EditorArgsMut {
+                    editor_buffer,
+                    editor_engine,
+                },
+                caret_row - fold.start_row_index,
+                CaretDirection::Up,
+            );
+        }
+
+        scroll_editor_buffer::clip_caret_to_content_width(EditorArgsMut {
+            editor_buffer,
+            editor_engine,
+        });
+
+        None
+    }
+
+    /// Editing inside a folded region unfolds it. [EditorEvent::Backspace] at the start of
+    /// a line and [EditorEvent::Delete] at the end of a line also touch the row above and
+    /// below the caret (respectively). [EditorEvent::DeleteSelection] touches all the
+    /// selected rows.
+    pub fn unfold_rows_touched_by_edit(
+        editor_buffer: &mut EditorBuffer,
+        editor_engine: &mut EditorEngine,
+        editor_event: &EditorEvent,
+    ) {
+        let caret_row = editor_buffer.get_caret(CaretKind::ScrollAdjusted).row_index;
+        let caret_col_location = caret_get::find_col(EditorArgs {
+            editor_buffer,
+            editor_engine,
+        });
+
+        let selected_row_indices = editor_buffer
+            .get_selection_map()
+            .iter()
+            .map(|(row_index, _)| *row_index)
+            .collect::<Vec<_>>();
+
+        let fold_map = editor_buffer.get_fold_map_mut();
+        fold_map.unfold_all_containing(caret_row);
+        match (editor_event, caret_col_location) {
+            (EditorEvent::DeleteSelection, _) => {
+                for row_index in selected_row_indices {
+                    fold_map.unfold_all_containing(row_index);
+                }
+            }
+            (EditorEvent::Backspace, CaretColLocationInLine::AtStart)
+                if caret_row > ch!(0) =>
+            {
+                fold_map.unfold_all_containing(caret_row - 1);
+            }
+            (EditorEvent::Delete, CaretColLocationInLine::AtEnd) => {
+                fold_map.unfold_all_containing(caret_row + 1);
+            }
+            _ => {}
+        }
+    }
+
+    /// Lines that were added or removed by an edit shift the folds below the caret.
+    pub fn shift_folds_after_edit(
+        editor_buffer: &mut EditorBuffer,
+        caret_row_before_edit: ChUnit,
+        line_count_before_edit: ChUnit,
+    ) {
+        let line_count_after_edit = editor_buffer.len();
+        let delta = ch!(@to_usize line_count_after_edit) as isize
+            - ch!(@to_usize line_count_before_edit) as isize;
+        editor_buffer
+            .get_fold_map_mut()
+            .shift_rows_after(caret_row_before_edit, delta);
+    }
+}
+
+mod mark_mut {
+    use super::*;
+
+    /// Lines that were added by an edit shift the marks below the caret down. Lines that
+    /// were removed by an edit drop the marks on them, and shift the marks below them up.
+    pub fn shift_marks_after_edit(
+        editor_buffer: &mut EditorBuffer,
+        is_caret_row_removed: bool,
+        caret_row_before_edit: ChUnit,
+        line_count_before_edit: ChUnit,
+    ) {
+        let line_count_after_edit = editor_buffer.len();
+        let caret_row_after_edit =
+            editor_buffer.get_caret(CaretKind::ScrollAdjusted).row_index;
+        let marks = editor_buffer.get_marks_mut();
+
+        match line_count_after_edit.cmp(&line_count_before_edit) {
+            Ordering::Equal => {}
+            Ordering::Greater => {
+                let amount = line_count_after_edit - line_count_before_edit;
+                for position in marks.values_mut() {
+                    if position.row_index > caret_row_before_edit {
+                        position.row_index += amount;
+                    }
+                }
+                editor_buffer.shift_mixed_line_breaks(
+                    ch!(@to_usize caret_row_before_edit),
+                    ch!(@to_usize amount) as isize,
+                );
+            }
+            Ordering::Less => {
+                let amount = line_count_before_edit - line_count_after_edit;
+                // Unless the caret row itself is removed (eg: by [EditorEvent::DeleteLine]),
+                // the removed rows are joined into the row that the caret ends up on.
+                let first_removed_row_index = if is_caret_row_removed {
+                    caret_row_before_edit
+                } else {
+                    caret_row_before_edit.min(caret_row_after_edit) + 1
+                };
+                let end_removed_row_index = first_removed_row_index + amount;
+                marks.retain(|_, position| {
+                    position.row_index < first_removed_row_index
+                        || position.row_index >= end_removed_row_index
+                });
+                for position in marks.values_mut() {
+                    if position.row_index >= end_removed_row_index {
+                        position.row_index -= amount;
+                    }
+                }
+                // The line break after the last removed row is kept, unless the caret row
+                // itself is removed.
+                let first_removed_line_break_index = if is_caret_row_removed {
+                    first_removed_row_index
+                } else {
+                    first_removed_row_index - 1
+                };
+                editor_buffer.shift_mixed_line_breaks(
+                    ch!(@to_usize first_removed_line_break_index),
+                    -(ch!(@to_usize amount) as isize),
+                );
+            }
+        }
+    }
+}
+
+mod multi_caret_mut {
+    use super::*;
+
+    /// Add a secondary caret on the line below the lowest caret (primary or secondary).
+    /// The col is clipped to the width of that line.
+    pub fn add_caret_below(buffer: &mut EditorBuffer, engine: &mut EditorEngine) {
+        empty_check_early_return!(buffer, @Nothing);
+        multiline_disabled_check_early_return!(engine, @Nothing);
+
+        let lowest_caret = buffer
+            .get_secondary_carets()
+            .iter()
+            .copied()
+            .chain(std::iter::once(buffer.get_caret(CaretKind::ScrollAdjusted)))
+            .max_by_key(|caret| (caret.row_index, caret.col_index))
+            .unwrap_or_default();
+
+        let row_index = lowest_caret.row_index + 1;
+        if row_index >= buffer.len() {
+            return;
+        }
+        let Some(line) = buffer.get_lines().get(ch!(@to_usize row_index)) else {
+            return;
+        };
+        let col_index = snap_to_grapheme_cluster(line, lowest_caret.col_index);
+
+        let mut secondary_carets = buffer.get_secondary_carets().clone();
+        secondary_carets.push(position!(col_index: col_index, row_index: row_index));
+        buffer.set_secondary_carets(secondary_carets);
+    }
+
+    /// These are the only events that are applied at all the carets. They never add or
+    /// remove lines, so the carets can't end up on the wrong rows.
+    pub fn is_multi_caret_edit(editor_event: &EditorEvent) -> bool {
+        matches!(
+            editor_event,
+            EditorEvent::InsertChar(_)
+                | EditorEvent::InsertString(_)
+                | EditorEvent::Backspace
+                | EditorEvent::Delete
+        )
+    }
+
+    /// Apply `editor_event` at the primary and secondary carets. The carets are processed
+    /// from the bottom of the buffer to the top, and right to left in each line, so that
+    /// an edit never changes the position of a caret that hasn't been processed yet. The
+    /// carets to the right of an edit (that have already been processed) are shifted by
+    /// the change in the width of the line.
+    ///
+    /// Lines are never joined, so [EditorEvent::Backspace] at the start of a line and
+    /// [EditorEvent::Delete] at the end of a line do nothing for that caret.
+    pub fn apply_edit_at_all_carets(
+        buffer: &mut EditorBuffer,
+        engine: &mut EditorEngine,
+        editor_event: &EditorEvent,
+    ) {
+        empty_check_early_return!(buffer, @Nothing);
+
+        let primary_caret = buffer.get_caret(CaretKind::ScrollAdjusted);
+        let mut carets: Vec<(Position, /* is_primary */ bool)> = buffer
+            .get_secondary_carets()
+            .iter()
+            .map(|caret| (*caret, false))
+            .chain(std::iter::once((primary_caret, true)))
+            .collect();
+        carets.sort_by_key(|(caret, _)| (caret.row_index, caret.col_index));
+        carets.reverse();
+
+        let viewport_width = engine.viewport_width();
+        let insert_mode = engine.insert_mode;
+        let mut processed_carets: Vec<(Position, bool)> =
+            Vec::with_capacity(carets.len());
+
+        validate_editor_buffer_change::apply_change(
+            buffer,
+            engine,
+            |lines, caret, scroll_offset| {
+                for (position, is_primary) in carets {
+                    let row_index = ch!(@to_usize position.row_index);
+                    let maybe_edit = lines.get(row_index).and_then(|line| {
+                        edit_line(line, position.col_index, editor_event, insert_mode)
+                            .map(|(new_line, new_col_index)| {
+                                (line.display_width, new_line, new_col_index)
+                            })
+                    });
+                    let Some((old_line_width, new_line, new_col_index)) = maybe_edit
+                    else {
+                        processed_carets.push((position, is_primary));
+                        continue;
+                    };
+
+                    // Shift the carets to the right of this one, in the same line.
+                    let new_line_width = new_line.display_width;
+                    for (other, _) in processed_carets
+                        .iter_mut()
+                        .filter(|(other, _)| other.row_index == position.row_index)
+                    {
+                        if new_line_width >= old_line_width {
+                            other.col_index += new_line_width - old_line_width;
+                        } else {
+                            other.col_index -= old_line_width - new_line_width;
+                        }
+                    }
+
+                    let _ = replace(&mut lines[row_index], new_line);
+                    processed_carets.push((
+                        position!(col_index: new_col_index, row_index: position.row_index),
+                        is_primary,
+                    ));
+                }
+
+                // The primary caret never changes rows, so only its col has to be moved.
+                if let Some((primary_caret, _)) =
+                    processed_carets.iter().find(|(_, is_primary)| *is_primary)
+                {
+                    let line_display_width = lines
+                        .get(ch!(@to_usize primary_caret.row_index))
+                        .map(|line| line.display_width)
+                        .unwrap_or_default();
+                    scroll_editor_buffer::set_caret_col(
+                        caret,
+                        scroll_offset,
+                        viewport_width,
+                        line_display_width,
+                        primary_caret.col_index,
+                    );
+                }
+            },
+        );
+
+        buffer.set_secondary_carets(
+            processed_carets
+                .into_iter()
+                .filter(|(_, is_primary)| !is_primary)
+                .map(|(position, _)| position)
+                .collect(),
+        );
+    }
+
+    /// Returns the new line and the new col of the caret, or [None] if `editor_event`
+    /// doesn't change the line.
+    fn edit_line(
+        line: &UnicodeString,
+        col_index: ChUnit,
+        editor_event: &EditorEvent,
+        insert_mode: InsertMode,
+    ) -> Option<(UnicodeString, ChUnit)> {
+        match editor_event {
+            EditorEvent::InsertChar(character) => {
+                let (new_line, width) = content_mut::insert_chunk_at_display_col(
+                    line,
+                    col_index,
+                    &String::from(*character),
+                    insert_mode,
+                )?;
+                Some((new_line, col_index + width))
+            }
+            EditorEvent::InsertString(chunk) => {
+                let (new_line, width) = content_mut::insert_chunk_at_display_col(
+                    line,
+                    col_index,
+                    chunk,
+                    insert_mode,
+                )?;
+                Some((new_line, col_index + width))
+            }
+            EditorEvent::Backspace => {
+                let segment_to_left = line.iter().find(|segment| {
+                    segment.display_col_offset < col_index
+                        && segment.display_col_offset + segment.unicode_width >= col_index
+                })?;
+                let new_col_index = segment_to_left.display_col_offset;
+                let new_line = line.delete_char_at_display_col(new_col_index)?;
+                Some((new_line, new_col_index))
+            }
+            EditorEvent::Delete => {
+                if col_index >= line.display_width {
+                    return None;
+                }
+                let new_line = line.delete_char_at_display_col(col_index)?;
+                Some((new_line, col_index))
+            }
+            _ => None,
+        }
+    }
+
+    /// Move the secondary carets the same way as the primary caret is moved by
+    /// `editor_event` (only [EditorEvent::MoveCaret], [EditorEvent::Home], and
+    /// [EditorEvent::End] are supported). Secondary carets don't wrap to other lines, and
+    /// they don't scroll the viewport.
+    pub fn move_secondary_carets(buffer: &mut EditorBuffer, editor_event: &EditorEvent) {
+        let line_count = buffer.len();
+        let lines = buffer.get_lines();
+
+        let moved_carets = buffer
+            .get_secondary_carets()
+            .iter()
+            .map(|caret| {
+                let Some(line) = lines.get(ch!(@to_usize caret.row_index)) else {
+                    return *caret;
+                };
+                match editor_event {
+                    EditorEvent::Home => position!(col_index: 0, row_index: caret.row_index),
+                    EditorEvent::End => {
+                        position!(col_index: line.display_width, row_index: caret.row_index)
+                    }
+                    EditorEvent::MoveCaret(CaretDirection::Left) => {
+                        let col_index = line
+                            .iter()
+                            .rev()
+                            .find(|segment| segment.display_col_offset < caret.col_index)
+                            .map(|segment| segment.display_col_offset)
+                            .unwrap_or(caret.col_index);
+                        position!(col_index: col_index, row_index: caret.row_index)
+                    }
+                    EditorEvent::MoveCaret(CaretDirection::Right) => {
+                        let col_index = match line.at_display_col_index(caret.col_index) {
+                            Some(segment) => segment.display_col_offset + segment.unicode_width,
+                            None => caret.col_index,
+                        };
+                        position!(col_index: col_index, row_index: caret.row_index)
+                    }
+                    EditorEvent::MoveCaret(CaretDirection::Up) => {
+                        move_to_row(lines, *caret, caret.row_index - 1)
+                    }
+                    EditorEvent::MoveCaret(CaretDirection::Down) => {
+                        if caret.row_index + 1 < line_count {
+                            move_to_row(lines, *caret, caret.row_index + 1)
+                        } else {
+                            *caret
+                        }
+                    }
+                    _ => *caret,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        buffer.set_secondary_carets(moved_carets);
+
+        fn move_to_row(
+            lines: &EditorLines,
+            caret: Position,
+            row_index: ChUnit,
+        ) -> Position {
+            match lines.get(ch!(@to_usize row_index)) {
+                Some(line) => position!(
+                    col_index: snap_to_grapheme_cluster(line, caret.col_index),
+                    row_index: row_index
+                ),
+                None => caret,
+            }
+        }
+    }
+
+    /// Clip `col_index` to the width of the `line`, and move it to the start of the
+    /// grapheme cluster that it is in the middle of (if any).
+    fn snap_to_grapheme_cluster(line: &UnicodeString, col_index: ChUnit) -> ChUnit {
+        let col_index = col_index.min(line.display_width);
+        match line.at_display_col_index(col_index) {
+            Some(segment) => segment.display_col_offset,
+            None => col_index,
+        }
+    }
+}
+
+mod auto_close_pairs {
+    use super::*;
+
+    /// Rewrite a single [EditorEvent::InsertChar] or [EditorEvent::Backspace] so that the
+    /// chars in [EditorEngineConfig::auto_close_pair_table] are inserted and removed in
+    /// pairs. Any other events are returned as is.
+    pub fn translate(
+        buffer: &EditorBuffer,
+        engine: &EditorEngine,
+        editor_events: Vec<EditorEvent>,
+    ) -> Vec<EditorEvent> {
+        let config_options = &engine.config_options;
+        if !config_options.auto_close_pairs
+            || editor_events.len() != 1
+            || buffer.has_selection()
+            || buffer.has_secondary_carets()
+        {
+            return editor_events;
+        }
+
+        let pair_table = &config_options.auto_close_pair_table;
+        let char_at_caret = first_char(content_get::string_at_caret(buffer, engine));
+        let char_left_of_caret =
+            first_char(content_get::string_to_left_of_caret(buffer, engine));
+
+        match editor_events[0].clone() {
+            EditorEvent::InsertChar(typed_char) => {
+                // Type over the closing char instead of inserting another one.
+                let is_closing_char =
+                    pair_table.iter().any(|(_, close)| *close == typed_char);
+                if is_closing_char && char_at_caret == Some(typed_char) {
+                    return vec![EditorEvent::MoveCaret(CaretDirection::Right)];
+                }
+
+                let Some((open, close)) =
+                    pair_table.iter().find(|(open, _)| *open == typed_char)
+                else {
+                    return editor_events;
+                };
+                let is_before_word = char_at_caret.map_or(false, char::is_alphanumeric);
+                let is_quote_after_word = open == close
+                    && char_left_of_caret.map_or(false, char::is_alphanumeric);
+                if is_before_word || is_quote_after_word {
+                    return editor_events;
+                }
+
+                vec![
+                    EditorEvent::InsertString(format!("{open}{close}")),
+                    EditorEvent::MoveCaret(CaretDirection::Left),
+                ]
+            }
+            EditorEvent::Backspace => match (char_left_of_caret, char_at_caret) {
+                (Some(left), Some(right)) if pair_table.contains(&(left, right)) => {
+                    vec![EditorEvent::Delete, EditorEvent::Backspace]
+                }
+                _ => editor_events,
+            },
+            _ => editor_events,
+        }
+    }
+
+    fn first_char(maybe_result: Option<UnicodeStringSegmentSliceResult>) -> Option<char> {
+        maybe_result.and_then(|it| it.unicode_string_seg.string.chars().next())
+    }
+}
+
 mod caret_location_enums {
     use super::*;
 
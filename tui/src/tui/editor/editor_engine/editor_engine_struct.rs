@@ -14,8 +14,7 @@
  *   See the License for the specific language governing permissions and
  *   limitations under the License.
  */
-
-use std::fmt::Debug;
+use std::{fmt::Debug, sync::Arc, time::Duration};
 
 use r3bl_rs_utils_core::*;
 use serde::*;
@@ -42,8 +41,45 @@ pub struct EditorEngine {
     pub config_options: EditorEngineConfig,
     /// Syntax highlighting support. This is a very heavy object to create, re-use it.
     pub syntax_set: SyntaxSet,
-    /// Syntax highlighting support. This is a very heavy object to create, re-use it.
-    pub theme: Theme,
+    /// Syntax highlighting support. This is a very heavy object to create, re-use it. It
+    /// is replaced w/ the active [TuiTheme]'s syntect theme each time
+    /// [EditorEngineApi::render_engine](EditorEngineApi::render_engine) is called.
+    pub theme: Arc<Theme>,
+    /// The styles used to highlight markdown. It is replaced w/ the active [TuiTheme]'s
+    /// [MarkdownStyleConfig] each time
+    /// [EditorEngineApi::render_engine](EditorEngineApi::render_engine) is called.
+    pub markdown_style_config: MarkdownStyleConfig,
+    /// Used by the [KeyBindingMode]s to keep track of the current mode, pending keys, and
+    /// the [KillRing].
+    pub key_binding_state: KeyBindingState,
+    /// Toggled by [EditorEvent::ToggleInsertMode] (the `Insert` key).
+    pub insert_mode: InsertMode,
+    /// Whether the box that this editor is rendered in has keyboard focus. Set by
+    /// [EditorEngineApi::render_engine](EditorEngineApi::render_engine), so that all the
+    /// render functions (and the [EditorComponent] or [DialogComponent] that owns this
+    /// engine) can style themselves accordingly.
+    pub focus_state: FocusState,
+    /// Only used if [EditorEngineConfig::smooth_scroll] is enabled.
+    pub smooth_scroll: SmoothScroll,
+    /// Set by [EditorComponent::with_completion_provider]. There is no completion popup
+    /// if this isn't set.
+    #[serde(skip)]
+    pub maybe_completion_provider: Option<CompletionProviderFn>,
+    #[serde(skip)]
+    pub completion_state: CompletionState,
+    /// Opened by [EditorEvent::OpenReplace].
+    #[serde(skip)]
+    pub replace_state: ReplaceState,
+    /// Shown in the gutter when [EditorEngineConfig::diagnostics_gutter] is enabled.
+    #[serde(skip)]
+    pub diagnostics: Diagnostics,
+    /// Used to detect double & triple clicks.
+    #[serde(skip)]
+    pub click_state: ClickState,
+    /// The lines that have been syntax highlighted, filled by
+    /// [EditorEngineApi::render_engine](EditorEngineApi::render_engine).
+    #[serde(skip)]
+    pub highlight_cache: HighlightCache,
 }
 
 impl Default for EditorEngine {
@@ -58,16 +94,67 @@ impl EditorEngine {
             current_box: Default::default(),
             config_options,
             syntax_set: SyntaxSet::load_defaults_newlines(),
-            theme: try_load_r3bl_theme().unwrap_or_else(|_| load_default_theme()),
+            theme: Arc::new(
+                try_load_r3bl_theme().unwrap_or_else(|_| load_default_theme()),
+            ),
+            markdown_style_config: Default::default(),
+            key_binding_state: Default::default(),
+            insert_mode: Default::default(),
+            focus_state: Default::default(),
+            smooth_scroll: Default::default(),
+            maybe_completion_provider: None,
+            completion_state: Default::default(),
+            replace_state: Default::default(),
+            diagnostics: Default::default(),
+            click_state: Default::default(),
+            highlight_cache: Default::default(),
+        }
+    }
+
+    /// The diagnostics gutter (if enabled) takes up the first cols of the box that is
+    /// passed to [EditorEngineApi::render_engine], so it isn't part of
+    /// [current_box](EditorEngine::current_box).
+    pub fn gutter_width(&self) -> ChUnit {
+        if self.config_options.diagnostics_gutter {
+            ch!(DIAGNOSTICS_GUTTER_WIDTH)
+        } else {
+            ch!(0)
         }
     }
 
+    /// The vertical scrollbar (if enabled) takes up the last col of the box.
     pub fn viewport_width(&self) -> ChUnit {
-        self.current_box.style_adjusted_bounds_size.col_count
+        let col_count = self.current_box.style_adjusted_bounds_size.col_count;
+        if self.config_options.scrollbars.has_vertical() {
+            col_count - 1
+        } else {
+            col_count
+        }
     }
 
+    /// The horizontal scrollbar (if enabled) takes up the last row of the box.
     pub fn viewport_height(&self) -> ChUnit {
-        self.current_box.style_adjusted_bounds_size.row_count
+        let row_count = self.current_box.style_adjusted_bounds_size.row_count;
+        if self.config_options.scrollbars.has_horizontal() {
+            row_count - 1
+        } else {
+            row_count
+        }
+    }
+
+    /// The shape of the terminal cursor for the current mode: a block in Vim's normal &
+    /// visual modes, an underline in [InsertMode::Overwrite], & a bar otherwise. The
+    /// [EditorComponent] that has focus sets it w/ [GlobalData::set_cursor_shape].
+    pub fn get_cursor_shape(&self) -> CursorShape {
+        if self.config_options.key_binding_mode == KeyBindingMode::Vim
+            && self.key_binding_state.vim_mode != VimMode::Insert
+        {
+            return CursorShape::Block;
+        }
+        match self.insert_mode {
+            InsertMode::Insert => CursorShape::Bar,
+            InsertMode::Overwrite => CursorShape::Underline,
+        }
     }
 }
 
@@ -75,7 +162,130 @@ impl EditorEngine {
 pub struct EditorEngineConfig {
     pub multiline_mode: LineMode,
     pub syntax_highlight: SyntaxHighlightMode,
+    /// How long syntax highlighting can take in each render. The visible lines that aren't
+    /// highlighted by then are painted w/out syntax highlighting, & highlighted in the next
+    /// render. [None] means there's no budget. More info in [HighlightCache].
+    pub highlight_budget: Option<Duration>,
     pub edit_mode: EditMode,
+    /// Display only, the buffer content and caret are not affected by this.
+    pub render_whitespace: WhitespaceMode,
+    pub whitespace_markers: WhitespaceMarkers,
+    pub key_binding_mode: KeyBindingMode,
+    /// The keys that run each [EditorEvent]. The [KeyBindingMode::Vim] &
+    /// [KeyBindingMode::Emacs] bindings take precedence over these.
+    pub key_map: KeyMap,
+    /// How long to wait for the next key of a chord (eg: Vim's `dd`, or the ones in the
+    /// [key_map](EditorEngineConfig::key_map)) before the keys that have been pressed are
+    /// flushed, ie: applied as if each had been pressed on its own. [None] waits forever.
+    /// More info in [KeyBindingsApi].
+    pub chord_timeout: Option<Duration>,
+    /// Minimum number of rows to keep above and below the caret (like Vim's
+    /// `scrolloff`). The viewport scrolls early so that this context is visible. If the
+    /// viewport is too short for this margin, then it is reduced to fit.
+    pub scroll_off: usize,
+    /// Same as [scroll_off](EditorEngineConfig::scroll_off) but for the columns to the
+    /// left and right of the caret (like Vim's `sidescrolloff`).
+    pub side_scroll_off: usize,
+    /// Where the caret ends up in the viewport when it moves out of it, & the content is
+    /// scrolled to follow it. More info in [ScrollStrategy].
+    pub scroll_strategy: ScrollStrategy,
+    /// How many rows the content can be scrolled past its last line, so that the last
+    /// line doesn't have to sit at the bottom of the viewport. The rows below it are
+    /// painted empty. It is capped at the viewport height - 1 (ie: the last line at the
+    /// top of the viewport), so use `usize::MAX` for that. This applies to
+    /// [EditorEvent::RecenterCaret], the [scroll_strategy](EditorEngineConfig::scroll_strategy)
+    /// & the vertical scrollbar, but the caret still stops at the last line. Pass it to
+    /// [EditorBuffer::set_scroll_offset] when scrolling the buffer directly.
+    pub overscroll: usize,
+    /// When set, [EditorComponent] asks the app to persist the buffer once there have been
+    /// no content changes for this long. More info in [AutoSaveTimer]. This is ignored for
+    /// [EditMode::ReadOnly] buffers.
+    pub auto_save: Option<Duration>,
+    /// When set, [EditorComponent] only calls its
+    /// [on_editor_buffer_change_handler](EditorComponent::on_editor_buffer_change_handler)
+    /// once there have been no changes for this long, instead of on each change. The
+    /// editor keeps using its latest buffer in the meantime. More info in
+    /// [ChangeDebounceTimer].
+    pub change_debounce: Option<Duration>,
+    /// When `true`, typing an opening char from
+    /// [auto_close_pair_table](EditorEngineConfig::auto_close_pair_table) also inserts
+    /// the closing char after the caret. Typing the closing char right before an existing
+    /// one types over it, and backspace between an empty pair deletes both chars.
+    pub auto_close_pairs: bool,
+    /// `(open, close)` chars, eg: `('(', ')')`. Pairs where both chars are the same (eg:
+    /// quotes) are not auto closed right after a word, so that apostrophes still work.
+    pub auto_close_pair_table: Vec<(char, char)>,
+    /// Each scrollbar that is enabled takes up 1 col (vertical) or 1 row (horizontal) of
+    /// the box, so the viewport is smaller. When disabled they don't take up any space.
+    pub scrollbars: ScrollbarMode,
+    /// When `true`, the start & end of lines that are clipped by the viewport are
+    /// painted w/ a marker. Display only, like
+    /// [render_whitespace](EditorEngineConfig::render_whitespace).
+    pub render_clip_markers: bool,
+    pub scrollbar_markers: ScrollbarMarkers,
+    /// When `true`, the caret is painted w/ a hollow (underlined & dimmed) style when the
+    /// editor is [FocusState::Blurred], so that it is clear which editor has focus when
+    /// there are several on screen. When `false`, the caret isn't painted at all.
+    pub show_caret_when_blurred: bool,
+    /// When enabled, the content scrolls a few rows per frame instead of jumping (eg: on
+    /// PageDown). More info in [SmoothScroll].
+    pub smooth_scroll: bool,
+    /// When `true`, the background color at the end of each syntax highlighted line (eg:
+    /// in a Markdown code block w/ a background color) is extended to the right edge of
+    /// the viewport, so that highlighted regions are painted as rectangles. More info in
+    /// [extend_bg_to_line_end].
+    pub extend_bg_to_line_end: bool,
+    /// When `true`, the first cols of the box are a gutter, which shows the most severe
+    /// of the [EditorEngine::diagnostics] for each row. More info in [Diagnostics].
+    pub diagnostics_gutter: bool,
+    /// When `true`, the lines that have right-to-left text (eg: Arabic or Hebrew) are
+    /// reordered for display. The buffer & caret movement stay in logical order. This is
+    /// off by default, since only the text & the caret are reordered: the selection, the
+    /// search & diagnostic highlights, the whitespace markers, horizontal scrolling &
+    /// mouse clicks still use the logical cols, so they are in the wrong place on
+    /// right-to-left lines. More info in [bidi].
+    pub bidi_reorder: bool,
+    /// When `true`, the rows that have a caret on them (including the secondary carets)
+    /// are painted w/ [current_line_style](EditorEngineConfig::current_line_style),
+    /// across the whole width of the viewport. The selection is painted over it.
+    pub highlight_current_line: bool,
+    /// Only its background color should be set, since it is added to the style of the
+    /// syntax highlighting. The default is [get_current_line_style].
+    pub current_line_style: Style,
+    /// Display cols (eg: `vec![80, 100]`) at which a vertical guide is painted, so that
+    /// lines up to that many cols long end just before it. The guides are painted
+    /// beneath the text, and the ones that are scrolled out of the viewport are skipped.
+    pub rulers: Vec<usize>,
+    /// When `true`, a faint vertical guide is painted every
+    /// [tab_width](EditorEngineConfig::tab_width) cols in the leading whitespace of each
+    /// line, to show its indent levels. Blank lines get the guides that the lines around
+    /// them share, so they don't break up an indented block. This is display only, the
+    /// buffer content & caret are not affected.
+    pub indent_guides: bool,
+    /// The number of cols that [EditorEvent::IndentLines] & [EditorEvent::DedentLines]
+    /// add or remove, and the width of a tab when lines are dedented.
+    pub tab_width: usize,
+    /// When `true`, lines are indented w/ [tab_width](EditorEngineConfig::tab_width)
+    /// spaces, otherwise w/ a tab.
+    pub use_soft_tabs: bool,
+    /// Which chars are part of a word, for the word motions (eg:
+    /// [EditorEvent::MoveCaretToNextWord]), [EditorEvent::DeleteWord], and double click
+    /// selection.
+    pub word_char_class: WordCharClass,
+}
+
+/// Builds an [EditorEngineConfig], starting from its [Default], so that only the options
+/// that differ from it have to be set, eg:
+///
+/// ```ignore
+/// let config_options = EditorEngineConfig::builder()
+///     .set_tab_width(2)
+///     .set_diagnostics_gutter(true)
+///     .build();
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct EditorEngineConfigBuilder {
+    config: EditorEngineConfig,
 }
 
 mod editor_engine_config_options_impl {
@@ -88,24 +298,567 @@ mod editor_engine_config_options_impl {
                 syntax_highlight: SyntaxHighlightMode::Enable(
                     DEFAULT_SYN_HI_FILE_EXT.to_string(),
                 ),
+                highlight_budget: Some(Duration::from_millis(
+                    DEFAULT_HIGHLIGHT_BUDGET_MS,
+                )),
                 edit_mode: EditMode::ReadWrite,
+                render_whitespace: WhitespaceMode::None,
+                whitespace_markers: WhitespaceMarkers::default(),
+                key_binding_mode: KeyBindingMode::Standard,
+                key_map: KeyMap::default(),
+                chord_timeout: Some(Duration::from_millis(DEFAULT_CHORD_TIMEOUT_MS)),
+                scroll_off: 0,
+                side_scroll_off: 0,
+                scroll_strategy: ScrollStrategy::MinimalEdge,
+                overscroll: 0,
+                auto_save: None,
+                change_debounce: None,
+                auto_close_pairs: false,
+                auto_close_pair_table: DEFAULT_AUTO_CLOSE_PAIRS.to_vec(),
+                scrollbars: ScrollbarMode::None,
+                render_clip_markers: false,
+                scrollbar_markers: ScrollbarMarkers::default(),
+                show_caret_when_blurred: true,
+                smooth_scroll: false,
+                extend_bg_to_line_end: false,
+                diagnostics_gutter: false,
+                bidi_reorder: false,
+                highlight_current_line: false,
+                current_line_style: get_current_line_style(),
+                rulers: vec![],
+                indent_guides: false,
+                tab_width: DEFAULT_TAB_WIDTH,
+                use_soft_tabs: true,
+                word_char_class: WordCharClass::default(),
+            }
+        }
+    }
+
+    impl EditorEngineConfig {
+        /// What [EditorEvent::IndentLines] inserts at the start of each line.
+        pub fn get_indent_unit(&self) -> String {
+            match self.use_soft_tabs {
+                true => " ".repeat(self.tab_width),
+                false => "\t".to_string(),
             }
         }
     }
 }
 
+mod editor_engine_config_builder_impl {
+    use super::*;
+
+    impl EditorEngineConfig {
+        pub fn builder() -> EditorEngineConfigBuilder { EditorEngineConfigBuilder::new() }
+    }
+
+    impl EditorEngineConfigBuilder {
+        pub fn new() -> Self { Self::default() }
+
+        pub fn set_multiline_mode(mut self, multiline_mode: LineMode) -> Self {
+            self.config.multiline_mode = multiline_mode;
+            self
+        }
+
+        pub fn set_syntax_highlight(
+            mut self,
+            syntax_highlight: SyntaxHighlightMode,
+        ) -> Self {
+            self.config.syntax_highlight = syntax_highlight;
+            self
+        }
+
+        pub fn set_highlight_budget(
+            mut self,
+            highlight_budget: Option<Duration>,
+        ) -> Self {
+            self.config.highlight_budget = highlight_budget;
+            self
+        }
+
+        pub fn set_edit_mode(mut self, edit_mode: EditMode) -> Self {
+            self.config.edit_mode = edit_mode;
+            self
+        }
+
+        pub fn set_render_whitespace(
+            mut self,
+            render_whitespace: WhitespaceMode,
+        ) -> Self {
+            self.config.render_whitespace = render_whitespace;
+            self
+        }
+
+        pub fn set_whitespace_markers(
+            mut self,
+            whitespace_markers: WhitespaceMarkers,
+        ) -> Self {
+            self.config.whitespace_markers = whitespace_markers;
+            self
+        }
+
+        pub fn set_key_binding_mode(mut self, key_binding_mode: KeyBindingMode) -> Self {
+            self.config.key_binding_mode = key_binding_mode;
+            self
+        }
+
+        pub fn set_key_map(mut self, key_map: KeyMap) -> Self {
+            self.config.key_map = key_map;
+            self
+        }
+
+        pub fn set_chord_timeout(mut self, chord_timeout: Option<Duration>) -> Self {
+            self.config.chord_timeout = chord_timeout;
+            self
+        }
+
+        pub fn set_scroll_off(mut self, scroll_off: usize) -> Self {
+            self.config.scroll_off = scroll_off;
+            self
+        }
+
+        pub fn set_side_scroll_off(mut self, side_scroll_off: usize) -> Self {
+            self.config.side_scroll_off = side_scroll_off;
+            self
+        }
+
+        pub fn set_scroll_strategy(mut self, scroll_strategy: ScrollStrategy) -> Self {
+            self.config.scroll_strategy = scroll_strategy;
+            self
+        }
+
+        pub fn set_overscroll(mut self, overscroll: usize) -> Self {
+            self.config.overscroll = overscroll;
+            self
+        }
+
+        pub fn set_auto_save(mut self, auto_save: Option<Duration>) -> Self {
+            self.config.auto_save = auto_save;
+            self
+        }
+
+        pub fn set_change_debounce(mut self, change_debounce: Option<Duration>) -> Self {
+            self.config.change_debounce = change_debounce;
+            self
+        }
+
+        pub fn set_auto_close_pairs(mut self, auto_close_pairs: bool) -> Self {
+            self.config.auto_close_pairs = auto_close_pairs;
+            self
+        }
+
+        pub fn set_auto_close_pair_table(
+            mut self,
+            auto_close_pair_table: Vec<(char, char)>,
+        ) -> Self {
+            self.config.auto_close_pair_table = auto_close_pair_table;
+            self
+        }
+
+        pub fn set_scrollbars(mut self, scrollbars: ScrollbarMode) -> Self {
+            self.config.scrollbars = scrollbars;
+            self
+        }
+
+        pub fn set_render_clip_markers(mut self, render_clip_markers: bool) -> Self {
+            self.config.render_clip_markers = render_clip_markers;
+            self
+        }
+
+        pub fn set_scrollbar_markers(
+            mut self,
+            scrollbar_markers: ScrollbarMarkers,
+        ) -> Self {
+            self.config.scrollbar_markers = scrollbar_markers;
+            self
+        }
+
+        pub fn set_show_caret_when_blurred(
+            mut self,
+            show_caret_when_blurred: bool,
+        ) -> Self {
+            self.config.show_caret_when_blurred = show_caret_when_blurred;
+            self
+        }
+
+        pub fn set_smooth_scroll(mut self, smooth_scroll: bool) -> Self {
+            self.config.smooth_scroll = smooth_scroll;
+            self
+        }
+
+        pub fn set_extend_bg_to_line_end(mut self, extend_bg_to_line_end: bool) -> Self {
+            self.config.extend_bg_to_line_end = extend_bg_to_line_end;
+            self
+        }
+
+        pub fn set_diagnostics_gutter(mut self, diagnostics_gutter: bool) -> Self {
+            self.config.diagnostics_gutter = diagnostics_gutter;
+            self
+        }
+
+        pub fn set_bidi_reorder(mut self, bidi_reorder: bool) -> Self {
+            self.config.bidi_reorder = bidi_reorder;
+            self
+        }
+
+        pub fn set_highlight_current_line(
+            mut self,
+            highlight_current_line: bool,
+        ) -> Self {
+            self.config.highlight_current_line = highlight_current_line;
+            self
+        }
+
+        pub fn set_current_line_style(mut self, current_line_style: Style) -> Self {
+            self.config.current_line_style = current_line_style;
+            self
+        }
+
+        pub fn set_rulers(mut self, rulers: Vec<usize>) -> Self {
+            self.config.rulers = rulers;
+            self
+        }
+
+        pub fn set_indent_guides(mut self, indent_guides: bool) -> Self {
+            self.config.indent_guides = indent_guides;
+            self
+        }
+
+        pub fn set_tab_width(mut self, tab_width: usize) -> Self {
+            self.config.tab_width = tab_width;
+            self
+        }
+
+        pub fn set_use_soft_tabs(mut self, use_soft_tabs: bool) -> Self {
+            self.config.use_soft_tabs = use_soft_tabs;
+            self
+        }
+
+        pub fn set_word_char_class(mut self, word_char_class: WordCharClass) -> Self {
+            self.config.word_char_class = word_char_class;
+            self
+        }
+
+        pub fn build(self) -> EditorEngineConfig { self.config }
+    }
+}
+
+/// How far the content is scrolled (vertically) when the caret moves out of the viewport,
+/// or into the [scroll_off](EditorEngineConfig::scroll_off) margin. Horizontal scrolling
+/// is always [MinimalEdge](ScrollStrategy::MinimalEdge).
+///
+/// The margin is kept w/ every strategy, so eg: w/ [Top](ScrollStrategy::Top) the caret
+/// ends up `scroll_off` rows below the top of the viewport. Use
+/// [EditorEvent::RecenterCaret] to center the caret once, w/ any strategy.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ScrollStrategy {
+    /// Scroll just enough to reveal the caret, ie: it is at the top or bottom edge.
+    #[default]
+    MinimalEdge,
+    /// Scroll so that the caret is in the center of the viewport (like Vim's `zz`).
+    Center,
+    /// Scroll so that the caret is at the top of the viewport (like Vim's `zt`).
+    Top,
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum EditMode {
     ReadOnly,
     ReadWrite,
 }
 
+/// What happens to the content to the right of the caret when a character is typed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum InsertMode {
+    /// The content is shifted to the right.
+    #[default]
+    Insert,
+    /// The grapheme cluster under the caret is replaced. At the end of a line, the
+    /// character is appended.
+    Overwrite,
+}
+
+/// Whether an editor has keyboard focus. More info in [EditorEngine::focus_state].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FocusState {
+    Focused,
+    #[default]
+    Blurred,
+}
+
+impl From<bool> for FocusState {
+    fn from(has_focus: bool) -> Self {
+        if has_focus {
+            FocusState::Focused
+        } else {
+            FocusState::Blurred
+        }
+    }
+}
+
+impl FocusState {
+    pub fn is_focused(&self) -> bool { *self == FocusState::Focused }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum LineMode {
     SingleLine,
     MultiLine,
 }
 
+/// The set of key bindings that [EditorEngineApi::apply_event] uses to convert
+/// [InputEvent]s into [EditorEvent]s. More info in [KeyBindingsApi].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum KeyBindingMode {
+    /// Only the [EditorEngineConfig::key_map] is used.
+    Standard,
+    /// Modal editing w/ [VimMode::Normal], [VimMode::Insert], and [VimMode::Visual].
+    Vim,
+    /// `Ctrl+A`/`Ctrl+E`, `Ctrl+K`/`Ctrl+Y` (w/ a [KillRing]), `Alt+F`/`Alt+B`, and
+    /// `Ctrl+Space` to set the mark.
+    Emacs,
+}
+
+/// Which whitespace characters (spaces and tabs) get painted w/ a marker.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WhitespaceMode {
+    None,
+    /// Only the whitespace at the end of a line.
+    Trailing,
+    All,
+}
+
+/// The characters & style used to paint whitespace when [WhitespaceMode] is not
+/// [WhitespaceMode::None].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WhitespaceMarkers {
+    pub space_char: char,
+    pub tab_char: char,
+    pub style: Style,
+}
+
+mod whitespace_config_impl {
+    use super::*;
+
+    impl Default for WhitespaceMarkers {
+        fn default() -> Self {
+            Self {
+                space_char: DEFAULT_WHITESPACE_SPACE_MARKER_CHAR,
+                tab_char: DEFAULT_WHITESPACE_TAB_MARKER_CHAR,
+                style: get_whitespace_marker_style(),
+            }
+        }
+    }
+
+    impl WhitespaceMode {
+        /// Returns the segments in `line` that should be painted w/ a marker.
+        pub fn get_marked_segments<'a>(
+            &self,
+            line: &'a UnicodeString,
+        ) -> Vec<&'a GraphemeClusterSegment> {
+            let is_whitespace = |segment: &GraphemeClusterSegment| {
+                matches!(segment.string.as_str(), " " | "\t")
+            };
+
+            let start_index = match self {
+                WhitespaceMode::None => return vec![],
+                WhitespaceMode::All => 0,
+                WhitespaceMode::Trailing => line
+                    .iter()
+                    .rposition(|segment| !is_whitespace(segment))
+                    .map(|it| it + 1)
+                    .unwrap_or(0),
+            };
+
+            line.iter()
+                .skip(start_index)
+                .filter(|segment| is_whitespace(segment))
+                .collect()
+        }
+    }
+
+    impl WhitespaceMarkers {
+        pub fn get_marker_char(&self, segment: &GraphemeClusterSegment) -> Option<char> {
+            match segment.string.as_str() {
+                " " => Some(self.space_char),
+                "\t" => Some(self.tab_char),
+                _ => None,
+            }
+        }
+    }
+}
+
+/// Decides which chars make up a word, eg: for [EditorEvent::MoveCaretToNextWord] & double
+/// click selection. Alphanumeric chars are always word chars, & so are the
+/// [extra_chars](WordCharClass::extra_chars). The [Default] only adds `_`, so that
+/// `foo_bar` is a single word. Apps can add more, eg: `-` for CSS:
+///
+/// ```ignore
+/// let config_options = EditorEngineConfig {
+///     word_char_class: WordCharClass::default().with_extra_chars("-"),
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WordCharClass {
+    pub extra_chars: Vec<char>,
+}
+
+/// What a grapheme cluster is to a [WordCharClass]. A word is a run of [CharClass::Word]
+/// (or of [CharClass::Punctuation]) clusters.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CharClass {
+    Whitespace,
+    Word,
+    Punctuation,
+}
+
+mod word_char_class_impl {
+    use super::*;
+
+    impl Default for WordCharClass {
+        fn default() -> Self {
+            Self {
+                extra_chars: vec!['_'],
+            }
+        }
+    }
+
+    impl WordCharClass {
+        pub fn with_extra_chars(mut self, extra_chars: &str) -> Self {
+            for it in extra_chars.chars() {
+                if !self.extra_chars.contains(&it) {
+                    self.extra_chars.push(it);
+                }
+            }
+            self
+        }
+
+        pub fn is_word_char(&self, character: char) -> bool {
+            character.is_alphanumeric() || self.extra_chars.contains(&character)
+        }
+
+        /// Only the first char of the `segment` is looked at.
+        pub fn get_char_class(&self, segment: &GraphemeClusterSegment) -> CharClass {
+            match segment.string.chars().next() {
+                Some(it) if it.is_whitespace() => CharClass::Whitespace,
+                Some(it) if self.is_word_char(it) => CharClass::Word,
+                _ => CharClass::Punctuation,
+            }
+        }
+
+        /// Returns the display col range (start inclusive, end exclusive) of the run of
+        /// clusters w/ the same [CharClass] as the one at `display_col_index` in `line`.
+        /// If `display_col_index` is past the end of the line, then the last cluster is
+        /// used. Returns [None] if the line is empty.
+        pub fn get_word_range_at(
+            &self,
+            line: &UnicodeString,
+            display_col_index: ChUnit,
+        ) -> Option<SelectionRange> {
+            let segments = line.iter().collect::<Vec<_>>();
+            let index = segments
+                .iter()
+                .position(|it| {
+                    display_col_index < it.display_col_offset + it.unicode_width
+                })
+                .or(segments.len().checked_sub(1))?;
+            let char_class = self.get_char_class(segments[index]);
+            let is_same_class =
+                |it: &&&GraphemeClusterSegment| self.get_char_class(it) == char_class;
+
+            let start = segments[..index]
+                .iter()
+                .rev()
+                .take_while(is_same_class)
+                .last()
+                .unwrap_or(&segments[index]);
+            let end = segments[index..]
+                .iter()
+                .take_while(is_same_class)
+                .last()
+                .unwrap_or(&segments[index]);
+            Some(SelectionRange {
+                start_display_col_index: start.display_col_offset,
+                end_display_col_index: end.display_col_offset + end.unicode_width,
+            })
+        }
+    }
+}
+
+/// Which scrollbars are painted by [EditorEngineApi::render_engine].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ScrollbarMode {
+    None,
+    /// Shows the position of the viewport in the document.
+    Vertical,
+    /// Shows the horizontal scroll offset.
+    Horizontal,
+    Both,
+}
+
+/// The characters & styles used to paint the scrollbars, and the markers for lines that
+/// are clipped by the viewport.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScrollbarMarkers {
+    pub track_char: char,
+    pub thumb_char: char,
+    pub clip_start_char: char,
+    pub clip_end_char: char,
+    pub track_style: Style,
+    pub thumb_style: Style,
+    pub clip_marker_style: Style,
+}
+
+mod scrollbar_config_impl {
+    use super::*;
+
+    impl Default for ScrollbarMarkers {
+        fn default() -> Self {
+            Self {
+                track_char: DEFAULT_SCROLLBAR_TRACK_CHAR,
+                thumb_char: DEFAULT_SCROLLBAR_THUMB_CHAR,
+                clip_start_char: DEFAULT_CLIP_START_MARKER_CHAR,
+                clip_end_char: DEFAULT_CLIP_END_MARKER_CHAR,
+                track_style: get_scrollbar_track_style(),
+                thumb_style: get_scrollbar_thumb_style(),
+                clip_marker_style: get_clip_marker_style(),
+            }
+        }
+    }
+
+    impl ScrollbarMode {
+        pub fn has_vertical(&self) -> bool {
+            matches!(self, ScrollbarMode::Vertical | ScrollbarMode::Both)
+        }
+
+        pub fn has_horizontal(&self) -> bool {
+            matches!(self, ScrollbarMode::Horizontal | ScrollbarMode::Both)
+        }
+
+        /// Returns the `(start index, length)` of the thumb in a track that is
+        /// `track_len` long. The thumb is proportional to the part of the content that
+        /// is visible, and fills the whole track when all of it is visible.
+        pub fn get_thumb_range(
+            track_len: ChUnit,
+            content_len: ChUnit,
+            scroll_offset: ChUnit,
+        ) -> (ChUnit, ChUnit) {
+            let track_len = ch!(@to_usize track_len);
+            let content_len = ch!(@to_usize content_len);
+            if track_len == 0 || content_len <= track_len {
+                return (ch!(0), ch!(track_len));
+            }
+
+            let thumb_len = (track_len * track_len / content_len).clamp(1, track_len);
+            let max_scroll_offset = content_len - track_len;
+            let scroll_offset = ch!(@to_usize scroll_offset).min(max_scroll_offset);
+            let thumb_start = scroll_offset * (track_len - thumb_len) / max_scroll_offset;
+            (ch!(thumb_start), ch!(thumb_len))
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum SyntaxHighlightMode {
     Disable,
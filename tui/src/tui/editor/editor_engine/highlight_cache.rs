@@ -0,0 +1,188 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+//! Syntax highlighting w/ syntect is slow for long lines, so a frame that has lots of them
+//! to highlight (eg: right after a big file is opened, or a page is scrolled) can take
+//! longer than the frame budget. To keep the editor responsive:
+//! 1. Each line that is highlighted is cached (by its content) in the [HighlightCache] of
+//!    the [EditorEngine], so it is only highlighted once, no matter how often it is
+//!    painted (or scrolled past).
+//! 2. Before each render, the visible lines that aren't cached yet are highlighted, until
+//!    [EditorEngineConfig::highlight_budget] runs out. The rest are painted w/out syntax
+//!    highlighting in this frame, & a [RenderTick](crate::RenderTick) is requested so
+//!    that they are highlighted in the next one (at least 1 line per frame).
+//!
+//! The default budget is generous ([DEFAULT_HIGHLIGHT_BUDGET_MS]), so small files are
+//! always highlighted in a single frame. This applies to the syntect path; the Markdown
+//! highlighter parses the whole document at once.
+
+use std::{collections::HashMap,
+          sync::Arc,
+          time::{Duration, Instant}};
+
+use syntect::{easy::HighlightLines, highlighting::Theme, parsing::SyntaxSet};
+
+use crate::*;
+
+/// The cache is cleared when it has more lines than this, so that it doesn't grow forever
+/// as lines are edited.
+pub const HIGHLIGHT_CACHE_MAX_LINE_COUNT: usize = 10_000;
+
+/// The highlighted lines, keyed by their content. Each line is highlighted on its own, so
+/// lines w/ the same content have the same highlighting. More info in the [module
+/// docs](self).
+#[derive(Clone, Debug, Default)]
+pub struct HighlightCache {
+    /// The file extension & theme that the lines were highlighted w/. The cache is cleared
+    /// when either one changes.
+    maybe_key: Option<(String, Arc<Theme>)>,
+    lines: HashMap<String, StyleUSSpanLine>,
+}
+
+impl HighlightCache {
+    pub fn get(&self, line: &str) -> Option<&StyleUSSpanLine> { self.lines.get(line) }
+
+    pub fn len(&self) -> usize { self.lines.len() }
+
+    pub fn is_empty(&self) -> bool { self.lines.is_empty() }
+
+    pub fn clear(&mut self) {
+        self.maybe_key = None;
+        self.lines.clear();
+    }
+
+    /// Highlight the `lines` that aren't cached yet, until `maybe_budget` runs out ([None]
+    /// means there's no budget). At least 1 line is highlighted, so that each call makes
+    /// progress. Returns `true` if some lines are still not highlighted.
+    ///
+    /// Nothing is highlighted (& `false` is returned) if there's no syntax for the
+    /// `file_extension`.
+    pub fn highlight_lines<'a>(
+        &mut self,
+        lines: impl Iterator<Item = &'a str>,
+        file_extension: &str,
+        syntax_set: &SyntaxSet,
+        theme: &Arc<Theme>,
+        maybe_budget: Option<Duration>,
+    ) -> bool {
+        let Some(syntax_ref) = try_get_syntax_ref(syntax_set, file_extension) else {
+            return false;
+        };
+
+        let is_same_key = matches!(
+            &self.maybe_key,
+            Some((it_file_extension, it_theme))
+                if it_file_extension == file_extension && Arc::ptr_eq(it_theme, theme)
+        );
+        if !is_same_key {
+            self.lines.clear();
+            self.maybe_key = Some((file_extension.to_string(), theme.clone()));
+        }
+
+        let start = Instant::now();
+        let mut highlighted_line_count = 0;
+        for line in lines {
+            if self.lines.contains_key(line) {
+                continue;
+            }
+            if let Some(budget) = maybe_budget {
+                if highlighted_line_count > 0 && start.elapsed() >= budget {
+                    return true;
+                }
+            }
+            if self.lines.len() >= HIGHLIGHT_CACHE_MAX_LINE_COUNT {
+                self.lines.clear();
+            }
+
+            // The highlighter is mutated when it is used, so it is re-created for each
+            // line (which is highlighted on its own).
+            let mut highlighter = HighlightLines::new(syntax_ref, theme.as_ref());
+            let styled_line = match highlighter.highlight_line(line, syntax_set) {
+                Ok(it) => syntect_to_styled_text_conversion::from_syntect_to_tui(it),
+                // Cache the plain text, so that it isn't tried again on every render.
+                Err(_) => {
+                    let mut it = StyleUSSpanLine::default();
+                    it += StyleUSSpan::new(Default::default(), US::from(line));
+                    it
+                }
+            };
+            self.lines.insert(line.to_string(), styled_line);
+            highlighted_line_count += 1;
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_rs_utils_core::*;
+
+    use super::*;
+
+    #[test]
+    fn test_highlight_lines_w_budget() {
+        let engine = EditorEngine::default();
+        let lines = ["fn main() {", "    let x = 1;", "}"];
+        let mut cache = HighlightCache::default();
+
+        // Only 1 line is highlighted in each frame, when there's no time for any.
+        let budget = Some(Duration::ZERO);
+        let highlight = |cache: &mut HighlightCache, budget| {
+            cache.highlight_lines(
+                lines.iter().copied(),
+                "rs",
+                &engine.syntax_set,
+                &engine.theme,
+                budget,
+            )
+        };
+        assert!(highlight(&mut cache, budget));
+        assert_eq2!(cache.len(), 1);
+        assert!(cache.get("fn main() {").is_some());
+        assert!(cache.get("}").is_none());
+        assert!(highlight(&mut cache, budget));
+        assert!(!highlight(&mut cache, budget));
+        assert_eq2!(cache.len(), 3);
+
+        // The text of the highlighted line is the same as the line.
+        let text = cache
+            .get("    let x = 1;")
+            .unwrap()
+            .iter()
+            .map(|it| it.text.string.clone())
+            .collect::<String>();
+        assert_eq2!(text, "    let x = 1;");
+
+        // A new theme clears the cache.
+        let theme = Arc::new(engine.theme.as_ref().clone());
+        cache.highlight_lines(
+            lines.iter().copied(),
+            "rs",
+            &engine.syntax_set,
+            &theme,
+            None,
+        );
+        assert_eq2!(cache.len(), 3);
+        assert!(!cache.highlight_lines(
+            ["}"].into_iter(),
+            "unknown_ext",
+            &engine.syntax_set,
+            &theme,
+            None
+        ));
+    }
+}
@@ -0,0 +1,881 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::{collections::VecDeque,
+          fmt::{Display, Formatter, Result},
+          mem::take,
+          time::Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::*;
+
+/// Converts [InputEvent]s into [EditorEvent]s using the key binding tables for the
+/// [KeyBindingMode] that is set in [EditorEngineConfig]. This is called by
+/// [EditorEngineApi::apply_event] before it falls back to the [KeyMap] in
+/// [EditorEngineConfig::key_map].
+///
+/// The key bindings are data driven, each mode is just a table of [KeyBinding]s. This
+/// way the motion & editing logic lives in [EditorEvent] and is shared by all the modes.
+///
+/// Multi key bindings (chords, eg: Vim's `dd`, or the ones that are bound w/
+/// [KeyMap::bind_chord]) are resolved here too:
+/// 1. While the keys that have been pressed are the start of a chord, they are kept in
+///    [KeyBindingState::pending_keys] & nothing is applied.
+/// 2. If the next key doesn't continue the chord, the pending keys are flushed, ie: each
+///    one is translated as if it had been pressed on its own. Then the next key is looked
+///    up on its own (so it may start another chord).
+/// 3. The pending keys are also flushed once [EditorEngineConfig::chord_timeout] has
+///    passed, so that eg: a lone `j` is typed even if `jk` is bound.
+pub struct KeyBindingsApi;
+
+/// The modes that are available when [KeyBindingMode::Vim] is used.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum VimMode {
+    #[default]
+    Normal,
+    Insert,
+    Visual,
+}
+
+/// The modes that are available when [KeyBindingMode::Emacs] is used.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EmacsMode {
+    #[default]
+    Normal,
+    /// The mark has been set (`Ctrl+Space`), so motions extend the selection.
+    MarkSet,
+}
+
+/// The state that the key bindings have to keep in between calls to
+/// [EditorEngineApi::apply_event]. This lives in the [EditorEngine] and not the
+/// [EditorBuffer].
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyBindingState {
+    pub vim_mode: VimMode,
+    pub emacs_mode: EmacsMode,
+    /// The keys of a multi key binding (eg: `dd`) that have been pressed so far.
+    pub pending_keys: Vec<KeyPress>,
+    /// When the 1st of the [pending_keys](KeyBindingState::pending_keys) was pressed.
+    /// More info in [EditorEngineConfig::chord_timeout].
+    #[serde(skip)]
+    pub maybe_pending_keys_instant: Option<Instant>,
+    pub kill_ring: KillRing,
+}
+
+/// A small stack of the text that has been deleted by [EditorEvent::DeleteLine],
+/// [EditorEvent::DeleteWord], [EditorEvent::DeleteToEndOfLine], and
+/// [EditorEvent::DeleteSelection]. [EditorEvent::Yank] inserts the most recent entry.
+///
+/// There is no system clipboard support yet, so this is the clipboard for the editor &
+/// it is shared by all the [KeyBindingMode]s. Only the last [KILL_RING_CAPACITY]
+/// entries are kept.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KillRing {
+    pub entries: VecDeque<String>,
+}
+
+/// Maps [KeyPress]es to the [EditorEvent] (command) that they run. This is the table
+/// for [KeyBindingMode::Standard], and the fallback for the keys that aren't handled by
+/// the [KeyBindingMode::Vim] & [KeyBindingMode::Emacs] tables.
+///
+/// The [Default] is the standard set of key bindings. To customize them, start w/ the
+/// default & [bind](KeyMap::bind) or [unbind](KeyMap::unbind) keys, then set the result
+/// in [EditorEngineConfig::key_map]. Plain characters that aren't bound are inserted.
+///
+/// Multi key bindings (eg: `Ctrl+X Ctrl+S`) are bound w/ [bind_chord](KeyMap::bind_chord).
+/// More info on how they are resolved in [KeyBindingsApi].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyMap {
+    pub entries: Vec<(KeyPress, EditorEvent)>,
+    pub chords: Vec<(Vec<KeyPress>, EditorEvent)>,
+}
+
+/// A single row in a key binding table. When all the `keys` have been pressed (in
+/// order), then the `editor_events` are applied, and the mode is switched (if
+/// `maybe_next_mode` is set). `M` is the type of mode, eg: [VimMode] or [EmacsMode].
+#[derive(Clone, PartialEq, Eq)]
+pub struct KeyBinding<M> {
+    pub keys: Vec<KeyPress>,
+    pub editor_events: Vec<EditorEvent>,
+    pub maybe_next_mode: Option<M>,
+}
+
+pub enum KeyBindingResult {
+    /// The key bindings don't handle this [InputEvent], so fall back to
+    /// [EditorEvent::try_from].
+    NotHandled,
+    /// The [InputEvent] is consumed. The [Vec] is empty if there is nothing to apply
+    /// (eg: the 1st key of `dd`, or a key that only switches the mode).
+    Handled(Vec<EditorEvent>),
+}
+
+enum KeyBindingMatch<'a, M> {
+    Matched(&'a KeyBinding<M>),
+    Partial,
+    NoMatch,
+}
+
+mod key_bindings_api_impl {
+    use super::*;
+
+    impl KeyBindingsApi {
+        pub fn translate(
+            editor_engine: &mut EditorEngine,
+            input_event: &InputEvent,
+        ) -> KeyBindingResult {
+            Self::translate_at(editor_engine, input_event, Instant::now())
+        }
+
+        /// Same as [translate](KeyBindingsApi::translate), where `now` is when the
+        /// `input_event` happened. If any pending keys are flushed, then the result is
+        /// always [KeyBindingResult::Handled] (w/ the [KeyMap] already applied to
+        /// `input_event`).
+        pub fn translate_at(
+            editor_engine: &mut EditorEngine,
+            input_event: &InputEvent,
+            now: Instant,
+        ) -> KeyBindingResult {
+            let mut flushed_editor_events =
+                Self::flush_timed_out_pending_keys(editor_engine, now);
+
+            let result = match input_event {
+                InputEvent::Keyboard(key_press) => {
+                    // The chord is broken, so flush it before `key_press` is looked up.
+                    let state = &editor_engine.key_binding_state;
+                    let mut keys = state.pending_keys.clone();
+                    keys.push(*key_press);
+                    if !state.pending_keys.is_empty()
+                        && !is_chord_prefix(editor_engine, &keys)
+                    {
+                        flushed_editor_events
+                            .extend(Self::flush_pending_keys(editor_engine));
+                    }
+                    translate_key_press(editor_engine, *key_press, Some(now))
+                }
+                _ => KeyBindingResult::NotHandled,
+            };
+
+            if flushed_editor_events.is_empty() {
+                return result;
+            }
+            match result {
+                KeyBindingResult::Handled(editor_events) => {
+                    flushed_editor_events.extend(editor_events)
+                }
+                KeyBindingResult::NotHandled => flushed_editor_events
+                    .extend(editor_engine.config_options.key_map.translate(input_event)),
+            }
+            KeyBindingResult::Handled(flushed_editor_events)
+        }
+
+        /// Returns the [EditorEvent]s of the pending keys if
+        /// [EditorEngineConfig::chord_timeout] has passed since the 1st one was pressed.
+        /// This is called before each key is looked up, & also by the [EditorComponent]
+        /// on each render, since there may not be another key for a while.
+        pub fn flush_timed_out_pending_keys(
+            editor_engine: &mut EditorEngine,
+            now: Instant,
+        ) -> Vec<EditorEvent> {
+            if Self::is_chord_timed_out(editor_engine, now) {
+                Self::flush_pending_keys(editor_engine)
+            } else {
+                vec![]
+            }
+        }
+
+        pub fn is_chord_timed_out(editor_engine: &EditorEngine, now: Instant) -> bool {
+            match (
+                editor_engine.config_options.chord_timeout,
+                editor_engine.key_binding_state.maybe_pending_keys_instant,
+            ) {
+                (Some(chord_timeout), Some(instant)) => {
+                    now.saturating_duration_since(instant) >= chord_timeout
+                }
+                _ => false,
+            }
+        }
+
+        /// Each of the pending keys is translated as if it had been pressed on its own,
+        /// ie: w/out looking for chords.
+        fn flush_pending_keys(editor_engine: &mut EditorEngine) -> Vec<EditorEvent> {
+            let state = &mut editor_engine.key_binding_state;
+            state.maybe_pending_keys_instant = None;
+            let pending_keys = take(&mut state.pending_keys);
+
+            let mut editor_events = vec![];
+            for key_press in pending_keys {
+                match translate_key_press(editor_engine, key_press, None) {
+                    KeyBindingResult::Handled(it) => editor_events.extend(it),
+                    KeyBindingResult::NotHandled => editor_events.extend(
+                        editor_engine
+                            .config_options
+                            .key_map
+                            .translate(&InputEvent::Keyboard(key_press)),
+                    ),
+                }
+            }
+            editor_events
+        }
+
+        pub fn get_vim_mode(editor_engine: &EditorEngine) -> Option<VimMode> {
+            match editor_engine.config_options.key_binding_mode {
+                KeyBindingMode::Vim => Some(editor_engine.key_binding_state.vim_mode),
+                _ => None,
+            }
+        }
+    }
+
+    impl<M> KeyBinding<M> {
+        pub fn new(keys: Vec<KeyPress>, editor_events: Vec<EditorEvent>) -> Self {
+            Self {
+                keys,
+                editor_events,
+                maybe_next_mode: None,
+            }
+        }
+
+        pub fn switch_to(mut self, mode: M) -> Self {
+            self.maybe_next_mode = Some(mode);
+            self
+        }
+    }
+
+    impl KillRing {
+        /// Empty strings are ignored.
+        pub fn push(&mut self, text: String) {
+            if text.is_empty() {
+                return;
+            }
+            self.entries.push_back(text);
+            while self.entries.len() > KILL_RING_CAPACITY {
+                self.entries.pop_front();
+            }
+        }
+
+        pub fn get_top(&self) -> Option<&String> { self.entries.back() }
+    }
+
+    impl KeyMap {
+        /// Bind `key_press` to `editor_event`, replacing the existing binding (if any).
+        pub fn bind(mut self, key_press: KeyPress, editor_event: EditorEvent) -> Self {
+            self = self.unbind(key_press);
+            self.entries.push((key_press, editor_event));
+            self
+        }
+
+        pub fn unbind(mut self, key_press: KeyPress) -> Self {
+            self.entries.retain(|(it, _)| *it != key_press);
+            self
+        }
+
+        pub fn get(&self, key_press: &KeyPress) -> Option<&EditorEvent> {
+            self.entries
+                .iter()
+                .find(|(it, _)| it == key_press)
+                .map(|(_, editor_event)| editor_event)
+        }
+
+        /// Bind the `keys` (pressed in order) to `editor_event`, replacing the existing
+        /// chord (if any). More info in [KeyBindingsApi].
+        pub fn bind_chord(
+            mut self,
+            keys: Vec<KeyPress>,
+            editor_event: EditorEvent,
+        ) -> Self {
+            self = self.unbind_chord(&keys);
+            self.chords.push((keys, editor_event));
+            self
+        }
+
+        pub fn unbind_chord(mut self, keys: &[KeyPress]) -> Self {
+            self.chords.retain(|(it, _)| it != keys);
+            self
+        }
+
+        /// The chords as rows of a key binding table, so they can be looked up along w/
+        /// the ones of the [KeyBindingMode].
+        pub fn get_chord_bindings<M>(&self) -> Vec<KeyBinding<M>> {
+            self.chords
+                .iter()
+                .map(|(keys, editor_event)| {
+                    KeyBinding::new(keys.clone(), vec![editor_event.clone()])
+                })
+                .collect()
+        }
+
+        /// Returns the [EditorEvent] for `input_event`, or [None] if it isn't bound.
+        /// Resize & paste events aren't keys, so they are always converted.
+        pub fn translate(&self, input_event: &InputEvent) -> Option<EditorEvent> {
+            match input_event {
+                InputEvent::Keyboard(key_press) => match self.get(key_press) {
+                    Some(editor_event) => Some(editor_event.clone()),
+                    None => match key_press {
+                        KeyPress::Plain {
+                            key: Key::Character(character),
+                        } => Some(EditorEvent::InsertChar(*character)),
+                        _ => None,
+                    },
+                },
+                InputEvent::Resize(size) => Some(EditorEvent::Resize(*size)),
+                InputEvent::Paste(text) => Some(EditorEvent::Paste(text.clone())),
+                _ => None,
+            }
+        }
+    }
+
+    impl Default for KeyMap {
+        fn default() -> Self {
+            let shift = |it: SpecialKey| keypress!(@special ModifierKeysMask::SHIFT, it);
+            let alt = |it: SpecialKey| keypress!(@special ModifierKeysMask::ALT, it);
+            let special = |it: SpecialKey| keypress!(@special it);
+            let ctrl_alt = ModifierKeysMask::CTRL | ModifierKeysMask::ALT;
+
+            let entries = vec![
+                // Selection events.
+                (
+                    shift(SpecialKey::Right),
+                    EditorEvent::Select(SelectionScope::OneCharRight),
+                ),
+                (
+                    shift(SpecialKey::Left),
+                    EditorEvent::Select(SelectionScope::OneCharLeft),
+                ),
+                (
+                    shift(SpecialKey::Down),
+                    EditorEvent::Select(SelectionScope::OneLineDown),
+                ),
+                (
+                    shift(SpecialKey::Up),
+                    EditorEvent::Select(SelectionScope::OneLineUp),
+                ),
+                (
+                    shift(SpecialKey::PageUp),
+                    EditorEvent::Select(SelectionScope::PageUp),
+                ),
+                (
+                    shift(SpecialKey::PageDown),
+                    EditorEvent::Select(SelectionScope::PageDown),
+                ),
+                (
+                    shift(SpecialKey::Home),
+                    EditorEvent::Select(SelectionScope::Home),
+                ),
+                (
+                    shift(SpecialKey::End),
+                    EditorEvent::Select(SelectionScope::End),
+                ),
+                // Multiple caret events.
+                (
+                    keypress!(@special ctrl_alt, SpecialKey::Down),
+                    EditorEvent::AddCaretBelow,
+                ),
+                (special(SpecialKey::Esc), EditorEvent::CollapseCarets),
+                // Line events.
+                (
+                    keypress!(@char ModifierKeysMask::CTRL, 'd'),
+                    EditorEvent::DuplicateLine,
+                ),
+                (
+                    keypress!(@char ModifierKeysMask::CTRL, 't'),
+                    EditorEvent::TransposeChars,
+                ),
+                (
+                    alt(SpecialKey::Up),
+                    EditorEvent::MoveLine(CaretDirection::Up),
+                ),
+                (
+                    alt(SpecialKey::Down),
+                    EditorEvent::MoveLine(CaretDirection::Down),
+                ),
+                (
+                    keypress!(@char ModifierKeysMask::CTRL, '/'),
+                    EditorEvent::ToggleLineComment,
+                ),
+                (
+                    special(SpecialKey::Tab),
+                    EditorEvent::IndentSelectionOrInsertIndent,
+                ),
+                (special(SpecialKey::BackTab), EditorEvent::DedentSelection),
+                // Some terminals report Shift+Tab as BackTab w/ the shift modifier.
+                (shift(SpecialKey::BackTab), EditorEvent::DedentSelection),
+                (
+                    keypress!(@char ModifierKeysMask::CTRL, 'r'),
+                    EditorEvent::OpenReplace,
+                ),
+                // Commands (carried out by the app).
+                (
+                    keypress!(@char ModifierKeysMask::CTRL, 's'),
+                    EditorEvent::Command(EditorCommand::Save),
+                ),
+                (
+                    keypress!(@char ModifierKeysMask::CTRL, 'o'),
+                    EditorEvent::Command(EditorCommand::Open),
+                ),
+                // Folding events.
+                (
+                    keypress!(@char ModifierKeysMask::ALT, 'z'),
+                    EditorEvent::ToggleFold,
+                ),
+                // Other events.
+                (special(SpecialKey::PageDown), EditorEvent::PageDown),
+                (special(SpecialKey::PageUp), EditorEvent::PageUp),
+                (special(SpecialKey::Home), EditorEvent::Home),
+                (special(SpecialKey::End), EditorEvent::End),
+                (special(SpecialKey::Enter), EditorEvent::InsertNewLine),
+                (special(SpecialKey::Delete), EditorEvent::Delete),
+                (special(SpecialKey::Insert), EditorEvent::ToggleInsertMode),
+                (special(SpecialKey::Backspace), EditorEvent::Backspace),
+                (
+                    special(SpecialKey::Up),
+                    EditorEvent::MoveCaret(CaretDirection::Up),
+                ),
+                (
+                    special(SpecialKey::Down),
+                    EditorEvent::MoveCaret(CaretDirection::Down),
+                ),
+                (
+                    special(SpecialKey::Left),
+                    EditorEvent::MoveCaret(CaretDirection::Left),
+                ),
+                (
+                    special(SpecialKey::Right),
+                    EditorEvent::MoveCaret(CaretDirection::Right),
+                ),
+            ];
+
+            Self {
+                entries,
+                chords: vec![],
+            }
+        }
+    }
+
+    impl Display for VimMode {
+        fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+            match self {
+                VimMode::Normal => write!(f, "NORMAL"),
+                VimMode::Insert => write!(f, "INSERT"),
+                VimMode::Visual => write!(f, "VISUAL"),
+            }
+        }
+    }
+
+    /// Dispatch `key_press` to the key bindings of the [KeyBindingMode]. `maybe_now` is
+    /// [None] while the pending keys are flushed, so that they don't start a chord.
+    fn translate_key_press(
+        editor_engine: &mut EditorEngine,
+        key_press: KeyPress,
+        maybe_now: Option<Instant>,
+    ) -> KeyBindingResult {
+        let EditorEngine {
+            config_options,
+            key_binding_state: state,
+            ..
+        } = editor_engine;
+
+        match config_options.key_binding_mode {
+            KeyBindingMode::Standard => {
+                let bindings = config_options.key_map.get_chord_bindings::<()>();
+                match find_key_binding(&bindings, state, key_press, maybe_now) {
+                    KeyBindingMatch::Matched(binding) => {
+                        KeyBindingResult::Handled(binding.editor_events.clone())
+                    }
+                    KeyBindingMatch::Partial => KeyBindingResult::Handled(vec![]),
+                    KeyBindingMatch::NoMatch => KeyBindingResult::NotHandled,
+                }
+            }
+            KeyBindingMode::Vim => vim_key_bindings::translate(
+                state,
+                &config_options.key_map,
+                key_press,
+                maybe_now,
+            ),
+            KeyBindingMode::Emacs => emacs_key_bindings::translate(
+                state,
+                &config_options.key_map,
+                key_press,
+                maybe_now,
+            ),
+        }
+    }
+
+    /// Whether `keys` are (the start of) a binding in the [KeyBindingMode] or a chord in
+    /// the [KeyMap].
+    fn is_chord_prefix(editor_engine: &EditorEngine, keys: &[KeyPress]) -> bool {
+        let config_options = &editor_engine.config_options;
+        let state = &editor_engine.key_binding_state;
+
+        let is_mode_prefix = match config_options.key_binding_mode {
+            KeyBindingMode::Standard => false,
+            KeyBindingMode::Vim => vim_key_bindings::get_key_bindings(state.vim_mode)
+                .iter()
+                .any(|it| it.keys.starts_with(keys)),
+            KeyBindingMode::Emacs => {
+                emacs_key_bindings::get_key_bindings(state.emacs_mode)
+                    .iter()
+                    .any(|it| it.keys.starts_with(keys))
+            }
+        };
+        is_mode_prefix
+            || config_options
+                .key_map
+                .chords
+                .iter()
+                .any(|(it, _)| it.starts_with(keys))
+    }
+
+    /// Add `key_press` to the `pending_keys` and look for a binding in `bindings`. The
+    /// `pending_keys` are cleared unless there is a partial match, which is only allowed
+    /// when `maybe_now` is set (it is when the 1st pending key was pressed).
+    pub(super) fn find_key_binding<'a, M>(
+        bindings: &'a [KeyBinding<M>],
+        state: &mut KeyBindingState,
+        key_press: KeyPress,
+        maybe_now: Option<Instant>,
+    ) -> KeyBindingMatch<'a, M> {
+        let pending_keys = &mut state.pending_keys;
+        pending_keys.push(key_press);
+
+        if let Some(binding) = bindings.iter().find(|it| it.keys == *pending_keys) {
+            pending_keys.clear();
+            state.maybe_pending_keys_instant = None;
+            return KeyBindingMatch::Matched(binding);
+        }
+
+        if let Some(now) = maybe_now {
+            if bindings.iter().any(|it| it.keys.starts_with(pending_keys)) {
+                state.maybe_pending_keys_instant.get_or_insert(now);
+                return KeyBindingMatch::Partial;
+            }
+        }
+
+        pending_keys.clear();
+        state.maybe_pending_keys_instant = None;
+        KeyBindingMatch::NoMatch
+    }
+}
+
+mod vim_key_bindings {
+    use super::{key_bindings_api_impl::find_key_binding, *};
+
+    /// The chords in the `key_map` come after (so they don't override) the bindings of
+    /// the [VimMode].
+    pub fn translate(
+        state: &mut KeyBindingState,
+        key_map: &KeyMap,
+        key_press: KeyPress,
+        maybe_now: Option<Instant>,
+    ) -> KeyBindingResult {
+        let bindings: Vec<_> = get_key_bindings(state.vim_mode)
+            .into_iter()
+            .chain(key_map.get_chord_bindings())
+            .collect();
+        match find_key_binding(&bindings, state, key_press, maybe_now) {
+            KeyBindingMatch::Matched(binding) => {
+                if let Some(vim_mode) = binding.maybe_next_mode {
+                    state.vim_mode = vim_mode;
+                }
+                KeyBindingResult::Handled(binding.editor_events.clone())
+            }
+            KeyBindingMatch::Partial => KeyBindingResult::Handled(vec![]),
+            KeyBindingMatch::NoMatch => match state.vim_mode {
+                VimMode::Insert => KeyBindingResult::NotHandled,
+                // Editing (eg: typing text, Enter, Backspace or Tab) is only allowed in
+                // insert mode. The other keys fall back to the `key_map` (eg: the arrow
+                // keys & commands), & the ones that it doesn't bind (eg: Ctrl+C) are left
+                // for the app.
+                _ => match key_map.translate(&InputEvent::Keyboard(key_press)) {
+                    Some(editor_event) if editor_event.is_edit() => {
+                        KeyBindingResult::Handled(vec![])
+                    }
+                    _ => KeyBindingResult::NotHandled,
+                },
+            },
+        }
+    }
+
+    pub fn get_key_bindings(vim_mode: VimMode) -> Vec<KeyBinding<VimMode>> {
+        let esc = keypress!(@special SpecialKey::Esc);
+        let char = |it: char| keypress!(@char it);
+
+        match vim_mode {
+            VimMode::Normal => vec![
+                // Motions.
+                KeyBinding::new(
+                    vec![char('h')],
+                    vec![EditorEvent::MoveCaret(CaretDirection::Left)],
+                ),
+                KeyBinding::new(
+                    vec![char('j')],
+                    vec![EditorEvent::MoveCaret(CaretDirection::Down)],
+                ),
+                KeyBinding::new(
+                    vec![char('k')],
+                    vec![EditorEvent::MoveCaret(CaretDirection::Up)],
+                ),
+                KeyBinding::new(
+                    vec![char('l')],
+                    vec![EditorEvent::MoveCaret(CaretDirection::Right)],
+                ),
+                KeyBinding::new(vec![char('w')], vec![EditorEvent::MoveCaretToNextWord]),
+                KeyBinding::new(vec![char('b')], vec![EditorEvent::MoveCaretToPrevWord]),
+                KeyBinding::new(vec![char('0')], vec![EditorEvent::Home]),
+                KeyBinding::new(vec![char('$')], vec![EditorEvent::End]),
+                // Operators.
+                KeyBinding::new(vec![char('x')], vec![EditorEvent::Delete]),
+                KeyBinding::new(vec![char('P')], vec![EditorEvent::Yank]),
+                KeyBinding::new(
+                    vec![char('d'), char('d')],
+                    vec![EditorEvent::DeleteLine],
+                ),
+                KeyBinding::new(
+                    vec![char('d'), char('w')],
+                    vec![EditorEvent::DeleteWord],
+                ),
+                KeyBinding::new(
+                    vec![char('z'), char('a')],
+                    vec![EditorEvent::ToggleFold],
+                ),
+                KeyBinding::new(
+                    vec![char('z'), char('z')],
+                    vec![EditorEvent::RecenterCaret],
+                ),
+                KeyBinding::new(
+                    vec![char('>'), char('>')],
+                    vec![EditorEvent::IndentLines],
+                ),
+                KeyBinding::new(
+                    vec![char('<'), char('<')],
+                    vec![EditorEvent::DedentLines],
+                ),
+                // Mode switches.
+                KeyBinding::new(vec![char('i')], vec![]).switch_to(VimMode::Insert),
+                KeyBinding::new(
+                    vec![char('a')],
+                    vec![EditorEvent::MoveCaret(CaretDirection::Right)],
+                )
+                .switch_to(VimMode::Insert),
+                KeyBinding::new(vec![char('I')], vec![EditorEvent::Home])
+                    .switch_to(VimMode::Insert),
+                KeyBinding::new(vec![char('A')], vec![EditorEvent::End])
+                    .switch_to(VimMode::Insert),
+                KeyBinding::new(
+                    vec![char('o')],
+                    vec![EditorEvent::End, EditorEvent::InsertNewLine],
+                )
+                .switch_to(VimMode::Insert),
+                KeyBinding::new(vec![char('v')], vec![]).switch_to(VimMode::Visual),
+            ]
+            .into_iter()
+            .chain(get_mark_key_bindings())
+            .collect(),
+            VimMode::Insert => vec![KeyBinding::new(
+                vec![esc],
+                vec![EditorEvent::MoveCaret(CaretDirection::Left)],
+            )
+            .switch_to(VimMode::Normal)],
+            VimMode::Visual => vec![
+                // Motions extend the selection.
+                KeyBinding::new(
+                    vec![char('h')],
+                    vec![EditorEvent::Select(SelectionScope::OneCharLeft)],
+                ),
+                KeyBinding::new(
+                    vec![char('j')],
+                    vec![EditorEvent::Select(SelectionScope::OneLineDown)],
+                ),
+                KeyBinding::new(
+                    vec![char('k')],
+                    vec![EditorEvent::Select(SelectionScope::OneLineUp)],
+                ),
+                KeyBinding::new(
+                    vec![char('l')],
+                    vec![EditorEvent::Select(SelectionScope::OneCharRight)],
+                ),
+                KeyBinding::new(
+                    vec![char('0')],
+                    vec![EditorEvent::Select(SelectionScope::Home)],
+                ),
+                KeyBinding::new(
+                    vec![char('$')],
+                    vec![EditorEvent::Select(SelectionScope::End)],
+                ),
+                // Operators.
+                KeyBinding::new(vec![char('d')], vec![EditorEvent::DeleteSelection])
+                    .switch_to(VimMode::Normal),
+                KeyBinding::new(vec![char('x')], vec![EditorEvent::DeleteSelection])
+                    .switch_to(VimMode::Normal),
+                // The selection is kept, so that it can be indented again.
+                KeyBinding::new(vec![char('>')], vec![EditorEvent::IndentLines]),
+                KeyBinding::new(vec![char('<')], vec![EditorEvent::DedentLines]),
+                // Mode switches.
+                KeyBinding::new(vec![char('v')], vec![EditorEvent::ClearSelection])
+                    .switch_to(VimMode::Normal),
+                KeyBinding::new(vec![esc], vec![EditorEvent::ClearSelection])
+                    .switch_to(VimMode::Normal),
+            ],
+        }
+    }
+
+    /// `m{a-z}` sets a mark at the caret, and `'{a-z}` jumps to it.
+    fn get_mark_key_bindings() -> Vec<KeyBinding<VimMode>> {
+        let char = |it: char| keypress!(@char it);
+
+        ('a'..='z')
+            .flat_map(|name| {
+                [
+                    KeyBinding::new(
+                        vec![char('m'), char(name)],
+                        vec![EditorEvent::SetMark(name)],
+                    ),
+                    KeyBinding::new(
+                        vec![char('\''), char(name)],
+                        vec![EditorEvent::GotoMark(name)],
+                    ),
+                ]
+            })
+            .collect()
+    }
+}
+
+mod emacs_key_bindings {
+    use super::{key_bindings_api_impl::find_key_binding, *};
+
+    /// The chords in the `key_map` come after (so they don't override) the bindings of
+    /// the [EmacsMode].
+    pub fn translate(
+        state: &mut KeyBindingState,
+        key_map: &KeyMap,
+        key_press: KeyPress,
+        maybe_now: Option<Instant>,
+    ) -> KeyBindingResult {
+        let bindings: Vec<_> = get_key_bindings(state.emacs_mode)
+            .into_iter()
+            .chain(key_map.get_chord_bindings())
+            .collect();
+        match find_key_binding(&bindings, state, key_press, maybe_now) {
+            KeyBindingMatch::Matched(binding) => {
+                if let Some(emacs_mode) = binding.maybe_next_mode {
+                    state.emacs_mode = emacs_mode;
+                }
+                KeyBindingResult::Handled(binding.editor_events.clone())
+            }
+            KeyBindingMatch::Partial => KeyBindingResult::Handled(vec![]),
+            // Any other key deactivates the mark.
+            KeyBindingMatch::NoMatch => {
+                state.emacs_mode = EmacsMode::Normal;
+                KeyBindingResult::NotHandled
+            }
+        }
+    }
+
+    pub fn get_key_bindings(emacs_mode: EmacsMode) -> Vec<KeyBinding<EmacsMode>> {
+        let ctrl = |it: char| keypress!(@char ModifierKeysMask::CTRL, it);
+        let alt = |it: char| keypress!(@char ModifierKeysMask::ALT, it);
+        let special = |it: SpecialKey| keypress!(@special it);
+
+        match emacs_mode {
+            EmacsMode::Normal => vec![
+                // Motions.
+                KeyBinding::new(vec![ctrl('a')], vec![EditorEvent::Home]),
+                KeyBinding::new(vec![ctrl('e')], vec![EditorEvent::End]),
+                KeyBinding::new(
+                    vec![ctrl('f')],
+                    vec![EditorEvent::MoveCaret(CaretDirection::Right)],
+                ),
+                KeyBinding::new(
+                    vec![ctrl('b')],
+                    vec![EditorEvent::MoveCaret(CaretDirection::Left)],
+                ),
+                KeyBinding::new(
+                    vec![ctrl('n')],
+                    vec![EditorEvent::MoveCaret(CaretDirection::Down)],
+                ),
+                KeyBinding::new(
+                    vec![ctrl('p')],
+                    vec![EditorEvent::MoveCaret(CaretDirection::Up)],
+                ),
+                KeyBinding::new(vec![alt('f')], vec![EditorEvent::MoveCaretToNextWord]),
+                KeyBinding::new(vec![alt('b')], vec![EditorEvent::MoveCaretToPrevWord]),
+                // Kill & yank.
+                KeyBinding::new(vec![ctrl('k')], vec![EditorEvent::DeleteToEndOfLine]),
+                KeyBinding::new(vec![ctrl('y')], vec![EditorEvent::Yank]),
+                KeyBinding::new(vec![alt('d')], vec![EditorEvent::DeleteWord]),
+                // Transpose.
+                KeyBinding::new(vec![ctrl('t')], vec![EditorEvent::TransposeChars]),
+                KeyBinding::new(vec![alt('t')], vec![EditorEvent::TransposeWords]),
+                // View.
+                KeyBinding::new(vec![ctrl('l')], vec![EditorEvent::RecenterCaret]),
+                // Mark.
+                KeyBinding::new(vec![ctrl(' ')], vec![EditorEvent::ClearSelection])
+                    .switch_to(EmacsMode::MarkSet),
+            ],
+            EmacsMode::MarkSet => vec![
+                // Motions extend the selection.
+                KeyBinding::new(
+                    vec![ctrl('a')],
+                    vec![EditorEvent::Select(SelectionScope::Home)],
+                ),
+                KeyBinding::new(
+                    vec![ctrl('e')],
+                    vec![EditorEvent::Select(SelectionScope::End)],
+                ),
+                KeyBinding::new(
+                    vec![ctrl('f')],
+                    vec![EditorEvent::Select(SelectionScope::OneCharRight)],
+                ),
+                KeyBinding::new(
+                    vec![ctrl('b')],
+                    vec![EditorEvent::Select(SelectionScope::OneCharLeft)],
+                ),
+                KeyBinding::new(
+                    vec![ctrl('n')],
+                    vec![EditorEvent::Select(SelectionScope::OneLineDown)],
+                ),
+                KeyBinding::new(
+                    vec![ctrl('p')],
+                    vec![EditorEvent::Select(SelectionScope::OneLineUp)],
+                ),
+                KeyBinding::new(
+                    vec![special(SpecialKey::Right)],
+                    vec![EditorEvent::Select(SelectionScope::OneCharRight)],
+                ),
+                KeyBinding::new(
+                    vec![special(SpecialKey::Left)],
+                    vec![EditorEvent::Select(SelectionScope::OneCharLeft)],
+                ),
+                KeyBinding::new(
+                    vec![special(SpecialKey::Down)],
+                    vec![EditorEvent::Select(SelectionScope::OneLineDown)],
+                ),
+                KeyBinding::new(
+                    vec![special(SpecialKey::Up)],
+                    vec![EditorEvent::Select(SelectionScope::OneLineUp)],
+                ),
+                // Kill the region.
+                KeyBinding::new(vec![ctrl('w')], vec![EditorEvent::DeleteSelection])
+                    .switch_to(EmacsMode::Normal),
+                // Deactivate the mark.
+                KeyBinding::new(vec![ctrl('g')], vec![EditorEvent::ClearSelection])
+                    .switch_to(EmacsMode::Normal),
+                KeyBinding::new(vec![ctrl(' ')], vec![EditorEvent::ClearSelection])
+                    .switch_to(EmacsMode::Normal),
+            ],
+        }
+    }
+}
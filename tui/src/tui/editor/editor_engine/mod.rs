@@ -16,11 +16,25 @@
  */
 
 // Attach.
+pub mod bidi;
+pub mod completion;
+pub mod diagnostics;
 pub mod editor_engine_api;
 pub mod editor_engine_internal_api;
 pub mod editor_engine_struct;
+pub mod highlight_cache;
+pub mod key_bindings;
+pub mod mouse_click;
+pub mod search_replace;
 
 // Re-export.
+pub use bidi::*;
+pub use completion::*;
+pub use diagnostics::*;
 pub use editor_engine_api::*;
 pub use editor_engine_internal_api::*;
 pub use editor_engine_struct::*;
+pub use highlight_cache::*;
+pub use key_bindings::*;
+pub use mouse_click::*;
+pub use search_replace::*;
@@ -0,0 +1,147 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::time::{Duration, Instant};
+
+use r3bl_rs_utils_core::*;
+
+use crate::*;
+
+/// Counts the left clicks in the editor, so that a double click selects the word under
+/// the mouse, & a triple click selects the whole line. Clicks are counted when they land
+/// on the same cell within [MULTI_CLICK_INTERVAL_MS] of each other. This lives in the
+/// [EditorEngine]. More info in [try_handle_mouse_input_event].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ClickState {
+    /// Where (in terminal coordinates) & when the last click happened.
+    pub maybe_last_click: Option<(Position, Instant)>,
+    /// 1 for a single click, 2 for a double click, & 3 for a triple click. It wraps
+    /// back to 1 after that.
+    pub click_count: usize,
+}
+
+mod click_state_impl {
+    use super::*;
+
+    impl ClickState {
+        /// Record a click at `position` & return the new
+        /// [click_count](ClickState::click_count).
+        pub fn register_click(&mut self, position: Position, now: Instant) -> usize {
+            let is_repeat_click = match self.maybe_last_click {
+                Some((last_position, last_instant)) => {
+                    last_position == position
+                        && now.saturating_duration_since(last_instant)
+                            <= Duration::from_millis(MULTI_CLICK_INTERVAL_MS)
+                }
+                None => false,
+            };
+            self.click_count = match is_repeat_click {
+                true if self.click_count < 3 => self.click_count + 1,
+                _ => 1,
+            };
+            self.maybe_last_click = Some((position, now));
+            self.click_count
+        }
+    }
+}
+
+mod mouse_click_api {
+    use super::*;
+
+    /// A left click in the editor's box moves the caret to it, a double click selects the
+    /// word under the mouse (using [EditorEngineConfig::word_char_class]), & a triple
+    /// click selects the whole line. This doesn't change the content, so it works in
+    /// [EditMode::ReadOnly] too.
+    ///
+    /// Returns the new buffer if the event was handled.
+    pub fn try_handle_mouse_input_event(
+        editor_engine: &mut EditorEngine,
+        editor_buffer: &EditorBuffer,
+        input_event: &InputEvent,
+    ) -> Option<EditorBuffer> {
+        try_handle_mouse_input_event_at(
+            editor_engine,
+            editor_buffer,
+            input_event,
+            Instant::now(),
+        )
+    }
+
+    /// Same as [try_handle_mouse_input_event], where `now` is when the `input_event`
+    /// happened.
+    pub fn try_handle_mouse_input_event_at(
+        editor_engine: &mut EditorEngine,
+        editor_buffer: &EditorBuffer,
+        input_event: &InputEvent,
+        now: Instant,
+    ) -> Option<EditorBuffer> {
+        let InputEvent::Mouse(MouseInput {
+            pos,
+            kind: MouseInputKind::MouseDown(Button::Left),
+            ..
+        }) = input_event
+        else {
+            return None;
+        };
+
+        let viewport = size! {
+            col_count: editor_engine.viewport_width(),
+            row_count: editor_engine.viewport_height()
+        };
+        let origin_pos = editor_engine.current_box.style_adjusted_origin_pos;
+        let is_in_viewport = pos.col_index >= origin_pos.col_index
+            && pos.row_index >= origin_pos.row_index
+            && pos.col_index < origin_pos.col_index + viewport.col_count
+            && pos.row_index < origin_pos.row_index + viewport.row_count;
+        if !is_in_viewport {
+            return None;
+        }
+
+        // Rows that are hidden by folds aren't painted, so the display row has to be
+        // mapped back to a buffer row.
+        let scroll_offset = editor_buffer.get_scroll_offset();
+        let display_row_index = ch!(@to_usize pos.row_index - origin_pos.row_index);
+        let row_index = match editor_buffer
+            .get_fold_map()
+            .visible_rows(scroll_offset.row_index, ch!(@to_usize editor_buffer.len()))
+            .nth(display_row_index)
+        {
+            Some(VisibleRow::Line(row_index)) => row_index,
+            Some(VisibleRow::Folded(fold)) => fold.start_row_index,
+            // Below the last line.
+            None => editor_buffer.len(),
+        };
+        let position = position! {
+            col_index: pos.col_index - origin_pos.col_index + scroll_offset.col_index,
+            row_index: row_index
+        };
+
+        let click_count = editor_engine.click_state.register_click(*pos, now);
+        let mut new_editor_buffer = editor_buffer.clone();
+        match click_count {
+            2 => new_editor_buffer.select_word_at(
+                position,
+                &editor_engine.config_options.word_char_class,
+                viewport,
+            ),
+            3 => new_editor_buffer.select_line_at(position.row_index, viewport),
+            _ => new_editor_buffer.click_at(position, viewport),
+        };
+        Some(new_editor_buffer)
+    }
+}
+pub use mouse_click_api::*;
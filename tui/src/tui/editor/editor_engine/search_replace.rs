@@ -0,0 +1,460 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use r3bl_rs_utils_core::*;
+
+use crate::*;
+
+/// Which field of the replace bar the typed chars go into.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ReplaceField {
+    #[default]
+    Query,
+    Replacement,
+}
+
+/// The state of the replace mode, which lives in the [EditorEngine]. It is opened by
+/// [EditorEvent::OpenReplace], & while it is open it gets all the keyboard input. More
+/// info in [try_handle_replace_input_event].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ReplaceState {
+    pub is_active: bool,
+    pub query: String,
+    pub replacement: String,
+    pub options: SearchOptions,
+    pub focused_field: ReplaceField,
+    /// Index into the matches of the `query`, of the one that is replaced next.
+    pub current_match_index: usize,
+    /// The compiled `query` when [SearchOptions::is_regex] is set, so that it is only
+    /// compiled again when the `query` or the `options` change. More info in
+    /// [update_regex_cache](ReplaceState::update_regex_cache).
+    #[cfg(feature = "regex")]
+    pub maybe_regex_cache: Option<RegexCache>,
+}
+
+/// A regex & the `query` & `options` that it was compiled from, or the message of the
+/// error if the `query` is invalid.
+#[cfg(feature = "regex")]
+#[derive(Clone, Debug)]
+pub struct RegexCache {
+    pub query: String,
+    pub options: SearchOptions,
+    pub result: Result<regex::Regex, String>,
+}
+
+/// The `result` is derived from the `query` & `options`, & [regex::Regex] isn't
+/// comparable.
+#[cfg(feature = "regex")]
+impl PartialEq for RegexCache {
+    fn eq(&self, other: &Self) -> bool {
+        self.query == other.query && self.options == other.options
+    }
+}
+
+#[cfg(feature = "regex")]
+impl Eq for RegexCache {}
+
+mod replace_state_impl {
+    use super::*;
+
+    impl ReplaceState {
+        pub fn open(&mut self) {
+            self.is_active = true;
+            self.focused_field = ReplaceField::Query;
+        }
+
+        /// The `query`, `replacement` & `options` are kept for the next time it is opened.
+        pub fn close(&mut self) { self.is_active = false; }
+
+        /// Call this after changing the `query` or the `options`. The regex is only
+        /// compiled if [SearchOptions::is_regex] is set & it isn't cached already.
+        pub fn update_regex_cache(&mut self) {
+            #[cfg(feature = "regex")]
+            {
+                if !self.options.is_regex {
+                    self.maybe_regex_cache = None;
+                } else if self.get_cached_regex().is_none() {
+                    self.maybe_regex_cache = Some(RegexCache {
+                        query: self.query.clone(),
+                        options: self.options,
+                        result: build_search_regex(&self.query, &self.options),
+                    });
+                }
+            }
+        }
+
+        /// Returns the cached regex if it is for the current `query` & `options`.
+        #[cfg(feature = "regex")]
+        fn get_cached_regex(&self) -> Option<&Result<regex::Regex, String>> {
+            self.maybe_regex_cache
+                .as_ref()
+                .filter(|it| it.query == self.query && it.options == self.options)
+                .map(|it| &it.result)
+        }
+
+        /// Returns the message of the error if the `query` is an invalid regex.
+        pub fn get_query_error(&self) -> Option<&str> {
+            #[cfg(feature = "regex")]
+            if let Some(Err(message)) = self.get_cached_regex() {
+                return Some(message);
+            }
+            #[cfg(not(feature = "regex"))]
+            if self.options.is_regex {
+                return Some(REGEX_FEATURE_DISABLED_MSG);
+            }
+            None
+        }
+
+        pub fn get_matches(&self, editor_buffer: &EditorBuffer) -> Vec<SearchMatch> {
+            #[cfg(feature = "regex")]
+            match self.get_cached_regex() {
+                Some(Ok(regex)) => return editor_buffer.find_regex_matches(regex),
+                Some(Err(_)) => return vec![],
+                None => {}
+            }
+            editor_buffer.find_matches(&self.query, &self.options)
+        }
+
+        /// Returns the text that replaces `search_match`, which is the `replacement` w/
+        /// the capture group references (eg: `$1`) expanded for a regex `query`.
+        #[cfg_attr(not(feature = "regex"), allow(unused_variables))]
+        pub fn get_replacement_for(
+            &self,
+            editor_buffer: &EditorBuffer,
+            search_match: &SearchMatch,
+        ) -> Option<String> {
+            #[cfg(feature = "regex")]
+            if let Some(Ok(regex)) = self.get_cached_regex() {
+                return editor_buffer.expand_regex_replacement(
+                    regex,
+                    search_match,
+                    &self.replacement,
+                );
+            }
+            Some(self.replacement.clone())
+        }
+
+        /// Replace all the matches in `editor_buffer` in one change. Returns the number of
+        /// replacements.
+        pub fn replace_all(&self, editor_buffer: &mut EditorBuffer) -> usize {
+            #[cfg(feature = "regex")]
+            if let Some(Ok(regex)) = self.get_cached_regex() {
+                return editor_buffer.replace_all_regex(regex, &self.replacement);
+            }
+            editor_buffer.replace_all(&self.query, &self.replacement, &self.options)
+        }
+
+        /// Returns the current match, and the number of matches.
+        pub fn get_current_match(
+            &self,
+            editor_buffer: &EditorBuffer,
+        ) -> (Option<SearchMatch>, usize) {
+            let matches = self.get_matches(editor_buffer);
+            let current_match_index =
+                self.current_match_index.min(matches.len().max(1) - 1);
+            (matches.get(current_match_index).copied(), matches.len())
+        }
+
+        pub(super) fn get_focused_field_mut(&mut self) -> &mut String {
+            match self.focused_field {
+                ReplaceField::Query => &mut self.query,
+                ReplaceField::Replacement => &mut self.replacement,
+            }
+        }
+
+        /// Make the first match at or after the caret the current one, eg: when the
+        /// query changes.
+        pub(super) fn reset_current_match(&mut self, editor_buffer: &EditorBuffer) {
+            let caret = editor_buffer.get_caret(CaretKind::ScrollAdjusted);
+            self.current_match_index = self
+                .get_matches(editor_buffer)
+                .iter()
+                .position(|it| {
+                    (it.row_index, it.start_col_index)
+                        >= (caret.row_index, caret.col_index)
+                })
+                .unwrap_or(0);
+        }
+    }
+}
+
+pub mod replace_api {
+    use super::*;
+
+    /// When the replace mode is open, it gets the first chance to handle the
+    /// `input_event`, and the keys don't reach the buffer:
+    /// - Typed (and pasted) chars & Backspace edit the focused field, & Tab switches
+    ///   between the query & the replacement.
+    /// - Alt+C toggles [SearchOptions::case_insensitive], & Alt+W toggles
+    ///   [SearchOptions::whole_word]. W/ the `regex` feature, Alt+R toggles
+    ///   `SearchOptions::is_regex`.
+    /// - Up & Down move to the previous & next match.
+    /// - Enter replaces the current match & moves to the next one.
+    /// - Alt+A replaces all the matches in one change, so a single undo reverts them.
+    /// - Esc closes it.
+    ///
+    /// Returns the new buffer if the event was handled.
+    pub fn try_handle_replace_input_event(
+        editor_engine: &mut EditorEngine,
+        editor_buffer: &EditorBuffer,
+        input_event: &InputEvent,
+    ) -> Option<EditorBuffer> {
+        if !editor_engine.replace_state.is_active {
+            return None;
+        }
+
+        let viewport = size! {
+            col_count: editor_engine.viewport_width(),
+            row_count: editor_engine.viewport_height()
+        };
+        let replace_state = &mut editor_engine.replace_state;
+        let mut new_editor_buffer = editor_buffer.clone();
+
+        match input_event {
+            InputEvent::Keyboard(KeyPress::Plain { key }) => match key {
+                Key::Character(character) => {
+                    replace_state.get_focused_field_mut().push(*character);
+                }
+                Key::SpecialKey(SpecialKey::Backspace) => {
+                    replace_state.get_focused_field_mut().pop();
+                }
+                Key::SpecialKey(SpecialKey::Tab) => {
+                    replace_state.focused_field = match replace_state.focused_field {
+                        ReplaceField::Query => ReplaceField::Replacement,
+                        ReplaceField::Replacement => ReplaceField::Query,
+                    };
+                    return Some(new_editor_buffer);
+                }
+                Key::SpecialKey(SpecialKey::Up) => {
+                    let match_count = replace_state.get_matches(editor_buffer).len();
+                    replace_state.current_match_index =
+                        match replace_state.current_match_index.min(match_count) {
+                            0 => match_count.max(1) - 1,
+                            index => index - 1,
+                        };
+                    goto_current_match(replace_state, &mut new_editor_buffer, viewport);
+                    return Some(new_editor_buffer);
+                }
+                Key::SpecialKey(SpecialKey::Down) => {
+                    let match_count = replace_state.get_matches(editor_buffer).len();
+                    replace_state.current_match_index =
+                        match replace_state.current_match_index + 1 < match_count {
+                            true => replace_state.current_match_index + 1,
+                            false => 0,
+                        };
+                    goto_current_match(replace_state, &mut new_editor_buffer, viewport);
+                    return Some(new_editor_buffer);
+                }
+                Key::SpecialKey(SpecialKey::Enter) => {
+                    if let (Some(search_match), _) =
+                        replace_state.get_current_match(editor_buffer)
+                    {
+                        if let Some(replacement) = replace_state
+                            .get_replacement_for(editor_buffer, &search_match)
+                        {
+                            new_editor_buffer.replace_match(
+                                &search_match,
+                                &replacement,
+                                viewport,
+                            );
+                        }
+                    }
+                    return Some(new_editor_buffer);
+                }
+                Key::SpecialKey(SpecialKey::Esc) => {
+                    replace_state.close();
+                    return Some(new_editor_buffer);
+                }
+                _ => return Some(new_editor_buffer),
+            },
+            InputEvent::Keyboard(KeyPress::WithModifiers {
+                key: Key::Character(character),
+                mask: ModifierKeysMask::ALT,
+            }) => match character {
+                'c' => {
+                    replace_state.options.case_insensitive =
+                        !replace_state.options.case_insensitive;
+                }
+                'w' => {
+                    replace_state.options.whole_word = !replace_state.options.whole_word
+                }
+                #[cfg(feature = "regex")]
+                'r' => replace_state.options.is_regex = !replace_state.options.is_regex,
+                'a' => {
+                    replace_state.replace_all(&mut new_editor_buffer);
+                    return Some(new_editor_buffer);
+                }
+                _ => return Some(new_editor_buffer),
+            },
+            InputEvent::Keyboard(_) => return Some(new_editor_buffer),
+            InputEvent::Paste(text) => {
+                let first_line = text.lines().next().unwrap_or_default();
+                replace_state.get_focused_field_mut().push_str(first_line);
+            }
+            _ => return None,
+        }
+
+        // The query or the options changed.
+        replace_state.update_regex_cache();
+        replace_state.reset_current_match(editor_buffer);
+        goto_current_match(replace_state, &mut new_editor_buffer, viewport);
+        Some(new_editor_buffer)
+    }
+
+    fn goto_current_match(
+        replace_state: &ReplaceState,
+        editor_buffer: &mut EditorBuffer,
+        viewport: Size,
+    ) {
+        if let (Some(search_match), _) = replace_state.get_current_match(editor_buffer) {
+            editor_buffer.goto_match(&search_match, viewport);
+        }
+    }
+
+    /// Highlight the matches that are in the viewport (the current one stands out), and
+    /// paint the replace bar in the last row of the viewport, eg:
+    /// `Find: foo▒ Replace: bar 2/5 [ignore case]`.
+    pub fn render_replace_mode(
+        editor_engine: &EditorEngine,
+        editor_buffer: &EditorBuffer,
+    ) -> Option<RenderOps> {
+        let replace_state = &editor_engine.replace_state;
+        if !replace_state.is_active {
+            return None;
+        }
+
+        let origin_pos = editor_engine.current_box.style_adjusted_origin_pos;
+        let scroll_offset = editor_buffer.get_scroll_offset();
+        let viewport_width = editor_engine.viewport_width();
+        let viewport_height = editor_engine.viewport_height();
+        let matches = replace_state.get_matches(editor_buffer);
+        let (maybe_current_match, match_count) =
+            replace_state.get_current_match(editor_buffer);
+
+        let mut render_ops = render_ops!();
+
+        for search_match in &matches {
+            // Take the rows that are hidden by folds into account.
+            let Some(row_index) = editor_buffer.get_fold_map().get_display_row_index(
+                scroll_offset.row_index,
+                editor_buffer.get_lines().len(),
+                search_match.row_index,
+            ) else {
+                continue;
+            };
+            // Clip the match to the viewport.
+            let start_col_index =
+                search_match.start_col_index.max(scroll_offset.col_index);
+            let end_col_index = search_match
+                .end_col_index
+                .min(scroll_offset.col_index + viewport_width);
+            if row_index >= viewport_height || start_col_index >= end_col_index {
+                continue;
+            }
+            let Some(line) = editor_buffer
+                .get_lines()
+                .get(ch!(@to_usize search_match.row_index))
+            else {
+                continue;
+            };
+
+            let style = match maybe_current_match == Some(*search_match) {
+                true => get_search_current_match_style(),
+                false => get_search_match_style(),
+            };
+            render_ops.push(RenderOp::MoveCursorPositionRelTo(
+                origin_pos,
+                position! {
+                    col_index: start_col_index - scroll_offset.col_index,
+                    row_index: row_index
+                },
+            ));
+            render_ops.push(RenderOp::ApplyColors(Some(style)));
+            render_ops.push(RenderOp::PaintTextWithAttributes(
+                line.clip_to_width(start_col_index, end_col_index - start_col_index)
+                    .to_string(),
+                Some(style),
+            ));
+            render_ops.push(RenderOp::ResetColor);
+        }
+
+        // Paint the replace bar.
+        let cursor = |field: ReplaceField| match replace_state.focused_field == field {
+            true => DEFAULT_CURSOR_CHAR.to_string(),
+            false => String::new(),
+        };
+        let current_match_number = match maybe_current_match {
+            Some(_) => replace_state.current_match_index.min(match_count - 1) + 1,
+            None => 0,
+        };
+        let mut text = format!(
+            " Find: {}{} Replace: {}{} {current_match_number}/{match_count}",
+            replace_state.query,
+            cursor(ReplaceField::Query),
+            replace_state.replacement,
+            cursor(ReplaceField::Replacement),
+        );
+        if replace_state.options.case_insensitive {
+            text.push_str(" [ignore case]");
+        }
+        if replace_state.options.whole_word {
+            text.push_str(" [whole word]");
+        }
+        #[cfg(feature = "regex")]
+        if replace_state.options.is_regex {
+            text.push_str(" [regex]");
+        }
+        let text = UnicodeString::from(text);
+        let clipped_text = text.clip_to_width(ch!(0), viewport_width);
+        let mut remaining_width =
+            viewport_width - ch!(UnicodeString::str_display_width(clipped_text));
+        let style = get_completion_popup_style();
+
+        render_ops.push(RenderOp::MoveCursorPositionRelTo(
+            origin_pos,
+            position! { col_index: 0, row_index: viewport_height - 1 },
+        ));
+        render_ops.push(RenderOp::ApplyColors(Some(style)));
+        render_ops.push(RenderOp::PaintTextWithAttributes(
+            clipped_text.to_string(),
+            Some(style),
+        ));
+
+        // An invalid regex doesn't match anything, & its error is shown after the text.
+        if let Some(error) = replace_state.get_query_error() {
+            let error_text = UnicodeString::from(format!(" {error}"));
+            let clipped_error_text = error_text.clip_to_width(ch!(0), remaining_width);
+            remaining_width = remaining_width
+                - ch!(UnicodeString::str_display_width(clipped_error_text));
+            render_ops.push(RenderOp::ApplyColors(Some(get_search_error_style())));
+            render_ops.push(RenderOp::PaintTextWithAttributes(
+                clipped_error_text.to_string(),
+                Some(get_search_error_style()),
+            ));
+        }
+
+        render_ops.push(RenderOp::ApplyColors(Some(style)));
+        render_ops.push(RenderOp::PaintTextWithAttributes(
+            SPACER.repeat(ch!(@to_usize remaining_width)),
+            Some(style),
+        ));
+        render_ops.push(RenderOp::ResetColor);
+
+        Some(render_ops)
+    }
+}
+pub use replace_api::*;
@@ -136,6 +136,145 @@ mod test_config_options {
             EditorEngineInternalApi::line_at_caret_to_string(&buffer, &engine);
         assert_eq2!(maybe_line_str.unwrap().string, "abcaba");
     }
+
+    #[test]
+    fn test_whitespace_markers() {
+        let line = UnicodeString::from("a b\t  ");
+        let markers = WhitespaceMarkers::default();
+
+        let get_marked = |mode: WhitespaceMode| {
+            mode.get_marked_segments(&line)
+                .into_iter()
+                .map(|segment| {
+                    (
+                        segment.display_col_offset,
+                        markers.get_marker_char(segment).unwrap(),
+                    )
+                })
+                .collect::<Vec<_>>()
+        };
+
+        // The spaces after the tab start wherever its display width ends.
+        let tab_width = ch!(UnicodeString::str_display_width("\t"));
+
+        assert_eq2!(get_marked(WhitespaceMode::None), vec![]);
+        assert_eq2!(
+            get_marked(WhitespaceMode::Trailing),
+            vec![
+                (ch!(3), '→'),
+                (ch!(3) + tab_width, '·'),
+                (ch!(4) + tab_width, '·')
+            ]
+        );
+        assert_eq2!(
+            get_marked(WhitespaceMode::All),
+            vec![
+                (ch!(1), '·'),
+                (ch!(3), '→'),
+                (ch!(3) + tab_width, '·'),
+                (ch!(4) + tab_width, '·')
+            ]
+        );
+
+        // Markers don't change the buffer content.
+        let mut buffer = EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT));
+        buffer.set_lines(vec!["a b\t  ".into()]);
+        assert_eq2!(buffer.get_line_display_width(ch!(0)), ch!(5) + tab_width);
+    }
+
+    #[test]
+    fn test_scrollbars() {
+        // The thumb fills the track when all the content is visible.
+        assert_eq2!(
+            ScrollbarMode::get_thumb_range(ch!(10), ch!(5), ch!(0)),
+            (ch!(0), ch!(10))
+        );
+        // Otherwise it is proportional, and moves w/ the scroll offset.
+        assert_eq2!(
+            ScrollbarMode::get_thumb_range(ch!(10), ch!(40), ch!(0)),
+            (ch!(0), ch!(2))
+        );
+        assert_eq2!(
+            ScrollbarMode::get_thumb_range(ch!(10), ch!(40), ch!(15)),
+            (ch!(4), ch!(2))
+        );
+        assert_eq2!(
+            ScrollbarMode::get_thumb_range(ch!(10), ch!(40), ch!(30)),
+            (ch!(8), ch!(2))
+        );
+        assert_eq2!(
+            ScrollbarMode::get_thumb_range(ch!(10), ch!(1000), ch!(0)),
+            (ch!(0), ch!(1))
+        );
+
+        // Disabled scrollbars don't take up any space.
+        let engine = mock_real_objects_for_editor::make_editor_engine();
+        assert_eq2!(engine.viewport_width(), ch!(10));
+        assert_eq2!(engine.viewport_height(), ch!(10));
+
+        // Each enabled scrollbar takes up a col or a row.
+        let mut engine = EditorEngine {
+            config_options: EditorEngineConfig {
+                scrollbars: ScrollbarMode::Both,
+                ..Default::default()
+            },
+            ..mock_real_objects_for_editor::make_editor_engine()
+        };
+        assert_eq2!(engine.viewport_width(), ch!(9));
+        assert_eq2!(engine.viewport_height(), ch!(9));
+
+        // The caret scrolls the viewport before it reaches the vertical scrollbar.
+        let mut buffer = EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT));
+        EditorEvent::apply_editor_events(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::InsertString("0123456789".into())],
+            &mock_real_objects_for_editor::make_shared_global_data(None),
+            &mut mock_real_objects_for_editor::make_component_registry(),
+            FlexBoxId::from(0),
+        );
+        assert_eq2!(buffer.get_scroll_offset().col_index, ch!(2));
+        assert_eq2!(buffer.get_caret(CaretKind::Raw).col_index, ch!(8));
+
+        // The caret isn't scrolled under the horizontal scrollbar either.
+        buffer.set_lines((0..20).map(|it| format!("line{it}")).collect());
+        EditorEvent::apply_editor_events(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::GotoLine(20)],
+            &mock_real_objects_for_editor::make_shared_global_data(None),
+            &mut mock_real_objects_for_editor::make_component_registry(),
+            FlexBoxId::from(0),
+        );
+        assert_eq2!(buffer.get_scroll_offset().row_index, ch!(11));
+        assert_eq2!(buffer.get_caret(CaretKind::Raw).row_index, ch!(8));
+    }
+
+    #[test]
+    fn test_config_options_builder() {
+        // Nothing set, same as default.
+        assert_eq2!(
+            EditorEngineConfig::builder().build(),
+            EditorEngineConfig::default()
+        );
+
+        // Only the options that are set differ from the default.
+        let config_options = EditorEngineConfig::builder()
+            .set_tab_width(2)
+            .set_diagnostics_gutter(true)
+            .set_rulers(vec![80])
+            .build();
+        assert_eq2!(
+            config_options,
+            EditorEngineConfig {
+                tab_width: 2,
+                diagnostics_gutter: true,
+                rulers: vec![80],
+                ..Default::default()
+            }
+        );
+        assert_eq2!(config_options.get_indent_unit(), "  ".to_string());
+    }
 }
 
 #[cfg(test)]
@@ -1756,6 +1895,3185 @@ mod test_editor_ops {
             assert_eq2!(result.unwrap().unicode_string_seg.string, "░");
         }
     }
+
+    #[test]
+    fn editor_fold_toggle_and_navigate() {
+        let mut buffer = EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT));
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        let shared_global_data =
+            mock_real_objects_for_editor::make_shared_global_data(None);
+
+        buffer.set_lines(vec![
+            "# a".into(),
+            "b".into(),
+            "c".into(),
+            "# d".into(),
+            "e".into(),
+        ]);
+
+        // Fold the heading section at row 0.
+        // `this` should look like:
+        // R ┌──────────┐
+        // 0 ▸⋯ 3 lines │
+        // 1 │# d       │
+        // 2 │e         │
+        //   └▴─────────┘
+        //   C0123456789
+        EditorEvent::apply_editor_events(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::ToggleFold],
+            &shared_global_data,
+            &mut mock_real_objects_for_editor::make_component_registry(),
+            FlexBoxId::from(0),
+        );
+        assert_eq2!(
+            buffer.get_fold_map().folds,
+            vec![FoldRange::new(ch!(0), ch!(2))]
+        );
+
+        // Moving down skips over the folded rows.
+        EditorEvent::apply_editor_events(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::MoveCaret(CaretDirection::Down)],
+            &shared_global_data,
+            &mut mock_real_objects_for_editor::make_component_registry(),
+            FlexBoxId::from(0),
+        );
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 0, row_index: 3)
+        );
+        assert_eq2!(
+            buffer
+                .get_fold_map()
+                .get_display_row_index(ch!(0), 5, ch!(3)),
+            Some(ch!(1))
+        );
+
+        // Moving up lands on the summary line.
+        EditorEvent::apply_editor_events(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::MoveCaret(CaretDirection::Up)],
+            &shared_global_data,
+            &mut mock_real_objects_for_editor::make_component_registry(),
+            FlexBoxId::from(0),
+        );
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 0, row_index: 0)
+        );
+
+        // Editing below the fold leaves it alone, editing inside it unfolds it.
+        EditorEvent::apply_editor_events(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::MoveCaret(CaretDirection::Down)],
+            &shared_global_data,
+            &mut mock_real_objects_for_editor::make_component_registry(),
+            FlexBoxId::from(0),
+        );
+        EditorEvent::apply_editor_events(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::InsertString("x".into())],
+            &shared_global_data,
+            &mut mock_real_objects_for_editor::make_component_registry(),
+            FlexBoxId::from(0),
+        );
+        assert_eq2!(
+            buffer.get_fold_map().folds,
+            vec![FoldRange::new(ch!(0), ch!(2))]
+        );
+        EditorEvent::apply_editor_events(
+            &mut engine,
+            &mut buffer,
+            vec![
+                EditorEvent::MoveCaret(CaretDirection::Up),
+                EditorEvent::InsertString("y".into()),
+            ],
+            &shared_global_data,
+            &mut mock_real_objects_for_editor::make_component_registry(),
+            FlexBoxId::from(0),
+        );
+        assert!(buffer.get_fold_map().is_empty());
+    }
+
+    #[test]
+    fn editor_goto_line() {
+        let mut buffer = EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT));
+        let mut engine = mock_real_objects_for_editor::make_editor_engine_with_bounds(
+            size!( col_count: 10, row_count: 4 ),
+        );
+        let shared_global_data =
+            mock_real_objects_for_editor::make_shared_global_data(None);
+
+        buffer.set_lines((0..10).map(|it| format!("  line{it}")).collect());
+
+        // Line 6 is centered in the viewport, and the caret is at the first
+        // non-whitespace col.
+        EditorEvent::apply_editor_events(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::GotoLine(6)],
+            &shared_global_data,
+            &mut mock_real_objects_for_editor::make_component_registry(),
+            FlexBoxId::from(0),
+        );
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 2, row_index: 5)
+        );
+        assert_eq2!(
+            buffer.get_caret(CaretKind::Raw),
+            position!(col_index: 2, row_index: 2)
+        );
+
+        // Out of range line numbers are clamped to the last line, w/out scrolling past
+        // the end of the buffer.
+        buffer.goto_line(100, size!( col_count: 10, row_count: 4 ));
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 2, row_index: 9)
+        );
+        assert_eq2!(buffer.get_scroll_offset().row_index, ch!(6));
+
+        // Folds that hide the line are expanded.
+        buffer.fold(FoldRange::new(ch!(0), ch!(3)));
+        buffer.goto_line(2, size!( col_count: 10, row_count: 4 ));
+        assert!(buffer.get_fold_map().is_empty());
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 2, row_index: 1)
+        );
+    }
+
+    #[test]
+    fn editor_set_content_and_insert_at() {
+        let viewport = size!( col_count: 10, row_count: 4 );
+        let mut buffer = EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT));
+
+        // Replacing the content is an edit, and the caret moves to the top.
+        buffer.set_content("abc\r\ndef\nghi");
+        assert_eq2!(buffer.get_as_string(), "abc, def, ghi");
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 0, row_index: 0)
+        );
+        assert!(buffer.is_dirty());
+
+        // The caret can be kept in place, clamped to the new content.
+        buffer.set_content_and_clamp_caret(
+            "abc\nd",
+            position!(col_index: 2, row_index: 2),
+            viewport,
+        );
+        assert_eq2!(buffer.get_as_string(), "abc, d");
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 1, row_index: 1)
+        );
+
+        // Inserting at the caret moves it to the end of the inserted text.
+        buffer.mark_clean();
+        let end_position =
+            buffer.insert_at(position!(col_index: 1, row_index: 1), "ef", viewport);
+        assert_eq2!(end_position, position!(col_index: 3, row_index: 1));
+        assert_eq2!(buffer.get_as_string(), "abc, def");
+        assert_eq2!(buffer.get_caret(CaretKind::ScrollAdjusted), end_position);
+        assert!(buffer.is_dirty());
+
+        // Inserting lines before the caret shifts it down.
+        let end_position =
+            buffer.insert_at(position!(col_index: 1, row_index: 0), "1\n2\n3", viewport);
+        assert_eq2!(end_position, position!(col_index: 1, row_index: 2));
+        assert_eq2!(buffer.get_as_string(), "a1, 2, 3bc, def");
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 3, row_index: 3)
+        );
+
+        // The caret is scrolled into view when it moves out of the viewport.
+        buffer.insert_at(position!(col_index: 3, row_index: 3), "\n\n\n\n", viewport);
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 0, row_index: 7)
+        );
+        assert_eq2!(buffer.get_scroll_offset().row_index, ch!(4));
+        assert_eq2!(
+            buffer.get_caret(CaretKind::Raw),
+            position!(col_index: 0, row_index: 3)
+        );
+    }
+
+    #[test]
+    fn editor_line_endings() {
+        let mut buffer = EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT));
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        let shared_global_data =
+            mock_real_objects_for_editor::make_shared_global_data(None);
+
+        // Mixed line endings pick the majority, & are preserved on save.
+        buffer.load_content("a\r\nb\nc\r\n");
+        assert_eq2!(buffer.get_as_string(), "a, b, c, ");
+        assert_eq2!(buffer.get_line_ending(), LineEnding::CrLf);
+        assert!(buffer.has_mixed_line_endings());
+        assert!(!buffer.is_dirty());
+        assert_eq2!(buffer.get_content_for_save(), "a\r\nb\nc\r\n");
+
+        // Inserted lines get the majority line ending, & removing them restores the
+        // original content.
+        EditorEvent::apply_editor_events(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::End, EditorEvent::InsertNewLine],
+            &shared_global_data,
+            &mut mock_real_objects_for_editor::make_component_registry(),
+            FlexBoxId::from(0),
+        );
+        assert_eq2!(buffer.get_content_for_save(), "a\r\n\r\nb\nc\r\n");
+        EditorEvent::apply_editor_events(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::Backspace],
+            &shared_global_data,
+            &mut mock_real_objects_for_editor::make_component_registry(),
+            FlexBoxId::from(0),
+        );
+        assert_eq2!(buffer.get_content_for_save(), "a\r\nb\nc\r\n");
+
+        // Lines inserted in the middle of the text (eg: a completion) also get the majority
+        // line ending, & the line endings after them stay on the same lines.
+        let mut other_buffer = EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT));
+        other_buffer.load_content("a\nb\nc\r\nd");
+        other_buffer.insert_at(
+            position!(col_index: 1, row_index: 0),
+            "x\ny",
+            size!( col_count: 10, row_count: 10 ),
+        );
+        assert_eq2!(other_buffer.get_content_for_save(), "ax\ny\nb\nc\r\nd");
+
+        // Converting is an edit, & it replaces all the line endings.
+        buffer.mark_clean();
+        EditorEvent::apply_editor_events(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::ConvertLineEndings(LineEnding::Lf)],
+            &shared_global_data,
+            &mut mock_real_objects_for_editor::make_component_registry(),
+            FlexBoxId::from(0),
+        );
+        assert_eq2!(buffer.get_line_ending(), LineEnding::Lf);
+        assert!(!buffer.has_mixed_line_endings());
+        assert!(buffer.is_dirty());
+        assert_eq2!(buffer.get_content_for_save(), "a\nb\nc\n");
+
+        // Converting to the same line ending is a no op.
+        buffer.mark_clean();
+        assert!(!buffer.convert_line_endings(LineEnding::Lf));
+        assert!(!buffer.is_dirty());
+    }
+
+    #[test]
+    fn editor_caret_position_and_stats() {
+        let mut buffer = EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT));
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        let shared_global_data =
+            mock_real_objects_for_editor::make_shared_global_data(None);
+
+        buffer.set_lines(vec!["a😃b".to_string(), "cd".to_string()]);
+        assert_eq2!(buffer.caret_display_position(), (1, 1));
+        assert_eq2!(buffer.line_count(), 2);
+        assert_eq2!(buffer.char_count(), 5);
+        assert!(!buffer.is_dirty());
+
+        // Moving the caret doesn't make the buffer dirty. The wide char takes up 2 cols.
+        EditorEvent::apply_editor_events(
+            &mut engine,
+            &mut buffer,
+            vec![
+                EditorEvent::MoveCaret(CaretDirection::Right),
+                EditorEvent::MoveCaret(CaretDirection::Right),
+            ],
+            &shared_global_data,
+            &mut mock_real_objects_for_editor::make_component_registry(),
+            FlexBoxId::from(0),
+        );
+        assert_eq2!(buffer.caret_display_position(), (1, 4));
+        assert!(!buffer.is_dirty());
+
+        // Editing does.
+        EditorEvent::apply_editor_events(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::InsertNewLine],
+            &shared_global_data,
+            &mut mock_real_objects_for_editor::make_component_registry(),
+            FlexBoxId::from(0),
+        );
+        assert_eq2!(buffer.caret_display_position(), (2, 1));
+        assert_eq2!(buffer.line_count(), 3);
+        assert_eq2!(buffer.char_count(), 5);
+        assert!(buffer.is_dirty());
+
+        buffer.mark_clean();
+        assert!(!buffer.is_dirty());
+    }
+
+    #[test]
+    fn editor_caret_moves_by_display_width() {
+        let mut buffer = EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT));
+        let mut engine = mock_real_objects_for_editor::make_editor_engine_with_bounds(
+            size!( col_count: 40, row_count: 10 ),
+        );
+        let shared_global_data =
+            mock_real_objects_for_editor::make_shared_global_data(None);
+        let apply = |engine: &mut EditorEngine,
+                     buffer: &mut EditorBuffer,
+                     editor_event: EditorEvent| {
+            EditorEvent::apply_editor_events(
+                engine,
+                buffer,
+                vec![editor_event],
+                &shared_global_data,
+                &mut mock_real_objects_for_editor::make_component_registry(),
+                FlexBoxId::from(0),
+            );
+        };
+
+        // ASCII, `e` + combining acute accent, wide CJK, emoji ZWJ sequence, ASCII.
+        let family = "👨‍👩‍👧";
+        let family_width = ch!(UnicodeString::str_display_width(family));
+        buffer.set_lines(vec![format!("ae\u{301}中{family}b")]);
+        assert_eq2!(buffer.char_count(), 5);
+
+        let expected_col_and_str = [
+            (ch!(1), "e\u{301}"),
+            (ch!(2), "中"),
+            (ch!(4), family),
+            (ch!(4) + family_width, "b"),
+        ];
+
+        for (col_index, expected) in expected_col_and_str {
+            apply(
+                &mut engine,
+                &mut buffer,
+                EditorEvent::MoveCaret(CaretDirection::Right),
+            );
+            assert_eq2!(
+                buffer.get_caret(CaretKind::ScrollAdjusted).col_index,
+                col_index
+            );
+            assert::str_is_at_caret(&buffer, &engine, expected);
+        }
+        apply(
+            &mut engine,
+            &mut buffer,
+            EditorEvent::MoveCaret(CaretDirection::Right),
+        );
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted).col_index,
+            ch!(5) + family_width
+        );
+
+        for (col_index, expected) in expected_col_and_str.into_iter().rev() {
+            apply(
+                &mut engine,
+                &mut buffer,
+                EditorEvent::MoveCaret(CaretDirection::Left),
+            );
+            assert_eq2!(
+                buffer.get_caret(CaretKind::ScrollAdjusted).col_index,
+                col_index
+            );
+            assert::str_is_at_caret(&buffer, &engine, expected);
+        }
+        apply(
+            &mut engine,
+            &mut buffer,
+            EditorEvent::MoveCaret(CaretDirection::Left),
+        );
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted).col_index,
+            ch!(0)
+        );
+
+        // Zero width segments are skipped, so the caret never gets stuck on them.
+        buffer.set_lines(vec!["x\u{200B}y\u{200B}".to_string()]);
+        apply(&mut engine, &mut buffer, EditorEvent::End);
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted).col_index,
+            ch!(2)
+        );
+        apply(
+            &mut engine,
+            &mut buffer,
+            EditorEvent::MoveCaret(CaretDirection::Left),
+        );
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted).col_index,
+            ch!(1)
+        );
+        assert::str_is_at_caret(&buffer, &engine, "y");
+        apply(
+            &mut engine,
+            &mut buffer,
+            EditorEvent::MoveCaret(CaretDirection::Left),
+        );
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted).col_index,
+            ch!(0)
+        );
+        assert::str_is_at_caret(&buffer, &engine, "x");
+
+        // Typing a combining mark after a char doesn't move the caret.
+        buffer.set_lines(vec!["e".to_string()]);
+        apply(&mut engine, &mut buffer, EditorEvent::End);
+        apply(
+            &mut engine,
+            &mut buffer,
+            EditorEvent::InsertString("\u{301}".into()),
+        );
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted).col_index,
+            ch!(1)
+        );
+        assert_eq2!(buffer.get_as_string(), "e\u{301}");
+    }
+
+    #[test]
+    fn editor_overwrite_mode() {
+        let mut buffer = EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT));
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        let shared_global_data =
+            mock_real_objects_for_editor::make_shared_global_data(None);
+        let apply = |engine: &mut EditorEngine,
+                     buffer: &mut EditorBuffer,
+                     events: Vec<EditorEvent>| {
+            EditorEvent::apply_editor_events(
+                engine,
+                buffer,
+                events,
+                &shared_global_data,
+                &mut mock_real_objects_for_editor::make_component_registry(),
+                FlexBoxId::from(0),
+            );
+        };
+
+        // The Insert key toggles the mode.
+        let insert_key = InputEvent::Keyboard(keypress!(@special SpecialKey::Insert));
+        assert!(matches!(
+            EditorEvent::try_from(&insert_key),
+            Ok(EditorEvent::ToggleInsertMode)
+        ));
+
+        buffer.set_lines(vec!["abc".to_string()]);
+        apply(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::ToggleInsertMode],
+        );
+        assert_eq2!(engine.insert_mode, InsertMode::Overwrite);
+
+        // Typing replaces the grapheme cluster under the caret, even if the widths differ.
+        apply(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::InsertChar('X'), EditorEvent::InsertChar('中')],
+        );
+        assert_eq2!(buffer.get_as_string(), "X中c");
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 3, row_index: 0)
+        );
+
+        // At the end of the line, typing appends.
+        apply(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::End, EditorEvent::InsertString("de".into())],
+        );
+        assert_eq2!(buffer.get_as_string(), "X中cde");
+
+        // Backspace still deletes to the left of the caret.
+        apply(&mut engine, &mut buffer, vec![EditorEvent::Backspace]);
+        assert_eq2!(buffer.get_as_string(), "X中cd");
+
+        // Back to insert mode.
+        apply(
+            &mut engine,
+            &mut buffer,
+            vec![
+                EditorEvent::ToggleInsertMode,
+                EditorEvent::Home,
+                EditorEvent::InsertChar('Y'),
+            ],
+        );
+        assert_eq2!(engine.insert_mode, InsertMode::Insert);
+        assert_eq2!(buffer.get_as_string(), "YX中cd");
+    }
+
+    #[test]
+    fn editor_auto_close_pairs() {
+        let mut buffer = EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT));
+        let mut engine = EditorEngine {
+            config_options: EditorEngineConfig {
+                auto_close_pairs: true,
+                ..Default::default()
+            },
+            ..mock_real_objects_for_editor::make_editor_engine()
+        };
+        let shared_global_data =
+            mock_real_objects_for_editor::make_shared_global_data(None);
+
+        let mut type_event = |editor_event: EditorEvent| {
+            let editor_events = EditorEngineInternalApi::translate_auto_close_pairs(
+                &buffer,
+                &engine,
+                vec![editor_event],
+            );
+            EditorEvent::apply_editor_events(
+                &mut engine,
+                &mut buffer,
+                editor_events,
+                &shared_global_data,
+                &mut mock_real_objects_for_editor::make_component_registry(),
+                FlexBoxId::from(0),
+            );
+            (buffer.get_as_string(), buffer.caret_display_position())
+        };
+
+        // Opening char inserts the closing one w/ the caret in between.
+        assert_eq2!(
+            type_event(EditorEvent::InsertChar('(')),
+            ("()".into(), (1, 2))
+        );
+        assert_eq2!(
+            type_event(EditorEvent::InsertChar('a')),
+            ("(a)".into(), (1, 3))
+        );
+
+        // Closing char types over the existing one.
+        assert_eq2!(
+            type_event(EditorEvent::InsertChar(')')),
+            ("(a)".into(), (1, 4))
+        );
+
+        // Quotes aren't auto closed after a word.
+        assert_eq2!(
+            type_event(EditorEvent::InsertChar('b')),
+            ("(a)b".into(), (1, 5))
+        );
+        assert_eq2!(
+            type_event(EditorEvent::InsertChar('\'')),
+            ("(a)b'".into(), (1, 6))
+        );
+
+        // Backspace between an empty pair deletes both chars.
+        assert_eq2!(
+            type_event(EditorEvent::InsertChar(' ')),
+            ("(a)b' ".into(), (1, 7))
+        );
+        assert_eq2!(
+            type_event(EditorEvent::InsertChar('[')),
+            ("(a)b' []".into(), (1, 8))
+        );
+        assert_eq2!(
+            type_event(EditorEvent::Backspace),
+            ("(a)b' ".into(), (1, 7))
+        );
+    }
+
+    #[test]
+    fn editor_paste() {
+        let mut buffer = EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT));
+        let mut engine = EditorEngine {
+            config_options: EditorEngineConfig {
+                auto_close_pairs: true,
+                ..Default::default()
+            },
+            ..mock_real_objects_for_editor::make_editor_engine()
+        };
+
+        // The pasted block is a single event, & the pairs in it aren't auto closed.
+        let input_event = InputEvent::Paste("a(\r\nb\"\n".into());
+        let editor_event = EditorEvent::try_from(&input_event).unwrap();
+        assert!(matches!(&editor_event, EditorEvent::Paste(_)));
+
+        EditorEvent::apply_editor_event(
+            &mut engine,
+            &mut buffer,
+            editor_event,
+            &mock_real_objects_for_editor::make_shared_global_data(None),
+            &mut mock_real_objects_for_editor::make_component_registry(),
+            FlexBoxId::from(0),
+        );
+        assert_eq2!(buffer.get_as_string_with_newlines(), "a(\nb\"\n");
+        assert_eq2!(buffer.caret_display_position(), (3, 1));
+        assert!(buffer.is_dirty());
+    }
+
+    #[test]
+    fn editor_duplicate_and_move_lines() {
+        let mut buffer = EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT));
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        let shared_global_data =
+            mock_real_objects_for_editor::make_shared_global_data(None);
+
+        buffer.set_lines(vec!["a0".into(), "b1".into(), "c2".into()]);
+        let mut apply = |editor_events: Vec<EditorEvent>| {
+            EditorEvent::apply_editor_events(
+                &mut engine,
+                &mut buffer,
+                editor_events,
+                &shared_global_data,
+                &mut mock_real_objects_for_editor::make_component_registry(),
+                FlexBoxId::from(0),
+            );
+            (buffer.get_as_string(), buffer.caret_display_position())
+        };
+
+        // The caret moves to the copy & keeps its col.
+        assert_eq2!(
+            apply(vec![
+                EditorEvent::MoveCaret(CaretDirection::Down),
+                EditorEvent::MoveCaret(CaretDirection::Right),
+                EditorEvent::DuplicateLine,
+            ]),
+            ("a0, b1, b1, c2".into(), (3, 2))
+        );
+
+        // The caret moves w/ the line.
+        assert_eq2!(
+            apply(vec![EditorEvent::MoveLine(CaretDirection::Down)]),
+            ("a0, b1, c2, b1".into(), (4, 2))
+        );
+
+        // Moving past the end of the buffer is a no-op.
+        assert_eq2!(
+            apply(vec![EditorEvent::MoveLine(CaretDirection::Down)]),
+            ("a0, b1, c2, b1".into(), (4, 2))
+        );
+
+        // All the selected lines are moved.
+        assert_eq2!(
+            apply(vec![
+                EditorEvent::Select(SelectionScope::OneLineUp),
+                EditorEvent::MoveLine(CaretDirection::Up),
+            ]),
+            ("a0, c2, b1, b1".into(), (2, 2))
+        );
+        assert!(buffer.is_dirty());
+    }
+
+    #[test]
+    fn editor_toggle_line_comment() {
+        let mut buffer = EditorBuffer::new_empty(Some("rs"));
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        let shared_global_data =
+            mock_real_objects_for_editor::make_shared_global_data(None);
+
+        buffer.set_lines(vec![
+            "fn a() {".into(),
+            "    b();".into(),
+            "".into(),
+            "}".into(),
+        ]);
+
+        // The prefix is inserted after the indentation, and blank lines are skipped.
+        assert!(buffer.toggle_line_comment(0..=3, "//"));
+        assert_eq2!(buffer.get_as_string(), "// fn a() {,     // b();, , // }");
+
+        // All the lines are commented out, so toggling again removes the prefix.
+        assert!(buffer.toggle_line_comment(0..=3, "//"));
+        assert_eq2!(buffer.get_as_string(), "fn a() {,     b();, , }");
+
+        // The prefix is looked up from the file extension, and the caret stays on the
+        // same char.
+        let mut editor_events = vec![EditorEvent::MoveCaret(CaretDirection::Down)];
+        editor_events
+            .extend((0..5).map(|_| EditorEvent::MoveCaret(CaretDirection::Right)));
+        editor_events.push(EditorEvent::ToggleLineComment);
+        EditorEvent::apply_editor_events(
+            &mut engine,
+            &mut buffer,
+            editor_events,
+            &shared_global_data,
+            &mut mock_real_objects_for_editor::make_component_registry(),
+            FlexBoxId::from(0),
+        );
+        assert_eq2!(buffer.get_as_string(), "fn a() {,     // b();, , }");
+        assert_eq2!(buffer.caret_display_position(), (2, 9));
+    }
+
+    #[test]
+    fn editor_indent_and_dedent_lines() {
+        let mut buffer = EditorBuffer::new_empty(Some("rs"));
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        let shared_global_data =
+            mock_real_objects_for_editor::make_shared_global_data(None);
+
+        buffer.set_lines(vec!["a".into(), "".into(), "  b".into(), "\tc".into()]);
+
+        // Blank lines are skipped.
+        assert!(buffer.indent_lines(0..=2, "    "));
+        assert_eq2!(buffer.get_as_string(), "    a, ,       b, \tc");
+
+        // Up to 4 cols of leading whitespace is removed, & a tab counts as 4 cols.
+        assert!(buffer.dedent_lines(0..=3, 4));
+        assert_eq2!(buffer.get_as_string(), "a, ,   b, c");
+        assert!(buffer.dedent_lines(0..=3, 4));
+        assert_eq2!(buffer.get_as_string(), "a, , b, c");
+
+        // There is no leading whitespace left, so the text isn't touched.
+        assert!(!buffer.dedent_lines(0..=3, 4));
+
+        // The selected lines are indented by the tab width, as a single change, & the
+        // caret and selection stay on the same chars.
+        let selection = |start_col_index: usize, end_col_index: usize| SelectionRange {
+            start_display_col_index: ch!(start_col_index),
+            end_display_col_index: ch!(end_col_index),
+        };
+        let apply = |engine: &mut EditorEngine,
+                     buffer: &mut EditorBuffer,
+                     editor_events: Vec<EditorEvent>| {
+            EditorEvent::apply_editor_events(
+                engine,
+                buffer,
+                editor_events,
+                &shared_global_data,
+                &mut mock_real_objects_for_editor::make_component_registry(),
+                FlexBoxId::from(0),
+            );
+        };
+        engine.config_options.tab_width = 2;
+        apply(
+            &mut engine,
+            &mut buffer,
+            (0..3)
+                .map(|_| EditorEvent::MoveCaret(CaretDirection::Down))
+                .collect(),
+        );
+        {
+            let (_, caret, _, selection_map) = buffer.get_mut();
+            *caret = position!(col_index: 1, row_index: 3);
+            for row_index in [2, 3] {
+                selection_map.insert(
+                    ch!(row_index),
+                    selection(0, 1),
+                    CaretMovementDirection::Down,
+                );
+            }
+        }
+        apply(&mut engine, &mut buffer, vec![EditorEvent::IndentLines]);
+        assert_eq2!(buffer.get_as_string(), "a, ,   b,   c");
+        assert_eq2!(buffer.caret_display_position(), (4, 4));
+        assert_eq2!(
+            buffer.get_selection_map().get(ch!(2)).copied(),
+            Some(selection(0, 3))
+        );
+
+        apply(&mut engine, &mut buffer, vec![EditorEvent::DedentLines]);
+        assert_eq2!(buffer.get_as_string(), "a, , b, c");
+        assert_eq2!(buffer.caret_display_position(), (4, 2));
+        assert_eq2!(
+            buffer.get_selection_map().get(ch!(3)).copied(),
+            Some(selection(0, 1))
+        );
+
+        // Hard tabs.
+        engine.config_options.use_soft_tabs = false;
+        apply(&mut engine, &mut buffer, vec![EditorEvent::IndentLines]);
+        assert_eq2!(buffer.get_as_string(), "a, , \tb, \tc");
+        assert!(buffer.is_dirty());
+    }
+}
+
+#[cfg(test)]
+mod test_multiple_carets {
+    use r3bl_rs_utils_core::*;
+
+    use super::*;
+    use crate::*;
+
+    fn apply(
+        engine: &mut EditorEngine,
+        buffer: &mut EditorBuffer,
+        events: Vec<EditorEvent>,
+    ) {
+        EditorEvent::apply_editor_events(
+            engine,
+            buffer,
+            events,
+            &mock_real_objects_for_editor::make_shared_global_data(None),
+            &mut mock_real_objects_for_editor::make_component_registry(),
+            FlexBoxId::from(0),
+        );
+    }
+
+    #[test]
+    fn editor_multiple_carets() {
+        let mut buffer = EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT));
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        buffer.set_lines(vec!["abc".into(), "abc".into(), "x".into()]);
+
+        // Add carets below, the last one is clipped to the width of its line.
+        apply(
+            &mut engine,
+            &mut buffer,
+            vec![
+                EditorEvent::End,
+                EditorEvent::AddCaretBelow,
+                EditorEvent::AddCaretBelow,
+                EditorEvent::AddCaretBelow,
+            ],
+        );
+        assert_eq2!(
+            buffer.get_secondary_carets(),
+            &vec![
+                position!(col_index: 3, row_index: 1),
+                position!(col_index: 1, row_index: 2)
+            ]
+        );
+
+        // Insert at all the carets.
+        apply(&mut engine, &mut buffer, vec![EditorEvent::InsertChar('!')]);
+        assert_eq2!(buffer.get_as_string(), "abc!, abc!, x!");
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 4, row_index: 0)
+        );
+        assert_eq2!(
+            buffer.get_secondary_carets(),
+            &vec![
+                position!(col_index: 4, row_index: 1),
+                position!(col_index: 2, row_index: 2)
+            ]
+        );
+
+        // Backspace at the start of a line doesn't join lines.
+        apply(&mut engine, &mut buffer, vec![EditorEvent::Backspace; 3]);
+        assert_eq2!(buffer.get_as_string(), "a, a, ");
+        assert_eq2!(buffer.len(), ch!(3));
+
+        // Secondary carets move along w/ the primary caret.
+        apply(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::MoveCaret(CaretDirection::Left)],
+        );
+        assert_eq2!(
+            buffer.get_secondary_carets(),
+            &vec![
+                position!(col_index: 0, row_index: 1),
+                position!(col_index: 0, row_index: 2)
+            ]
+        );
+
+        // Collapse back to one caret.
+        apply(&mut engine, &mut buffer, vec![EditorEvent::CollapseCarets]);
+        assert!(!buffer.has_secondary_carets());
+
+        // Carets in the same line are processed right to left.
+        buffer.set_secondary_carets(vec![position!(col_index: 1, row_index: 0)]);
+        apply(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::InsertString("xy".into())],
+        );
+        assert_eq2!(buffer.get_lines()[0].string, "xyaxy");
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 2, row_index: 0)
+        );
+        assert_eq2!(
+            buffer.get_secondary_carets(),
+            &vec![position!(col_index: 5, row_index: 0)]
+        );
+
+        // Other edits collapse the carets.
+        apply(&mut engine, &mut buffer, vec![EditorEvent::InsertNewLine]);
+        assert!(!buffer.has_secondary_carets());
+    }
+}
+
+#[cfg(test)]
+mod test_scroll_off {
+    use r3bl_rs_utils_core::*;
+
+    use super::*;
+    use crate::*;
+
+    fn apply_editor_event_n_times(
+        engine: &mut EditorEngine,
+        buffer: &mut EditorBuffer,
+        editor_event: EditorEvent,
+        count: usize,
+    ) {
+        EditorEvent::apply_editor_events(
+            engine,
+            buffer,
+            vec![editor_event; count],
+            &mock_real_objects_for_editor::make_shared_global_data(None),
+            &mut mock_real_objects_for_editor::make_component_registry(),
+            FlexBoxId::from(0),
+        );
+    }
+
+    #[test]
+    fn editor_scroll_off_vertical() {
+        let mut buffer = EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT));
+        let mut engine = EditorEngine {
+            config_options: EditorEngineConfig {
+                scroll_off: 1,
+                ..Default::default()
+            },
+            ..mock_real_objects_for_editor::make_editor_engine_with_bounds(
+                size!( col_count: 10, row_count: 5 ),
+            )
+        };
+        buffer.set_lines((0..20).map(|it| format!("line{it}")).collect());
+
+        // Scroll down before the caret reaches the bottom of the viewport.
+        let down = EditorEvent::MoveCaret(CaretDirection::Down);
+        apply_editor_event_n_times(&mut engine, &mut buffer, down, 4);
+        assert_eq2!(
+            buffer.get_caret(CaretKind::Raw),
+            position!(col_index: 0, row_index: 3)
+        );
+        assert_eq2!(
+            buffer.get_scroll_offset(),
+            position!(col_index: 0, row_index: 1)
+        );
+
+        // Scroll up before the caret reaches the top of the viewport.
+        let up = EditorEvent::MoveCaret(CaretDirection::Up);
+        apply_editor_event_n_times(&mut engine, &mut buffer, up, 3);
+        assert_eq2!(
+            buffer.get_caret(CaretKind::Raw),
+            position!(col_index: 0, row_index: 1)
+        );
+        assert_eq2!(
+            buffer.get_scroll_offset(),
+            position!(col_index: 0, row_index: 0)
+        );
+
+        // The margin can't be kept at the top of the buffer.
+        let up = EditorEvent::MoveCaret(CaretDirection::Up);
+        apply_editor_event_n_times(&mut engine, &mut buffer, up, 1);
+        assert_eq2!(
+            buffer.get_caret(CaretKind::Raw),
+            position!(col_index: 0, row_index: 0)
+        );
+
+        // A margin that doesn't fit in the viewport is ignored.
+        engine.config_options.scroll_off = 10;
+        engine.current_box.style_adjusted_bounds_size =
+            size!( col_count: 10, row_count: 2 );
+        let down = EditorEvent::MoveCaret(CaretDirection::Down);
+        apply_editor_event_n_times(&mut engine, &mut buffer, down, 1);
+        assert_eq2!(
+            buffer.get_caret(CaretKind::Raw),
+            position!(col_index: 0, row_index: 1)
+        );
+        assert_eq2!(
+            buffer.get_scroll_offset(),
+            position!(col_index: 0, row_index: 0)
+        );
+    }
+
+    #[test]
+    fn editor_scroll_strategy() {
+        let make_engine =
+            |scroll_strategy: ScrollStrategy, scroll_off: usize| EditorEngine {
+                config_options: EditorEngineConfig {
+                    scroll_strategy,
+                    scroll_off,
+                    ..Default::default()
+                },
+                ..mock_real_objects_for_editor::make_editor_engine_with_bounds(
+                    size!( col_count: 10, row_count: 5 ),
+                )
+            };
+        let down = EditorEvent::MoveCaret(CaretDirection::Down);
+
+        // Center the caret when it moves out of the viewport. W/out a margin the content
+        // first scrolls on the 6th move, since the caret can move down to the row just
+        // past the viewport (see `inc_caret_row`).
+        let mut buffer = EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT));
+        buffer.set_lines((0..20).map(|it| format!("line{it}")).collect());
+        let mut engine = make_engine(ScrollStrategy::Center, 0);
+        apply_editor_event_n_times(&mut engine, &mut buffer, down.clone(), 5);
+        assert_eq2!(
+            buffer.get_scroll_offset(),
+            position!(col_index: 0, row_index: 0)
+        );
+        apply_editor_event_n_times(&mut engine, &mut buffer, down.clone(), 1);
+        assert_eq2!(
+            buffer.get_caret(CaretKind::Raw),
+            position!(col_index: 0, row_index: 2)
+        );
+        assert_eq2!(
+            buffer.get_scroll_offset(),
+            position!(col_index: 0, row_index: 4)
+        );
+
+        // Moving inside the viewport doesn't scroll.
+        apply_editor_event_n_times(&mut engine, &mut buffer, down.clone(), 1);
+        assert_eq2!(
+            buffer.get_scroll_offset(),
+            position!(col_index: 0, row_index: 4)
+        );
+
+        // It doesn't scroll past the end of the buffer.
+        apply_editor_event_n_times(&mut engine, &mut buffer, down.clone(), 30);
+        assert_eq2!(buffer.get_caret(CaretKind::Raw).row_index, ch!(4));
+        assert_eq2!(
+            buffer.get_scroll_offset(),
+            position!(col_index: 0, row_index: 15)
+        );
+
+        // Move the caret to the top when it moves into the margin, but keep the margin.
+        let mut buffer = EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT));
+        buffer.set_lines((0..20).map(|it| format!("line{it}")).collect());
+        let mut engine = make_engine(ScrollStrategy::Top, 1);
+        apply_editor_event_n_times(&mut engine, &mut buffer, down, 4);
+        assert_eq2!(
+            buffer.get_caret(CaretKind::Raw),
+            position!(col_index: 0, row_index: 1)
+        );
+        assert_eq2!(
+            buffer.get_scroll_offset(),
+            position!(col_index: 0, row_index: 3)
+        );
+
+        // Recenter once.
+        apply_editor_event_n_times(
+            &mut engine,
+            &mut buffer,
+            EditorEvent::RecenterCaret,
+            1,
+        );
+        assert_eq2!(
+            buffer.get_caret(CaretKind::Raw),
+            position!(col_index: 0, row_index: 2)
+        );
+        assert_eq2!(
+            buffer.get_scroll_offset(),
+            position!(col_index: 0, row_index: 2)
+        );
+    }
+
+    #[test]
+    fn editor_scroll_off_horizontal() {
+        let mut buffer = EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT));
+        let mut engine = EditorEngine {
+            config_options: EditorEngineConfig {
+                side_scroll_off: 2,
+                ..Default::default()
+            },
+            ..mock_real_objects_for_editor::make_editor_engine_with_bounds(
+                size!( col_count: 10, row_count: 5 ),
+            )
+        };
+        buffer.set_lines(vec!["abcdefghij".repeat(3)]);
+
+        // The margin isn't kept past the end of the line.
+        apply_editor_event_n_times(&mut engine, &mut buffer, EditorEvent::End, 1);
+        assert_eq2!(
+            buffer.get_caret(CaretKind::Raw),
+            position!(col_index: 9, row_index: 0)
+        );
+        assert_eq2!(
+            buffer.get_scroll_offset(),
+            position!(col_index: 21, row_index: 0)
+        );
+
+        // Scroll left before the caret reaches the left edge of the viewport.
+        let left = EditorEvent::MoveCaret(CaretDirection::Left);
+        apply_editor_event_n_times(&mut engine, &mut buffer, left, 8);
+        assert_eq2!(
+            buffer.get_caret(CaretKind::Raw),
+            position!(col_index: 2, row_index: 0)
+        );
+        assert_eq2!(
+            buffer.get_scroll_offset(),
+            position!(col_index: 20, row_index: 0)
+        );
+
+        // Scroll right before the caret reaches the right edge of the viewport.
+        apply_editor_event_n_times(&mut engine, &mut buffer, EditorEvent::Home, 1);
+        let right = EditorEvent::MoveCaret(CaretDirection::Right);
+        apply_editor_event_n_times(&mut engine, &mut buffer, right, 8);
+        assert_eq2!(
+            buffer.get_caret(CaretKind::Raw),
+            position!(col_index: 7, row_index: 0)
+        );
+        assert_eq2!(
+            buffer.get_scroll_offset(),
+            position!(col_index: 1, row_index: 0)
+        );
+    }
+
+    #[test]
+    fn editor_set_scroll_offset() {
+        let mut buffer = EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT));
+        let mut engine = EditorEngine {
+            config_options: EditorEngineConfig {
+                scroll_off: 1,
+                ..Default::default()
+            },
+            ..mock_real_objects_for_editor::make_editor_engine_with_bounds(
+                size!( col_count: 10, row_count: 5 ),
+            )
+        };
+        let viewport = size!( col_count: 10, row_count: 5 );
+        buffer.set_lines((0..20).map(|it| format!("line{it}")).collect());
+
+        // The caret (on the 1st row) is moved to the top of the viewport.
+        buffer.set_scroll_offset(ch!(10), ch!(0), viewport, 0);
+        assert_eq2!(
+            buffer.get_scroll_offset(),
+            position!(col_index: 0, row_index: 10)
+        );
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 0, row_index: 10)
+        );
+
+        // The caret stays on the same text when it is still in the viewport.
+        let down = EditorEvent::MoveCaret(CaretDirection::Down);
+        apply_editor_event_n_times(&mut engine, &mut buffer, down, 2);
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 0, row_index: 12)
+        );
+        buffer.set_scroll_offset(ch!(9), ch!(0), viewport, 0);
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 0, row_index: 12)
+        );
+
+        // The offset is clamped to the end of the buffer.
+        buffer.set_scroll_offset(ch!(100), ch!(100), viewport, 0);
+        assert_eq2!(
+            buffer.get_scroll_offset(),
+            position!(col_index: 0, row_index: 15)
+        );
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 0, row_index: 15)
+        );
+    }
+
+    #[test]
+    fn editor_overscroll() {
+        let mut buffer = EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT));
+        let viewport = size!( col_count: 10, row_count: 5 );
+        buffer.set_lines((0..20).map(|it| format!("line{it}")).collect());
+
+        // The overscroll is capped so that the last line stays in the viewport.
+        assert_eq2!(buffer.get_max_scroll_row_index(ch!(5), 0), ch!(15));
+        assert_eq2!(buffer.get_max_scroll_row_index(ch!(5), 2), ch!(17));
+        assert_eq2!(buffer.get_max_scroll_row_index(ch!(5), usize::MAX), ch!(19));
+
+        // The offset can go past the end of the buffer, but the caret stops at the last
+        // line.
+        buffer.set_scroll_offset(ch!(100), ch!(0), viewport, 2);
+        assert_eq2!(buffer.get_scroll_offset().row_index, ch!(17));
+        buffer.set_scroll_offset(ch!(100), ch!(0), viewport, usize::MAX);
+        assert_eq2!(buffer.get_scroll_offset().row_index, ch!(19));
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 0, row_index: 19)
+        );
+        assert_eq2!(buffer.get_caret(CaretKind::Raw).row_index, ch!(0));
+
+        // The last line can be recentered.
+        buffer.recenter_caret(viewport, 0);
+        assert_eq2!(buffer.get_scroll_offset().row_index, ch!(15));
+        buffer.recenter_caret(viewport, 2);
+        assert_eq2!(buffer.get_scroll_offset().row_index, ch!(17));
+        assert_eq2!(buffer.get_caret(CaretKind::Raw).row_index, ch!(2));
+
+        // The scrollbar thumb reaches the bottom of the track at the max offset.
+        assert_eq2!(
+            ScrollbarMode::get_thumb_range(ch!(5), ch!(17) + ch!(5), ch!(17)),
+            (ch!(4), ch!(1))
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_key_bindings {
+    use std::time::{Duration, Instant};
+
+    use r3bl_rs_utils_core::*;
+
+    use super::*;
+    use crate::*;
+
+    /// Simulate what [EditorEngineApi::apply_event] does w/ the key bindings.
+    fn apply_input_events(
+        engine: &mut EditorEngine,
+        buffer: &mut EditorBuffer,
+        input_events: Vec<InputEvent>,
+    ) {
+        apply_input_events_at(engine, buffer, input_events, Instant::now());
+    }
+
+    /// All the `input_events` happen at `now`.
+    fn apply_input_events_at(
+        engine: &mut EditorEngine,
+        buffer: &mut EditorBuffer,
+        input_events: Vec<InputEvent>,
+        now: Instant,
+    ) {
+        for input_event in input_events {
+            let editor_events =
+                match KeyBindingsApi::translate_at(engine, &input_event, now) {
+                    KeyBindingResult::Handled(it) => it,
+                    KeyBindingResult::NotHandled => engine
+                        .config_options
+                        .key_map
+                        .translate(&input_event)
+                        .into_iter()
+                        .collect(),
+                };
+            EditorEvent::apply_editor_events(
+                engine,
+                buffer,
+                editor_events,
+                &mock_real_objects_for_editor::make_shared_global_data(None),
+                &mut mock_real_objects_for_editor::make_component_registry(),
+                FlexBoxId::from(0),
+            );
+        }
+    }
+
+    fn keys(it: &str) -> Vec<InputEvent> {
+        it.chars()
+            .map(|it| InputEvent::Keyboard(keypress!(@char it)))
+            .collect()
+    }
+
+    #[test]
+    fn test_custom_key_map() {
+        let ctrl_d = keypress!(@char ModifierKeysMask::CTRL, 'd');
+        let home = keypress!(@special SpecialKey::Home);
+
+        // Default bindings.
+        let key_map = KeyMap::default();
+        assert!(matches!(
+            key_map.get(&ctrl_d),
+            Some(EditorEvent::DuplicateLine)
+        ));
+        assert!(matches!(key_map.get(&home), Some(EditorEvent::Home)));
+        assert!(matches!(
+            key_map.translate(&InputEvent::Keyboard(keypress!(@char 'a'))),
+            Some(EditorEvent::InsertChar('a'))
+        ));
+
+        // Rebind Ctrl+D & unbind Home.
+        let key_map = key_map.bind(ctrl_d, EditorEvent::DeleteLine).unbind(home);
+        assert!(matches!(
+            key_map.get(&ctrl_d),
+            Some(EditorEvent::DeleteLine)
+        ));
+        assert!(key_map.translate(&InputEvent::Keyboard(home)).is_none());
+
+        let mut buffer = EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT));
+        let mut engine = EditorEngine {
+            config_options: EditorEngineConfig {
+                key_map,
+                ..Default::default()
+            },
+            ..mock_real_objects_for_editor::make_editor_engine()
+        };
+        buffer.set_lines(vec!["abc".into(), "def".into()]);
+
+        apply_input_events(
+            &mut engine,
+            &mut buffer,
+            vec![
+                InputEvent::Keyboard(keypress!(@special SpecialKey::End)),
+                InputEvent::Keyboard(home),
+                InputEvent::Keyboard(ctrl_d),
+            ],
+        );
+        assert_eq2!(buffer.get_as_string_with_newlines(), "def");
+    }
+
+    #[test]
+    fn test_vim_modes_motions_and_operators() {
+        let mut buffer = EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT));
+        let mut engine = EditorEngine {
+            config_options: EditorEngineConfig {
+                key_binding_mode: KeyBindingMode::Vim,
+                ..Default::default()
+            },
+            ..mock_real_objects_for_editor::make_editor_engine_with_bounds(
+                size!( col_count: 20, row_count: 10 ),
+            )
+        };
+        buffer.set_lines(vec!["foo bar baz".into(), "second line".into()]);
+        assert_eq2!(KeyBindingsApi::get_vim_mode(&engine), Some(VimMode::Normal));
+
+        // Typing text is ignored in normal mode.
+        apply_input_events(&mut engine, &mut buffer, keys("q"));
+        assert_eq2!(buffer.get_lines()[0].string, "foo bar baz");
+
+        // Insert & then go back to normal mode.
+        apply_input_events(&mut engine, &mut buffer, keys("iX"));
+        assert_eq2!(KeyBindingsApi::get_vim_mode(&engine), Some(VimMode::Insert));
+        apply_input_events(
+            &mut engine,
+            &mut buffer,
+            vec![InputEvent::Keyboard(keypress!(@special SpecialKey::Esc))],
+        );
+        assert_eq2!(KeyBindingsApi::get_vim_mode(&engine), Some(VimMode::Normal));
+        assert_eq2!(buffer.get_lines()[0].string, "Xfoo bar baz");
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 0, row_index: 0)
+        );
+
+        // Word motion & operators.
+        apply_input_events(&mut engine, &mut buffer, keys("w"));
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 5, row_index: 0)
+        );
+        apply_input_events(&mut engine, &mut buffer, keys("d"));
+        assert_eq2!(engine.key_binding_state.pending_keys.len(), 1);
+        apply_input_events(&mut engine, &mut buffer, keys("w"));
+        assert_eq2!(buffer.get_lines()[0].string, "Xfoo baz");
+        apply_input_events(&mut engine, &mut buffer, keys("x"));
+        assert_eq2!(buffer.get_lines()[0].string, "Xfoo az");
+
+        // Delete the 2nd line.
+        apply_input_events(&mut engine, &mut buffer, keys("jdd"));
+        assert_eq2!(buffer.get_lines().len(), 1);
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 0, row_index: 0)
+        );
+
+        // Visual mode selection & delete.
+        apply_input_events(&mut engine, &mut buffer, keys("vll"));
+        assert_eq2!(KeyBindingsApi::get_vim_mode(&engine), Some(VimMode::Visual));
+        apply_input_events(&mut engine, &mut buffer, keys("d"));
+        assert_eq2!(KeyBindingsApi::get_vim_mode(&engine), Some(VimMode::Normal));
+        assert_eq2!(buffer.get_lines()[0].string, "oo az");
+        assert!(!buffer.has_selection());
+    }
+
+    #[test]
+    fn test_vim_marks() {
+        let mut buffer = EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT));
+        let mut engine = EditorEngine {
+            config_options: EditorEngineConfig {
+                key_binding_mode: KeyBindingMode::Vim,
+                ..Default::default()
+            },
+            ..mock_real_objects_for_editor::make_editor_engine_with_bounds(
+                size!( col_count: 20, row_count: 10 ),
+            )
+        };
+        buffer.set_lines((0..6).map(|it| format!("a{it}")).collect());
+
+        // Set a mark & jump back to it.
+        apply_input_events(&mut engine, &mut buffer, keys("jjlma"));
+        assert_eq2!(
+            buffer.get_marks().get(&'a'),
+            Some(&position!(col_index: 1, row_index: 2))
+        );
+        apply_input_events(&mut engine, &mut buffer, keys("kk'a"));
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 1, row_index: 2)
+        );
+
+        // Deleting a line above the mark shifts it up.
+        apply_input_events(&mut engine, &mut buffer, keys("kkdd"));
+        assert_eq2!(buffer.get_lines()[1].string, "a2");
+        assert_eq2!(
+            buffer.get_marks().get(&'a'),
+            Some(&position!(col_index: 1, row_index: 1))
+        );
+
+        // Inserting a line above the mark shifts it down.
+        apply_input_events(&mut engine, &mut buffer, keys("o"));
+        apply_input_events(
+            &mut engine,
+            &mut buffer,
+            vec![InputEvent::Keyboard(keypress!(@special SpecialKey::Esc))],
+        );
+        apply_input_events(&mut engine, &mut buffer, keys("'a"));
+        assert_eq2!(buffer.get_lines()[2].string, "a2");
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 1, row_index: 2)
+        );
+
+        // Deleting the line w/ the mark drops it.
+        apply_input_events(&mut engine, &mut buffer, keys("dd"));
+        assert!(buffer.get_marks().is_empty());
+    }
+
+    #[test]
+    fn test_emacs_kill_yank_and_mark() {
+        let ctrl =
+            |it: char| InputEvent::Keyboard(keypress!(@char ModifierKeysMask::CTRL, it));
+        let alt =
+            |it: char| InputEvent::Keyboard(keypress!(@char ModifierKeysMask::ALT, it));
+
+        let mut buffer = EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT));
+        let mut engine = EditorEngine {
+            config_options: EditorEngineConfig {
+                key_binding_mode: KeyBindingMode::Emacs,
+                ..Default::default()
+            },
+            ..mock_real_objects_for_editor::make_editor_engine_with_bounds(
+                size!( col_count: 20, row_count: 10 ),
+            )
+        };
+        buffer.set_lines(vec!["foo bar baz".into(), "second".into()]);
+        assert_eq2!(KeyBindingsApi::get_vim_mode(&engine), None);
+
+        // Kill to end of line & yank it back.
+        apply_input_events(&mut engine, &mut buffer, vec![ctrl('k')]);
+        assert_eq2!(buffer.get_lines()[0].string, "");
+        assert_eq2!(
+            engine.key_binding_state.kill_ring.get_top(),
+            Some(&"foo bar baz".to_string())
+        );
+        apply_input_events(&mut engine, &mut buffer, vec![ctrl('y')]);
+        assert_eq2!(buffer.get_lines()[0].string, "foo bar baz");
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 11, row_index: 0)
+        );
+
+        // Word motion.
+        apply_input_events(&mut engine, &mut buffer, vec![ctrl('a'), alt('f')]);
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 4, row_index: 0)
+        );
+
+        // Set the mark, extend the region & kill it.
+        apply_input_events(&mut engine, &mut buffer, vec![ctrl(' ')]);
+        assert_eq2!(engine.key_binding_state.emacs_mode, EmacsMode::MarkSet);
+        apply_input_events(
+            &mut engine,
+            &mut buffer,
+            vec![ctrl('f'), ctrl('f'), ctrl('f')],
+        );
+        assert!(buffer.has_selection());
+        apply_input_events(&mut engine, &mut buffer, vec![ctrl('w')]);
+        assert_eq2!(engine.key_binding_state.emacs_mode, EmacsMode::Normal);
+        assert_eq2!(buffer.get_lines()[0].string, "foo  baz");
+        assert!(!buffer.has_selection());
+
+        // Yank the killed region at the end of the line.
+        apply_input_events(&mut engine, &mut buffer, vec![ctrl('e'), ctrl('y')]);
+        assert_eq2!(buffer.get_lines()[0].string, "foo  bazbar");
+    }
+
+    #[test]
+    fn test_chord_timeout() {
+        let char = |it: char| keypress!(@char it);
+        let start = Instant::now();
+
+        let mut buffer = EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT));
+        let mut engine = EditorEngine {
+            config_options: EditorEngineConfig {
+                key_map: KeyMap::default()
+                    .bind_chord(vec![char('j'), char('k')], EditorEvent::Home),
+                chord_timeout: Some(Duration::from_millis(500)),
+                ..Default::default()
+            },
+            ..mock_real_objects_for_editor::make_editor_engine()
+        };
+
+        // The whole chord.
+        apply_input_events_at(&mut engine, &mut buffer, keys("abjk"), start);
+        assert_eq2!(buffer.get_lines()[0].string, "ab");
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 0, row_index: 0)
+        );
+
+        // A partial chord + an unrelated key: the `j` is typed before the `x`.
+        apply_input_events_at(&mut engine, &mut buffer, keys("jx"), start);
+        assert_eq2!(buffer.get_lines()[0].string, "jxab");
+        assert!(engine.key_binding_state.pending_keys.is_empty());
+
+        // A partial chord + the start of another one.
+        apply_input_events_at(&mut engine, &mut buffer, keys("jj"), start);
+        assert_eq2!(buffer.get_lines()[0].string, "jxjab");
+        assert_eq2!(engine.key_binding_state.pending_keys, vec![char('j')]);
+        apply_input_events_at(&mut engine, &mut buffer, keys("k"), start);
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 0, row_index: 0)
+        );
+
+        // A lone `j` is flushed once the timeout has passed.
+        apply_input_events_at(&mut engine, &mut buffer, keys("j"), start);
+        assert!(KeyBindingsApi::flush_timed_out_pending_keys(
+            &mut engine,
+            start + Duration::from_millis(100)
+        )
+        .is_empty());
+        assert!(matches!(
+            KeyBindingsApi::flush_timed_out_pending_keys(
+                &mut engine,
+                start + Duration::from_millis(500)
+            )
+            .as_slice(),
+            [EditorEvent::InsertChar('j')]
+        ));
+        assert!(engine.key_binding_state.pending_keys.is_empty());
+
+        // The `k` is too late to complete the chord.
+        apply_input_events_at(&mut engine, &mut buffer, keys("j"), start);
+        apply_input_events_at(
+            &mut engine,
+            &mut buffer,
+            keys("k"),
+            start + Duration::from_secs(1),
+        );
+        assert_eq2!(buffer.get_lines()[0].string, "jkjxjab");
+    }
+
+    #[test]
+    fn test_vim_partial_chord_then_motion() {
+        let mut buffer = EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT));
+        let mut engine = EditorEngine {
+            config_options: EditorEngineConfig {
+                key_binding_mode: KeyBindingMode::Vim,
+                ..Default::default()
+            },
+            ..mock_real_objects_for_editor::make_editor_engine_with_bounds(
+                size!( col_count: 20, row_count: 10 ),
+            )
+        };
+        buffer.set_lines(vec!["abc".into(), "def".into()]);
+
+        // The `d` doesn't do anything on its own, & the `j` still moves the caret.
+        apply_input_events(&mut engine, &mut buffer, keys("dj"));
+        assert_eq2!(buffer.get_lines().len(), 2);
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 0, row_index: 1)
+        );
+        assert!(engine.key_binding_state.pending_keys.is_empty());
+    }
+
+    #[test]
+    fn test_cursor_shape_for_mode() {
+        let mut buffer = EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT));
+        let insert_key = InputEvent::Keyboard(keypress!(@special SpecialKey::Insert));
+        let esc_key = InputEvent::Keyboard(keypress!(@special SpecialKey::Esc));
+
+        // Standard mode: a bar, or an underline in overwrite mode.
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        assert_eq2!(engine.get_cursor_shape(), CursorShape::Bar);
+        apply_input_events(&mut engine, &mut buffer, vec![insert_key.clone()]);
+        assert_eq2!(engine.get_cursor_shape(), CursorShape::Underline);
+
+        // Vim mode: a block outside of insert mode.
+        let mut engine = EditorEngine {
+            config_options: EditorEngineConfig {
+                key_binding_mode: KeyBindingMode::Vim,
+                ..Default::default()
+            },
+            ..mock_real_objects_for_editor::make_editor_engine()
+        };
+        assert_eq2!(engine.get_cursor_shape(), CursorShape::Block);
+        apply_input_events(&mut engine, &mut buffer, keys("i"));
+        assert_eq2!(engine.get_cursor_shape(), CursorShape::Bar);
+        apply_input_events(&mut engine, &mut buffer, vec![esc_key]);
+        assert_eq2!(engine.get_cursor_shape(), CursorShape::Block);
+        apply_input_events(&mut engine, &mut buffer, keys("v"));
+        assert_eq2!(engine.get_cursor_shape(), CursorShape::Block);
+    }
+
+    #[test]
+    fn test_vim_normal_mode_ignores_edit_keys() {
+        let mut buffer = EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT));
+        let mut engine = EditorEngine {
+            config_options: EditorEngineConfig {
+                key_binding_mode: KeyBindingMode::Vim,
+                ..Default::default()
+            },
+            ..mock_real_objects_for_editor::make_editor_engine_with_bounds(
+                size!( col_count: 20, row_count: 10 ),
+            )
+        };
+        buffer.set_lines(vec!["abc".into(), "def".into()]);
+        let special_key = |it: SpecialKey| InputEvent::Keyboard(keypress!(@special it));
+
+        // The keys that the key map binds to edits are ignored.
+        apply_input_events(
+            &mut engine,
+            &mut buffer,
+            vec![
+                special_key(SpecialKey::Right),
+                special_key(SpecialKey::Backspace),
+                special_key(SpecialKey::Enter),
+                special_key(SpecialKey::Delete),
+                special_key(SpecialKey::Tab),
+            ],
+        );
+        assert_eq2!(buffer.get_as_string(), "abc, def");
+        assert!(!buffer.is_dirty());
+
+        // The other keys still work, eg: the arrow keys.
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 1, row_index: 0)
+        );
+        apply_input_events(
+            &mut engine,
+            &mut buffer,
+            vec![special_key(SpecialKey::Down)],
+        );
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 1, row_index: 1)
+        );
+
+        // In insert mode they edit.
+        apply_input_events(&mut engine, &mut buffer, keys("i"));
+        apply_input_events(
+            &mut engine,
+            &mut buffer,
+            vec![special_key(SpecialKey::Backspace)],
+        );
+        assert_eq2!(buffer.get_as_string(), "abc, ef");
+    }
+
+    #[test]
+    fn test_tab_indents_selection_or_inserts_indent() {
+        let mut buffer = EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT));
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        engine.config_options.tab_width = 2;
+        engine.config_options.use_soft_tabs = true;
+        buffer.set_lines(vec!["ab".into(), "cd".into()]);
+        let tab = vec![InputEvent::Keyboard(keypress!(@special SpecialKey::Tab))];
+        let back_tab = vec![InputEvent::Keyboard(
+            keypress!(@special SpecialKey::BackTab),
+        )];
+
+        // W/out a selection, Tab inserts an indent at the caret.
+        apply_input_events(
+            &mut engine,
+            &mut buffer,
+            vec![InputEvent::Keyboard(keypress!(@special SpecialKey::Right))],
+        );
+        apply_input_events(&mut engine, &mut buffer, tab.clone());
+        assert_eq2!(buffer.get_as_string(), "a  b, cd");
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 3, row_index: 0)
+        );
+
+        // & Shift+Tab doesn't do anything.
+        apply_input_events(&mut engine, &mut buffer, back_tab.clone());
+        assert_eq2!(buffer.get_as_string(), "a  b, cd");
+
+        // W/ a selection, they indent & dedent the selected lines.
+        apply_input_events(
+            &mut engine,
+            &mut buffer,
+            vec![InputEvent::Keyboard(
+                keypress!(@special ModifierKeysMask::SHIFT, SpecialKey::Down),
+            )],
+        );
+        assert!(buffer.has_selection());
+        apply_input_events(&mut engine, &mut buffer, tab);
+        assert_eq2!(buffer.get_as_string(), "  a  b,   cd");
+        apply_input_events(&mut engine, &mut buffer, back_tab);
+        assert_eq2!(buffer.get_as_string(), "a  b, cd");
+    }
+}
+
+#[cfg(test)]
+mod test_completion {
+    use r3bl_rs_utils_core::*;
+
+    use super::*;
+    use crate::*;
+
+    fn completion_provider(
+        editor_buffer: &EditorBuffer,
+        caret: Position,
+    ) -> Vec<Completion> {
+        let (_, prefix) = get_word_prefix_at(editor_buffer, caret);
+        ["print", "println", "push"]
+            .iter()
+            .filter(|it| it.starts_with(&prefix))
+            .map(|it| Completion::new(it))
+            .collect()
+    }
+
+    /// Simulate what [EditorEngineApi::apply_event] does w/ the completion popup.
+    fn apply_input_events(
+        engine: &mut EditorEngine,
+        buffer: &mut EditorBuffer,
+        input_events: Vec<InputEvent>,
+    ) {
+        for input_event in input_events {
+            if let Some(new_buffer) =
+                try_handle_completion_input_event(engine, buffer, &input_event)
+            {
+                *buffer = new_buffer;
+                continue;
+            }
+            let editor_events: Vec<EditorEvent> =
+                EditorEvent::try_from(&input_event).into_iter().collect();
+            let is_triggered = is_completion_trigger(
+                &editor_events,
+                engine.completion_state.is_active(),
+            );
+            EditorEvent::apply_editor_events(
+                engine,
+                buffer,
+                editor_events,
+                &mock_real_objects_for_editor::make_shared_global_data(None),
+                &mut mock_real_objects_for_editor::make_component_registry(),
+                FlexBoxId::from(0),
+            );
+            update_completions(engine, buffer, is_triggered);
+        }
+    }
+
+    fn keys(it: &str) -> Vec<InputEvent> {
+        it.chars()
+            .map(|it| InputEvent::Keyboard(keypress!(@char it)))
+            .collect()
+    }
+
+    fn special_key(special_key: SpecialKey) -> Vec<InputEvent> {
+        vec![InputEvent::Keyboard(keypress!(@special special_key))]
+    }
+
+    #[test]
+    fn test_get_word_prefix_at() {
+        let mut buffer = EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT));
+        buffer.set_lines(vec!["let foo_1 = ba".into()]);
+
+        assert_eq2!(
+            get_word_prefix_at(&buffer, position!(col_index: 9, row_index: 0)),
+            (position!(col_index: 4, row_index: 0), "foo_1".to_string())
+        );
+        assert_eq2!(
+            get_word_prefix_at(&buffer, position!(col_index: 14, row_index: 0)),
+            (position!(col_index: 12, row_index: 0), "ba".to_string())
+        );
+        assert_eq2!(
+            get_word_prefix_at(&buffer, position!(col_index: 10, row_index: 0)),
+            (position!(col_index: 10, row_index: 0), "".to_string())
+        );
+    }
+
+    #[test]
+    fn test_completion_popup() {
+        let mut buffer = EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT));
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        engine.maybe_completion_provider = Some(completion_provider);
+
+        // Typing a word opens the popup w/ the matching completions.
+        apply_input_events(&mut engine, &mut buffer, keys("x p"));
+        assert_eq2!(engine.completion_state.items.len(), 3);
+        assert_eq2!(
+            engine.completion_state.prefix_start,
+            position!(col_index: 2, row_index: 0)
+        );
+        apply_input_events(&mut engine, &mut buffer, keys("ri"));
+        assert_eq2!(engine.completion_state.items.len(), 2);
+
+        // Up & Down move the selection, and Enter replaces the prefix w/ it.
+        apply_input_events(&mut engine, &mut buffer, special_key(SpecialKey::Down));
+        assert_eq2!(
+            engine.completion_state.get_selected(),
+            Some(&Completion::new("println"))
+        );
+        apply_input_events(&mut engine, &mut buffer, special_key(SpecialKey::Enter));
+        assert!(!engine.completion_state.is_active());
+        assert_eq2!(buffer.get_as_string(), "x println");
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 9, row_index: 0)
+        );
+
+        // Esc dismisses the popup, and so does typing a non word char.
+        apply_input_events(&mut engine, &mut buffer, keys(" pu"));
+        assert!(engine.completion_state.is_active());
+        apply_input_events(&mut engine, &mut buffer, special_key(SpecialKey::Esc));
+        assert!(!engine.completion_state.is_active());
+        assert_eq2!(buffer.get_as_string(), "x println pu");
+        apply_input_events(&mut engine, &mut buffer, keys("s("));
+        assert!(!engine.completion_state.is_active());
+
+        // W/out a provider there is no popup.
+        engine.maybe_completion_provider = None;
+        apply_input_events(&mut engine, &mut buffer, keys("p"));
+        assert!(!engine.completion_state.is_active());
+    }
+}
+
+#[cfg(test)]
+mod test_replace {
+    use r3bl_rs_utils_core::*;
+
+    use super::*;
+    use crate::*;
+
+    /// Simulate what [EditorEngineApi::apply_event] does w/ the replace bar.
+    fn apply_input_events(
+        engine: &mut EditorEngine,
+        buffer: &mut EditorBuffer,
+        input_events: Vec<InputEvent>,
+    ) {
+        for input_event in input_events {
+            if let Some(new_buffer) =
+                try_handle_replace_input_event(engine, buffer, &input_event)
+            {
+                *buffer = new_buffer;
+                continue;
+            }
+            EditorEvent::apply_editor_events(
+                engine,
+                buffer,
+                EditorEvent::try_from(&input_event).into_iter().collect(),
+                &mock_real_objects_for_editor::make_shared_global_data(None),
+                &mut mock_real_objects_for_editor::make_component_registry(),
+                FlexBoxId::from(0),
+            );
+        }
+    }
+
+    fn keys(it: &str) -> Vec<InputEvent> {
+        it.chars()
+            .map(|it| InputEvent::Keyboard(keypress!(@char it)))
+            .collect()
+    }
+
+    fn special_key(special_key: SpecialKey) -> Vec<InputEvent> {
+        vec![InputEvent::Keyboard(keypress!(@special special_key))]
+    }
+
+    fn char_w_mask(mask: ModifierKeysMask, character: char) -> Vec<InputEvent> {
+        vec![InputEvent::Keyboard(keypress!(@char mask, character))]
+    }
+
+    #[test]
+    fn test_find_matches_and_replace_all() {
+        let mut buffer = EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT));
+        buffer.set_lines(vec!["Foo foo".into(), "food foo_bar FOO".into()]);
+        let case_insensitive = SearchOptions {
+            case_insensitive: true,
+            ..Default::default()
+        };
+        let whole_word = SearchOptions {
+            case_insensitive: true,
+            whole_word: true,
+            ..Default::default()
+        };
+
+        assert_eq2!(
+            buffer.find_matches("foo", &SearchOptions::default()).len(),
+            3
+        );
+        assert_eq2!(buffer.find_matches("foo", &case_insensitive).len(), 5);
+        assert_eq2!(
+            buffer.find_matches("foo", &whole_word),
+            vec![
+                SearchMatch {
+                    row_index: ch!(0),
+                    start_col_index: ch!(0),
+                    end_col_index: ch!(3),
+                },
+                SearchMatch {
+                    row_index: ch!(0),
+                    start_col_index: ch!(4),
+                    end_col_index: ch!(7),
+                },
+                SearchMatch {
+                    row_index: ch!(1),
+                    start_col_index: ch!(13),
+                    end_col_index: ch!(16),
+                },
+            ]
+        );
+
+        // Nothing is replaced for an empty query, or a replacement w/ newlines.
+        assert_eq2!(buffer.replace_all("", "x", &whole_word), 0);
+        assert_eq2!(buffer.replace_all("foo", "x\ny", &whole_word), 0);
+        assert!(!buffer.is_dirty());
+
+        // The caret is clamped to its (now shorter) line.
+        {
+            let (_, caret, _, _) = buffer.get_mut();
+            *caret = position!(col_index: 16, row_index: 1);
+        }
+        assert_eq2!(buffer.replace_all("foo", "x", &whole_word), 3);
+        assert_eq2!(buffer.get_as_string(), "x x, food foo_bar x");
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 14, row_index: 1)
+        );
+        assert!(buffer.is_dirty());
+    }
+
+    #[test]
+    fn test_replace_mode() {
+        let mut buffer = EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT));
+        let mut engine = mock_real_objects_for_editor::make_editor_engine_with_bounds(
+            size!(col_count: 80, row_count: 10),
+        );
+        buffer.set_lines(vec!["ab Ab ab".into()]);
+
+        // Ctrl+R opens the replace bar, & typed chars go into the query.
+        apply_input_events(
+            &mut engine,
+            &mut buffer,
+            char_w_mask(ModifierKeysMask::CTRL, 'r'),
+        );
+        assert!(engine.replace_state.is_active);
+        apply_input_events(&mut engine, &mut buffer, keys("ab"));
+        assert_eq2!(engine.replace_state.query, "ab");
+        assert_eq2!(buffer.get_as_string(), "ab Ab ab");
+        assert_eq2!(engine.replace_state.get_current_match(&buffer).1, 2);
+
+        // Tab switches to the replacement, & Enter replaces the current match.
+        apply_input_events(&mut engine, &mut buffer, special_key(SpecialKey::Tab));
+        apply_input_events(&mut engine, &mut buffer, keys("x"));
+        assert_eq2!(engine.replace_state.replacement, "x");
+        apply_input_events(&mut engine, &mut buffer, special_key(SpecialKey::Enter));
+        assert_eq2!(buffer.get_as_string(), "x Ab ab");
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 1, row_index: 0)
+        );
+
+        // Alt+C ignores case, so the next match is `Ab`.
+        apply_input_events(
+            &mut engine,
+            &mut buffer,
+            char_w_mask(ModifierKeysMask::ALT, 'c'),
+        );
+        let (maybe_current_match, match_count) =
+            engine.replace_state.get_current_match(&buffer);
+        assert_eq2!(match_count, 2);
+        assert_eq2!(maybe_current_match.unwrap().start_col_index, ch!(2));
+
+        // The matches are highlighted, & the bar shows the current match.
+        let render_ops = render_replace_mode(&engine, &buffer).unwrap();
+        assert!(render_ops.iter().any(|it| matches!(
+            it,
+            RenderOp::PaintTextWithAttributes(text, _)
+                if text.starts_with(" Find: ab Replace: x▒ 1/2 [ignore case]")
+        )));
+
+        // Alt+A replaces the rest at once.
+        apply_input_events(
+            &mut engine,
+            &mut buffer,
+            char_w_mask(ModifierKeysMask::ALT, 'a'),
+        );
+        assert_eq2!(buffer.get_as_string(), "x x x");
+
+        // Esc closes the bar, & typed chars go into the buffer again.
+        apply_input_events(&mut engine, &mut buffer, special_key(SpecialKey::Esc));
+        assert!(!engine.replace_state.is_active);
+        apply_input_events(&mut engine, &mut buffer, keys("z"));
+        assert_eq2!(buffer.get_as_string(), "x zx x");
+    }
+
+    #[cfg(feature = "regex")]
+    #[test]
+    fn test_regex_replace_all() {
+        let mut buffer = EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT));
+        buffer.set_lines(vec!["let a = foo(1, 2);".into(), "foo(3, 4) + 1".into()]);
+        let options = SearchOptions {
+            is_regex: true,
+            ..Default::default()
+        };
+
+        // Empty matches are skipped.
+        assert_eq2!(buffer.find_matches("x*", &options).len(), 0);
+        assert_eq2!(buffer.find_matches(r"\d", &options).len(), 5);
+
+        // An invalid regex doesn't match anything.
+        assert!(build_search_regex("foo(", &options).is_err());
+        assert_eq2!(buffer.find_matches("foo(", &options).len(), 0);
+        assert_eq2!(buffer.replace_all("foo(", "bar", &options), 0);
+
+        // Capture groups are expanded in the replacement.
+        assert_eq2!(
+            buffer.replace_all(r"foo\((\d), (\d)\)", "bar($2, $1)", &options),
+            2
+        );
+        assert_eq2!(buffer.get_as_string(), "let a = bar(2, 1);, bar(4, 3) + 1");
+    }
+
+    #[cfg(not(feature = "regex"))]
+    #[test]
+    fn test_regex_rejected_wo_feature() {
+        let mut buffer = EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT));
+        buffer.set_lines(vec!["foo(1)".into()]);
+        let options = SearchOptions {
+            is_regex: true,
+            ..Default::default()
+        };
+
+        // Even a query that would match as plain text is rejected.
+        assert_eq2!(buffer.find_matches("foo", &options).len(), 0);
+        assert_eq2!(buffer.replace_all("foo", "bar", &options), 0);
+        assert_eq2!(buffer.get_as_string(), "foo(1)");
+
+        let replace_state = ReplaceState {
+            query: "foo".into(),
+            options,
+            ..Default::default()
+        };
+        assert_eq2!(
+            replace_state.get_query_error(),
+            Some(REGEX_FEATURE_DISABLED_MSG)
+        );
+    }
+
+    #[cfg(feature = "regex")]
+    #[test]
+    fn test_regex_replace_mode() {
+        let mut buffer = EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT));
+        let mut engine = mock_real_objects_for_editor::make_editor_engine_with_bounds(
+            size!(col_count: 80, row_count: 10),
+        );
+        buffer.set_lines(vec!["ann@x bob@y".into()]);
+
+        // Alt+R switches to regex mode, & the compiled query is cached.
+        apply_input_events(
+            &mut engine,
+            &mut buffer,
+            char_w_mask(ModifierKeysMask::CTRL, 'r'),
+        );
+        apply_input_events(
+            &mut engine,
+            &mut buffer,
+            char_w_mask(ModifierKeysMask::ALT, 'r'),
+        );
+        apply_input_events(&mut engine, &mut buffer, keys(r"(\w+)@"));
+        assert!(matches!(
+            engine.replace_state.maybe_regex_cache,
+            Some(RegexCache { ref query, result: Ok(_), .. }) if query == r"(\w+)@"
+        ));
+        assert_eq2!(engine.replace_state.get_current_match(&buffer).1, 2);
+
+        // Enter replaces the current match w/ the capture group expanded, & Alt+A
+        // replaces the rest.
+        apply_input_events(&mut engine, &mut buffer, special_key(SpecialKey::Tab));
+        apply_input_events(&mut engine, &mut buffer, keys("${1} at "));
+        apply_input_events(&mut engine, &mut buffer, special_key(SpecialKey::Enter));
+        assert_eq2!(buffer.get_as_string(), "ann at x bob@y");
+        apply_input_events(
+            &mut engine,
+            &mut buffer,
+            char_w_mask(ModifierKeysMask::ALT, 'a'),
+        );
+        assert_eq2!(buffer.get_as_string(), "ann at x bob at y");
+
+        // An invalid regex shows an error in the bar, & doesn't match anything.
+        apply_input_events(&mut engine, &mut buffer, special_key(SpecialKey::Tab));
+        apply_input_events(&mut engine, &mut buffer, keys("("));
+        assert!(engine.replace_state.get_query_error().is_some());
+        assert_eq2!(engine.replace_state.get_current_match(&buffer).1, 0);
+        let render_ops = render_replace_mode(&engine, &buffer).unwrap();
+        assert!(render_ops.iter().any(|it| matches!(
+            it,
+            RenderOp::PaintTextWithAttributes(_, Some(style))
+                if *style == get_search_error_style()
+        )));
+    }
+}
+
+#[cfg(test)]
+mod test_focus_state {
+    use r3bl_rs_utils_core::*;
+    use r3bl_rs_utils_macro::style;
+
+    use super::*;
+    use crate::{test_dialog::mock_real_objects_for_dialog, *};
+
+    pub async fn render(
+        engine: &mut EditorEngine,
+        buffer: &EditorBuffer,
+        component_registry: &mut ComponentRegistry<
+            mock_real_objects_for_dialog::State,
+            String,
+        >,
+    ) -> Vec<RenderOp> {
+        let flex_box = FlexBox {
+            id: FlexBoxId::from(0),
+            style_adjusted_bounds_size: size!( col_count: 10, row_count: 10 ),
+            ..Default::default()
+        };
+        let shared_store = &mock_real_objects_for_dialog::create_store();
+        let state = &shared_store.read().await.state.clone();
+        let args = EditorEngineArgs {
+            shared_global_data: &mock_real_objects_for_editor::make_shared_global_data(
+                None,
+            ),
+            shared_store,
+            state,
+            component_registry,
+            self_id: flex_box.id,
+            editor_buffer: buffer,
+            editor_engine: engine,
+        };
+        let pipeline = EditorEngineApi::render_engine(args, &flex_box)
+            .await
+            .unwrap();
+        pipeline.get_all_render_op_in(ZOrder::Normal).unwrap()
+    }
+
+    fn has_caret_w_style(render_ops: &[RenderOp], style: Style) -> bool {
+        render_ops.iter().any(|it| {
+            matches!(
+                it,
+                RenderOp::PaintTextWithAttributes(text, Some(it_style))
+                    if text == "a" && *it_style == style
+            )
+        })
+    }
+
+    #[tokio::test]
+    async fn test_caret_is_hollow_when_blurred() {
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        let mut buffer = EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT));
+        buffer.set_lines(vec!["abc".to_string()]);
+        let mut component_registry =
+            mock_real_objects_for_editor::make_component_registry();
+        let focused_caret_style = style! { attrib: [reverse] };
+
+        // Nothing has focus.
+        let render_ops = render(&mut engine, &buffer, &mut component_registry).await;
+        assert_eq2!(engine.focus_state, FocusState::Blurred);
+        assert!(has_caret_w_style(&render_ops, get_blurred_caret_style()));
+        assert!(!has_caret_w_style(&render_ops, focused_caret_style));
+
+        // The editor has focus.
+        component_registry.has_focus.set_id(FlexBoxId::from(0));
+        let render_ops = render(&mut engine, &buffer, &mut component_registry).await;
+        assert_eq2!(engine.focus_state, FocusState::Focused);
+        assert!(has_caret_w_style(&render_ops, focused_caret_style));
+        assert!(!has_caret_w_style(&render_ops, get_blurred_caret_style()));
+
+        // The caret isn't painted at all when blurred.
+        component_registry.has_focus.set_id(FlexBoxId::from(1));
+        engine.config_options.show_caret_when_blurred = false;
+        let render_ops = render(&mut engine, &buffer, &mut component_registry).await;
+        assert_eq2!(engine.focus_state, FocusState::Blurred);
+        assert!(!has_caret_w_style(&render_ops, get_blurred_caret_style()));
+        assert!(!has_caret_w_style(&render_ops, focused_caret_style));
+    }
+}
+
+#[cfg(test)]
+mod test_diagnostics {
+    use r3bl_rs_utils_core::*;
+
+    use super::*;
+    use crate::*;
+
+    #[tokio::test]
+    async fn test_gutter_and_underline() {
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        engine.config_options.diagnostics_gutter = true;
+        engine.config_options.syntax_highlight = SyntaxHighlightMode::Disable;
+        let mut buffer = EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT));
+        buffer.set_lines(vec!["abc".to_string(), "def".to_string()]);
+        engine.diagnostics.set_all(vec![Diagnostic {
+            start_col_index: ch!(0),
+            end_col_index: ch!(2),
+            ..Diagnostic::new(ch!(1), DiagnosticSeverity::Error, "oops")
+        }]);
+        let mut component_registry =
+            mock_real_objects_for_editor::make_component_registry();
+
+        let render_ops =
+            test_focus_state::render(&mut engine, &buffer, &mut component_registry).await;
+
+        // The content starts after the gutter.
+        assert_eq2!(
+            engine.current_box.style_adjusted_origin_pos,
+            position!(col_index: 2, row_index: 0)
+        );
+        assert_eq2!(engine.viewport_width(), ch!(8));
+
+        // The glyph is painted in the gutter, & the cols are underlined.
+        let error_style = get_diagnostic_style(DiagnosticSeverity::Error);
+        assert!(render_ops.iter().any(|it| matches!(
+            it,
+            RenderOp::PaintTextWithAttributes(text, Some(style))
+                if *text == DIAGNOSTIC_ERROR_CHAR.to_string() && *style == error_style
+        )));
+        assert!(render_ops.iter().any(|it| matches!(
+            it,
+            RenderOp::PaintTextWithAttributes(text, Some(style))
+                if text == "de" && style.underline
+        )));
+
+        // Inserting a line above the diagnostic shifts it down.
+        EditorEvent::apply_editor_events(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::InsertNewLine],
+            &mock_real_objects_for_editor::make_shared_global_data(None),
+            &mut component_registry,
+            FlexBoxId::from(0),
+        );
+        assert_eq2!(
+            engine.diagnostics.iter().next().map(|it| it.row_index),
+            Some(ch!(2))
+        );
+        assert_eq2!(
+            engine
+                .diagnostics
+                .get_at_caret(&buffer)
+                .map(|it| it.row_index),
+            None
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_editor_command {
+    use r3bl_rs_utils_core::*;
+
+    use super::*;
+    use crate::{test_dialog::mock_real_objects_for_dialog, *};
+
+    #[tokio::test]
+    async fn test_apply_event_returns_command() {
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        let mut buffer = EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT));
+        buffer.set_lines(vec!["abc".to_string()]);
+        let mut component_registry =
+            mock_real_objects_for_editor::make_component_registry();
+        let shared_store = &mock_real_objects_for_dialog::create_store();
+        let state = &shared_store.read().await.state.clone();
+
+        let args = EditorEngineArgs {
+            shared_global_data: &mock_real_objects_for_editor::make_shared_global_data(
+                None,
+            ),
+            shared_store,
+            state,
+            component_registry: &mut component_registry,
+            self_id: FlexBoxId::from(0),
+            editor_buffer: &buffer,
+            editor_engine: &mut engine,
+        };
+        let input_event =
+            InputEvent::Keyboard(keypress!(@char ModifierKeysMask::CTRL, 's'));
+        let result = EditorEngineApi::apply_event(args, &input_event)
+            .await
+            .unwrap();
+
+        // The buffer isn't changed, the app gets the command instead.
+        assert!(matches!(
+            result,
+            EditorEngineApplyEventResult::Command(EditorCommand::Save)
+        ));
+        assert!(!buffer.is_dirty());
+    }
+
+    #[tokio::test]
+    async fn test_apply_event_leaves_esc_for_the_app() {
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        let mut buffer = EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT));
+        buffer.set_lines(vec!["abc".to_string(), "def".to_string()]);
+        let mut component_registry =
+            mock_real_objects_for_editor::make_component_registry();
+        let shared_store = &mock_real_objects_for_dialog::create_store();
+        let state = &shared_store.read().await.state.clone();
+        let shared_global_data =
+            &mock_real_objects_for_editor::make_shared_global_data(None);
+        let esc = InputEvent::Keyboard(keypress!(@special SpecialKey::Esc));
+
+        // W/ a single caret there is nothing to collapse, so the app gets the key.
+        let args = EditorEngineArgs {
+            shared_global_data,
+            shared_store,
+            state,
+            component_registry: &mut component_registry,
+            self_id: FlexBoxId::from(0),
+            editor_buffer: &buffer,
+            editor_engine: &mut engine,
+        };
+        let result = EditorEngineApi::apply_event(args, &esc).await.unwrap();
+        assert!(matches!(result, EditorEngineApplyEventResult::NotApplied));
+
+        // Otherwise the editor consumes it.
+        buffer.set_secondary_carets(vec![position!(col_index: 0, row_index: 1)]);
+        let args = EditorEngineArgs {
+            shared_global_data,
+            shared_store,
+            state,
+            component_registry: &mut component_registry,
+            self_id: FlexBoxId::from(0),
+            editor_buffer: &buffer,
+            editor_engine: &mut engine,
+        };
+        let result = EditorEngineApi::apply_event(args, &esc).await.unwrap();
+        let EditorEngineApplyEventResult::Applied(new_buffer) = result else {
+            panic!("Esc should collapse the carets");
+        };
+        assert!(!new_buffer.has_secondary_carets());
+    }
+}
+
+#[cfg(test)]
+mod test_wide_char_horizontal_scroll {
+    use r3bl_rs_utils_core::*;
+
+    use super::*;
+    use crate::*;
+
+    #[test]
+    fn editor_scroll_offset_snaps_to_grapheme_boundary() {
+        let mut buffer = EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT));
+        let mut engine = mock_real_objects_for_editor::make_editor_engine_with_bounds(
+            size!( col_count: 4, row_count: 5 ),
+        );
+        buffer.set_lines(vec!["abcdefgh".to_string(), "中中中中".to_string()]);
+
+        // Scroll right by 1 col.
+        EditorEvent::apply_editor_events(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::MoveCaret(CaretDirection::Right); 4],
+            &mock_real_objects_for_editor::make_shared_global_data(None),
+            &mut mock_real_objects_for_editor::make_component_registry(),
+            FlexBoxId::from(0),
+        );
+        assert_eq2!(buffer.get_scroll_offset().col_index, ch!(1));
+
+        // Col 1 is in the middle of the first `中` in the next row, so the scroll offset
+        // moves to the end of it (& the caret stays on the same text).
+        EditorEvent::apply_editor_events(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::MoveCaret(CaretDirection::Down)],
+            &mock_real_objects_for_editor::make_shared_global_data(None),
+            &mut mock_real_objects_for_editor::make_component_registry(),
+            FlexBoxId::from(0),
+        );
+        assert_eq2!(
+            buffer.get_scroll_offset(),
+            position!(col_index: 2, row_index: 0)
+        );
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 4, row_index: 1)
+        );
+
+        // Same for an offset that is set by the app.
+        buffer.set_scroll_offset(ch!(0), ch!(3), size!( col_count: 4, row_count: 5 ), 0);
+        assert_eq2!(buffer.get_scroll_offset().col_index, ch!(4));
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted).col_index,
+            ch!(4)
+        );
+    }
+
+    #[test]
+    fn editor_clip_pads_wide_chars_at_the_edges() {
+        let style_1 = Style {
+            bold: true,
+            ..Default::default()
+        };
+        let style_2 = Style {
+            italic: true,
+            ..Default::default()
+        };
+        let mut line = StyleUSSpanLine::default();
+        line += StyleUSSpan::new(style_1, US::from("ab"));
+        line += StyleUSSpan::new(style_2, US::from("中文"));
+
+        // Col 3 is in the middle of `中`. It used to be dropped (w/out painting anything
+        // in its place), which shifted `文` left by a col.
+        let clipped = line.clip(ch!(3), ch!(3));
+        let clipped = clipped
+            .iter()
+            .map(|it| (*it.get_style(), it.get_text().string.clone()))
+            .collect::<Vec<_>>();
+        assert_eq2!(
+            clipped,
+            vec![(style_2, " ".to_string()), (style_2, "文".to_string())]
+        );
+
+        // `中` is cut off by the right edge.
+        let clipped = line.clip(ch!(1), ch!(2));
+        let clipped = clipped
+            .iter()
+            .map(|it| (*it.get_style(), it.get_text().string.clone()))
+            .collect::<Vec<_>>();
+        assert_eq2!(
+            clipped,
+            vec![(style_1, "b".to_string()), (style_2, " ".to_string())]
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_render_visible_rows {
+    use r3bl_rs_utils_core::*;
+
+    use super::*;
+    use crate::{test_dialog::mock_real_objects_for_dialog, *};
+
+    #[tokio::test]
+    async fn test_render_visible_rows() {
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        engine.config_options.syntax_highlight = SyntaxHighlightMode::Disable;
+        let mut buffer = EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT));
+        buffer.set_lines(vec!["abc".to_string(), "de".to_string()]);
+        {
+            let (_, _, _, selection_map) = buffer.get_mut();
+            selection_map.insert(
+                ch!(0),
+                SelectionRange::new(ch!(1), ch!(3)),
+                CaretMovementDirection::Right,
+            );
+        }
+        let mut component_registry =
+            mock_real_objects_for_editor::make_component_registry();
+
+        let flex_box = FlexBox {
+            id: FlexBoxId::from(0),
+            style_adjusted_origin_pos: position!( col_index: 2, row_index: 1 ),
+            style_adjusted_bounds_size: size!( col_count: 10, row_count: 4 ),
+            ..Default::default()
+        };
+        let shared_store = &mock_real_objects_for_dialog::create_store();
+        let state = &shared_store.read().await.state.clone();
+        let args = EditorEngineArgs {
+            shared_global_data: &mock_real_objects_for_editor::make_shared_global_data(
+                None,
+            ),
+            shared_store,
+            state,
+            component_registry: &mut component_registry,
+            self_id: flex_box.id,
+            editor_buffer: &buffer,
+            editor_engine: &mut engine,
+        };
+        let rows = EditorEngineApi::render_visible_rows(args, &flex_box)
+            .await
+            .unwrap();
+
+        // One row for each row of the box, w/ all of its cols.
+        assert_eq2!(rows.len(), 4);
+        let plain_texts = rows
+            .iter()
+            .map(|it| it.to_plain_text_us().string)
+            .collect::<Vec<_>>();
+        assert_eq2!(
+            plain_texts,
+            vec!["abc       ", "de        ", "          ", "          "]
+        );
+
+        // The selection is a run of its own.
+        assert!(rows[0].iter().any(|it| it.get_text().string == "bc"));
+    }
+}
+
+#[cfg(test)]
+mod test_rulers {
+    use r3bl_rs_utils_core::*;
+
+    use super::*;
+    use crate::*;
+
+    fn get_ruler_col_indices(render_ops: &[RenderOp]) -> Vec<ChUnit> {
+        let mut it = vec![];
+        for window in render_ops.windows(2) {
+            if let [RenderOp::MoveCursorPositionRelTo(_, position), RenderOp::ApplyColors(Some(style))] =
+                window
+            {
+                if *style == get_ruler_style() && !it.contains(&position.col_index) {
+                    it.push(position.col_index);
+                }
+            }
+        }
+        it
+    }
+
+    #[tokio::test]
+    async fn test_rulers_are_painted_beneath_the_content() {
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        engine.config_options.syntax_highlight = SyntaxHighlightMode::Disable;
+        engine.config_options.diagnostics_gutter = true;
+        engine.config_options.rulers = vec![4, 6, 20];
+        let mut buffer = EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT));
+        buffer.set_lines(vec!["abcdefghijkl".to_string()]);
+        let mut component_registry =
+            mock_real_objects_for_editor::make_component_registry();
+
+        // The ruler at col 20 is outside the viewport.
+        let render_ops =
+            test_focus_state::render(&mut engine, &buffer, &mut component_registry).await;
+        assert_eq2!(get_ruler_col_indices(&render_ops), vec![ch!(4), ch!(6)]);
+
+        // Each ruler is painted on every row, before the content.
+        let ruler_op_indices = render_ops
+            .iter()
+            .enumerate()
+            .filter(|(_, it)| {
+                matches!(it, RenderOp::ApplyColors(Some(style)) if *style == get_ruler_style())
+            })
+            .map(|(index, _)| index)
+            .collect::<Vec<_>>();
+        assert_eq2!(
+            ruler_op_indices.len(),
+            2 * ch!(@to_usize engine.viewport_height())
+        );
+        let content_op_index = render_ops
+            .iter()
+            .position(|it| {
+                matches!(it, RenderOp::PaintTextWithAttributes(text, _) if text.starts_with("abc"))
+            })
+            .unwrap();
+        assert!(ruler_op_indices
+            .iter()
+            .all(|index| *index < content_op_index));
+
+        // Scroll horizontally, so the first ruler is scrolled out of the viewport.
+        let viewport = size!(
+            col_count: engine.viewport_width(),
+            row_count: engine.viewport_height()
+        );
+        buffer.set_scroll_offset(ch!(0), ch!(5), viewport, 0);
+        let render_ops =
+            test_focus_state::render(&mut engine, &buffer, &mut component_registry).await;
+        assert_eq2!(get_ruler_col_indices(&render_ops), vec![ch!(1)]);
+    }
+}
+
+#[cfg(test)]
+mod test_indent_guides {
+    use r3bl_rs_utils_core::*;
+
+    use super::*;
+    use crate::*;
+
+    /// Returns the `(row_index, col_index)` of each guide.
+    fn get_guide_positions(render_ops: &[RenderOp]) -> Vec<(usize, usize)> {
+        let mut it = vec![];
+        for window in render_ops.windows(2) {
+            if let [RenderOp::MoveCursorPositionRelTo(_, position), RenderOp::ApplyColors(Some(style))] =
+                window
+            {
+                if *style == get_indent_guide_style() {
+                    it.push((
+                        ch!(@to_usize position.row_index),
+                        ch!(@to_usize position.col_index),
+                    ));
+                }
+            }
+        }
+        it
+    }
+
+    #[tokio::test]
+    async fn test_indent_guides() {
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        engine.config_options.syntax_highlight = SyntaxHighlightMode::Disable;
+        let mut buffer = EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT));
+        buffer.set_lines(vec![
+            "fn a() {".to_string(),
+            "    if x {".to_string(),
+            "        y();".to_string(),
+            "".to_string(),
+            "    }".to_string(),
+            "}".to_string(),
+        ]);
+        let mut component_registry =
+            mock_real_objects_for_editor::make_component_registry();
+
+        // Disabled by default.
+        let render_ops =
+            test_focus_state::render(&mut engine, &buffer, &mut component_registry).await;
+        assert_eq2!(get_guide_positions(&render_ops), vec![]);
+
+        // A guide every tab width cols in the leading whitespace. The blank line gets the
+        // guides that the lines around it share.
+        engine.config_options.indent_guides = true;
+        let render_ops =
+            test_focus_state::render(&mut engine, &buffer, &mut component_registry).await;
+        assert_eq2!(
+            get_guide_positions(&render_ops),
+            vec![(1, 0), (2, 0), (2, 4), (3, 0), (4, 0)]
+        );
+
+        engine.config_options.tab_width = 2;
+        let render_ops =
+            test_focus_state::render(&mut engine, &buffer, &mut component_registry).await;
+        assert_eq2!(
+            get_guide_positions(&render_ops),
+            vec![
+                (1, 0),
+                (1, 2),
+                (2, 0),
+                (2, 2),
+                (2, 4),
+                (2, 6),
+                (3, 0),
+                (3, 2),
+                (4, 0),
+                (4, 2)
+            ]
+        );
+
+        // Scroll horizontally, so the guides at col 0 (& 2) are out of the viewport.
+        engine.config_options.tab_width = 4;
+        let viewport = size!(
+            col_count: engine.viewport_width(),
+            row_count: engine.viewport_height()
+        );
+        buffer.set_scroll_offset(ch!(0), ch!(3), viewport, 0);
+        let render_ops =
+            test_focus_state::render(&mut engine, &buffer, &mut component_registry).await;
+        assert_eq2!(get_guide_positions(&render_ops), vec![(2, 1)]);
+    }
+}
+
+#[cfg(test)]
+mod test_snippets {
+    use r3bl_rs_utils_core::*;
+
+    use super::*;
+    use crate::*;
+
+    /// Simulate what [EditorEngineApi::apply_event] does w/ the snippet session.
+    fn apply_input_events(
+        engine: &mut EditorEngine,
+        buffer: &mut EditorBuffer,
+        input_events: Vec<InputEvent>,
+    ) {
+        for input_event in input_events {
+            if let Some(new_buffer) =
+                try_handle_snippet_input_event(engine, buffer, &input_event)
+            {
+                *buffer = new_buffer;
+                continue;
+            }
+            EditorEvent::apply_editor_events(
+                engine,
+                buffer,
+                EditorEvent::try_from(&input_event).into_iter().collect(),
+                &mock_real_objects_for_editor::make_shared_global_data(None),
+                &mut mock_real_objects_for_editor::make_component_registry(),
+                FlexBoxId::from(0),
+            );
+        }
+    }
+
+    fn insert_snippet(
+        engine: &mut EditorEngine,
+        buffer: &mut EditorBuffer,
+        template: &str,
+    ) {
+        EditorEvent::apply_editor_events(
+            engine,
+            buffer,
+            vec![EditorEvent::InsertSnippet(template.to_string())],
+            &mock_real_objects_for_editor::make_shared_global_data(None),
+            &mut mock_real_objects_for_editor::make_component_registry(),
+            FlexBoxId::from(0),
+        );
+    }
+
+    fn keys(it: &str) -> Vec<InputEvent> {
+        it.chars()
+            .map(|it| InputEvent::Keyboard(keypress!(@char it)))
+            .collect()
+    }
+
+    fn special_key(special_key: SpecialKey) -> Vec<InputEvent> {
+        vec![InputEvent::Keyboard(keypress!(@special special_key))]
+    }
+
+    fn selection(start_col_index: usize, end_col_index: usize) -> Option<SelectionRange> {
+        Some(SelectionRange {
+            start_display_col_index: ch!(start_col_index),
+            end_display_col_index: ch!(end_col_index),
+        })
+    }
+
+    #[test]
+    fn test_insert_snippet_and_cycle_tab_stops() {
+        let mut engine =
+            mock_real_objects_for_editor::make_editor_engine_with_bounds(size! {
+                col_count: 40, row_count: 10
+            });
+        let mut buffer = EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT));
+
+        // The whole expansion is a single edit.
+        insert_snippet(
+            &mut engine,
+            &mut buffer,
+            "fn ${1:name}($2: ${3:u8}) -> $3 {\n    $0\n}",
+        );
+        assert_eq2!(
+            buffer.get_as_string_with_newlines(),
+            "fn name(: u8) -> u8 {\n    \n}"
+        );
+        assert!(buffer.is_dirty());
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 7, row_index: 0)
+        );
+        assert_eq2!(
+            buffer.get_selection_map().get(ch!(0)).copied(),
+            selection(3, 7)
+        );
+
+        // Typing replaces the selected placeholder.
+        apply_input_events(&mut engine, &mut buffer, keys("foo"));
+        assert_eq2!(buffer.get_lines()[0].string, "fn foo(: u8) -> u8 {");
+
+        // Tab goes to the (empty) 2nd tab stop.
+        apply_input_events(&mut engine, &mut buffer, special_key(SpecialKey::Tab));
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 7, row_index: 0)
+        );
+        assert!(buffer.get_selection_map().is_empty());
+        apply_input_events(&mut engine, &mut buffer, keys("x"));
+
+        // The mirror of the 3rd tab stop is updated as it is typed over.
+        apply_input_events(&mut engine, &mut buffer, special_key(SpecialKey::Tab));
+        assert_eq2!(
+            buffer.get_selection_map().get(ch!(0)).copied(),
+            selection(10, 12)
+        );
+        apply_input_events(&mut engine, &mut buffer, keys("i32"));
+        assert_eq2!(buffer.get_lines()[0].string, "fn foo(x: i32) -> i32 {");
+
+        // Shift+Tab goes back to the 2nd tab stop, which grew to fit the text.
+        apply_input_events(&mut engine, &mut buffer, special_key(SpecialKey::BackTab));
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 8, row_index: 0)
+        );
+        assert_eq2!(
+            buffer.get_selection_map().get(ch!(0)).copied(),
+            selection(7, 8)
+        );
+
+        // Reaching `$0` ends the session.
+        apply_input_events(&mut engine, &mut buffer, special_key(SpecialKey::Tab));
+        apply_input_events(&mut engine, &mut buffer, special_key(SpecialKey::Tab));
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 4, row_index: 1)
+        );
+        assert_eq2!(buffer.get_snippet_session(), None);
+    }
+
+    #[test]
+    fn test_snippet_mirrors_in_the_middle_of_a_line() {
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        let mut buffer = EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT));
+        buffer.set_lines(vec!["x = ;".into()]);
+        let viewport = engine.current_box.style_adjusted_bounds_size;
+
+        buffer.insert_snippet(
+            "${1:a}+$1",
+            position!(col_index: 4, row_index: 0),
+            viewport,
+        );
+        assert_eq2!(buffer.get_lines()[0].string, "x = a+a;");
+
+        apply_input_events(&mut engine, &mut buffer, keys("bc"));
+        assert_eq2!(buffer.get_lines()[0].string, "x = bc+bc;");
+        apply_input_events(&mut engine, &mut buffer, special_key(SpecialKey::Backspace));
+        assert_eq2!(buffer.get_lines()[0].string, "x = b+b;");
+
+        // Esc ends the session, so the mirror isn't updated anymore.
+        apply_input_events(&mut engine, &mut buffer, special_key(SpecialKey::Esc));
+        assert_eq2!(buffer.get_snippet_session(), None);
+        apply_input_events(&mut engine, &mut buffer, keys("d"));
+        assert_eq2!(buffer.get_lines()[0].string, "x = bd+b;");
+    }
+
+    #[test]
+    fn test_edit_outside_tab_stop_ends_snippet_session() {
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        let mut buffer = EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT));
+
+        insert_snippet(&mut engine, &mut buffer, "$1 $2");
+        assert!(buffer.get_snippet_session().is_some());
+        apply_input_events(&mut engine, &mut buffer, special_key(SpecialKey::Enter));
+        assert_eq2!(buffer.get_snippet_session(), None);
+    }
+}
+
+#[cfg(test)]
+mod test_word_selection {
+    use std::time::{Duration, Instant};
+
+    use r3bl_rs_utils_core::*;
+
+    use super::*;
+    use crate::*;
+
+    fn apply_editor_event(
+        engine: &mut EditorEngine,
+        buffer: &mut EditorBuffer,
+        editor_event: EditorEvent,
+    ) {
+        EditorEvent::apply_editor_events(
+            engine,
+            buffer,
+            vec![editor_event],
+            &mock_real_objects_for_editor::make_shared_global_data(None),
+            &mut mock_real_objects_for_editor::make_component_registry(),
+            FlexBoxId::from(0),
+        );
+    }
+
+    fn click(
+        engine: &mut EditorEngine,
+        buffer: &mut EditorBuffer,
+        pos: Position,
+        now: Instant,
+    ) {
+        let input_event = InputEvent::Mouse(MouseInput {
+            pos,
+            kind: MouseInputKind::MouseDown(Button::Left),
+            maybe_modifier_keys: None,
+        });
+        *buffer =
+            try_handle_mouse_input_event_at(engine, buffer, &input_event, now).unwrap();
+    }
+
+    #[test]
+    fn test_word_char_class() {
+        let line = UnicodeString::from("a foo_bar-baz  ;;");
+        let word_char_class = WordCharClass::default();
+        assert_eq2!(
+            word_char_class.get_word_range_at(&line, ch!(4)),
+            Some(SelectionRange::new(ch!(2), ch!(9)))
+        );
+        assert_eq2!(
+            word_char_class.get_word_range_at(&line, ch!(9)),
+            Some(SelectionRange::new(ch!(9), ch!(10)))
+        );
+        // Past the end of the line, the last word is used.
+        assert_eq2!(
+            word_char_class.get_word_range_at(&line, ch!(40)),
+            Some(SelectionRange::new(ch!(15), ch!(17)))
+        );
+        assert_eq2!(
+            word_char_class.get_word_range_at(&UnicodeString::from(""), ch!(0)),
+            None
+        );
+
+        let word_char_class = WordCharClass::default().with_extra_chars("-");
+        assert_eq2!(word_char_class.extra_chars, vec!['_', '-']);
+        assert_eq2!(
+            word_char_class.get_word_range_at(&line, ch!(9)),
+            Some(SelectionRange::new(ch!(2), ch!(13)))
+        );
+    }
+
+    #[test]
+    fn test_word_motions_use_word_char_class() {
+        let mut engine = mock_real_objects_for_editor::make_editor_engine_with_bounds(
+            size!(col_count: 20, row_count: 10),
+        );
+        let mut buffer = EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT));
+        buffer.set_lines(vec!["foo-bar baz".to_string()]);
+
+        // `-` is punctuation by default.
+        apply_editor_event(&mut engine, &mut buffer, EditorEvent::MoveCaretToNextWord);
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted).col_index,
+            ch!(3)
+        );
+
+        engine.config_options.word_char_class =
+            WordCharClass::default().with_extra_chars("-");
+        apply_editor_event(&mut engine, &mut buffer, EditorEvent::MoveCaretToPrevWord);
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted).col_index,
+            ch!(0)
+        );
+        apply_editor_event(&mut engine, &mut buffer, EditorEvent::MoveCaretToNextWord);
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted).col_index,
+            ch!(8)
+        );
+
+        apply_editor_event(&mut engine, &mut buffer, EditorEvent::Home);
+        apply_editor_event(&mut engine, &mut buffer, EditorEvent::DeleteWord);
+        assert_eq2!(buffer.get_lines()[0].string, "baz");
+    }
+
+    #[test]
+    fn test_double_and_triple_click() {
+        let mut engine = mock_real_objects_for_editor::make_editor_engine_with_bounds(
+            size!(col_count: 20, row_count: 10),
+        );
+        let mut buffer = EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT));
+        buffer.set_lines(vec!["foo_bar baz".to_string(), "qux".to_string()]);
+        let start = Instant::now();
+        let pos = position!(col_index: 2, row_index: 0);
+
+        // Single click moves the caret.
+        click(&mut engine, &mut buffer, pos, start);
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 2, row_index: 0)
+        );
+        assert!(buffer.get_selection_map().is_empty());
+
+        // Double click selects `foo_bar` as one word.
+        click(
+            &mut engine,
+            &mut buffer,
+            pos,
+            start + Duration::from_millis(100),
+        );
+        assert_eq2!(
+            buffer.get_selection_map().get(ch!(0)),
+            Some(&SelectionRange::new(ch!(0), ch!(7)))
+        );
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted).col_index,
+            ch!(7)
+        );
+
+        // Triple click selects the whole line.
+        click(
+            &mut engine,
+            &mut buffer,
+            pos,
+            start + Duration::from_millis(200),
+        );
+        assert_eq2!(
+            buffer.get_selection_map().get(ch!(0)),
+            Some(&SelectionRange::new(ch!(0), ch!(11)))
+        );
+
+        // A click that is too late starts over. Clicks below the content go to the last
+        // line.
+        let later = start + Duration::from_millis(300 + MULTI_CLICK_INTERVAL_MS);
+        click(&mut engine, &mut buffer, pos, later);
+        assert!(buffer.get_selection_map().is_empty());
+        click(
+            &mut engine,
+            &mut buffer,
+            position!(col_index: 9, row_index: 5),
+            later,
+        );
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 3, row_index: 1)
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_copy_plain_text {
+    use r3bl_rs_utils_core::*;
+
+    use crate::*;
+
+    #[test]
+    fn test_selected_and_plain_text_have_no_escape_sequences() {
+        let mut buffer = EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT));
+        buffer.set_lines(vec![
+            "\x1b[32mINFO\x1b[0m started".to_string(),
+            "# heading".to_string(),
+            "\x1b[1;31mERROR\x1b[0m failed".to_string(),
+        ]);
+        assert_eq2!(buffer.get_selected_text(), None);
+
+        // Select the end of the 1st line & the start of the 2nd one.
+        let first_line_width = buffer.get_line_display_width(ch!(0));
+        {
+            let (_, _, _, selection_map) = buffer.get_mut();
+            selection_map.insert(
+                ch!(1),
+                SelectionRange::new(ch!(0), ch!(1)),
+                CaretMovementDirection::Down,
+            );
+            selection_map.insert(
+                ch!(0),
+                SelectionRange::new(ch!(0), first_line_width),
+                CaretMovementDirection::Down,
+            );
+        }
+        let selected_text = buffer.get_selected_text().unwrap();
+        assert_eq2!(selected_text, "INFO started\n#");
+        assert!(!selected_text.contains('\x1b'));
+
+        let plain_text = buffer.get_plain_text();
+        assert_eq2!(plain_text, "INFO started\n# heading\nERROR failed");
+        assert!(!plain_text.contains('\x1b'));
+    }
+}
+
+#[cfg(test)]
+mod test_transpose {
+    use r3bl_rs_utils_core::*;
+
+    use super::*;
+    use crate::*;
+
+    fn make_buffer(lines: &[&str]) -> EditorBuffer {
+        let mut buffer = EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT));
+        buffer.set_lines(lines.iter().map(|it| it.to_string()).collect());
+        buffer
+    }
+
+    #[test]
+    fn test_transpose_chars() {
+        let viewport = size!(col_count: 20, row_count: 10);
+
+        // In the middle of a line, the caret moves past both chars.
+        let mut buffer = make_buffer(&["abcd"]);
+        assert!(buffer.transpose_chars(position!(col_index: 1, row_index: 0), viewport));
+        assert_eq2!(buffer.get_as_string(), "bacd");
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 2, row_index: 0)
+        );
+
+        // At the end of a line, the last 2 chars are swapped & the caret stays put.
+        assert!(buffer.transpose_chars(position!(col_index: 4, row_index: 0), viewport));
+        assert_eq2!(buffer.get_as_string(), "badc");
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 4, row_index: 0)
+        );
+
+        // At the start of the buffer there's nothing to swap.
+        assert!(!buffer.transpose_chars(position!(col_index: 0, row_index: 0), viewport));
+        assert_eq2!(buffer.get_as_string(), "badc");
+
+        // Wide & multi byte grapheme clusters are swapped whole.
+        let mut buffer = make_buffer(&["a😀é"]);
+        assert!(buffer.transpose_chars(position!(col_index: 1, row_index: 0), viewport));
+        assert_eq2!(buffer.get_as_string(), "😀aé");
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 3, row_index: 0)
+        );
+
+        // At the start of a line, the first char moves to the end of the line above.
+        let mut buffer = make_buffer(&["ab", "cd", "e", ""]);
+        assert!(buffer.transpose_chars(position!(col_index: 0, row_index: 1), viewport));
+        assert_eq2!(buffer.get_as_string(), "abc, d, e, ");
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 0, row_index: 1)
+        );
+
+        // Same at the end of a line w/ just 1 char.
+        assert!(buffer.transpose_chars(position!(col_index: 1, row_index: 2), viewport));
+        assert_eq2!(buffer.get_as_string(), "abc, de, , ");
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 0, row_index: 2)
+        );
+
+        // Nothing to swap on an empty line.
+        assert!(!buffer.transpose_chars(position!(col_index: 0, row_index: 3), viewport));
+    }
+
+    #[test]
+    fn test_transpose_words() {
+        let viewport = size!(col_count: 30, row_count: 10);
+        let word_char_class = WordCharClass::default();
+
+        // In a word, it is swapped w/ the next one.
+        let mut buffer = make_buffer(&["foo bar, baz"]);
+        assert!(buffer.transpose_words(
+            position!(col_index: 5, row_index: 0),
+            &word_char_class,
+            viewport
+        ));
+        assert_eq2!(buffer.get_as_string(), "foo baz, bar");
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 12, row_index: 0)
+        );
+
+        // At the start of a word, the one before it is swapped w/ it.
+        assert!(buffer.transpose_words(
+            position!(col_index: 4, row_index: 0),
+            &word_char_class,
+            viewport
+        ));
+        assert_eq2!(buffer.get_as_string(), "baz foo, bar");
+
+        // W/ no word after it, the last 2 words are swapped.
+        assert!(buffer.transpose_words(
+            position!(col_index: 12, row_index: 0),
+            &word_char_class,
+            viewport
+        ));
+        assert_eq2!(buffer.get_as_string(), "baz bar, foo");
+
+        // A line w/ 1 word has nothing to swap.
+        let mut buffer = make_buffer(&["  foo  "]);
+        assert!(!buffer.transpose_words(
+            position!(col_index: 3, row_index: 0),
+            &word_char_class,
+            viewport
+        ));
+    }
+
+    #[test]
+    fn test_transpose_key_bindings() {
+        let mut engine = mock_real_objects_for_editor::make_editor_engine_with_bounds(
+            size!(col_count: 20, row_count: 10),
+        );
+        let mut buffer = make_buffer(&["ab cd"]);
+        EditorEvent::apply_editor_events(
+            &mut engine,
+            &mut buffer,
+            vec![
+                EditorEvent::MoveCaret(CaretDirection::Right),
+                EditorEvent::TransposeChars,
+            ],
+            &mock_real_objects_for_editor::make_shared_global_data(None),
+            &mut mock_real_objects_for_editor::make_component_registry(),
+            FlexBoxId::from(0),
+        );
+        assert_eq2!(buffer.get_as_string(), "ba cd");
+
+        let key_map = KeyMap::default();
+        assert_eq2!(
+            key_map.translate(&InputEvent::Keyboard(
+                keypress!(@char ModifierKeysMask::CTRL, 't')
+            )),
+            Some(EditorEvent::TransposeChars)
+        );
+    }
 }
 
 pub mod mock_real_objects_for_editor {
@@ -0,0 +1,156 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::cmp::Reverse;
+
+use r3bl_rs_utils_core::*;
+
+/// Added to the score of a query grapheme that matches right after the previous one.
+const CONSECUTIVE_MATCH_BONUS: usize = 5;
+
+/// Added to the score of a query grapheme that matches at the start of a word in the
+/// candidate, eg: the `f` in `Open File` or in `openFile`.
+const WORD_START_MATCH_BONUS: usize = 3;
+
+/// The result of a successful [fuzzy_match()].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FuzzyScore {
+    /// Higher is better.
+    pub score: usize,
+    /// The logical indices of the grapheme clusters (not chars or bytes) in the candidate
+    /// that matched the query, in order. These line up w/
+    /// [GraphemeClusterSegment::logical_index] in a [UnicodeString] made from the
+    /// candidate, which is handy to highlight the matched graphemes (even wide ones).
+    pub matched_grapheme_indices: Vec<usize>,
+}
+
+/// Returns [None] unless all the grapheme clusters in `query` appear in `candidate` in the
+/// same order (ignoring case & the whitespace in `query`). Each matching grapheme scores
+/// 1, plus a bonus if it comes right after the previous match, or if it is at the start of
+/// a word.
+///
+/// The graphemes are matched greedily (each one at the first place it fits), which is
+/// good enough for short strings like command names. An empty `query` matches everything
+/// w/ a score of 0.
+pub fn fuzzy_match(query: &str, candidate: &str) -> Option<FuzzyScore> {
+    let query_us = UnicodeString::from(query);
+    let candidate_us = UnicodeString::from(candidate);
+    let mut fuzzy_score = FuzzyScore::default();
+    let mut start_index = 0;
+
+    for query_segment in query_us
+        .iter()
+        .filter(|it| !it.string.chars().all(char::is_whitespace))
+    {
+        let match_index = (start_index..candidate_us.len()).find(|index| {
+            is_same_grapheme_ignoring_case(
+                &candidate_us[*index].string,
+                &query_segment.string,
+            )
+        })?;
+
+        fuzzy_score.score += 1;
+        if match_index > 0
+            && fuzzy_score.matched_grapheme_indices.last() == Some(&(match_index - 1))
+        {
+            fuzzy_score.score += CONSECUTIVE_MATCH_BONUS;
+        }
+        if is_word_start(&candidate_us, match_index) {
+            fuzzy_score.score += WORD_START_MATCH_BONUS;
+        }
+
+        fuzzy_score.matched_grapheme_indices.push(match_index);
+        start_index = match_index + 1;
+    }
+
+    Some(fuzzy_score)
+}
+
+/// Returns the indices of the `candidates` that match `query`, best match first. Ties go to
+/// the shorter candidate, and then to the one that comes first in `candidates`, so the
+/// ranking is stable. If `query` is blank, then all the indices are returned in order.
+pub fn rank_fuzzy_matches(query: &str, candidates: &[&str]) -> Vec<usize> {
+    if query.trim().is_empty() {
+        return (0..candidates.len()).collect();
+    }
+
+    let mut matches: Vec<(usize, usize)> = candidates
+        .iter()
+        .enumerate()
+        .filter_map(|(index, candidate)| {
+            fuzzy_match(query, candidate).map(|it| (index, it.score))
+        })
+        .collect();
+
+    matches.sort_by_key(|(index, score)| {
+        (Reverse(*score), candidates[*index].chars().count(), *index)
+    });
+
+    matches.into_iter().map(|(index, _)| index).collect()
+}
+
+/// Splits `text` into runs of grapheme clusters that are either all in
+/// `matched_grapheme_indices` or all not, eg: to paint the matched graphemes w/ a different
+/// style. Returns `(run, is_matched)`.
+pub fn split_into_fuzzy_match_runs(
+    text: &str,
+    matched_grapheme_indices: &[usize],
+) -> Vec<(String, bool)> {
+    let mut runs: Vec<(String, bool)> = vec![];
+    for segment in UnicodeString::from(text).iter() {
+        push_to_fuzzy_match_runs(
+            &mut runs,
+            &segment.string,
+            matched_grapheme_indices.contains(&segment.logical_index),
+        );
+    }
+    runs
+}
+
+/// Appends `text` to the last run if it has the same `is_matched`, or starts a new run.
+pub fn push_to_fuzzy_match_runs(
+    runs: &mut Vec<(String, bool)>,
+    text: &str,
+    is_matched: bool,
+) {
+    match runs.last_mut() {
+        Some((run, is_run_matched)) if *is_run_matched == is_matched => {
+            run.push_str(text)
+        }
+        _ => runs.push((text.to_string(), is_matched)),
+    }
+}
+
+fn is_same_grapheme_ignoring_case(lhs: &str, rhs: &str) -> bool {
+    lhs.chars()
+        .flat_map(char::to_lowercase)
+        .eq(rhs.chars().flat_map(char::to_lowercase))
+}
+
+fn is_word_start(graphemes: &UnicodeString, index: usize) -> bool {
+    if index == 0 {
+        return true;
+    }
+    let first_char_of = |index: usize| graphemes[index].string.chars().next();
+    match (first_char_of(index - 1), first_char_of(index)) {
+        (Some(prev_char), Some(char)) => {
+            !prev_char.is_alphanumeric()
+                || (prev_char.is_lowercase() && char.is_uppercase())
+        }
+        _ => false,
+    }
+}
@@ -0,0 +1,122 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+#[cfg(test)]
+mod test_fuzzy_match {
+    use r3bl_rs_utils_core::*;
+
+    use crate::*;
+
+    fn get_score(query: &str, candidate: &str) -> usize {
+        fuzzy_match(query, candidate).unwrap().score
+    }
+
+    #[test]
+    fn test_fuzzy_match() {
+        // All the chars have to match, in order, ignoring case.
+        assert!(fuzzy_match("ofi", "Open File").is_some());
+        assert!(fuzzy_match("OPEN", "open file").is_some());
+        assert!(fuzzy_match("open file", "Open File").is_some());
+        assert!(fuzzy_match("fo", "Open File").is_none());
+        assert!(fuzzy_match("x", "Open File").is_none());
+        assert_eq2!(fuzzy_match("", "Open File"), Some(FuzzyScore::default()));
+
+        // The matched grapheme indices are returned, so they can be highlighted.
+        assert_eq2!(
+            fuzzy_match("ofi", "Open File")
+                .unwrap()
+                .matched_grapheme_indices,
+            vec![0, 5, 6]
+        );
+        assert_eq2!(
+            fuzzy_match("éa", "Café Bar")
+                .unwrap()
+                .matched_grapheme_indices,
+            vec![3, 6]
+        );
+
+        // Indices are of grapheme clusters, not chars, so they work w/ emoji & wide chars.
+        assert_eq2!(
+            fuzzy_match("ab", "🙏🏽 a 📦 b")
+                .unwrap()
+                .matched_grapheme_indices,
+            vec![2, 6]
+        );
+        assert_eq2!(
+            fuzzy_match("中文", "学中文")
+                .unwrap()
+                .matched_grapheme_indices,
+            vec![1, 2]
+        );
+    }
+
+    #[test]
+    fn test_fuzzy_match_scoring() {
+        // Consecutive matches score higher.
+        assert!(get_score("ope", "Open File") > get_score("ope", "Go To Preview"));
+
+        // Matches at the start of a word (including camel case) score higher.
+        assert!(get_score("gtl", "Go To Line") > get_score("gtl", "Toggle Title"));
+        assert!(get_score("of", "openFile") > get_score("of", "openfile"));
+        assert!(get_score("of", "open_file") > get_score("of", "openfile"));
+    }
+
+    #[test]
+    fn test_rank_fuzzy_matches() {
+        let candidates = ["Close File", "Open File", "Open Folder", "Go To Line"];
+
+        // Blank query keeps the order.
+        assert_eq2!(rank_fuzzy_matches(" ", &candidates), vec![0, 1, 2, 3]);
+
+        // Best match first, & ties go to the shorter candidate.
+        assert_eq2!(rank_fuzzy_matches("of", &candidates), vec![1, 2, 0]);
+        assert_eq2!(rank_fuzzy_matches("file", &candidates), vec![1, 0]);
+        assert_eq2!(rank_fuzzy_matches("gtl", &candidates), vec![3]);
+        assert_eq2!(rank_fuzzy_matches("zzz", &candidates), Vec::<usize>::new());
+
+        // Ties of the same length keep the order of the candidates (stable ranking).
+        let candidates = ["b_file", "a_file", "c_file"];
+        assert_eq2!(rank_fuzzy_matches("file", &candidates), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn test_split_into_fuzzy_match_runs() {
+        assert_eq2!(
+            split_into_fuzzy_match_runs("Open File", &[0, 5, 6]),
+            vec![
+                ("O".to_string(), true),
+                ("pen ".to_string(), false),
+                ("Fi".to_string(), true),
+                ("le".to_string(), false),
+            ]
+        );
+        assert_eq2!(
+            split_into_fuzzy_match_runs("abc", &[]),
+            vec![("abc".to_string(), false)]
+        );
+        assert_eq2!(split_into_fuzzy_match_runs("", &[]), vec![]);
+        assert_eq2!(
+            split_into_fuzzy_match_runs("🙏🏽a📦b", &[1, 3]),
+            vec![
+                ("🙏🏽".to_string(), false),
+                ("a".to_string(), true),
+                ("📦".to_string(), false),
+                ("b".to_string(), true),
+            ]
+        );
+    }
+}
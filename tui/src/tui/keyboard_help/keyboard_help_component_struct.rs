@@ -0,0 +1,475 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::{fmt::Debug, sync::Arc};
+
+use async_trait::async_trait;
+use r3bl_rs_utils_core::*;
+use r3bl_rs_utils_macro::style;
+use tokio::sync::RwLock;
+
+use crate::*;
+
+/// This is a reusable modal [Component] that lists the active key bindings, so that users
+/// can discover the shortcuts. The list is generated each time it is rendered, so it is
+/// always accurate:
+/// - The [GlobalHotkey]s are read from the [ComponentRegistry].
+/// - The editor's bindings are read from the `key_map` (which should be the same one as
+///   in [EditorEngineConfig::key_map]).
+///
+/// The bindings are grouped by category (more info in [get_keyboard_help_entries]), and
+/// the panel can be scrolled w/ Up, Down, PageUp, PageDown, Home & End.
+///
+/// To open it, the app pushes its `id` w/ [ComponentRegistry::push_modal] (eg: when
+/// [is_toggle_event](KeyboardHelpComponent::is_toggle_event) is `true`), so it can be
+/// opened over a dialog. <kbd>Esc</kbd>, <kbd>?</kbd>, or <kbd>F1</kbd> close it, & focus
+/// is restored.
+#[derive(Clone, Debug)]
+pub struct KeyboardHelpComponent {
+    pub id: FlexBoxId,
+    /// Painted in the top border of the panel.
+    pub title: String,
+    pub key_map: KeyMap,
+    pub scroll_offset_row_index: usize,
+    /// This is saved from the last render, so that scrolling knows how far it can go.
+    pub viewport_height: ChUnit,
+    /// This is saved from the last render, so that scrolling knows how far it can go.
+    pub line_count: usize,
+}
+
+/// A row in the [KeyboardHelpComponent]. All the `keys` that run the same command are
+/// joined into 1 entry.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KeyboardHelpEntry {
+    pub category: String,
+    pub keys: String,
+    pub description: String,
+}
+
+/// The category of the [GlobalHotkey]s, which are listed first.
+pub const KEYBOARD_HELP_GLOBAL_CATEGORY: &str = "Global";
+
+/// The panel is never wider than this, so that the keys & descriptions stay close.
+const MAX_PANEL_COL_COUNT: u16 = 72;
+
+pub mod keyboard_help_component_impl {
+    use super::*;
+
+    #[async_trait]
+    impl<S, A> Component<S, A> for KeyboardHelpComponent
+    where
+        S: Debug + Default + Clone + PartialEq + Sync + Send + 'static,
+        A: Debug + Default + Clone + Sync + Send + 'static,
+    {
+        fn reset(&mut self) { self.scroll_offset_row_index = 0; }
+
+        fn get_id(&self) -> FlexBoxId { self.id }
+
+        async fn handle_event(
+            &mut self,
+            args: ComponentScopeArgs<'_, S, A>,
+            input_event: &InputEvent,
+        ) -> CommonResult<EventPropagation> {
+            let ComponentScopeArgs {
+                component_registry, ..
+            } = args;
+
+            let is_esc =
+                *input_event == InputEvent::Keyboard(keypress!(@special SpecialKey::Esc));
+            if is_esc || KeyboardHelpComponent::is_toggle_event(input_event) {
+                component_registry.has_focus.reset_modal_id();
+                self.scroll_offset_row_index = 0;
+                return Ok(EventPropagation::ConsumedRender);
+            }
+
+            match ListNavigation::try_from_input_event(input_event) {
+                Some(navigation) => {
+                    self.scroll(navigation);
+                    Ok(EventPropagation::ConsumedRender)
+                }
+                // This is modal, so other keys don't get to the rest of the app.
+                None => Ok(EventPropagation::ConsumedNoRender),
+            }
+        }
+
+        /// Like the [CommandPaletteComponent], the `_current_box` is ignored since the
+        /// panel is painted over the middle of the screen.
+        async fn render(
+            &mut self,
+            args: ComponentScopeArgs<'_, S, A>,
+            _current_box: &FlexBox,
+            _surface_bounds: SurfaceBounds, /* Ignore this. */
+        ) -> CommonResult<RenderPipeline> {
+            let ComponentScopeArgs {
+                component_registry,
+                window_size,
+                ..
+            } = args;
+
+            let entries = get_keyboard_help_entries(
+                &self.key_map,
+                &component_registry.global_hotkeys,
+            );
+            let lines = layout_keyboard_help_lines(&entries);
+
+            let mut render_pipeline = render_pipeline!();
+            let Some((origin_pos, panel_size)) = get_panel_bounds(*window_size) else {
+                return Ok(render_pipeline);
+            };
+
+            // The window or the bindings might have changed since the last render.
+            self.viewport_height = panel_size.row_count - 2;
+            self.line_count = lines.len();
+            self.scroll_offset_row_index =
+                std::cmp::min(self.scroll_offset_row_index, self.get_max_scroll_offset());
+
+            render_pipeline.push(
+                ZOrder::Glass,
+                render_panel(
+                    &self.title,
+                    &lines,
+                    self.scroll_offset_row_index,
+                    origin_pos,
+                    panel_size,
+                ),
+            );
+            Ok(render_pipeline)
+        }
+    }
+
+    impl KeyboardHelpComponent {
+        /// <kbd>?</kbd> or <kbd>F1</kbd>.
+        pub fn is_toggle_event(input_event: &InputEvent) -> bool {
+            *input_event == InputEvent::Keyboard(keypress!(@char '?'))
+                || *input_event == InputEvent::Keyboard(keypress!(@fn FunctionKey::F1))
+        }
+
+        /// Call this when the editor's [EditorEngineConfig::key_map] changes.
+        pub fn set_key_map(&mut self, key_map: KeyMap) {
+            self.key_map = key_map;
+            self.scroll_offset_row_index = 0;
+        }
+
+        /// Scroll w/in the lines (from the last render). Paging scrolls by the viewport
+        /// height.
+        pub fn scroll(&mut self, navigation: ListNavigation) {
+            let page_size = std::cmp::max(ch!(@to_usize self.viewport_height), 1);
+            self.scroll_offset_row_index = match navigation {
+                ListNavigation::Up => self.scroll_offset_row_index.saturating_sub(1),
+                ListNavigation::Down => self.scroll_offset_row_index + 1,
+                ListNavigation::PageUp => {
+                    self.scroll_offset_row_index.saturating_sub(page_size)
+                }
+                ListNavigation::PageDown => self.scroll_offset_row_index + page_size,
+                ListNavigation::Home => 0,
+                ListNavigation::End => usize::MAX,
+            }
+            .min(self.get_max_scroll_offset());
+        }
+
+        /// Scrolling stops when the last line is at the bottom of the viewport.
+        fn get_max_scroll_offset(&self) -> usize {
+            self.line_count
+                .saturating_sub(ch!(@to_usize self.viewport_height))
+        }
+    }
+}
+pub use keyboard_help_component_impl::*;
+
+/// The [GlobalHotkey]s come first (in the [KEYBOARD_HELP_GLOBAL_CATEGORY]), followed by
+/// the bindings (& then the chords) in the `key_map` grouped by
+/// [EditorEvent::get_help_category]. Categories & entries are in the order in which they
+/// first appear. Keys that run the same command are joined into 1 entry.
+pub fn get_keyboard_help_entries<S, A>(
+    key_map: &KeyMap,
+    global_hotkeys: &[GlobalHotkey<S, A>],
+) -> Vec<KeyboardHelpEntry>
+where
+    S: Debug + Default + Clone + PartialEq + Sync + Send,
+    A: Debug + Default + Clone + Sync + Send,
+{
+    let mut entries: Vec<KeyboardHelpEntry> = vec![];
+
+    let mut add_entry = |category: &str, keys: String, description: String| {
+        if let Some(existing_entry) = entries
+            .iter_mut()
+            .find(|it| it.category == category && it.description == description)
+        {
+            existing_entry.keys = format!("{}, {}", existing_entry.keys, keys);
+        } else {
+            entries.push(KeyboardHelpEntry {
+                category: category.to_string(),
+                keys,
+                description,
+            });
+        }
+    };
+
+    for global_hotkey in global_hotkeys {
+        let keys: Vec<String> = global_hotkey
+            .input_events
+            .iter()
+            .filter_map(|it| match it {
+                InputEvent::Keyboard(key_press) => Some(key_press.to_string()),
+                _ => None,
+            })
+            .collect();
+        if keys.is_empty() {
+            continue;
+        }
+        add_entry(
+            KEYBOARD_HELP_GLOBAL_CATEGORY,
+            keys.join(", "),
+            global_hotkey.description.clone(),
+        );
+    }
+
+    for (key_press, editor_event) in &key_map.entries {
+        add_entry(
+            editor_event.get_help_category(),
+            key_press.to_string(),
+            editor_event.get_help_description(),
+        );
+    }
+
+    for (keys, editor_event) in &key_map.chords {
+        let keys: Vec<String> = keys.iter().map(|it| it.to_string()).collect();
+        add_entry(
+            editor_event.get_help_category(),
+            keys.join(" "),
+            editor_event.get_help_description(),
+        );
+    }
+
+    // Group the entries by category (the sort is stable).
+    let mut categories: Vec<String> = vec![];
+    for entry in &entries {
+        if !categories.contains(&entry.category) {
+            categories.push(entry.category.clone());
+        }
+    }
+    entries.sort_by_key(|entry| categories.iter().position(|it| *it == entry.category));
+    entries
+}
+
+/// A line in the panel of the [KeyboardHelpComponent].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum KeyboardHelpLine {
+    Heading(String),
+    Entry { keys: String, description: String },
+    Blank,
+}
+
+/// Each category gets a [KeyboardHelpLine::Heading], & there is a
+/// [KeyboardHelpLine::Blank] line in between categories.
+pub fn layout_keyboard_help_lines(
+    entries: &[KeyboardHelpEntry],
+) -> Vec<KeyboardHelpLine> {
+    let mut lines = vec![];
+    let mut maybe_current_category: Option<&str> = None;
+
+    for entry in entries {
+        if maybe_current_category != Some(entry.category.as_str()) {
+            if maybe_current_category.is_some() {
+                lines.push(KeyboardHelpLine::Blank);
+            }
+            lines.push(KeyboardHelpLine::Heading(entry.category.clone()));
+            maybe_current_category = Some(entry.category.as_str());
+        }
+        lines.push(KeyboardHelpLine::Entry {
+            keys: entry.keys.clone(),
+            description: entry.description.clone(),
+        });
+    }
+
+    lines
+}
+
+/// The panel is centered in the window, w/ a 1 col & row margin. Returns [None] if the
+/// window is too small to fit a panel w/ any content.
+fn get_panel_bounds(window_size: Size) -> Option<(Position, Size)> {
+    let col_count = std::cmp::min(*window_size.col_count, MAX_PANEL_COL_COUNT + 2);
+    let (col_count, row_count) = (
+        col_count.checked_sub(2)?,
+        (*window_size.row_count).checked_sub(2)?,
+    );
+    if col_count < 10 || row_count < 3 {
+        return None;
+    }
+    let origin_pos = position!(
+        col_index: (*window_size.col_count - col_count) / 2,
+        row_index: 1
+    );
+    Some((
+        origin_pos,
+        size!(col_count: col_count, row_count: row_count),
+    ))
+}
+
+/// The keys col is as wide as the widest keys (in all the `lines`, so that it doesn't
+/// change while scrolling), but no more than half the panel.
+fn render_panel(
+    title: &str,
+    lines: &[KeyboardHelpLine],
+    scroll_offset_row_index: usize,
+    origin_pos: Position,
+    panel_size: Size,
+) -> RenderOps {
+    let mut ops = render_ops!();
+    let inner_col_count = ch!(@to_usize panel_size.col_count - 2);
+    let inner_row_count = ch!(@to_usize panel_size.row_count - 2);
+    let key_col_count = std::cmp::min(
+        lines
+            .iter()
+            .filter_map(|it| match it {
+                KeyboardHelpLine::Entry { keys, .. } => {
+                    Some(ch!(@to_usize UnicodeString::from(keys.as_str()).display_width))
+                }
+                _ => None,
+            })
+            .max()
+            .unwrap_or(0),
+        inner_col_count / 2,
+    );
+    let border_style = get_keyboard_help_border_style();
+
+    // Top border w/ the title.
+    let title = format!(" {title} ");
+    let title = UnicodeString::from(title.as_str())
+        .truncate_end_to_fit_width(ch!(inner_col_count))
+        .to_string();
+    let title_width = ch!(@to_usize UnicodeString::from(title.as_str()).display_width);
+    ops.push(RenderOp::ResetColor);
+    ops.push(RenderOp::MoveCursorPositionAbs(origin_pos));
+    ops.push(RenderOp::ApplyColors(Some(border_style)));
+    ops.push(RenderOp::PaintTextWithAttributes(
+        format!(
+            "{}{}{}{}",
+            BorderGlyphCharacter::TopLeft.as_ref(),
+            title,
+            BorderGlyphCharacter::Horizontal
+                .as_ref()
+                .repeat(inner_col_count - title_width),
+            BorderGlyphCharacter::TopRight.as_ref()
+        ),
+        Some(border_style),
+    ));
+
+    // Content, & the blank rows below it.
+    for row_index in 0..inner_row_count {
+        let row_pos = origin_pos + position!(col_index: 0, row_index: row_index + 1);
+        let (text, maybe_style) = match lines.get(scroll_offset_row_index + row_index) {
+            Some(KeyboardHelpLine::Heading(category)) => {
+                (category.clone(), Some(get_keyboard_help_heading_style()))
+            }
+            Some(KeyboardHelpLine::Entry { keys, description }) => {
+                let keys = UnicodeString::from(keys.as_str());
+                let keys = keys.truncate_end_to_fit_width(ch!(key_col_count));
+                let padding = SPACER.repeat(
+                    key_col_count
+                        - ch!(@to_usize UnicodeString::from(keys).display_width),
+                );
+                (format!(" {keys}{padding}  {description}"), None)
+            }
+            Some(KeyboardHelpLine::Blank) | None => (String::new(), None),
+        };
+        let text = UnicodeString::from(text.as_str());
+        let text = text.truncate_end_to_fit_width(ch!(inner_col_count));
+        let padding = SPACER.repeat(
+            inner_col_count - ch!(@to_usize UnicodeString::from(text).display_width),
+        );
+
+        ops.push(RenderOp::ResetColor);
+        ops.push(RenderOp::MoveCursorPositionAbs(row_pos));
+        ops.push(RenderOp::ApplyColors(Some(border_style)));
+        ops.push(RenderOp::PaintTextWithAttributes(
+            BorderGlyphCharacter::Vertical.as_ref().to_string(),
+            Some(border_style),
+        ));
+        ops.push(RenderOp::ResetColor);
+        ops.push(RenderOp::PaintTextWithAttributes(
+            format!("{text}{padding}"),
+            maybe_style,
+        ));
+        ops.push(RenderOp::ApplyColors(Some(border_style)));
+        ops.push(RenderOp::PaintTextWithAttributes(
+            BorderGlyphCharacter::Vertical.as_ref().to_string(),
+            Some(border_style),
+        ));
+    }
+
+    // Bottom border.
+    let row_pos = origin_pos + position!(col_index: 0, row_index: inner_row_count + 1);
+    ops.push(RenderOp::ResetColor);
+    ops.push(RenderOp::MoveCursorPositionAbs(row_pos));
+    ops.push(RenderOp::ApplyColors(Some(border_style)));
+    ops.push(RenderOp::PaintTextWithAttributes(
+        format!(
+            "{}{}{}",
+            BorderGlyphCharacter::BottomLeft.as_ref(),
+            BorderGlyphCharacter::Horizontal
+                .as_ref()
+                .repeat(inner_col_count),
+            BorderGlyphCharacter::BottomRight.as_ref()
+        ),
+        Some(border_style),
+    ));
+    ops.push(RenderOp::ResetColor);
+
+    ops
+}
+
+/// This is the style for the border & title of the [KeyboardHelpComponent].
+pub fn get_keyboard_help_border_style() -> Style {
+    style! {
+        color_fg: TuiColor::Basic(ANSIBasicColor::Cyan)
+    }
+}
+
+/// This is the style for the category headings in the [KeyboardHelpComponent].
+pub fn get_keyboard_help_heading_style() -> Style {
+    style! {
+        attrib: [bold, underline]
+    }
+}
+
+mod constructor {
+    use super::*;
+
+    impl KeyboardHelpComponent {
+        /// The `key_map` should be the same one that the editor uses (in
+        /// [EditorEngineConfig::key_map]).
+        pub fn new(id: FlexBoxId, title: &str, key_map: KeyMap) -> Self {
+            Self {
+                id,
+                title: title.to_string(),
+                key_map,
+                scroll_offset_row_index: 0,
+                viewport_height: ch!(0),
+                line_count: 0,
+            }
+        }
+
+        pub fn new_shared(
+            id: FlexBoxId,
+            title: &str,
+            key_map: KeyMap,
+        ) -> Arc<RwLock<Self>> {
+            Arc::new(RwLock::new(KeyboardHelpComponent::new(id, title, key_map)))
+        }
+    }
+}
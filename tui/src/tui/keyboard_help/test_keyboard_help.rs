@@ -0,0 +1,163 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+#[cfg(test)]
+mod test_keyboard_help_entries {
+    use r3bl_redux::*;
+    use r3bl_rs_utils_core::*;
+
+    use crate::{test_dialog::mock_real_objects_for_dialog, *};
+
+    type State = mock_real_objects_for_dialog::State;
+
+    fn on_quit(_: &SharedStore<State, String>, _: &InputEvent) -> EventPropagation {
+        EventPropagation::ExitMainEventLoop
+    }
+
+    fn entry(category: &str, keys: &str, description: &str) -> KeyboardHelpEntry {
+        KeyboardHelpEntry {
+            category: category.to_string(),
+            keys: keys.to_string(),
+            description: description.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_get_keyboard_help_entries() {
+        let mut component_registry = ComponentRegistry::<State, String>::default();
+        component_registry.register_global_hotkey(
+            vec![
+                InputEvent::Keyboard(keypress!(@char ModifierKeysMask::CTRL, 'q')),
+                InputEvent::Keyboard(keypress!(@fn FunctionKey::F10)),
+            ],
+            "Quit",
+            on_quit,
+        );
+
+        let key_map = KeyMap {
+            entries: vec![
+                (
+                    keypress!(@special SpecialKey::Backspace),
+                    EditorEvent::Backspace,
+                ),
+                (keypress!(@special SpecialKey::Home), EditorEvent::Home),
+                (keypress!(@special SpecialKey::Delete), EditorEvent::Delete),
+                (
+                    keypress!(@char ModifierKeysMask::CTRL, 'a'),
+                    EditorEvent::Home,
+                ),
+            ],
+            chords: vec![(
+                vec![
+                    keypress!(@char ModifierKeysMask::CTRL, 'x'),
+                    keypress!(@char ModifierKeysMask::CTRL, 'a'),
+                ],
+                EditorEvent::Home,
+            )],
+        };
+
+        // Grouped by category, & the keys for the same command are joined.
+        assert_eq2!(
+            get_keyboard_help_entries(&key_map, &component_registry.global_hotkeys),
+            vec![
+                entry(KEYBOARD_HELP_GLOBAL_CATEGORY, "Ctrl+q, F10", "Quit"),
+                entry("Editing", "Backspace", "Delete char before caret"),
+                entry("Editing", "Delete", "Delete char at caret"),
+                entry(
+                    "Navigation",
+                    "Home, Ctrl+a, Ctrl+x Ctrl+a",
+                    "Go to start of line"
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_every_default_binding_is_listed() {
+        let key_map = KeyMap::default();
+        let entries = get_keyboard_help_entries::<State, String>(&key_map, &[]);
+        for (key_press, editor_event) in &key_map.entries {
+            assert!(entries.iter().any(|it| {
+                it.description == editor_event.get_help_description()
+                    && it.keys.split(", ").any(|it| it == key_press.to_string())
+            }));
+        }
+    }
+
+    #[test]
+    fn test_layout_keyboard_help_lines() {
+        let entries = vec![
+            entry("Editing", "Backspace", "Delete char before caret"),
+            entry("Navigation", "Home", "Go to start of line"),
+        ];
+        assert_eq2!(
+            layout_keyboard_help_lines(&entries),
+            vec![
+                KeyboardHelpLine::Heading("Editing".to_string()),
+                KeyboardHelpLine::Entry {
+                    keys: "Backspace".to_string(),
+                    description: "Delete char before caret".to_string(),
+                },
+                KeyboardHelpLine::Blank,
+                KeyboardHelpLine::Heading("Navigation".to_string()),
+                KeyboardHelpLine::Entry {
+                    keys: "Home".to_string(),
+                    description: "Go to start of line".to_string(),
+                },
+            ]
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_keyboard_help_component {
+    use r3bl_rs_utils_core::*;
+
+    use crate::*;
+
+    #[test]
+    fn test_is_toggle_event() {
+        assert!(KeyboardHelpComponent::is_toggle_event(
+            &InputEvent::Keyboard(keypress!(@char '?'))
+        ));
+        assert!(KeyboardHelpComponent::is_toggle_event(
+            &InputEvent::Keyboard(keypress!(@fn FunctionKey::F1))
+        ));
+        assert!(!KeyboardHelpComponent::is_toggle_event(
+            &InputEvent::Keyboard(keypress!(@char 'h'))
+        ));
+    }
+
+    #[test]
+    fn test_scroll() {
+        let mut component =
+            KeyboardHelpComponent::new(FlexBoxId::from(0), "Help", KeyMap::default());
+        component.line_count = 10;
+        component.viewport_height = ch!(4);
+
+        component.scroll(ListNavigation::Down);
+        assert_eq2!(component.scroll_offset_row_index, 1);
+
+        // Can't scroll past the last line (at the bottom of the viewport).
+        component.scroll(ListNavigation::End);
+        assert_eq2!(component.scroll_offset_row_index, 6);
+
+        // Changing the key map scrolls back to the top.
+        component.set_key_map(KeyMap::default());
+        assert_eq2!(component.scroll_offset_row_index, 0);
+    }
+}
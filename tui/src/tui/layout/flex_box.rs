@@ -74,7 +74,18 @@ pub struct FlexBox {
     pub style_adjusted_bounds_size: Size,
     pub requested_size_percent: RequestedSizePercent,
     pub insertion_pos_for_next_box: Option<Position>,
+    /// The space (in the [LayoutDirection] of this box) that the last child gave up
+    /// because of its [max size](FlexBoxProps::maybe_max_size). It is offered to the next
+    /// child.
+    pub unallocated_size_for_next_box: ChUnit,
+    /// The sum of the requested percentages (in the [LayoutDirection] of this box) of the
+    /// children that have been added so far. The next child starts at the edge that is
+    /// rounded from this sum, so that the children tile this box w/out gaps.
+    pub requested_percent_sum_for_next_box: u16,
     pub maybe_computed_style: Option<Style>,
+    /// From [FlexBoxProps::maybe_tab_index]. It is passed to the [ComponentRegistry]
+    /// when a component is rendered in this box.
+    pub maybe_tab_index: Option<usize>,
 }
 
 mod flex_box_impl {
@@ -101,10 +112,19 @@ mod flex_box_impl {
                     "insertion_pos_for_next_box",
                     format_option!(&self.insertion_pos_for_next_box),
                 )
+                .field(
+                    "unallocated_size_for_next_box",
+                    &self.unallocated_size_for_next_box,
+                )
+                .field(
+                    "requested_percent_sum_for_next_box",
+                    &self.requested_percent_sum_for_next_box,
+                )
                 .field(
                     "maybe_computed_style",
                     format_option!(&self.maybe_computed_style),
                 )
+                .field("maybe_tab_index", format_option!(&self.maybe_tab_index))
                 .finish()
         }
     }
@@ -55,12 +55,27 @@ pub trait PerformPositioningAndSizing {
 }
 
 /// Properties that are needed to create a [FlexBox].
+///
+/// The size of the box is the `requested_size_percent` of its container, clamped into
+/// `maybe_min_size` & `maybe_max_size` (eg: to keep a sidebar at least 20 cols wide on a
+/// small terminal). In both of these a `0` col or row count means that there is no
+/// constraint for that dimension. When a box grows to its min size, the next siblings
+/// get less space, and when it shrinks to its max size, the space that it gave up is
+/// offered to the next sibling. A box never overflows its container, even if that means
+/// that it is smaller than its min size.
+///
+/// `maybe_tab_index` sets the position of the component that is rendered in this box in
+/// the focus order of the [ComponentRegistry]. More info in
+/// [focus_next](ComponentRegistry::focus_next).
 #[derive(Clone, Debug, Default)]
 pub struct FlexBoxProps {
     pub id: FlexBoxId,
     pub dir: LayoutDirection,
     pub requested_size_percent: RequestedSizePercent,
     pub maybe_styles: Option<Vec<Style>>,
+    pub maybe_min_size: Option<Size>,
+    pub maybe_max_size: Option<Size>,
+    pub maybe_tab_index: Option<usize>,
 }
 
 /// Properties that are needed to create a [Surface].
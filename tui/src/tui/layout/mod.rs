@@ -19,14 +19,20 @@
 pub mod flex_box;
 pub mod layout_error;
 pub mod layout_management;
+pub mod split_pane;
 pub mod surface;
+pub mod toast;
 
 // Re-export the public items.
 pub use flex_box::*;
 pub use layout_error::*;
 pub use layout_management::*;
+pub use split_pane::*;
 pub use surface::*;
+pub use toast::*;
 
 // Tests.
 mod test_surface_2_col_complex;
 mod test_surface_2_col_simple;
+mod test_surface_percent_rounding;
+mod test_surface_size_constraints;
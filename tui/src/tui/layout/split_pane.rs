@@ -0,0 +1,513 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use r3bl_rs_utils_core::*;
+use r3bl_rs_utils_macro::style;
+use serde::{Deserialize, Serialize};
+
+use crate::*;
+
+/// Lays out 2 panes (boxes) side by side in the current box of a [Surface], w/ a divider
+/// line between them. The `split_percent` is the size of the first pane (width for
+/// [LayoutDirection::Horizontal], height for [LayoutDirection::Vertical]), & the second
+/// pane gets the rest.
+///
+/// The split can be changed at runtime by the user (see [SplitPane::handle_event]):
+/// - Keyboard: `Alt+Shift+Left` / `Alt+Shift+Right` (or `Up` / `Down` for a vertical
+///   split) nudge the divider by `nudge_step_percent`.
+/// - Mouse: the divider can be dragged w/ the left button.
+///
+/// Since the [Surface] is created from scratch on every render, the [SplitPane] must be
+/// owned by the app (eg: as a field in the struct that implements [App]) so that the
+/// split is kept across renders.
+///
+/// Here's how to use it in a [SurfaceRender]:
+/// 1. Call [box_start!] for the container, w/ the same `dir` as the [SplitPane].
+/// 2. Call [SplitPane::pane_box_start] for [SplitPaneSide::First], render its contents,
+///    & call [box_end!].
+/// 3. Do the same for [SplitPaneSide::Second].
+/// 4. Call [SplitPane::render_divider], then [box_end!] for the container.
+///
+/// The divider is painted over the last column (or row) of the first pane, so give the
+/// first pane some padding in its style in order to keep its content clear of it.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SplitPane {
+    pub dir: LayoutDirection,
+    /// Size of the first pane, as a percentage of the container box.
+    pub split_percent: u8,
+    /// Neither pane can get smaller than this percentage of the container box.
+    pub min_percent: u8,
+    pub nudge_step_percent: u8,
+    /// [get_default_split_pane_divider_style] is used if this isn't set.
+    pub maybe_divider_style: Option<Style>,
+    /// Saved by [SplitPane::render_divider], so that mouse events can be mapped to the
+    /// divider.
+    pub maybe_container_bounds: Option<SurfaceBounds>,
+    pub is_dragging: bool,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SplitPaneSide {
+    First,
+    Second,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SplitPaneNudge {
+    /// Make the first pane bigger.
+    Grow,
+    /// Make the first pane smaller.
+    Shrink,
+}
+
+pub const SPLIT_PANE_DEFAULT_MIN_PERCENT: u8 = 10;
+pub const SPLIT_PANE_DEFAULT_NUDGE_STEP_PERCENT: u8 = 5;
+
+impl Default for SplitPane {
+    fn default() -> Self { Self::new(LayoutDirection::Horizontal, 50) }
+}
+
+mod constructor {
+    use super::*;
+
+    impl SplitPane {
+        pub fn new(dir: LayoutDirection, split_percent: u8) -> Self {
+            let mut it = Self {
+                dir,
+                split_percent,
+                min_percent: SPLIT_PANE_DEFAULT_MIN_PERCENT,
+                nudge_step_percent: SPLIT_PANE_DEFAULT_NUDGE_STEP_PERCENT,
+                maybe_divider_style: None,
+                maybe_container_bounds: None,
+                is_dragging: false,
+            };
+            it.set_split_percent(split_percent);
+            it
+        }
+    }
+}
+
+mod split_pane_impl {
+    use super::*;
+
+    impl SplitPane {
+        /// The `split_percent` is clamped so that neither pane is smaller than
+        /// `min_percent`.
+        pub fn set_split_percent(&mut self, split_percent: u8) {
+            let min = self.min_percent.min(50);
+            self.split_percent = split_percent.clamp(min, 100 - min);
+        }
+
+        pub fn nudge(&mut self, nudge: SplitPaneNudge) {
+            let split_percent = match nudge {
+                SplitPaneNudge::Grow => {
+                    self.split_percent.saturating_add(self.nudge_step_percent)
+                }
+                SplitPaneNudge::Shrink => {
+                    self.split_percent.saturating_sub(self.nudge_step_percent)
+                }
+            };
+            self.set_split_percent(split_percent);
+        }
+
+        pub fn get_pane_percent(&self, side: SplitPaneSide) -> u8 {
+            match side {
+                SplitPaneSide::First => self.split_percent,
+                SplitPaneSide::Second => 100 - self.split_percent,
+            }
+        }
+
+        pub fn get_pane_size_percent(
+            &self,
+            side: SplitPaneSide,
+        ) -> CommonResult<RequestedSizePercent> {
+            let pane_percent = self.get_pane_percent(side) as i32;
+            Ok(match self.dir {
+                LayoutDirection::Vertical => {
+                    requested_size_percent!(width: 100, height: pane_percent)
+                }
+                LayoutDirection::Horizontal => {
+                    requested_size_percent!(width: pane_percent, height: 100)
+                }
+            })
+        }
+
+        /// Start the box for the given `side` in the current box of the `surface`. Make
+        /// sure to make a corresponding call to [box_end!].
+        pub fn pane_box_start(
+            &self,
+            surface: &mut Surface,
+            side: SplitPaneSide,
+            id: FlexBoxId,
+            dir: LayoutDirection,
+            maybe_styles: Option<Vec<Style>>,
+        ) -> CommonResult<()> {
+            surface.box_start(FlexBoxProps {
+                id,
+                dir,
+                requested_size_percent: self.get_pane_size_percent(side)?,
+                maybe_styles,
+                ..Default::default()
+            })
+        }
+
+        /// Paint the divider in the current box of the `surface` (which is the container
+        /// of both panes) at [ZOrder::High], so that it is painted over the contents of
+        /// the panes.
+        pub fn render_divider(&mut self, surface: &mut Surface) -> CommonResult<()> {
+            let container_box = surface.current_box()?;
+            let container_bounds = SurfaceBounds {
+                origin_pos: container_box.origin_pos,
+                box_size: container_box.bounds_size,
+            };
+            self.maybe_container_bounds = Some(container_bounds);
+
+            let render_ops = self.render_divider_in(container_bounds);
+            surface.render_pipeline.push(ZOrder::High, render_ops);
+
+            Ok(())
+        }
+
+        /// This uses the same math as the [Surface] to figure out where the first pane
+        /// ends, & returns the position of its last column (or row).
+        pub fn get_divider_pos(&self, container_bounds: SurfaceBounds) -> Position {
+            let SurfaceBounds {
+                origin_pos,
+                box_size,
+            } = container_bounds;
+            let split_percent = self.split_percent as u16;
+            match self.dir {
+                LayoutDirection::Vertical => {
+                    let height =
+                        calc_percentage_rounded(split_percent, box_size.row_count);
+                    position!(
+                        col_index: origin_pos.col_index,
+                        row_index: origin_pos.row_index + height - 1
+                    )
+                }
+                LayoutDirection::Horizontal => {
+                    let width =
+                        calc_percentage_rounded(split_percent, box_size.col_count);
+                    position!(
+                        col_index: origin_pos.col_index + width - 1,
+                        row_index: origin_pos.row_index
+                    )
+                }
+            }
+        }
+
+        pub fn render_divider_in(&self, container_bounds: SurfaceBounds) -> RenderOps {
+            let mut render_ops = render_ops!();
+            let divider_pos = self.get_divider_pos(container_bounds);
+            let style = self
+                .maybe_divider_style
+                .unwrap_or_else(get_default_split_pane_divider_style);
+            let box_size = container_bounds.box_size;
+
+            match self.dir {
+                LayoutDirection::Vertical => {
+                    render_ops.push(RenderOp::MoveCursorPositionAbs(divider_pos));
+                    render_ops.push(RenderOp::PaintTextWithAttributes(
+                        SPLIT_PANE_HORIZ_LINE.repeat(ch!(@to_usize box_size.col_count)),
+                        Some(style),
+                    ));
+                }
+                LayoutDirection::Horizontal => {
+                    for row_offset in 0..*box_size.row_count {
+                        render_ops.push(RenderOp::MoveCursorPositionAbs(
+                            divider_pos + position!(col_index: 0, row_index: row_offset),
+                        ));
+                        render_ops.push(RenderOp::PaintTextWithAttributes(
+                            SPLIT_PANE_VERT_LINE.into(),
+                            Some(style),
+                        ));
+                    }
+                }
+            }
+            render_ops.push(RenderOp::ResetColor);
+
+            render_ops
+        }
+    }
+}
+
+mod handle_event_impl {
+    use super::*;
+
+    impl SplitPane {
+        /// Call this from [App::app_handle_event] before routing the `input_event` to the
+        /// focused component. Returns [EventPropagation::ConsumedRender] if the split has
+        /// been changed, or if a drag is in progress.
+        pub fn handle_event(&mut self, input_event: &InputEvent) -> EventPropagation {
+            match input_event {
+                InputEvent::Keyboard(keypress) => match self.get_nudge(keypress) {
+                    Some(nudge) => {
+                        self.nudge(nudge);
+                        EventPropagation::ConsumedRender
+                    }
+                    None => EventPropagation::Propagate,
+                },
+                InputEvent::Mouse(mouse_input) => self.handle_mouse_input(mouse_input),
+                _ => EventPropagation::Propagate,
+            }
+        }
+
+        fn get_nudge(&self, keypress: &KeyPress) -> Option<SplitPaneNudge> {
+            let (shrink_key, grow_key) = match self.dir {
+                LayoutDirection::Vertical => (SpecialKey::Up, SpecialKey::Down),
+                LayoutDirection::Horizontal => (SpecialKey::Left, SpecialKey::Right),
+            };
+            let mask = ModifierKeysMask::ALT | ModifierKeysMask::SHIFT;
+
+            if *keypress == keypress!(@special mask, grow_key) {
+                Some(SplitPaneNudge::Grow)
+            } else if *keypress == keypress!(@special mask, shrink_key) {
+                Some(SplitPaneNudge::Shrink)
+            } else {
+                None
+            }
+        }
+
+        fn handle_mouse_input(&mut self, mouse_input: &MouseInput) -> EventPropagation {
+            let Some(container_bounds) = self.maybe_container_bounds else {
+                return EventPropagation::Propagate;
+            };
+
+            match mouse_input.kind {
+                MouseInputKind::MouseDown(Button::Left)
+                    if self.is_on_divider(container_bounds, mouse_input.pos) =>
+                {
+                    self.is_dragging = true;
+                    EventPropagation::ConsumedRender
+                }
+                MouseInputKind::MouseDrag(Button::Left) if self.is_dragging => {
+                    self.drag_to(container_bounds, mouse_input.pos);
+                    EventPropagation::ConsumedRender
+                }
+                MouseInputKind::MouseUp(Button::Left) if self.is_dragging => {
+                    self.is_dragging = false;
+                    self.drag_to(container_bounds, mouse_input.pos);
+                    EventPropagation::ConsumedRender
+                }
+                _ => EventPropagation::Propagate,
+            }
+        }
+
+        fn is_on_divider(&self, container_bounds: SurfaceBounds, pos: Position) -> bool {
+            let SurfaceBounds {
+                origin_pos,
+                box_size,
+            } = container_bounds;
+            let divider_pos = self.get_divider_pos(container_bounds);
+            match self.dir {
+                LayoutDirection::Vertical => {
+                    pos.row_index == divider_pos.row_index
+                        && pos.col_index >= origin_pos.col_index
+                        && pos.col_index < origin_pos.col_index + box_size.col_count
+                }
+                LayoutDirection::Horizontal => {
+                    pos.col_index == divider_pos.col_index
+                        && pos.row_index >= origin_pos.row_index
+                        && pos.row_index < origin_pos.row_index + box_size.row_count
+                }
+            }
+        }
+
+        /// Move the divider so that it is at `pos`.
+        fn drag_to(&mut self, container_bounds: SurfaceBounds, pos: Position) {
+            let SurfaceBounds {
+                origin_pos,
+                box_size,
+            } = container_bounds;
+            let (offset, length) = match self.dir {
+                LayoutDirection::Vertical => {
+                    (pos.row_index - origin_pos.row_index + 1, box_size.row_count)
+                }
+                LayoutDirection::Horizontal => {
+                    (pos.col_index - origin_pos.col_index + 1, box_size.col_count)
+                }
+            };
+            if *length == 0 {
+                return;
+            }
+            let split_percent = (*offset as u32 * 100 / *length as u32).min(100) as u8;
+            self.set_split_percent(split_percent);
+        }
+    }
+}
+
+pub const SPLIT_PANE_VERT_LINE: &str = "│";
+pub const SPLIT_PANE_HORIZ_LINE: &str = "─";
+
+/// This is the default style for the divider of a [SplitPane].
+pub fn get_default_split_pane_divider_style() -> Style {
+    style! {
+        attrib: [dim]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn container_bounds() -> SurfaceBounds {
+        SurfaceBounds {
+            origin_pos: position!(col_index: 0, row_index: 0),
+            box_size: size!(col_count: 100, row_count: 10),
+        }
+    }
+
+    fn mouse(kind: MouseInputKind, col_index: u16, row_index: u16) -> InputEvent {
+        InputEvent::Mouse(MouseInput {
+            pos: position!(col_index: col_index, row_index: row_index),
+            kind,
+            maybe_modifier_keys: None,
+        })
+    }
+
+    #[test]
+    fn test_split_pane_nudge() {
+        let mut split_pane = SplitPane::new(LayoutDirection::Horizontal, 50);
+        assert_eq2!(split_pane.get_pane_percent(SplitPaneSide::Second), 50);
+
+        let grow = InputEvent::Keyboard(keypress!(
+            @special ModifierKeysMask::ALT | ModifierKeysMask::SHIFT, SpecialKey::Right
+        ));
+        assert_eq2!(
+            split_pane.handle_event(&grow),
+            EventPropagation::ConsumedRender
+        );
+        assert_eq2!(split_pane.split_percent, 55);
+        assert_eq2!(split_pane.get_pane_percent(SplitPaneSide::Second), 45);
+
+        // Up / Down are only for a vertical split.
+        let other = InputEvent::Keyboard(keypress!(
+            @special ModifierKeysMask::ALT | ModifierKeysMask::SHIFT, SpecialKey::Up
+        ));
+        assert_eq2!(split_pane.handle_event(&other), EventPropagation::Propagate);
+
+        // Clamped to `min_percent`.
+        for _ in 0..20 {
+            split_pane.nudge(SplitPaneNudge::Shrink);
+        }
+        assert_eq2!(split_pane.split_percent, SPLIT_PANE_DEFAULT_MIN_PERCENT);
+        assert_eq2!(
+            SplitPane::new(LayoutDirection::Vertical, 100).split_percent,
+            90
+        );
+    }
+
+    #[test]
+    fn test_split_pane_layout() -> CommonResult<()> {
+        throws!({
+            let mut split_pane = SplitPane::new(LayoutDirection::Horizontal, 30);
+            let mut surface = Surface::default();
+            surface.surface_start(SurfaceProps {
+                pos: position!(col_index: 0, row_index: 0),
+                size: size!(col_count: 100, row_count: 10),
+            })?;
+            box_start!(
+                in: surface,
+                id: FlexBoxId::from(0),
+                dir: LayoutDirection::Horizontal,
+                requested_size_percent: requested_size_percent!(width: 100, height: 100),
+                styles: [],
+            );
+
+            split_pane.pane_box_start(
+                &mut surface,
+                SplitPaneSide::First,
+                FlexBoxId::from(1),
+                LayoutDirection::Vertical,
+                None,
+            )?;
+            assert_eq2!(surface.current_box()?.bounds_size.col_count, ch!(30));
+            box_end!(in: surface);
+
+            split_pane.pane_box_start(
+                &mut surface,
+                SplitPaneSide::Second,
+                FlexBoxId::from(2),
+                LayoutDirection::Vertical,
+                None,
+            )?;
+            let second_box = surface.current_box()?;
+            assert_eq2!(
+                second_box.origin_pos,
+                position!(col_index: 30, row_index: 0)
+            );
+            assert_eq2!(second_box.bounds_size.col_count, ch!(70));
+            box_end!(in: surface);
+
+            split_pane.render_divider(&mut surface)?;
+            box_end!(in: surface);
+            surface.surface_end()?;
+
+            assert_eq2!(split_pane.maybe_container_bounds, Some(container_bounds()));
+            let render_ops = surface
+                .render_pipeline
+                .get_all_render_op_in(ZOrder::High)
+                .unwrap();
+            assert_eq2!(
+                render_ops[0],
+                RenderOp::MoveCursorPositionAbs(position!(col_index: 29, row_index: 0))
+            );
+            // 2 ops per row, & a reset at the end.
+            assert_eq2!(render_ops.len(), 10 * 2 + 1);
+        });
+    }
+
+    #[test]
+    fn test_split_pane_mouse_drag() {
+        let mut split_pane = SplitPane::new(LayoutDirection::Horizontal, 50);
+
+        // Mouse events are ignored until the divider has been rendered.
+        let down_on_divider = mouse(MouseInputKind::MouseDown(Button::Left), 49, 5);
+        assert_eq2!(
+            split_pane.handle_event(&down_on_divider),
+            EventPropagation::Propagate
+        );
+
+        split_pane.maybe_container_bounds = Some(container_bounds());
+
+        // Not on the divider.
+        let down_elsewhere = mouse(MouseInputKind::MouseDown(Button::Left), 10, 5);
+        assert_eq2!(
+            split_pane.handle_event(&down_elsewhere),
+            EventPropagation::Propagate
+        );
+        assert!(!split_pane.is_dragging);
+
+        assert_eq2!(
+            split_pane.handle_event(&down_on_divider),
+            EventPropagation::ConsumedRender
+        );
+        assert!(split_pane.is_dragging);
+
+        let drag = mouse(MouseInputKind::MouseDrag(Button::Left), 69, 5);
+        split_pane.handle_event(&drag);
+        assert_eq2!(split_pane.split_percent, 70);
+
+        let up = mouse(MouseInputKind::MouseUp(Button::Left), 99, 5);
+        split_pane.handle_event(&up);
+        assert!(!split_pane.is_dragging);
+        assert_eq2!(
+            split_pane.split_percent,
+            100 - SPLIT_PANE_DEFAULT_MIN_PERCENT
+        );
+    }
+}
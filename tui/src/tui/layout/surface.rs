@@ -15,6 +15,8 @@
  *   limitations under the License.
  */
 
+use std::time::Duration;
+
 use r3bl_rs_utils_core::*;
 use serde::{Deserialize, Serialize};
 
@@ -31,12 +33,27 @@ pub struct Surface {
     pub render_pipeline: RenderPipeline,
 }
 
-#[derive(Copy, Clone, Debug, Default, Serialize, Deserialize)]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct SurfaceBounds {
     pub origin_pos: Position,
     pub box_size: Size,
 }
 
+impl Surface {
+    /// Show a [Toast] w/ `text` over the UI for `duration`. If `maybe_style` isn't set,
+    /// then [get_default_toast_style] is used. More info in [render_toasts].
+    pub fn push_toast(
+        &mut self,
+        text: impl Into<String>,
+        duration: Duration,
+        maybe_style: Option<Style>,
+    ) {
+        self.render_pipeline
+            .toasts
+            .push(Toast::new(text, duration, maybe_style));
+    }
+}
+
 mod surface_bounds_impl {
     use super::*;
 
@@ -192,7 +209,11 @@ impl PerformPositioningAndSizing for Surface {
     fn add_non_root_box(&mut self, flex_box_props: FlexBoxProps) -> CommonResult<()> {
         throws!({
             let container_box = self.current_box()?;
+            let container_dir = container_box.dir;
+            let container_origin_pos = container_box.origin_pos;
             let container_bounds = container_box.bounds_size;
+            let unallocated_size = container_box.unallocated_size_for_next_box;
+            let requested_percent_sum = container_box.requested_percent_sum_for_next_box;
 
             let maybe_cascaded_style: Option<Style> =
                 cascade_styles(container_box, &flex_box_props);
@@ -202,22 +223,79 @@ impl PerformPositioningAndSizing for Surface {
                 height_pc,
             } = flex_box_props.requested_size_percent;
 
-            let requested_size_allocation = size!(
-              col_count: width_pc.calc_percentage(container_bounds.col_count),
-              row_count: height_pc.calc_percentage(container_bounds.row_count)
-            );
-
             let origin_pos = unwrap_or_err! {
               container_box.insertion_pos_for_next_box,
               LayoutErrorType::BoxCursorPositionUndefined
             };
 
-            self.update_insertion_pos_for_next_box(requested_size_allocation)?;
+            // In the direction of the container, the size of this box is the distance
+            // between its start & end edges, which are rounded from the sum of the
+            // percentages of the previous siblings. So the siblings tile the container w/
+            // no gaps or overlaps. The space that a previous sibling gave up (due to its
+            // max size) is also offered to this box.
+            let mut requested_size_allocation = size!(
+              col_count: calc_percentage_rounded(*width_pc as u16, container_bounds.col_count),
+              row_count: calc_percentage_rounded(*height_pc as u16, container_bounds.row_count)
+            );
+            let requested_pc = match container_dir {
+                LayoutDirection::Horizontal => *width_pc,
+                LayoutDirection::Vertical => *height_pc,
+            } as u16;
+            let calc_size_between_edges = |total: ChUnit| -> ChUnit {
+                calc_percentage_rounded(requested_percent_sum + requested_pc, total)
+                    - calc_percentage_rounded(requested_percent_sum, total)
+                    + unallocated_size
+            };
+            match container_dir {
+                LayoutDirection::Horizontal => {
+                    requested_size_allocation.col_count =
+                        calc_size_between_edges(container_bounds.col_count)
+                }
+                LayoutDirection::Vertical => {
+                    requested_size_allocation.row_count =
+                        calc_size_between_edges(container_bounds.row_count)
+                }
+            }
+
+            // The space that is left in the container, after the previous siblings.
+            let mut available_size = container_bounds;
+            match container_dir {
+                LayoutDirection::Horizontal => {
+                    available_size.col_count = container_origin_pos.col_index
+                        + container_bounds.col_count
+                        - origin_pos.col_index
+                }
+                LayoutDirection::Vertical => {
+                    available_size.row_count = container_origin_pos.row_index
+                        + container_bounds.row_count
+                        - origin_pos.row_index
+                }
+            }
+
+            let allocated_size = clamp_to_size_constraints(
+                &flex_box_props,
+                requested_size_allocation,
+                available_size,
+            );
+
+            // Give the space that this box didn't take to the next sibling.
+            let container_box = self.current_box()?;
+            container_box.requested_percent_sum_for_next_box += requested_pc;
+            container_box.unallocated_size_for_next_box = match container_dir {
+                LayoutDirection::Horizontal => {
+                    requested_size_allocation.col_count - allocated_size.col_count
+                }
+                LayoutDirection::Vertical => {
+                    requested_size_allocation.row_count - allocated_size.row_count
+                }
+            };
+
+            self.update_insertion_pos_for_next_box(allocated_size)?;
 
             self.stack_of_boxes.push(make_non_root_box_with_style(
                 flex_box_props,
                 origin_pos,
-                container_bounds,
+                allocated_size,
                 maybe_cascaded_style,
             ));
         });
@@ -231,9 +309,15 @@ impl PerformPositioningAndSizing for Surface {
                 height_pc,
             } = flex_box_props.requested_size_percent;
 
-            let bounds_size = size!(
-              col_count: width_pc.calc_percentage(self.box_size.col_count),
-              row_count: height_pc.calc_percentage(self.box_size.row_count)
+            let requested_size_allocation = size!(
+              col_count: calc_percentage_rounded(*width_pc as u16, self.box_size.col_count),
+              row_count: calc_percentage_rounded(*height_pc as u16, self.box_size.row_count)
+            );
+
+            let bounds_size = clamp_to_size_constraints(
+                &flex_box_props,
+                requested_size_allocation,
+                self.box_size,
             );
 
             self.stack_of_boxes.push(make_root_box_with_style(
@@ -260,16 +344,14 @@ fn make_non_root_box_with_style(
                 height_pc,
             },
         maybe_styles: _,
+        maybe_min_size: _,
+        maybe_max_size: _,
+        maybe_tab_index,
     }: FlexBoxProps,
     origin_pos: Position,
-    container_bounds: Size,
+    bounds_size: Size,
     maybe_cascaded_style: Option<Style>,
 ) -> FlexBox {
-    let bounds_size = size!(
-      col_count: width_pc.calc_percentage(container_bounds.col_count),
-      row_count: height_pc.calc_percentage(container_bounds.row_count)
-    );
-
     // Adjust `bounds_size` & `origin` based on the style's padding.
     let (style_adjusted_origin_pos, style_adjusted_bounds_size) =
         adjust_with_style(&maybe_cascaded_style, origin_pos, bounds_size);
@@ -287,6 +369,9 @@ fn make_non_root_box_with_style(
         },
         maybe_computed_style: maybe_cascaded_style,
         insertion_pos_for_next_box: None,
+        unallocated_size_for_next_box: ch!(0),
+        requested_percent_sum_for_next_box: 0,
+        maybe_tab_index,
     }
 }
 
@@ -296,6 +381,9 @@ fn make_root_box_with_style(
         dir,
         requested_size_percent,
         maybe_styles,
+        maybe_min_size: _,
+        maybe_max_size: _,
+        maybe_tab_index,
     }: FlexBoxProps,
     origin_pos: Position,
     bounds_size: Size,
@@ -316,7 +404,94 @@ fn make_root_box_with_style(
         requested_size_percent,
         maybe_computed_style: computed_style,
         insertion_pos_for_next_box: Some(origin_pos),
+        unallocated_size_for_next_box: ch!(0),
+        requested_percent_sum_for_next_box: 0,
+        maybe_tab_index,
+    }
+}
+
+/// Returns `percent` of `total`, rounded to the nearest cell (halves are rounded up). Unlike
+/// [Percent::calc_percentage] (which truncates), the `percent` can be more than 100, since
+/// it is also used for the sum of the percentages of sibling boxes.
+pub fn calc_percentage_rounded(percent: u16, total: ChUnit) -> ChUnit {
+    let it = (u32::from(percent) * u32::from(*total) + 50) / 100;
+    ch!(it.min(u32::from(ChUnitPrimitiveType::MAX)) as ChUnitPrimitiveType)
+}
+
+/// Clamp the `requested_size` of a box into the `maybe_min_size` & `maybe_max_size` of its
+/// [FlexBoxProps]. When these can't all be satisfied, they are applied in this priority
+/// order (and the conflict is logged):
+/// 1. The box never overflows the `available_size` that is left in its container.
+/// 2. `maybe_min_size`.
+/// 3. `maybe_max_size`.
+fn clamp_to_size_constraints(
+    flex_box_props: &FlexBoxProps,
+    requested_size: Size,
+    available_size: Size,
+) -> Size {
+    let min_size = flex_box_props.maybe_min_size.unwrap_or_default();
+    let max_size = flex_box_props.maybe_max_size.unwrap_or_default();
+
+    size!(
+      col_count: clamp_dimension(
+        flex_box_props.id,
+        "col_count",
+        requested_size.col_count,
+        available_size.col_count,
+        min_size.col_count,
+        max_size.col_count
+      ),
+      row_count: clamp_dimension(
+        flex_box_props.id,
+        "row_count",
+        requested_size.row_count,
+        available_size.row_count,
+        min_size.row_count,
+        max_size.row_count
+      )
+    )
+}
+
+/// A `min` or `max` of `0` means that there is no constraint.
+fn clamp_dimension(
+    id: FlexBoxId,
+    dimension: &str,
+    requested: ChUnit,
+    available: ChUnit,
+    min: ChUnit,
+    max: ChUnit,
+) -> ChUnit {
+    let mut it = requested;
+
+    if max > ch!(0) {
+        if min > max {
+            call_if_true!(DEBUG_TUI_MOD, {
+                let msg = format!(
+                    "🍀 layout : {id} min {dimension} {min:?} > max {dimension} {max:?}; \
+                    using min"
+                );
+                log_debug(msg);
+            });
+        }
+        it = it.min(max);
     }
+
+    it = it.max(min);
+
+    if it > available {
+        if min > available {
+            call_if_true!(DEBUG_TUI_MOD, {
+                let msg = format!(
+                    "🍀 layout : {id} min {dimension} {min:?} doesn't fit in the \
+                    available {dimension} {available:?}; using available"
+                );
+                log_debug(msg);
+            });
+        }
+        it = available;
+    }
+
+    it
 }
 
 /// Adjust `origin` & `bounds_size` based on the `maybe_style`'s padding.
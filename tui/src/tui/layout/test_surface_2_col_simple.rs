@@ -55,6 +55,7 @@ mod tests {
                 dir: LayoutDirection::Horizontal,
                 requested_size_percent: requested_size_percent!(width:100, height:100),
                 maybe_styles: None,
+                ..Default::default()
             })?;
 
             make_container_assertions(surface)?;
@@ -143,6 +144,7 @@ mod tests {
                 id: FlexBoxId::from(2),
                 dir: LayoutDirection::Vertical,
                 requested_size_percent: requested_size_percent!(width:50, height:100),
+                ..Default::default()
             })?;
             make_right_col_assertions(surface)?;
             surface.box_end()?;
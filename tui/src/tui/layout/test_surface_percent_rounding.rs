@@ -0,0 +1,119 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+#[cfg(test)]
+mod tests {
+    use r3bl_rs_utils_core::*;
+
+    use crate::*;
+
+    /// Lay out one col for each of the `width_pcs` in a surface that is `col_count` wide,
+    /// & return the origin & bounds size of each col.
+    fn layout_cols(
+        col_count: u16,
+        width_pcs: &[u16],
+    ) -> CommonResult<Vec<(Position, Size)>> {
+        let mut surface = Surface::default();
+        let mut it = vec![];
+
+        surface.surface_start(SurfaceProps {
+            pos: position!(col_index: 0, row_index: 0),
+            size: size!(col_count: col_count, row_count: 10),
+        })?;
+
+        box_start!(
+            in: surface,
+            id: FlexBoxId::from(0),
+            dir: LayoutDirection::Horizontal,
+            requested_size_percent: requested_size_percent!(width: 100, height: 100),
+            styles: [],
+        );
+
+        for (index, width_pc) in width_pcs.iter().enumerate() {
+            box_start!(
+                in: surface,
+                id: FlexBoxId::from(index as u8 + 1),
+                dir: LayoutDirection::Vertical,
+                requested_size_percent: requested_size_percent!(width: *width_pc, height: 100),
+                styles: [],
+            );
+            let current_box = surface.current_box()?;
+            it.push((current_box.origin_pos, current_box.bounds_size));
+            box_end!(in: surface);
+        }
+
+        box_end!(in: surface);
+        surface.surface_end()?;
+
+        Ok(it)
+    }
+
+    /// Each col starts where the previous one ends. Returns the col after the last one.
+    fn assert_no_gaps_or_overlaps(cols: &[(Position, Size)]) -> ChUnit {
+        let mut expected_col_index = ch!(0);
+        for (origin_pos, bounds_size) in cols {
+            assert_eq2!(origin_pos.col_index, expected_col_index);
+            expected_col_index += bounds_size.col_count;
+        }
+        expected_col_index
+    }
+
+    #[test]
+    fn test_three_33_percent_cols_in_80_cols() -> CommonResult<()> {
+        throws!({
+            let cols = layout_cols(80, &[33, 33, 33])?;
+            let widths: Vec<ChUnit> =
+                cols.iter().map(|(_, size)| size.col_count).collect();
+            assert_eq2!(widths, vec![ch!(26), ch!(27), ch!(26)]);
+            // 99% of 80 is 79.2 cols.
+            assert_eq2!(assert_no_gaps_or_overlaps(&cols), ch!(79));
+        });
+    }
+
+    #[test]
+    fn test_cols_that_add_up_to_100_percent_tile_the_container() -> CommonResult<()> {
+        throws!({
+            let cases: [(u16, &[u16]); 5] = [
+                (80, &[33, 33, 34]),
+                (81, &[50, 50]),
+                (7, &[25, 25, 25, 25]),
+                (101, &[10, 20, 30, 40]),
+                (3, &[1, 1, 98]),
+            ];
+            for (col_count, width_pcs) in cases {
+                let cols = layout_cols(col_count, width_pcs)?;
+                assert_eq2!(assert_no_gaps_or_overlaps(&cols), ch!(col_count));
+            }
+
+            // The remainder is allocated deterministically.
+            let widths: Vec<ChUnit> = layout_cols(81, &[50, 50])?
+                .iter()
+                .map(|(_, size)| size.col_count)
+                .collect();
+            assert_eq2!(widths, vec![ch!(41), ch!(40)]);
+        });
+    }
+
+    #[test]
+    fn test_calc_percentage_rounded() {
+        assert_eq2!(calc_percentage_rounded(33, ch!(80)), ch!(26));
+        assert_eq2!(calc_percentage_rounded(66, ch!(80)), ch!(53));
+        assert_eq2!(calc_percentage_rounded(50, ch!(5)), ch!(3));
+        assert_eq2!(calc_percentage_rounded(100, ch!(5)), ch!(5));
+        assert_eq2!(calc_percentage_rounded(0, ch!(5)), ch!(0));
+    }
+}
@@ -0,0 +1,125 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+#[cfg(test)]
+mod tests {
+    use r3bl_rs_utils_core::*;
+
+    use crate::*;
+
+    /// Lay out 2 cols in a 50 x 10 surface & return the bounds size of each one.
+    fn layout_2_col(
+        left_props: FlexBoxProps,
+        right_props: FlexBoxProps,
+    ) -> CommonResult<(Size, Size)> {
+        let mut surface = Surface::default();
+
+        surface.surface_start(SurfaceProps {
+            pos: position!(col_index: 0, row_index: 0),
+            size: size!(col_count: 50, row_count: 10),
+        })?;
+
+        surface.box_start(FlexBoxProps {
+            id: FlexBoxId::from(0),
+            dir: LayoutDirection::Horizontal,
+            requested_size_percent: requested_size_percent!(width: 100, height: 100),
+            ..Default::default()
+        })?;
+
+        surface.box_start(left_props)?;
+        let left_size = surface.current_box()?.bounds_size;
+        surface.box_end()?;
+
+        surface.box_start(right_props)?;
+        let right_size = surface.current_box()?.bounds_size;
+        surface.box_end()?;
+
+        surface.box_end()?;
+        surface.surface_end()?;
+
+        Ok((left_size, right_size))
+    }
+
+    fn make_col_props(id: u8, width_pc: u16) -> CommonResult<FlexBoxProps> {
+        Ok(FlexBoxProps {
+            id: FlexBoxId::from(id),
+            dir: LayoutDirection::Vertical,
+            requested_size_percent: requested_size_percent!(width: width_pc, height: 100),
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_min_size_takes_space_from_next_box() -> CommonResult<()> {
+        throws!({
+            let (left_size, right_size) = layout_2_col(
+                FlexBoxProps {
+                    maybe_min_size: Some(size!(col_count: 20, row_count: 0)),
+                    ..make_col_props(1, 10)?
+                },
+                make_col_props(2, 90)?,
+            )?;
+            assert_eq2!(left_size, size!(col_count: 20, row_count: 10));
+            assert_eq2!(right_size, size!(col_count: 30, row_count: 10));
+        });
+    }
+
+    #[test]
+    fn test_max_size_gives_space_to_next_box() -> CommonResult<()> {
+        throws!({
+            let (left_size, right_size) = layout_2_col(
+                FlexBoxProps {
+                    maybe_max_size: Some(size!(col_count: 10, row_count: 5)),
+                    ..make_col_props(1, 50)?
+                },
+                make_col_props(2, 50)?,
+            )?;
+            assert_eq2!(left_size, size!(col_count: 10, row_count: 5));
+            assert_eq2!(right_size, size!(col_count: 40, row_count: 10));
+        });
+    }
+
+    #[test]
+    fn test_conflicting_constraints_are_applied_in_priority_order() -> CommonResult<()> {
+        throws!({
+            // Min wins over max.
+            let (left_size, _) = layout_2_col(
+                FlexBoxProps {
+                    maybe_min_size: Some(size!(col_count: 20, row_count: 0)),
+                    maybe_max_size: Some(size!(col_count: 10, row_count: 0)),
+                    ..make_col_props(1, 50)?
+                },
+                make_col_props(2, 50)?,
+            )?;
+            assert_eq2!(left_size, size!(col_count: 20, row_count: 10));
+
+            // The container wins over min.
+            let (left_size, right_size) = layout_2_col(
+                FlexBoxProps {
+                    maybe_min_size: Some(size!(col_count: 60, row_count: 0)),
+                    ..make_col_props(1, 10)?
+                },
+                FlexBoxProps {
+                    maybe_min_size: Some(size!(col_count: 5, row_count: 0)),
+                    ..make_col_props(2, 90)?
+                },
+            )?;
+            assert_eq2!(left_size, size!(col_count: 50, row_count: 10));
+            assert_eq2!(right_size, size!(col_count: 0, row_count: 10));
+        });
+    }
+}
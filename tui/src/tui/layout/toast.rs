@@ -0,0 +1,222 @@
+/*
+ *   Copyright (c) 2022 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::time::{Duration, Instant};
+
+use r3bl_rs_utils_core::*;
+use r3bl_rs_utils_macro::style;
+use serde::{Deserialize, Serialize};
+
+use crate::*;
+
+/// A transient notification (eg: "Saved") that is painted over the UI in the top right
+/// corner, until its `duration` has passed. Toasts aren't components, so they never take
+/// focus or consume input events.
+///
+/// Use [Surface::push_toast] to show one. It travels in the [RenderPipeline::toasts] of
+/// the surface, & [render_toasts] moves it into the [ToastStack] in [GlobalData] (where it
+/// lives until it expires) when the app is painted.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Toast {
+    pub text: String,
+    pub duration: Duration,
+    /// [get_default_toast_style] is used if this isn't set.
+    pub maybe_style: Option<Style>,
+}
+
+impl Toast {
+    pub fn new(
+        text: impl Into<String>,
+        duration: Duration,
+        maybe_style: Option<Style>,
+    ) -> Self {
+        Self {
+            text: text.into(),
+            duration,
+            maybe_style,
+        }
+    }
+}
+
+/// The [Toast]s that are currently shown, oldest first. This lives in the [GlobalData].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ToastStack {
+    pub active_toasts: Vec<(Toast, Instant)>,
+}
+
+impl ToastStack {
+    /// The `toast` expires once its [Toast::duration] has passed since `now`.
+    pub fn push(&mut self, toast: Toast, now: Instant) {
+        let expires_at = now + toast.duration;
+        self.active_toasts.push((toast, expires_at));
+    }
+
+    pub fn remove_expired(&mut self, now: Instant) {
+        self.active_toasts
+            .retain(|(_, expires_at)| *expires_at > now);
+    }
+
+    pub fn is_empty(&self) -> bool { self.active_toasts.is_empty() }
+
+    pub fn len(&self) -> usize { self.active_toasts.len() }
+
+    /// How long until the next toast expires (& the app has to be painted again to make
+    /// it disappear).
+    pub fn get_delay_until_next_expiry(&self, now: Instant) -> Option<Duration> {
+        self.active_toasts
+            .iter()
+            .map(|(_, expires_at)| expires_at.saturating_duration_since(now))
+            .min()
+    }
+
+    /// The toasts are stacked vertically in the top right corner of the window, 1 per
+    /// row. Toasts that don't fit in the window are not painted.
+    pub fn render(&self, window_size: Size) -> RenderOps {
+        let mut render_ops = render_ops!();
+
+        for (row_index, (toast, _)) in self.active_toasts.iter().enumerate() {
+            let row_index = ch!(row_index);
+            if row_index >= window_size.row_count {
+                break;
+            }
+
+            let text = UnicodeString::from(format!(" {} ", toast.text));
+            let text = UnicodeString::from(
+                text.truncate_end_to_fit_width(window_size.col_count),
+            );
+            let col_index = window_size.col_count - text.display_width;
+            let style = toast.maybe_style.unwrap_or_else(get_default_toast_style);
+
+            render_ops.push(RenderOp::MoveCursorPositionAbs(
+                position! { col_index: col_index, row_index: row_index },
+            ));
+            render_ops.push(RenderOp::ApplyColors(Some(style)));
+            render_ops.push(RenderOp::PaintTextWithAttributes(text.string, Some(style)));
+            render_ops.push(RenderOp::ResetColor);
+        }
+
+        render_ops
+    }
+}
+
+/// Called by [TerminalWindow::main_event_loop] before the `render_pipeline` of the app is
+/// painted:
+/// 1. The [RenderPipeline::toasts] that were pushed in this render are added to the
+///    [ToastStack] in the [GlobalData], & the expired ones are removed.
+/// 2. The remaining toasts are painted at [ZOrder::Glass], just like a modal dialog.
+/// 3. A [RenderTick] is requested for when the next toast expires, so that it disappears
+///    even if nothing else causes the app to be painted.
+pub async fn render_toasts(
+    render_pipeline: &mut RenderPipeline,
+    shared_global_data: &SharedGlobalData,
+) {
+    let now = Instant::now();
+    let mut global_data = shared_global_data.write().await;
+
+    // The toasts aren't part of any dirty region, so the whole frame is painted while
+    // they're shown, & once more after the last one goes away.
+    if !global_data.toast_stack.is_empty() || !render_pipeline.toasts.is_empty() {
+        render_pipeline.mark_all_dirty();
+    }
+
+    for toast in render_pipeline.toasts.drain(..) {
+        global_data.toast_stack.push(toast, now);
+    }
+    global_data.toast_stack.remove_expired(now);
+    if global_data.toast_stack.is_empty() {
+        return;
+    }
+
+    let render_ops = global_data.toast_stack.render(global_data.get_size());
+    render_pipeline.push(ZOrder::Glass, render_ops);
+
+    if let Some(delay) = global_data.toast_stack.get_delay_until_next_expiry(now) {
+        global_data.request_render_tick(delay);
+    }
+}
+
+/// This is the default style for a [Toast].
+pub fn get_default_toast_style() -> Style {
+    style! {
+        attrib: [bold]
+        color_fg: TuiColor::Basic(ANSIBasicColor::Black)
+        color_bg: TuiColor::Basic(ANSIBasicColor::Yellow)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn toast(text: &str, millis: u64) -> Toast {
+        Toast::new(text, Duration::from_millis(millis), None)
+    }
+
+    #[test]
+    fn test_toast_stack_expiry() {
+        let now = Instant::now();
+        let mut toast_stack = ToastStack::default();
+        toast_stack.push(toast("Saved", 1000), now);
+        toast_stack.push(toast("Copy failed", 500), now);
+        assert_eq2!(toast_stack.len(), 2);
+        assert_eq2!(
+            toast_stack.get_delay_until_next_expiry(now),
+            Some(Duration::from_millis(500))
+        );
+
+        // Only the 2nd toast has expired.
+        let later = now + Duration::from_millis(500);
+        toast_stack.remove_expired(later);
+        assert_eq2!(toast_stack.len(), 1);
+        assert_eq2!(toast_stack.active_toasts[0].0.text, "Saved");
+        assert_eq2!(
+            toast_stack.get_delay_until_next_expiry(later),
+            Some(Duration::from_millis(500))
+        );
+
+        toast_stack.remove_expired(now + Duration::from_millis(1000));
+        assert!(toast_stack.is_empty());
+        assert_eq2!(toast_stack.get_delay_until_next_expiry(now), None);
+    }
+
+    #[test]
+    fn test_toast_stack_render() {
+        let now = Instant::now();
+        let mut toast_stack = ToastStack::default();
+        toast_stack.push(toast("Saved", 1000), now);
+        toast_stack.push(toast("Copy failed", 1000), now);
+        toast_stack.push(toast("Does not fit", 1000), now);
+
+        let window_size = size!( col_count: 10, row_count: 2 );
+        let render_ops = toast_stack.render(window_size);
+        let style = get_default_toast_style();
+        assert_eq2!(
+            render_ops.list,
+            vec![
+                RenderOp::MoveCursorPositionAbs(position! { col_index: 3, row_index: 0 }),
+                RenderOp::ApplyColors(Some(style)),
+                RenderOp::PaintTextWithAttributes(" Saved ".into(), Some(style)),
+                RenderOp::ResetColor,
+                // Truncated to fit the window.
+                RenderOp::MoveCursorPositionAbs(position! { col_index: 0, row_index: 1 }),
+                RenderOp::ApplyColors(Some(style)),
+                RenderOp::PaintTextWithAttributes(" Copy fail".into(), Some(style)),
+                RenderOp::ResetColor,
+            ]
+        );
+    }
+}
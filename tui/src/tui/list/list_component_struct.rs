@@ -0,0 +1,357 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::{fmt::Debug, sync::Arc};
+
+use async_trait::async_trait;
+use r3bl_redux::*;
+use r3bl_rs_utils_core::*;
+use tokio::sync::RwLock;
+
+use crate::*;
+
+/// This is a reusable [Component] that shows a scrollable list of `items`, and lets the user
+/// move the focus w/ Up, Down, PageUp, PageDown, Home & End (and select items w/ Space in
+/// multi-select mode). The viewport scrolls to keep the focused item in view.
+/// - Each item is turned into the text for its row by the `render_item_fn`.
+/// - The `on_selection_change_handler` is called w/ the new [ListSelection] when it changes,
+///   typically to dispatch an action to the store (just like the [EditorComponent]).
+#[derive(Clone)]
+pub struct ListComponent<T, S, A>
+where
+    T: Clone + Sync + Send,
+    S: Debug + Default + Clone + PartialEq + Sync + Send,
+    A: Debug + Default + Clone + Sync + Send,
+{
+    pub id: FlexBoxId,
+    pub items: Vec<T>,
+    pub config: ListComponentConfig,
+    pub render_item_fn: ListItemRenderFn<T>,
+    pub on_selection_change_handler: Option<OnListSelectionChangeFn<S, A>>,
+    pub selection: ListSelection,
+    pub scroll_offset_row_index: usize,
+    /// This is saved from the last render, so that paging knows how many items fit.
+    pub viewport_height: ChUnit,
+    /// Only used if [ListComponentConfig::smooth_scroll] is enabled.
+    pub smooth_scroll: SmoothScroll,
+}
+
+pub type ListItemRenderFn<T> = fn(&T) -> String;
+
+pub type OnListSelectionChangeFn<S, A> = fn(&SharedStore<S, A>, FlexBoxId, ListSelection);
+
+pub mod list_component_impl {
+    use super::*;
+
+    #[async_trait]
+    impl<T, S, A> Component<S, A> for ListComponent<T, S, A>
+    where
+        T: Clone + Sync + Send + 'static,
+        S: Debug + Default + Clone + PartialEq + Sync + Send + 'static,
+        A: Debug + Default + Clone + Sync + Send + 'static,
+    {
+        fn reset(&mut self) {
+            self.selection = ListSelection::default();
+            self.scroll_offset_row_index = 0;
+            self.smooth_scroll.jump_to(0);
+        }
+
+        fn get_id(&self) -> FlexBoxId { self.id }
+
+        async fn handle_event(
+            &mut self,
+            args: ComponentScopeArgs<'_, S, A>,
+            input_event: &InputEvent,
+        ) -> CommonResult<EventPropagation> {
+            let ComponentScopeArgs { shared_store, .. } = args;
+
+            let old_selection = self.selection.clone();
+
+            if let Some(navigation) = ListNavigation::try_from_input_event(input_event) {
+                self.selection.navigate(
+                    navigation,
+                    self.items.len(),
+                    ch!(@to_usize self.viewport_height),
+                );
+            } else if self.config.multi_select
+                && !self.items.is_empty()
+                && input_event.matches(&[InputEvent::Keyboard(KeyPress::Plain {
+                    key: Key::Character(' '),
+                })])
+            {
+                self.selection.toggle_focused();
+            } else {
+                return Ok(EventPropagation::Propagate);
+            }
+
+            self.scroll_focused_into_view();
+
+            if self.selection != old_selection {
+                if let Some(on_selection_change_handler) =
+                    self.on_selection_change_handler
+                {
+                    on_selection_change_handler(
+                        shared_store,
+                        self.id,
+                        self.selection.clone(),
+                    );
+                }
+            }
+
+            Ok(EventPropagation::ConsumedRender)
+        }
+
+        async fn render(
+            &mut self,
+            args: ComponentScopeArgs<'_, S, A>,
+            current_box: &FlexBox,
+            _surface_bounds: SurfaceBounds, /* Ignore this. */
+        ) -> CommonResult<RenderPipeline> {
+            let ComponentScopeArgs {
+                shared_global_data, ..
+            } = args;
+
+            let origin_pos = current_box.style_adjusted_origin_pos;
+            let bounds_size = current_box.style_adjusted_bounds_size;
+
+            // The box might have been resized since the last render.
+            self.viewport_height = self.config.get_viewport_height(bounds_size);
+            self.scroll_focused_into_view();
+
+            // The offset that is painted lags behind the actual one while smooth scrolling.
+            let scroll_offset_row_index = if self.config.smooth_scroll {
+                let it = self.smooth_scroll.advance(self.scroll_offset_row_index);
+                if self.smooth_scroll.is_animating() {
+                    shared_global_data
+                        .write()
+                        .await
+                        .request_render_tick(SMOOTH_SCROLL_FRAME_INTERVAL);
+                }
+                it
+            } else {
+                self.smooth_scroll.jump_to(self.scroll_offset_row_index);
+                self.scroll_offset_row_index
+            };
+
+            let viewport_height = ch!(@to_usize self.viewport_height);
+            let has_scrollbar =
+                self.config.show_scrollbar && self.items.len() > viewport_height;
+            let item_col_count = match has_scrollbar {
+                true => bounds_size.col_count - 1,
+                false => bounds_size.col_count,
+            };
+
+            let mut render_ops = render_ops!();
+            let mut row_index = ch!(0);
+
+            // Paint header.
+            if let Some(header) = &self.config.maybe_header {
+                paint_row(
+                    &mut render_ops,
+                    origin_pos,
+                    row_index,
+                    header,
+                    item_col_count,
+                    Some(self.config.header_style),
+                );
+                row_index += 1;
+            }
+            let first_item_row_index = row_index;
+
+            // Paint the items that are in the viewport.
+            for (index, item) in self
+                .items
+                .iter()
+                .enumerate()
+                .skip(scroll_offset_row_index)
+                .take(viewport_height)
+            {
+                let mut text = String::new();
+                if self.config.multi_select {
+                    text.push_str(match self.selection.is_selected(index) {
+                        true => &self.config.selected_marker,
+                        false => &self.config.unselected_marker,
+                    });
+                }
+                text.push_str(&(self.render_item_fn)(item));
+
+                let maybe_style = match index == self.selection.focused_index {
+                    true => Some(self.config.focused_item_style),
+                    false => self.config.maybe_item_style,
+                };
+
+                paint_row(
+                    &mut render_ops,
+                    origin_pos,
+                    row_index,
+                    &text,
+                    item_col_count,
+                    maybe_style,
+                );
+                row_index += 1;
+            }
+
+            // Paint scrollbar.
+            if has_scrollbar {
+                let markers = &self.config.scrollbar_markers;
+                let (thumb_start, thumb_len) = ScrollbarMode::get_thumb_range(
+                    self.viewport_height,
+                    ch!(self.items.len()),
+                    ch!(scroll_offset_row_index),
+                );
+                for scrollbar_row_index in 0..viewport_height {
+                    let scrollbar_row_index = ch!(scrollbar_row_index);
+                    let is_thumb = scrollbar_row_index >= thumb_start
+                        && scrollbar_row_index < thumb_start + thumb_len;
+                    let (marker_char, style) = match is_thumb {
+                        true => (markers.thumb_char, markers.thumb_style),
+                        false => (markers.track_char, markers.track_style),
+                    };
+                    render_ops.push(RenderOp::MoveCursorPositionRelTo(
+                        origin_pos,
+                        position! {
+                            col_index: item_col_count,
+                            row_index: first_item_row_index + scrollbar_row_index
+                        },
+                    ));
+                    render_ops.push(RenderOp::ApplyColors(Some(style)));
+                    render_ops.push(RenderOp::PaintTextWithAttributes(
+                        marker_char.to_string(),
+                        None,
+                    ));
+                    render_ops.push(RenderOp::ResetColor);
+                }
+            }
+
+            let mut render_pipeline = render_pipeline!();
+            render_pipeline.push(ZOrder::Normal, render_ops);
+            Ok(render_pipeline)
+        }
+    }
+
+    /// Paint `text` in the given row, clipped (or padded w/ spaces) to `col_count`, so
+    /// that the style of the row spans the whole width of the list.
+    fn paint_row(
+        render_ops: &mut RenderOps,
+        origin_pos: Position,
+        row_index: ChUnit,
+        text: &str,
+        col_count: ChUnit,
+        maybe_style: Option<Style>,
+    ) {
+        let text = UnicodeString::from(text);
+        let clipped_text = text.clip_to_width(ch!(0), col_count);
+        let padding_len = col_count - ch!(UnicodeString::str_display_width(clipped_text));
+        let padded_text = format!(
+            "{clipped_text}{}",
+            SPACER.repeat(ch!(@to_usize padding_len))
+        );
+
+        render_ops.push(RenderOp::MoveCursorPositionRelTo(
+            origin_pos,
+            position! { col_index: 0, row_index: row_index },
+        ));
+        render_ops.push(RenderOp::ApplyColors(maybe_style));
+        render_ops.push(RenderOp::PaintTextWithAttributes(padded_text, maybe_style));
+        render_ops.push(RenderOp::ResetColor);
+    }
+
+    impl<T, S, A> ListComponent<T, S, A>
+    where
+        T: Clone + Sync + Send,
+        S: Debug + Default + Clone + PartialEq + Sync + Send,
+        A: Debug + Default + Clone + Sync + Send,
+    {
+        /// Replace the items, and drop the parts of the selection that no longer exist.
+        pub fn set_items(&mut self, items: Vec<T>) {
+            self.items = items;
+            self.selection.clamp_to_item_count(self.items.len());
+            self.scroll_focused_into_view();
+        }
+
+        pub fn get_focused_item(&self) -> Option<&T> {
+            self.items.get(self.selection.focused_index)
+        }
+
+        pub fn get_selected_items(&self) -> Vec<&T> {
+            self.selection
+                .selected_indices
+                .iter()
+                .filter_map(|index| self.items.get(*index))
+                .collect()
+        }
+
+        fn scroll_focused_into_view(&mut self) {
+            self.scroll_offset_row_index =
+                self.selection.get_scroll_offset_to_keep_focused_in_view(
+                    self.scroll_offset_row_index,
+                    ch!(@to_usize self.viewport_height),
+                );
+        }
+    }
+}
+pub use list_component_impl::*;
+
+mod constructor {
+    use super::*;
+
+    impl<T, S, A> ListComponent<T, S, A>
+    where
+        T: Clone + Sync + Send,
+        S: Debug + Default + Clone + PartialEq + Sync + Send,
+        A: Debug + Default + Clone + Sync + Send,
+    {
+        /// The on_selection_change handler is a lambda that is called if the focused or
+        /// selected items change. Typically this results in a Redux action being created and
+        /// then dispatched to the given store.
+        pub fn new(
+            id: FlexBoxId,
+            items: Vec<T>,
+            config: ListComponentConfig,
+            render_item_fn: ListItemRenderFn<T>,
+            on_selection_change: OnListSelectionChangeFn<S, A>,
+        ) -> Self {
+            Self {
+                id,
+                items,
+                config,
+                render_item_fn,
+                on_selection_change_handler: Some(on_selection_change),
+                selection: ListSelection::default(),
+                scroll_offset_row_index: 0,
+                viewport_height: ch!(0),
+                smooth_scroll: SmoothScroll::default(),
+            }
+        }
+
+        pub fn new_shared(
+            id: FlexBoxId,
+            items: Vec<T>,
+            config: ListComponentConfig,
+            render_item_fn: ListItemRenderFn<T>,
+            on_selection_change: OnListSelectionChangeFn<S, A>,
+        ) -> Arc<RwLock<Self>> {
+            Arc::new(RwLock::new(ListComponent::new(
+                id,
+                items,
+                config,
+                render_item_fn,
+                on_selection_change,
+            )))
+        }
+    }
+}
@@ -0,0 +1,72 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use r3bl_rs_utils_core::*;
+
+use crate::*;
+
+/// Configure how the [ListComponent] renders its items.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ListComponentConfig {
+    /// This is painted in the 1st row, and it does not scroll w/ the items.
+    pub maybe_header: Option<String>,
+    /// When enabled, Space toggles whether the focused item is selected.
+    pub multi_select: bool,
+    /// Painted before each item in multi-select mode.
+    pub selected_marker: String,
+    pub unselected_marker: String,
+    /// A vertical scrollbar is painted in the last col when the items don't fit.
+    pub show_scrollbar: bool,
+    pub scrollbar_markers: ScrollbarMarkers,
+    /// When enabled, the items scroll a few rows per frame instead of jumping (eg: on
+    /// PageDown). More info in [SmoothScroll].
+    pub smooth_scroll: bool,
+    pub maybe_item_style: Option<Style>,
+    pub focused_item_style: Style,
+    pub header_style: Style,
+}
+
+mod list_config_impl {
+    use super::*;
+
+    impl Default for ListComponentConfig {
+        fn default() -> Self {
+            Self {
+                maybe_header: None,
+                multi_select: false,
+                selected_marker: DEFAULT_LIST_SELECTED_MARKER.to_string(),
+                unselected_marker: DEFAULT_LIST_UNSELECTED_MARKER.to_string(),
+                show_scrollbar: true,
+                scrollbar_markers: ScrollbarMarkers::default(),
+                smooth_scroll: false,
+                maybe_item_style: None,
+                focused_item_style: get_list_focused_item_style(),
+                header_style: get_list_header_style(),
+            }
+        }
+    }
+
+    impl ListComponentConfig {
+        /// The header (if any) takes one row of the box, the items get the rest.
+        pub fn get_viewport_height(&self, box_bounds_size: Size) -> ChUnit {
+            match self.maybe_header {
+                Some(_) => box_bounds_size.row_count - 1,
+                None => box_bounds_size.row_count,
+            }
+        }
+    }
+}
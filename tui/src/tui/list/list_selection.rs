@@ -0,0 +1,122 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::collections::BTreeSet;
+
+use crate::*;
+
+/// Which item of a [ListComponent] has focus (the one that the keyboard moves around) and
+/// which items are selected (in multi-select mode). This is passed to the
+/// [OnListSelectionChangeFn] whenever it changes.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ListSelection {
+    pub focused_index: usize,
+    pub selected_indices: BTreeSet<usize>,
+}
+
+/// The ways in which the focus can be moved in a [ListComponent].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ListNavigation {
+    Up,
+    Down,
+    PageUp,
+    PageDown,
+    Home,
+    End,
+}
+
+mod list_selection_impl {
+    use super::*;
+
+    impl ListNavigation {
+        pub fn try_from_input_event(input_event: &InputEvent) -> Option<Self> {
+            let InputEvent::Keyboard(KeyPress::Plain {
+                key: Key::SpecialKey(special_key),
+            }) = input_event
+            else {
+                return None;
+            };
+            match special_key {
+                SpecialKey::Up => Some(ListNavigation::Up),
+                SpecialKey::Down => Some(ListNavigation::Down),
+                SpecialKey::PageUp => Some(ListNavigation::PageUp),
+                SpecialKey::PageDown => Some(ListNavigation::PageDown),
+                SpecialKey::Home => Some(ListNavigation::Home),
+                SpecialKey::End => Some(ListNavigation::End),
+                _ => None,
+            }
+        }
+    }
+
+    impl ListSelection {
+        /// Move the focus w/in `0..item_count`. Paging moves by `viewport_height` items.
+        pub fn navigate(
+            &mut self,
+            navigation: ListNavigation,
+            item_count: usize,
+            viewport_height: usize,
+        ) {
+            let Some(max_index) = item_count.checked_sub(1) else {
+                self.focused_index = 0;
+                return;
+            };
+            let page_size = viewport_height.max(1);
+            self.focused_index = match navigation {
+                ListNavigation::Up => self.focused_index.saturating_sub(1),
+                ListNavigation::Down => self.focused_index + 1,
+                ListNavigation::PageUp => self.focused_index.saturating_sub(page_size),
+                ListNavigation::PageDown => self.focused_index + page_size,
+                ListNavigation::Home => 0,
+                ListNavigation::End => max_index,
+            }
+            .min(max_index);
+        }
+
+        pub fn toggle_focused(&mut self) {
+            if !self.selected_indices.remove(&self.focused_index) {
+                self.selected_indices.insert(self.focused_index);
+            }
+        }
+
+        pub fn is_selected(&self, index: usize) -> bool {
+            self.selected_indices.contains(&index)
+        }
+
+        /// Drop anything that is out of range after the items have changed.
+        pub fn clamp_to_item_count(&mut self, item_count: usize) {
+            self.focused_index = self.focused_index.min(item_count.saturating_sub(1));
+            self.selected_indices.retain(|index| *index < item_count);
+        }
+
+        /// Return the scroll offset that keeps the focused item in a viewport that is
+        /// `viewport_height` items tall. The viewport only scrolls when the focused item
+        /// leaves it (just like the results panel of the dialog).
+        pub fn get_scroll_offset_to_keep_focused_in_view(
+            &self,
+            scroll_offset: usize,
+            viewport_height: usize,
+        ) -> usize {
+            if viewport_height == 0 || self.focused_index < scroll_offset {
+                return self.focused_index;
+            }
+            if self.focused_index >= scroll_offset + viewport_height {
+                return self.focused_index + 1 - viewport_height;
+            }
+            scroll_offset
+        }
+    }
+}
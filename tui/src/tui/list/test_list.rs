@@ -0,0 +1,122 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+#[cfg(test)]
+mod test_list_selection {
+    use r3bl_rs_utils_core::*;
+
+    use crate::*;
+
+    #[test]
+    fn test_navigation_from_input_event() {
+        assert_eq2!(
+            ListNavigation::try_from_input_event(&InputEvent::Keyboard(
+                keypress!(@special SpecialKey::PageDown)
+            )),
+            Some(ListNavigation::PageDown)
+        );
+        assert_eq2!(
+            ListNavigation::try_from_input_event(&InputEvent::Keyboard(
+                keypress!(@char ' ')
+            )),
+            None
+        );
+    }
+
+    #[test]
+    fn test_navigate() {
+        let mut selection = ListSelection::default();
+        let item_count = 20;
+        let viewport_height = 5;
+
+        // Can't move above the first item.
+        selection.navigate(ListNavigation::Up, item_count, viewport_height);
+        assert_eq2!(selection.focused_index, 0);
+
+        selection.navigate(ListNavigation::Down, item_count, viewport_height);
+        assert_eq2!(selection.focused_index, 1);
+
+        // Paging moves by the viewport height.
+        selection.navigate(ListNavigation::PageDown, item_count, viewport_height);
+        assert_eq2!(selection.focused_index, 6);
+        selection.navigate(ListNavigation::PageUp, item_count, viewport_height);
+        assert_eq2!(selection.focused_index, 1);
+
+        // Can't move below the last item.
+        selection.navigate(ListNavigation::End, item_count, viewport_height);
+        assert_eq2!(selection.focused_index, 19);
+        selection.navigate(ListNavigation::PageDown, item_count, viewport_height);
+        assert_eq2!(selection.focused_index, 19);
+
+        selection.navigate(ListNavigation::Home, item_count, viewport_height);
+        assert_eq2!(selection.focused_index, 0);
+
+        // Empty list.
+        selection.navigate(ListNavigation::End, 0, viewport_height);
+        assert_eq2!(selection.focused_index, 0);
+    }
+
+    #[test]
+    fn test_keep_focused_in_view() {
+        let viewport_height = 5;
+        let mut selection = ListSelection::default();
+
+        // In view, so no need to scroll.
+        selection.focused_index = 4;
+        assert_eq2!(
+            selection.get_scroll_offset_to_keep_focused_in_view(0, viewport_height),
+            0
+        );
+
+        // Below the viewport, so scroll until it is the last row.
+        selection.focused_index = 7;
+        assert_eq2!(
+            selection.get_scroll_offset_to_keep_focused_in_view(0, viewport_height),
+            3
+        );
+
+        // Above the viewport, so scroll until it is the first row.
+        selection.focused_index = 2;
+        assert_eq2!(
+            selection.get_scroll_offset_to_keep_focused_in_view(3, viewport_height),
+            2
+        );
+    }
+
+    #[test]
+    fn test_multi_select() {
+        let mut selection = ListSelection::default();
+
+        selection.toggle_focused();
+        selection.navigate(ListNavigation::End, 4, 4);
+        selection.toggle_focused();
+        assert!(selection.is_selected(0));
+        assert!(selection.is_selected(3));
+        assert!(!selection.is_selected(1));
+
+        // Toggling again deselects.
+        selection.toggle_focused();
+        assert!(!selection.is_selected(3));
+
+        // Out of range indices are dropped when the items change.
+        selection.toggle_focused();
+        selection.clamp_to_item_count(2);
+        assert_eq2!(selection.focused_index, 1);
+        assert_eq2!(selection.selected_indices.len(), 1);
+        assert!(selection.is_selected(0));
+    }
+}
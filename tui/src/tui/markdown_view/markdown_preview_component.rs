@@ -0,0 +1,155 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::{fmt::Debug, sync::Arc};
+
+use async_trait::async_trait;
+use r3bl_rs_utils_core::*;
+use tokio::sync::RwLock;
+
+use crate::*;
+
+/// A live preview of the Markdown in an [EditorComponent], eg: in the other column of a 2
+/// column layout. It is a [MarkdownViewComponent] that gets its source from the buffer of
+/// the editor (w/ the id in [editor_id](MarkdownPreviewComponent::editor_id)) in the
+/// store, so it is updated each time the change handler of the editor puts a new buffer
+/// in the store.
+///
+/// 1. Re-parsing is debounced by the editor: set [EditorEngineConfig::change_debounce] so
+///    that the buffer is only passed to the change handler (& so to this component) once
+///    the user stops typing. Renders that don't change the source (eg: the caret moves)
+///    don't parse it again.
+/// 2. The whole source is parsed each time it changes, since blocks (eg: code blocks,
+///    lists & blockquotes) depend on the lines before them.
+/// 3. When [sync_scroll](MarkdownPreviewComponent::sync_scroll) is set, the preview is
+///    scrolled so that the first line that is visible in the editor is at the top of the
+///    preview. The user can still scroll the preview (when it has focus), and it stays
+///    there until the editor scrolls again.
+///
+/// ```ignore
+/// let editor_id = FlexBoxId::from(ComponentId::Editor as u8);
+/// let preview_id = FlexBoxId::from(ComponentId::Preview as u8);
+/// let config_options = EditorEngineConfig {
+///     change_debounce: Some(Duration::from_millis(250)),
+///     ..Default::default()
+/// };
+/// component_registry.put(
+///     editor_id,
+///     EditorComponent::new_shared(editor_id, config_options, on_buffer_change),
+/// );
+/// component_registry.put(
+///     preview_id,
+///     MarkdownPreviewComponent::new_shared(preview_id, editor_id),
+/// );
+/// ```
+#[derive(Clone, Debug)]
+pub struct MarkdownPreviewComponent {
+    pub markdown_view: MarkdownViewComponent,
+    /// The id of the [EditorComponent] whose buffer is previewed.
+    pub editor_id: FlexBoxId,
+    pub sync_scroll: bool,
+    /// The first visible row of the editor the last time the preview was scrolled to it.
+    pub maybe_synced_editor_row_index: Option<ChUnit>,
+}
+
+pub mod markdown_preview_component_impl {
+    use super::*;
+
+    #[async_trait]
+    impl<S, A> Component<S, A> for MarkdownPreviewComponent
+    where
+        S: HasEditorBuffers + Debug + Default + Clone + PartialEq + Sync + Send + 'static,
+        A: Debug + Default + Clone + Sync + Send + 'static,
+    {
+        fn reset(&mut self) {
+            Component::<S, A>::reset(&mut self.markdown_view);
+            self.maybe_synced_editor_row_index = None;
+        }
+
+        fn get_id(&self) -> FlexBoxId { self.markdown_view.id }
+
+        async fn handle_event(
+            &mut self,
+            args: ComponentScopeArgs<'_, S, A>,
+            input_event: &InputEvent,
+        ) -> CommonResult<EventPropagation> {
+            self.markdown_view.handle_event(args, input_event).await
+        }
+
+        async fn render(
+            &mut self,
+            args: ComponentScopeArgs<'_, S, A>,
+            current_box: &FlexBox,
+            surface_bounds: SurfaceBounds,
+        ) -> CommonResult<RenderPipeline> {
+            if let Some(editor_buffer) = args.state.get_editor_buffer(self.editor_id) {
+                self.sync_with_editor_buffer(editor_buffer);
+            }
+            self.markdown_view
+                .render(args, current_box, surface_bounds)
+                .await
+        }
+    }
+
+    impl MarkdownPreviewComponent {
+        /// This is called on each render w/ the buffer of the editor from the store. It is
+        /// public so that apps which don't keep the buffer in the store can call it.
+        pub fn sync_with_editor_buffer(&mut self, editor_buffer: &EditorBuffer) {
+            // Each line ends w/ a new line, just like the editor's syntax highlighter.
+            let mut source = String::new();
+            for line in editor_buffer.get_lines() {
+                source.push_str(&line.string);
+                source.push('\n');
+            }
+            self.markdown_view.update_source(source);
+
+            if !self.sync_scroll {
+                return;
+            }
+            let editor_row_index = editor_buffer.get_scroll_offset().row_index;
+            if self.maybe_synced_editor_row_index == Some(editor_row_index) {
+                return;
+            }
+            self.maybe_synced_editor_row_index = Some(editor_row_index);
+            self.markdown_view
+                .scroll_to_source_row(ch!(@to_usize editor_row_index));
+        }
+    }
+}
+pub use markdown_preview_component_impl::*;
+
+mod constructor {
+    use super::*;
+
+    impl MarkdownPreviewComponent {
+        /// The scroll of the preview follows the editor, set
+        /// [sync_scroll](MarkdownPreviewComponent::sync_scroll) to `false` to turn it
+        /// off.
+        pub fn new(id: FlexBoxId, editor_id: FlexBoxId) -> Self {
+            Self {
+                markdown_view: MarkdownViewComponent::new(id, ""),
+                editor_id,
+                sync_scroll: true,
+                maybe_synced_editor_row_index: None,
+            }
+        }
+
+        pub fn new_shared(id: FlexBoxId, editor_id: FlexBoxId) -> Arc<RwLock<Self>> {
+            Arc::new(RwLock::new(MarkdownPreviewComponent::new(id, editor_id)))
+        }
+    }
+}
@@ -0,0 +1,380 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::{collections::HashMap, fmt::Debug, path::PathBuf, sync::Arc};
+
+use async_trait::async_trait;
+use r3bl_rs_utils_core::*;
+use syntect::{highlighting::Theme, parsing::SyntaxSet};
+use tokio::sync::RwLock;
+
+use crate::*;
+
+/// This is a reusable [Component] that shows a Markdown document for reading (it can't be
+/// edited). The document is parsed w/ [parse_markdown] & laid out by
+/// [layout_markdown_for_reading_with_images], and the user can scroll it w/ Up, Down,
+/// PageUp, PageDown, Home & End.
+///
+/// Images are painted w/ the [GraphicsProtocol] in
+/// [inline_image_options](MarkdownViewComponent::inline_image_options) when they are
+/// fully visible, and as alt text otherwise.
+///
+/// The source is only parsed & laid out again when it, the width of the box, or the theme
+/// changes, so scrolling (or any other render) is cheap. This is what makes it usable as a
+/// live preview, more info in [MarkdownPreviewComponent].
+#[derive(Clone, Debug)]
+pub struct MarkdownViewComponent {
+    pub id: FlexBoxId,
+    /// Set this w/ [MarkdownViewComponent::set_source] or
+    /// [MarkdownViewComponent::update_source], so that the layout is made again.
+    pub source: String,
+    pub scroll_offset_row_index: usize,
+    /// This is saved from the last render, so that scrolling knows how far it can go.
+    pub viewport_height: ChUnit,
+    /// This is saved from the last render, so that scrolling knows how far it can go.
+    pub line_count: usize,
+    /// Syntax highlighting support for code blocks. This is a very heavy object to create,
+    /// re-use it.
+    pub syntax_set: SyntaxSet,
+    /// Set [InlineImageOptions::graphics_protocol] to [GraphicsProtocol::None] to always
+    /// paint images as alt text.
+    pub inline_image_options: InlineImageOptions,
+    /// The escape sequences for the images that were painted, so that the image files are
+    /// only read & encoded once (for each size). [None] if the image can't be painted.
+    pub inline_image_cache: HashMap<(PathBuf, Size), Option<String>>,
+    /// The layout from the last render. [None] if the source has changed since then.
+    pub maybe_layout_cache: Option<MarkdownViewLayoutCache>,
+    /// Set by [MarkdownViewComponent::scroll_to_source_row], and applied in the next
+    /// render (once the source has been laid out).
+    pub maybe_scroll_to_source_row_index: Option<usize>,
+}
+
+/// The lines that [MarkdownViewComponent] paints for its source, and the source line that
+/// each block in them starts on. It is only valid for the [MarkdownViewLayoutKey] that it
+/// was made w/.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MarkdownViewLayoutCache {
+    pub key: MarkdownViewLayoutKey,
+    pub layout: MarkdownReadingLayout,
+    /// The index of the source line that each block starts on. Each item lines up w/ the
+    /// one in [MarkdownReadingLayout::block_row_indices].
+    pub block_source_row_indices: Vec<usize>,
+}
+
+/// The things (besides the source) that the layout of a [MarkdownViewComponent] depends
+/// on.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MarkdownViewLayoutKey {
+    pub col_count: ChUnit,
+    pub theme_id: String,
+    pub maybe_computed_style: Option<Style>,
+}
+
+pub mod markdown_view_component_impl {
+    use super::*;
+
+    #[async_trait]
+    impl<S, A> Component<S, A> for MarkdownViewComponent
+    where
+        S: Debug + Default + Clone + PartialEq + Sync + Send + 'static,
+        A: Debug + Default + Clone + Sync + Send + 'static,
+    {
+        fn reset(&mut self) { self.scroll_offset_row_index = 0; }
+
+        fn get_id(&self) -> FlexBoxId { self.id }
+
+        async fn handle_event(
+            &mut self,
+            _args: ComponentScopeArgs<'_, S, A>,
+            input_event: &InputEvent,
+        ) -> CommonResult<EventPropagation> {
+            match ListNavigation::try_from_input_event(input_event) {
+                Some(navigation) => {
+                    self.scroll(navigation);
+                    Ok(EventPropagation::ConsumedRender)
+                }
+                None => Ok(EventPropagation::Propagate),
+            }
+        }
+
+        async fn render(
+            &mut self,
+            args: ComponentScopeArgs<'_, S, A>,
+            current_box: &FlexBox,
+            _surface_bounds: SurfaceBounds, /* Ignore this. */
+        ) -> CommonResult<RenderPipeline> {
+            let ComponentScopeArgs {
+                shared_global_data, ..
+            } = args;
+
+            let origin_pos = current_box.style_adjusted_origin_pos;
+            let bounds_size = current_box.style_adjusted_bounds_size;
+
+            // Always use the active theme (it can be switched at runtime).
+            let (theme, md_style_config, theme_id) = {
+                let global_data = shared_global_data.read().await;
+                let active_theme = global_data.get_active_theme();
+                (
+                    active_theme.syntect_theme.clone(),
+                    active_theme.markdown_style_config,
+                    active_theme.id.clone(),
+                )
+            };
+
+            let key = MarkdownViewLayoutKey {
+                col_count: bounds_size.col_count,
+                theme_id,
+                maybe_computed_style: current_box.get_computed_style(),
+            };
+            let layout_cache = match self.maybe_layout_cache.take() {
+                Some(it) if it.key == key => it,
+                _ => self.make_layout_cache(key, &md_style_config, theme.as_ref())?,
+            };
+            let lines = &layout_cache.layout.lines;
+
+            // The box or the source might have changed since the last render.
+            self.viewport_height = bounds_size.row_count;
+            self.line_count = lines.len();
+            if let Some(source_row_index) = self.maybe_scroll_to_source_row_index.take() {
+                self.scroll_offset_row_index =
+                    layout_cache.get_rendered_row_index(source_row_index);
+            }
+            self.scroll_offset_row_index =
+                std::cmp::min(self.scroll_offset_row_index, self.get_max_scroll_offset());
+
+            let mut render_ops = render_ops!();
+            for (row_index, line) in lines
+                .iter()
+                .skip(self.scroll_offset_row_index)
+                .take(ch!(@to_usize self.viewport_height))
+                .enumerate()
+            {
+                render_ops.push(RenderOp::MoveCursorPositionRelTo(
+                    origin_pos,
+                    position! { col_index: 0, row_index: ch!(row_index) },
+                ));
+                line.clip(ch!(0), bounds_size.col_count)
+                    .render_into(&mut render_ops);
+                render_ops.push(RenderOp::ResetColor);
+            }
+
+            let mut render_pipeline = render_pipeline!();
+            render_pipeline.push(ZOrder::Normal, render_ops);
+            render_pipeline.inline_images = self.get_visible_inline_images(
+                &layout_cache.layout.image_placements,
+                origin_pos,
+            );
+            self.maybe_layout_cache = Some(layout_cache);
+            Ok(render_pipeline)
+        }
+    }
+
+    impl MarkdownViewComponent {
+        /// Replace the Markdown that is shown, and scroll back to the top.
+        pub fn set_source(&mut self, source: impl Into<String>) {
+            self.source = source.into();
+            self.scroll_offset_row_index = 0;
+            self.inline_image_cache.clear();
+            self.maybe_layout_cache = None;
+        }
+
+        /// Unlike [set_source](MarkdownViewComponent::set_source), this keeps the scroll
+        /// offset (& the images), which is what you want when the source is edited, eg:
+        /// in a live preview. Returns `false` (& keeps the layout) if the source hasn't
+        /// changed.
+        pub fn update_source(&mut self, source: impl Into<String>) -> bool {
+            let source = source.into();
+            if source == self.source {
+                return false;
+            }
+            self.source = source;
+            self.maybe_layout_cache = None;
+            true
+        }
+
+        /// Scroll so that the lines that are painted for the given line of the source are
+        /// at the top of the viewport (or as close as they can get). This is applied in
+        /// the next render.
+        pub fn scroll_to_source_row(&mut self, source_row_index: usize) {
+            self.maybe_scroll_to_source_row_index = Some(source_row_index);
+        }
+
+        /// Returns the index of the line that is painted for the given line of the
+        /// source, or [None] if the source hasn't been laid out yet (it is laid out when
+        /// it is rendered).
+        pub fn get_rendered_row_index(&self, source_row_index: usize) -> Option<usize> {
+            self.maybe_layout_cache
+                .as_ref()
+                .map(|it| it.get_rendered_row_index(source_row_index))
+        }
+
+        fn make_layout_cache(
+            &self,
+            key: MarkdownViewLayoutKey,
+            md_style_config: &MarkdownStyleConfig,
+            theme: &Theme,
+        ) -> CommonResult<MarkdownViewLayoutCache> {
+            let Ok((_, (document, block_source_row_indices))) =
+                parse_markdown_with_source_rows(&self.source)
+            else {
+                return CommonError::new_err_with_only_type(
+                    CommonErrorType::ParsingError,
+                );
+            };
+            let layout = layout_markdown_blocks_for_reading(
+                &document,
+                key.col_count,
+                &key.maybe_computed_style,
+                md_style_config,
+                Some((&self.syntax_set, theme)),
+                Some(&self.inline_image_options),
+            );
+            Ok(MarkdownViewLayoutCache {
+                key,
+                layout,
+                block_source_row_indices,
+            })
+        }
+
+        /// Scroll w/in the lines (from the last render). Paging scrolls by the viewport
+        /// height.
+        pub fn scroll(&mut self, navigation: ListNavigation) {
+            let page_size = std::cmp::max(ch!(@to_usize self.viewport_height), 1);
+            self.scroll_offset_row_index = match navigation {
+                ListNavigation::Up => self.scroll_offset_row_index.saturating_sub(1),
+                ListNavigation::Down => self.scroll_offset_row_index + 1,
+                ListNavigation::PageUp => {
+                    self.scroll_offset_row_index.saturating_sub(page_size)
+                }
+                ListNavigation::PageDown => self.scroll_offset_row_index + page_size,
+                ListNavigation::Home => 0,
+                ListNavigation::End => usize::MAX,
+            }
+            .min(self.get_max_scroll_offset());
+        }
+
+        /// Only the images that are fully in the viewport are painted, since the protocols
+        /// can't clip them.
+        fn get_visible_inline_images(
+            &mut self,
+            image_placements: &[InlineImagePlacement],
+            origin_pos: Position,
+        ) -> Vec<InlineImage> {
+            let graphics_protocol = self.inline_image_options.graphics_protocol;
+            let start_row_index = self.scroll_offset_row_index;
+            let end_row_index = start_row_index + ch!(@to_usize self.viewport_height);
+
+            let mut inline_images = vec![];
+            for placement in image_placements {
+                let placement_end_row_index =
+                    placement.row_index + ch!(@to_usize placement.size.row_count);
+                if placement.row_index < start_row_index
+                    || placement_end_row_index > end_row_index
+                {
+                    continue;
+                }
+
+                let maybe_escape_sequence = self
+                    .inline_image_cache
+                    .entry((placement.path.clone(), placement.size))
+                    .or_insert_with(|| {
+                        std::fs::read(&placement.path).ok().and_then(|image_bytes| {
+                            graphics_protocol.encode_image(&image_bytes, placement.size)
+                        })
+                    });
+                if let Some(escape_sequence) = maybe_escape_sequence {
+                    inline_images.push(InlineImage {
+                        origin_pos: position! {
+                            col_index: origin_pos.col_index,
+                            row_index: origin_pos.row_index
+                                + ch!(placement.row_index - start_row_index)
+                        },
+                        size: placement.size,
+                        escape_sequence: escape_sequence.clone(),
+                    });
+                }
+            }
+            inline_images
+        }
+
+        /// Scrolling stops when the last line is at the bottom of the viewport.
+        fn get_max_scroll_offset(&self) -> usize {
+            self.line_count
+                .saturating_sub(ch!(@to_usize self.viewport_height))
+        }
+    }
+}
+pub use markdown_view_component_impl::*;
+
+mod markdown_view_layout_cache_impl {
+    use super::*;
+
+    impl MarkdownViewLayoutCache {
+        /// The source line is mapped to the block that it is in, and then to the same
+        /// line w/in the lines that are painted for the block. This is approximate when
+        /// the lines of a block are wrapped, so it is clamped to the lines of the block.
+        /// Lines after the last block map to its last line.
+        pub fn get_rendered_row_index(&self, source_row_index: usize) -> usize {
+            let block_row_indices = &self.layout.block_row_indices;
+            let line_count = self.layout.lines.len();
+
+            // The block that the source line is in.
+            let block_index = self
+                .block_source_row_indices
+                .partition_point(|it| *it <= source_row_index);
+            let Some(block_index) = block_index.checked_sub(1) else {
+                return 0;
+            };
+
+            let start_row_index = block_row_indices[block_index];
+            let end_row_index = block_row_indices
+                .get(block_index + 1)
+                .copied()
+                .unwrap_or(line_count);
+            let offset = source_row_index - self.block_source_row_indices[block_index];
+            let last_row_index = end_row_index.saturating_sub(1).max(start_row_index);
+            (start_row_index + offset).min(last_row_index)
+        }
+    }
+}
+
+mod constructor {
+    use super::*;
+
+    impl MarkdownViewComponent {
+        /// Syntax highlighting support - [SyntaxSet] is a very expensive object to create,
+        /// so it is created once here and re-used.
+        pub fn new(id: FlexBoxId, source: impl Into<String>) -> Self {
+            Self {
+                id,
+                source: source.into(),
+                scroll_offset_row_index: 0,
+                viewport_height: ch!(0),
+                line_count: 0,
+                syntax_set: SyntaxSet::load_defaults_newlines(),
+                inline_image_options: Default::default(),
+                inline_image_cache: Default::default(),
+                maybe_layout_cache: None,
+                maybe_scroll_to_source_row_index: None,
+            }
+        }
+
+        pub fn new_shared(id: FlexBoxId, source: impl Into<String>) -> Arc<RwLock<Self>> {
+            Arc::new(RwLock::new(MarkdownViewComponent::new(id, source)))
+        }
+    }
+}
@@ -0,0 +1,648 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+//! Lays out a [MdDocument] for reading (and not editing), which is what the
+//! [MarkdownViewComponent] paints. Unlike [StyleUSSpanLines::from_document] (which the
+//! [editor] uses), the Markdown markup is hidden:
+//! - Headings are bold w/out the `#` marker, & `#` / `##` headings are underlined.
+//! - List items are indented & use bullets (which change w/ each level of nesting).
+//! - Lines that start w/ `>` are painted as a blockquote, w/ a bar for each level of
+//!   nesting.
+//! - Code blocks are painted in a box (& still syntax highlighted).
+//! - Images are painted as a placeholder w/ their alt text. An image that is on a line of
+//!   its own & points at a local file can also be painted w/ a [GraphicsProtocol] (if the
+//!   terminal supports one), more info in [layout_markdown_for_reading_with_images].
+//!
+//! The inline styles come from the same [MarkdownStyleConfig] that the [editor] uses. Long
+//! lines are wrapped to fit the width of the box (except for code blocks, which are
+//! clipped).
+
+use std::path::PathBuf;
+
+use r3bl_rs_utils_core::*;
+use r3bl_rs_utils_macro::style;
+use syntect::{highlighting::Theme, parsing::SyntaxSet};
+
+use crate::{constants::*, *};
+
+/// The bullets for the nesting levels of unordered lists. They repeat if the nesting is
+/// deeper than this.
+pub const MARKDOWN_VIEW_BULLETS: [&str; 3] = ["•", "◦", "▪"];
+
+/// How images are painted by [layout_markdown_for_reading_with_images].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InlineImageOptions {
+    /// Images are painted as alt text if this is [GraphicsProtocol::None].
+    pub graphics_protocol: GraphicsProtocol,
+    /// Relative image paths are resolved against this dir (eg: the dir of the Markdown
+    /// file). If it isn't set, they are resolved against the current dir.
+    pub maybe_base_dir: Option<PathBuf>,
+    /// The number of rows that each image takes up. The image is scaled to fit in these
+    /// rows & the width of the box.
+    pub row_count: usize,
+}
+
+/// Where to paint an image, in the lines returned by
+/// [layout_markdown_for_reading_with_images].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InlineImagePlacement {
+    /// Index of the first line of the rows that are reserved for the image.
+    pub row_index: usize,
+    pub size: Size,
+    pub path: PathBuf,
+}
+
+/// Returned by [layout_markdown_blocks_for_reading].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MarkdownReadingLayout {
+    pub lines: StyleUSSpanLines,
+    pub image_placements: Vec<InlineImagePlacement>,
+    /// The index (in `lines`) of the first line of each block in the [MdDocument]. A block
+    /// that isn't painted (eg: an empty list) has the same index as the next one.
+    pub block_row_indices: Vec<usize>,
+}
+
+mod inline_image_options_impl {
+    use super::*;
+
+    impl Default for InlineImageOptions {
+        fn default() -> Self {
+            Self {
+                graphics_protocol: GraphicsProtocol::detect(),
+                maybe_base_dir: None,
+                row_count: DEFAULT_INLINE_IMAGE_ROW_COUNT,
+            }
+        }
+    }
+
+    impl InlineImageOptions {
+        /// Returns the path of the local image file that `url` points to. Returns [None]
+        /// if there is no [GraphicsProtocol], or if `url` is remote (eg: `https://..`), or
+        /// the file doesn't exist.
+        pub fn resolve_local_image(&self, url: &str) -> Option<PathBuf> {
+            if self.graphics_protocol == GraphicsProtocol::None {
+                return None;
+            }
+            let url = url.strip_prefix("file://").unwrap_or(url);
+            if url.is_empty() || url.contains("://") || url.starts_with("data:") {
+                return None;
+            }
+
+            let path = PathBuf::from(url);
+            let path = match (&self.maybe_base_dir, path.is_relative()) {
+                (Some(base_dir), true) => base_dir.join(path),
+                _ => path,
+            };
+            path.is_file().then_some(path)
+        }
+    }
+}
+
+/// Returns the lines to paint for `document` in a box that is `col_count` cols wide. More
+/// info in the [module docs](self).
+pub fn layout_markdown_for_reading(
+    document: &MdDocument,
+    col_count: ChUnit,
+    maybe_current_box_computed_style: &Option<Style>,
+    md_style_config: &MarkdownStyleConfig,
+    maybe_syntect_tuple: Option<(&SyntaxSet, &Theme)>,
+) -> StyleUSSpanLines {
+    let (lines, _) = layout_markdown_for_reading_with_images(
+        document,
+        col_count,
+        maybe_current_box_computed_style,
+        md_style_config,
+        maybe_syntect_tuple,
+        None,
+    );
+    lines
+}
+
+/// Same as [layout_markdown_for_reading], except that an image that is on a line of its
+/// own, eg: `![alt](images/logo.png)`, & that [resolves to a local
+/// file](InlineImageOptions::resolve_local_image) gets [InlineImageOptions::row_count]
+/// rows. Its alt text is painted in the first row (in case the image can't be painted),
+/// and the [InlineImagePlacement]s that are returned say where to paint the images.
+pub fn layout_markdown_for_reading_with_images(
+    document: &MdDocument,
+    col_count: ChUnit,
+    maybe_current_box_computed_style: &Option<Style>,
+    md_style_config: &MarkdownStyleConfig,
+    maybe_syntect_tuple: Option<(&SyntaxSet, &Theme)>,
+    maybe_image_options: Option<&InlineImageOptions>,
+) -> (StyleUSSpanLines, Vec<InlineImagePlacement>) {
+    let MarkdownReadingLayout {
+        lines,
+        image_placements,
+        ..
+    } = layout_markdown_blocks_for_reading(
+        document,
+        col_count,
+        maybe_current_box_computed_style,
+        md_style_config,
+        maybe_syntect_tuple,
+        maybe_image_options,
+    );
+    (lines, image_placements)
+}
+
+/// Same as [layout_markdown_for_reading_with_images], except that it also returns the
+/// index of the first line that is painted for each block of `document`, in
+/// [MarkdownReadingLayout::block_row_indices].
+pub fn layout_markdown_blocks_for_reading(
+    document: &MdDocument,
+    col_count: ChUnit,
+    maybe_current_box_computed_style: &Option<Style>,
+    md_style_config: &MarkdownStyleConfig,
+    maybe_syntect_tuple: Option<(&SyntaxSet, &Theme)>,
+    maybe_image_options: Option<&InlineImageOptions>,
+) -> MarkdownReadingLayout {
+    let base_style = maybe_current_box_computed_style.unwrap_or_default();
+    let mut lines = StyleUSSpanLines::default();
+    let mut image_placements = vec![];
+    let mut block_row_indices = Vec::with_capacity(document.len());
+    // The nesting level of the last blockquote line (if any), for lazy continuation lines.
+    let mut blockquote_nesting_level = 0;
+
+    for block in document.iter() {
+        block_row_indices.push(lines.len());
+
+        if !matches!(block, MdBlockElement::Text(_)) {
+            blockquote_nesting_level = 0;
+        }
+
+        match block {
+            MdBlockElement::Title(title) => {
+                let line =
+                    make_line(base_style + get_metadata_title_value_style(), title);
+                lines += wrap_line(&line, col_count, &List::default(), &List::default());
+            }
+            MdBlockElement::Date(date) => {
+                let line = make_line(base_style + get_foreground_dim_style(), date);
+                lines += wrap_line(&line, col_count, &List::default(), &List::default());
+            }
+            MdBlockElement::Authors(authors) => {
+                let line = make_line(
+                    base_style + get_foreground_dim_style(),
+                    &authors.join(", "),
+                );
+                lines += wrap_line(&line, col_count, &List::default(), &List::default());
+            }
+            MdBlockElement::Tags(tags) => {
+                let line = make_line(
+                    base_style + get_metadata_tags_values_style(),
+                    &tags
+                        .iter()
+                        .map(|tag| format!("{HEADING_CHAR}{tag}"))
+                        .collect::<Vec<String>>()
+                        .join(SPACE),
+                );
+                lines += wrap_line(&line, col_count, &List::default(), &List::default());
+            }
+            MdBlockElement::Heading(heading_data) => {
+                lines += layout_heading(
+                    heading_data,
+                    col_count,
+                    maybe_current_box_computed_style,
+                    md_style_config,
+                );
+            }
+            MdBlockElement::Text(fragments) => {
+                let maybe_image_path =
+                    match (maybe_image_options, blockquote_nesting_level) {
+                        (Some(image_options), 0) => get_image_only_url(fragments)
+                            .and_then(|url| image_options.resolve_local_image(url))
+                            .map(|path| (path, image_options.row_count)),
+                        _ => None,
+                    };
+                if let Some((path, row_count)) = maybe_image_path {
+                    let row_index = lines.len();
+                    lines += wrap_line(
+                        &make_reading_line(fragments, &base_style, md_style_config),
+                        col_count,
+                        &List::default(),
+                        &List::default(),
+                    );
+                    while lines.len() < row_index + row_count {
+                        lines += StyleUSSpanLine::default();
+                    }
+                    image_placements.push(InlineImagePlacement {
+                        row_index,
+                        size: size! { col_count: col_count, row_count: ch!(row_count) },
+                        path,
+                    });
+                    continue;
+                }
+
+                let (text_lines, nesting_level) = layout_text(
+                    fragments,
+                    blockquote_nesting_level,
+                    col_count,
+                    &base_style,
+                    md_style_config,
+                );
+                blockquote_nesting_level = nesting_level;
+                lines += text_lines;
+            }
+            MdBlockElement::SmartList((list_lines, bullet_kind, indent)) => {
+                lines += layout_smart_list(
+                    list_lines,
+                    bullet_kind,
+                    *indent,
+                    col_count,
+                    &base_style,
+                    md_style_config,
+                );
+            }
+            MdBlockElement::CodeBlock(code_block_lines) => {
+                lines += layout_code_block(
+                    code_block_lines,
+                    col_count,
+                    maybe_current_box_computed_style,
+                    md_style_config,
+                    maybe_syntect_tuple,
+                );
+            }
+        }
+    }
+
+    MarkdownReadingLayout {
+        lines,
+        image_placements,
+        block_row_indices,
+    }
+}
+
+/// Returns the url of the image if it is the only thing on the line (other than
+/// whitespace).
+fn get_image_only_url<'a>(fragments: &MdLineFragments<'a>) -> Option<&'a str> {
+    let mut it = fragments.iter().filter(
+        |fragment| !matches!(fragment, MdLineFragment::Plain(text) if text.trim().is_empty()),
+    );
+    match (it.next(), it.next()) {
+        (Some(MdLineFragment::Image(HyperlinkData { url, .. })), None) => Some(*url),
+        _ => None,
+    }
+}
+
+/// The heading text is colorized just like in the [editor] (but w/out the `#` marker), and
+/// `#` & `##` headings are underlined w/ a rule, since a terminal can't make them larger.
+fn layout_heading(
+    heading_data: &HeadingData,
+    col_count: ChUnit,
+    maybe_current_box_computed_style: &Option<Style>,
+    md_style_config: &MarkdownStyleConfig,
+) -> StyleUSSpanLines {
+    let mut line = StyleUSSpanLine::from_heading_data(
+        heading_data,
+        maybe_current_box_computed_style,
+        md_style_config,
+    );
+    // Drop the span for the `#` marker.
+    line.items.remove(0);
+    line.add_style(style! { attrib: [bold] });
+
+    let mut lines = wrap_line(&line, col_count, &List::default(), &List::default());
+
+    let maybe_rule_char = match heading_data.level {
+        HeadingLevel::Heading1 => Some("═"),
+        HeadingLevel::Heading2 => Some("─"),
+        _ => None,
+    };
+    if let Some(rule_char) = maybe_rule_char {
+        let rule_width = std::cmp::min(line.display_width(), col_count);
+        lines += make_line(
+            maybe_current_box_computed_style.unwrap_or_default()
+                + get_foreground_dim_style(),
+            &rule_char.repeat(ch!(@to_usize rule_width)),
+        );
+    }
+
+    lines
+}
+
+/// A line that starts w/ `>` is painted as a blockquote, eg: `> Some quote`, w/ a bar for
+/// each level of nesting (eg: `> > Nested quote`), & the text is muted. A line right after
+/// it that isn't blank is a lazy continuation of the blockquote, at the same level. Returns
+/// the lines & the nesting level of this line (`0` if it isn't part of a blockquote).
+fn layout_text(
+    fragments: &MdLineFragments,
+    lazy_nesting_level: usize,
+    col_count: ChUnit,
+    base_style: &Style,
+    md_style_config: &MarkdownStyleConfig,
+) -> (StyleUSSpanLines, usize) {
+    let (text_fragments, nesting_level) = match get_blockquote_prefix(fragments) {
+        Some(prefix) => {
+            let mut it = fragments.clone();
+            it.items[0] = MdLineFragment::Plain(prefix.text);
+            (it, prefix.nesting_level)
+        }
+        None if lazy_nesting_level > 0 && !is_blank_line(fragments) => {
+            (fragments.clone(), lazy_nesting_level)
+        }
+        None => {
+            let lines = wrap_line(
+                &make_reading_line(fragments, base_style, md_style_config),
+                col_count,
+                &List::default(),
+                &List::default(),
+            );
+            return (lines, 0);
+        }
+    };
+
+    let mut line = make_reading_line(&text_fragments, base_style, md_style_config);
+    line.add_style(md_style_config.blockquote);
+
+    let mut bars = StyleUSSpanLine::default();
+    for level in 0..nesting_level {
+        bars += StyleUSSpan::new(
+            *base_style
+                + md_style_config.get_blockquote_bar_style_for_nesting_level(level),
+            US::from(format!("{}{SPACE}", md_style_config.blockquote_bar_char)),
+        );
+    }
+
+    (wrap_line(&line, col_count, &bars, &bars), nesting_level)
+}
+
+/// Each block is a single list item (a nested item is a block of its own w/ a bigger
+/// `indent`). The lines after the first one in the item are lined up w/ the content of the
+/// first one.
+fn layout_smart_list(
+    list_lines: &Lines,
+    bullet_kind: &BulletKind,
+    indent: usize,
+    col_count: ChUnit,
+    base_style: &Style,
+    md_style_config: &MarkdownStyleConfig,
+) -> StyleUSSpanLines {
+    let nesting_level = indent / LIST_PREFIX_BASE_WIDTH;
+    let (bullet, bullet_style) = match bullet_kind {
+        BulletKind::Ordered(number) => (
+            format!("{number}{PERIOD}{SPACE}"),
+            *base_style + md_style_config.list_number,
+        ),
+        BulletKind::Unordered => {
+            let bullet =
+                MARKDOWN_VIEW_BULLETS[nesting_level % MARKDOWN_VIEW_BULLETS.len()];
+            (
+                format!("{bullet}{SPACE}"),
+                *base_style
+                    + md_style_config
+                        .get_list_bullet_style_for_nesting_level(nesting_level),
+            )
+        }
+    };
+    let first_line_prefix =
+        make_line(bullet_style, &format!("{}{bullet}", SPACE.repeat(indent)));
+    let rest_line_prefix = make_line(
+        *base_style,
+        &SPACE.repeat(indent + UnicodeString::str_display_width(&bullet)),
+    );
+
+    let mut lines = StyleUSSpanLines::default();
+    for (index, fragments) in list_lines.iter().enumerate() {
+        let line = make_reading_line(fragments, base_style, md_style_config);
+        let prefix = match index {
+            0 => &first_line_prefix,
+            _ => &rest_line_prefix,
+        };
+        lines += wrap_line(&line, col_count, prefix, &rest_line_prefix);
+    }
+    lines
+}
+
+/// The code is syntax highlighted just like in the [editor], and then painted in a box w/
+/// the language in the top border. Lines that don't fit are clipped (not wrapped).
+fn layout_code_block(
+    code_block_lines: &CodeBlockLines,
+    col_count: ChUnit,
+    maybe_current_box_computed_style: &Option<Style>,
+    md_style_config: &MarkdownStyleConfig,
+    maybe_syntect_tuple: Option<(&SyntaxSet, &Theme)>,
+) -> StyleUSSpanLines {
+    let border_style = maybe_current_box_computed_style.unwrap_or_default()
+        + get_code_block_border_style();
+    // The box takes up 2 cols on each side: `│ ` & ` │`.
+    let content_col_count = col_count - 4;
+
+    let highlighted_lines = StyleUSSpanLines::from_block_codeblock(
+        code_block_lines,
+        maybe_current_box_computed_style,
+        md_style_config,
+        maybe_syntect_tuple,
+    );
+
+    let mut lines = StyleUSSpanLines::default();
+
+    // Top border, eg: `┌─ rust ────┐`.
+    let mut top_border = "┌─".to_string();
+    if let Some(language) = code_block_lines.first().and_then(|it| it.language) {
+        top_border.push_str(&format!("{SPACE}{language}{SPACE}"));
+    }
+    let top_border_width = ch!(UnicodeString::str_display_width(&top_border));
+    let fill_width = col_count - top_border_width - 1;
+    top_border.push_str(&"─".repeat(ch!(@to_usize fill_width)));
+    top_border.push('┐');
+    lines += make_line(border_style, &top_border);
+
+    // Content.
+    for (code_block_line, highlighted_line) in
+        code_block_lines.iter().zip(highlighted_lines.iter())
+    {
+        if !matches!(code_block_line.content, CodeBlockLineContent::Text(_)) {
+            continue;
+        }
+        let content =
+            StyleUSSpanLine::from(highlighted_line.clip(ch!(0), content_col_count));
+        let padding_width = content_col_count - content.display_width();
+
+        let mut line = make_line(border_style, "│ ");
+        line += content;
+        line += StyleUSSpan::new(
+            border_style,
+            US::from(format!(
+                "{}│",
+                SPACE.repeat(ch!(@to_usize padding_width) + 1)
+            )),
+        );
+        lines += line;
+    }
+
+    // Bottom border.
+    lines += make_line(
+        border_style,
+        &format!("└{}┘", "─".repeat(ch!(@to_usize (col_count - 2)))),
+    );
+
+    lines
+}
+
+/// Converts `fragments` to a line w/out the markup, eg: `**bold**` is painted as `bold`
+/// (in bold), & a link is painted as its text (w/out the URL).
+fn make_reading_line(
+    fragments: &MdLineFragments,
+    base_style: &Style,
+    md_style_config: &MarkdownStyleConfig,
+) -> StyleUSSpanLine {
+    let mut line = StyleUSSpanLine::default();
+    for fragment in fragments.iter() {
+        match fragment {
+            MdLineFragment::Bold(text) => {
+                line +=
+                    StyleUSSpan::new(*base_style + md_style_config.bold, US::from(*text));
+            }
+            MdLineFragment::Italic(text) => {
+                line += StyleUSSpan::new(
+                    *base_style + md_style_config.italic,
+                    US::from(*text),
+                );
+            }
+            MdLineFragment::BoldItalic(text) => {
+                line += StyleUSSpan::new(
+                    *base_style + md_style_config.bold_italic,
+                    US::from(*text),
+                );
+            }
+            MdLineFragment::InlineCode(text) => {
+                line += StyleUSSpan::new(
+                    *base_style + md_style_config.inline_code,
+                    US::from(*text),
+                );
+            }
+            MdLineFragment::Link(HyperlinkData { text, .. }) => {
+                line += StyleUSSpan::new(
+                    *base_style + md_style_config.link_text,
+                    US::from(*text),
+                );
+            }
+            MdLineFragment::Image(HyperlinkData { text: alt_text, .. }) => {
+                let dim_style = *base_style + get_foreground_dim_style();
+                line += StyleUSSpan::new(dim_style, US::from("[image: "));
+                line += StyleUSSpan::new(
+                    *base_style + md_style_config.link_text,
+                    US::from(*alt_text),
+                );
+                line += StyleUSSpan::new(dim_style, US::from(RIGHT_IMAGE));
+            }
+            // The bullets are painted by layout_smart_list.
+            MdLineFragment::OrderedListBullet { .. }
+            | MdLineFragment::UnorderedListBullet { .. } => {}
+            // There is no markup to hide in these.
+            MdLineFragment::Plain(_) | MdLineFragment::Checkbox(_) => {
+                line.items.extend(StyleUSSpan::from_fragment(
+                    fragment,
+                    &Some(*base_style),
+                    md_style_config,
+                ));
+            }
+        }
+    }
+    line
+}
+
+fn make_line(style: Style, text: &str) -> StyleUSSpanLine {
+    let mut line = StyleUSSpanLine::default();
+    line += StyleUSSpan::new(style, US::from(text));
+    line
+}
+
+/// Splits `line` into rows that fit in `col_count` cols (including the prefix), breaking at
+/// the last whitespace that fits, or in the middle of a word if there is none. The first
+/// row starts w/ `first_prefix` & the rest w/ `rest_prefix`. An empty `line` is a single
+/// (empty) row.
+pub fn wrap_line(
+    line: &StyleUSSpanLine,
+    col_count: ChUnit,
+    first_prefix: &StyleUSSpanLine,
+    rest_prefix: &StyleUSSpanLine,
+) -> StyleUSSpanLines {
+    let graphemes: Vec<(Style, &GraphemeClusterSegment)> = line
+        .iter()
+        .flat_map(|span| span.text.iter().map(move |segment| (span.style, segment)))
+        .collect();
+
+    let is_whitespace =
+        |segment: &GraphemeClusterSegment| segment.string.trim().is_empty();
+    let get_row_width = |row: &[(Style, &GraphemeClusterSegment)]| {
+        row.iter()
+            .fold(ch!(0), |acc, (_, segment)| acc + segment.unicode_width)
+    };
+
+    let mut rows: Vec<Vec<(Style, &GraphemeClusterSegment)>> = vec![];
+    let mut row: Vec<(Style, &GraphemeClusterSegment)> = vec![];
+
+    for grapheme in graphemes {
+        let prefix = if rows.is_empty() {
+            first_prefix
+        } else {
+            rest_prefix
+        };
+        // There is always room for at least one grapheme, so this always terminates.
+        let avail_col_count = std::cmp::max(col_count - prefix.display_width(), ch!(1));
+
+        if !row.is_empty()
+            && get_row_width(&row) + grapheme.1.unicode_width > avail_col_count
+        {
+            // Carry the partial word at the end of the row over to the next row (unless
+            // the row ends at a word boundary).
+            let maybe_whitespace_index = match is_whitespace(grapheme.1) {
+                true => None,
+                false => row.iter().rposition(|(_, segment)| is_whitespace(*segment)),
+            };
+            let carry = match maybe_whitespace_index {
+                Some(index) if index > 0 => {
+                    let carry = row.split_off(index + 1);
+                    row.pop(); /* Drop the whitespace. */
+                    carry
+                }
+                _ => vec![],
+            };
+            rows.push(row);
+            row = carry;
+        }
+
+        // Don't start a wrapped row w/ whitespace.
+        if row.is_empty() && !rows.is_empty() && is_whitespace(grapheme.1) {
+            continue;
+        }
+
+        row.push(grapheme);
+    }
+    rows.push(row);
+
+    let mut lines = StyleUSSpanLines::default();
+    for (index, row) in rows.into_iter().enumerate() {
+        let mut line = match index {
+            0 => first_prefix.clone(),
+            _ => rest_prefix.clone(),
+        };
+        for (style, segment) in row {
+            match line.items.last_mut() {
+                Some(last_span) if last_span.style == style => {
+                    last_span.text =
+                        US::from(format!("{}{}", last_span.text.string, segment.string));
+                }
+                _ => line += StyleUSSpan::new(style, US::from(segment.string.as_str())),
+            }
+        }
+        lines += line;
+    }
+    lines
+}
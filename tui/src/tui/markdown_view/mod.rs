@@ -0,0 +1,29 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+// Attach sources.
+pub mod markdown_preview_component;
+pub mod markdown_view_component_struct;
+pub mod markdown_view_layout;
+
+// Re-export.
+pub use markdown_preview_component::*;
+pub use markdown_view_component_struct::*;
+pub use markdown_view_layout::*;
+
+// Tests.
+pub mod test_markdown_view;
@@ -0,0 +1,279 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+#[cfg(test)]
+mod test_markdown_view_layout {
+    use r3bl_rs_utils_core::*;
+
+    use crate::*;
+
+    fn layout(input: &str, col_count: u16) -> Vec<String> {
+        let (_, document) = parse_markdown(input).unwrap();
+        layout_markdown_for_reading(
+            &document,
+            ch!(col_count),
+            &None,
+            &MarkdownStyleConfig::default(),
+            None,
+        )
+        .iter()
+        .map(|line| line.get_plain_text())
+        .collect()
+    }
+
+    fn make_plain_line(text: &str) -> StyleUSSpanLine {
+        let mut line = StyleUSSpanLine::default();
+        line += StyleUSSpan::new(Style::default(), US::from(text));
+        line
+    }
+
+    #[test]
+    fn test_heading() {
+        assert_eq2!(layout("# Hello\n", 20), vec!["Hello", "═════"]);
+        assert_eq2!(layout("## Hello\n", 20), vec!["Hello", "─────"]);
+        assert_eq2!(layout("### Hello\n", 20), vec!["Hello"]);
+    }
+
+    #[test]
+    fn test_inline_markup_is_hidden() {
+        assert_eq2!(
+            layout("**bold** `code` [link](https://r3bl.com)\n", 40),
+            vec!["bold code link"]
+        );
+        assert_eq2!(
+            layout("![some alt text](https://r3bl.com/image.png)\n", 40),
+            vec!["[image: some alt text]"]
+        );
+    }
+
+    #[test]
+    fn test_inline_image() {
+        let base_dir = std::env::temp_dir();
+        std::fs::write(
+            base_dir.join("r3bl_tui_test_inline_image.png"),
+            b"\x89PNG\r\n\x1a\n",
+        )
+        .unwrap();
+        let image_options = InlineImageOptions {
+            graphics_protocol: GraphicsProtocol::Kitty,
+            maybe_base_dir: Some(base_dir.clone()),
+            row_count: 3,
+        };
+        let (_, document) = parse_markdown(
+            "![logo](r3bl_tui_test_inline_image.png)\n![remote](https://r3bl.com/a.png)\n",
+        )
+        .unwrap();
+
+        let (lines, image_placements) = layout_markdown_for_reading_with_images(
+            &document,
+            ch!(40),
+            &None,
+            &MarkdownStyleConfig::default(),
+            None,
+            Some(&image_options),
+        );
+
+        // Only the local image gets rows of its own, w/ the alt text in the first one.
+        assert_eq2!(
+            lines
+                .iter()
+                .map(|line| line.get_plain_text())
+                .collect::<Vec<_>>(),
+            vec!["[image: logo]", "", "", "[image: remote]"]
+        );
+        assert_eq2!(
+            image_placements,
+            vec![InlineImagePlacement {
+                row_index: 0,
+                size: size!( col_count: 40, row_count: 3 ),
+                path: base_dir.join("r3bl_tui_test_inline_image.png"),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_blockquote() {
+        assert_eq2!(layout("> Some quote\n", 40), vec!["┃ Some quote"]);
+        assert_eq2!(layout("> aaa bbb\n", 7), vec!["┃ aaa", "┃ bbb"]);
+    }
+
+    #[test]
+    fn test_nested_blockquote() {
+        assert_eq2!(
+            layout("> one\n>> two\nlazy\n\nafter\n", 40),
+            vec!["┃ one", "┃ ┃ two", "┃ ┃ lazy", "", "after"]
+        );
+    }
+
+    #[test]
+    fn test_nested_lists() {
+        assert_eq2!(
+            layout("- ul1\n  - ul2\n    - ul3\n1. ol1\n", 40),
+            vec!["• ul1", "  ◦ ul2", "    ▪ ul3", "1. ol1"]
+        );
+
+        // Wrapped lines are lined up w/ the content (not the bullet).
+        assert_eq2!(layout("- aaa bbb\n", 7), vec!["• aaa", "  bbb"]);
+    }
+
+    #[test]
+    fn test_code_block() {
+        assert_eq2!(
+            layout("```rust\nfn main() {}\n```\n", 20),
+            vec![
+                "┌─ rust ───────────┐",
+                "│ fn main() {}     │",
+                "└──────────────────┘",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_wrap_line() {
+        let no_prefix = StyleUSSpanLine::default();
+        let wrap = |text: &str, col_count: u16| -> Vec<String> {
+            wrap_line(
+                &make_plain_line(text),
+                ch!(col_count),
+                &no_prefix,
+                &no_prefix,
+            )
+            .iter()
+            .map(|line| line.get_plain_text())
+            .collect()
+        };
+
+        // Break at whitespace.
+        assert_eq2!(wrap("aaa bbb ccc", 7), vec!["aaa bbb", "ccc"]);
+        assert_eq2!(wrap("aaa bbbb", 6), vec!["aaa", "bbbb"]);
+
+        // Break in the middle of a word that doesn't fit.
+        assert_eq2!(wrap("abcdefghij", 4), vec!["abcd", "efgh", "ij"]);
+
+        // Wide chars aren't split.
+        assert_eq2!(wrap("😃😃😃", 5), vec!["😃😃", "😃"]);
+
+        // Empty line.
+        assert_eq2!(wrap("", 10), vec![""]);
+    }
+}
+
+#[cfg(test)]
+mod test_markdown_view_component {
+    use r3bl_rs_utils_core::*;
+
+    use crate::*;
+
+    #[test]
+    fn test_scroll() {
+        let mut component = MarkdownViewComponent::new(FlexBoxId::from(0), "");
+        component.line_count = 10;
+        component.viewport_height = ch!(4);
+
+        component.scroll(ListNavigation::Up);
+        assert_eq2!(component.scroll_offset_row_index, 0);
+
+        component.scroll(ListNavigation::Down);
+        assert_eq2!(component.scroll_offset_row_index, 1);
+
+        // Paging scrolls by the viewport height.
+        component.scroll(ListNavigation::PageDown);
+        assert_eq2!(component.scroll_offset_row_index, 5);
+
+        // Can't scroll past the last line (at the bottom of the viewport).
+        component.scroll(ListNavigation::PageDown);
+        assert_eq2!(component.scroll_offset_row_index, 6);
+        component.scroll(ListNavigation::End);
+        assert_eq2!(component.scroll_offset_row_index, 6);
+
+        component.scroll(ListNavigation::Home);
+        assert_eq2!(component.scroll_offset_row_index, 0);
+
+        // Changing the source scrolls back to the top.
+        component.scroll(ListNavigation::End);
+        component.set_source("# Hello");
+        assert_eq2!(component.scroll_offset_row_index, 0);
+    }
+
+    #[test]
+    fn test_get_rendered_row_index() {
+        let input = "# Title\n\nsome text that is wrapped\n```\na\nb\n```\nend\n";
+        let (_, (document, block_source_row_indices)) =
+            parse_markdown_with_source_rows(input).unwrap();
+        assert_eq2!(block_source_row_indices, vec![0, 1, 2, 3, 7]);
+
+        let layout = layout_markdown_blocks_for_reading(
+            &document,
+            ch!(10),
+            &None,
+            &MarkdownStyleConfig::default(),
+            None,
+            None,
+        );
+        let block_row_indices = layout.block_row_indices.clone();
+        let line_count = layout.lines.len();
+        let layout_cache = MarkdownViewLayoutCache {
+            key: MarkdownViewLayoutKey {
+                col_count: ch!(10),
+                theme_id: String::new(),
+                maybe_computed_style: None,
+            },
+            layout,
+            block_source_row_indices,
+        };
+
+        // Each block starts on its own line.
+        assert_eq2!(layout_cache.get_rendered_row_index(0), 0);
+        assert_eq2!(layout_cache.get_rendered_row_index(2), block_row_indices[2]);
+        // The lines in a code block line up w/ the source (the fence is the top border).
+        assert_eq2!(layout_cache.get_rendered_row_index(3), block_row_indices[3]);
+        assert_eq2!(
+            layout_cache.get_rendered_row_index(5),
+            block_row_indices[3] + 2
+        );
+        // Past the end of the source.
+        assert_eq2!(layout_cache.get_rendered_row_index(100), line_count - 1);
+    }
+
+    #[test]
+    fn test_preview_sync_with_editor_buffer() {
+        let mut preview =
+            MarkdownPreviewComponent::new(FlexBoxId::from(1), FlexBoxId::from(0));
+        let mut buffer = EditorBuffer::new_empty(None);
+        buffer.set_lines(vec!["# Hello".to_string(), "world".to_string()]);
+
+        preview.sync_with_editor_buffer(&buffer);
+        assert_eq2!(preview.markdown_view.source, "# Hello\nworld\n");
+        assert_eq2!(
+            preview.markdown_view.maybe_scroll_to_source_row_index,
+            Some(0)
+        );
+
+        // The scroll of the preview is only synced when the editor scrolls, & editing the
+        // source doesn't scroll back to the top.
+        preview.markdown_view.maybe_scroll_to_source_row_index = None;
+        preview.markdown_view.scroll_offset_row_index = 1;
+        buffer.set_lines(vec!["# Hello".to_string(), "there".to_string()]);
+        preview.sync_with_editor_buffer(&buffer);
+        assert_eq2!(preview.markdown_view.source, "# Hello\nthere\n");
+        assert_eq2!(preview.markdown_view.maybe_scroll_to_source_row_index, None);
+        assert_eq2!(preview.markdown_view.scroll_offset_row_index, 1);
+
+        // Nothing changes if the source is the same.
+        assert!(!preview.markdown_view.update_source("# Hello\nthere\n"));
+    }
+}
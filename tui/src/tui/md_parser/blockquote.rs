@@ -0,0 +1,112 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+//! Blockquotes aren't a block of their own in the [MdDocument], they are
+//! [MdBlockElement::Text] lines that start w/ `>`. These helpers find the `>` markers (and
+//! the nesting level) so that the [editor] & the [MarkdownViewComponent] can paint them
+//! w/ a bar for each level.
+//!
+//! A line that doesn't start w/ `>` but comes right after a blockquote line (w/out a blank
+//! line in between) is a lazy continuation of the blockquote, at the same nesting level.
+
+use crate::*;
+
+pub const BLOCKQUOTE_MARKER: char = '>';
+
+/// The `>` markers at the start of a blockquote line, eg: for `> > Some quote` the
+/// `markers` are `> > `, the `nesting_level` is `2`, and the `text` is `Some quote`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BlockquotePrefix<'a> {
+    pub nesting_level: usize,
+    pub markers: &'a str,
+    pub text: &'a str,
+}
+
+/// Returns [None] if `line` doesn't start w/ a `>`. Each `>` can be followed by a space,
+/// so `>>`, `> >` & `>> ` are all nesting level `2`.
+pub fn parse_blockquote_prefix(line: &str) -> Option<BlockquotePrefix> {
+    let mut nesting_level = 0;
+    let mut rest = line;
+    while let Some(after_marker) = rest.strip_prefix(BLOCKQUOTE_MARKER) {
+        nesting_level += 1;
+        rest = after_marker.strip_prefix(' ').unwrap_or(after_marker);
+    }
+
+    if nesting_level == 0 {
+        return None;
+    }
+
+    Some(BlockquotePrefix {
+        nesting_level,
+        markers: &line[..line.len() - rest.len()],
+        text: rest,
+    })
+}
+
+/// Returns the [BlockquotePrefix] of the first fragment of a [MdBlockElement::Text] line.
+pub fn get_blockquote_prefix<'a>(
+    fragments: &MdLineFragments<'a>,
+) -> Option<BlockquotePrefix<'a>> {
+    match fragments.first() {
+        Some(MdLineFragment::Plain(text)) => parse_blockquote_prefix(text),
+        _ => None,
+    }
+}
+
+/// A blank line ends a blockquote (so the next line isn't a lazy continuation).
+pub fn is_blank_line(fragments: &MdLineFragments) -> bool {
+    fragments.iter().all(|fragment| match fragment {
+        MdLineFragment::Plain(text) => text.trim().is_empty(),
+        _ => false,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_rs_utils_core::*;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_blockquote_prefix() {
+        assert_eq2!(parse_blockquote_prefix("no quote"), None);
+        assert_eq2!(
+            parse_blockquote_prefix("> quote"),
+            Some(BlockquotePrefix {
+                nesting_level: 1,
+                markers: "> ",
+                text: "quote",
+            })
+        );
+        assert_eq2!(
+            parse_blockquote_prefix(">> > quote"),
+            Some(BlockquotePrefix {
+                nesting_level: 3,
+                markers: ">> > ",
+                text: "quote",
+            })
+        );
+        assert_eq2!(
+            parse_blockquote_prefix(">"),
+            Some(BlockquotePrefix {
+                nesting_level: 1,
+                markers: ">",
+                text: "",
+            })
+        );
+    }
+}
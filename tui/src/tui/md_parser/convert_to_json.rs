@@ -0,0 +1,423 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+//! This module converts the [MdDocument] that is produced by [parse_markdown] into JSON,
+//! so that tools & tests can look at how a document is parsed (eg: to debug why something
+//! is highlighted the way it is). The entry point is [parse_markdown_to_json].
+//!
+//! The structure of the JSON is stable, and doesn't change when the types in [types] do:
+//! - The document is `{"blocks": [...], "remainder": "..."}`, where `remainder` is the
+//!   input that couldn't be parsed (it is empty when the whole document is parsed).
+//! - Each block & fragment is an object w/ a `type` (eg: `"heading"`, `"bold"`), in
+//!   snake case.
+//! - The text of blocks & fragments has a `span`, which is where it is in the input. Its
+//!   `row_index` is the line, & its `col_index` & `grapheme_count` are counted in
+//!   grapheme clusters (not bytes or chars), just like the [UnicodeString]s that are
+//!   painted. The markup (eg: `**` or `# `) isn't in the span. It is `null` for text that
+//!   isn't a slice of the input.
+
+use r3bl_rs_utils_core::*;
+use serde::Serialize;
+
+use crate::*;
+
+/// Parse `input` w/ [parse_markdown] & return the [MdDocument] as JSON. More info on the
+/// structure of the JSON in the [module docs](self).
+///
+/// ```rust
+/// use r3bl_tui::*;
+///
+/// let json = parse_markdown_to_json("# Hi\n");
+/// assert_eq!(
+///     json,
+///     r#"{"blocks":[{"type":"heading","level":1,"text":"Hi","span":{"row_index":0,"col_index":2,"grapheme_count":2}}],"remainder":""}"#
+/// );
+/// ```
+pub fn parse_markdown_to_json(input: &str) -> String {
+    let json_document = match parse_markdown(input) {
+        Ok((remainder, document)) => {
+            MdJsonDocument::new(&document, remainder, &SpanLocator::new(input))
+        }
+        Err(_) => MdJsonDocument {
+            blocks: vec![],
+            remainder: input,
+        },
+    };
+    // The JSON types are all plain structs & enums, so this can't fail.
+    serde_json::to_string(&json_document).unwrap_or_default()
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct MdJsonDocument<'a> {
+    pub blocks: Vec<MdJsonBlock<'a>>,
+    pub remainder: &'a str,
+}
+
+/// The JSON for a [MdBlockElement].
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum MdJsonBlock<'a> {
+    Heading {
+        level: usize,
+        text: &'a str,
+        span: Option<MdJsonSpan>,
+    },
+    SmartList {
+        bullet_kind: MdJsonBulletKind,
+        indent: usize,
+        lines: Vec<Vec<MdJsonFragment<'a>>>,
+    },
+    Text {
+        fragments: Vec<MdJsonFragment<'a>>,
+    },
+    /// Only the lines between the start & end tags are in `lines`.
+    CodeBlock {
+        language: Option<&'a str>,
+        lines: Vec<MdJsonText<'a>>,
+    },
+    Title(MdJsonText<'a>),
+    Date(MdJsonText<'a>),
+    Tags {
+        items: Vec<MdJsonText<'a>>,
+    },
+    Authors {
+        items: Vec<MdJsonText<'a>>,
+    },
+}
+
+/// The JSON for a [BulletKind].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum MdJsonBulletKind {
+    Ordered { number: usize },
+    Unordered,
+}
+
+/// The JSON for a [MdLineFragment].
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum MdJsonFragment<'a> {
+    UnorderedListBullet {
+        indent: usize,
+        is_first_line: bool,
+    },
+    OrderedListBullet {
+        indent: usize,
+        number: usize,
+        is_first_line: bool,
+    },
+    Plain(MdJsonText<'a>),
+    Bold(MdJsonText<'a>),
+    Italic(MdJsonText<'a>),
+    BoldItalic(MdJsonText<'a>),
+    InlineCode(MdJsonText<'a>),
+    Link(MdJsonHyperlink<'a>),
+    Image(MdJsonHyperlink<'a>),
+    Checkbox {
+        is_checked: bool,
+    },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub struct MdJsonText<'a> {
+    pub text: &'a str,
+    pub span: Option<MdJsonSpan>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub struct MdJsonHyperlink<'a> {
+    pub text: &'a str,
+    pub url: &'a str,
+    pub span: Option<MdJsonSpan>,
+}
+
+/// Where a string slice is in the input. The cols are counted in grapheme clusters. More
+/// info in the [module docs](self).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub struct MdJsonSpan {
+    pub row_index: usize,
+    pub col_index: usize,
+    pub grapheme_count: usize,
+}
+
+/// Finds the [MdJsonSpan] of the string slices (that the parser returns) in the input.
+struct SpanLocator<'a> {
+    input: &'a str,
+    /// The byte offset of the start of each line in the `input`.
+    line_start_byte_offsets: Vec<usize>,
+}
+
+mod span_locator_impl {
+    use super::*;
+
+    impl<'a> SpanLocator<'a> {
+        pub fn new(input: &'a str) -> Self {
+            let line_start_byte_offsets = std::iter::once(0)
+                .chain(input.match_indices('\n').map(|(index, _)| index + 1))
+                .collect();
+            Self {
+                input,
+                line_start_byte_offsets,
+            }
+        }
+
+        /// Returns [None] if `slice` isn't a part of the input.
+        pub fn get_span(&self, slice: &str) -> Option<MdJsonSpan> {
+            let input_start = self.input.as_ptr() as usize;
+            let slice_start = slice.as_ptr() as usize;
+            if slice_start < input_start
+                || slice_start + slice.len() > input_start + self.input.len()
+            {
+                return None;
+            }
+            let byte_offset = slice_start - input_start;
+
+            // The last line that starts at or before the slice.
+            let row_index = self
+                .line_start_byte_offsets
+                .partition_point(|it| *it <= byte_offset)
+                - 1;
+            let line_start_byte_offset = self.line_start_byte_offsets[row_index];
+            let count_graphemes =
+                |it: &str| UnicodeString::from(it).grapheme_cluster_segment_count;
+
+            Some(MdJsonSpan {
+                row_index,
+                col_index: count_graphemes(
+                    &self.input[line_start_byte_offset..byte_offset],
+                ),
+                grapheme_count: count_graphemes(slice),
+            })
+        }
+
+        pub fn get_text(&self, text: &'a str) -> MdJsonText<'a> {
+            MdJsonText {
+                text,
+                span: self.get_span(text),
+            }
+        }
+    }
+}
+
+mod convert_impl {
+    use super::*;
+
+    impl<'a> MdJsonDocument<'a> {
+        pub(super) fn new(
+            document: &MdDocument<'a>,
+            remainder: &'a str,
+            locator: &SpanLocator<'a>,
+        ) -> Self {
+            Self {
+                blocks: document
+                    .iter()
+                    .map(|block| MdJsonBlock::new(block, locator))
+                    .collect(),
+                remainder,
+            }
+        }
+    }
+
+    impl<'a> MdJsonBlock<'a> {
+        pub(super) fn new(block: &MdBlockElement<'a>, locator: &SpanLocator<'a>) -> Self {
+            let get_fragments =
+                |fragments: &MdLineFragments<'a>| -> Vec<MdJsonFragment<'a>> {
+                    fragments
+                        .iter()
+                        .map(|fragment| MdJsonFragment::new(fragment, locator))
+                        .collect()
+                };
+            let get_items = |items: &List<&'a str>| -> Vec<MdJsonText<'a>> {
+                items.iter().map(|it| locator.get_text(*it)).collect()
+            };
+
+            match block {
+                MdBlockElement::Heading(heading_data) => MdJsonBlock::Heading {
+                    level: heading_data.level.into(),
+                    text: heading_data.text,
+                    span: locator.get_span(heading_data.text),
+                },
+                MdBlockElement::SmartList((lines, bullet_kind, indent)) => {
+                    MdJsonBlock::SmartList {
+                        bullet_kind: match bullet_kind {
+                            BulletKind::Ordered(number) => {
+                                MdJsonBulletKind::Ordered { number: *number }
+                            }
+                            BulletKind::Unordered => MdJsonBulletKind::Unordered,
+                        },
+                        indent: *indent,
+                        lines: lines.iter().map(get_fragments).collect(),
+                    }
+                }
+                MdBlockElement::Text(fragments) => MdJsonBlock::Text {
+                    fragments: get_fragments(fragments),
+                },
+                MdBlockElement::CodeBlock(code_block_lines) => MdJsonBlock::CodeBlock {
+                    language: code_block_lines.first().and_then(|it| it.language),
+                    lines: code_block_lines
+                        .iter()
+                        .filter_map(|it| match it.content {
+                            CodeBlockLineContent::Text(text) => {
+                                Some(locator.get_text(text))
+                            }
+                            CodeBlockLineContent::StartTag
+                            | CodeBlockLineContent::EndTag => None,
+                        })
+                        .collect(),
+                },
+                MdBlockElement::Title(text) => MdJsonBlock::Title(locator.get_text(text)),
+                MdBlockElement::Date(text) => MdJsonBlock::Date(locator.get_text(text)),
+                MdBlockElement::Tags(items) => MdJsonBlock::Tags {
+                    items: get_items(items),
+                },
+                MdBlockElement::Authors(items) => MdJsonBlock::Authors {
+                    items: get_items(items),
+                },
+            }
+        }
+    }
+
+    impl<'a> MdJsonFragment<'a> {
+        pub(super) fn new(
+            fragment: &MdLineFragment<'a>,
+            locator: &SpanLocator<'a>,
+        ) -> Self {
+            let get_hyperlink = |hyperlink_data: &HyperlinkData<'a>| MdJsonHyperlink {
+                text: hyperlink_data.text,
+                url: hyperlink_data.url,
+                span: locator.get_span(hyperlink_data.text),
+            };
+
+            match fragment {
+                MdLineFragment::UnorderedListBullet {
+                    indent,
+                    is_first_line,
+                } => MdJsonFragment::UnorderedListBullet {
+                    indent: *indent,
+                    is_first_line: *is_first_line,
+                },
+                MdLineFragment::OrderedListBullet {
+                    indent,
+                    number,
+                    is_first_line,
+                } => MdJsonFragment::OrderedListBullet {
+                    indent: *indent,
+                    number: *number,
+                    is_first_line: *is_first_line,
+                },
+                MdLineFragment::Plain(text) => {
+                    MdJsonFragment::Plain(locator.get_text(text))
+                }
+                MdLineFragment::Bold(text) => {
+                    MdJsonFragment::Bold(locator.get_text(text))
+                }
+                MdLineFragment::Italic(text) => {
+                    MdJsonFragment::Italic(locator.get_text(text))
+                }
+                MdLineFragment::BoldItalic(text) => {
+                    MdJsonFragment::BoldItalic(locator.get_text(text))
+                }
+                MdLineFragment::InlineCode(text) => {
+                    MdJsonFragment::InlineCode(locator.get_text(text))
+                }
+                MdLineFragment::Link(hyperlink_data) => {
+                    MdJsonFragment::Link(get_hyperlink(hyperlink_data))
+                }
+                MdLineFragment::Image(hyperlink_data) => {
+                    MdJsonFragment::Image(get_hyperlink(hyperlink_data))
+                }
+                MdLineFragment::Checkbox(is_checked) => MdJsonFragment::Checkbox {
+                    is_checked: *is_checked,
+                },
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value};
+
+    use super::*;
+
+    fn find_fragment<'a>(fragments: &'a Value, fragment_type: &str) -> &'a Value {
+        fragments
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|it| it["type"] == fragment_type)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_parse_markdown_to_json() {
+        let input = [
+            "# Foobar",
+            "Use the 📦 manager [pip](https://x.y) to **install** it.",
+            "```bash",
+            "pip install foobar",
+            "```",
+            "",
+        ]
+        .join("\n");
+        let json: Value = serde_json::from_str(&parse_markdown_to_json(&input)).unwrap();
+        assert_eq2!(json["remainder"], "");
+
+        let blocks = &json["blocks"];
+        assert_eq2!(
+            blocks[0],
+            json!({
+                "type": "heading",
+                "level": 1,
+                "text": "Foobar",
+                "span": { "row_index": 0, "col_index": 2, "grapheme_count": 6 }
+            })
+        );
+
+        // The emoji is a single grapheme cluster.
+        let fragments = &blocks[1]["fragments"];
+        assert_eq2!(
+            find_fragment(fragments, "link"),
+            &json!({
+                "type": "link",
+                "text": "pip",
+                "url": "https://x.y",
+                "span": { "row_index": 1, "col_index": 19, "grapheme_count": 3 }
+            })
+        );
+        assert_eq2!(
+            find_fragment(fragments, "bold"),
+            &json!({
+                "type": "bold",
+                "text": "install",
+                "span": { "row_index": 1, "col_index": 42, "grapheme_count": 7 }
+            })
+        );
+
+        assert_eq2!(
+            blocks[2],
+            json!({
+                "type": "code_block",
+                "language": "bash",
+                "lines": [{
+                    "text": "pip install foobar",
+                    "span": { "row_index": 3, "col_index": 0, "grapheme_count": 18 }
+                }]
+            })
+        );
+    }
+}
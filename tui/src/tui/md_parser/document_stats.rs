@@ -0,0 +1,181 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+//! Document statistics for a Markdown [EditorBuffer], eg: to show in a status bar. These
+//! use the [MdDocument] from [parse_markdown] (and not a naive split on whitespace), so
+//! markup (like heading markers, list bullets, checkboxes & emphasis), code blocks, inline
+//! code, images & URLs aren't counted as words.
+
+use std::time::Duration;
+
+use r3bl_rs_utils_core::*;
+
+use crate::{constants::*, *};
+
+/// The average silent reading speed of an adult (in words per minute), used by
+/// [markdown_reading_time].
+pub const READING_SPEED_WORDS_PER_MINUTE: usize = 200;
+
+/// Returns the number of words in the Markdown in `editor_buffer`. Only the text that a
+/// reader would read is counted (more info in [document_word_count]).
+pub fn markdown_word_count(editor_buffer: &EditorBuffer) -> CommonResult<usize> {
+    // Convert the editor text into a string (each line ends w/ a new line, like in
+    // try_parse_and_highlight).
+    let editor_text_to_string = {
+        let mut line_to_str_acc = Vec::<&str>::new();
+        for line in editor_buffer.get_lines() {
+            line_to_str_acc.push(line.string.as_str());
+            line_to_str_acc.push(NEW_LINE);
+        }
+        line_to_str_acc.join("")
+    };
+
+    match parse_markdown(&editor_text_to_string) {
+        Ok((_, document)) => Ok(document_word_count(&document)),
+        Err(_) => CommonError::new_err_with_only_type(CommonErrorType::ParsingError),
+    }
+}
+
+/// Returns the estimated time to read the Markdown in `editor_buffer`, based on
+/// [markdown_word_count] & [READING_SPEED_WORDS_PER_MINUTE].
+pub fn markdown_reading_time(editor_buffer: &EditorBuffer) -> CommonResult<Duration> {
+    let word_count = markdown_word_count(editor_buffer)?;
+    Ok(reading_time_for_word_count(word_count))
+}
+
+pub fn reading_time_for_word_count(word_count: usize) -> Duration {
+    Duration::from_secs_f64(
+        word_count as f64 * 60.0 / READING_SPEED_WORDS_PER_MINUTE as f64,
+    )
+}
+
+/// Returns the number of words in `document`:
+/// - Counted: the title, headings, text & smart list items (including bold, italic &
+///   link text).
+/// - Not counted: code blocks, inline code, images, link URLs, list bullets, checkboxes,
+///   and the other metadata (tags, authors & date).
+pub fn document_word_count(document: &MdDocument) -> usize {
+    document
+        .iter()
+        .map(|block| match block {
+            MdBlockElement::Title(title) => count_words(title),
+            MdBlockElement::Heading(heading_data) => count_words(heading_data.text),
+            MdBlockElement::Text(fragments) => count_words(&get_readable_text(fragments)),
+            MdBlockElement::SmartList((lines, _bullet_kind, _indent)) => lines
+                .iter()
+                .map(|fragments| count_words(&get_readable_text(fragments)))
+                .sum(),
+            MdBlockElement::CodeBlock(_)
+            | MdBlockElement::Date(_)
+            | MdBlockElement::Tags(_)
+            | MdBlockElement::Authors(_) => 0,
+        })
+        .sum()
+}
+
+/// Joins the text that a reader would read in `fragments` (w/out the markup), so that a
+/// word that is split across fragments (eg: `foo**bar**`) is counted once. Fragments that
+/// aren't read are replaced w/ a space, so they split words.
+fn get_readable_text(fragments: &MdLineFragments) -> String {
+    let mut acc = String::new();
+    for fragment in fragments.iter() {
+        match fragment {
+            MdLineFragment::Plain(text)
+            | MdLineFragment::Bold(text)
+            | MdLineFragment::Italic(text)
+            | MdLineFragment::BoldItalic(text)
+            | MdLineFragment::Link(HyperlinkData { text, .. }) => acc.push_str(text),
+            MdLineFragment::InlineCode(_)
+            | MdLineFragment::Image(_)
+            | MdLineFragment::Checkbox(_)
+            | MdLineFragment::OrderedListBullet { .. }
+            | MdLineFragment::UnorderedListBullet { .. } => acc.push_str(SPACE),
+        }
+    }
+    acc
+}
+
+/// A word is a run of non whitespace chars that contains at least one alphanumeric char, so
+/// stray punctuation (eg: ` - ` or ` & `) isn't counted.
+fn count_words(text: &str) -> usize {
+    text.split_whitespace()
+        .filter(|word| word.chars().any(char::is_alphanumeric))
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_editor_buffer(lines: &[&str]) -> EditorBuffer {
+        let mut editor_buffer = EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT));
+        editor_buffer.set_lines(lines.iter().map(|it| it.to_string()).collect());
+        editor_buffer
+    }
+
+    #[test]
+    fn test_markdown_word_count() -> CommonResult<()> {
+        let editor_buffer = make_editor_buffer(&[
+            "@title: Hello World",
+            "@tags: tag1, tag2",
+            "# A heading",
+            "Some **bold** and *italic* text, w/ `inline code` & a [link](https://r3bl.com).",
+            "![an image](https://r3bl.com/image.png)",
+            "```rust",
+            "fn main() { println!(\"not counted\"); }",
+            "```",
+            "- first item",
+            "- [x] done item",
+            "1. numbered item",
+        ]);
+
+        // Title: 2, heading: 2, text: 8 (not `inline code`, `&`, the URL, or the image),
+        // list items: 6.
+        assert_eq2!(markdown_word_count(&editor_buffer)?, 18);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_markdown_word_count_joins_fragments() -> CommonResult<()> {
+        let editor_buffer = make_editor_buffer(&["foo**bar** baz"]);
+        assert_eq2!(markdown_word_count(&editor_buffer)?, 2);
+
+        let editor_buffer = make_editor_buffer(&[]);
+        assert_eq2!(markdown_word_count(&editor_buffer)?, 0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_markdown_reading_time() -> CommonResult<()> {
+        assert_eq2!(reading_time_for_word_count(0), Duration::ZERO);
+        assert_eq2!(
+            reading_time_for_word_count(READING_SPEED_WORDS_PER_MINUTE),
+            Duration::from_secs(60)
+        );
+        assert_eq2!(reading_time_for_word_count(100), Duration::from_secs(30));
+
+        let editor_buffer = make_editor_buffer(&["one two three four"]);
+        assert_eq2!(
+            markdown_reading_time(&editor_buffer)?,
+            Duration::from_secs_f64(1.2)
+        );
+
+        Ok(())
+    }
+}
@@ -36,10 +36,19 @@
 //!    lists, unordered lists, code blocks, text blocks, heading blocks, can be found [block].
 //! 5. All the parsers that are related to parsing a single line of Markdown text, such as links,
 //!    bold, italic, etc. can be found [parse_element].
+//! 6. Document statistics (word count & reading time) for a Markdown [EditorBuffer] can be found
+//!    in [document_stats].
+//! 7. Blockquotes are text lines that start w/ `>`, the helpers to find their nesting level
+//!    can be found in [blockquote].
+//! 8. A JSON export of the [MdDocument] (eg: to write assertions against how a document is
+//!    parsed) can be found in [convert_to_json].
 
 // External use.
 pub mod block;
+pub mod blockquote;
+pub mod convert_to_json;
 pub mod convert_to_plain_text;
+pub mod document_stats;
 pub mod parse_element;
 pub mod parse_metadata_kcsv;
 pub mod parse_metadata_kv;
@@ -47,7 +56,10 @@ pub mod parser;
 pub mod types;
 
 pub use block::*;
+pub use blockquote::*;
+pub use convert_to_json::*;
 pub use convert_to_plain_text::*;
+pub use document_stats::*;
 pub use parse_element::*;
 pub use parse_metadata_kcsv::*;
 pub use parse_metadata_kv::*;
@@ -35,8 +35,52 @@ use crate::{constants::*, tui::misc_types::list_of::List, *};
 /// 5. Code block (which contains string slices of the language & code). The parsers in
 ///    [mod@parse_block_code] file handle this.
 /// 6. line (which contains a [MdLineFragments]). The parsers in [parse_element] file handle this.
-#[rustfmt::skip]
 pub fn parse_markdown(input: &str) -> IResult<&str, MdDocument> {
+    let (input, output) = many0(parse_block)(input)?;
+    let it = List::from(output);
+    Ok((input, it))
+}
+
+/// Same as [parse_markdown], except that it also returns the index of the source line that
+/// each block starts on (so the [Vec] is as long as the [MdDocument]). This is used to map
+/// the lines of the source to the lines that are painted for it, eg: to keep a
+/// [MarkdownPreviewComponent] scrolled to the same place as the editor.
+pub fn parse_markdown_with_source_rows(
+    input: &str,
+) -> IResult<&str, (MdDocument, Vec<usize>)> {
+    let mut blocks = vec![];
+    let mut source_rows = vec![];
+    let mut source_row_index = 0;
+    let mut remainder = input;
+
+    // This works just like `many0`.
+    loop {
+        match parse_block(remainder) {
+            Ok((rest, block)) => {
+                // Stop parsers that don't consume anything from looping forever.
+                if rest.len() == remainder.len() {
+                    return Err(nom::Err::Error(nom::error::Error::new(
+                        remainder,
+                        nom::error::ErrorKind::Many0,
+                    )));
+                }
+                let consumed = &remainder[..remainder.len() - rest.len()];
+                blocks.push(block);
+                source_rows.push(source_row_index);
+                source_row_index += consumed.matches(NEW_LINE).count();
+                remainder = rest;
+            }
+            Err(nom::Err::Error(_)) => break,
+            Err(error) => return Err(error),
+        }
+    }
+
+    Ok((remainder, (List::from(blocks), source_rows)))
+}
+
+/// Parses a single block, more info in [parse_markdown].
+#[rustfmt::skip]
+fn parse_block(input: &str) -> IResult<&str, MdBlockElement> {
     // key: TAGS, value: CSV parser.
     fn parse_tags_list(input: &str) -> IResult<&str, List<&str>>
     {
@@ -61,21 +105,17 @@ pub fn parse_markdown(input: &str) -> IResult<&str, MdDocument> {
         parse_kv_opt_eol(DATE, input)
     }
 
-    let (input, output) = many0(
-        // NOTE: The ordering of the parsers below matters.
-        alt((
-            map(parse_title_value,                   MdBlockElement::Title),
-            map(parse_tags_list,                     MdBlockElement::Tags),
-            map(parse_authors_list,                  MdBlockElement::Authors),
-            map(parse_date_value,                    MdBlockElement::Date),
-            map(parse_block_heading_opt_eol,         MdBlockElement::Heading),
-            map(parse_block_smart_list,              MdBlockElement::SmartList),
-            map(parse_block_code,                    MdBlockElement::CodeBlock),
-            map(parse_block_markdown_text_until_eol, MdBlockElement::Text),
-        )),
-    )(input)?;
-    let it = List::from(output);
-    Ok((input, it))
+    // NOTE: The ordering of the parsers below matters.
+    alt((
+        map(parse_title_value,                   MdBlockElement::Title),
+        map(parse_tags_list,                     MdBlockElement::Tags),
+        map(parse_authors_list,                  MdBlockElement::Authors),
+        map(parse_date_value,                    MdBlockElement::Date),
+        map(parse_block_heading_opt_eol,         MdBlockElement::Heading),
+        map(parse_block_smart_list,              MdBlockElement::SmartList),
+        map(parse_block_code,                    MdBlockElement::CodeBlock),
+        map(parse_block_markdown_text_until_eol, MdBlockElement::Text),
+    ))(input)
 }
 
 #[cfg(test)]
@@ -278,4 +318,24 @@ mod tests {
         assert_eq2!(remainder, "`inline code`");
         assert_eq2!(blocks.len(), 6);
     }
+
+    #[test]
+    fn test_parse_markdown_with_source_rows() {
+        let input = [
+            "# Heading",
+            "```rs",
+            "let a=1;",
+            "```",
+            "- one",
+            "  more",
+            "text",
+            "",
+        ]
+        .join("\n");
+        let (remainder, (blocks, source_rows)) =
+            parse_markdown_with_source_rows(&input).unwrap();
+        assert_eq2!(remainder, "");
+        assert_eq2!(blocks, parse_markdown(&input).unwrap().1);
+        assert_eq2!(source_rows, vec![0, 1, 4, 6]);
+    }
 }
@@ -191,6 +191,47 @@ pub mod global_constants {
     pub const SPACER: &str = " ";
     pub const DEFAULT_CURSOR_CHAR: char = '▒';
     pub const DEFAULT_SYN_HI_FILE_EXT: &str = "md";
+    pub const FOLDED_REGION_SUMMARY_PREFIX: &str = "⋯";
+    pub const DEFAULT_WHITESPACE_SPACE_MARKER_CHAR: char = '·';
+    pub const DEFAULT_WHITESPACE_TAB_MARKER_CHAR: char = '→';
+    pub const DEFAULT_SCROLLBAR_TRACK_CHAR: char = '│';
+    pub const DEFAULT_SCROLLBAR_THUMB_CHAR: char = '█';
+    pub const DEFAULT_CLIP_START_MARKER_CHAR: char = '‹';
+    pub const DEFAULT_CLIP_END_MARKER_CHAR: char = '›';
+    pub const RULER_CHAR: char = '│';
+    pub const INDENT_GUIDE_CHAR: char = '│';
+    pub const DIAGNOSTICS_GUTTER_WIDTH: u16 = 2;
+    pub const DIAGNOSTIC_ERROR_CHAR: char = '●';
+    pub const DIAGNOSTIC_WARNING_CHAR: char = '▲';
+    pub const DIAGNOSTIC_INFO_CHAR: char = '◆';
+    pub const DIAGNOSTIC_HINT_CHAR: char = '·';
+    pub const DEFAULT_INLINE_IMAGE_ROW_COUNT: usize = 10;
+    pub const KILL_RING_CAPACITY: usize = 16;
+    pub const DEFAULT_CHORD_TIMEOUT_MS: u64 = 1000;
+    pub const DEFAULT_TAB_WIDTH: usize = 4;
+    pub const MULTI_CLICK_INTERVAL_MS: u64 = 500;
+    pub const DEFAULT_HIGHLIGHT_BUDGET_MS: u64 = 50;
+    pub const OVERWRITE_MODE_INDICATOR_TEXT: &str = "OVR";
+    pub const DIALOG_CLOSE_HINT: &str = "[x]";
+    pub const DIALOG_WINDOW_TOO_SMALL_MSG: &str = "window too small";
+    pub const REGEX_FEATURE_DISABLED_MSG: &str = "regex needs the `regex` feature";
+    pub const ELLIPSIS: &str = "…";
+    pub const DIALOG_ANIMATION_FRAME_INTERVAL_MS: u64 = 16;
+    pub const DEFAULT_DIALOG_MULTILINE_ROW_COUNT: u16 = 5;
+    pub const DEFAULT_MAX_FPS: u16 = 60;
+    pub const RESIZE_DEBOUNCE_MS: u64 = 50;
+    pub const DEFAULT_SPINNER_FRAMES: [&str; 10] =
+        ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+    pub const DEFAULT_SPINNER_FRAME_INTERVAL_MS: u64 = 80;
+    pub const DEFAULT_PROGRESS_BAR_FILLED_CHAR: char = '█';
+    pub const DEFAULT_PROGRESS_BAR_EMPTY_CHAR: char = '░';
+    pub const DEFAULT_LIST_SELECTED_MARKER: &str = "[x] ";
+    pub const DEFAULT_LIST_UNSELECTED_MARKER: &str = "[ ] ";
+    pub const MAX_NUMBERED_TAB_COUNT: usize = 9;
+    pub const COMPLETION_POPUP_MAX_ROW_COUNT: usize = 8;
+    pub const COMPLETION_POPUP_MAX_COL_COUNT: usize = 40;
+    pub const DEFAULT_AUTO_CLOSE_PAIRS: [(char, char); 5] =
+        [('(', ')'), ('[', ']'), ('{', '}'), ('"', '"'), ('\'', '\'')];
 }
 pub use global_constants::*;
 
@@ -389,7 +430,7 @@ pub mod dialog_component_traits {
         fn get_dialog_buffer(&self, id: FlexBoxId) -> Option<&DialogBuffer>;
     }
 
-    #[derive(Debug)]
+    #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
     pub enum DialogChoice {
         Yes(String),
         No,
@@ -398,5 +439,24 @@ pub mod dialog_component_traits {
     pub type OnDialogPressFn<S, A> = fn(DialogChoice, &SharedStore<S, A>);
 
     pub type OnDialogEditorChangeFn<S, A> = fn(EditorBuffer, &SharedStore<S, A>);
+
+    /// Called w/ the index & text of the selected result.
+    pub type OnDialogResultsSelectionChangeFn<S, A> =
+        fn(usize, String, &SharedStore<S, A>);
 }
 pub use dialog_component_traits::*;
+
+mod progress_component_traits {
+    use super::*;
+
+    /// This marker trait is meant to be implemented by whatever state struct is being used to store
+    /// the progress of long running operations for the re-usable progress component. It is used in
+    /// the `where` clause of the [ProgressComponent] to ensure that the generic type `S` implements
+    /// this trait.
+    pub trait HasProgress {
+        /// Return a value between `0.0` & `1.0` to render a progress bar, or [None] to render a
+        /// spinner (when the progress can't be measured).
+        fn get_progress(&self, id: FlexBoxId) -> Option<f32>;
+    }
+}
+pub use progress_component_traits::*;
@@ -48,30 +48,54 @@ pub const DEBUG_TUI_SHOW_TERMINAL_BACKEND: bool = false;
 // Attach sources.
 pub mod animator;
 pub mod color_wheel;
+pub mod command_palette;
 pub mod dialog;
 pub mod editor;
+pub mod fuzzy_match;
+pub mod keyboard_help;
 pub mod layout;
+pub mod list;
 pub mod lolcat;
+pub mod markdown_view;
 pub mod md_parser;
 pub mod misc_types;
+pub mod progress;
 pub mod rsx;
+pub mod status_bar;
 pub mod syntax_highlighting;
+pub mod tabs;
 pub mod terminal_lib_backends;
 pub mod terminal_window;
+#[cfg(any(test, feature = "test-support"))]
+pub mod test_support;
+pub mod text_input;
+pub mod theme;
 
 // Re-export.
 pub use animator::*;
 pub use color_wheel::*;
+pub use command_palette::*;
 pub use dialog::*;
 pub use editor::*;
+pub use fuzzy_match::*;
+pub use keyboard_help::*;
 pub use layout::*;
+pub use list::*;
 pub use lolcat::*;
+pub use markdown_view::*;
 pub use md_parser::*;
 pub use misc_types::*;
+pub use progress::*;
 pub use rsx::*;
+pub use status_bar::*;
 pub use syntax_highlighting::*;
+pub use tabs::*;
 pub use terminal_lib_backends::*;
 pub use terminal_window::*;
+#[cfg(any(test, feature = "test-support"))]
+pub use test_support::*;
+pub use text_input::*;
+pub use theme::*;
 
 // Tests.
 mod test_make_style_macro;
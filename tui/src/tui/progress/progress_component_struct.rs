@@ -0,0 +1,158 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::{fmt::Debug, sync::Arc, time::Instant};
+
+use async_trait::async_trait;
+use r3bl_rs_utils_core::*;
+use tokio::sync::RwLock;
+
+use crate::*;
+
+/// This is a reusable [Component] that shows the progress of a long running operation in
+/// the first row of its box. The progress is read from the state via [HasProgress]:
+/// - A value between `0.0` & `1.0` renders a bar.
+/// - [None] renders a spinner, which keeps animating on its own (by requesting a
+///   [RenderTick]) so there's no need to dispatch actions just to advance it.
+#[derive(Clone, Debug, Default)]
+pub struct ProgressComponent {
+    pub id: FlexBoxId,
+    pub config: ProgressComponentConfig,
+    /// This is set when the spinner is first painted, and cleared when a bar is painted.
+    pub maybe_spinner_start_instant: Option<Instant>,
+}
+
+pub mod progress_component_impl {
+    use super::*;
+
+    #[async_trait]
+    impl<S, A> Component<S, A> for ProgressComponent
+    where
+        S: HasProgress + Default + Clone + PartialEq + Debug + Sync + Send + 'static,
+        A: Debug + Default + Clone + Sync + Send + 'static,
+    {
+        fn reset(&mut self) { self.maybe_spinner_start_instant = None; }
+
+        fn get_id(&self) -> FlexBoxId { self.id }
+
+        /// This component doesn't handle any input.
+        async fn handle_event(
+            &mut self,
+            _args: ComponentScopeArgs<'_, S, A>,
+            _input_event: &InputEvent,
+        ) -> CommonResult<EventPropagation> {
+            Ok(EventPropagation::Propagate)
+        }
+
+        async fn render(
+            &mut self,
+            args: ComponentScopeArgs<'_, S, A>,
+            current_box: &FlexBox,
+            _surface_bounds: SurfaceBounds, /* Ignore this. */
+        ) -> CommonResult<RenderPipeline> {
+            throws_with_return!({
+                let ComponentScopeArgs {
+                    state,
+                    shared_global_data,
+                    ..
+                } = args;
+
+                let origin_pos = current_box.style_adjusted_origin_pos;
+                let col_count = current_box.style_adjusted_bounds_size.col_count;
+
+                let mut render_ops = render_ops!();
+                render_ops.push(RenderOp::MoveCursorPositionRelTo(
+                    origin_pos,
+                    position! { col_index: 0, row_index: 0 },
+                ));
+
+                match state.get_progress(self.id) {
+                    Some(progress) => {
+                        self.maybe_spinner_start_instant = None;
+                        self.render_bar(progress, col_count, &mut render_ops);
+                    }
+                    None => {
+                        self.render_spinner(&mut render_ops);
+                        shared_global_data
+                            .write()
+                            .await
+                            .request_render_tick(self.config.spinner_frame_interval);
+                    }
+                }
+
+                let mut render_pipeline = render_pipeline!();
+                render_pipeline.push(ZOrder::Normal, render_ops);
+                render_pipeline
+            });
+        }
+    }
+
+    impl ProgressComponent {
+        fn render_bar(
+            &self,
+            progress: f32,
+            col_count: ChUnit,
+            render_ops: &mut RenderOps,
+        ) {
+            let ProgressBarText {
+                filled,
+                empty,
+                percentage,
+            } = self.config.make_bar(progress, col_count);
+
+            render_ops.push(RenderOp::ApplyColors(Some(self.config.bar_filled_style)));
+            render_ops.push(RenderOp::PaintTextWithAttributes(filled, None));
+            render_ops.push(RenderOp::ApplyColors(Some(self.config.bar_empty_style)));
+            render_ops.push(RenderOp::PaintTextWithAttributes(empty, None));
+            render_ops.push(RenderOp::ResetColor);
+            render_ops.push(RenderOp::PaintTextWithAttributes(percentage, None));
+        }
+
+        fn render_spinner(&mut self, render_ops: &mut RenderOps) {
+            let start_instant = *self
+                .maybe_spinner_start_instant
+                .get_or_insert_with(Instant::now);
+            let frame = self.config.get_spinner_frame(start_instant.elapsed());
+
+            render_ops.push(RenderOp::ApplyColors(Some(self.config.spinner_style)));
+            render_ops.push(RenderOp::PaintTextWithAttributes(frame.to_string(), None));
+            render_ops.push(RenderOp::ResetColor);
+        }
+    }
+}
+pub use progress_component_impl::*;
+
+mod constructor {
+    use super::*;
+
+    impl ProgressComponent {
+        pub fn new(id: FlexBoxId, config: ProgressComponentConfig) -> Self {
+            Self {
+                id,
+                config,
+                maybe_spinner_start_instant: None,
+            }
+        }
+
+        pub fn new_shared(
+            id: FlexBoxId,
+            config: ProgressComponentConfig,
+        ) -> Arc<RwLock<Self>> {
+            Arc::new(RwLock::new(ProgressComponent::new(id, config)))
+        }
+    }
+}
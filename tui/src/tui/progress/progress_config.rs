@@ -0,0 +1,111 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::time::Duration;
+
+use r3bl_rs_utils_core::*;
+
+use crate::*;
+
+/// Configure how the [ProgressComponent] renders its spinner & bar.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProgressComponentConfig {
+    /// These are cycled thru (one every `spinner_frame_interval`) when the progress can't
+    /// be measured.
+    pub spinner_frames: Vec<String>,
+    pub spinner_frame_interval: Duration,
+    pub bar_filled_char: char,
+    pub bar_empty_char: char,
+    /// Paint the percentage (eg: ` 42%`) after the bar.
+    pub show_percentage: bool,
+    pub spinner_style: Style,
+    pub bar_filled_style: Style,
+    pub bar_empty_style: Style,
+}
+
+/// The text for each part of a determinate progress bar. More info in
+/// [ProgressComponentConfig::make_bar].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ProgressBarText {
+    pub filled: String,
+    pub empty: String,
+    pub percentage: String,
+}
+
+mod progress_config_impl {
+    use super::*;
+
+    impl Default for ProgressComponentConfig {
+        fn default() -> Self {
+            Self {
+                spinner_frames: DEFAULT_SPINNER_FRAMES
+                    .iter()
+                    .map(|frame| frame.to_string())
+                    .collect(),
+                spinner_frame_interval: Duration::from_millis(
+                    DEFAULT_SPINNER_FRAME_INTERVAL_MS,
+                ),
+                bar_filled_char: DEFAULT_PROGRESS_BAR_FILLED_CHAR,
+                bar_empty_char: DEFAULT_PROGRESS_BAR_EMPTY_CHAR,
+                show_percentage: true,
+                spinner_style: get_progress_spinner_style(),
+                bar_filled_style: get_progress_bar_filled_style(),
+                bar_empty_style: get_progress_bar_empty_style(),
+            }
+        }
+    }
+
+    impl ProgressComponentConfig {
+        /// Return the spinner frame to paint once `elapsed` time has passed since the
+        /// spinner was first shown.
+        pub fn get_spinner_frame(&self, elapsed: Duration) -> &str {
+            if self.spinner_frames.is_empty() {
+                return "";
+            }
+            let frame_interval_ms = self.spinner_frame_interval.as_millis().max(1);
+            let frame_index = elapsed.as_millis() / frame_interval_ms;
+            &self.spinner_frames[frame_index as usize % self.spinner_frames.len()]
+        }
+
+        /// Lay out a bar for `progress` (which is clamped to `0.0..=1.0`) that fits in
+        /// `col_count` columns, including the percentage (if it is shown).
+        pub fn make_bar(&self, progress: f32, col_count: ChUnit) -> ProgressBarText {
+            let progress = if progress.is_nan() {
+                0.0
+            } else {
+                progress.clamp(0.0, 1.0)
+            };
+
+            let percentage = match self.show_percentage {
+                true => format!(" {:>3}%", (progress * 100.0).round() as u8),
+                false => String::new(),
+            };
+
+            let bar_width = ch!(@to_usize col_count).saturating_sub(percentage.len());
+            let filled_width = (progress * bar_width as f32).round() as usize;
+
+            ProgressBarText {
+                filled: self.bar_filled_char.to_string().repeat(filled_width),
+                empty: self
+                    .bar_empty_char
+                    .to_string()
+                    .repeat(bar_width - filled_width),
+                percentage,
+            }
+        }
+    }
+}
@@ -0,0 +1,81 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+#[cfg(test)]
+mod test_progress_config {
+    use std::time::Duration;
+
+    use r3bl_rs_utils_core::*;
+
+    use crate::*;
+
+    #[test]
+    fn test_get_spinner_frame() {
+        let config = ProgressComponentConfig {
+            spinner_frames: vec!["a".into(), "b".into(), "c".into()],
+            spinner_frame_interval: Duration::from_millis(100),
+            ..Default::default()
+        };
+
+        assert_eq2!(config.get_spinner_frame(Duration::ZERO), "a");
+        assert_eq2!(config.get_spinner_frame(Duration::from_millis(99)), "a");
+        assert_eq2!(config.get_spinner_frame(Duration::from_millis(100)), "b");
+        assert_eq2!(config.get_spinner_frame(Duration::from_millis(250)), "c");
+        // Wraps around to the 1st frame.
+        assert_eq2!(config.get_spinner_frame(Duration::from_millis(300)), "a");
+    }
+
+    #[test]
+    fn test_make_bar() {
+        let config = ProgressComponentConfig {
+            bar_filled_char: '#',
+            bar_empty_char: '-',
+            ..Default::default()
+        };
+
+        // The percentage takes 5 cols, which leaves 10 for the bar.
+        assert_eq2!(
+            config.make_bar(0.5, ch!(15)),
+            ProgressBarText {
+                filled: "#####".into(),
+                empty: "-----".into(),
+                percentage: "  50%".into(),
+            }
+        );
+
+        // Out of range values are clamped.
+        assert_eq2!(config.make_bar(1.5, ch!(15)).filled, "#".repeat(10));
+        assert_eq2!(config.make_bar(-1.0, ch!(15)).empty, "-".repeat(10));
+
+        // W/out the percentage, the bar takes all the cols.
+        let config = ProgressComponentConfig {
+            show_percentage: false,
+            ..config
+        };
+        assert_eq2!(
+            config.make_bar(0.25, ch!(8)),
+            ProgressBarText {
+                filled: "##".into(),
+                empty: "------".into(),
+                percentage: "".into(),
+            }
+        );
+
+        // The bar is dropped when there's no room for it.
+        assert_eq2!(config.make_bar(0.25, ch!(0)), ProgressBarText::default());
+    }
+}
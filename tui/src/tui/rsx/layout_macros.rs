@@ -25,7 +25,11 @@ macro_rules! box_end {
     };
 }
 
-/// When calling this, make sure to make a corresponding call to [box_end!].
+/// When calling this, make sure to make a corresponding call to [box_end!]. The optional
+/// `when` is passed to [get_styles!], so that the matching [ConditionalStyle]s are added
+/// to the box's styles. The optional `tab_index` sets the position of the component that
+/// is rendered in this box in the focus order. More info in
+/// [focus_next](ComponentRegistry::focus_next).
 #[macro_export]
 macro_rules! box_start {
     (
@@ -34,19 +38,28 @@ macro_rules! box_start {
         dir:                    $arg_dir : expr,                    // Eg: Direction::Horizontal,
         requested_size_percent: $arg_requested_size_percent : expr, // Eg: (50, 100).try_into()?,
         styles:                 [$($args:tt)*]                      // Eg: [ "style1" , "style2" ]
+        $(, when:               $arg_context : expr)?               // Eg: when: state
+        $(, tab_index:          $arg_tab_index : expr)?             // Eg: tab_index: 1
         $(,)*                   /* Optional trailing comma https://stackoverflow.com/a/43143459/2085356. */
     ) => {
         $arg_surface.box_start(box_props! {
             id:                     $arg_id,
             dir:                    $arg_dir,
             requested_size_percent: $arg_requested_size_percent,
-            maybe_styles:           get_styles! { @from: $arg_surface.stylesheet, [$($args)*] }
+            maybe_styles:           get_styles! {
+                                        @from: $arg_surface.stylesheet, [$($args)*]
+                                        $(, @when: $arg_context)?
+                                    }
+            $(, tab_index:          $arg_tab_index)?
         })?
     };
 }
 
 #[macro_export]
 macro_rules! box_props {
+  (@maybe_tab_index) => { None };
+  (@maybe_tab_index $arg_tab_index: expr) => { Some($arg_tab_index) };
+
   (
     id:                     $arg_id : expr,                     // Eg: 0,
     dir:                    $arg_dir : expr,                    // Eg: Direction::Horizontal,
@@ -54,6 +67,7 @@ macro_rules! box_props {
     maybe_styles:           $arg_styles: expr                   // Eg: get_styles! {
                                                                 //     from: stylesheet,
                                                                 //     ["style1", "style2"] };
+    $(, tab_index:          $arg_tab_index : expr)?             // Eg: tab_index: 1
     $(,)*                   /* Optional trailing comma https://stackoverflow.com/a/43143459/2085356. */
   ) => {
     FlexBoxProps {
@@ -61,6 +75,9 @@ macro_rules! box_props {
       dir: $arg_dir,
       requested_size_percent: $arg_requested_size_percent,
       maybe_styles: $arg_styles,
+      maybe_min_size: None,
+      maybe_max_size: None,
+      maybe_tab_index: box_props!(@maybe_tab_index $($arg_tab_index)?),
     }
   };
 
@@ -69,6 +86,7 @@ macro_rules! box_props {
     dir:                    $arg_dir : expr,                    // Eg: Direction::Horizontal,
     requested_size_percent: $arg_requested_size_percent : expr, // Eg: (50, 100).try_into()?,
     maybe_styles:           [$($args:tt)*]                      // Eg: [style!{...} , style!{...}]
+    $(, tab_index:          $arg_tab_index : expr)?             // Eg: tab_index: 1
     $(,)*                   /* Optional trailing comma https://stackoverflow.com/a/43143459/2085356. */
   ) => {
     FlexBoxProps {
@@ -76,6 +94,9 @@ macro_rules! box_props {
       dir: $arg_dir,
       requested_size_percent: $arg_requested_size_percent,
       maybe_styles: Some(vec![$($args)*]),
+      maybe_min_size: None,
+      maybe_max_size: None,
+      maybe_tab_index: box_props!(@maybe_tab_index $($arg_tab_index)?),
     }
   };
 
@@ -83,6 +104,7 @@ macro_rules! box_props {
     id:                     $arg_id : expr,                     // Eg:0,
     dir:                    $arg_dir : expr,                    // Eg: Direction::Horizontal,
     requested_size_percent: $arg_requested_size_percent : expr, // Eg: (50, 100).try_into()?,
+    $(tab_index:            $arg_tab_index : expr,)?            // Eg: tab_index: 1,
     $(,)*                   /* Optional trailing comma https://stackoverflow.com/a/43143459/2085356. */
   ) => {
     FlexBoxProps {
@@ -90,6 +112,9 @@ macro_rules! box_props {
       dir: $arg_dir,
       requested_size_percent: $arg_requested_size_percent,
       maybe_styles: None,
+      maybe_min_size: None,
+      maybe_max_size: None,
+      maybe_tab_index: box_props!(@maybe_tab_index $($arg_tab_index)?),
     }
   };
 }
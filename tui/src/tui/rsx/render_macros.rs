@@ -36,7 +36,12 @@ macro_rules! render_component_in_current_box {
         if let Some(component_ref) = maybe_component_ref {
             let surface_bounds = SurfaceBounds::from(&*($arg_surface));
             let current_box = $arg_surface.current_box()?;
-            let queue = component_ref
+            // The focus order can be set in the layout, w/ the box that the component is
+            // rendered in.
+            if let Some(tab_index) = current_box.maybe_tab_index {
+                $arg_registry.set_tab_index($arg_component_id, tab_index);
+            }
+            let mut queue = component_ref
                 .write()
                 .await
                 .render(
@@ -51,6 +56,9 @@ macro_rules! render_component_in_current_box {
                     surface_bounds,
                 )
                 .await?;
+            if $arg_registry.is_background_dimmed_for($arg_component_id) {
+                queue.is_background_dimmed = true;
+            }
             $arg_surface.render_pipeline += queue;
         }
     };
@@ -78,7 +86,7 @@ macro_rules! render_component_in_given_box {
 
         if let Some(component_ref) = maybe_component_ref {
             let surface_bounds = SurfaceBounds::from(&*($arg_surface));
-            let queue: RenderPipeline = component_ref
+            let mut queue: RenderPipeline = component_ref
                 .write()
                 .await
                 .render(
@@ -93,6 +101,9 @@ macro_rules! render_component_in_given_box {
                     surface_bounds,
                 )
                 .await?;
+            if $arg_registry.is_background_dimmed_for($arg_component_id) {
+                queue.is_background_dimmed = true;
+            }
             $arg_surface.render_pipeline += queue;
         }
     }};
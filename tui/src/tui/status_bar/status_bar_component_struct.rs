@@ -0,0 +1,398 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::{fmt::Debug, sync::Arc};
+
+use async_trait::async_trait;
+use r3bl_rs_utils_core::*;
+use r3bl_rs_utils_macro::style;
+use tokio::sync::RwLock;
+
+use crate::*;
+
+/// This is a reusable [Component] that paints a status bar in the first row of its box
+/// (eg: a 1 row box at the bottom of the window). It is made of segments, which are
+/// returned by the [StatusBarSegmentFn]s each time it is rendered, so it always shows
+/// what is in the state:
+/// - Each segment is packed w/ the other segments that have the same
+///   [StatusBarAlignment], separated by a space.
+/// - The segments that come first in [segments](StatusBarComponent::segments) are the
+///   most important. When they don't all fit, the last one is truncated (or dropped, if
+///   nothing of it is left) first.
+///
+/// There are helpers for the common segments (eg: [StatusBarSegment::caret_position]).
+///
+/// ```ignore
+/// fn caret_position(state: &State) -> Option<StatusBarSegment> {
+///     let editor_buffer = state.get_editor_buffer(FlexBoxId::from(EDITOR_ID))?;
+///     Some(StatusBarSegment::caret_position(editor_buffer, StatusBarAlignment::Right))
+/// }
+///
+/// let status_bar = StatusBarComponent::new_shared(id, vec![caret_position]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct StatusBarComponent<S>
+where
+    S: Debug + Default + Clone + PartialEq + Sync + Send,
+{
+    pub id: FlexBoxId,
+    pub segments: Vec<StatusBarSegmentFn<S>>,
+    /// The style of the whole row. The style of each segment is added to it.
+    pub style: Style,
+}
+
+/// Return [None] to hide the segment (eg: the dirty indicator when the buffer is saved).
+pub type StatusBarSegmentFn<S> = fn(&S) -> Option<StatusBarSegment>;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum StatusBarAlignment {
+    #[default]
+    Left,
+    Center,
+    Right,
+}
+
+/// A part of the [StatusBarComponent]. More info in [StatusBarSegmentFn].
+#[derive(Clone, Debug, Default)]
+pub struct StatusBarSegment {
+    pub alignment: StatusBarAlignment,
+    pub styled_texts: StyledTexts,
+}
+
+/// Painted by [StatusBarSegment::dirty_indicator].
+pub const STATUS_BAR_DIRTY_INDICATOR_TEXT: &str = "[+]";
+
+/// Used by [StatusBarSegment::file_name] when there is no file name.
+pub const STATUS_BAR_UNTITLED_FILE_NAME: &str = "untitled";
+
+pub mod status_bar_component_impl {
+    use super::*;
+
+    #[async_trait]
+    impl<S, A> Component<S, A> for StatusBarComponent<S>
+    where
+        S: Debug + Default + Clone + PartialEq + Sync + Send + 'static,
+        A: Debug + Default + Clone + Sync + Send + 'static,
+    {
+        fn reset(&mut self) {}
+
+        fn get_id(&self) -> FlexBoxId { self.id }
+
+        /// This component doesn't handle any input.
+        async fn handle_event(
+            &mut self,
+            _args: ComponentScopeArgs<'_, S, A>,
+            _input_event: &InputEvent,
+        ) -> CommonResult<EventPropagation> {
+            Ok(EventPropagation::Propagate)
+        }
+
+        async fn render(
+            &mut self,
+            args: ComponentScopeArgs<'_, S, A>,
+            current_box: &FlexBox,
+            _surface_bounds: SurfaceBounds, /* Ignore this. */
+        ) -> CommonResult<RenderPipeline> {
+            let ComponentScopeArgs { state, .. } = args;
+
+            let segments: Vec<StatusBarSegment> =
+                self.segments.iter().filter_map(|it| it(state)).collect();
+            let styled_texts = layout_status_bar(
+                &segments,
+                current_box.style_adjusted_bounds_size.col_count,
+                self.style,
+            );
+
+            let mut render_ops = render_ops!();
+            render_ops.push(RenderOp::MoveCursorPositionRelTo(
+                current_box.style_adjusted_origin_pos,
+                position! { col_index: 0, row_index: 0 },
+            ));
+            styled_texts.render_into(&mut render_ops);
+            render_ops.push(RenderOp::ResetColor);
+
+            let mut render_pipeline = render_pipeline!();
+            render_pipeline.push(ZOrder::Normal, render_ops);
+            Ok(render_pipeline)
+        }
+    }
+
+    impl StatusBarSegment {
+        pub fn new(alignment: StatusBarAlignment, styled_texts: StyledTexts) -> Self {
+            Self {
+                alignment,
+                styled_texts,
+            }
+        }
+
+        pub fn from_text(
+            alignment: StatusBarAlignment,
+            text: &str,
+            style: Style,
+        ) -> Self {
+            Self::new(
+                alignment,
+                styled_texts! { styled_text! { @style: style, @text: text } },
+            )
+        }
+
+        pub fn display_width(&self) -> ChUnit { self.styled_texts.display_width() }
+
+        /// `Ln 12, Col 4`, from [EditorBuffer::caret_display_position].
+        pub fn caret_position(
+            editor_buffer: &EditorBuffer,
+            alignment: StatusBarAlignment,
+        ) -> Self {
+            let (line, col) = editor_buffer.caret_display_position();
+            Self::from_text(
+                alignment,
+                &format!("Ln {line}, Col {col}"),
+                Style::default(),
+            )
+        }
+
+        /// The `maybe_file_name`, or [STATUS_BAR_UNTITLED_FILE_NAME] (w/ the
+        /// [extension](EditorBuffer::get_maybe_file_extension) of the `editor_buffer`)
+        /// if there isn't one.
+        pub fn file_name(
+            maybe_file_name: Option<&str>,
+            editor_buffer: &EditorBuffer,
+            alignment: StatusBarAlignment,
+        ) -> Self {
+            let file_name =
+                match (maybe_file_name, editor_buffer.get_maybe_file_extension()) {
+                    (Some(file_name), _) => file_name.to_string(),
+                    (None, Some(file_extension)) => {
+                        format!("{STATUS_BAR_UNTITLED_FILE_NAME}.{file_extension}")
+                    }
+                    (None, None) => STATUS_BAR_UNTITLED_FILE_NAME.to_string(),
+                };
+            Self::from_text(alignment, &file_name, style! { attrib: [bold] })
+        }
+
+        /// [STATUS_BAR_DIRTY_INDICATOR_TEXT] if the `editor_buffer` has unsaved changes
+        /// (more info in [EditorBuffer::is_dirty]), otherwise [None].
+        pub fn dirty_indicator(
+            editor_buffer: &EditorBuffer,
+            alignment: StatusBarAlignment,
+        ) -> Option<Self> {
+            editor_buffer.is_dirty().then(|| {
+                Self::from_text(
+                    alignment,
+                    STATUS_BAR_DIRTY_INDICATOR_TEXT,
+                    Style::default(),
+                )
+            })
+        }
+
+        /// The same labels as the mode indicator that the editor paints, eg: `NORMAL OVR`.
+        /// Returns [None] if there is nothing to show. The modes live in the
+        /// [EditorEngine] (get them w/ [KeyBindingsApi::get_vim_mode] &
+        /// [EditorEngine::insert_mode]), so the app has to copy them into the state.
+        pub fn mode(
+            maybe_vim_mode: Option<VimMode>,
+            insert_mode: InsertMode,
+            alignment: StatusBarAlignment,
+        ) -> Option<Self> {
+            let mut labels = vec![];
+            if let Some(vim_mode) = maybe_vim_mode {
+                labels.push(vim_mode.to_string());
+            }
+            if insert_mode == InsertMode::Overwrite {
+                labels.push(OVERWRITE_MODE_INDICATOR_TEXT.to_string());
+            }
+            if labels.is_empty() {
+                return None;
+            }
+            Some(Self::from_text(
+                alignment,
+                &format!(" {} ", labels.join(" ")),
+                get_vim_mode_indicator_style(),
+            ))
+        }
+    }
+}
+pub use status_bar_component_impl::*;
+
+/// Pack the `segments` (in importance order, more info in [StatusBarComponent]) into a row
+/// that is `col_count` wide:
+/// - [StatusBarAlignment::Left] segments start at the first col.
+/// - [StatusBarAlignment::Right] segments end at the last col.
+/// - [StatusBarAlignment::Center] segments are centered in the row, but they are moved
+///   (if needed) so that they don't overlap the other segments.
+///
+/// The gaps are filled w/ spaces in the `style`, which is added to the style of each
+/// segment.
+pub fn layout_status_bar(
+    segments: &[StatusBarSegment],
+    col_count: ChUnit,
+    style: Style,
+) -> StyledTexts {
+    let col_count = ch!(@to_usize col_count);
+    let mut segments: Vec<StatusBarSegment> = segments
+        .iter()
+        .filter(|it| it.display_width() > ch!(0))
+        .cloned()
+        .collect();
+
+    // Truncate (or drop) the least important segments until the rest fit.
+    loop {
+        let required_width = get_required_width(&segments);
+        if required_width <= col_count {
+            break;
+        }
+        let Some(last_segment) = segments.last_mut() else {
+            break;
+        };
+        let overflow = required_width - col_count;
+        let width = ch!(@to_usize last_segment.display_width());
+        if width > overflow {
+            last_segment.styled_texts =
+                truncate_styled_texts(&last_segment.styled_texts, ch!(width - overflow));
+        } else {
+            segments.pop();
+        }
+    }
+
+    let get_group = |alignment: StatusBarAlignment| -> StyledTexts {
+        let mut it = StyledTexts::default();
+        for segment in segments.iter().filter(|it| it.alignment == alignment) {
+            if !it.is_empty() {
+                it += styled_text! { @style: style, @text: SPACER };
+            }
+            for StyledText(segment_style, text) in segment.styled_texts.iter() {
+                it += StyledText(style + *segment_style, text.clone());
+            }
+        }
+        it
+    };
+    let left = get_group(StatusBarAlignment::Left);
+    let center = get_group(StatusBarAlignment::Center);
+    let right = get_group(StatusBarAlignment::Right);
+    let left_width = ch!(@to_usize left.display_width());
+    let center_width = ch!(@to_usize center.display_width());
+    let right_width = ch!(@to_usize right.display_width());
+
+    // Keep a gap of 1 col between the center & the other groups.
+    let right_start_col_index = col_count - right_width;
+    let center_start_col_index = {
+        let ideal = (col_count - center_width) / 2;
+        let min = if left_width > 0 { left_width + 1 } else { 0 };
+        let max = if right_width > 0 {
+            right_start_col_index.saturating_sub(center_width + 1)
+        } else {
+            col_count - center_width
+        };
+        ideal.min(max).max(min)
+    };
+
+    let mut it = StyledTexts::default();
+    let pad_to = |it: &mut StyledTexts, col_index: usize| {
+        let width = ch!(@to_usize it.display_width());
+        if col_index > width {
+            *it +=
+                styled_text! { @style: style, @text: SPACER.repeat(col_index - width) };
+        }
+    };
+    it += left;
+    if center_width > 0 {
+        pad_to(&mut it, center_start_col_index);
+        it += center;
+    }
+    pad_to(&mut it, right_start_col_index);
+    it += right;
+    pad_to(&mut it, col_count);
+    it
+}
+
+/// The width of the segments, w/ a space in between the segments of each
+/// [StatusBarAlignment], & a space in between the groups.
+fn get_required_width(segments: &[StatusBarSegment]) -> usize {
+    let mut group_count = 0;
+    let mut width = 0;
+    for alignment in [
+        StatusBarAlignment::Left,
+        StatusBarAlignment::Center,
+        StatusBarAlignment::Right,
+    ] {
+        let widths: Vec<usize> = segments
+            .iter()
+            .filter(|it| it.alignment == alignment)
+            .map(|it| ch!(@to_usize it.display_width()))
+            .collect();
+        if widths.is_empty() {
+            continue;
+        }
+        group_count += 1;
+        width += widths.iter().sum::<usize>() + widths.len() - 1;
+    }
+    width + group_count.max(1) - 1
+}
+
+fn truncate_styled_texts(
+    styled_texts: &StyledTexts,
+    max_display_col_count: ChUnit,
+) -> StyledTexts {
+    let mut it = StyledTexts::default();
+    let mut avail_col_count = max_display_col_count;
+    for StyledText(style, text) in styled_texts.iter() {
+        let truncated_text = text.truncate_end_to_fit_width(avail_col_count);
+        if truncated_text.is_empty() {
+            break;
+        }
+        avail_col_count -= UnicodeString::from(truncated_text).display_width;
+        it += styled_text! { @style: *style, @text: truncated_text };
+    }
+    it
+}
+
+/// This is the default style of the [StatusBarComponent].
+pub fn get_status_bar_style() -> Style {
+    style! {
+        color_fg: TuiColor::Basic(ANSIBasicColor::White)
+        color_bg: match ColorSupport::detect() {
+            ColorSupport::Grayscale => TuiColor::Basic(ANSIBasicColor::DarkGrey),
+            ColorSupport::Ansi256 => TuiColor::Ansi(AnsiValue::new(237)), // Grey23.
+            ColorSupport::Truecolor => TuiColor::Rgb(RgbValue::from_hex("#3a3a3a")),
+        }
+    }
+}
+
+mod constructor {
+    use super::*;
+
+    impl<S> StatusBarComponent<S>
+    where
+        S: Debug + Default + Clone + PartialEq + Sync + Send,
+    {
+        /// The segments are in importance order, more info in [StatusBarComponent].
+        pub fn new(id: FlexBoxId, segments: Vec<StatusBarSegmentFn<S>>) -> Self {
+            Self {
+                id,
+                segments,
+                style: get_status_bar_style(),
+            }
+        }
+
+        pub fn new_shared(
+            id: FlexBoxId,
+            segments: Vec<StatusBarSegmentFn<S>>,
+        ) -> Arc<RwLock<Self>> {
+            Arc::new(RwLock::new(StatusBarComponent::new(id, segments)))
+        }
+    }
+}
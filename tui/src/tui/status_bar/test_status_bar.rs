@@ -0,0 +1,118 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+#[cfg(test)]
+mod test_status_bar {
+    use r3bl_rs_utils_core::*;
+    use r3bl_rs_utils_macro::style;
+
+    use crate::*;
+
+    fn segment(alignment: StatusBarAlignment, text: &str) -> StatusBarSegment {
+        StatusBarSegment::from_text(alignment, text, Style::default())
+    }
+
+    fn get_plain_text(styled_texts: &StyledTexts) -> String {
+        styled_texts.to_plain_text_us().string
+    }
+
+    #[test]
+    fn test_layout_status_bar() {
+        let segments = vec![
+            segment(StatusBarAlignment::Left, "NORMAL"),
+            segment(StatusBarAlignment::Right, "Ln 1, Col 1"),
+            segment(StatusBarAlignment::Center, "a.md"),
+            segment(StatusBarAlignment::Left, "[+]"),
+        ];
+
+        let it = layout_status_bar(&segments, ch!(30), Style::default());
+        assert_eq2!(get_plain_text(&it), "NORMAL [+]   a.md  Ln 1, Col 1");
+        assert_eq2!(it.display_width(), ch!(30));
+
+        // The center is moved so that it doesn't overlap the left segments.
+        let segments = vec![
+            segment(StatusBarAlignment::Left, "NORMAL [+]"),
+            segment(StatusBarAlignment::Center, "a.md"),
+        ];
+        let it = layout_status_bar(&segments, ch!(20), Style::default());
+        assert_eq2!(get_plain_text(&it), "NORMAL [+] a.md     ");
+    }
+
+    #[test]
+    fn test_layout_status_bar_truncates_least_important_segment() {
+        let segments = vec![
+            segment(StatusBarAlignment::Left, "NORMAL"),
+            segment(StatusBarAlignment::Right, "Ln 1, Col 1"),
+            segment(StatusBarAlignment::Left, "long_file_name.md"),
+        ];
+
+        // The last segment is truncated.
+        let it = layout_status_bar(&segments, ch!(25), Style::default());
+        assert_eq2!(get_plain_text(&it), "NORMAL long_f Ln 1, Col 1");
+        assert_eq2!(it.display_width(), ch!(25));
+
+        // The last segment is dropped, & then the one before it is truncated.
+        let it = layout_status_bar(&segments, ch!(15), Style::default());
+        assert_eq2!(get_plain_text(&it), "NORMAL Ln 1, Co");
+
+        // Nothing fits.
+        let it = layout_status_bar(&segments, ch!(0), Style::default());
+        assert_eq2!(get_plain_text(&it), "");
+    }
+
+    #[test]
+    fn test_layout_status_bar_adds_the_bar_style() {
+        let bar_style = style! { color_bg: TuiColor::Basic(ANSIBasicColor::Blue) };
+        let fg_style = style! { color_fg: TuiColor::Basic(ANSIBasicColor::Red) };
+        let segments = vec![StatusBarSegment::from_text(
+            StatusBarAlignment::Left,
+            "abc",
+            fg_style,
+        )];
+
+        let it = layout_status_bar(&segments, ch!(5), bar_style);
+        assert_eq2!(it[0].get_style().color_fg, fg_style.color_fg);
+        assert_eq2!(it[0].get_style().color_bg, bar_style.color_bg);
+        // The padding is in the bar style.
+        assert_eq2!(it[1].get_style(), &bar_style);
+    }
+
+    #[test]
+    fn test_editor_segments() {
+        let mut editor_buffer = EditorBuffer::new_empty(Some("md"));
+        editor_buffer.set_lines(vec!["abc".to_string()]);
+        let alignment = StatusBarAlignment::Left;
+
+        let it = StatusBarSegment::caret_position(&editor_buffer, alignment);
+        assert_eq2!(get_plain_text(&it.styled_texts), "Ln 1, Col 1");
+
+        let it = StatusBarSegment::file_name(None, &editor_buffer, alignment);
+        assert_eq2!(get_plain_text(&it.styled_texts), "untitled.md");
+        let it = StatusBarSegment::file_name(Some("a.md"), &editor_buffer, alignment);
+        assert_eq2!(get_plain_text(&it.styled_texts), "a.md");
+
+        editor_buffer.mark_clean();
+        assert!(StatusBarSegment::dirty_indicator(&editor_buffer, alignment).is_none());
+        editor_buffer.mark_dirty();
+        let it = StatusBarSegment::dirty_indicator(&editor_buffer, alignment).unwrap();
+        assert_eq2!(get_plain_text(&it.styled_texts), "[+]");
+
+        assert!(StatusBarSegment::mode(None, InsertMode::Insert, alignment).is_none());
+        let it = StatusBarSegment::mode(None, InsertMode::Overwrite, alignment).unwrap();
+        assert_eq2!(get_plain_text(&it.styled_texts), " OVR ");
+    }
+}
@@ -133,6 +133,10 @@ impl StyleUSSpanLine {
 
     /// Clip the text (in one line) in this range: [ `start_col` .. `end_col` ]. Each line is
     /// represented as a [List] of ([Style], [US])`s.
+    ///
+    /// A wide grapheme cluster (eg: `中`) that straddles either edge is painted as spaces
+    /// (w/ its style), since half of it can't be painted. This way the rest of the line
+    /// stays in its cols. More info in [UnicodeString::get_clip_to_width_padding].
     pub fn clip(
         &self,
         scroll_offset_col_index: ChUnit,
@@ -141,9 +145,22 @@ impl StyleUSSpanLine {
         // Populated and returned at the end.
         let mut list: List<StyleUSSpan> = List::default();
 
+        let plain_text = US::from(self.get_plain_text());
+        let (start_padding, end_padding) = plain_text
+            .get_clip_to_width_padding(scroll_offset_col_index, max_display_col_count);
+        let start_padding_style =
+            self.get_style_at_display_col_index(scroll_offset_col_index);
+        let end_padding_style = self.get_style_at_display_col_index(
+            scroll_offset_col_index + max_display_col_count - end_padding,
+        );
+
+        // The text starts after the wide grapheme cluster at the start (if any).
+        let scroll_offset_col_index = scroll_offset_col_index + start_padding;
+        let max_display_col_count = max_display_col_count - start_padding;
+
         // Clip w/out syntax highlighting & store this as a pattern to match against.
         let plain_text_pattern: &str =
-            &self.get_plain_text_clipped(scroll_offset_col_index, max_display_col_count);
+            plain_text.clip_to_width(scroll_offset_col_index, max_display_col_count);
         let mut matcher = PatternMatcherStateMachine::new(
             plain_text_pattern,
             Some(scroll_offset_col_index),
@@ -190,9 +207,39 @@ impl StyleUSSpanLine {
             }
         }
 
+        if start_padding > ch!(0) {
+            list.insert(
+                0,
+                StyleUSSpan::new(
+                    start_padding_style,
+                    US::from(SPACE.repeat(ch!(@to_usize start_padding))),
+                ),
+            );
+        }
+        if end_padding > ch!(0) {
+            list.push(StyleUSSpan::new(
+                end_padding_style,
+                US::from(SPACE.repeat(ch!(@to_usize end_padding))),
+            ));
+        }
+
         StyledTexts::from(list)
     }
 
+    /// Returns the style of the span that the given display col is in, or the style of the
+    /// last span if it is past the end of the line.
+    fn get_style_at_display_col_index(&self, display_col_index: ChUnit) -> Style {
+        let mut span_start_col_index = ch!(0);
+        for span in self.iter() {
+            let span_end_col_index = span_start_col_index + span.text.display_width;
+            if display_col_index < span_end_col_index {
+                return span.style;
+            }
+            span_start_col_index = span_end_col_index;
+        }
+        self.last().map(|span| span.style).unwrap_or_default()
+    }
+
     pub fn display_width(&self) -> ChUnit {
         let mut size = ch!(0);
         for StyleUSSpan {
@@ -0,0 +1,81 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use syntect::parsing::SyntaxSet;
+
+use crate::*;
+
+/// Line comment prefix for each language, keyed by the name of the syntect syntax (eg:
+/// `Rust`). Languages that only have block comments (eg: `Markdown`, `HTML`) are not in
+/// this table.
+pub const LINE_COMMENT_PREFIXES: &[(&str, &str)] = &[
+    ("Rust", "//"),
+    ("C", "//"),
+    ("C++", "//"),
+    ("C#", "//"),
+    ("Go", "//"),
+    ("Java", "//"),
+    ("JavaScript", "//"),
+    ("Objective-C", "//"),
+    ("Scala", "//"),
+    ("D", "//"),
+    ("Python", "#"),
+    ("Ruby", "#"),
+    ("Perl", "#"),
+    ("R", "#"),
+    ("YAML", "#"),
+    ("Makefile", "#"),
+    ("Bourne Again Shell (bash)", "#"),
+    ("Haskell", "--"),
+    ("Lua", "--"),
+    ("SQL", "--"),
+    ("Lisp", ";"),
+    ("Clojure", ";"),
+    ("Erlang", "%"),
+    ("LaTeX", "%"),
+    ("TeX", "%"),
+    ("Batch File", "REM"),
+];
+
+/// Use the same language detection as syntax highlighting (see [try_get_syntax_ref]) to
+/// find the line comment prefix for the given `file_extension`, eg: `//` for `rs`.
+pub fn try_get_line_comment_prefix(
+    syntax_set: &SyntaxSet,
+    file_extension: &str,
+) -> Option<&'static str> {
+    let syntax = try_get_syntax_ref(syntax_set, file_extension)?;
+    LINE_COMMENT_PREFIXES
+        .iter()
+        .find(|(language_name, _)| *language_name == syntax.name)
+        .map(|(_, prefix)| *prefix)
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_rs_utils_core::*;
+
+    use super::*;
+
+    #[test]
+    fn test_try_get_line_comment_prefix() {
+        let syntax_set = SyntaxSet::load_defaults_newlines();
+        assert_eq2!(try_get_line_comment_prefix(&syntax_set, "rs"), Some("//"));
+        assert_eq2!(try_get_line_comment_prefix(&syntax_set, "py"), Some("#"));
+        assert_eq2!(try_get_line_comment_prefix(&syntax_set, "md"), None);
+        assert_eq2!(try_get_line_comment_prefix(&syntax_set, "unknown"), None);
+    }
+}
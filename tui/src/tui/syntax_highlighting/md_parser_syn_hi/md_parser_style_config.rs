@@ -0,0 +1,158 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+//! [MarkdownStyleConfig] holds the style for each kind of markdown element, so that an app
+//! can align the markdown colors w/ its own look (and swap them along w/ the rest of its
+//! [TuiTheme]). The defaults come from [crate::md_parser_stylesheet].
+
+use r3bl_rs_utils_core::*;
+use serde::{Deserialize, Serialize};
+
+use crate::*;
+
+pub const DEFAULT_BLOCKQUOTE_BAR_CHAR: char = '┃';
+
+/// The style used for each markdown element by [try_parse_and_highlight] (and the
+/// [MarkdownViewComponent]). These styles are painted on top of the computed style of the
+/// box that the markdown is rendered in.
+///
+/// Use [MarkdownStyleConfig::default] to get the current look, and then override any subset
+/// of the fields.
+///
+/// ```rust
+/// use r3bl_rs_utils_core::*;
+/// use r3bl_rs_utils_macro::style;
+/// use r3bl_tui::*;
+///
+/// let config = MarkdownStyleConfig {
+///     bold: style! { attrib: [bold] color_fg: TuiColor::Basic(ANSIBasicColor::Red) },
+///     ..Default::default()
+/// }
+/// .with_heading_style(1, style! { color_fg: TuiColor::Basic(ANSIBasicColor::Blue) });
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct MarkdownStyleConfig {
+    /// One for each heading level, `#` to `######`. When [None] the heading text is painted
+    /// w/ a color wheel (which is the default).
+    pub heading: [Option<Style>; 6],
+    pub bold: Style,
+    pub italic: Style,
+    pub bold_italic: Style,
+    pub inline_code: Style,
+    /// The content of a code block when it can't be highlighted by syntect.
+    pub code_block: Style,
+    pub code_block_lang: Style,
+    pub link_text: Style,
+    pub link_url: Style,
+    /// The text of a blockquote (not the bars before it).
+    pub blockquote: Style,
+    /// The bars of nested blockquotes cycle through these, one per nesting level.
+    pub blockquote_bar: [Style; 3],
+    /// Painted instead of each `>` marker of a blockquote. The [editor] only uses it if it
+    /// is 1 col wide (just like `>`), so that the caret still lines up w/ the text.
+    pub blockquote_bar_char: char,
+    /// The bullets of nested list items cycle through these, one per nesting level.
+    pub list_bullet: [Style; 3],
+    pub list_number: Style,
+}
+
+impl Default for MarkdownStyleConfig {
+    fn default() -> Self {
+        Self {
+            heading: [None; 6],
+            bold: get_bold_style(),
+            italic: get_italic_style(),
+            bold_italic: get_bold_italic_style(),
+            inline_code: get_inline_code_style(),
+            code_block: get_code_block_content_style(),
+            code_block_lang: get_code_block_lang_style(),
+            link_text: get_link_text_style(),
+            link_url: get_link_url_style(),
+            blockquote: get_blockquote_style(),
+            blockquote_bar: [
+                get_blockquote_bar_style_for_nesting_level(0),
+                get_blockquote_bar_style_for_nesting_level(1),
+                get_blockquote_bar_style_for_nesting_level(2),
+            ],
+            blockquote_bar_char: DEFAULT_BLOCKQUOTE_BAR_CHAR,
+            list_bullet: [
+                get_list_bullet_style_for_nesting_level(0),
+                get_list_bullet_style_for_nesting_level(1),
+                get_list_bullet_style_for_nesting_level(2),
+            ],
+            list_number: get_list_number_style(),
+        }
+    }
+}
+
+impl MarkdownStyleConfig {
+    /// `level` is 1 based (ie, `1` is for `#`). Levels that are out of range are ignored.
+    pub fn with_heading_style(mut self, level: usize, style: Style) -> Self {
+        if let Some(it) = self.heading.get_mut(level.wrapping_sub(1)) {
+            *it = Some(style);
+        }
+        self
+    }
+
+    /// [None] means that the heading should be painted w/ a color wheel.
+    pub fn get_heading_style(&self, level: usize) -> Option<Style> {
+        self.heading.get(level.wrapping_sub(1)).copied().flatten()
+    }
+
+    pub fn get_list_bullet_style_for_nesting_level(&self, nesting_level: usize) -> Style {
+        self.list_bullet[nesting_level % self.list_bullet.len()]
+    }
+
+    /// `nesting_level` is 0 based (ie, `0` is for the first `>`).
+    pub fn get_blockquote_bar_style_for_nesting_level(
+        &self,
+        nesting_level: usize,
+    ) -> Style {
+        self.blockquote_bar[nesting_level % self.blockquote_bar.len()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_rs_utils_macro::style;
+
+    use super::*;
+
+    #[test]
+    fn test_default_matches_stylesheet() {
+        let config = MarkdownStyleConfig::default();
+        assert_eq2!(config.bold, get_bold_style());
+        assert_eq2!(config.get_heading_style(1), None);
+        assert_eq2!(
+            config.get_list_bullet_style_for_nesting_level(4),
+            get_list_bullet_style_for_nesting_level(4)
+        );
+    }
+
+    #[test]
+    fn test_with_heading_style() {
+        let style = style! { color_fg: TuiColor::Basic(ANSIBasicColor::Blue) };
+        let config = MarkdownStyleConfig::default()
+            .with_heading_style(2, style)
+            .with_heading_style(0, style)
+            .with_heading_style(7, style);
+        assert_eq2!(config.get_heading_style(1), None);
+        assert_eq2!(config.get_heading_style(2), Some(style));
+        assert_eq2!(config.get_heading_style(0), None);
+        assert_eq2!(config.get_heading_style(7), None);
+    }
+}
@@ -34,6 +34,352 @@ pub fn get_selection_style() -> Style {
     }
 }
 
+/// This style is for the summary line that is painted in place of a folded region.
+pub fn get_folded_region_style() -> Style {
+    style! {
+        attrib: [dim, italic]
+        color_fg: match ColorSupport::detect() {
+            ColorSupport::Grayscale => TuiColor::Basic(ANSIBasicColor::White),
+            ColorSupport::Ansi256 => TuiColor::Ansi(AnsiValue::new(244)), // Grey50.
+            ColorSupport::Truecolor => TuiColor::Rgb(RgbValue::from_hex("#8a8a8a")),
+        }
+    }
+}
+
+/// This is the default style for the markers that are painted on top of whitespace.
+pub fn get_whitespace_marker_style() -> Style {
+    style! {
+        attrib: [dim]
+        color_fg: match ColorSupport::detect() {
+            ColorSupport::Grayscale => TuiColor::Basic(ANSIBasicColor::DarkGrey),
+            ColorSupport::Ansi256 => TuiColor::Ansi(AnsiValue::new(240)), // Grey35.
+            ColorSupport::Truecolor => TuiColor::Rgb(RgbValue::from_hex("#585858")),
+        }
+    }
+}
+
+/// This is the default style for the track of the scrollbars in the editor.
+pub fn get_scrollbar_track_style() -> Style {
+    style! {
+        attrib: [dim]
+        color_fg: match ColorSupport::detect() {
+            ColorSupport::Grayscale => TuiColor::Basic(ANSIBasicColor::DarkGrey),
+            ColorSupport::Ansi256 => TuiColor::Ansi(AnsiValue::new(238)), // Grey27.
+            ColorSupport::Truecolor => TuiColor::Rgb(RgbValue::from_hex("#444444")),
+        }
+    }
+}
+
+/// This is the default style for the thumb of the scrollbars in the editor.
+pub fn get_scrollbar_thumb_style() -> Style {
+    style! {
+        color_fg: match ColorSupport::detect() {
+            ColorSupport::Grayscale => TuiColor::Basic(ANSIBasicColor::White),
+            ColorSupport::Ansi256 => TuiColor::Ansi(AnsiValue::new(246)), // Grey58.
+            ColorSupport::Truecolor => TuiColor::Rgb(RgbValue::from_hex("#949494")),
+        }
+    }
+}
+
+/// This is the default style for the markers that are painted at the start & end of
+/// lines that are clipped by the editor's viewport.
+pub fn get_clip_marker_style() -> Style {
+    style! {
+        attrib: [bold]
+        color_fg: match ColorSupport::detect() {
+            ColorSupport::Grayscale => TuiColor::Basic(ANSIBasicColor::Yellow),
+            ColorSupport::Ansi256 => TuiColor::Ansi(AnsiValue::new(179)), // LightGoldenrod3.
+            ColorSupport::Truecolor => TuiColor::Rgb(RgbValue::from_hex("#d7af5f")),
+        }
+    }
+}
+
+/// This is the style for the gutter glyph & the underline of a [DiagnosticSeverity].
+pub fn get_diagnostic_style(severity: DiagnosticSeverity) -> Style {
+    let color_fg = match (severity, ColorSupport::detect()) {
+        (DiagnosticSeverity::Error, ColorSupport::Grayscale) => {
+            TuiColor::Basic(ANSIBasicColor::Red)
+        }
+        (DiagnosticSeverity::Error, ColorSupport::Ansi256) => {
+            TuiColor::Ansi(AnsiValue::new(203)) // IndianRed1.
+        }
+        (DiagnosticSeverity::Error, ColorSupport::Truecolor) => {
+            TuiColor::Rgb(RgbValue::from_hex("#ff5f5f"))
+        }
+        (DiagnosticSeverity::Warning, ColorSupport::Grayscale) => {
+            TuiColor::Basic(ANSIBasicColor::Yellow)
+        }
+        (DiagnosticSeverity::Warning, ColorSupport::Ansi256) => {
+            TuiColor::Ansi(AnsiValue::new(221)) // LightGoldenrod2.
+        }
+        (DiagnosticSeverity::Warning, ColorSupport::Truecolor) => {
+            TuiColor::Rgb(RgbValue::from_hex("#ffd75f"))
+        }
+        (DiagnosticSeverity::Info, ColorSupport::Grayscale) => {
+            TuiColor::Basic(ANSIBasicColor::Blue)
+        }
+        (DiagnosticSeverity::Info, ColorSupport::Ansi256) => {
+            TuiColor::Ansi(AnsiValue::new(75)) // SteelBlue1.
+        }
+        (DiagnosticSeverity::Info, ColorSupport::Truecolor) => {
+            TuiColor::Rgb(RgbValue::from_hex("#5fafff"))
+        }
+        (DiagnosticSeverity::Hint, ColorSupport::Grayscale) => {
+            TuiColor::Basic(ANSIBasicColor::Grey)
+        }
+        (DiagnosticSeverity::Hint, ColorSupport::Ansi256) => {
+            TuiColor::Ansi(AnsiValue::new(246)) // Grey58.
+        }
+        (DiagnosticSeverity::Hint, ColorSupport::Truecolor) => {
+            TuiColor::Rgb(RgbValue::from_hex("#949494"))
+        }
+    };
+    style! {
+        color_fg: color_fg
+    }
+}
+
+/// This is the default style for the spinner in the progress component.
+pub fn get_progress_spinner_style() -> Style {
+    style! {
+        attrib: [bold]
+        color_fg: match ColorSupport::detect() {
+            ColorSupport::Grayscale => TuiColor::Basic(ANSIBasicColor::Cyan),
+            ColorSupport::Ansi256 => TuiColor::Ansi(AnsiValue::new(80)), // DarkTurquoise.
+            ColorSupport::Truecolor => TuiColor::Rgb(RgbValue::from_hex("#5fd7d7")),
+        }
+    }
+}
+
+/// This is the default style for the filled part of the bar in the progress component.
+pub fn get_progress_bar_filled_style() -> Style {
+    style! {
+        color_fg: match ColorSupport::detect() {
+            ColorSupport::Grayscale => TuiColor::Basic(ANSIBasicColor::Green),
+            ColorSupport::Ansi256 => TuiColor::Ansi(AnsiValue::new(77)), // PaleGreen3.
+            ColorSupport::Truecolor => TuiColor::Rgb(RgbValue::from_hex("#5fd75f")),
+        }
+    }
+}
+
+/// This is the default style for the empty part of the bar in the progress component.
+pub fn get_progress_bar_empty_style() -> Style {
+    style! {
+        attrib: [dim]
+        color_fg: match ColorSupport::detect() {
+            ColorSupport::Grayscale => TuiColor::Basic(ANSIBasicColor::DarkGrey),
+            ColorSupport::Ansi256 => TuiColor::Ansi(AnsiValue::new(238)), // Grey27.
+            ColorSupport::Truecolor => TuiColor::Rgb(RgbValue::from_hex("#444444")),
+        }
+    }
+}
+
+/// This is the default style for the item that has focus in the list component.
+pub fn get_list_focused_item_style() -> Style {
+    style! {
+        attrib: [reverse]
+    }
+}
+
+/// This is the default style for the header row of the list component.
+pub fn get_list_header_style() -> Style {
+    style! {
+        attrib: [bold]
+        color_fg: match ColorSupport::detect() {
+            ColorSupport::Grayscale => TuiColor::Basic(ANSIBasicColor::White),
+            ColorSupport::Ansi256 => TuiColor::Ansi(AnsiValue::new(252)), // Grey82.
+            ColorSupport::Truecolor => TuiColor::Rgb(RgbValue::from_hex("#d0d0d0")),
+        }
+    }
+}
+
+/// This is the default style for the label of the active tab in the tabs component.
+pub fn get_active_tab_style() -> Style {
+    style! {
+        attrib: [bold]
+        color_fg: match ColorSupport::detect() {
+            ColorSupport::Grayscale => TuiColor::Basic(ANSIBasicColor::Black),
+            ColorSupport::Ansi256 => TuiColor::Ansi(AnsiValue::new(16)), // Grey0.
+            ColorSupport::Truecolor => TuiColor::Rgb(RgbValue::from_hex("#000000")),
+        }
+        color_bg: match ColorSupport::detect() {
+            ColorSupport::Grayscale => TuiColor::Basic(ANSIBasicColor::Cyan),
+            ColorSupport::Ansi256 => TuiColor::Ansi(AnsiValue::new(80)), // DarkTurquoise.
+            ColorSupport::Truecolor => TuiColor::Rgb(RgbValue::from_hex("#5fd7d7")),
+        }
+    }
+}
+
+/// This is the default style for the labels of the inactive tabs in the tabs component.
+pub fn get_inactive_tab_style() -> Style {
+    style! {
+        attrib: [dim]
+        color_fg: match ColorSupport::detect() {
+            ColorSupport::Grayscale => TuiColor::Basic(ANSIBasicColor::White),
+            ColorSupport::Ansi256 => TuiColor::Ansi(AnsiValue::new(250)), // Grey74.
+            ColorSupport::Truecolor => TuiColor::Rgb(RgbValue::from_hex("#bcbcbc")),
+        }
+    }
+}
+
+/// This is the default style for the placeholder of an empty text input component.
+pub fn get_text_input_placeholder_style() -> Style {
+    style! {
+        attrib: [dim, italic]
+        color_fg: match ColorSupport::detect() {
+            ColorSupport::Grayscale => TuiColor::Basic(ANSIBasicColor::White),
+            ColorSupport::Ansi256 => TuiColor::Ansi(AnsiValue::new(245)), // Grey54.
+            ColorSupport::Truecolor => TuiColor::Rgb(RgbValue::from_hex("#8a8a8a")),
+        }
+    }
+}
+
+/// This is the default style for the items in the completion popup of the editor.
+pub fn get_completion_popup_style() -> Style {
+    style! {
+        color_fg: match ColorSupport::detect() {
+            ColorSupport::Grayscale => TuiColor::Basic(ANSIBasicColor::White),
+            ColorSupport::Ansi256 => TuiColor::Ansi(AnsiValue::new(252)), // Grey82.
+            ColorSupport::Truecolor => TuiColor::Rgb(RgbValue::from_hex("#d0d0d0")),
+        }
+        color_bg: match ColorSupport::detect() {
+            ColorSupport::Grayscale => TuiColor::Basic(ANSIBasicColor::DarkGrey),
+            ColorSupport::Ansi256 => TuiColor::Ansi(AnsiValue::new(236)), // Grey19.
+            ColorSupport::Truecolor => TuiColor::Rgb(RgbValue::from_hex("#303030")),
+        }
+    }
+}
+
+/// This is the default style for the selected item in the completion popup of the
+/// editor.
+pub fn get_completion_popup_selected_style() -> Style {
+    style! {
+        attrib: [bold]
+        color_fg: match ColorSupport::detect() {
+            ColorSupport::Grayscale => TuiColor::Basic(ANSIBasicColor::Black),
+            ColorSupport::Ansi256 => TuiColor::Ansi(AnsiValue::new(16)), // Grey0.
+            ColorSupport::Truecolor => TuiColor::Rgb(RgbValue::from_hex("#000000")),
+        }
+        color_bg: match ColorSupport::detect() {
+            ColorSupport::Grayscale => TuiColor::Basic(ANSIBasicColor::Cyan),
+            ColorSupport::Ansi256 => TuiColor::Ansi(AnsiValue::new(80)), // DarkTurquoise.
+            ColorSupport::Truecolor => TuiColor::Rgb(RgbValue::from_hex("#5fd7d7")),
+        }
+    }
+}
+
+/// This style is for the matches of the query in the replace mode of the editor.
+pub fn get_search_match_style() -> Style {
+    style! {
+        color_fg: TuiColor::Rgb(RgbValue::from_hex("#1e1e1e"))
+        color_bg: match ColorSupport::detect() {
+            ColorSupport::Grayscale => TuiColor::Basic(ANSIBasicColor::Yellow),
+            ColorSupport::Ansi256 => TuiColor::Ansi(AnsiValue::new(180)), // Tan.
+            ColorSupport::Truecolor => TuiColor::Rgb(RgbValue::from_hex("#d7af87")),
+        }
+    }
+}
+
+/// This style is for the match that is replaced next in the replace mode of the editor.
+pub fn get_search_current_match_style() -> Style {
+    style! {
+        attrib: [bold]
+        color_fg: TuiColor::Rgb(RgbValue::from_hex("#1e1e1e"))
+        color_bg: match ColorSupport::detect() {
+            ColorSupport::Grayscale => TuiColor::Basic(ANSIBasicColor::DarkYellow),
+            ColorSupport::Ansi256 => TuiColor::Ansi(AnsiValue::new(214)), // Orange1.
+            ColorSupport::Truecolor => TuiColor::Rgb(RgbValue::from_hex("#ffaf00")),
+        }
+    }
+}
+
+/// This style is for the error that is shown in the replace bar of the editor when the
+/// query is an invalid regex.
+pub fn get_search_error_style() -> Style {
+    style! {
+        attrib: [bold]
+        color_fg: TuiColor::Basic(ANSIBasicColor::Red)
+        color_bg: match ColorSupport::detect() {
+            ColorSupport::Grayscale => TuiColor::Basic(ANSIBasicColor::DarkGrey),
+            ColorSupport::Ansi256 => TuiColor::Ansi(AnsiValue::new(236)), // Grey19.
+            ColorSupport::Truecolor => TuiColor::Rgb(RgbValue::from_hex("#303030")),
+        }
+    }
+}
+
+/// This style is for the secondary carets in the editor. The primary caret is painted w/
+/// reverse video, so these are underlined to tell them apart.
+pub fn get_secondary_caret_style() -> Style {
+    style! {
+        attrib: [underline]
+        color_bg: match ColorSupport::detect() {
+            ColorSupport::Grayscale => TuiColor::Basic(ANSIBasicColor::DarkGrey),
+            ColorSupport::Ansi256 => TuiColor::Ansi(AnsiValue::new(60)), // MediumPurple4.
+            ColorSupport::Truecolor => TuiColor::Rgb(RgbValue::from_hex("#5f5f87")),
+        }
+    }
+}
+
+/// This style is for the vertical guides that are painted at the cols in
+/// [EditorEngineConfig::rulers].
+pub fn get_ruler_style() -> Style {
+    style! {
+        attrib: [dim]
+        color_fg: match ColorSupport::detect() {
+            ColorSupport::Grayscale => TuiColor::Basic(ANSIBasicColor::DarkGrey),
+            ColorSupport::Ansi256 => TuiColor::Ansi(AnsiValue::new(238)), // Grey27.
+            ColorSupport::Truecolor => TuiColor::Rgb(RgbValue::from_hex("#444444")),
+        }
+    }
+}
+
+/// This style is for the vertical guides that are painted in the leading whitespace of
+/// indented lines, when [EditorEngineConfig::indent_guides] is enabled.
+pub fn get_indent_guide_style() -> Style {
+    style! {
+        attrib: [dim]
+        color_fg: match ColorSupport::detect() {
+            ColorSupport::Grayscale => TuiColor::Basic(ANSIBasicColor::DarkGrey),
+            ColorSupport::Ansi256 => TuiColor::Ansi(AnsiValue::new(239)), // Grey30.
+            ColorSupport::Truecolor => TuiColor::Rgb(RgbValue::from_hex("#4e4e4e")),
+        }
+    }
+}
+
+/// This style is for the rows that have a caret on them, when
+/// [EditorEngineConfig::highlight_current_line] is enabled. It only has a background
+/// color, so that the foreground colors of the syntax highlighting are kept.
+pub fn get_current_line_style() -> Style {
+    style! {
+        color_bg: match ColorSupport::detect() {
+            ColorSupport::Grayscale => TuiColor::Basic(ANSIBasicColor::Black),
+            ColorSupport::Ansi256 => TuiColor::Ansi(AnsiValue::new(236)), // Grey19.
+            ColorSupport::Truecolor => TuiColor::Rgb(RgbValue::from_hex("#303030")),
+        }
+    }
+}
+
+/// This style is for the caret of an editor that doesn't have focus. It is hollow (just
+/// underlined & dimmed), unlike the reverse video caret of the editor that has focus.
+pub fn get_blurred_caret_style() -> Style {
+    style! {
+        attrib: [underline, dim]
+    }
+}
+
+/// This style is for the [VimMode] indicator that is painted in the editor.
+pub fn get_vim_mode_indicator_style() -> Style {
+    style! {
+        attrib: [bold]
+        color_fg: TuiColor::Rgb(RgbValue::from_hex("#1e1e1e"))
+        color_bg: match ColorSupport::detect() {
+            ColorSupport::Grayscale => TuiColor::Basic(ANSIBasicColor::White),
+            ColorSupport::Ansi256 => TuiColor::Ansi(AnsiValue::new(114)), // PaleGreen3.
+            ColorSupport::Truecolor => TuiColor::Rgb(RgbValue::from_hex("#87d787")),
+        }
+    }
+}
+
 /// This style is for the foreground text of the entire document. This is the default
 /// style. It is overridden by other styles like bold, italic, etc. below.
 pub fn get_foreground_style() -> Style {
@@ -162,6 +508,38 @@ pub fn get_list_bullet_style() -> Style {
     }
 }
 
+/// The bullets (and the `─` leaders) of nested list items cycle through these colors, so
+/// that the nesting level is easy to see. Level 0 is [get_list_bullet_style].
+pub fn get_list_bullet_style_for_nesting_level(nesting_level: usize) -> Style {
+    match nesting_level % 3 {
+        0 => get_list_bullet_style(),
+        1 => style! {
+            color_fg: match ColorSupport::detect() {
+                ColorSupport::Grayscale => TuiColor::Basic(ANSIBasicColor::Cyan),
+                _ => TuiColor::Rgb(RgbValue::from_hex("#a6f8f2")), // Pale cyan.
+            }
+        },
+        _ => style! {
+            color_fg: match ColorSupport::detect() {
+                ColorSupport::Grayscale => TuiColor::Basic(ANSIBasicColor::Magenta),
+                _ => TuiColor::Rgb(RgbValue::from_hex("#f8a6d8")), // Pale pink.
+            }
+        },
+    }
+}
+
+/// This is for the number of an ordered list item (eg: `1.`), not the `│` after it.
+pub fn get_list_number_style() -> Style {
+    style! {
+        attrib: [bold]
+        color_fg: match ColorSupport::detect() {
+            ColorSupport::Grayscale => TuiColor::Basic(ANSIBasicColor::DarkYellow),
+            ColorSupport::Ansi256 => TuiColor::Ansi(AnsiValue::new(214)), // Orange1.
+            ColorSupport::Truecolor => TuiColor::Rgb(RgbValue::from_hex("#ffaf00")),
+        }
+    }
+}
+
 pub fn get_code_block_lang_style() -> Style {
     get_inline_code_style()
         + style! {
@@ -171,6 +549,31 @@ pub fn get_code_block_lang_style() -> Style {
 
 pub fn get_code_block_content_style() -> Style { get_inline_code_style() }
 
+/// This is for the box that is painted around a code block by the [MarkdownViewComponent].
+pub fn get_code_block_border_style() -> Style { get_foreground_dim_style() }
+
+/// This is for the text of a blockquote (not the `┃` bar that is painted before it) in the
+/// [MarkdownViewComponent].
+pub fn get_blockquote_style() -> Style {
+    style! {
+        attrib: [italic]
+        color_fg: match ColorSupport::detect() {
+            ColorSupport::Grayscale => TuiColor::Basic(ANSIBasicColor::White),
+            ColorSupport::Ansi256 => TuiColor::Ansi(AnsiValue::new(250)), // Grey74.
+            ColorSupport::Truecolor => TuiColor::Rgb(RgbValue::from_hex("#bcbcbc")),
+        }
+    }
+}
+
+/// The bars of nested blockquotes cycle through these colors, one per nesting level. Level
+/// 0 is dim, & the rest use the colors of [get_list_bullet_style_for_nesting_level].
+pub fn get_blockquote_bar_style_for_nesting_level(nesting_level: usize) -> Style {
+    match nesting_level % 3 {
+        0 => get_foreground_dim_style(),
+        it => get_list_bullet_style_for_nesting_level(it),
+    }
+}
+
 /// - Bg color: #4f86ed
 /// - Fg color: black
 pub fn get_metadata_title_marker_style() -> Style {
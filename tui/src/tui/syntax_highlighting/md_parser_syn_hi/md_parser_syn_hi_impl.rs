@@ -25,15 +25,16 @@ use crate::{constants::*, *};
 
 /// This is the main function that the [editor] uses this in order to display the markdown to the
 /// user.It is responsible for converting:
-/// - from a &[Vec] of [US] which comes from the [editor],
+/// - from the [EditorLines] which come from the [editor],
 /// - into a [StyleUSSpanLines], which the [editor] will clip & render.
 ///
 /// # Arguments
 /// - `editor_text` - The text that the user has typed into the editor.
 /// - `current_box_computed_style` - The computed style of the box that the editor is in.
 pub fn try_parse_and_highlight(
-    editor_text_lines: &Vec<US>,
+    editor_text_lines: &EditorLines,
     maybe_current_box_computed_style: &Option<Style>,
+    md_style_config: &MarkdownStyleConfig,
     maybe_syntect_tuple: Option<(&SyntaxSet, &Theme)>,
 ) -> CommonResult<StyleUSSpanLines> {
     // Convert the editor text into a string.
@@ -51,6 +52,7 @@ pub fn try_parse_and_highlight(
         Ok((_, document)) => Ok(StyleUSSpanLines::from_document(
             &document,
             maybe_current_box_computed_style,
+            md_style_config,
             maybe_syntect_tuple,
         )),
         Err(_) => CommonError::new_err_with_only_type(CommonErrorType::ParsingError),
@@ -63,7 +65,8 @@ mod tests_try_parse_and_highlight {
 
     #[test]
     fn from_vec_us() -> CommonResult<()> {
-        let editor_text_lines = vec![US::new("Hello"), US::new("World")];
+        let editor_text_lines =
+            EditorLines::from(vec![US::new("Hello"), US::new("World")]);
         let current_box_computed_style = style! {
             color_bg: TuiColor::Basic(ANSIBasicColor::Red)
         };
@@ -71,6 +74,7 @@ mod tests_try_parse_and_highlight {
         let style_us_span_lines = try_parse_and_highlight(
             &editor_text_lines,
             &Some(current_box_computed_style),
+            &MarkdownStyleConfig::default(),
             None,
         )?;
 
@@ -114,13 +118,32 @@ impl StyleUSSpanLines {
     pub fn from_document(
         document: &MdDocument,
         maybe_current_box_computed_style: &Option<Style>,
+        md_style_config: &MarkdownStyleConfig,
         maybe_syntect_tuple: Option<(&SyntaxSet, &Theme)>,
     ) -> Self {
         let mut lines = StyleUSSpanLines::default();
+        // The nesting level of the last blockquote line (if any), for lazy continuation
+        // lines.
+        let mut blockquote_nesting_level = 0;
         for block in document.iter() {
+            if let MdBlockElement::Text(fragments_in_one_line) = block {
+                if let Some((line, nesting_level)) = StyleUSSpanLine::from_blockquote(
+                    fragments_in_one_line,
+                    blockquote_nesting_level,
+                    maybe_current_box_computed_style,
+                    md_style_config,
+                ) {
+                    blockquote_nesting_level = nesting_level;
+                    lines.push(line);
+                    continue;
+                }
+            }
+            blockquote_nesting_level = 0;
+
             let block_to_lines = StyleUSSpanLines::from_block(
                 block,
                 maybe_current_box_computed_style,
+                md_style_config,
                 maybe_syntect_tuple,
             );
             lines.items.extend(block_to_lines.items);
@@ -137,17 +160,18 @@ impl StyleUSSpanLines {
     /// ```
     ///
     /// Case 1: Fallback
-    /// - 1st line        : "```": `get_foreground_dim_style()`, lang: `get_code_block_lang_style()`
-    /// - 2nd line .. end : content: `get_inline_code_style()`
+    /// - 1st line        : "```": `get_foreground_dim_style()`, lang: `md_style_config.code_block_lang`
+    /// - 2nd line .. end : content: `md_style_config.code_block`
     /// - last line       : "```": `get_foreground_dim_style()`
     ///
     /// Case 2: Syntect
-    /// - 1st line        : "```": `get_foreground_dim_style()`, lang: `get_code_block_lang_style()`
+    /// - 1st line        : "```": `get_foreground_dim_style()`, lang: `md_style_config.code_block_lang`
     /// - 2nd line .. end : use syntect to highlight
     /// - last line       : "```": `get_foreground_dim_style()`
     pub fn from_block_codeblock(
         code_block_lines: &CodeBlockLines,
         maybe_current_box_computed_style: &Option<Style>,
+        md_style_config: &MarkdownStyleConfig,
         maybe_syntect_tuple: Option<(&SyntaxSet, &Theme)>,
     ) -> Self {
         mod inner {
@@ -156,6 +180,7 @@ impl StyleUSSpanLines {
             pub fn try_use_syntect(
                 code_block_lines: &CodeBlockLines,
                 maybe_current_box_computed_style: &Option<Style>,
+                md_style_config: &MarkdownStyleConfig,
                 maybe_syntect_tuple: Option<(&SyntaxSet, &Theme)>,
             ) -> Option<StyleUSSpanLines> {
                 let mut acc_lines_output = StyleUSSpanLines::default();
@@ -204,7 +229,7 @@ impl StyleUSSpanLines {
                             if let Some(language) = code_block_line.language {
                                 acc_line_output += StyleUSSpan::new(
                                     maybe_current_box_computed_style.unwrap_or_default()
-                                        + get_code_block_lang_style(),
+                                        + md_style_config.code_block_lang,
                                     US::from(language),
                                 );
                             }
@@ -229,6 +254,7 @@ impl StyleUSSpanLines {
             pub fn use_fallback(
                 code_block_lines: &CodeBlockLines,
                 maybe_current_box_computed_style: &Option<Style>,
+                md_style_config: &MarkdownStyleConfig,
             ) -> StyleUSSpanLines {
                 let mut acc_lines_output = StyleUSSpanLines::default();
 
@@ -246,7 +272,7 @@ impl StyleUSSpanLines {
                             if let Some(language) = code_block_line.language {
                                 acc_line_output += StyleUSSpan::new(
                                     maybe_current_box_computed_style.unwrap_or_default()
-                                        + get_code_block_lang_style(),
+                                        + md_style_config.code_block_lang,
                                     US::from(language),
                                 );
                             }
@@ -263,7 +289,7 @@ impl StyleUSSpanLines {
                         CodeBlockLineContent::Text(content) => {
                             acc_line_output += StyleUSSpan::new(
                                 maybe_current_box_computed_style.unwrap_or_default()
-                                    + get_code_block_content_style(),
+                                    + md_style_config.code_block,
                                 US::from(content),
                             );
                         }
@@ -279,16 +305,22 @@ impl StyleUSSpanLines {
         match inner::try_use_syntect(
             code_block_lines,
             maybe_current_box_computed_style,
+            md_style_config,
             maybe_syntect_tuple,
         ) {
             Some(syntect_output) => syntect_output,
-            _ => inner::use_fallback(code_block_lines, maybe_current_box_computed_style),
+            _ => inner::use_fallback(
+                code_block_lines,
+                maybe_current_box_computed_style,
+                md_style_config,
+            ),
         }
     }
 
     pub fn from_block_smart_list(
         input_ul_lines: &Lines,
         maybe_current_box_computed_style: &Option<Style>,
+        md_style_config: &MarkdownStyleConfig,
     ) -> Self {
         let mut acc_lines_output = StyleUSSpanLines::default();
 
@@ -299,6 +331,7 @@ impl StyleUSSpanLines {
             let postfix_span_list = StyleUSSpanLine::from_fragments(
                 input_line,
                 maybe_current_box_computed_style,
+                md_style_config,
             );
 
             acc_line_output += postfix_span_list;
@@ -315,6 +348,7 @@ impl StyleUSSpanLines {
     pub fn from_block(
         block: &MdBlockElement,
         maybe_current_box_computed_style: &Option<Style>,
+        md_style_config: &MarkdownStyleConfig,
         maybe_syntect_tuple: Option<(&SyntaxSet, &Theme)>,
     ) -> Self {
         let mut lines = StyleUSSpanLines::default();
@@ -352,24 +386,37 @@ impl StyleUSSpanLines {
                 lines.push(StyleUSSpanLine::from_heading_data(
                     heading_data,
                     maybe_current_box_computed_style,
+                    md_style_config,
                 ));
             }
             MdBlockElement::Text(fragments_in_one_line) => {
-                lines.push(StyleUSSpanLine::from_fragments(
+                let line = match StyleUSSpanLine::from_blockquote(
                     fragments_in_one_line,
+                    0,
                     maybe_current_box_computed_style,
-                ))
+                    md_style_config,
+                ) {
+                    Some((line, _)) => line,
+                    None => StyleUSSpanLine::from_fragments(
+                        fragments_in_one_line,
+                        maybe_current_box_computed_style,
+                        md_style_config,
+                    ),
+                };
+                lines.push(line)
             }
             MdBlockElement::SmartList((list_lines, _bullet_kind, _indent)) => {
                 lines += StyleUSSpanLines::from_block_smart_list(
                     list_lines,
                     maybe_current_box_computed_style,
+                    md_style_config,
                 );
             }
             MdBlockElement::CodeBlock(code_block_lines) => {
                 lines += StyleUSSpanLines::from_block_codeblock(
                     code_block_lines,
                     maybe_current_box_computed_style,
+                    md_style_config,
                     maybe_syntect_tuple,
                 );
             }
@@ -388,6 +435,7 @@ impl StyleUSSpan {
     fn format_hyperlink_data(
         link_data: &HyperlinkData,
         maybe_current_box_computed_style: &Option<Style>,
+        md_style_config: &MarkdownStyleConfig,
         hyperlink_type: HyperlinkType,
     ) -> Vec<Self> {
         let link_text = link_data.text.to_string();
@@ -396,11 +444,11 @@ impl StyleUSSpan {
         let base_style = maybe_current_box_computed_style.unwrap_or_default()
             + get_foreground_dim_style();
 
-        let link_text_style =
-            maybe_current_box_computed_style.unwrap_or_default() + get_link_text_style();
+        let link_text_style = maybe_current_box_computed_style.unwrap_or_default()
+            + md_style_config.link_text;
 
-        let link_url_style =
-            maybe_current_box_computed_style.unwrap_or_default() + get_link_url_style();
+        let link_url_style = maybe_current_box_computed_style.unwrap_or_default()
+            + md_style_config.link_url;
 
         vec![
             // [link_text] or ![link_text]
@@ -436,20 +484,45 @@ impl StyleUSSpan {
     pub fn from_fragment(
         fragment: &MdLineFragment,
         maybe_current_box_computed_style: &Option<Style>,
+        md_style_config: &MarkdownStyleConfig,
     ) -> Vec<Self> {
         match fragment {
+            // The first line is painted as `──1.│`, where the `─` leaders (one per col of
+            // indent) show the nesting level, just like an unordered list item. Each span
+            // is as wide as the text it replaces (eg: `  1. `), so nothing is misaligned.
             MdLineFragment::OrderedListBullet {
                 indent,
                 number,
                 is_first_line,
             } => {
-                let bullet =
-                    generate_ordered_list_item_bullet(indent, number, is_first_line);
-                vec![StyleUSSpan::new(
+                let bullet_style = maybe_current_box_computed_style.unwrap_or_default()
+                    + md_style_config.get_list_bullet_style_for_nesting_level(
+                        indent / LIST_PREFIX_BASE_WIDTH,
+                    );
+
+                if !is_first_line {
+                    let bullet =
+                        generate_ordered_list_item_bullet(indent, number, is_first_line);
+                    return vec![StyleUSSpan::new(bullet_style, US::from(bullet))];
+                }
+
+                let mut spans = vec![];
+                if *indent > 0 {
+                    spans.push(StyleUSSpan::new(
+                        bullet_style,
+                        US::from(LIST_SPACE_DISPLAY.repeat(*indent)),
+                    ));
+                }
+                spans.push(StyleUSSpan::new(
                     maybe_current_box_computed_style.unwrap_or_default()
-                        + get_list_bullet_style(),
-                    US::from(bullet),
-                )]
+                        + md_style_config.list_number,
+                    US::from(format!("{number}{PERIOD}")),
+                ));
+                spans.push(StyleUSSpan::new(
+                    bullet_style,
+                    US::from(LIST_SPACE_END_DISPLAY_REST_LINE),
+                ));
+                spans
             }
 
             MdLineFragment::UnorderedListBullet {
@@ -459,7 +532,9 @@ impl StyleUSSpan {
                 let bullet = generate_unordered_list_item_bullet(indent, is_first_line);
                 vec![StyleUSSpan::new(
                     maybe_current_box_computed_style.unwrap_or_default()
-                        + get_list_bullet_style(),
+                        + md_style_config.get_list_bullet_style_for_nesting_level(
+                            indent / LIST_PREFIX_BASE_WIDTH,
+                        ),
                     US::from(bullet),
                 )]
             }
@@ -479,7 +554,7 @@ impl StyleUSSpan {
                     ),
                     StyleUSSpan::new(
                         maybe_current_box_computed_style.unwrap_or_default()
-                            + get_bold_style(),
+                            + md_style_config.bold,
                         US::from(*bold_text),
                     ),
                     StyleUSSpan::new(
@@ -498,7 +573,7 @@ impl StyleUSSpan {
                 ),
                 StyleUSSpan::new(
                     maybe_current_box_computed_style.unwrap_or_default()
-                        + get_italic_style(),
+                        + md_style_config.italic,
                     US::from(*italic_text),
                 ),
                 StyleUSSpan::new(
@@ -516,7 +591,7 @@ impl StyleUSSpan {
                 ),
                 StyleUSSpan::new(
                     maybe_current_box_computed_style.unwrap_or_default()
-                        + get_bold_italic_style(),
+                        + md_style_config.bold_italic,
                     US::from(*bitalic_text),
                 ),
                 StyleUSSpan::new(
@@ -534,7 +609,7 @@ impl StyleUSSpan {
                 ),
                 StyleUSSpan::new(
                     maybe_current_box_computed_style.unwrap_or_default()
-                        + get_inline_code_style(),
+                        + md_style_config.inline_code,
                     US::from(*inline_code_text),
                 ),
                 StyleUSSpan::new(
@@ -547,12 +622,14 @@ impl StyleUSSpan {
             MdLineFragment::Link(link_data) => Self::format_hyperlink_data(
                 link_data,
                 maybe_current_box_computed_style,
+                md_style_config,
                 HyperlinkType::Link,
             ),
 
             MdLineFragment::Image(link_data) => Self::format_hyperlink_data(
                 link_data,
                 maybe_current_box_computed_style,
+                md_style_config,
                 HyperlinkType::Image,
             ),
 
@@ -591,18 +668,88 @@ impl StyleUSSpanLine {
     pub fn from_fragments(
         fragments_in_one_line: &FragmentsInOneLine,
         maybe_current_box_computed_style: &Option<Style>,
+        md_style_config: &MarkdownStyleConfig,
     ) -> Self {
         let mut acc = vec![];
 
         for fragment in fragments_in_one_line.iter() {
-            let vec_spans =
-                StyleUSSpan::from_fragment(fragment, maybe_current_box_computed_style);
+            let vec_spans = StyleUSSpan::from_fragment(
+                fragment,
+                maybe_current_box_computed_style,
+                md_style_config,
+            );
             acc.extend(vec_spans);
         }
 
         List { items: acc }
     }
 
+    /// Returns the line & its nesting level, or [None] if the line isn't part of a
+    /// blockquote. More info in [blockquote].
+    /// - A line that starts w/ `>` markers: each marker is painted as
+    ///   [blockquote_bar_char](MarkdownStyleConfig::blockquote_bar_char) (if it is 1 col
+    ///   wide) w/ the style for its nesting level, and the text after them is painted w/
+    ///   [blockquote](MarkdownStyleConfig::blockquote).
+    /// - Otherwise, if `lazy_nesting_level` isn't `0` (ie, the previous line was part of a
+    ///   blockquote), a line that isn't blank is a lazy continuation of it.
+    pub fn from_blockquote(
+        fragments_in_one_line: &FragmentsInOneLine,
+        lazy_nesting_level: usize,
+        maybe_current_box_computed_style: &Option<Style>,
+        md_style_config: &MarkdownStyleConfig,
+    ) -> Option<(Self, usize)> {
+        let base_style = maybe_current_box_computed_style.unwrap_or_default();
+
+        let Some(prefix) = get_blockquote_prefix(fragments_in_one_line) else {
+            if lazy_nesting_level == 0 || is_blank_line(fragments_in_one_line) {
+                return None;
+            }
+            let mut line = StyleUSSpanLine::from_fragments(
+                fragments_in_one_line,
+                maybe_current_box_computed_style,
+                md_style_config,
+            );
+            line.add_style(md_style_config.blockquote);
+            return Some((line, lazy_nesting_level));
+        };
+
+        let bar_char = {
+            let it = md_style_config.blockquote_bar_char.to_string();
+            match UnicodeString::str_display_width(&it) {
+                1 => it,
+                _ => BLOCKQUOTE_MARKER.to_string(),
+            }
+        };
+
+        let mut line = StyleUSSpanLine::default();
+        let mut nesting_level = 0;
+        for character in prefix.markers.chars() {
+            if character == BLOCKQUOTE_MARKER {
+                line += StyleUSSpan::new(
+                    base_style
+                        + md_style_config
+                            .get_blockquote_bar_style_for_nesting_level(nesting_level),
+                    US::from(bar_char.as_str()),
+                );
+                nesting_level += 1;
+            } else {
+                line += StyleUSSpan::new(base_style, US::from(character.to_string()));
+            }
+        }
+
+        let mut text_fragments = fragments_in_one_line.clone();
+        text_fragments.items[0] = MdLineFragment::Plain(prefix.text);
+        let mut text_line = StyleUSSpanLine::from_fragments(
+            &text_fragments,
+            maybe_current_box_computed_style,
+            md_style_config,
+        );
+        text_line.add_style(md_style_config.blockquote);
+        line += text_line;
+
+        Some((line, prefix.nesting_level))
+    }
+
     /// This is a sample [HeadingData] that needs to be converted into a [StyleUSSpanLine].
     ///
     /// ```text
@@ -618,8 +765,8 @@ impl StyleUSSpanLine {
     pub fn from_heading_data(
         heading_data: &HeadingData,
         maybe_current_box_computed_style: &Option<Style>,
+        md_style_config: &MarkdownStyleConfig,
     ) -> Self {
-        let mut color_wheel = ColorWheel::from_heading_data(heading_data);
         let mut line = StyleUSSpanLine::default();
 
         let heading_level_span: StyleUSSpan = {
@@ -633,8 +780,18 @@ impl StyleUSSpanLine {
             StyleUSSpan::new(my_style, heading_level)
         };
 
-        let heading_text_span: StyleUSSpanLine = {
+        // A heading level that has its own style in the config is painted w/ it, instead of
+        // the color wheel.
+        let heading_text_span: StyleUSSpanLine = if let Some(heading_style) =
+            md_style_config.get_heading_style(heading_data.level.into())
+        {
+            list![StyleUSSpan::new(
+                maybe_current_box_computed_style.unwrap_or_default() + heading_style,
+                US::from(heading_data.text),
+            )]
+        } else {
             let heading_text = UnicodeString::from(heading_data.text);
+            let mut color_wheel = ColorWheel::from_heading_data(heading_data);
             let styled_texts = color_wheel.colorize_into_styled_texts(
                 &heading_text,
                 GradientGenerationPolicy::ReuseExistingGradientAndResetIndex,
@@ -682,7 +839,11 @@ mod tests_style_us_span_lines_from {
             let style = style! {
                 color_bg: TuiColor::Basic(ANSIBasicColor::Red)
             };
-            let actual = StyleUSSpan::from_fragment(&fragment, &Some(style));
+            let actual = StyleUSSpan::from_fragment(
+                &fragment,
+                &Some(style),
+                &MarkdownStyleConfig::default(),
+            );
 
             assert_eq2!(actual.len(), 1);
 
@@ -703,7 +864,11 @@ mod tests_style_us_span_lines_from {
             let style = style! {
                 color_bg: TuiColor::Basic(ANSIBasicColor::Red)
             };
-            let actual = StyleUSSpan::from_fragment(&fragment, &Some(style));
+            let actual = StyleUSSpan::from_fragment(
+                &fragment,
+                &Some(style),
+                &MarkdownStyleConfig::default(),
+            );
 
             assert_eq2!(actual.len(), 1);
 
@@ -727,7 +892,11 @@ mod tests_style_us_span_lines_from {
             let style = style! {
                 color_bg: TuiColor::Basic(ANSIBasicColor::Red)
             };
-            let actual = StyleUSSpan::from_fragment(&fragment, &Some(style));
+            let actual = StyleUSSpan::from_fragment(
+                &fragment,
+                &Some(style),
+                &MarkdownStyleConfig::default(),
+            );
 
             assert_eq2!(actual.len(), 6);
 
@@ -762,7 +931,11 @@ mod tests_style_us_span_lines_from {
             let style = style! {
                 color_bg: TuiColor::Basic(ANSIBasicColor::Red)
             };
-            let actual = StyleUSSpan::from_fragment(&fragment, &Some(style));
+            let actual = StyleUSSpan::from_fragment(
+                &fragment,
+                &Some(style),
+                &MarkdownStyleConfig::default(),
+            );
 
             assert_eq2!(actual.len(), 6);
 
@@ -901,7 +1074,11 @@ mod tests_style_us_span_lines_from {
                 color_bg: TuiColor::Basic(ANSIBasicColor::Red)
             };
 
-            let actual = StyleUSSpan::from_fragment(&fragment, &Some(style));
+            let actual = StyleUSSpan::from_fragment(
+                &fragment,
+                &Some(style),
+                &MarkdownStyleConfig::default(),
+            );
 
             // println!("{}", List::from(actual.clone())..pretty_print_debug());
 
@@ -926,7 +1103,11 @@ mod tests_style_us_span_lines_from {
                 color_bg: TuiColor::Basic(ANSIBasicColor::Red)
             };
 
-            let actual = StyleUSSpan::from_fragment(&fragment, &Some(style));
+            let actual = StyleUSSpan::from_fragment(
+                &fragment,
+                &Some(style),
+                &MarkdownStyleConfig::default(),
+            );
 
             // println!("{}", List::from(actual.clone())..pretty_print_debug());
 
@@ -951,7 +1132,11 @@ mod tests_style_us_span_lines_from {
                 color_bg: TuiColor::Basic(ANSIBasicColor::Red)
             };
 
-            let actual = StyleUSSpan::from_fragment(&fragment, &Some(style));
+            let actual = StyleUSSpan::from_fragment(
+                &fragment,
+                &Some(style),
+                &MarkdownStyleConfig::default(),
+            );
 
             // println!("{}", List::from(actual.clone())..pretty_print_debug());
 
@@ -976,7 +1161,11 @@ mod tests_style_us_span_lines_from {
                 color_bg: TuiColor::Basic(ANSIBasicColor::Red)
             };
 
-            let actual = StyleUSSpan::from_fragment(&fragment, &Some(style));
+            let actual = StyleUSSpan::from_fragment(
+                &fragment,
+                &Some(style),
+                &MarkdownStyleConfig::default(),
+            );
 
             // println!("{}", List::from(actual.clone())..pretty_print_debug());
 
@@ -1000,7 +1189,11 @@ mod tests_style_us_span_lines_from {
             let style = style! {
                 color_bg: TuiColor::Basic(ANSIBasicColor::Red)
             };
-            let actual = StyleUSSpan::from_fragment(&fragment, &Some(style));
+            let actual = StyleUSSpan::from_fragment(
+                &fragment,
+                &Some(style),
+                &MarkdownStyleConfig::default(),
+            );
             let expected = vec![StyleUSSpan::new(
                 style + get_foreground_style(),
                 US::from("Foobar"),
@@ -1020,7 +1213,12 @@ mod tests_style_us_span_lines_from {
             let style = style! {
                 color_bg: TuiColor::Basic(ANSIBasicColor::Red)
             };
-            let lines = StyleUSSpanLines::from_block(&tags, &Some(style), None);
+            let lines = StyleUSSpanLines::from_block(
+                &tags,
+                &Some(style),
+                &MarkdownStyleConfig::default(),
+                None,
+            );
             let line_0 = &lines.items[0];
             let mut iter = line_0.items.iter();
 
@@ -1075,7 +1273,12 @@ mod tests_style_us_span_lines_from {
             let style = style! {
                 color_bg: TuiColor::Basic(ANSIBasicColor::Red)
             };
-            let lines = StyleUSSpanLines::from_block(&title, &Some(style), None);
+            let lines = StyleUSSpanLines::from_block(
+                &title,
+                &Some(style),
+                &MarkdownStyleConfig::default(),
+                None,
+            );
             // println!("{}", lines..pretty_print_debug());
 
             let line_0 = &lines.items[0];
@@ -1126,8 +1329,12 @@ mod tests_style_us_span_lines_from {
                 color_bg: TuiColor::Basic(ANSIBasicColor::Red)
             };
 
-            let lines =
-                StyleUSSpanLines::from_block(&codeblock_block, &Some(style), None);
+            let lines = StyleUSSpanLines::from_block(
+                &codeblock_block,
+                &Some(style),
+                &MarkdownStyleConfig::default(),
+                None,
+            );
 
             let line_0 = &lines.items[0];
             // println!("{}", line_0..pretty_print_debug());
@@ -1169,16 +1376,25 @@ mod tests_style_us_span_lines_from {
             let ol_block_1 = &doc[0];
             {
                 // println!("{:#?}", ol_block_1);
-                let lines = StyleUSSpanLines::from_block(ol_block_1, &Some(style), None);
+                let lines = StyleUSSpanLines::from_block(
+                    ol_block_1,
+                    &Some(style),
+                    &MarkdownStyleConfig::default(),
+                    None,
+                );
 
                 let line_0 = &lines.items[0];
                 // println!("{}", line_0..pretty_print_debug());
                 assert_eq2!(
                     line_0.items[0],
-                    StyleUSSpan::new(style + get_list_bullet_style(), US::from("100.│"))
+                    StyleUSSpan::new(style + get_list_number_style(), US::from("100."))
                 );
                 assert_eq2!(
                     line_0.items[1],
+                    StyleUSSpan::new(style + get_list_bullet_style(), US::from("│"))
+                );
+                assert_eq2!(
+                    line_0.items[2],
                     StyleUSSpan::new(style + get_foreground_style(), US::from("Foo"),)
                 );
             }
@@ -1186,16 +1402,25 @@ mod tests_style_us_span_lines_from {
             let ol_block_2 = &doc[1];
             {
                 // println!("{:#?}", ol_block_2);
-                let lines = StyleUSSpanLines::from_block(ol_block_2, &Some(style), None);
+                let lines = StyleUSSpanLines::from_block(
+                    ol_block_2,
+                    &Some(style),
+                    &MarkdownStyleConfig::default(),
+                    None,
+                );
 
                 let line_0 = &lines.items[0];
                 // println!("{}", line_0..pretty_print_debug());
                 assert_eq2!(
                     line_0.items[0],
-                    StyleUSSpan::new(style + get_list_bullet_style(), US::from("200.│"))
+                    StyleUSSpan::new(style + get_list_number_style(), US::from("200."))
                 );
                 assert_eq2!(
                     line_0.items[1],
+                    StyleUSSpan::new(style + get_list_bullet_style(), US::from("│"))
+                );
+                assert_eq2!(
+                    line_0.items[2],
                     StyleUSSpan::new(style + get_foreground_style(), US::from("Bar"),)
                 );
             }
@@ -1214,7 +1439,12 @@ mod tests_style_us_span_lines_from {
             // First smart list.
             {
                 let ul_block_0 = &doc[0];
-                let lines = StyleUSSpanLines::from_block(ul_block_0, &Some(style), None);
+                let lines = StyleUSSpanLines::from_block(
+                    ul_block_0,
+                    &Some(style),
+                    &MarkdownStyleConfig::default(),
+                    None,
+                );
                 let line_0 = &lines.items[0];
                 assert_eq2!(
                     line_0.items[0],
@@ -1229,7 +1459,12 @@ mod tests_style_us_span_lines_from {
             // Second smart list.
             {
                 let ul_block_1 = &doc[1];
-                let lines = StyleUSSpanLines::from_block(ul_block_1, &Some(style), None);
+                let lines = StyleUSSpanLines::from_block(
+                    ul_block_1,
+                    &Some(style),
+                    &MarkdownStyleConfig::default(),
+                    None,
+                );
                 let line_0 = &lines.items[0];
                 assert_eq2!(
                     line_0.items[0],
@@ -1244,6 +1479,76 @@ mod tests_style_us_span_lines_from {
             Ok(())
         }
 
+        #[test]
+        fn test_block_nested_lists() -> CommonResult<()> {
+            let style = style! {
+                color_bg: TuiColor::Basic(ANSIBasicColor::Red)
+            };
+            // Mixed ordered & unordered nesting, a task list item, and a loose list (w/ a
+            // blank line between the items).
+            let input_lines = [
+                "- ul1",
+                "  1. ol1",
+                "     ol1 line 2",
+                "    - [x] done",
+                "",
+                "- ul2",
+                "10. ol10",
+            ];
+            let input = format!("{}\n", input_lines.join("\n"));
+            let (remainder, doc) = parse_markdown(&input).unwrap();
+            assert_eq2!(remainder, "");
+
+            let lines = StyleUSSpanLines::from_document(
+                &doc,
+                &Some(style),
+                &MarkdownStyleConfig::default(),
+                None,
+            );
+
+            // Each line is as wide as the text it replaces, so nothing is misaligned.
+            assert_eq2!(lines.len(), input_lines.len());
+            for (line, input_line) in lines.iter().zip(input_lines.iter()) {
+                assert_eq2!(
+                    line.display_width(),
+                    ch!(UnicodeString::str_display_width(input_line))
+                );
+            }
+
+            // Ordered list item nested 1 level deep.
+            let line_1 = &lines[1];
+            assert_eq2!(
+                line_1.items[0],
+                StyleUSSpan::new(
+                    style + get_list_bullet_style_for_nesting_level(1),
+                    US::from("──")
+                )
+            );
+            assert_eq2!(
+                line_1.items[1],
+                StyleUSSpan::new(style + get_list_number_style(), US::from("1."))
+            );
+
+            // Task list item nested 2 levels deep.
+            let line_3 = &lines[3];
+            assert_eq2!(
+                line_3.items[0],
+                StyleUSSpan::new(
+                    style + get_list_bullet_style_for_nesting_level(2),
+                    US::from("─────┤")
+                )
+            );
+            assert_eq2!(
+                line_3.items[1],
+                StyleUSSpan::new(
+                    style + get_checkbox_checked_style(),
+                    US::from(CHECKED_OUTPUT)
+                )
+            );
+
+            Ok(())
+        }
+
         #[test]
         fn test_block_text() {
             let text_block = MdBlockElement::Text(list![MdLineFragment::Plain("Foobar")]);
@@ -1251,7 +1556,12 @@ mod tests_style_us_span_lines_from {
                 color_bg: TuiColor::Basic(ANSIBasicColor::Red)
             };
 
-            let lines = StyleUSSpanLines::from_block(&text_block, &Some(style), None);
+            let lines = StyleUSSpanLines::from_block(
+                &text_block,
+                &Some(style),
+                &MarkdownStyleConfig::default(),
+                None,
+            );
             // println!("{}", lines..pretty_print_debug());
 
             let line_0 = &lines.items[0];
@@ -1261,6 +1571,43 @@ mod tests_style_us_span_lines_from {
             assert_eq2!(style, &(*style + get_foreground_style()));
         }
 
+        #[test]
+        fn test_block_blockquote() {
+            let md_style_config = MarkdownStyleConfig::default();
+            let (_, document) = parse_markdown("> a\n>> b\nlazy\n\nafter\n").unwrap();
+            let lines =
+                StyleUSSpanLines::from_document(&document, &None, &md_style_config, None);
+            let get_texts = |line: &StyleUSSpanLine| -> Vec<String> {
+                line.iter().map(|span| span.text.string.clone()).collect()
+            };
+
+            // The `>` markers are replaced w/ bars (one col each, like in the source).
+            assert_eq2!(get_texts(&lines.items[0]), vec!["┃", " ", "a"]);
+            assert_eq2!(get_texts(&lines.items[1]), vec!["┃", "┃", " ", "b"]);
+            assert_eq2!(
+                lines.items[1].items[1].style,
+                Style::default()
+                    + md_style_config.get_blockquote_bar_style_for_nesting_level(1)
+            );
+            assert_eq2!(
+                lines.items[1].items[3].style,
+                get_foreground_style() + md_style_config.blockquote
+            );
+
+            // Lazy continuation.
+            assert_eq2!(get_texts(&lines.items[2]), vec!["lazy"]);
+            assert_eq2!(
+                lines.items[2].items[0].style,
+                get_foreground_style() + md_style_config.blockquote
+            );
+
+            // A blank line ends the blockquote.
+            assert_eq2!(
+                lines.items[4].items[0].style,
+                Style::default() + get_foreground_style()
+            );
+        }
+
         #[test]
         fn test_block_heading() {
             let heading_block = MdBlockElement::Heading(HeadingData {
@@ -1271,7 +1618,12 @@ mod tests_style_us_span_lines_from {
                 color_bg: TuiColor::Basic(ANSIBasicColor::Red)
             });
 
-            let lines = StyleUSSpanLines::from_block(&heading_block, &maybe_style, None);
+            let lines = StyleUSSpanLines::from_block(
+                &heading_block,
+                &maybe_style,
+                &MarkdownStyleConfig::default(),
+                None,
+            );
             // println!("{}", lines..pretty_print_debug());
 
             // There should just be 1 line.
@@ -1302,5 +1654,55 @@ mod tests_style_us_span_lines_from {
                 assert_eq2!(span.style.color_fg.is_some(), true);
             }
         }
+
+        #[test]
+        fn test_block_heading_w_style_config() {
+            let heading_block = MdBlockElement::Heading(HeadingData {
+                level: HeadingLevel::Heading2,
+                text: "Foobar",
+            });
+            let style = style! {
+                color_bg: TuiColor::Basic(ANSIBasicColor::Red)
+            };
+            let heading_style = style! {
+                attrib: [bold]
+                color_fg: TuiColor::Basic(ANSIBasicColor::Blue)
+            };
+            let bold_style = style! {
+                color_fg: TuiColor::Basic(ANSIBasicColor::Green)
+            };
+            let md_style_config = MarkdownStyleConfig {
+                bold: bold_style,
+                ..Default::default()
+            }
+            .with_heading_style(2, heading_style);
+
+            // The heading text is painted w/ the style from the config, instead of the color
+            // wheel.
+            let lines = StyleUSSpanLines::from_block(
+                &heading_block,
+                &Some(style),
+                &md_style_config,
+                None,
+            );
+            let spans_in_line = &lines.items[0].items;
+            assert_eq2!(spans_in_line.len(), 2);
+            assert_eq2!(spans_in_line[0].text.string, "## ");
+            assert_eq2!(
+                spans_in_line[1],
+                StyleUSSpan::new(style + heading_style, US::from("Foobar"))
+            );
+
+            // Other elements pick up the overridden styles too.
+            let actual = StyleUSSpan::from_fragment(
+                &MdLineFragment::Bold("Foobar"),
+                &Some(style),
+                &md_style_config,
+            );
+            assert_eq2!(
+                actual[1],
+                StyleUSSpan::new(style + bold_style, US::from("Foobar"))
+            );
+        }
     }
 }
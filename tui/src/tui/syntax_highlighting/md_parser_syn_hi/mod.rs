@@ -23,9 +23,11 @@
 //! 3. [crate::editor] - Responsible for displaying the [crate::MdDocument] to the user.
 
 // Attach.
+pub mod md_parser_style_config;
 pub mod md_parser_stylesheet;
 pub mod md_parser_syn_hi_impl;
 
 // Re-export.
+pub use md_parser_style_config::*;
 pub use md_parser_stylesheet::*;
 pub use md_parser_syn_hi_impl::*;
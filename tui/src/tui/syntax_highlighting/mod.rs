@@ -17,6 +17,7 @@
 
 // Attach sources.
 pub mod intermediate_types;
+pub mod line_comment_prefix;
 pub mod md_parser_syn_hi;
 pub mod pattern_matcher;
 pub mod r3bl_syntect_theme;
@@ -24,6 +25,7 @@ pub mod syntect_to_styled_text_conversion;
 
 // Re-export
 pub use intermediate_types::*;
+pub use line_comment_prefix::*;
 pub use md_parser_syn_hi::*;
 pub use pattern_matcher::*;
 pub use r3bl_syntect_theme::*;
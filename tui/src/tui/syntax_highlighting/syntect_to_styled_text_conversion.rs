@@ -27,6 +27,9 @@
 //! This requires the conversion code to perform the following steps:
 //! 1. Convert the syntect [SyntectStyleStrSpanLine] into a [StyleUSSpanLine].
 //! 2. Then convert [StyleUSSpanLine] into a [StyledTexts].
+//!
+//! Once a line is clipped into a [StyledTexts], [extend_bg_to_line_end] can pad it so that
+//! its background color reaches the right edge of the box.
 
 use r3bl_rs_utils_core::*;
 use syntect::parsing::SyntaxSet;
@@ -61,6 +64,43 @@ pub fn from_syntect_to_tui(
     it
 }
 
+/// Pads `styled_texts` w/ spaces in the `line_end_style` (usually the style of the last span
+/// of the line before it was clipped), so that its background color extends to
+/// `max_display_col_count`. This way a highlighted region (eg: a code block) is painted as
+/// a rectangle instead of having a ragged right edge. `max_display_col_count` should be
+/// the width that is available to the component (eg: [EditorEngine::viewport_width]), not
+/// the terminal width. Nothing is padded if `line_end_style` has no background color.
+pub fn extend_bg_to_line_end(
+    styled_texts: &mut StyledTexts,
+    line_end_style: Style,
+    max_display_col_count: ChUnit,
+) {
+    if line_end_style.color_bg.is_none() {
+        return;
+    }
+    let display_width = styled_texts.display_width();
+    if display_width >= max_display_col_count {
+        return;
+    }
+    let padding = SPACER.repeat(ch!(@to_usize max_display_col_count - display_width));
+    *styled_texts += styled_text!(@style: line_end_style, @text: padding);
+}
+
+/// Adds the `current_line_style` (see [EditorEngineConfig::highlight_current_line]) to the
+/// style of each span, so that the foreground colors of the syntax highlighting are kept,
+/// and then pads `styled_texts` w/ spaces in the `current_line_style` up to
+/// `max_display_col_count`, so that the whole row of the viewport is highlighted.
+pub fn apply_current_line_style(
+    styled_texts: &mut StyledTexts,
+    current_line_style: Style,
+    max_display_col_count: ChUnit,
+) {
+    for StyledText(style, _) in styled_texts.iter_mut() {
+        *style += current_line_style;
+    }
+    extend_bg_to_line_end(styled_texts, current_line_style, max_display_col_count);
+}
+
 mod syntect_support {
     use super::*;
 
@@ -99,3 +139,61 @@ mod syntect_support {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use r3bl_rs_utils_macro::style;
+
+    use super::*;
+
+    #[test]
+    fn test_extend_bg_to_line_end() {
+        let bg_style = style! { color_bg: TuiColor::Basic(ANSIBasicColor::Blue) };
+
+        // Padded up to the given width.
+        let mut styled_texts = styled_texts! {
+            styled_text! { @style: Style::default(), @text: "fn" },
+            styled_text! { @style: bg_style, @text: " main" },
+        };
+        extend_bg_to_line_end(&mut styled_texts, bg_style, ch!(10));
+        assert_eq2!(styled_texts.display_width(), ch!(10));
+        assert_eq2!(styled_texts.len(), 3);
+        assert_eq2!(styled_texts[2].get_style(), &bg_style);
+
+        // An empty line is padded too.
+        let mut styled_texts = StyledTexts::default();
+        extend_bg_to_line_end(&mut styled_texts, bg_style, ch!(4));
+        assert_eq2!(styled_texts.display_width(), ch!(4));
+
+        // No background color.
+        let mut styled_texts = styled_texts! {
+            styled_text! { @style: Style::default(), @text: "text" },
+        };
+        extend_bg_to_line_end(&mut styled_texts, Style::default(), ch!(10));
+        assert_eq2!(styled_texts.display_width(), ch!(4));
+    }
+
+    #[test]
+    fn test_apply_current_line_style() {
+        let fg_style = style! { color_fg: TuiColor::Basic(ANSIBasicColor::Red) };
+        let current_line_style =
+            style! { color_bg: TuiColor::Basic(ANSIBasicColor::DarkGrey) };
+
+        let mut styled_texts = styled_texts! {
+            styled_text! { @style: fg_style, @text: "fn" },
+            styled_text! { @style: Style::default(), @text: " main" },
+        };
+        apply_current_line_style(&mut styled_texts, current_line_style, ch!(10));
+
+        // The foreground color is kept & the background is added.
+        assert_eq2!(styled_texts.display_width(), ch!(10));
+        assert_eq2!(styled_texts.len(), 3);
+        assert_eq2!(styled_texts[0].get_style().color_fg, fg_style.color_fg);
+        for styled_text in styled_texts.iter() {
+            assert_eq2!(
+                styled_text.get_style().color_bg,
+                current_line_style.color_bg
+            );
+        }
+    }
+}
@@ -0,0 +1,432 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::{fmt::Debug, sync::Arc};
+
+use async_trait::async_trait;
+use r3bl_redux::*;
+use r3bl_rs_utils_core::*;
+use tokio::sync::RwLock;
+
+use crate::*;
+
+/// This is a reusable [Component] that paints a strip of tab labels in the first row of its
+/// box, and the child [Component] of the active tab in the rest of it.
+/// - Only the active child is rendered & gets the input events, except for Ctrl+PageUp,
+///   Ctrl+PageDown & Alt+`1`-`9`, which always switch tabs (even if the child is an
+///   [EditorComponent], which consumes all the plain keys). If the child doesn't consume an
+///   event, then Left & Right (or the number keys `1`-`9`) switch tabs too.
+/// - The [TabsComponent] is the one that should get focus in the [ComponentRegistry]. While it
+///   delegates to the active child, it lends its focus to the child's `id`, so the child
+///   renders & behaves as if it had focus (eg: the [EditorComponent] paints its caret).
+/// - The `on_tab_change_handler` is called w/ the index of the new active tab, which is handy
+///   for apps that lazy-load the content of a tab.
+#[derive(Clone)]
+pub struct TabsComponent<S, A>
+where
+    S: Debug + Default + Clone + PartialEq + Sync + Send,
+    A: Debug + Default + Clone + Sync + Send,
+{
+    pub id: FlexBoxId,
+    pub tabs: Vec<Tab<S, A>>,
+    pub active_tab_index: usize,
+    pub config: TabsComponentConfig,
+    pub on_tab_change_handler: Option<OnTabChangeFn<S, A>>,
+}
+
+/// A label in the tab strip, and the child [Component] that is shown when it is active.
+#[derive(Clone)]
+pub struct Tab<S, A>
+where
+    S: Debug + Default + Clone + PartialEq + Sync + Send,
+    A: Debug + Default + Clone + Sync + Send,
+{
+    pub label: String,
+    pub child: SharedComponent<S, A>,
+}
+
+pub type OnTabChangeFn<S, A> = fn(&SharedStore<S, A>, FlexBoxId, usize);
+
+/// Return the index of the tab to switch to for the `input_event`, if any. Left & Right wrap
+/// around, and the number keys `1`-`9` pick a tab directly.
+pub fn get_tab_index_for_input_event(
+    active_tab_index: usize,
+    tab_count: usize,
+    input_event: &InputEvent,
+) -> Option<usize> {
+    let InputEvent::Keyboard(KeyPress::Plain { key }) = input_event else {
+        return None;
+    };
+    get_tab_index_for_key(active_tab_index, tab_count, key)
+}
+
+/// Same as [get_tab_index_for_input_event], but for the modifier-qualified bindings, which
+/// are checked before the active child gets the `input_event`. Ctrl+PageUp & Ctrl+PageDown
+/// wrap around, and Alt+`1`-`9` pick a tab directly.
+pub fn get_tab_index_for_modified_input_event(
+    active_tab_index: usize,
+    tab_count: usize,
+    input_event: &InputEvent,
+) -> Option<usize> {
+    let InputEvent::Keyboard(KeyPress::WithModifiers { key, mask }) = input_event else {
+        return None;
+    };
+
+    let key = match key {
+        Key::SpecialKey(SpecialKey::PageUp) if *mask == ModifierKeysMask::CTRL => {
+            Key::SpecialKey(SpecialKey::Left)
+        }
+        Key::SpecialKey(SpecialKey::PageDown) if *mask == ModifierKeysMask::CTRL => {
+            Key::SpecialKey(SpecialKey::Right)
+        }
+        Key::Character(_) if *mask == ModifierKeysMask::ALT => *key,
+        _ => return None,
+    };
+    get_tab_index_for_key(active_tab_index, tab_count, &key)
+}
+
+fn get_tab_index_for_key(
+    active_tab_index: usize,
+    tab_count: usize,
+    key: &Key,
+) -> Option<usize> {
+    if tab_count == 0 {
+        return None;
+    }
+
+    match key {
+        Key::SpecialKey(SpecialKey::Left) => {
+            Some((active_tab_index + tab_count - 1) % tab_count)
+        }
+        Key::SpecialKey(SpecialKey::Right) => Some((active_tab_index + 1) % tab_count),
+        Key::Character(character) => match character.to_digit(10) {
+            Some(digit @ 1..=9) if (digit as usize) <= tab_count => {
+                Some(digit as usize - 1)
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+pub mod tabs_component_impl {
+    use super::*;
+
+    #[async_trait]
+    impl<S, A> Component<S, A> for TabsComponent<S, A>
+    where
+        S: Debug + Default + Clone + PartialEq + Sync + Send + 'static,
+        A: Debug + Default + Clone + Sync + Send + 'static,
+    {
+        /// Reset the active child (not all of them).
+        fn reset(&mut self) {
+            if let Some(tab) = self.tabs.get(self.active_tab_index) {
+                if let Ok(mut child) = tab.child.try_write() {
+                    child.reset();
+                }
+            }
+        }
+
+        fn get_id(&self) -> FlexBoxId { self.id }
+
+        async fn handle_event(
+            &mut self,
+            args: ComponentScopeArgs<'_, S, A>,
+            input_event: &InputEvent,
+        ) -> CommonResult<EventPropagation> {
+            let ComponentScopeArgs {
+                shared_global_data,
+                shared_store,
+                state,
+                component_registry,
+                window_size,
+            } = args;
+
+            // The modifier-qualified bindings switch tabs before the active child sees the
+            // event.
+            if let Some(new_tab_index) = get_tab_index_for_modified_input_event(
+                self.active_tab_index,
+                self.tabs.len(),
+                input_event,
+            ) {
+                self.switch_to_tab(shared_store, new_tab_index);
+                return Ok(EventPropagation::ConsumedRender);
+            }
+
+            // Give the active child the first chance to handle the event.
+            if let Some(tab) = self.tabs.get(self.active_tab_index) {
+                let child_id = tab.child.read().await.get_id();
+                let had_focus = lend_focus(component_registry, self.id, child_id);
+                let result = tab
+                    .child
+                    .write()
+                    .await
+                    .handle_event(
+                        ComponentScopeArgs {
+                            shared_global_data,
+                            shared_store,
+                            state,
+                            component_registry: &mut *component_registry,
+                            window_size,
+                        },
+                        input_event,
+                    )
+                    .await;
+                take_back_focus(component_registry, had_focus, self.id, child_id);
+
+                let event_propagation = result?;
+                if event_propagation != EventPropagation::Propagate {
+                    return Ok(event_propagation);
+                }
+            }
+
+            // Then switch tabs.
+            match get_tab_index_for_input_event(
+                self.active_tab_index,
+                self.tabs.len(),
+                input_event,
+            ) {
+                Some(new_tab_index) => {
+                    self.switch_to_tab(shared_store, new_tab_index);
+                    Ok(EventPropagation::ConsumedRender)
+                }
+                None => Ok(EventPropagation::Propagate),
+            }
+        }
+
+        async fn render(
+            &mut self,
+            args: ComponentScopeArgs<'_, S, A>,
+            current_box: &FlexBox,
+            surface_bounds: SurfaceBounds,
+        ) -> CommonResult<RenderPipeline> {
+            throws_with_return!({
+                let ComponentScopeArgs {
+                    shared_global_data,
+                    shared_store,
+                    state,
+                    component_registry,
+                    window_size,
+                } = args;
+
+                let mut render_pipeline = render_pipeline!();
+                render_pipeline.push(ZOrder::Normal, self.render_tab_strip(current_box));
+
+                // Render the active child below the tab strip.
+                if let Some(tab) = self.tabs.get(self.active_tab_index) {
+                    let child_id = tab.child.read().await.get_id();
+                    let child_box = make_child_box(current_box, child_id);
+                    let had_focus = lend_focus(component_registry, self.id, child_id);
+                    let result = tab
+                        .child
+                        .write()
+                        .await
+                        .render(
+                            ComponentScopeArgs {
+                                shared_global_data,
+                                shared_store,
+                                state,
+                                component_registry: &mut *component_registry,
+                                window_size,
+                            },
+                            &child_box,
+                            surface_bounds,
+                        )
+                        .await;
+                    take_back_focus(component_registry, had_focus, self.id, child_id);
+                    render_pipeline += result?;
+                }
+
+                render_pipeline
+            });
+        }
+
+        fn handle_resize(&mut self, new_size: Size) {
+            for tab in self.tabs.iter() {
+                if let Ok(mut child) = tab.child.try_write() {
+                    child.handle_resize(new_size);
+                }
+            }
+        }
+    }
+
+    impl<S, A> TabsComponent<S, A>
+    where
+        S: Debug + Default + Clone + PartialEq + Sync + Send,
+        A: Debug + Default + Clone + Sync + Send,
+    {
+        pub fn get_active_tab(&self) -> Option<&Tab<S, A>> {
+            self.tabs.get(self.active_tab_index)
+        }
+
+        /// The `on_tab_change_handler` is only called if the active tab changes.
+        fn switch_to_tab(
+            &mut self,
+            shared_store: &SharedStore<S, A>,
+            new_tab_index: usize,
+        ) {
+            if new_tab_index != self.active_tab_index {
+                self.active_tab_index = new_tab_index;
+                if let Some(on_tab_change_handler) = self.on_tab_change_handler {
+                    on_tab_change_handler(shared_store, self.id, new_tab_index);
+                }
+            }
+        }
+
+        fn render_tab_strip(&self, current_box: &FlexBox) -> RenderOps {
+            let origin_pos = current_box.style_adjusted_origin_pos;
+            let mut remaining_col_count =
+                current_box.style_adjusted_bounds_size.col_count;
+
+            let mut render_ops = render_ops!();
+            render_ops.push(RenderOp::MoveCursorPositionRelTo(
+                origin_pos,
+                position! { col_index: 0, row_index: 0 },
+            ));
+
+            for (tab_index, tab) in self.tabs.iter().enumerate() {
+                let label_text = self.config.get_tab_label_text(tab_index, &tab.label);
+                let label_text = UnicodeString::from(label_text);
+                let clipped_label_text =
+                    label_text.clip_to_width(ch!(0), remaining_col_count);
+                remaining_col_count -=
+                    ch!(UnicodeString::str_display_width(clipped_label_text));
+
+                let style = match tab_index == self.active_tab_index {
+                    true => self.config.active_tab_style,
+                    false => self.config.inactive_tab_style,
+                };
+                render_ops.push(RenderOp::ApplyColors(Some(style)));
+                render_ops.push(RenderOp::PaintTextWithAttributes(
+                    clipped_label_text.to_string(),
+                    Some(style),
+                ));
+                render_ops.push(RenderOp::ResetColor);
+            }
+
+            // Fill the rest of the strip.
+            if let Some(strip_style) = self.config.maybe_strip_style {
+                render_ops.push(RenderOp::ApplyColors(Some(strip_style)));
+                render_ops.push(RenderOp::PaintTextWithAttributes(
+                    SPACER.repeat(ch!(@to_usize remaining_col_count)),
+                    Some(strip_style),
+                ));
+                render_ops.push(RenderOp::ResetColor);
+            }
+
+            render_ops
+        }
+    }
+
+    /// The child gets all of the box, except for the row w/ the tab strip.
+    fn make_child_box(current_box: &FlexBox, child_id: FlexBoxId) -> FlexBox {
+        let mut child_box = *current_box;
+        child_box.id = child_id;
+        child_box.origin_pos.row_index += 1;
+        child_box.bounds_size.row_count -= 1;
+        child_box.style_adjusted_origin_pos.row_index += 1;
+        child_box.style_adjusted_bounds_size.row_count -= 1;
+        child_box
+    }
+
+    /// If the [TabsComponent] has focus, then hand it to the child. Returns whether it did.
+    fn lend_focus<S, A>(
+        component_registry: &mut ComponentRegistry<S, A>,
+        self_id: FlexBoxId,
+        child_id: FlexBoxId,
+    ) -> bool
+    where
+        S: Debug + Default + Clone + PartialEq + Sync + Send,
+        A: Debug + Default + Clone + Sync + Send,
+    {
+        let has_focus = component_registry.has_focus.does_id_have_focus(self_id);
+        if has_focus {
+            component_registry.has_focus.set_id(child_id);
+        }
+        has_focus
+    }
+
+    /// Undo [lend_focus], even if the child has set a modal id in the meantime.
+    fn take_back_focus<S, A>(
+        component_registry: &mut ComponentRegistry<S, A>,
+        had_focus: bool,
+        self_id: FlexBoxId,
+        child_id: FlexBoxId,
+    ) where
+        S: Debug + Default + Clone + PartialEq + Sync + Send,
+        A: Debug + Default + Clone + Sync + Send,
+    {
+        if had_focus {
+            component_registry.has_focus.replace_id(child_id, self_id);
+        }
+    }
+}
+pub use tabs_component_impl::*;
+
+mod constructor {
+    use super::*;
+
+    impl<S, A> TabsComponent<S, A>
+    where
+        S: Debug + Default + Clone + PartialEq + Sync + Send,
+        A: Debug + Default + Clone + Sync + Send,
+    {
+        /// The on_tab_change handler is a lambda that is called w/ the index of the new
+        /// active tab when the user switches tabs.
+        pub fn new(
+            id: FlexBoxId,
+            tabs: Vec<Tab<S, A>>,
+            config: TabsComponentConfig,
+            on_tab_change: OnTabChangeFn<S, A>,
+        ) -> Self {
+            Self {
+                id,
+                tabs,
+                active_tab_index: 0,
+                config,
+                on_tab_change_handler: Some(on_tab_change),
+            }
+        }
+
+        pub fn new_shared(
+            id: FlexBoxId,
+            tabs: Vec<Tab<S, A>>,
+            config: TabsComponentConfig,
+            on_tab_change: OnTabChangeFn<S, A>,
+        ) -> Arc<RwLock<Self>> {
+            Arc::new(RwLock::new(TabsComponent::new(
+                id,
+                tabs,
+                config,
+                on_tab_change,
+            )))
+        }
+    }
+
+    impl<S, A> Tab<S, A>
+    where
+        S: Debug + Default + Clone + PartialEq + Sync + Send,
+        A: Debug + Default + Clone + Sync + Send,
+    {
+        pub fn new(label: &str, child: SharedComponent<S, A>) -> Self {
+            Self {
+                label: label.to_string(),
+                child,
+            }
+        }
+    }
+}
@@ -0,0 +1,56 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use r3bl_rs_utils_core::*;
+
+use crate::*;
+
+/// Configure how the [TabsComponent] renders its tab strip.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TabsComponentConfig {
+    /// Prefix each label w/ the number key that switches to it (eg: `1:Files`).
+    pub show_tab_numbers: bool,
+    pub active_tab_style: Style,
+    pub inactive_tab_style: Style,
+    /// This fills the rest of the strip after the last label.
+    pub maybe_strip_style: Option<Style>,
+}
+
+mod tabs_config_impl {
+    use super::*;
+
+    impl Default for TabsComponentConfig {
+        fn default() -> Self {
+            Self {
+                show_tab_numbers: true,
+                active_tab_style: get_active_tab_style(),
+                inactive_tab_style: get_inactive_tab_style(),
+                maybe_strip_style: None,
+            }
+        }
+    }
+
+    impl TabsComponentConfig {
+        /// Return the text that is painted in the strip for the tab at `tab_index`.
+        pub fn get_tab_label_text(&self, tab_index: usize, label: &str) -> String {
+            match self.show_tab_numbers && tab_index < MAX_NUMBERED_TAB_COUNT {
+                true => format!(" {}:{label} ", tab_index + 1),
+                false => format!(" {label} "),
+            }
+        }
+    }
+}
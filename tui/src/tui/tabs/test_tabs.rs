@@ -0,0 +1,190 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+#[cfg(test)]
+mod test_tabs {
+    use std::{collections::HashMap, sync::Arc};
+
+    use r3bl_redux::*;
+    use r3bl_rs_utils_core::*;
+    use tokio::sync::RwLock;
+
+    use crate::*;
+
+    #[derive(Clone, Debug, Default, PartialEq)]
+    struct State {
+        editor_buffers: HashMap<FlexBoxId, EditorBuffer>,
+    }
+
+    impl HasEditorBuffers for State {
+        fn get_editor_buffer(&self, id: FlexBoxId) -> Option<&EditorBuffer> {
+            self.editor_buffers.get(&id)
+        }
+    }
+
+    #[test]
+    fn test_get_tab_index_for_input_event() {
+        let left = InputEvent::Keyboard(keypress!(@special SpecialKey::Left));
+        let right = InputEvent::Keyboard(keypress!(@special SpecialKey::Right));
+
+        // Left & Right wrap around.
+        assert_eq2!(get_tab_index_for_input_event(0, 3, &right), Some(1));
+        assert_eq2!(get_tab_index_for_input_event(2, 3, &right), Some(0));
+        assert_eq2!(get_tab_index_for_input_event(0, 3, &left), Some(2));
+
+        // Number keys pick a tab, if it exists.
+        let two = InputEvent::Keyboard(keypress!(@char '2'));
+        let five = InputEvent::Keyboard(keypress!(@char '5'));
+        let zero = InputEvent::Keyboard(keypress!(@char '0'));
+        assert_eq2!(get_tab_index_for_input_event(0, 3, &two), Some(1));
+        assert_eq2!(get_tab_index_for_input_event(0, 3, &five), None);
+        assert_eq2!(get_tab_index_for_input_event(0, 3, &zero), None);
+
+        // Other keys, or no tabs.
+        let a = InputEvent::Keyboard(keypress!(@char 'a'));
+        assert_eq2!(get_tab_index_for_input_event(0, 3, &a), None);
+        assert_eq2!(get_tab_index_for_input_event(0, 0, &right), None);
+    }
+
+    #[test]
+    fn test_get_tab_index_for_modified_input_event() {
+        let ctrl_page_up = InputEvent::Keyboard(
+            keypress!(@special ModifierKeysMask::CTRL, SpecialKey::PageUp),
+        );
+        let ctrl_page_down = InputEvent::Keyboard(
+            keypress!(@special ModifierKeysMask::CTRL, SpecialKey::PageDown),
+        );
+        let alt_two = InputEvent::Keyboard(keypress!(@char ModifierKeysMask::ALT, '2'));
+        let ctrl_two = InputEvent::Keyboard(keypress!(@char ModifierKeysMask::CTRL, '2'));
+
+        assert_eq2!(
+            get_tab_index_for_modified_input_event(2, 3, &ctrl_page_down),
+            Some(0)
+        );
+        assert_eq2!(
+            get_tab_index_for_modified_input_event(0, 3, &ctrl_page_up),
+            Some(2)
+        );
+        assert_eq2!(
+            get_tab_index_for_modified_input_event(0, 3, &alt_two),
+            Some(1)
+        );
+        assert_eq2!(
+            get_tab_index_for_modified_input_event(0, 3, &ctrl_two),
+            None
+        );
+
+        // The plain keys are left for the active child.
+        let right = InputEvent::Keyboard(keypress!(@special SpecialKey::Right));
+        assert_eq2!(get_tab_index_for_modified_input_event(0, 3, &right), None);
+    }
+
+    #[tokio::test]
+    async fn test_switch_tabs_w_editor_child() {
+        let window_size = &size!( col_count: 10, row_count: 10 );
+        let shared_store: &SharedStore<State, String> =
+            &Arc::new(RwLock::new(Store::default()));
+        let shared_global_data =
+            &test_editor::mock_real_objects_for_editor::make_shared_global_data(
+                (*window_size).into(),
+            );
+        let component_registry = &mut ComponentRegistry::default();
+        let state = &State::default();
+
+        let make_editor_child = |id: u8| -> SharedComponent<State, String> {
+            let mut editor_component = EditorComponent::new(
+                FlexBoxId::from(id),
+                EditorEngineConfig::default(),
+                |_, _, _| {},
+            );
+            editor_component.editor_engine =
+                test_editor::mock_real_objects_for_editor::make_editor_engine();
+            Arc::new(RwLock::new(editor_component))
+        };
+        let mut tabs_component = TabsComponent::new(
+            FlexBoxId::from(0),
+            vec![
+                Tab::new("One", make_editor_child(1)),
+                Tab::new("Two", make_editor_child(2)),
+            ],
+            TabsComponentConfig::default(),
+            |_, _, _| {},
+        );
+
+        // The editor consumes the plain keys, so they don't switch tabs.
+        for input_event in [
+            InputEvent::Keyboard(keypress!(@char '2')),
+            InputEvent::Keyboard(keypress!(@special SpecialKey::Right)),
+        ] {
+            let args = ComponentScopeArgs {
+                shared_global_data,
+                shared_store,
+                state,
+                component_registry,
+                window_size,
+            };
+            let event_propagation = tabs_component
+                .handle_event(args, &input_event)
+                .await
+                .unwrap();
+            assert_ne!(event_propagation, EventPropagation::Propagate);
+            assert_eq2!(tabs_component.active_tab_index, 0);
+        }
+
+        // The modifier-qualified keys switch tabs before the editor sees them.
+        for (input_event, expected_tab_index) in [
+            (
+                InputEvent::Keyboard(
+                    keypress!(@special ModifierKeysMask::CTRL, SpecialKey::PageDown),
+                ),
+                1,
+            ),
+            (
+                InputEvent::Keyboard(keypress!(@char ModifierKeysMask::ALT, '1')),
+                0,
+            ),
+        ] {
+            let args = ComponentScopeArgs {
+                shared_global_data,
+                shared_store,
+                state,
+                component_registry,
+                window_size,
+            };
+            let event_propagation = tabs_component
+                .handle_event(args, &input_event)
+                .await
+                .unwrap();
+            assert_eq2!(event_propagation, EventPropagation::ConsumedRender);
+            assert_eq2!(tabs_component.active_tab_index, expected_tab_index);
+        }
+    }
+
+    #[test]
+    fn test_get_tab_label_text() {
+        let config = TabsComponentConfig::default();
+        assert_eq2!(config.get_tab_label_text(0, "Files"), " 1:Files ");
+        // Only the first 9 tabs have a number key.
+        assert_eq2!(config.get_tab_label_text(9, "Logs"), " Logs ");
+
+        let config = TabsComponentConfig {
+            show_tab_numbers: false,
+            ..Default::default()
+        };
+        assert_eq2!(config.get_tab_label_text(0, "Files"), " Files ");
+    }
+}
@@ -0,0 +1,126 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+//! Copy text to the system clipboard w/ the OSC 52 escape sequence, which works over SSH
+//! & doesn't need any extra dependencies. Most terminals support it (some only after it
+//! is turned on in their preferences), & the ones that don't just ignore it.
+//!
+//! The text is always [stripped](strip_ansi_escape_sequences) first, so that content that
+//! has escape sequences in it (eg: a log file w/ colors) is copied as plain text.
+//!
+//! - OSC 52: <https://invisible-island.net/xterm/ctlseqs/ctlseqs.html#h3-Operating-System-Commands>
+
+use std::io::{stdout, Write};
+
+use crossterm::{queue, style::Print};
+use r3bl_rs_utils_core::*;
+
+use crate::*;
+
+/// Copy `text` (w/ any escape sequences removed) to the system clipboard. More info in
+/// the [module docs](self).
+pub fn copy_to_clipboard(text: &str) {
+    let escape_sequence = get_clipboard_escape_sequence(text);
+    match TERMINAL_LIB_BACKEND {
+        TerminalLibBackend::Crossterm => {
+            exec_render_op!(
+                queue!(stdout(), Print(escape_sequence)),
+                "Clipboard -> copy"
+            );
+            exec_render_op!(stdout().flush(), "Clipboard -> flush");
+        }
+        TerminalLibBackend::Termion => todo!(), // FUTURE: implement clipboard for termion
+    }
+}
+
+/// Returns the OSC 52 escape sequence that puts `text` (w/ any escape sequences removed)
+/// in the clipboard.
+pub fn get_clipboard_escape_sequence(text: &str) -> String {
+    let plain_text = strip_ansi_escape_sequences(text);
+    format!("\x1b]52;c;{}\x07", encode_base64(plain_text.as_bytes()))
+}
+
+/// Remove the escape sequences from `text`, ie: CSI sequences (eg: the SGR ones that set
+/// colors), OSC sequences (eg: hyperlinks) & any other `ESC` sequence. Other control
+/// chars (eg: `\t` & `\n`) are kept.
+pub fn strip_ansi_escape_sequences(text: &str) -> String {
+    let mut it = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(char) = chars.next() {
+        match char {
+            // 8-bit CSI.
+            '\u{9b}' => skip_csi_sequence(&mut chars),
+            '\x1b' => match chars.next() {
+                Some('[') => skip_csi_sequence(&mut chars),
+                // OSC, DCS, APC, PM & SOS end w/ ST (`ESC \`), or BEL for OSC.
+                Some(']' | 'P' | '_' | '^' | 'X') => {
+                    while let Some(char) = chars.next() {
+                        if char == '\x07' {
+                            break;
+                        }
+                        if char == '\x1b' {
+                            if chars.peek() == Some(&'\\') {
+                                chars.next();
+                            }
+                            break;
+                        }
+                    }
+                }
+                // Any other sequence is `ESC` followed by 1 char.
+                _ => {}
+            },
+            _ => it.push(char),
+        }
+    }
+    it
+}
+
+/// Skip the parameter & intermediate bytes, up to & including the final byte.
+fn skip_csi_sequence(chars: &mut impl Iterator<Item = char>) {
+    for char in chars.by_ref() {
+        if ('\u{40}'..='\u{7e}').contains(&char) {
+            break;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_rs_utils_core::*;
+
+    use super::*;
+
+    #[test]
+    fn test_strip_ansi_escape_sequences() {
+        assert_eq2!(
+            strip_ansi_escape_sequences(
+                "\x1b[1;38;2;255;0;0mERROR\x1b[0m: \x1b]8;;https://r3bl.com\x1b\\link\x1b]8;;\x07\tok"
+            ),
+            "ERROR: link\tok"
+        );
+        assert_eq2!(strip_ansi_escape_sequences("\u{9b}2Jplain 😀"), "plain 😀");
+        assert_eq2!(strip_ansi_escape_sequences("\x1b7saved\x1b8"), "saved");
+    }
+
+    #[test]
+    fn test_get_clipboard_escape_sequence() {
+        assert_eq2!(
+            get_clipboard_escape_sequence("\x1b[31mfoo\x1b[0m"),
+            "\x1b]52;c;Zm9v\x07"
+        );
+    }
+}
@@ -15,13 +15,90 @@
  *   limitations under the License.
  */
 
+use std::io::Write;
+
 use async_trait::async_trait;
+use crossterm::{queue,
+                style::{Attribute,
+                        Print,
+                        SetAttribute,
+                        SetBackgroundColor,
+                        SetForegroundColor}};
 use r3bl_rs_utils_core::*;
 
 use crate::*;
 
 pub struct OffscreenBufferPaintImplCrossterm;
 
+impl OffscreenBufferPaintImplCrossterm {
+    /// Write each line of the [OffscreenBuffer] to the `writer` as a line of ANSI text,
+    /// w/out moving the cursor, so this works w/out raw mode & the output scrolls like any
+    /// other. The [PixelChar::Spacer]s at the end of each line are left out. This is used
+    /// by [paint_sync].
+    pub fn write_lines(
+        offscreen_buffer: &OffscreenBuffer,
+        writer: &mut impl Write,
+    ) -> CommonResult<()> {
+        for line in offscreen_buffer.buffer.iter() {
+            let end_index = line
+                .iter()
+                .rposition(|it| !matches!(it, PixelChar::Spacer))
+                .map_or(0, |it| it + 1);
+
+            let mut prev_style: Option<Style> = None;
+            let mut text = String::new();
+            for pixel_char in &line[..end_index] {
+                let (pixel_char_str, pixel_char_style): (&str, Option<Style>) =
+                    match pixel_char {
+                        PixelChar::Void => continue,
+                        PixelChar::Spacer => (SPACER, None),
+                        PixelChar::PlainText {
+                            content,
+                            maybe_style,
+                        } => (&content.string, *maybe_style),
+                    };
+                if !render_helpers::style_eq(&pixel_char_style, &prev_style) {
+                    write_styled_text(writer, &text, &prev_style)?;
+                    text.clear();
+                    prev_style = pixel_char_style;
+                }
+                text.push_str(pixel_char_str);
+            }
+            write_styled_text(writer, &text, &prev_style)?;
+            queue!(writer, Print("\n"))?;
+        }
+        writer.flush()?;
+        return Ok(());
+
+        fn write_styled_text(
+            writer: &mut impl Write,
+            text: &str,
+            maybe_style: &Option<Style>,
+        ) -> CommonResult<()> {
+            if text.is_empty() {
+                return Ok(());
+            }
+            let Some(style) = maybe_style else {
+                queue!(writer, Print(text))?;
+                return Ok(());
+            };
+            if let Some(color_bg) = style.color_bg {
+                let color_bg = color_converter::to_crossterm_color(color_bg);
+                queue!(writer, SetBackgroundColor(color_bg))?;
+            }
+            if let Some(color_fg) = style.color_fg {
+                let color_fg = color_converter::to_crossterm_color(color_fg);
+                queue!(writer, SetForegroundColor(color_fg))?;
+            }
+            for attribute in style_to_attribute(style) {
+                queue!(writer, SetAttribute(attribute))?;
+            }
+            queue!(writer, Print(text), SetAttribute(Attribute::Reset))?;
+            Ok(())
+        }
+    }
+}
+
 #[async_trait]
 impl OffscreenBufferPaint for OffscreenBufferPaintImplCrossterm {
     async fn paint(
@@ -170,40 +247,111 @@ impl OffscreenBufferPaint for OffscreenBufferPaintImplCrossterm {
         context.render_ops
     }
 
+    /// Only the [PixelChar]s that have changed since the last paint are in
+    /// `diff_chunks`. Adjacent ones (in the same row) that have the same [Style] are
+    /// coalesced into a single run, which is painted w/ 1 cursor move & 1 set of colors.
+    /// The cursor is not moved at all if the previous run ended right where the next one
+    /// starts.
     async fn render_diff(&mut self, diff_chunks: &PixelCharDiffChunks) -> RenderOps {
+        use diff_render_helpers::*;
+
         call_if_true!(DEBUG_TUI_COMPOSITOR, {
             let msg = format!("🎨 offscreen_buffer_paint_impl_crossterm::render_diff() ok ✅: \ndiff_chunks: \n{}",
             diff_chunks.pretty_print());
             log_info(msg);
         });
 
-        let mut it = render_ops!();
+        let mut context = DiffContext::default();
 
         for (position, pixel_char) in diff_chunks.iter() {
-            it.push(RenderOp::MoveCursorPositionAbs(*position));
-            it.push(RenderOp::ResetColor);
-            match pixel_char {
-                PixelChar::Void => continue,
-                PixelChar::Spacer => {
-                    it.push(RenderOp::CompositorNoClipTruncPaintTextWithAttributes(
-                        SPACER.into(),
-                        None,
-                    ))
-                }
-                PixelChar::PlainText {
-                    content,
-                    maybe_style,
-                } => {
-                    it.push(RenderOp::ApplyColors(*maybe_style));
-                    it.push(RenderOp::CompositorNoClipTruncPaintTextWithAttributes(
-                        content.string.clone(),
-                        *maybe_style,
-                    ))
+            let (pixel_char_str, pixel_char_style): (&str, Option<Style>) =
+                match pixel_char {
+                    // The wide char to the left of this has already been painted.
+                    PixelChar::Void => continue,
+                    PixelChar::Spacer => (SPACER, None),
+                    PixelChar::PlainText {
+                        content,
+                        maybe_style,
+                    } => (&content.string, *maybe_style),
+                };
+
+            let is_continuation_of_run = match context.maybe_run {
+                Some(ref run) => {
+                    run.get_end_position() == *position
+                        && render_helpers::style_eq(&run.maybe_style, &pixel_char_style)
                 }
+                None => false,
+            };
+
+            if !is_continuation_of_run {
+                context.flush_run();
+                context.maybe_run = Some(Run {
+                    start_position: *position,
+                    text: String::new(),
+                    maybe_style: pixel_char_style,
+                });
+            }
+
+            if let Some(ref mut run) = context.maybe_run {
+                run.text.push_str(pixel_char_str);
+            }
+        }
+
+        context.flush_run();
+        context.render_ops
+    }
+}
+
+mod diff_render_helpers {
+    use super::*;
+
+    /// Consecutive [PixelChar]s in a row that have the same style.
+    pub struct Run {
+        pub start_position: Position,
+        pub text: String,
+        pub maybe_style: Option<Style>,
+    }
+
+    impl Run {
+        /// The position right after the last [PixelChar] in this run.
+        pub fn get_end_position(&self) -> Position {
+            let display_width = UnicodeString::from(self.text.as_str()).display_width;
+            position! {
+                col_index: self.start_position.col_index + display_width,
+                row_index: self.start_position.row_index
             }
         }
+    }
+
+    #[derive(Default)]
+    pub struct DiffContext {
+        pub maybe_run: Option<Run>,
+        /// Where the terminal cursor is after the last run has been painted.
+        pub maybe_cursor_position: Option<Position>,
+        pub render_ops: RenderOps,
+    }
+
+    impl DiffContext {
+        pub fn flush_run(&mut self) {
+            let Some(run) = self.maybe_run.take() else {
+                return;
+            };
 
-        it
+            if self.maybe_cursor_position != Some(run.start_position) {
+                self.render_ops
+                    .push(RenderOp::MoveCursorPositionAbs(run.start_position));
+            }
+            self.render_ops.push(RenderOp::ResetColor);
+            if run.maybe_style.is_some() {
+                self.render_ops.push(RenderOp::ApplyColors(run.maybe_style));
+            }
+            self.maybe_cursor_position = Some(run.get_end_position());
+            self.render_ops
+                .push(RenderOp::CompositorNoClipTruncPaintTextWithAttributes(
+                    run.text,
+                    run.maybe_style,
+                ));
+        }
     }
 }
 
@@ -418,4 +566,68 @@ mod tests {
             )
         );
     }
+
+    #[tokio::test]
+    async fn test_render_diff_coalesces_runs() {
+        let style = Some(style! { attrib: [bold] color_fg: color!(@green) });
+        let plain_text = |text: &str, maybe_style: Option<Style>| PixelChar::PlainText {
+            content: GraphemeClusterSegment::from(text),
+            maybe_style,
+        };
+
+        // Input:  R0 "ab ··c"
+        //         R1 "😃d"
+        //            C012345
+        let diff_chunks: PixelCharDiffChunks = vec![
+            (
+                position! { col_index: 0, row_index: 0 },
+                plain_text("a", style),
+            ),
+            (
+                position! { col_index: 1, row_index: 0 },
+                plain_text("b", style),
+            ),
+            (position! { col_index: 2, row_index: 0 }, PixelChar::Spacer),
+            (
+                position! { col_index: 5, row_index: 0 },
+                plain_text("c", style),
+            ),
+            (
+                position! { col_index: 0, row_index: 1 },
+                plain_text("😃", None),
+            ),
+            (position! { col_index: 1, row_index: 1 }, PixelChar::Void),
+            (
+                position! { col_index: 2, row_index: 1 },
+                plain_text("d", None),
+            ),
+        ]
+        .into();
+
+        let mut paint = OffscreenBufferPaintImplCrossterm {};
+        let render_ops = paint.render_diff(&diff_chunks).await;
+
+        // Painting each of the 7 chunks on its own takes 25 render ops (w/ 7 cursor
+        // moves). Coalescing them into 4 runs takes 13 (w/ 3 cursor moves).
+        let expected = vec![
+            RenderOp::MoveCursorPositionAbs(position! { col_index: 0, row_index: 0 }),
+            RenderOp::ResetColor,
+            RenderOp::ApplyColors(style),
+            RenderOp::CompositorNoClipTruncPaintTextWithAttributes("ab".into(), style),
+            // The cursor is already at col 2, so it is not moved.
+            RenderOp::ResetColor,
+            RenderOp::CompositorNoClipTruncPaintTextWithAttributes(" ".into(), None),
+            RenderOp::MoveCursorPositionAbs(position! { col_index: 5, row_index: 0 }),
+            RenderOp::ResetColor,
+            RenderOp::ApplyColors(style),
+            RenderOp::CompositorNoClipTruncPaintTextWithAttributes("c".into(), style),
+            RenderOp::MoveCursorPositionAbs(position! { col_index: 0, row_index: 1 }),
+            RenderOp::ResetColor,
+            RenderOp::CompositorNoClipTruncPaintTextWithAttributes("😃d".into(), None),
+        ];
+        assert_eq2!(render_ops.len(), expected.len());
+        for (index, render_op) in expected.into_iter().enumerate() {
+            assert_eq2!(render_ops[index], render_op);
+        }
+    }
 }
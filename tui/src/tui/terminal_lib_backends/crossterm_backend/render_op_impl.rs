@@ -51,7 +51,8 @@ mod render_op_impl_crossterm_impl_trait_paint_render_op {
                         .await;
                 }
                 RenderOp::ExitRawMode => {
-                    RenderOpImplCrossterm::raw_mode_exit(skip_flush);
+                    RenderOpImplCrossterm::raw_mode_exit(skip_flush, shared_global_data)
+                        .await;
                 }
                 RenderOp::MoveCursorPositionAbs(abs_pos) => {
                     RenderOpImplCrossterm::move_cursor_position_abs(
@@ -165,18 +166,40 @@ mod render_op_impl_crossterm_impl {
             )
         }
 
-        pub fn raw_mode_exit(skip_flush: &mut bool) {
+        /// The cursor shape (if the app changed it) is restored before anything else. This
+        /// does nothing if raw mode isn't active (eg: it was already restored by the panic
+        /// hook). More info in [RawMode].
+        pub async fn raw_mode_exit(
+            skip_flush: &mut bool,
+            shared_global_data: &SharedGlobalData,
+        ) {
+            if !RawMode::take_is_active() {
+                return;
+            }
+            {
+                let mut global_data = shared_global_data.write().await;
+                restore_cursor_shape(&mut global_data);
+                // The cursor is hidden again when raw mode is entered again.
+                global_data.is_hardware_cursor_visible = false;
+            }
+            Self::restore_terminal();
+            *skip_flush = true;
+        }
+
+        /// Undo everything that [raw_mode_enter](Self::raw_mode_enter) did. This is
+        /// synchronous, so that it can be called from the panic hook.
+        pub fn restore_terminal() {
             exec_render_op! {
               queue!(stdout(),
                 Show,
                 LeaveAlternateScreen,
-                DisableMouseCapture
+                DisableMouseCapture,
+                DisableBracketedPaste
               ),
-              "ExitRawMode -> Show, LeaveAlternateScreen, DisableMouseCapture"
+              "ExitRawMode -> Show, LeaveAlternateScreen, DisableMouseCapture, DisableBracketedPaste"
             };
             render_op_impl_crossterm_impl_trait_flush::flush();
             exec_render_op! {terminal::disable_raw_mode(), "ExitRawMode -> disable_raw_mode()"}
-            *skip_flush = true;
         }
 
         pub async fn raw_mode_enter(
@@ -190,14 +213,16 @@ mod render_op_impl_crossterm_impl {
             exec_render_op! {
               queue!(stdout(),
                 EnableMouseCapture,
+                EnableBracketedPaste,
                 EnterAlternateScreen,
                 MoveTo(0,0),
                 Clear(ClearType::All),
                 Hide,
               ),
-            "EnterRawMode -> EnableMouseCapture, EnterAlternateScreen, MoveTo(0,0), Clear(ClearType::All), Hide"
+            "EnterRawMode -> EnableMouseCapture, EnableBracketedPaste, EnterAlternateScreen, MoveTo(0,0), Clear(ClearType::All), Hide"
             }
             render_op_impl_crossterm_impl_trait_flush::flush();
+            RawMode::set_is_active();
             *skip_flush = true;
         }
 
@@ -272,6 +297,8 @@ mod render_op_impl_crossterm_impl {
     }
 }
 
+pub(crate) use perform_paint::style_to_attribute;
+
 mod perform_paint {
     use super::*;
 
@@ -283,7 +310,7 @@ mod perform_paint {
         pub shared_global_data: &'a SharedGlobalData,
     }
 
-    fn style_to_attribute(&style: &Style) -> Vec<Attribute> {
+    pub fn style_to_attribute(&style: &Style) -> Vec<Attribute> {
         let mut it = vec![];
         if style.bold {
             it.push(Attribute::Bold);
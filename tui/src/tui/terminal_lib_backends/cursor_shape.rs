@@ -0,0 +1,157 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+//! The shape of the terminal cursor is set w/ an escape sequence. Most terminals support
+//! DECSCUSR (`CSI Ps SP q`), but some don't, or map the shapes differently. So the
+//! sequences that are used come from a [CursorShapeProfile] that can be replaced (in
+//! [GlobalData::cursor_shape_profile]) to fix a wrong shape, or turn cursor shaping off
+//! w/ [CursorShapeProfile::no_op].
+//!
+//! - DECSCUSR: <https://vt100.net/docs/vt510-rm/DECSCUSR.html>
+
+use std::io::{stdout, Write};
+
+use crossterm::{queue, style::Print};
+use r3bl_rs_utils_core::*;
+use serde::{Deserialize, Serialize};
+
+use crate::*;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CursorShape {
+    Block,
+    Bar,
+    Underline,
+}
+
+/// The escape sequences that set each [CursorShape], and the one that restores the shape
+/// that the terminal had before the app started (which is written when raw mode ends). An
+/// empty sequence isn't written. The [Default] is [CursorShapeProfile::decscusr].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CursorShapeProfile {
+    pub block: String,
+    pub bar: String,
+    pub underline: String,
+    pub restore: String,
+}
+
+mod cursor_shape_profile_impl {
+    use super::*;
+
+    impl Default for CursorShapeProfile {
+        fn default() -> Self { Self::decscusr() }
+    }
+
+    impl CursorShapeProfile {
+        /// The steady (non blinking) DECSCUSR shapes. `CSI 0 SP q` restores the shape
+        /// that is set in the terminal's preferences.
+        pub fn decscusr() -> Self {
+            Self {
+                block: "\x1b[2 q".to_string(),
+                underline: "\x1b[4 q".to_string(),
+                bar: "\x1b[6 q".to_string(),
+                restore: "\x1b[0 q".to_string(),
+            }
+        }
+
+        /// For terminals that don't support cursor shaping (they'd print the sequences
+        /// as garbage).
+        pub fn no_op() -> Self {
+            Self {
+                block: String::new(),
+                bar: String::new(),
+                underline: String::new(),
+                restore: String::new(),
+            }
+        }
+
+        pub fn get_escape_sequence(&self, cursor_shape: CursorShape) -> &str {
+            match cursor_shape {
+                CursorShape::Block => &self.block,
+                CursorShape::Bar => &self.bar,
+                CursorShape::Underline => &self.underline,
+            }
+        }
+    }
+}
+
+/// Called by [TerminalWindow::main_event_loop] right after the `render_pipeline` of the
+/// app is painted. The shape that is requested w/ [GlobalData::set_cursor_shape] is only
+/// written when it is different from the one that was painted last.
+pub async fn paint_cursor_shape(shared_global_data: &SharedGlobalData) {
+    let mut global_data = shared_global_data.write().await;
+    let Some(cursor_shape) = global_data.maybe_cursor_shape else {
+        return;
+    };
+    if global_data.maybe_painted_cursor_shape == Some(cursor_shape) {
+        return;
+    }
+    global_data.maybe_painted_cursor_shape = Some(cursor_shape);
+
+    let escape_sequence = global_data
+        .cursor_shape_profile
+        .get_escape_sequence(cursor_shape);
+    write_escape_sequence(escape_sequence, "CursorShape -> paint");
+}
+
+/// Called when raw mode ends, so that the terminal gets back the shape that it had before
+/// the app changed it. Nothing is written if the app never changed it.
+pub fn restore_cursor_shape(global_data: &mut GlobalData) {
+    if global_data.maybe_painted_cursor_shape.take().is_none() {
+        return;
+    }
+    write_escape_sequence(
+        &global_data.cursor_shape_profile.restore,
+        "CursorShape -> restore",
+    );
+}
+
+fn write_escape_sequence(escape_sequence: &str, msg: &str) {
+    if escape_sequence.is_empty() {
+        return;
+    }
+    match TERMINAL_LIB_BACKEND {
+        TerminalLibBackend::Crossterm => {
+            exec_render_op!(queue!(stdout(), Print(escape_sequence)), msg);
+            exec_render_op!(stdout().flush(), msg);
+        }
+        TerminalLibBackend::Termion => todo!(), // FUTURE: implement cursor shape for termion
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_rs_utils_core::*;
+
+    use super::*;
+
+    #[test]
+    fn test_cursor_shape_profile() {
+        let profile = CursorShapeProfile::default();
+        assert_eq2!(profile.get_escape_sequence(CursorShape::Block), "\x1b[2 q");
+        assert_eq2!(
+            profile.get_escape_sequence(CursorShape::Underline),
+            "\x1b[4 q"
+        );
+        assert_eq2!(profile.get_escape_sequence(CursorShape::Bar), "\x1b[6 q");
+        assert_eq2!(profile.restore, "\x1b[0 q");
+
+        let profile = CursorShapeProfile::no_op();
+        assert_eq2!(profile.get_escape_sequence(CursorShape::Bar), "");
+        assert_eq2!(profile.restore, "");
+    }
+}
@@ -0,0 +1,77 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+//! The terminal's (hardware) cursor is hidden while raw mode is active, since the
+//! [EditorComponent] paints a caret of its own. A component that wants the cursor shown
+//! at a spot of its own (eg: in an input field) asks for it w/
+//! [RenderPipeline::request_cursor_at] from its [Component::render], & it is moved there
+//! by [paint_hardware_cursor] after the pipeline is painted. When no component asks for
+//! it, the cursor is hidden again.
+
+use std::io::{stdout, Write};
+
+use crossterm::{cursor::{Hide, MoveTo, Show},
+                queue};
+use r3bl_rs_utils_core::*;
+
+use crate::*;
+
+/// Called by [TerminalWindow::main_event_loop] right after the `render_pipeline` of the
+/// app is painted (since painting moves the cursor around). The cursor is moved to
+/// [RenderPipeline::maybe_cursor_position] on each render, but it is only shown or hidden
+/// when that changes. More info in the [module docs](self).
+pub async fn paint_hardware_cursor(
+    render_pipeline: &RenderPipeline,
+    shared_global_data: &SharedGlobalData,
+) {
+    let mut global_data = shared_global_data.write().await;
+    let was_visible = global_data.is_hardware_cursor_visible;
+    global_data.is_hardware_cursor_visible =
+        render_pipeline.maybe_cursor_position.is_some();
+    let window_size = global_data.window_size;
+
+    match TERMINAL_LIB_BACKEND {
+        TerminalLibBackend::Crossterm => {
+            match render_pipeline.maybe_cursor_position {
+                Some(position) => {
+                    let position = sanitize_abs_position(position, window_size);
+                    exec_render_op!(
+                        queue!(
+                            stdout(),
+                            MoveTo(
+                                ch!(@to_u16 position.col_index),
+                                ch!(@to_u16 position.row_index)
+                            )
+                        ),
+                        "HardwareCursor -> move"
+                    );
+                    if !was_visible {
+                        exec_render_op!(queue!(stdout(), Show), "HardwareCursor -> show");
+                    }
+                }
+                None => {
+                    if !was_visible {
+                        return;
+                    }
+                    exec_render_op!(queue!(stdout(), Hide), "HardwareCursor -> hide");
+                }
+            }
+            exec_render_op!(stdout().flush(), "HardwareCursor -> flush");
+        }
+        TerminalLibBackend::Termion => todo!(), // FUTURE: implement hardware cursor for termion
+    }
+}
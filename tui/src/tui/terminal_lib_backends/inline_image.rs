@@ -0,0 +1,312 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+//! Inline images are painted w/ a terminal graphics protocol, which is an escape sequence
+//! that carries the (base64 encoded) image & the box of cells to paint it in:
+//! - Kitty: <https://sw.kovidgoyal.net/kitty/graphics-protocol/>
+//! - iTerm2: <https://iterm2.com/documentation-images.html>
+//!
+//! These sequences can't be stored in the [OffscreenBuffer] (which only holds chars), so
+//! they travel in the [RenderPipeline::inline_images] & are written to the terminal by
+//! [paint_inline_images] after the pipeline is painted.
+
+use std::io::{stdout, Write};
+
+use crossterm::{cursor::MoveTo, queue, style::Print};
+use r3bl_rs_utils_core::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{graphics_protocol_global_static::{clear_graphics_protocol_override,
+                                              get_graphics_protocol_override,
+                                              set_graphics_protocol_override},
+            *};
+
+/// The terminal graphics protocol that is used to paint inline images.
+/// [GraphicsProtocol::None] means that images are painted as (styled) alt text.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GraphicsProtocol {
+    #[default]
+    None,
+    Kitty,
+    ITerm2,
+}
+
+/// An image that is painted in the box of cells that starts at `origin_pos` (an absolute
+/// position) & is `size` big. More info in the [module docs](self).
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InlineImage {
+    pub origin_pos: Position,
+    pub size: Size,
+    /// Made by [GraphicsProtocol::encode_image].
+    pub escape_sequence: String,
+}
+
+/// The Kitty protocol limits the payload of each escape sequence to this many bytes.
+const KITTY_CHUNK_SIZE: usize = 4096;
+
+const PNG_SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+
+mod graphics_protocol_impl {
+    use super::*;
+
+    impl GraphicsProtocol {
+        pub fn set_graphics_protocol_override(graphics_protocol: GraphicsProtocol) {
+            set_graphics_protocol_override(graphics_protocol);
+        }
+
+        pub fn clear_graphics_protocol_override() { clear_graphics_protocol_override(); }
+
+        /// Unsupported terminals print the escape sequences as garbage, so this is
+        /// conservative and only returns a protocol for terminals that are known to
+        /// support it. Terminal multiplexers (eg: tmux) need the sequences to be wrapped,
+        /// so no protocol is used in them. If you want to override this please use
+        /// [set_graphics_protocol_override](GraphicsProtocol::set_graphics_protocol_override)
+        /// & [clear_graphics_protocol_override](GraphicsProtocol::clear_graphics_protocol_override).
+        pub fn detect() -> GraphicsProtocol {
+            // Override is set.
+            if let Some(graphics_protocol) = get_graphics_protocol_override() {
+                return graphics_protocol;
+            }
+
+            // Override is not set.
+            Self::detect_from_env(|key| std::env::var(key).ok())
+        }
+
+        /// `get_env_var` returns the value of the given environment variable, if it is
+        /// set.
+        pub fn detect_from_env(
+            get_env_var: impl Fn(&str) -> Option<String>,
+        ) -> GraphicsProtocol {
+            let term = get_env_var("TERM").unwrap_or_default();
+            let term_program = get_env_var("TERM_PROGRAM").unwrap_or_default();
+
+            if get_env_var("TMUX").is_some()
+                || term.starts_with("screen")
+                || term.starts_with("tmux")
+            {
+                return GraphicsProtocol::None;
+            }
+            if term == "xterm-kitty" || get_env_var("KITTY_WINDOW_ID").is_some() {
+                return GraphicsProtocol::Kitty;
+            }
+            if term_program == "iTerm.app" || term_program == "WezTerm" {
+                return GraphicsProtocol::ITerm2;
+            }
+            GraphicsProtocol::None
+        }
+
+        /// Kitty can only decode PNG images (w/out extra dependencies), while iTerm2 can
+        /// decode any format that macOS can.
+        pub fn can_paint(&self, image_bytes: &[u8]) -> bool {
+            match self {
+                GraphicsProtocol::None => false,
+                GraphicsProtocol::Kitty => image_bytes.starts_with(PNG_SIGNATURE),
+                GraphicsProtocol::ITerm2 => !image_bytes.is_empty(),
+            }
+        }
+
+        /// Returns the escape sequence that paints `image_bytes` (the content of an image
+        /// file) scaled to fit in a box of cells that is `size` big, starting at the
+        /// cursor. Returns [None] if this protocol [can't paint](GraphicsProtocol::can_paint)
+        /// the image.
+        pub fn encode_image(&self, image_bytes: &[u8], size: Size) -> Option<String> {
+            if !self.can_paint(image_bytes) {
+                return None;
+            }
+
+            let col_count = ch!(@to_usize size.col_count);
+            let row_count = ch!(@to_usize size.row_count);
+            let payload = encode_base64(image_bytes);
+
+            let escape_sequence = match self {
+                GraphicsProtocol::None => return None,
+                // Transmit & display the PNG (`f=100`) w/out moving the cursor (`C=1`) or
+                // replying (`q=2`). `m=1` means that more chunks follow.
+                GraphicsProtocol::Kitty => {
+                    let chunks = payload.as_bytes().chunks(KITTY_CHUNK_SIZE);
+                    let last_chunk_index = chunks.len() - 1;
+                    let mut it = String::new();
+                    for (index, chunk) in chunks.enumerate() {
+                        let more = u8::from(index < last_chunk_index);
+                        let chunk = String::from_utf8_lossy(chunk);
+                        if index == 0 {
+                            it.push_str(&format!(
+                                "\x1b_Ga=T,f=100,c={col_count},r={row_count},C=1,q=2,m={more};{chunk}\x1b\\"
+                            ));
+                        } else {
+                            it.push_str(&format!("\x1b_Gm={more};{chunk}\x1b\\"));
+                        }
+                    }
+                    it
+                }
+                GraphicsProtocol::ITerm2 => format!(
+                    "\x1b]1337;File=inline=1;size={};width={col_count};height={row_count};preserveAspectRatio=1:{payload}\x07",
+                    image_bytes.len()
+                ),
+            };
+            Some(escape_sequence)
+        }
+    }
+}
+
+/// Called by [TerminalWindow::main_event_loop] right after the `render_pipeline` of the
+/// app is painted. The [OffscreenBuffer] doesn't know about the images, so when the
+/// cells under them don't change, they wouldn't be painted again (& the old image would
+/// stay on screen). To avoid this, the next render is painted in full when any images are
+/// painted in this one.
+pub async fn paint_inline_images(
+    render_pipeline: &RenderPipeline,
+    shared_global_data: &SharedGlobalData,
+) {
+    let mut global_data = shared_global_data.write().await;
+    let had_inline_images = global_data.has_inline_images;
+    global_data.has_inline_images = !render_pipeline.inline_images.is_empty();
+    if !had_inline_images && !global_data.has_inline_images {
+        return;
+    }
+    global_data.maybe_saved_offscreen_buffer = None;
+
+    match TERMINAL_LIB_BACKEND {
+        TerminalLibBackend::Crossterm => {
+            // Kitty keeps images in a layer of their own, so they aren't removed when
+            // the text is painted over them.
+            if GraphicsProtocol::detect() == GraphicsProtocol::Kitty {
+                exec_render_op!(
+                    queue!(stdout(), Print("\x1b_Ga=d,q=2\x1b\\")),
+                    "InlineImage -> delete all"
+                );
+            }
+            for inline_image in &render_pipeline.inline_images {
+                exec_render_op!(
+                    queue!(
+                        stdout(),
+                        MoveTo(
+                            ch!(@to_u16 inline_image.origin_pos.col_index),
+                            ch!(@to_u16 inline_image.origin_pos.row_index)
+                        ),
+                        Print(&inline_image.escape_sequence)
+                    ),
+                    "InlineImage -> paint"
+                );
+            }
+            exec_render_op!(stdout().flush(), "InlineImage -> flush");
+        }
+        TerminalLibBackend::Termion => todo!(), // FUTURE: implement inline images for termion
+    }
+}
+
+/// Standard base64 (w/ padding), which is what both protocols expect.
+pub(crate) fn encode_base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] =
+        b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut it = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let b0 = chunk[0] as u32;
+        let b1 = chunk.get(1).copied().unwrap_or(0) as u32;
+        let b2 = chunk.get(2).copied().unwrap_or(0) as u32;
+        let triple = (b0 << 16) | (b1 << 8) | b2;
+
+        it.push(ALPHABET[(triple >> 18) as usize & 0x3f] as char);
+        it.push(ALPHABET[(triple >> 12) as usize & 0x3f] as char);
+        match chunk.len() {
+            1 => it.push_str("=="),
+            2 => {
+                it.push(ALPHABET[(triple >> 6) as usize & 0x3f] as char);
+                it.push('=');
+            }
+            _ => {
+                it.push(ALPHABET[(triple >> 6) as usize & 0x3f] as char);
+                it.push(ALPHABET[triple as usize & 0x3f] as char);
+            }
+        }
+    }
+    it
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use r3bl_rs_utils_core::*;
+
+    use super::*;
+
+    #[test]
+    fn test_encode_base64() {
+        assert_eq2!(encode_base64(b""), "");
+        assert_eq2!(encode_base64(b"f"), "Zg==");
+        assert_eq2!(encode_base64(b"fo"), "Zm8=");
+        assert_eq2!(encode_base64(b"foo"), "Zm9v");
+        assert_eq2!(encode_base64(b"foobar"), "Zm9vYmFy");
+    }
+
+    #[test]
+    fn test_detect_from_env() {
+        let detect = |vars: &[(&str, &str)]| {
+            let vars: HashMap<String, String> = vars
+                .iter()
+                .map(|(key, value)| (key.to_string(), value.to_string()))
+                .collect();
+            GraphicsProtocol::detect_from_env(|key| vars.get(key).cloned())
+        };
+
+        assert_eq2!(
+            detect(&[("TERM", "xterm-256color")]),
+            GraphicsProtocol::None
+        );
+        assert_eq2!(detect(&[("TERM", "xterm-kitty")]), GraphicsProtocol::Kitty);
+        assert_eq2!(
+            detect(&[("TERM_PROGRAM", "iTerm.app")]),
+            GraphicsProtocol::ITerm2
+        );
+        // Multiplexers are never trusted.
+        assert_eq2!(
+            detect(&[
+                ("TERM", "xterm-kitty"),
+                ("TMUX", "/tmp/tmux-1000/default,1,0")
+            ]),
+            GraphicsProtocol::None
+        );
+    }
+
+    #[test]
+    fn test_encode_image() {
+        let size = size!( col_count: 10, row_count: 4 );
+        let png_bytes = [PNG_SIGNATURE, b"rest"].concat();
+
+        // Kitty only paints PNGs.
+        assert!(GraphicsProtocol::Kitty
+            .encode_image(b"GIF89a", size)
+            .is_none());
+        let it = GraphicsProtocol::Kitty
+            .encode_image(&png_bytes, size)
+            .unwrap();
+        assert!(it.starts_with("\x1b_Ga=T,f=100,c=10,r=4,C=1,q=2,m=0;"));
+        assert!(it.ends_with("\x1b\\"));
+
+        let it = GraphicsProtocol::ITerm2
+            .encode_image(b"GIF89a", size)
+            .unwrap();
+        assert!(it.starts_with("\x1b]1337;File=inline=1;size=6;width=10;height=4;"));
+        assert!(it.ends_with(&format!("{}\x07", encode_base64(b"GIF89a"))));
+
+        assert!(GraphicsProtocol::None
+            .encode_image(&png_bytes, size)
+            .is_none());
+    }
+}
@@ -180,6 +180,40 @@ pub enum Key {
     KittyKeyboardProtocol(Enhanced),
 }
 
+mod keypress_display_impl {
+    use std::fmt::{Display, Formatter};
+
+    use super::*;
+
+    /// For [ToString]. The modifier keys come first, eg: `Ctrl+Shift+Left`, `Alt+f`, or
+    /// `F1`. This is used to show key bindings to the user (eg: in the
+    /// [KeyboardHelpComponent]).
+    impl Display for KeyPress {
+        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+            let (key, mask) = match self {
+                KeyPress::Plain { key } => (key, ModifierKeysMask::empty()),
+                KeyPress::WithModifiers { key, mask } => (key, *mask),
+            };
+            if mask.contains(ModifierKeysMask::CTRL) {
+                write!(f, "Ctrl+")?;
+            }
+            if mask.contains(ModifierKeysMask::ALT) {
+                write!(f, "Alt+")?;
+            }
+            if mask.contains(ModifierKeysMask::SHIFT) {
+                write!(f, "Shift+")?;
+            }
+            match key {
+                Key::Character(' ') => write!(f, "Space"),
+                Key::Character(character) => write!(f, "{character}"),
+                Key::SpecialKey(special_key) => write!(f, "{special_key:?}"),
+                Key::FunctionKey(function_key) => write!(f, "{function_key:?}"),
+                Key::KittyKeyboardProtocol(enhanced) => write!(f, "{enhanced:?}"),
+            }
+        }
+    }
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Copy)]
 pub enum FunctionKey {
     F1,
@@ -56,9 +56,13 @@ pub const TERMINAL_LIB_BACKEND: TerminalLibBackend = TerminalLibBackend::Crosste
 
 // Attach source files.
 pub mod async_event_stream_ext;
+pub mod clipboard;
 pub mod color_converter;
 pub mod crossterm_backend;
+pub mod cursor_shape;
 pub mod enhanced_keys;
+pub mod hardware_cursor;
+pub mod inline_image;
 pub mod input_event;
 pub mod keypress;
 pub mod modifier_keys_mask;
@@ -69,14 +73,19 @@ pub mod raw_mode;
 pub mod render_op;
 pub mod render_pipeline;
 pub mod render_pipeline_to_offscreen_buffer;
+pub mod render_pipeline_to_svg;
 pub mod terminal_lib_operations;
 pub mod termion_backend;
 
 // Re-export.
 pub use async_event_stream_ext::*;
+pub use clipboard::*;
 pub use color_converter::*;
 pub use crossterm_backend::*;
+pub use cursor_shape::*;
 pub use enhanced_keys::*;
+pub use hardware_cursor::*;
+pub use inline_image::*;
 pub use input_event::*;
 pub use keypress::*;
 pub use modifier_keys_mask::*;
@@ -87,6 +96,7 @@ pub use raw_mode::*;
 pub use render_op::*;
 pub use render_pipeline::*;
 pub use render_pipeline_to_offscreen_buffer::*;
+pub use render_pipeline_to_svg::*;
 pub use terminal_lib_operations::*;
 pub use termion_backend::*;
 
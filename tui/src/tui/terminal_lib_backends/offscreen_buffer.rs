@@ -113,6 +113,42 @@ mod offscreen_buffer_impl {
             OffscreenBufferDiffResult::Comparable(it)
         }
 
+        /// Same as [diff](OffscreenBuffer::diff), except that only the cells in the
+        /// `dirty_regions` are compared. More info in [RenderPipeline::mark_dirty].
+        pub fn diff_in_regions(
+            &self,
+            other: &Self,
+            dirty_regions: &[DirtyRegion],
+        ) -> OffscreenBufferDiffResult {
+            if self.window_size != other.window_size {
+                return OffscreenBufferDiffResult::NotComparable;
+            }
+
+            let mut it = List::default();
+            for (row, (self_row, other_row)) in
+                self.buffer.iter().zip(other.buffer.iter()).enumerate()
+            {
+                let row_regions = dirty_regions
+                    .iter()
+                    .filter(|dirty_region| dirty_region.contains_row(ch!(row)))
+                    .collect::<Vec<_>>();
+                if row_regions.is_empty() {
+                    continue;
+                }
+                for (col, (self_pixel_char, other_pixel_char)) in
+                    self_row.iter().zip(other_row.iter()).enumerate()
+                {
+                    let position = position!(col_index: col, row_index: row);
+                    if row_regions.iter().any(|it| it.contains(position))
+                        && self_pixel_char != other_pixel_char
+                    {
+                        it.push((position, other_pixel_char.clone()));
+                    }
+                }
+            }
+            OffscreenBufferDiffResult::Comparable(it)
+        }
+
         /// Create a new buffer and fill it with empty chars.
         pub fn new_with_capacity_initialized(window_size: Size) -> Self {
             Self {
@@ -129,6 +165,82 @@ mod offscreen_buffer_impl {
             self.buffer = PixelCharLines::new_with_capacity_initialized(self.window_size);
         }
 
+        /// Fill the cells in the `dirty_region` (that are in the window) w/ empty chars.
+        pub fn clear_region(&mut self, dirty_region: &DirtyRegion) {
+            for (row, line) in self.buffer.iter_mut().enumerate() {
+                if !dirty_region.contains_row(ch!(row)) {
+                    continue;
+                }
+                for (col, pixel_char) in line.iter_mut().enumerate() {
+                    if dirty_region.contains(position!(col_index: col, row_index: row)) {
+                        *pixel_char = PixelChar::Spacer;
+                    }
+                }
+            }
+        }
+
+        /// Add the `dim` attribute to all the text that has been painted so far. More info
+        /// in [RenderPipeline::is_background_dimmed].
+        pub fn dim(&mut self) {
+            for line in self.buffer.iter_mut() {
+                for pixel_char in line.iter_mut() {
+                    if let PixelChar::PlainText { maybe_style, .. } = pixel_char {
+                        maybe_style.get_or_insert_with(Style::default).dim = true;
+                    }
+                }
+            }
+        }
+
+        /// Returns the `col_count` cells of the row at `row_index` (starting at
+        /// `col_index`) as runs of text that have the same style, eg: for a custom painter
+        /// or a snapshot test.
+        /// - [PixelChar::Spacer]s are spaces w/ the default style.
+        /// - The [PixelChar::Void] after a wide grapheme cluster is skipped (its cols are
+        ///   part of the grapheme cluster), unless it is the first cell, in which case it
+        ///   is a space.
+        pub fn get_styled_texts_in_row(
+            &self,
+            row_index: ChUnit,
+            col_index: ChUnit,
+            col_count: ChUnit,
+        ) -> StyledTexts {
+            let mut it = StyledTexts::default();
+            let Some(line) = self.buffer.get(ch!(@to_usize row_index)) else {
+                return it;
+            };
+
+            let mut maybe_run: Option<(Style, String)> = None;
+            let pixel_chars = line
+                .iter()
+                .skip(ch!(@to_usize col_index))
+                .take(ch!(@to_usize col_count));
+            for (index, pixel_char) in pixel_chars.enumerate() {
+                let (style, text) = match pixel_char {
+                    PixelChar::Void if index > 0 => continue,
+                    PixelChar::Void | PixelChar::Spacer => (Style::default(), " "),
+                    PixelChar::PlainText {
+                        content,
+                        maybe_style,
+                    } => (maybe_style.unwrap_or_default(), content.string.as_str()),
+                };
+                match &mut maybe_run {
+                    Some((run_style, run_text)) if *run_style == style => {
+                        run_text.push_str(text);
+                    }
+                    _ => {
+                        if let Some((run_style, run_text)) = maybe_run.take() {
+                            it += StyledText::new(run_style, run_text);
+                        }
+                        maybe_run = Some((style, text.to_string()));
+                    }
+                }
+            }
+            if let Some((run_style, run_text)) = maybe_run {
+                it += StyledText::new(run_style, run_text);
+            }
+            it
+        }
+
         pub fn pretty_print(&self) -> String {
             let mut lines = vec![];
             for row_index in 0..ch!(@to_usize self.window_size.row_count) {
@@ -15,6 +15,8 @@
  *   limitations under the License.
  */
 
+use std::io::Write;
+
 use async_trait::async_trait;
 use r3bl_rs_utils_core::*;
 
@@ -32,19 +34,47 @@ pub async fn paint(
     flush_kind: FlushKind,
     shared_global_data: &SharedGlobalData,
 ) {
-    let maybe_saved_offscreen_buffer = shared_global_data
-        .read()
-        .await
-        .maybe_saved_offscreen_buffer
-        .clone();
-    let offscreen_buffer = pipeline.convert(shared_global_data).await;
+    let (maybe_saved_offscreen_buffer, is_saved_offscreen_buffer_dimmed, window_size) = {
+        let global_data = shared_global_data.read().await;
+        (
+            global_data.maybe_saved_offscreen_buffer.clone(),
+            global_data.is_saved_offscreen_buffer_dimmed,
+            global_data.window_size,
+        )
+    };
+
+    // Only the dirty regions of the pipeline are composed & compared, if it has them, &
+    // the last frame can be reused. More info in [RenderPipeline::mark_dirty].
+    let maybe_dirty_regions =
+        match (&pipeline.maybe_dirty_regions, &maybe_saved_offscreen_buffer) {
+            (Some(dirty_regions), Some(saved_offscreen_buffer))
+                if saved_offscreen_buffer.window_size == window_size
+                    && !pipeline.is_background_dimmed
+                    && !is_saved_offscreen_buffer_dimmed =>
+            {
+                Some(dirty_regions.as_slice())
+            }
+            _ => None,
+        };
+
+    let offscreen_buffer = match (maybe_dirty_regions, &maybe_saved_offscreen_buffer) {
+        (Some(dirty_regions), Some(saved_offscreen_buffer)) => {
+            pipeline.convert_dirty_regions(saved_offscreen_buffer, dirty_regions)
+        }
+        _ => pipeline.convert(shared_global_data).await,
+    };
     match maybe_saved_offscreen_buffer {
         None => {
             perform_full_paint(&offscreen_buffer, flush_kind, shared_global_data).await;
         }
         Some(saved_offscreen_buffer) => {
             // Compare offscreen buffers & paint only the diff.
-            match saved_offscreen_buffer.diff(&offscreen_buffer) {
+            let diff_result = match maybe_dirty_regions {
+                Some(dirty_regions) => saved_offscreen_buffer
+                    .diff_in_regions(&offscreen_buffer, dirty_regions),
+                None => saved_offscreen_buffer.diff(&offscreen_buffer),
+            };
+            match diff_result {
                 OffscreenBufferDiffResult::NotComparable => {
                     perform_full_paint(&offscreen_buffer, flush_kind, shared_global_data)
                         .await;
@@ -55,10 +85,11 @@ pub async fn paint(
             }
         }
     }
-    shared_global_data
-        .write()
-        .await
-        .maybe_saved_offscreen_buffer = Some(offscreen_buffer);
+    {
+        let mut global_data = shared_global_data.write().await;
+        global_data.maybe_saved_offscreen_buffer = Some(offscreen_buffer);
+        global_data.is_saved_offscreen_buffer_dimmed = pipeline.is_background_dimmed;
+    }
 
     async fn perform_diff_paint(
         diff_chunks: &PixelCharDiffChunks,
@@ -94,6 +125,43 @@ pub async fn paint(
     }
 }
 
+/// Paint the render pipeline w/out the async main event loop, a [Store](r3bl_redux::Store)
+/// or a [SharedGlobalData], eg: for a small CLI that only uses this crate for styled output,
+/// and prints it once before it exits. This is additive, [paint] is still what the
+/// [TerminalWindow] uses.
+///
+/// 1. The pipeline is converted to an [OffscreenBuffer] that is `window_size` big, just
+///    like [paint] does. The positions in the [RenderOps] are relative to the top left
+///    corner of it.
+/// 2. Each line of the [OffscreenBuffer] is written to the `writer` (eg: [stdout]) as a
+///    line of ANSI text, where the cursor is. Raw mode isn't needed & the screen isn't
+///    cleared, so the output stays in the scrollback of the terminal.
+///
+/// ```ignore
+/// let mut pipeline = render_pipeline!();
+/// pipeline.push(ZOrder::Normal, render_ops! {
+///     @new
+///     RenderOp::MoveCursorPositionAbs(position! { col_index: 0, row_index: 0 }),
+///     RenderOp::PaintTextWithAttributes("Hello".into(), Some(style! { attrib: [bold] })),
+/// });
+/// paint_sync(&pipeline, size! { col_count: 80, row_count: 1 }, &mut stdout())?;
+/// ```
+///
+/// [stdout]: std::io::stdout
+pub fn paint_sync(
+    pipeline: &RenderPipeline,
+    window_size: Size,
+    writer: &mut impl Write,
+) -> CommonResult<()> {
+    let offscreen_buffer = pipeline.convert_with_window_size(window_size);
+    match TERMINAL_LIB_BACKEND {
+        TerminalLibBackend::Crossterm => {
+            OffscreenBufferPaintImplCrossterm::write_lines(&offscreen_buffer, writer)
+        }
+        TerminalLibBackend::Termion => todo!(), // FUTURE: implement paint_sync for termion
+    }
+}
+
 /// 1. Ensure that the [Position] is within the bounds of the terminal window using
 ///    [RenderOpsLocalData].
 /// 2. If the [Position] is outside of the bounds of the window then it is clamped to the nearest
@@ -104,10 +172,23 @@ pub async fn sanitize_and_save_abs_position(
     shared_global_data: &SharedGlobalData,
     local_data: &mut RenderOpsLocalData,
 ) -> Position {
+    let window_size = shared_global_data.read().await.window_size;
+    let sanitized_abs_pos = sanitize_abs_position(orig_abs_pos, window_size);
+
+    // Save the cursor position to local data.
+    local_data.cursor_position = sanitized_abs_pos;
+
+    sanitized_abs_pos
+}
+
+/// Same as [sanitize_and_save_abs_position], except that the `window_size` is given, and
+/// the clamped [Position] isn't saved anywhere. This is used by [paint_sync], which
+/// doesn't have a [SharedGlobalData].
+pub fn sanitize_abs_position(orig_abs_pos: Position, window_size: Size) -> Position {
     let Size {
         col_count: max_cols,
         row_count: max_rows,
-    } = shared_global_data.read().await.window_size;
+    } = window_size;
 
     let mut sanitized_abs_pos: Position = orig_abs_pos;
 
@@ -119,9 +200,6 @@ pub async fn sanitize_and_save_abs_position(
         sanitized_abs_pos.row_index = max_rows;
     }
 
-    // Save the cursor position to local data.
-    local_data.cursor_position = sanitized_abs_pos;
-
     debug(orig_abs_pos, sanitized_abs_pos);
 
     return sanitized_abs_pos;
@@ -15,14 +15,38 @@
  *   limitations under the License.
  */
 
+use std::sync::{atomic::{AtomicBool, Ordering},
+                Once};
+
 use crate::*;
 
 /// To use this directly, you need to make sure to create an instance using [start](RawMode::start)
 /// which enables raw mode and then make sure to call [end](RawMode::end) when you are done.
+///
+/// If the app panics while raw mode is active, the panic hook that is installed by
+/// [start](RawMode::start) restores the terminal (raw mode, cursor, alternate screen,
+/// mouse capture & bracketed paste) before the panic message is printed. Restoring is
+/// idempotent, so it is only done once, whether it is the panic hook or
+/// [end](RawMode::end) that gets there first.
 #[derive(Debug, Clone)]
 pub struct RawMode;
 
+static IS_RAW_MODE_ACTIVE: AtomicBool = AtomicBool::new(false);
+static INSTALL_PANIC_HOOK_FN: Once = Once::new();
+
 impl RawMode {
+    pub fn is_active() -> bool { IS_RAW_MODE_ACTIVE.load(Ordering::SeqCst) }
+
+    /// Called by the backend once raw mode is entered.
+    pub fn set_is_active() {
+        IS_RAW_MODE_ACTIVE.store(true, Ordering::SeqCst);
+        INSTALL_PANIC_HOOK_FN.call_once(install_panic_hook);
+    }
+
+    /// Returns whether raw mode was active, and marks it as inactive. Whoever gets `true`
+    /// is responsible for restoring the terminal.
+    pub fn take_is_active() -> bool { IS_RAW_MODE_ACTIVE.swap(false, Ordering::SeqCst) }
+
     pub async fn start(shared_global_data: &SharedGlobalData) {
         let mut skip_flush = false;
         RenderOps::route_paint_render_op_to_backend(
@@ -45,3 +69,20 @@ impl RawMode {
         .await;
     }
 }
+
+/// The previous hook (which prints the panic message) is called after the terminal is
+/// restored, otherwise the message would be garbled (or hidden in the alternate screen).
+fn install_panic_hook() {
+    let previous_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |panic_info| {
+        if RawMode::take_is_active() {
+            match TERMINAL_LIB_BACKEND {
+                TerminalLibBackend::Crossterm => {
+                    RenderOpImplCrossterm::restore_terminal()
+                }
+                TerminalLibBackend::Termion => {} // FUTURE: restore the terminal for termion
+            }
+        }
+        previous_hook(panic_info);
+    }));
+}
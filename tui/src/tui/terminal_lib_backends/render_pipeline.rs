@@ -19,6 +19,7 @@ use std::{collections::{hash_map::Entry, HashMap},
           fmt::Debug,
           ops::{AddAssign, Deref, DerefMut}};
 
+use r3bl_rs_utils_core::*;
 use serde::{Deserialize, Serialize};
 
 use super::*;
@@ -159,6 +160,63 @@ macro_rules! render_pipeline {
 pub struct RenderPipeline {
     /// [RenderOps] to paint for each [ZOrder].
     pub pipeline_map: PipelineMap,
+    /// [Toast]s that were pushed while this pipeline was rendered. More info in
+    /// [render_toasts].
+    pub toasts: Vec<Toast>,
+    /// [InlineImage]s to paint on top of this pipeline. More info in
+    /// [paint_inline_images].
+    pub inline_images: Vec<InlineImage>,
+    /// Where to show the terminal's cursor after this pipeline is painted, it is hidden
+    /// when this is [None]. More info in
+    /// [request_cursor_at](RenderPipeline::request_cursor_at).
+    pub maybe_cursor_position: Option<Position>,
+    /// When `true`, everything that is painted below [ZOrder::Glass] is dimmed, so that a
+    /// modal (which is painted on [ZOrder::Glass]) stands out. This is set for the top
+    /// modal by [render_component_in_current_box!] & [render_component_in_given_box!],
+    /// more info in [ComponentRegistry::push_modal].
+    pub is_background_dimmed: bool,
+    /// The regions of the window that changed since the last frame, [None] means that
+    /// all of it might have. More info in [mark_dirty](RenderPipeline::mark_dirty).
+    pub maybe_dirty_regions: Option<Vec<DirtyRegion>>,
+}
+
+/// A rectangle (in absolute terminal coordinates) that changed since the last frame. More
+/// info in [RenderPipeline::mark_dirty].
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DirtyRegion {
+    pub origin_pos: Position,
+    pub bounds_size: Size,
+}
+
+mod dirty_region_impl {
+    use super::*;
+
+    impl DirtyRegion {
+        pub fn new(origin_pos: Position, bounds_size: Size) -> Self {
+            Self {
+                origin_pos,
+                bounds_size,
+            }
+        }
+
+        pub fn contains(&self, position: Position) -> bool {
+            self.contains_row(position.row_index)
+                && position.col_index >= self.origin_pos.col_index
+                && position.col_index
+                    < self.origin_pos.col_index + self.bounds_size.col_count
+        }
+
+        pub fn contains_row(&self, row_index: ChUnit) -> bool {
+            row_index >= self.origin_pos.row_index
+                && row_index < self.origin_pos.row_index + self.bounds_size.row_count
+        }
+    }
+
+    impl From<&FlexBox> for DirtyRegion {
+        fn from(flex_box: &FlexBox) -> Self {
+            Self::new(flex_box.origin_pos, flex_box.bounds_size)
+        }
+    }
 }
 
 type PipelineMap = HashMap<ZOrder, Vec<RenderOps>>;
@@ -167,8 +225,30 @@ mod render_pipeline_impl {
     use super::*;
 
     impl RenderPipeline {
-        /// This will add `rhs` to `self`.
+        /// This will add `rhs` to `self`. The dirty regions of both are combined, and if
+        /// either of them didn't declare any, then neither does the result. A pipeline w/
+        /// no [RenderOps] (eg: a new one that pipelines are joined into) doesn't count.
         pub fn join_into(&mut self, mut rhs: RenderPipeline) {
+            self.maybe_dirty_regions = match (self.is_blank(), rhs.is_blank()) {
+                (true, _) => rhs.maybe_dirty_regions.take(),
+                (_, true) => self.maybe_dirty_regions.take(),
+                _ => match (
+                    self.maybe_dirty_regions.take(),
+                    rhs.maybe_dirty_regions.take(),
+                ) {
+                    (Some(mut lhs_regions), Some(rhs_regions)) => {
+                        lhs_regions.extend(rhs_regions);
+                        Some(lhs_regions)
+                    }
+                    _ => None,
+                },
+            };
+            self.toasts.append(&mut rhs.toasts);
+            self.inline_images.append(&mut rhs.inline_images);
+            if rhs.maybe_cursor_position.is_some() {
+                self.maybe_cursor_position = rhs.maybe_cursor_position;
+            }
+            self.is_background_dimmed |= rhs.is_background_dimmed;
             for (z_order, mut rhs_render_ops_vec) in rhs.drain() {
                 // Insert rhs_render_ops_vec into self_render_ops_vec.
                 match self.entry(z_order) {
@@ -185,6 +265,69 @@ mod render_pipeline_impl {
             }
         }
 
+        /// Show the terminal's cursor at the given (absolute) `position` after this
+        /// pipeline is painted, eg: in an input field. Only the component that has focus
+        /// should call this from its [Component::render], & the `position` is relative to
+        /// the terminal, so add the `style_adjusted_origin_pos` of its [FlexBox] to it.
+        /// When pipelines are [joined](RenderPipeline::join_into), the last request wins.
+        /// More info in [paint_hardware_cursor].
+        ///
+        /// ```ignore
+        /// if args.component_registry.has_focus.does_id_have_focus(self.get_id()) {
+        ///     pipeline.request_cursor_at(current_box.style_adjusted_origin_pos + caret_pos);
+        /// }
+        /// ```
+        pub fn request_cursor_at(&mut self, position: Position) {
+            self.maybe_cursor_position = Some(position);
+        }
+
+        /// Declare that only the given region of the window changed since the last frame
+        /// (in absolute terminal coordinates, eg: the [FlexBox] of a small widget), so
+        /// that the compositor can skip the rest of it. This is an optional optimization
+        /// for apps w/ big static panes, the pipeline must still have all the [RenderOps]
+        /// for the frame.
+        /// 1. A pipeline that doesn't declare any regions (the default) is painted in
+        ///    full, and it makes the pipelines that it is [joined](RenderPipeline::join_into)
+        ///    w/ painted in full too. So all the components in a frame have to declare
+        ///    their regions (or call [mark_clean](RenderPipeline::mark_clean)) to benefit.
+        /// 2. The compositor only re-composes the rows that intersect a region, and only
+        ///    compares (& paints) the cells in them. The rest come from the last frame.
+        /// 3. It falls back to a full paint when there's no last frame (eg: after a
+        ///    resize), or when the background is [dimmed](RenderPipeline::is_background_dimmed)
+        ///    in this frame or the last one.
+        ///
+        /// ```ignore
+        /// // In the render fn of the component.
+        /// if self.is_static_content_unchanged {
+        ///     pipeline.mark_clean();
+        /// } else {
+        ///     pipeline.mark_dirty(DirtyRegion::from(current_box));
+        /// }
+        /// ```
+        pub fn mark_dirty(&mut self, dirty_region: DirtyRegion) {
+            self.maybe_dirty_regions
+                .get_or_insert_with(Vec::new)
+                .push(dirty_region);
+        }
+
+        /// Declare that nothing in this pipeline changed since the last frame. More info
+        /// in [mark_dirty](RenderPipeline::mark_dirty).
+        pub fn mark_clean(&mut self) {
+            self.maybe_dirty_regions.get_or_insert_with(Vec::new);
+        }
+
+        /// Forget the declared regions, so that this pipeline is painted in full.
+        pub fn mark_all_dirty(&mut self) { self.maybe_dirty_regions = None; }
+
+        /// `true` if there are no [RenderOps] & no dirty regions were declared.
+        fn is_blank(&self) -> bool {
+            self.maybe_dirty_regions.is_none()
+                && self
+                    .pipeline_map
+                    .values()
+                    .all(|vec_render_ops| vec_render_ops.iter().all(|it| it.is_empty()))
+        }
+
         /// Add the given [RenderOps] to the pipeline at the given [ZOrder].
         pub fn push(&mut self, z_order: ZOrder, render_ops: RenderOps) {
             match self.pipeline_map.entry(z_order) {
@@ -222,6 +365,11 @@ mod render_pipeline_impl {
             // FUTURE: support termion, along w/ crossterm, by providing another impl of this fn #24
         }
 
+        /// Paint to stdout w/out the async main event loop. More info in [paint_sync].
+        pub fn paint_sync(&self, window_size: Size) -> CommonResult<()> {
+            paint_sync(self, window_size, &mut std::io::stdout())
+        }
+
         /// Move the [RenderOps] in the 'from' [ZOrder] (in self) to the 'to' [ZOrder] (in self).
         pub fn hoist(&mut self, z_order_from: ZOrder, z_order_to: ZOrder) {
             // If the 'from' [ZOrder] is not in the pipeline, then there's nothing to do.
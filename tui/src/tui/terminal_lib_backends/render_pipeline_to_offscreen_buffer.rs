@@ -31,23 +31,62 @@ impl RenderPipeline {
         shared_global_data: &SharedGlobalData,
     ) -> OffscreenBuffer {
         let my_window_size = shared_global_data.read().await.window_size;
+        self.convert_with_window_size(my_window_size)
+    }
 
+    /// Same as [convert](RenderPipeline::convert), except that the `my_window_size` is
+    /// given, so it doesn't need a [SharedGlobalData] (or an async runtime). This is used
+    /// by [paint_sync].
+    pub fn convert_with_window_size(&self, my_window_size: Size) -> OffscreenBuffer {
         let mut my_offscreen_buffer =
             OffscreenBuffer::new_with_capacity_initialized(my_window_size);
+        self.compose_into(&mut my_offscreen_buffer, None);
+        my_offscreen_buffer
+    }
+
+    /// Same as [convert_with_window_size](RenderPipeline::convert_with_window_size),
+    /// except that only the `dirty_regions` are cleared, and only the text in the rows
+    /// that intersect them is composed. Everything else is copied from the
+    /// `saved_offscreen_buffer` (the last frame, which must be the same size). More info
+    /// in [RenderPipeline::mark_dirty].
+    pub fn convert_dirty_regions(
+        &self,
+        saved_offscreen_buffer: &OffscreenBuffer,
+        dirty_regions: &[DirtyRegion],
+    ) -> OffscreenBuffer {
+        let mut my_offscreen_buffer = saved_offscreen_buffer.clone();
+        my_offscreen_buffer.my_pos = Default::default();
+        my_offscreen_buffer.my_fg_color = None;
+        my_offscreen_buffer.my_bg_color = None;
+        for dirty_region in dirty_regions {
+            my_offscreen_buffer.clear_region(dirty_region);
+        }
+        self.compose_into(&mut my_offscreen_buffer, Some(dirty_regions));
+        my_offscreen_buffer
+    }
 
+    fn compose_into(
+        &self,
+        my_offscreen_buffer: &mut OffscreenBuffer,
+        maybe_dirty_regions: Option<&[DirtyRegion]>,
+    ) {
+        let my_window_size = my_offscreen_buffer.window_size;
         let mut local_data = RenderOpsLocalData::default();
 
         for z_order in ZOrder::get_render_order().iter() {
+            if *z_order == ZOrder::Glass && self.is_background_dimmed {
+                my_offscreen_buffer.dim();
+            }
             if let Some(render_ops_vec) = self.get(z_order) {
                 for (_render_ops_index, render_ops) in render_ops_vec.iter().enumerate() {
                     for (_render_op_index, render_op) in render_ops.iter().enumerate() {
                         process_render_op(
                             render_op,
-                            shared_global_data,
-                            &mut my_offscreen_buffer,
+                            my_window_size,
+                            my_offscreen_buffer,
                             &mut local_data,
-                        )
-                        .await;
+                            maybe_dirty_regions,
+                        );
                     }
                 }
             }
@@ -57,40 +96,36 @@ impl RenderPipeline {
             let msg = format!("offscreen_buffer: \n🌟🌟🌟\n{my_offscreen_buffer:#?}");
             log_debug(msg);
         });
-
-        my_offscreen_buffer
     }
 }
 
-async fn process_render_op(
+fn process_render_op(
     render_op: &RenderOp,
-    shared_global_data: &SharedGlobalData,
+    my_window_size: Size,
     my_offscreen_buffer: &mut OffscreenBuffer,
     local_data: &mut RenderOpsLocalData,
+    maybe_dirty_regions: Option<&[DirtyRegion]>,
 ) {
     match render_op {
         // Don't process these.
         RenderOp::Noop | RenderOp::EnterRawMode | RenderOp::ExitRawMode => {}
         // Do process these.
-        RenderOp::ClearScreen => {
-            my_offscreen_buffer.clear();
-        }
+        RenderOp::ClearScreen => match maybe_dirty_regions {
+            None => my_offscreen_buffer.clear(),
+            Some(dirty_regions) => {
+                for dirty_region in dirty_regions {
+                    my_offscreen_buffer.clear_region(dirty_region);
+                }
+            }
+        },
         RenderOp::MoveCursorPositionAbs(new_abs_pos) => {
-            my_offscreen_buffer.my_pos = sanitize_and_save_abs_position(
-                *new_abs_pos,
-                shared_global_data,
-                local_data,
-            )
-            .await;
+            my_offscreen_buffer.my_pos =
+                save_sanitized_abs_position(*new_abs_pos, my_window_size, local_data);
         }
         RenderOp::MoveCursorPositionRelTo(box_origin_pos_ref, content_rel_pos_ref) => {
             let new_abs_pos = *box_origin_pos_ref + *content_rel_pos_ref;
-            my_offscreen_buffer.my_pos = sanitize_and_save_abs_position(
-                new_abs_pos,
-                shared_global_data,
-                local_data,
-            )
-            .await;
+            my_offscreen_buffer.my_pos =
+                save_sanitized_abs_position(new_abs_pos, my_window_size, local_data);
         }
         RenderOp::SetFgColor(fg_color_ref) => {
             my_offscreen_buffer.my_fg_color = Some(*fg_color_ref);
@@ -114,25 +149,62 @@ async fn process_render_op(
         ) => {
             // This is a no-op. This operation is executed by RenderOpImplCrossterm.
         }
+        // The rows that aren't dirty are already in the buffer, so the text is skipped
+        // (but the cursor still moves past it).
+        RenderOp::PaintTextWithAttributes(arg_text_ref, _)
+            if maybe_dirty_regions.is_some_and(|dirty_regions| {
+                !dirty_regions
+                    .iter()
+                    .any(|it| it.contains_row(my_offscreen_buffer.my_pos.row_index))
+            }) =>
+        {
+            let new_pos = my_offscreen_buffer.my_pos
+                + position! {
+                    col_index: UnicodeString::str_display_width(arg_text_ref),
+                    row_index: 0
+                };
+            my_offscreen_buffer.my_pos =
+                save_sanitized_abs_position(new_pos, my_window_size, local_data);
+        }
         RenderOp::PaintTextWithAttributes(arg_text_ref, maybe_style_ref) => {
-            let result_new_pos = print_text_with_attributes(
-                shared_global_data,
+            let result_new_pos = print_plain_text_sync(
                 arg_text_ref,
                 maybe_style_ref,
                 my_offscreen_buffer,
                 None,
-            )
-            .await;
+            );
             if let Ok(new_pos) = result_new_pos {
-                my_offscreen_buffer.my_pos = sanitize_and_save_abs_position(
-                    new_pos,
-                    shared_global_data,
-                    local_data,
-                )
-                .await;
+                my_offscreen_buffer.my_pos =
+                    save_sanitized_abs_position(new_pos, my_window_size, local_data);
             }
         }
     }
+
+    /// Like [sanitize_and_save_abs_position], w/out a [SharedGlobalData].
+    fn save_sanitized_abs_position(
+        orig_abs_pos: Position,
+        my_window_size: Size,
+        local_data: &mut RenderOpsLocalData,
+    ) -> Position {
+        let sanitized_abs_pos = sanitize_abs_position(orig_abs_pos, my_window_size);
+        local_data.cursor_position = sanitized_abs_pos;
+        sanitized_abs_pos
+    }
+}
+
+/// Same as [print_plain_text_sync], for the async render path.
+pub async fn print_plain_text(
+    arg_text_ref: &str,
+    maybe_style_ref: &Option<Style>,
+    my_offscreen_buffer: &mut OffscreenBuffer,
+    maybe_max_display_col_count: Option<ChUnit>,
+) -> CommonResult<Position> {
+    print_plain_text_sync(
+        arg_text_ref,
+        maybe_style_ref,
+        my_offscreen_buffer,
+        maybe_max_display_col_count,
+    )
 }
 
 /// This diagram shows what happens per line of text.
@@ -145,7 +217,7 @@ async fn process_render_op(
 /// <---------------- maybe_max_display_col_count ---------------->
 /// C0123456789012345678901234567890123456789012345678901234567890
 /// ```
-pub async fn print_plain_text(
+pub fn print_plain_text_sync(
     arg_text_ref: &str,
     maybe_style_ref: &Option<Style>,
     my_offscreen_buffer: &mut OffscreenBuffer,
@@ -183,7 +255,7 @@ pub async fn print_plain_text(
 
     call_if_true!(DEBUG_TUI_COMPOSITOR, {
         let msg = format!(
-            "\n🚀🚀🚀 print_plain_text():
+            "\n🚀🚀🚀 print_plain_text_sync():
             insertion at: display_row_index: {}, display_col_index: {}, window_size: {:?},
             text: '{}',
             width: {}",
@@ -984,4 +1056,62 @@ mod tests {
             assert_eq2!(my_offscreen_buffer.buffer[1][9], PixelChar::Spacer);
         }
     }
+
+    #[test]
+    fn test_paint_sync() {
+        let pipeline = render_pipeline!(@new ZOrder::Normal =>
+            RenderOp::MoveCursorPositionAbs(position! { col_index: 0, row_index: 0 }),
+            RenderOp::PaintTextWithAttributes(
+                "hi".to_string(), Some(style! { attrib: [bold] })),
+            RenderOp::PaintTextWithAttributes(" you".to_string(), None),
+            RenderOp::MoveCursorPositionAbs(position! { col_index: 2, row_index: 1 }),
+            RenderOp::PaintTextWithAttributes("x".to_string(), None)
+        );
+
+        // No event loop or shared global data is needed, and the spacers at the end of
+        // each line are left out.
+        let mut output: Vec<u8> = vec![];
+        paint_sync(
+            &pipeline,
+            size! { col_count: 10, row_count: 3 },
+            &mut output,
+        )
+        .unwrap();
+        assert_eq2!(
+            String::from_utf8(output).unwrap(),
+            "\x1b[1mhi\x1b[0m you\n  x\n\n"
+        );
+    }
+
+    #[test]
+    fn test_convert_w_background_dimmed() {
+        let mut pipeline = render_pipeline!(@new ZOrder::Normal =>
+            RenderOp::MoveCursorPositionAbs(position! { col_index: 0, row_index: 0 }),
+            RenderOp::PaintTextWithAttributes(
+                "ab".to_string(), Some(style! { attrib: [bold] }))
+        );
+        render_pipeline!(@push_into pipeline at ZOrder::Glass =>
+            RenderOp::MoveCursorPositionAbs(position! { col_index: 1, row_index: 0 }),
+            RenderOp::PaintTextWithAttributes("x".to_string(), None)
+        );
+        pipeline.is_background_dimmed = true;
+
+        // Only what is painted below the glass layer is dimmed.
+        let window_size = size! { col_count: 4, row_count: 1 };
+        let offscreen_buffer = pipeline.convert_with_window_size(window_size);
+        assert_eq2!(
+            offscreen_buffer.buffer[0][0],
+            PixelChar::PlainText {
+                content: GraphemeClusterSegment::from("a"),
+                maybe_style: Some(style! { attrib: [bold, dim] }),
+            }
+        );
+        assert_eq2!(
+            offscreen_buffer.buffer[0][1],
+            PixelChar::PlainText {
+                content: GraphemeClusterSegment::from("x"),
+                maybe_style: None,
+            }
+        );
+    }
 }
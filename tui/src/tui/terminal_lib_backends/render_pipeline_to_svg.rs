@@ -0,0 +1,285 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+//! Render a [RenderPipeline] to a self-contained SVG image, eg: to embed a screenshot of a
+//! TUI app in a README, or to check its output in CI. The pipeline is converted to an
+//! [OffscreenBuffer] (just like it is before it is painted), & then each cell is drawn as
+//! a background rectangle & a `<text>` element on a grid of
+//! [SvgFontMetrics::cell_width] by [SvgFontMetrics::cell_height].
+//!
+//! - Bold & italic are `font-weight` & `font-style`, underline & strikethrough are
+//!   `text-decoration`, dim is `opacity`, & reverse swaps the colors.
+//! - Colors that aren't set (or are [TuiColor::Reset]) use the
+//!   [default_fg_color](SvgFontMetrics::default_fg_color) &
+//!   [default_bg_color](SvgFontMetrics::default_bg_color).
+//! - Each grapheme cluster is placed in its own cell(s), so the grid lines up even if the
+//!   font that renders the SVG isn't exactly [cell_width](SvgFontMetrics::cell_width)
+//!   wide.
+
+use r3bl_rs_utils_core::*;
+
+use crate::*;
+
+/// The font & the size of each cell in the SVG. The [Default] is a 14px `monospace` font,
+/// white on black.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SvgFontMetrics {
+    /// Value of the `font-family` attribute, eg: `"'Fira Code', monospace"`.
+    pub font_family: String,
+    /// In pixels.
+    pub font_size: f32,
+    /// In pixels, the width of 1 col.
+    pub cell_width: f32,
+    /// In pixels, the height of 1 row.
+    pub cell_height: f32,
+    /// In pixels, from the top of a row to the baseline of its text.
+    pub baseline_offset: f32,
+    pub default_fg_color: RgbValue,
+    pub default_bg_color: RgbValue,
+}
+
+impl Default for SvgFontMetrics {
+    fn default() -> Self {
+        Self {
+            font_family: "monospace".to_string(),
+            font_size: 14.0,
+            cell_width: 8.4,
+            cell_height: 18.0,
+            baseline_offset: 14.0,
+            default_fg_color: RgbValue::from_u8(255, 255, 255),
+            default_bg_color: RgbValue::from_u8(0, 0, 0),
+        }
+    }
+}
+
+/// Render the `pipeline` into a window that is `window_size` big, & return it as an SVG
+/// document. More info in the [module docs](self).
+pub fn render_pipeline_to_svg(
+    pipeline: &RenderPipeline,
+    window_size: Size,
+    font_metrics: &SvgFontMetrics,
+) -> String {
+    let offscreen_buffer = pipeline.convert_with_window_size(window_size);
+    offscreen_buffer_to_svg(&offscreen_buffer, font_metrics)
+}
+
+/// Same as [render_pipeline_to_svg], for an [OffscreenBuffer] that has already been
+/// rendered.
+pub fn offscreen_buffer_to_svg(
+    offscreen_buffer: &OffscreenBuffer,
+    font_metrics: &SvgFontMetrics,
+) -> String {
+    let SvgFontMetrics {
+        font_family,
+        font_size,
+        cell_width,
+        cell_height,
+        baseline_offset,
+        default_fg_color,
+        default_bg_color,
+    } = font_metrics;
+
+    let width = ch!(@to_usize offscreen_buffer.window_size.col_count) as f32 * cell_width;
+    let height =
+        ch!(@to_usize offscreen_buffer.window_size.row_count) as f32 * cell_height;
+
+    let mut backgrounds = vec![];
+    let mut texts = vec![];
+
+    for (row_index, line) in offscreen_buffer.buffer.iter().enumerate() {
+        let y = row_index as f32 * cell_height;
+        for (col_index, pixel_char) in line.iter().enumerate() {
+            let PixelChar::PlainText {
+                content,
+                maybe_style,
+            } = pixel_char
+            else {
+                continue;
+            };
+            let style = maybe_style.unwrap_or_default();
+            let x = col_index as f32 * cell_width;
+
+            let maybe_fg_color = style.color_fg.and_then(get_rgb_value);
+            let maybe_bg_color = style.color_bg.and_then(get_rgb_value);
+            let (fg_color, maybe_bg_color) = if style.reverse {
+                (
+                    maybe_bg_color.unwrap_or(*default_bg_color),
+                    Some(maybe_fg_color.unwrap_or(*default_fg_color)),
+                )
+            } else {
+                (maybe_fg_color.unwrap_or(*default_fg_color), maybe_bg_color)
+            };
+
+            if let Some(bg_color) = maybe_bg_color {
+                let col_count = ch!(@to_usize content.unicode_width).max(1);
+                backgrounds.push(format!(
+                    r#"<rect x="{}" y="{}" width="{}" height="{}" fill="{}"/>"#,
+                    fmt_number(x),
+                    fmt_number(y),
+                    fmt_number(col_count as f32 * cell_width),
+                    fmt_number(*cell_height),
+                    fmt_color(bg_color),
+                ));
+            }
+
+            if style.hidden || content.string.trim().is_empty() {
+                continue;
+            }
+            let mut attributes = format!(r#" fill="{}""#, fmt_color(fg_color));
+            if style.bold {
+                attributes.push_str(r#" font-weight="bold""#);
+            }
+            if style.italic {
+                attributes.push_str(r#" font-style="italic""#);
+            }
+            match (style.underline, style.strikethrough) {
+                (true, true) => {
+                    attributes.push_str(r#" text-decoration="underline line-through""#)
+                }
+                (true, false) => attributes.push_str(r#" text-decoration="underline""#),
+                (false, true) => {
+                    attributes.push_str(r#" text-decoration="line-through""#)
+                }
+                (false, false) => {}
+            }
+            if style.dim {
+                attributes.push_str(r#" opacity="0.5""#);
+            }
+            texts.push(format!(
+                r#"<text x="{}" y="{}"{attributes}>{}</text>"#,
+                fmt_number(x),
+                fmt_number(y + baseline_offset),
+                escape_xml(&content.string),
+            ));
+        }
+    }
+
+    let mut lines = vec![
+        format!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{0}" height="{1}" viewBox="0 0 {0} {1}">"#,
+            fmt_number(width),
+            fmt_number(height),
+        ),
+        format!(
+            r#"<rect width="100%" height="100%" fill="{}"/>"#,
+            fmt_color(*default_bg_color)
+        ),
+    ];
+    lines.append(&mut backgrounds);
+    lines.push(format!(
+        r#"<g font-family="{}" font-size="{}" xml:space="preserve">"#,
+        escape_xml(font_family),
+        fmt_number(*font_size),
+    ));
+    lines.append(&mut texts);
+    lines.push("</g>".to_string());
+    lines.push("</svg>".to_string());
+    lines.join("\n") + "\n"
+}
+
+/// Returns [None] for [TuiColor::Reset], so that the default color is used.
+fn get_rgb_value(color: TuiColor) -> Option<RgbValue> {
+    match color {
+        TuiColor::Ansi(ansi_value) => Some(RgbValue::from(ansi_value)),
+        _ => RgbValue::try_from_tui_color(color).ok(),
+    }
+}
+
+fn fmt_color(color: RgbValue) -> String {
+    format!("#{:02x}{:02x}{:02x}", color.red, color.green, color.blue)
+}
+
+/// At most 2 decimal places, w/out trailing zeros, eg: `16.8` & not `16.800001`.
+fn fmt_number(number: f32) -> String {
+    let it = format!("{number:.2}");
+    it.trim_end_matches('0').trim_end_matches('.').to_string()
+}
+
+fn escape_xml(text: &str) -> String {
+    let mut it = String::with_capacity(text.len());
+    for char in text.chars() {
+        match char {
+            '&' => it.push_str("&amp;"),
+            '<' => it.push_str("&lt;"),
+            '>' => it.push_str("&gt;"),
+            '"' => it.push_str("&quot;"),
+            '\'' => it.push_str("&apos;"),
+            _ => it.push(char),
+        }
+    }
+    it
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_rs_utils_core::*;
+    use r3bl_rs_utils_macro::style;
+
+    use super::*;
+
+    #[test]
+    fn test_render_pipeline_to_svg() {
+        let pipeline = render_pipeline!(@new ZOrder::Normal =>
+            RenderOp::MoveCursorPositionAbs(position! { col_index: 0, row_index: 0 }),
+            RenderOp::SetFgColor(color!(255, 0, 0)),
+            RenderOp::PaintTextWithAttributes(
+                "<b".to_string(),
+                Some(style! { attrib: [bold, italic] })
+            ),
+            RenderOp::ResetColor,
+            RenderOp::MoveCursorPositionAbs(position! { col_index: 1, row_index: 1 }),
+            RenderOp::PaintTextWithAttributes(
+                "x".to_string(),
+                Some(style! { attrib: [reverse] })
+            )
+        );
+        let font_metrics = SvgFontMetrics {
+            cell_width: 10.0,
+            cell_height: 20.0,
+            baseline_offset: 15.0,
+            ..Default::default()
+        };
+        let svg = render_pipeline_to_svg(
+            &pipeline,
+            size! { col_count: 3, row_count: 2 },
+            &font_metrics,
+        );
+
+        assert!(svg.starts_with(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="30" height="40" viewBox="0 0 30 40">"#
+        ));
+        assert!(svg.contains(
+            r##"<text x="0" y="15" fill="#ff0000" font-weight="bold" font-style="italic">&lt;</text>"##
+        ));
+        assert!(svg.contains(
+            r##"<text x="10" y="15" fill="#ff0000" font-weight="bold" font-style="italic">b</text>"##
+        ));
+        // Reverse swaps the default colors.
+        assert!(svg.contains(
+            r##"<rect x="10" y="20" width="10" height="20" fill="#ffffff"/>"##
+        ));
+        assert!(svg.contains(r##"<text x="10" y="35" fill="#000000">x</text>"##));
+        assert!(svg.trim_end().ends_with("</svg>"));
+    }
+
+    #[test]
+    fn test_fmt_number() {
+        assert_eq2!(fmt_number(16.800001), "16.8");
+        assert_eq2!(fmt_number(20.0), "20");
+        assert_eq2!(fmt_number(0.0), "0");
+    }
+}
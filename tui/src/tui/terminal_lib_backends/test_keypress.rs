@@ -134,4 +134,20 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_keypress_display() {
+        assert_eq2!(keypress!(@char 'a').to_string(), "a");
+        assert_eq2!(keypress!(@char ' ').to_string(), "Space");
+        assert_eq2!(
+            keypress!(@char ModifierKeysMask::CTRL | ModifierKeysMask::SHIFT, 'p')
+                .to_string(),
+            "Ctrl+Shift+p"
+        );
+        assert_eq2!(
+            keypress!(@special ModifierKeysMask::ALT, SpecialKey::Left).to_string(),
+            "Alt+Left"
+        );
+        assert_eq2!(keypress!(@fn FunctionKey::F1).to_string(), "F1");
+    }
 }
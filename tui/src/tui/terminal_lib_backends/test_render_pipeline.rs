@@ -126,6 +126,29 @@ mod tests {
         };
     }
 
+    #[test]
+    fn join_pipelines_w_cursor_request() {
+        let mut pipeline_1 = render_pipeline!();
+        pipeline_1.request_cursor_at(position!(col_index: 2, row_index: 3));
+
+        // No request, so the one in pipeline_1 is kept.
+        let pipeline_2 = render_pipeline!();
+        pipeline_1.join_into(pipeline_2);
+        assert_eq2!(
+            pipeline_1.maybe_cursor_position,
+            Some(position!(col_index: 2, row_index: 3))
+        );
+
+        // The last request wins.
+        let mut pipeline_3 = render_pipeline!();
+        pipeline_3.request_cursor_at(position!(col_index: 5, row_index: 1));
+        pipeline_1.join_into(pipeline_3);
+        assert_eq2!(
+            pipeline_1.maybe_cursor_position,
+            Some(position!(col_index: 5, row_index: 1))
+        );
+    }
+
     #[test]
     fn hoist_z_order_in_pipeline() {
         let mut pipeline = render_pipeline!();
@@ -144,4 +167,99 @@ mod tests {
             2
         );
     }
+
+    #[test]
+    fn join_dirty_regions() {
+        let region_1 = DirtyRegion::new(
+            position!(col_index: 0, row_index: 0),
+            size!(col_count: 2, row_count: 1),
+        );
+        let region_2 = DirtyRegion::new(
+            position!(col_index: 4, row_index: 2),
+            size!(col_count: 3, row_count: 2),
+        );
+        assert!(region_2.contains(position!(col_index: 6, row_index: 3)));
+        assert!(!region_2.contains(position!(col_index: 7, row_index: 3)));
+        assert!(!region_2.contains_row(ch!(4)));
+
+        // A blank pipeline takes the regions of the ones that are joined into it.
+        let mut pipeline = render_pipeline!();
+        let mut pipeline_1 =
+            render_pipeline!(@new ZOrder::Normal => RenderOp::ResetColor);
+        pipeline_1.mark_dirty(region_1);
+        pipeline.join_into(pipeline_1);
+        assert_eq2!(pipeline.maybe_dirty_regions, Some(vec![region_1]));
+
+        // A clean pipeline doesn't add any regions.
+        let mut pipeline_2 =
+            render_pipeline!(@new ZOrder::Normal => RenderOp::ResetColor);
+        pipeline_2.mark_clean();
+        pipeline.join_into(pipeline_2);
+        assert_eq2!(pipeline.maybe_dirty_regions, Some(vec![region_1]));
+
+        let mut pipeline_3 = render_pipeline!(@new ZOrder::High => RenderOp::ResetColor);
+        pipeline_3.mark_dirty(region_2);
+        pipeline.join_into(pipeline_3);
+        assert_eq2!(pipeline.maybe_dirty_regions, Some(vec![region_1, region_2]));
+
+        // A pipeline that didn't declare any regions has to be painted in full.
+        let pipeline_4 = render_pipeline!(@new ZOrder::Normal => RenderOp::ResetColor);
+        pipeline.join_into(pipeline_4);
+        assert_eq2!(pipeline.maybe_dirty_regions, None);
+    }
+
+    #[test]
+    fn convert_dirty_regions() {
+        let window_size = size!(col_count: 5, row_count: 3);
+        let make_pipeline = |row_0: &str, row_1: &str| {
+            render_pipeline!(@new ZOrder::Normal =>
+              RenderOp::MoveCursorPositionAbs(position!(col_index: 0, row_index: 0)),
+              RenderOp::PaintTextWithAttributes(row_0.into(), None),
+              RenderOp::MoveCursorPositionAbs(position!(col_index: 0, row_index: 1)),
+              RenderOp::PaintTextWithAttributes(row_1.into(), None)
+            )
+        };
+        let saved_offscreen_buffer =
+            make_pipeline("aaa", "bbb").convert_with_window_size(window_size);
+
+        // Only row 1 changed.
+        let dirty_regions = [DirtyRegion::new(
+            position!(col_index: 0, row_index: 1),
+            size!(col_count: 5, row_count: 1),
+        )];
+        let mut pipeline = make_pipeline("aaa", "cc");
+        pipeline.mark_dirty(dirty_regions[0]);
+        let offscreen_buffer =
+            pipeline.convert_dirty_regions(&saved_offscreen_buffer, &dirty_regions);
+        assert_eq2!(
+            offscreen_buffer,
+            make_pipeline("aaa", "cc").convert_with_window_size(window_size)
+        );
+
+        // The cells that changed are the only ones that are painted.
+        let OffscreenBufferDiffResult::Comparable(diff_chunks) =
+            saved_offscreen_buffer.diff_in_regions(&offscreen_buffer, &dirty_regions)
+        else {
+            panic!("The buffers are the same size");
+        };
+        assert_eq2!(
+            diff_chunks
+                .iter()
+                .map(|(position, _)| *position)
+                .collect::<Vec<_>>(),
+            vec![
+                position!(col_index: 0, row_index: 1),
+                position!(col_index: 1, row_index: 1),
+                position!(col_index: 2, row_index: 1),
+            ]
+        );
+
+        // The rows that aren't dirty aren't composed, they come from the last frame.
+        let offscreen_buffer = make_pipeline("zzz", "cc")
+            .convert_dirty_regions(&saved_offscreen_buffer, &dirty_regions);
+        assert_eq2!(
+            offscreen_buffer,
+            make_pipeline("aaa", "cc").convert_with_window_size(window_size)
+        );
+    }
 }
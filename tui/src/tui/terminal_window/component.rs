@@ -87,6 +87,12 @@ where
         args: ComponentScopeArgs<'_, S, A>,
         input_event: &InputEvent,
     ) -> CommonResult<EventPropagation>;
+
+    /// This is an optional method that is called once the terminal window has stopped being
+    /// resized (rapid resize events are debounced). Components that cache anything derived from
+    /// their bounds (eg: scroll offsets, wrapped lines, viewport sizes) should invalidate it here.
+    /// The component is rendered w/ its new bounds right after this.
+    fn handle_resize(&mut self, _new_size: Size) {}
 }
 
 #[async_trait]
@@ -35,8 +35,16 @@ pub enum Continuation<T> {
 #[derive(Clone, Debug, PartialEq, Eq)]
 #[non_exhaustive]
 pub enum EventPropagation {
+    /// The event was handled, and the [main_event_loop] renders the app right away.
     ConsumedRender,
+    /// The event was handled, and the handler dispatched an action (or will, eg: after an
+    /// async task). The store subscriber renders the app once the state changes.
     Consumed,
+    /// The event was handled, but nothing changed, so there is nothing to render, eg:
+    /// pressing left when the caret is already at the start of the buffer. Unlike
+    /// [EventPropagation::Consumed], the handler must not dispatch any actions either.
+    ConsumedNoRender,
+    /// The event wasn't handled, so it is passed on (eg: to check the exit keys).
     Propagate,
     ExitMainEventLoop,
 }
@@ -15,7 +15,7 @@
  *   limitations under the License.
  */
 
-use std::{fmt::Debug, sync::Arc};
+use std::{fmt::Debug, sync::Arc, time::Instant};
 
 use async_trait::async_trait;
 use get_size::GetSize;
@@ -68,12 +68,35 @@ impl TerminalWindow {
         store: Store<S, A>,
         exit_keys: Vec<InputEvent>,
     ) -> CommonResult<()>
+    where
+        S: Debug + Default + Clone + PartialEq + Sync + Send + 'static,
+        A: Debug + Default + Clone + Sync + Send + 'static,
+    {
+        Self::main_event_loop_with_max_fps(shared_app, store, exit_keys, DEFAULT_MAX_FPS)
+            .await
+    }
+
+    /// Same as [main_event_loop](TerminalWindow::main_event_loop), except that the app
+    /// is painted at most `max_fps` times per second (`0` turns this off). Render
+    /// requests that arrive in between frames are coalesced. More info in
+    /// [RenderThrottle].
+    pub async fn main_event_loop_with_max_fps<S, A>(
+        shared_app: SharedApp<S, A>,
+        store: Store<S, A>,
+        exit_keys: Vec<InputEvent>,
+        max_fps: u16,
+    ) -> CommonResult<()>
     where
         S: Debug + Default + Clone + PartialEq + Sync + Send + 'static,
         A: Debug + Default + Clone + Sync + Send + 'static,
     {
         // Initialize the terminal window data struct.
-        let _global_data = GlobalData::try_to_create_instance()?;
+        let mut _global_data = GlobalData::try_to_create_instance()?;
+        _global_data.render_throttle = RenderThrottle::new(max_fps);
+
+        // mpsc channel to fire render ticks requested by components (eg: animations).
+        let (render_tick_sender, mut render_tick_receiver) = mpsc::channel::<()>(1);
+        _global_data.maybe_render_tick_sender = Some(render_tick_sender);
         let shared_global_data: SharedGlobalData = Arc::new(RwLock::new(_global_data));
 
         // Start raw mode.
@@ -91,7 +114,7 @@ impl TerminalWindow {
         let mut async_event_stream = AsyncEventStream::default();
 
         // Perform first render.
-        AppManager::render_app(&shared_store, &shared_app, &shared_global_data, None)
+        AppManager::request_render(&shared_store, &shared_app, &shared_global_data, None)
             .await?;
 
         shared_global_data
@@ -113,6 +136,18 @@ impl TerminalWindow {
                     }
                 }
 
+                // Handle render tick.
+                Some(_) = render_tick_receiver.recv() => {
+                    shared_global_data.write().await.render_tick.on_tick();
+                    let _ = AppManager::request_render(
+                        &shared_store,
+                        &shared_app,
+                        &shared_global_data,
+                        None,
+                    )
+                    .await;
+                }
+
                 // Handle input event.
                 maybe_input_event = async_event_stream.try_to_get_input_event() => {
                     if let Some(input_event) = maybe_input_event {
@@ -205,7 +240,7 @@ impl TerminalWindow {
                     };
                 }
                 EventPropagation::ConsumedRender => {
-                    let _ = AppManager::render_app(
+                    let _ = AppManager::request_render(
                         &shared_store,
                         &shared_app,
                         &shared_global_data,
@@ -213,7 +248,7 @@ impl TerminalWindow {
                     )
                     .await;
                 }
-                EventPropagation::Consumed => {}
+                EventPropagation::Consumed | EventPropagation::ConsumedNoRender => {}
                 EventPropagation::ExitMainEventLoop => {
                     // Exit the main event loop.
                     let _ = exit_channel_sender.send(true).await;
@@ -223,7 +258,9 @@ impl TerminalWindow {
     }
 
     /// Before any app gets to process the `input_event`, perform special handling in case it is a
-    /// resize event.
+    /// resize event. The new size is saved right away, but the full repaint & re-render are
+    /// debounced, so that dragging the terminal window doesn't re-layout the app for every
+    /// intermediate size. More info in [ResizeDebouncer].
     pub async fn handle_resize_event<S, A>(
         input_event: &InputEvent,
         shared_global_data: &SharedGlobalData,
@@ -234,18 +271,46 @@ impl TerminalWindow {
         A: Debug + Default + Clone + Sync + Send + 'static,
     {
         if let InputEvent::Resize(new_size) = input_event {
-            shared_global_data.write().await.set_size(*new_size);
-            shared_global_data
-                .write()
-                .await
-                .maybe_saved_offscreen_buffer = None;
-            let _ = AppManager::render_app(
-                shared_store,
-                shared_app,
-                shared_global_data,
-                None,
-            )
-            .await;
+            let new_size = *new_size;
+            let (generation, quiet_period) = {
+                let mut global_data = shared_global_data.write().await;
+                global_data.set_size(new_size);
+                (
+                    global_data.resize_debouncer.on_resize(),
+                    global_data.resize_debouncer.quiet_period,
+                )
+            };
+
+            let shared_global_data = shared_global_data.clone();
+            let shared_store = shared_store.clone();
+            let shared_app = shared_app.clone();
+            tokio::spawn(async move {
+                tokio::time::sleep(quiet_period).await;
+
+                // Another resize arrived in the meantime, it will take care of this.
+                if !shared_global_data
+                    .read()
+                    .await
+                    .resize_debouncer
+                    .is_latest(generation)
+                {
+                    return;
+                }
+
+                ComponentRegistry::notify_resize(
+                    shared_app.write().await.get_component_registry(),
+                    new_size,
+                )
+                .await;
+                shared_global_data.write().await.request_full_repaint();
+                let _ = AppManager::request_render(
+                    &shared_store,
+                    &shared_app,
+                    &shared_global_data,
+                    None,
+                )
+                .await;
+            });
         }
     }
 }
@@ -264,10 +329,10 @@ where
 impl<S, A> AsyncSubscriber<S> for AppManager<S, A>
 where
     S: Debug + Default + Clone + PartialEq + Sync + Send + 'static,
-    A: Debug + Default + Clone + Sync + Send,
+    A: Debug + Default + Clone + Sync + Send + 'static,
 {
     async fn run(&self, my_state: S) {
-        let result = AppManager::render_app(
+        let result = AppManager::request_render(
             &self.shared_store,
             &self.shared_app,
             &self.shared_global_data,
@@ -286,7 +351,7 @@ where
 impl<S, A> AppManager<S, A>
 where
     S: Debug + Default + Clone + PartialEq + Sync + Send + 'static,
-    A: Debug + Default + Clone + Sync + Send,
+    A: Debug + Default + Clone + Sync + Send + 'static,
 {
     fn new_box(
         shared_app: &SharedApp<S, A>,
@@ -300,7 +365,9 @@ where
         })
     }
 
-    /// Pass the event to the `shared_app` for further processing.
+    /// Pass the event to the `shared_app` for further processing. The global hotkeys in
+    /// the app's [ComponentRegistry] get the first chance to handle it. More info in
+    /// [register_global_hotkey](ComponentRegistry::register_global_hotkey).
     pub async fn route_input_to_app(
         shared_global_data: SharedGlobalData,
         shared_store: SharedStore<S, A>,
@@ -308,6 +375,15 @@ where
         input_event: InputEvent,
     ) -> CommonResult<EventPropagation> {
         throws_with_return!({
+            // Global hotkeys win over the app & the focused component.
+            if let Some(event_propagation) = ComponentRegistry::try_handle_global_hotkey(
+                shared_app.write().await.get_component_registry(),
+                &input_event,
+                &shared_store,
+            ) {
+                return Ok(event_propagation);
+            }
+
             // Create global scope args.
             let state = shared_store.read().await.get_state();
             let window_size = shared_global_data.read().await.get_size();
@@ -327,6 +403,75 @@ where
         });
     }
 
+    /// Paint the app now, or later if it was painted less than a frame ago. The
+    /// [RenderThrottle] decides which. A deferred render uses the latest state in the
+    /// store, so `maybe_state` is ignored in that case.
+    pub async fn request_render(
+        shared_store: &SharedStore<S, A>,
+        shared_app: &SharedApp<S, A>,
+        shared_global_data: &SharedGlobalData,
+        maybe_state: Option<S>,
+    ) -> CommonResult<()> {
+        let decision = shared_global_data
+            .write()
+            .await
+            .render_throttle
+            .request_render(Instant::now());
+
+        match decision {
+            RenderThrottleDecision::RenderNow => {
+                AppManager::render_app(
+                    shared_store,
+                    shared_app,
+                    shared_global_data,
+                    maybe_state,
+                )
+                .await
+            }
+            RenderThrottleDecision::RenderAfter(delay) => {
+                let shared_store = shared_store.clone();
+                let shared_app = shared_app.clone();
+                let shared_global_data = shared_global_data.clone();
+                tokio::spawn(async move {
+                    tokio::time::sleep(delay).await;
+                    shared_global_data
+                        .write()
+                        .await
+                        .render_throttle
+                        .on_deferred_render(Instant::now());
+                    let _ = AppManager::render_app(
+                        &shared_store,
+                        &shared_app,
+                        &shared_global_data,
+                        None,
+                    )
+                    .await;
+                });
+                Ok(())
+            }
+            RenderThrottleDecision::Coalesced => Ok(()),
+        }
+    }
+
+    /// If any component requested a [RenderTick] while it was rendered, then fire it once
+    /// the requested delay has passed.
+    async fn schedule_render_tick(shared_global_data: &SharedGlobalData) {
+        let (maybe_delay, maybe_sender) = {
+            let mut global_data = shared_global_data.write().await;
+            (
+                global_data.render_tick.take_delay_to_schedule(),
+                global_data.maybe_render_tick_sender.clone(),
+            )
+        };
+
+        if let (Some(delay), Some(sender)) = (maybe_delay, maybe_sender) {
+            tokio::spawn(async move {
+                tokio::time::sleep(delay).await;
+                let _ = sender.send(()).await;
+            });
+        }
+    }
+
     pub async fn render_app(
         shared_store: &SharedStore<S, A>,
         shared_app: &SharedApp<S, A>,
@@ -334,6 +479,8 @@ where
         maybe_state: Option<S>,
     ) -> CommonResult<()> {
         throws!({
+            RenderProfiler::begin_frame();
+
             // Create global scope args.
             let window_size = shared_global_data.read().await.get_size();
             let state: S = if let Some(state) = maybe_state {
@@ -359,6 +506,7 @@ where
                 Ok(render_window_size_too_small(window_size))
             } else {
                 // Call app_render.
+                let _span = RenderStageSpan::enter(RenderStage::Layout);
                 shared_app.write().await.app_render(global_scope_args).await
             };
 
@@ -373,10 +521,21 @@ where
                         log_error(msg);
                     });
                 }
-                Ok(render_pipeline) => {
-                    render_pipeline
-                        .paint(FlushKind::ClearBeforeFlush, shared_global_data)
-                        .await;
+                Ok(mut render_pipeline) => {
+                    render_toasts(&mut render_pipeline, shared_global_data).await;
+
+                    {
+                        let _span = RenderStageSpan::enter(RenderStage::Paint);
+                        render_pipeline
+                            .paint(FlushKind::ClearBeforeFlush, shared_global_data)
+                            .await;
+                        paint_inline_images(&render_pipeline, shared_global_data).await;
+                        paint_cursor_shape(shared_global_data).await;
+                        paint_hardware_cursor(&render_pipeline, shared_global_data).await;
+                    }
+                    RenderProfiler::end_frame();
+
+                    AppManager::<S, A>::schedule_render_tick(shared_global_data).await;
 
                     telemetry_global_static::set_end_ts();
 
@@ -26,7 +26,13 @@ use crate::*;
 /// multiple renders.
 /// 1. It is entirely up to the [App] on how this [ComponentRegistryMap] is used.
 /// 2. The methods provided allow components to be added to the map.
-#[derive(Default)]
+/// 3. [GlobalHotkey]s can be registered here, they work regardless of which component has
+///    focus. More info in [register_global_hotkey](ComponentRegistry::register_global_hotkey).
+/// 4. Focus can be moved between the components w/
+///    [focus_next](ComponentRegistry::focus_next) (eg: when Tab is pressed).
+/// 5. Modal components (eg: a help modal over a dialog) are stacked w/
+///    [push_modal](ComponentRegistry::push_modal) &
+///    [pop_modal](ComponentRegistry::pop_modal).
 pub struct ComponentRegistry<S, A>
 where
     S: Debug + Default + Clone + PartialEq + Sync + Send,
@@ -34,20 +40,65 @@ where
 {
     pub components: ComponentRegistryMap<S, A>,
     pub has_focus: HasFocus,
+    pub global_hotkeys: Vec<GlobalHotkey<S, A>>,
+    /// The ids of the [components](ComponentRegistry::components) in the order in which
+    /// they were [put](ComponentRegistry::put) (the map doesn't keep it).
+    ids_in_insertion_order: Vec<FlexBoxId>,
+    /// Set w/ [set_tab_index](ComponentRegistry::set_tab_index).
+    tab_indices: HashMap<FlexBoxId, usize>,
+    /// When `true` (the default), everything below the top modal is dimmed while it is
+    /// open. More info in [push_modal](ComponentRegistry::push_modal).
+    pub dim_background_for_modals: bool,
 }
 
 pub type ComponentRegistryMap<S, A> = HashMap<FlexBoxId, SharedComponent<S, A>>;
 
+/// The returned [EventPropagation] decides what happens to the [InputEvent] next. Return
+/// [EventPropagation::Propagate] to let the app (& the focused component) handle it too.
+pub type OnGlobalHotkeyFn<S, A> = fn(&SharedStore<S, A>, &InputEvent) -> EventPropagation;
+
+/// An action (eg: quit, help, command palette) that runs when any of the `input_events`
+/// is received, no matter which component has focus.
+pub struct GlobalHotkey<S, A>
+where
+    S: Debug + Default + Clone + PartialEq + Sync + Send,
+    A: Debug + Default + Clone + Sync + Send,
+{
+    pub input_events: Vec<InputEvent>,
+    /// Shown to the user in the [KeyboardHelpComponent].
+    pub description: String,
+    pub on_hotkey_handler: OnGlobalHotkeyFn<S, A>,
+}
+
 mod component_registry_impl {
     use super::*;
 
+    impl<S, A> Default for ComponentRegistry<S, A>
+    where
+        S: Debug + Default + Clone + PartialEq + Sync + Send,
+        A: Debug + Default + Clone + Sync + Send,
+    {
+        fn default() -> Self {
+            Self {
+                components: Default::default(),
+                has_focus: Default::default(),
+                global_hotkeys: Default::default(),
+                ids_in_insertion_order: Default::default(),
+                tab_indices: Default::default(),
+                dim_background_for_modals: true,
+            }
+        }
+    }
+
     impl<S, A> ComponentRegistry<S, A>
     where
         S: Debug + Default + Clone + PartialEq + Sync + Send,
         A: Debug + Default + Clone + Sync + Send,
     {
         pub fn put(&mut self, id: FlexBoxId, component: SharedComponent<S, A>) {
-            self.components.insert(id, component);
+            if self.components.insert(id, component).is_none() {
+                self.ids_in_insertion_order.push(id);
+            }
         }
 
         pub fn does_not_contain(&self, id: FlexBoxId) -> bool {
@@ -63,8 +114,114 @@ mod component_registry_impl {
         }
 
         pub fn remove(&mut self, id: FlexBoxId) -> Option<SharedComponent<S, A>> {
+            self.ids_in_insertion_order.retain(|it| *it != id);
+            self.tab_indices.remove(&id);
             self.components.remove(&id)
         }
+
+        /// Set the position of the component w/ the given `id` in the focus order. This is
+        /// called by [render_component_in_current_box!] when the box that the component is
+        /// rendered in has a `tab_index` (eg: [box_start!] w/ `tab_index: 1`). More info
+        /// in [focus_next](ComponentRegistry::focus_next).
+        pub fn set_tab_index(&mut self, id: FlexBoxId, tab_index: usize) {
+            self.tab_indices.insert(id, tab_index);
+        }
+
+        /// The ids of all the components, in the order in which focus moves between them:
+        /// 1. The components that have a tab index come first, sorted by it.
+        /// 2. The ones that don't come after them.
+        /// 3. Ties (& the ones w/out a tab index) are in the order in which they were
+        ///    [put](ComponentRegistry::put) in the registry.
+        pub fn get_focus_order(&self) -> Vec<FlexBoxId> {
+            let mut it = self.ids_in_insertion_order.clone();
+            // This is a stable sort, so ties keep the insertion order.
+            it.sort_by_key(|id| self.tab_indices.get(id).copied().unwrap_or(usize::MAX));
+            it
+        }
+
+        /// Move focus to the component after the one that has focus, in the
+        /// [focus order](ComponentRegistry::get_focus_order), wrapping around at the end.
+        /// If nothing has focus, then the first component gets it. Focus doesn't move while
+        /// a modal (eg: a dialog) has it.
+        pub fn focus_next(&mut self) { self.move_focus(true); }
+
+        /// Just like [focus_next](ComponentRegistry::focus_next), but in the other
+        /// direction (eg: when Shift+Tab is pressed).
+        pub fn focus_prev(&mut self) { self.move_focus(false); }
+
+        fn move_focus(&mut self, is_forward: bool) {
+            if self.has_focus.is_modal_set() {
+                return;
+            }
+            let focus_order = self.get_focus_order();
+            if focus_order.is_empty() {
+                return;
+            }
+            let count = focus_order.len();
+            let maybe_index = self
+                .has_focus
+                .get_id()
+                .and_then(|id| focus_order.iter().position(|it| *it == id));
+            let new_index = match (maybe_index, is_forward) {
+                (None, _) => 0,
+                (Some(index), true) => (index + 1) % count,
+                (Some(index), false) => (index + count - 1) % count,
+            };
+            self.has_focus.set_id(focus_order[new_index]);
+        }
+
+        /// Open the component w/ the given `id` as a modal, on top of the ones that are
+        /// already open (if any). Since it has focus, all the input goes to it (after the
+        /// [GlobalHotkey]s).
+        /// - When it is rendered w/ [render_component_in_current_box!] or
+        ///   [render_component_in_given_box!], everything that is painted below
+        ///   [ZOrder::Glass] is dimmed (unless
+        ///   [dim_background_for_modals](ComponentRegistry::dim_background_for_modals) is
+        ///   `false`).
+        /// - [pop_modal](ComponentRegistry::pop_modal) closes it, & gives focus back to the
+        ///   layer below it.
+        ///
+        /// Returns an error if nothing has focus, or if `id` is already open.
+        pub fn push_modal(&mut self, id: FlexBoxId) -> CommonResult<()> {
+            self.has_focus.push_modal_id(id)
+        }
+
+        /// Close the top modal & return its `id`. Returns [None] if no modal is open.
+        pub fn pop_modal(&mut self) -> Option<FlexBoxId> { self.has_focus.pop_modal_id() }
+
+        pub fn get_top_modal_id(&self) -> Option<FlexBoxId> {
+            self.has_focus.get_modal_ids().last().copied()
+        }
+
+        /// Whether the component w/ the given `id` is the top modal, & so the background
+        /// should be dimmed when it is rendered.
+        pub fn is_background_dimmed_for(&self, id: FlexBoxId) -> bool {
+            self.dim_background_for_modals && self.get_top_modal_id() == Some(id)
+        }
+
+        /// Run `on_hotkey` when any of the `input_events` is received. The `description`
+        /// is shown (along w/ the keys) in the [KeyboardHelpComponent].
+        ///
+        /// # Precedence
+        /// 1. Global hotkeys win over the app & the focused component. They are checked by
+        ///    the main event loop before the [InputEvent] is passed to [App::app_handle_event].
+        /// 2. If more than one hotkey matches, then they are run in the order in which they
+        ///    were registered, until one of them doesn't return
+        ///    [EventPropagation::Propagate].
+        /// 3. If all the matching handlers return [EventPropagation::Propagate], then the
+        ///    [InputEvent] is passed to the app as usual.
+        pub fn register_global_hotkey(
+            &mut self,
+            input_events: Vec<InputEvent>,
+            description: &str,
+            on_hotkey: OnGlobalHotkeyFn<S, A>,
+        ) {
+            self.global_hotkeys.push(GlobalHotkey {
+                input_events,
+                description: description.to_string(),
+                on_hotkey_handler: on_hotkey,
+            });
+        }
     }
 
     impl<S, A> ComponentRegistry<S, A>
@@ -104,6 +261,29 @@ mod component_registry_impl {
             }
         }
 
+        /// Let every registered [Component] know that the terminal window has been resized. More
+        /// info in [Component::handle_resize].
+        pub async fn notify_resize(this: &ComponentRegistry<S, A>, new_size: Size) {
+            for shared_component in this.components.values() {
+                shared_component.write().await.handle_resize(new_size);
+            }
+        }
+
+        /// Returns [None] if no [GlobalHotkey] consumed the `input_event`, so it should be
+        /// passed to the app. More info in
+        /// [register_global_hotkey](ComponentRegistry::register_global_hotkey).
+        pub fn try_handle_global_hotkey(
+            this: &ComponentRegistry<S, A>,
+            input_event: &InputEvent,
+            shared_store: &SharedStore<S, A>,
+        ) -> Option<EventPropagation> {
+            this.global_hotkeys
+                .iter()
+                .filter(|it| input_event.matches(&it.input_events))
+                .map(|it| (it.on_hotkey_handler)(shared_store, input_event))
+                .find(|it| *it != EventPropagation::Propagate)
+        }
+
         pub async fn route_event_to_focused_component(
             this: &mut ComponentRegistry<S, A>,
             input_event: &InputEvent,
@@ -139,6 +319,9 @@ mod component_registry_impl {
             f.debug_struct("ComponentRegistry")
                 .field("components", &self.components.keys().enumerate())
                 .field("has_focus", &self.has_focus)
+                .field("global_hotkeys", &self.global_hotkeys.len())
+                .field("focus_order", &self.get_focus_order())
+                .field("dim_background_for_modals", &self.dim_background_for_modals)
                 .finish()
         }
     }
@@ -175,3 +358,174 @@ macro_rules! call_handle_event {
         return Ok(result_event_propagation);
     }};
 }
+
+#[cfg(test)]
+mod tests {
+    use r3bl_rs_utils_core::*;
+
+    use super::*;
+    use crate::test_dialog::mock_real_objects_for_dialog;
+
+    type State = mock_real_objects_for_dialog::State;
+
+    fn on_quit(_: &SharedStore<State, String>, _: &InputEvent) -> EventPropagation {
+        EventPropagation::ExitMainEventLoop
+    }
+
+    fn on_log(_: &SharedStore<State, String>, _: &InputEvent) -> EventPropagation {
+        EventPropagation::Propagate
+    }
+
+    #[test]
+    fn test_focus_order() {
+        let mut component_registry = ComponentRegistry::<State, String>::default();
+        for id in [1, 2, 3, 4] {
+            component_registry.put(
+                FlexBoxId::from(id),
+                MarkdownViewComponent::new_shared(FlexBoxId::from(id), ""),
+            );
+        }
+
+        // Insertion order.
+        component_registry.focus_next();
+        assert_eq2!(
+            component_registry.has_focus.get_id(),
+            Some(FlexBoxId::from(1))
+        );
+        component_registry.focus_prev();
+        assert_eq2!(
+            component_registry.has_focus.get_id(),
+            Some(FlexBoxId::from(4))
+        );
+
+        // The components w/ a tab index come first, ties are in insertion order.
+        component_registry.set_tab_index(FlexBoxId::from(4), 0);
+        component_registry.set_tab_index(FlexBoxId::from(3), 1);
+        component_registry.set_tab_index(FlexBoxId::from(1), 1);
+        assert_eq2!(
+            component_registry.get_focus_order(),
+            vec![4, 1, 3, 2]
+                .into_iter()
+                .map(FlexBoxId::from)
+                .collect::<Vec<_>>()
+        );
+        component_registry.focus_next();
+        assert_eq2!(
+            component_registry.has_focus.get_id(),
+            Some(FlexBoxId::from(1))
+        );
+        component_registry.focus_next();
+        component_registry.focus_next();
+        assert_eq2!(
+            component_registry.has_focus.get_id(),
+            Some(FlexBoxId::from(2))
+        );
+        component_registry.focus_next();
+        assert_eq2!(
+            component_registry.has_focus.get_id(),
+            Some(FlexBoxId::from(4))
+        );
+
+        // Focus doesn't move while a modal has it.
+        component_registry
+            .has_focus
+            .try_set_modal_id(FlexBoxId::from(5))
+            .unwrap();
+        component_registry.focus_next();
+        assert_eq2!(
+            component_registry.has_focus.get_id(),
+            Some(FlexBoxId::from(5))
+        );
+        component_registry.has_focus.reset_modal_id();
+
+        // Removed components are skipped.
+        component_registry.remove(FlexBoxId::from(1));
+        component_registry.focus_next();
+        assert_eq2!(
+            component_registry.has_focus.get_id(),
+            Some(FlexBoxId::from(3))
+        );
+    }
+
+    #[test]
+    fn test_modal_stack() {
+        let mut component_registry = ComponentRegistry::<State, String>::default();
+        assert!(component_registry.push_modal(FlexBoxId::from(2)).is_err());
+
+        component_registry.has_focus.set_id(FlexBoxId::from(1));
+        component_registry.push_modal(FlexBoxId::from(2)).unwrap();
+        component_registry.push_modal(FlexBoxId::from(3)).unwrap();
+        assert_eq2!(
+            component_registry.get_top_modal_id(),
+            Some(FlexBoxId::from(3))
+        );
+        assert_eq2!(
+            component_registry.has_focus.get_id(),
+            Some(FlexBoxId::from(3))
+        );
+        assert!(component_registry.is_background_dimmed_for(FlexBoxId::from(3)));
+        assert!(!component_registry.is_background_dimmed_for(FlexBoxId::from(2)));
+
+        // Closing the top modal gives focus back to the one below it.
+        assert_eq2!(component_registry.pop_modal(), Some(FlexBoxId::from(3)));
+        assert_eq2!(
+            component_registry.has_focus.get_id(),
+            Some(FlexBoxId::from(2))
+        );
+        component_registry.dim_background_for_modals = false;
+        assert!(!component_registry.is_background_dimmed_for(FlexBoxId::from(2)));
+
+        assert_eq2!(component_registry.pop_modal(), Some(FlexBoxId::from(2)));
+        assert_eq2!(component_registry.pop_modal(), None);
+        assert_eq2!(
+            component_registry.has_focus.get_id(),
+            Some(FlexBoxId::from(1))
+        );
+    }
+
+    #[test]
+    fn test_global_hotkeys() {
+        let shared_store = mock_real_objects_for_dialog::create_store();
+        let ctrl_q = InputEvent::Keyboard(keypress!(@char ModifierKeysMask::CTRL, 'q'));
+        let ctrl_l = InputEvent::Keyboard(keypress!(@char ModifierKeysMask::CTRL, 'l'));
+        let other = InputEvent::Keyboard(keypress!(@char 'a'));
+
+        let mut component_registry = ComponentRegistry::<State, String>::default();
+        component_registry.register_global_hotkey(vec![ctrl_q.clone()], "Quit", on_quit);
+        component_registry.register_global_hotkey(
+            vec![ctrl_q.clone(), ctrl_l.clone()],
+            "Log",
+            on_log,
+        );
+
+        // Consumed by the 1st matching hotkey.
+        assert_eq2!(
+            ComponentRegistry::try_handle_global_hotkey(
+                &component_registry,
+                &ctrl_q,
+                &shared_store
+            ),
+            Some(EventPropagation::ExitMainEventLoop)
+        );
+
+        // The handler chose to propagate, so the app gets the event.
+        assert_eq2!(
+            ComponentRegistry::try_handle_global_hotkey(
+                &component_registry,
+                &ctrl_l,
+                &shared_store
+            ),
+            None
+        );
+
+        // Not a hotkey.
+        assert_eq2!(
+            ComponentRegistry::try_handle_global_hotkey(
+                &component_registry,
+                &other,
+                &shared_store
+            ),
+            None
+        );
+    }
+}
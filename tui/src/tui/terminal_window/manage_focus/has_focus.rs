@@ -26,21 +26,24 @@ use crate::*;
 ///
 /// There are 2 types of keyboard focus:
 /// 1. Non modal focus - This is just a single `id` that is stored. To change focus a new `id` is
-///    set in its place. Internally a `Vec` of capacity 2 is used to store this and the modal `id`.
-/// 2. Modal focus - There can only be one modal at a time. When a modal is active, the `id` of the
-///    [FlexBox] that had focus before the modal was activated is saved. When the modal is closed,
-///    the `id` of the [FlexBox] that had focus before the modal was activated is restored.
+///    set in its place. Internally a `Vec` is used to store this and the modal `id`s.
+/// 2. Modal focus - When a modal is active, the `id` of the [FlexBox] that had focus before the
+///    modal was activated is saved. When the modal is closed, the `id` of the [FlexBox] that had
+///    focus before the modal was activated is restored.
 ///
-/// ## Modal `id`, which is used by modal dialog box
+/// ## Modal `id`s, which are used by modal dialog boxes
 ///
-/// 1. Only one modal can be active at any time.
-/// 2. When a modal is active, the `id` of the [FlexBox] that had focus before the modal was
-///    activated is saved.
-/// 3. When the modal is closed, the `id` of the [FlexBox] that had focus before the modal was
-///    activated is restored.
+/// 1. Modals are stacked (eg: a help modal over a dialog) w/
+///    [push_modal_id](HasFocus::push_modal_id), & only the top one has focus.
+///    [try_set_modal_id](HasFocus::try_set_modal_id) only allows one modal at a time.
+/// 2. When a modal is active, the `id` of the [FlexBox] (or modal) that had focus before the
+///    modal was activated is saved.
+/// 3. When the modal is closed, the `id` of the [FlexBox] (or modal) that had focus before the
+///    modal was activated is restored.
 #[derive(Clone, PartialEq, Eq, Debug)]
 pub struct HasFocus {
-    /// This `id` has keyboard focus. This is global.
+    /// The last `id` has keyboard focus, the ones before it are the non modal `id` & the
+    /// modal `id`s below it. This is global.
     id_vec: Vec<FlexBoxId>,
 }
 
@@ -93,6 +96,17 @@ impl HasFocus {
     pub fn does_current_box_have_focus(&self, current_box: &FlexBox) -> bool {
         self.does_id_have_focus(current_box.id)
     }
+
+    /// Replace `old_id` w/ `new_id`, whether it is the non-modal or the modal `id`. This is
+    /// useful for a container (eg: [TabsComponent]) that lends its focus to a child while it
+    /// delegates to it, since the child might have set a modal `id` in the meantime.
+    pub fn replace_id(&mut self, old_id: FlexBoxId, new_id: FlexBoxId) {
+        for id in self.id_vec.iter_mut() {
+            if *id == old_id {
+                *id = new_id;
+            }
+        }
+    }
 }
 
 impl HasFocus {
@@ -119,21 +133,51 @@ impl HasFocus {
         Ok(())
     }
 
+    /// Pushes the `id` on top of the modal `id`s (if any), so that it has focus. The `id`
+    /// that had focus is restored w/ [pop_modal_id](HasFocus::pop_modal_id). Unlike
+    /// [try_set_modal_id](HasFocus::try_set_modal_id) this can be called while a modal is
+    /// set, but not for an `id` that is already in the stack.
+    pub fn push_modal_id(&mut self, id: FlexBoxId) -> CommonResult<()> {
+        if !self.is_set() {
+            let msg = "Modal id can only be set if id is already set. id is not set.";
+            return CommonError::new_err_with_only_msg(msg);
+        }
+
+        if self.get_modal_ids().contains(&id) {
+            let msg = format!("Modal id {id} is already set.");
+            return CommonError::new_err_with_only_msg(&msg);
+        }
+
+        self.id_vec.push(id);
+        Ok(())
+    }
+
+    /// Removes the top modal `id` & returns it. The `id` that had focus before it was
+    /// pushed has focus again. Returns [None] (& does nothing) if there's no modal `id`.
+    pub fn pop_modal_id(&mut self) -> Option<FlexBoxId> {
+        if self.is_modal_set() {
+            self.id_vec.pop()
+        } else {
+            None
+        }
+    }
+
+    /// The modal `id`s, from the bottom to the top (which has focus).
+    pub fn get_modal_ids(&self) -> &[FlexBoxId] {
+        self.id_vec.get(1..).unwrap_or_default()
+    }
+
     /// Checks whether any modal `id` is set.
-    pub fn is_modal_set(&self) -> bool { self.id_vec.len() == 2 }
+    pub fn is_modal_set(&self) -> bool { self.id_vec.len() >= 2 }
 
     /// Checks whether the given `id` is the modal `id`.
     pub fn is_modal_id(&self, id: FlexBoxId) -> bool {
         self.is_modal_set() && self.does_id_have_focus(id)
     }
 
-    /// Restores the modal `id` to the previous non-modal `id`. It does nothing if there's no modal
-    /// `id` set.
-    pub fn reset_modal_id(&mut self) {
-        if self.is_modal_set() {
-            self.id_vec.pop();
-        }
-    }
+    /// Restores the modal `id` to the previous `id` (which is a modal `id` if modals are
+    /// stacked). It does nothing if there's no modal `id` set.
+    pub fn reset_modal_id(&mut self) { self.pop_modal_id(); }
 }
 
 #[cfg(test)]
@@ -226,4 +270,47 @@ mod has_focus_tests {
         assert!(has_focus.is_set());
         assert!(!has_focus.is_empty());
     }
+
+    #[test]
+    fn works_with_stacked_modal_ids() {
+        let mut has_focus = HasFocus::default();
+        assert!(has_focus.push_modal_id(FlexBoxId::from(2)).is_err());
+
+        has_focus.set_id(FlexBoxId::from(1));
+        assert!(has_focus.push_modal_id(FlexBoxId::from(2)).is_ok());
+        assert!(has_focus.push_modal_id(FlexBoxId::from(3)).is_ok());
+        assert!(has_focus.push_modal_id(FlexBoxId::from(2)).is_err());
+        assert_eq2!(
+            has_focus.get_modal_ids(),
+            &[FlexBoxId::from(2), FlexBoxId::from(3)]
+        );
+        assert!(has_focus.is_modal_id(FlexBoxId::from(3)));
+        assert!(!has_focus.is_modal_id(FlexBoxId::from(2)));
+
+        // Each pop restores the focus of the layer below.
+        assert_eq2!(has_focus.pop_modal_id(), Some(FlexBoxId::from(3)));
+        assert_eq2!(has_focus.get_id(), Some(FlexBoxId::from(2)));
+        has_focus.reset_modal_id();
+        assert_eq2!(has_focus.get_id(), Some(FlexBoxId::from(1)));
+        assert!(!has_focus.is_modal_set());
+        assert_eq2!(has_focus.pop_modal_id(), None);
+        assert_eq2!(has_focus.get_id(), Some(FlexBoxId::from(1)));
+    }
+
+    #[test]
+    fn replace_id_keeps_modal_id() {
+        let mut has_focus = HasFocus::default();
+        has_focus.set_id(FlexBoxId::from(1));
+        assert!(has_focus.try_set_modal_id(FlexBoxId::from(2)).is_ok());
+
+        has_focus.replace_id(FlexBoxId::from(1), FlexBoxId::from(3));
+        assert!(has_focus.is_modal_id(FlexBoxId::from(2)));
+
+        has_focus.reset_modal_id();
+        assert_eq2!(has_focus.get_id(), Some(FlexBoxId::from(3)));
+
+        // Nothing happens if the old id doesn't have focus.
+        has_focus.replace_id(FlexBoxId::from(1), FlexBoxId::from(4));
+        assert_eq2!(has_focus.get_id(), Some(FlexBoxId::from(3)));
+    }
 }
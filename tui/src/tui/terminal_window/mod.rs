@@ -22,6 +22,9 @@ pub mod default_input_handler;
 pub mod event_routing_support;
 pub mod main_event_loop;
 pub mod manage_focus;
+pub mod render_profiler;
+pub mod render_throttle;
+pub mod resize_debouncer;
 pub mod shared_global_data;
 pub mod static_global_data;
 pub mod type_aliases;
@@ -33,6 +36,9 @@ pub use default_input_handler::*;
 pub use event_routing_support::*;
 pub use main_event_loop::*;
 pub use manage_focus::*;
+pub use render_profiler::*;
+pub use render_throttle::*;
+pub use resize_debouncer::*;
 pub use shared_global_data::*;
 pub use static_global_data::*;
 pub use type_aliases::*;
@@ -0,0 +1,192 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::{sync::atomic::{AtomicBool, AtomicU64, Ordering},
+          time::{Duration, Instant}};
+
+/// The stages of rendering a frame that are timed by the [RenderProfiler].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RenderStage {
+    /// [App::app_render](crate::App::app_render), ie: laying out the
+    /// [FlexBox](crate::FlexBox)es & rendering the components into a
+    /// [RenderPipeline](crate::RenderPipeline). This includes [RenderStage::Highlight].
+    Layout,
+    /// Syntax highlighting the content of the editor.
+    Highlight,
+    /// Painting the [RenderPipeline](crate::RenderPipeline) (&
+    /// [InlineImage](crate::InlineImage)s) to the terminal.
+    Paint,
+}
+
+/// How long each [RenderStage] took in the last frame that was rendered. More info in
+/// [RenderProfiler].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RenderFrameTiming {
+    pub layout: Duration,
+    pub highlight: Duration,
+    pub paint: Duration,
+}
+
+/// Records how long each [RenderStage] takes, so that you can find out which one is slow
+/// when the app stutters. It is disabled by default, call
+/// [set_enabled](RenderProfiler::set_enabled) to turn it on, and then
+/// [get_last_frame_timing](RenderProfiler::get_last_frame_timing) (eg: in a status bar).
+///
+/// When it is disabled, the only cost of a [RenderStageSpan] is a relaxed atomic load (it
+/// doesn't even get the current time).
+pub struct RenderProfiler;
+
+/// Times a [RenderStage] from when it is [entered](RenderStageSpan::enter) until it is
+/// dropped. If the same stage is entered more than once in a frame (eg: one
+/// [RenderStage::Highlight] for each editor) the durations are added up.
+#[must_use]
+pub struct RenderStageSpan {
+    stage: RenderStage,
+    maybe_start: Option<Instant>,
+}
+
+static IS_ENABLED: AtomicBool = AtomicBool::new(false);
+static HAS_LAST_FRAME: AtomicBool = AtomicBool::new(false);
+/// Time unit is nanoseconds. Indexed by [RenderStage::get_index].
+static CURRENT_FRAME_NANOS: [AtomicU64; 3] =
+    [AtomicU64::new(0), AtomicU64::new(0), AtomicU64::new(0)];
+/// Time unit is nanoseconds. Indexed by [RenderStage::get_index].
+static LAST_FRAME_NANOS: [AtomicU64; 3] =
+    [AtomicU64::new(0), AtomicU64::new(0), AtomicU64::new(0)];
+
+mod render_profiler_impl {
+    use super::*;
+
+    impl RenderStage {
+        fn get_index(&self) -> usize {
+            match self {
+                RenderStage::Layout => 0,
+                RenderStage::Highlight => 1,
+                RenderStage::Paint => 2,
+            }
+        }
+    }
+
+    impl RenderFrameTiming {
+        /// [RenderStage::Highlight] happens during [RenderStage::Layout], so it isn't
+        /// added again.
+        pub fn get_total(&self) -> Duration { self.layout + self.paint }
+    }
+
+    impl RenderProfiler {
+        pub fn set_enabled(is_enabled: bool) {
+            IS_ENABLED.store(is_enabled, Ordering::Relaxed);
+            if !is_enabled {
+                HAS_LAST_FRAME.store(false, Ordering::Relaxed);
+            }
+        }
+
+        pub fn is_enabled() -> bool { IS_ENABLED.load(Ordering::Relaxed) }
+
+        /// Returns [None] if profiling is disabled, or no frame has been rendered since it
+        /// was enabled.
+        pub fn get_last_frame_timing() -> Option<RenderFrameTiming> {
+            if !Self::is_enabled() || !HAS_LAST_FRAME.load(Ordering::Relaxed) {
+                return None;
+            }
+            let get = |stage: RenderStage| {
+                Duration::from_nanos(
+                    LAST_FRAME_NANOS[stage.get_index()].load(Ordering::Relaxed),
+                )
+            };
+            Some(RenderFrameTiming {
+                layout: get(RenderStage::Layout),
+                highlight: get(RenderStage::Highlight),
+                paint: get(RenderStage::Paint),
+            })
+        }
+
+        /// Called by [AppManager::render_app](crate::AppManager::render_app) before the
+        /// app is rendered.
+        pub fn begin_frame() {
+            if !Self::is_enabled() {
+                return;
+            }
+            for it in &CURRENT_FRAME_NANOS {
+                it.store(0, Ordering::Relaxed);
+            }
+        }
+
+        /// Called by [AppManager::render_app](crate::AppManager::render_app) after the
+        /// app is painted. The timing of this frame is now returned by
+        /// [get_last_frame_timing](RenderProfiler::get_last_frame_timing).
+        pub fn end_frame() {
+            if !Self::is_enabled() {
+                return;
+            }
+            for (current, last) in CURRENT_FRAME_NANOS.iter().zip(&LAST_FRAME_NANOS) {
+                last.store(current.load(Ordering::Relaxed), Ordering::Relaxed);
+            }
+            HAS_LAST_FRAME.store(true, Ordering::Relaxed);
+        }
+    }
+
+    impl RenderStageSpan {
+        pub fn enter(stage: RenderStage) -> Self {
+            let maybe_start = if RenderProfiler::is_enabled() {
+                Some(Instant::now())
+            } else {
+                None
+            };
+            Self { stage, maybe_start }
+        }
+    }
+
+    impl Drop for RenderStageSpan {
+        fn drop(&mut self) {
+            if let Some(start) = self.maybe_start {
+                let elapsed_nanos = start.elapsed().as_nanos() as u64;
+                CURRENT_FRAME_NANOS[self.stage.get_index()]
+                    .fetch_add(elapsed_nanos, Ordering::Relaxed);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_rs_utils_core::*;
+
+    use super::*;
+
+    #[test]
+    fn test_render_profiler() {
+        RenderProfiler::set_enabled(true);
+        RenderProfiler::begin_frame();
+        {
+            let _span = RenderStageSpan::enter(RenderStage::Layout);
+            {
+                let _span = RenderStageSpan::enter(RenderStage::Highlight);
+                std::thread::sleep(Duration::from_millis(2));
+            }
+        }
+        RenderProfiler::end_frame();
+
+        let timing = RenderProfiler::get_last_frame_timing().unwrap();
+        assert!(timing.highlight >= Duration::from_millis(2));
+        assert!(timing.layout >= timing.highlight);
+        assert_eq2!(timing.get_total(), timing.layout + timing.paint);
+
+        RenderProfiler::set_enabled(false);
+        assert_eq2!(RenderProfiler::get_last_frame_timing(), None);
+    }
+}
@@ -0,0 +1,217 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::time::{Duration, Instant};
+
+use crate::*;
+
+/// Caps how often the app is painted, so that rapid input (eg: key repeat) doesn't
+/// render many more frames than the terminal can display. Only the paint is throttled,
+/// state updates are still applied immediately.
+///
+/// This lives in the [GlobalData] & it is used by [TerminalWindow::main_event_loop]:
+/// 1. If a frame interval has passed since the last render, then render right away.
+/// 2. Otherwise a single render is scheduled for the start of the next frame. Any other
+///    render requests that arrive before then are coalesced into it, since it paints
+///    the latest state.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RenderThrottle {
+    /// [Duration::ZERO] means that rendering is not throttled.
+    pub frame_interval: Duration,
+    pub maybe_last_render_instant: Option<Instant>,
+    pub is_render_pending: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RenderThrottleDecision {
+    RenderNow,
+    /// Render once this much time has passed.
+    RenderAfter(Duration),
+    /// A render is already scheduled, nothing to do.
+    Coalesced,
+}
+
+impl Default for RenderThrottle {
+    fn default() -> Self { Self::new(DEFAULT_MAX_FPS) }
+}
+
+impl RenderThrottle {
+    /// A `max_fps` of `0` turns off throttling.
+    pub fn new(max_fps: u16) -> Self {
+        let frame_interval = match max_fps {
+            0 => Duration::ZERO,
+            _ => Duration::from_secs(1) / max_fps as u32,
+        };
+        Self {
+            frame_interval,
+            maybe_last_render_instant: None,
+            is_render_pending: false,
+        }
+    }
+
+    /// Call this each time the app needs to be painted. When [RenderThrottleDecision::
+    /// RenderAfter] is returned, then [on_deferred_render](Self::on_deferred_render)
+    /// must be called right before the scheduled render.
+    pub fn request_render(&mut self, now: Instant) -> RenderThrottleDecision {
+        if self.is_render_pending {
+            return RenderThrottleDecision::Coalesced;
+        }
+
+        let maybe_elapsed = self.maybe_last_render_instant.map(|last_render_instant| {
+            now.saturating_duration_since(last_render_instant)
+        });
+
+        match maybe_elapsed {
+            Some(elapsed) if elapsed < self.frame_interval => {
+                self.is_render_pending = true;
+                RenderThrottleDecision::RenderAfter(self.frame_interval - elapsed)
+            }
+            _ => {
+                self.maybe_last_render_instant = Some(now);
+                RenderThrottleDecision::RenderNow
+            }
+        }
+    }
+
+    pub fn on_deferred_render(&mut self, now: Instant) {
+        self.is_render_pending = false;
+        self.maybe_last_render_instant = Some(now);
+    }
+}
+
+/// Components that animate (eg: the spinner in [ProgressComponent]) need to be painted
+/// again after some time has passed, even if nothing else happens. Rather than having them
+/// dispatch actions to the store to cause re-renders, they request a render tick during
+/// [Component::render].
+///
+/// This lives in the [GlobalData] & it is used by [TerminalWindow::main_event_loop]:
+/// 1. Components call [request](Self::request) w/ the delay until they need to be painted
+///    again. If multiple components do this in the same render, the shortest delay wins.
+/// 2. Once the render is painted, [take_delay_to_schedule](Self::take_delay_to_schedule)
+///    returns the delay to wait before the next render. Only one tick is ever scheduled at
+///    a time, so renders that happen in the meantime don't pile up more ticks.
+/// 3. When the tick fires, [on_tick](Self::on_tick) is called & the app is rendered again
+///    (which goes thru the [RenderThrottle]).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RenderTick {
+    pub maybe_requested_delay: Option<Duration>,
+    pub is_scheduled: bool,
+}
+
+impl RenderTick {
+    pub fn request(&mut self, delay: Duration) {
+        self.maybe_requested_delay = Some(match self.maybe_requested_delay {
+            Some(requested_delay) => requested_delay.min(delay),
+            None => delay,
+        });
+    }
+
+    /// The request is consumed even if a tick is already scheduled, since the component
+    /// will request another one when it is painted by that tick.
+    pub fn take_delay_to_schedule(&mut self) -> Option<Duration> {
+        let maybe_delay = self.maybe_requested_delay.take();
+        if self.is_scheduled {
+            return None;
+        }
+        if maybe_delay.is_some() {
+            self.is_scheduled = true;
+        }
+        maybe_delay
+    }
+
+    pub fn on_tick(&mut self) { self.is_scheduled = false; }
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_rs_utils_core::*;
+
+    use super::*;
+
+    #[test]
+    fn test_render_throttle() {
+        let mut throttle = RenderThrottle::new(50);
+        assert_eq2!(throttle.frame_interval, Duration::from_millis(20));
+
+        // The 1st render happens right away.
+        let start = Instant::now();
+        assert_eq2!(
+            throttle.request_render(start),
+            RenderThrottleDecision::RenderNow
+        );
+
+        // Renders in the same frame are deferred to the next one & then coalesced.
+        assert_eq2!(
+            throttle.request_render(start + Duration::from_millis(5)),
+            RenderThrottleDecision::RenderAfter(Duration::from_millis(15))
+        );
+        assert_eq2!(
+            throttle.request_render(start + Duration::from_millis(10)),
+            RenderThrottleDecision::Coalesced
+        );
+
+        // Once the deferred render happens, the next frame starts.
+        throttle.on_deferred_render(start + Duration::from_millis(20));
+        assert_eq2!(
+            throttle.request_render(start + Duration::from_millis(30)),
+            RenderThrottleDecision::RenderAfter(Duration::from_millis(10))
+        );
+        throttle.on_deferred_render(start + Duration::from_millis(40));
+        assert_eq2!(
+            throttle.request_render(start + Duration::from_millis(60)),
+            RenderThrottleDecision::RenderNow
+        );
+
+        // Throttling can be turned off.
+        let mut throttle = RenderThrottle::new(0);
+        assert_eq2!(
+            throttle.request_render(start),
+            RenderThrottleDecision::RenderNow
+        );
+        assert_eq2!(
+            throttle.request_render(start),
+            RenderThrottleDecision::RenderNow
+        );
+    }
+
+    #[test]
+    fn test_render_tick() {
+        let mut render_tick = RenderTick::default();
+        assert_eq2!(render_tick.take_delay_to_schedule(), None);
+
+        // The shortest requested delay is scheduled.
+        render_tick.request(Duration::from_millis(80));
+        render_tick.request(Duration::from_millis(40));
+        assert_eq2!(
+            render_tick.take_delay_to_schedule(),
+            Some(Duration::from_millis(40))
+        );
+
+        // Renders that happen before the tick fires don't schedule another one.
+        render_tick.request(Duration::from_millis(80));
+        assert_eq2!(render_tick.take_delay_to_schedule(), None);
+        assert_eq2!(render_tick.maybe_requested_delay, None);
+
+        // Once the tick fires, the next request is scheduled.
+        render_tick.on_tick();
+        render_tick.request(Duration::from_millis(80));
+        assert_eq2!(
+            render_tick.take_delay_to_schedule(),
+            Some(Duration::from_millis(80))
+        );
+    }
+}
@@ -0,0 +1,88 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::time::Duration;
+
+use crate::*;
+
+/// Dragging the corner of a terminal window generates a burst of resize events. Laying
+/// out & repainting the whole app for each of them is wasted work, since only the last
+/// size is ever seen. So the expensive part of handling a resize is deferred until no
+/// other resize has arrived for `quiet_period`.
+///
+/// This lives in the [GlobalData] & it is used by [TerminalWindow::main_event_loop]:
+/// 1. Each resize event calls [on_resize](Self::on_resize), which returns a generation
+///    number, and then waits for `quiet_period`.
+/// 2. After waiting, the resize is only acted upon if
+///    [is_latest](Self::is_latest) returns true for that generation, ie: no other
+///    resize arrived in the meantime.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ResizeDebouncer {
+    /// [Duration::ZERO] means that resizes are not debounced.
+    pub quiet_period: Duration,
+    pub generation: usize,
+}
+
+impl Default for ResizeDebouncer {
+    fn default() -> Self { Self::new(Duration::from_millis(RESIZE_DEBOUNCE_MS)) }
+}
+
+impl ResizeDebouncer {
+    pub fn new(quiet_period: Duration) -> Self {
+        Self {
+            quiet_period,
+            generation: 0,
+        }
+    }
+
+    /// Call this each time a resize event arrives. Returns the generation to pass to
+    /// [is_latest](Self::is_latest) once the `quiet_period` has passed.
+    pub fn on_resize(&mut self) -> usize {
+        self.generation = self.generation.wrapping_add(1);
+        self.generation
+    }
+
+    pub fn is_latest(&self, generation: usize) -> bool { self.generation == generation }
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_rs_utils_core::*;
+
+    use super::*;
+
+    #[test]
+    fn test_resize_debouncer() {
+        let mut debouncer = ResizeDebouncer::default();
+        assert_eq2!(
+            debouncer.quiet_period,
+            Duration::from_millis(RESIZE_DEBOUNCE_MS)
+        );
+
+        // A single resize is acted upon.
+        let generation = debouncer.on_resize();
+        assert!(debouncer.is_latest(generation));
+
+        // Only the last resize in a burst is acted upon.
+        let first = debouncer.on_resize();
+        let second = debouncer.on_resize();
+        let third = debouncer.on_resize();
+        assert!(!debouncer.is_latest(first));
+        assert!(!debouncer.is_latest(second));
+        assert!(debouncer.is_latest(third));
+    }
+}
@@ -15,9 +15,10 @@
  *   limitations under the License.
  */
 
-use std::fmt::Debug;
+use std::{fmt::Debug, time::Duration};
 
 use r3bl_rs_utils_core::*;
+use tokio::sync::mpsc;
 
 use crate::*;
 
@@ -27,11 +28,45 @@ use crate::*;
 ///
 /// These are global state values for the entire application:
 /// - The `window_size` holds the [Size] of the terminal window.
-/// - The `maybe_saved_offscreen_buffer` holds the last rendered [OffscreenBuffer].
+/// - The `maybe_saved_offscreen_buffer` holds the last rendered [OffscreenBuffer], and
+///   `is_saved_offscreen_buffer_dimmed` is set if its background was dimmed. More info in
+///   [RenderPipeline::mark_dirty].
+/// - The `theme_registry` holds the [TuiTheme]s, and which one is active. More info in
+///   [ThemeRegistry].
+/// - The `render_throttle` caps how often the app is painted. More info in
+///   [RenderThrottle].
+/// - The `render_tick` lets components that animate request to be painted again later. More
+///   info in [RenderTick].
+/// - The `resize_debouncer` defers re-layout until the terminal stops being resized.
+///   More info in [ResizeDebouncer].
+/// - The `toast_stack` holds the [Toast]s that are currently shown. More info in
+///   [render_toasts].
+/// - The `has_inline_images` flag is set when [InlineImage]s were painted in the last
+///   render. More info in [paint_inline_images].
+/// - The `cursor_shape_profile` holds the escape sequences that set the shape of the
+///   terminal cursor. More info in [CursorShapeProfile] & [paint_cursor_shape].
+/// - The `is_hardware_cursor_visible` flag is set when a component asked for the terminal
+///   cursor to be shown in the last render. More info in [paint_hardware_cursor].
 #[derive(Clone, Default)]
 pub struct GlobalData {
     pub window_size: Size,
     pub maybe_saved_offscreen_buffer: Option<OffscreenBuffer>,
+    pub is_saved_offscreen_buffer_dimmed: bool,
+    pub theme_registry: ThemeRegistry,
+    pub render_throttle: RenderThrottle,
+    pub resize_debouncer: ResizeDebouncer,
+    pub render_tick: RenderTick,
+    pub toast_stack: ToastStack,
+    pub has_inline_images: bool,
+    pub cursor_shape_profile: CursorShapeProfile,
+    /// Set by [set_cursor_shape](GlobalData::set_cursor_shape).
+    pub maybe_cursor_shape: Option<CursorShape>,
+    /// The shape that was last written to the terminal, if any.
+    pub maybe_painted_cursor_shape: Option<CursorShape>,
+    pub is_hardware_cursor_visible: bool,
+    /// This is set by [TerminalWindow::main_event_loop], & it is used to fire the
+    /// scheduled [RenderTick].
+    pub maybe_render_tick_sender: Option<mpsc::Sender<()>>,
 }
 
 mod global_data_impl {
@@ -50,6 +85,10 @@ mod global_data_impl {
                     true => offscreen_buffer.pretty_print(),
                 },
             });
+            vec_lines.push(format!(
+                "active theme: {}",
+                self.theme_registry.get_active_theme().id
+            ));
             write!(f, "\nGlobalData\n  - {}", vec_lines.join("\n  - "))
         }
     }
@@ -68,6 +107,49 @@ mod global_data_impl {
 
         pub fn get_size(&self) -> Size { self.window_size }
 
+        /// Discard the saved offscreen buffer, so that the next paint repaints every cell
+        /// instead of just the ones that changed (eg: after a terminal resize).
+        pub fn request_full_repaint(&mut self) {
+            self.maybe_saved_offscreen_buffer = None;
+        }
+
+        /// The saved offscreen buffer is discarded, since every cell has to be repainted
+        /// w/ the new theme. To switch themes & trigger a re-render use
+        /// [set_active_theme](crate::set_active_theme).
+        pub fn set_active_theme(&mut self, id: &str) -> CommonResult<()> {
+            self.theme_registry.set_active_theme(id)?;
+            self.request_full_repaint();
+            self.dump_to_log("set_active_theme");
+            Ok(())
+        }
+
+        /// Call this from [Component::render] to get painted again after `delay`, w/out
+        /// dispatching an action. More info in [RenderTick].
+        pub fn request_render_tick(&mut self, delay: Duration) {
+            self.render_tick.request(delay);
+        }
+
+        /// The shape is written to the terminal w/ the next paint. More info in
+        /// [paint_cursor_shape].
+        pub fn set_cursor_shape(&mut self, cursor_shape: CursorShape) {
+            self.maybe_cursor_shape = Some(cursor_shape);
+        }
+
+        /// Use this to fix a wrong shape on terminals that map DECSCUSR differently, or
+        /// pass [CursorShapeProfile::no_op] for ones that don't support it. The current
+        /// shape is written again w/ the new profile on the next paint.
+        pub fn set_cursor_shape_profile(
+            &mut self,
+            cursor_shape_profile: CursorShapeProfile,
+        ) {
+            self.cursor_shape_profile = cursor_shape_profile;
+            self.maybe_painted_cursor_shape = None;
+        }
+
+        pub fn get_active_theme(&self) -> &TuiTheme {
+            self.theme_registry.get_active_theme()
+        }
+
         pub fn dump_to_log(&self, msg: &str) {
             let log_msg = format!("{msg} -> {self:?}");
             call_if_true!(DEBUG_TUI_MOD, log_info(log_msg));
@@ -19,7 +19,7 @@ use std::sync::atomic::{AtomicI64, Ordering};
 
 use chrono::Utc;
 
-use crate::ColorSupport;
+use crate::{ColorSupport, GraphicsProtocol};
 const NOT_SET_VALUE: i64 = -1;
 
 /// This module contains static global data that is meant to be used by the entire application. It
@@ -211,3 +211,49 @@ pub mod color_support_global_static {
         };
     }
 }
+
+/// This module contains static global data that is meant to be used by the entire application. It
+/// also provides functions to manipulate this data.
+///
+/// ### Graphics protocol
+/// The app can override the graphics protocol detection heuristics by providing a
+/// [GraphicsProtocol] value, eg: to turn inline images on (or off) if the user asks for it.
+pub mod graphics_protocol_global_static {
+    use super::*;
+
+    /// Global [GraphicsProtocol] override.
+    pub static mut GRAPHICS_PROTOCOL_OVERRIDE: AtomicI64 = AtomicI64::new(NOT_SET_VALUE);
+
+    /// Get the saved [GraphicsProtocol] from the static mutable variable
+    /// [GRAPHICS_PROTOCOL_OVERRIDE].
+    pub fn get_graphics_protocol_override() -> Option<GraphicsProtocol> {
+        let graphics_protocol_override =
+            unsafe { GRAPHICS_PROTOCOL_OVERRIDE.load(Ordering::SeqCst) };
+        match graphics_protocol_override {
+            0 => Some(GraphicsProtocol::None),
+            1 => Some(GraphicsProtocol::Kitty),
+            2 => Some(GraphicsProtocol::ITerm2),
+            _ => None,
+        }
+    }
+
+    pub fn clear_graphics_protocol_override() {
+        unsafe {
+            GRAPHICS_PROTOCOL_OVERRIDE.store(NOT_SET_VALUE, Ordering::SeqCst);
+        };
+    }
+
+    /// Save the [GraphicsProtocol] to the static mutable variable
+    /// [GRAPHICS_PROTOCOL_OVERRIDE].
+    pub fn set_graphics_protocol_override(graphics_protocol: GraphicsProtocol) {
+        let graphics_protocol_override = match graphics_protocol {
+            GraphicsProtocol::None => 0,
+            GraphicsProtocol::Kitty => 1,
+            GraphicsProtocol::ITerm2 => 2,
+        };
+        unsafe {
+            GRAPHICS_PROTOCOL_OVERRIDE
+                .store(graphics_protocol_override, Ordering::SeqCst);
+        };
+    }
+}
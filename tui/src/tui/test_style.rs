@@ -211,6 +211,81 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_stylesheet_conditional_styles() -> CommonResult<()> {
+        struct State {
+            has_error: bool,
+            is_busy: bool,
+        }
+
+        let mut stylesheet = stylesheet! { make_a_style(1), make_a_style(2) };
+        stylesheet.add_conditional_style(
+            1,
+            style! { color_fg: TuiColor::Basic(ANSIBasicColor::Red) },
+            |state: &State| state.has_error,
+        )?;
+        stylesheet.add_conditional_style(
+            1,
+            style! { color_fg: TuiColor::Basic(ANSIBasicColor::Yellow) },
+            |state: &State| state.is_busy,
+        )?;
+        stylesheet.add_conditional_style(
+            3,
+            style! { attrib: [underline] },
+            |state: &State| state.has_error,
+        )?;
+        assert!(stylesheet
+            .add_conditional_style(u8::MAX, Style::default(), |_: &State| true)
+            .is_err());
+
+        // Nothing matches.
+        let state = State {
+            has_error: false,
+            is_busy: false,
+        };
+        let styles = get_styles!(@from: stylesheet, [1, 3], @when: &state);
+        assert_eq2!(styles.as_ref().unwrap().len(), 1);
+        assert_eq2!(
+            Stylesheet::compute(&styles).unwrap().color_fg,
+            color!(0, 0, 0).into()
+        );
+
+        // A matching conditional style overrides its style, and the ones added later
+        // override the ones added before them.
+        let state = State {
+            has_error: true,
+            is_busy: true,
+        };
+        let styles = get_styles!(@from: stylesheet, [1, 3], @when: &state);
+        assert_eq2!(styles.as_ref().unwrap().len(), 4);
+        let computed = Stylesheet::compute(&styles).unwrap();
+        assert_eq2!(
+            computed.color_fg,
+            TuiColor::Basic(ANSIBasicColor::Yellow).into()
+        );
+        assert!(computed.underline);
+        assert!(computed.bold);
+
+        // The styles of later ids override the conditional styles of earlier ids.
+        let styles = get_styles!(@from: stylesheet, [1, 2], @when: &state);
+        assert_eq2!(
+            Stylesheet::compute(&styles).unwrap().color_fg,
+            color!(0, 0, 0).into()
+        );
+
+        // The predicates never match a context of another type, and are not used w/out
+        // one.
+        assert_eq2!(
+            get_styles!(@from: stylesheet, [1], @when: &true)
+                .unwrap()
+                .len(),
+            1
+        );
+        assert_eq2!(get_styles!(@from: stylesheet, [1]).unwrap().len(), 1);
+
+        Ok(())
+    }
+
     /// Helper function.
     fn make_a_style(id: u8) -> Style {
         Style {
@@ -0,0 +1,419 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::{fmt::Debug, sync::Arc};
+
+use r3bl_redux::*;
+use r3bl_rs_utils_core::*;
+use tokio::sync::RwLock;
+
+use crate::*;
+
+/// Runs a [Component] headless, w/ a minimal store, [ComponentRegistry] & [GlobalData], so
+/// that its [EventPropagation]s & [RenderPipeline] can be asserted on. Use
+/// [builder](ComponentTestHarness::builder) to create one.
+///
+/// ```ignore
+/// let mut harness = ComponentTestHarness::<State, Action>::builder()
+///     .set_reducer(Box::new(Reducer))
+///     .build()
+///     .await;
+/// let output = harness.run(&mut component, input_events).await?;
+/// assert_eq!(output.event_propagations, vec![EventPropagation::ConsumedRender]);
+/// assert!(output.get_painted_texts().contains(&"count: 1".to_string()));
+/// ```
+pub struct ComponentTestHarness<S, A>
+where
+    S: Debug + Default + Clone + PartialEq + Sync + Send,
+    A: Debug + Default + Clone + Sync + Send,
+{
+    pub shared_global_data: SharedGlobalData,
+    pub shared_store: SharedStore<S, A>,
+    pub component_registry: ComponentRegistry<S, A>,
+    pub window_size: Size,
+    /// The box that the component is rendered in.
+    pub current_box: FlexBox,
+    pub surface_bounds: SurfaceBounds,
+}
+
+/// Builds a [ComponentTestHarness]. By default the window is 80x24, the component's box
+/// fills it & has focus, and the store has the default state & no reducers.
+pub struct ComponentTestHarnessBuilder<S, A>
+where
+    S: Debug + Default + Clone + PartialEq + Sync + Send,
+    A: Debug + Default + Clone + Sync + Send,
+{
+    pub state: S,
+    pub reducers: AsyncReducerVec<S, A>,
+    pub window_size: Size,
+    pub maybe_box: Option<FlexBox>,
+    pub has_focus: bool,
+}
+
+/// What [ComponentTestHarness::run] returns.
+#[derive(Clone, Debug)]
+pub struct ComponentTestOutput {
+    /// One for each input event, in order.
+    pub event_propagations: Vec<EventPropagation>,
+    /// The result of rendering the component after all the input events were handled.
+    pub render_pipeline: RenderPipeline,
+}
+
+mod component_test_harness_builder_impl {
+    use super::*;
+
+    impl<S, A> Default for ComponentTestHarnessBuilder<S, A>
+    where
+        S: Debug + Default + Clone + PartialEq + Sync + Send,
+        A: Debug + Default + Clone + Sync + Send,
+    {
+        fn default() -> Self {
+            Self {
+                state: Default::default(),
+                reducers: Default::default(),
+                window_size: size!(col_count: 80, row_count: 24),
+                maybe_box: None,
+                has_focus: true,
+            }
+        }
+    }
+
+    impl<S, A> ComponentTestHarnessBuilder<S, A>
+    where
+        S: Debug + Default + Clone + PartialEq + Sync + Send,
+        A: Debug + Default + Clone + Sync + Send,
+    {
+        pub fn new() -> Self { Self::default() }
+
+        pub fn set_state(mut self, state: S) -> Self {
+            self.state = state;
+            self
+        }
+
+        /// Can be called more than once, the reducers are run in the order they are added.
+        pub fn set_reducer(mut self, reducer: AsyncReducerItem<S, A>) -> Self {
+            self.reducers.push(reducer);
+            self
+        }
+
+        pub fn set_window_size(mut self, window_size: Size) -> Self {
+            self.window_size = window_size;
+            self
+        }
+
+        /// By default the box fills the window & has [FlexBoxId] `0`.
+        pub fn set_box(mut self, flex_box: FlexBox) -> Self {
+            self.maybe_box = Some(flex_box);
+            self
+        }
+
+        pub fn set_has_focus(mut self, has_focus: bool) -> Self {
+            self.has_focus = has_focus;
+            self
+        }
+
+        pub async fn build(self) -> ComponentTestHarness<S, A> {
+            let mut global_data = GlobalData::default();
+            global_data.window_size = self.window_size;
+
+            let mut store = Store::<S, A> {
+                state: self.state,
+                ..Default::default()
+            };
+            for reducer in self.reducers {
+                store.add_reducer(reducer).await;
+            }
+
+            let current_box = self.maybe_box.unwrap_or_else(|| FlexBox {
+                id: FlexBoxId::from(0),
+                bounds_size: self.window_size,
+                style_adjusted_bounds_size: self.window_size,
+                ..Default::default()
+            });
+
+            let mut component_registry = ComponentRegistry::default();
+            if self.has_focus {
+                component_registry.has_focus.set_id(current_box.id);
+            }
+
+            ComponentTestHarness {
+                shared_global_data: Arc::new(RwLock::new(global_data)),
+                shared_store: Arc::new(RwLock::new(store)),
+                component_registry,
+                window_size: self.window_size,
+                current_box,
+                surface_bounds: SurfaceBounds {
+                    origin_pos: position!(col_index: 0, row_index: 0),
+                    box_size: self.window_size,
+                },
+            }
+        }
+    }
+}
+
+mod component_test_harness_impl {
+    use super::*;
+
+    impl<S, A> ComponentTestHarness<S, A>
+    where
+        S: Debug + Default + Clone + PartialEq + Sync + Send,
+        A: Debug + Default + Clone + Sync + Send,
+    {
+        pub fn builder() -> ComponentTestHarnessBuilder<S, A> {
+            ComponentTestHarnessBuilder::new()
+        }
+
+        pub async fn get_state(&self) -> S { self.shared_store.read().await.get_state() }
+
+        /// Pass the `input_event` to the `component`, w/ the latest state from the store.
+        /// Actions that it dispatches w/ [r3bl_redux::spawn_dispatch_action] are given a
+        /// chance to run before this returns.
+        pub async fn handle_event<C>(
+            &mut self,
+            component: &mut C,
+            input_event: &InputEvent,
+        ) -> CommonResult<EventPropagation>
+        where
+            C: Component<S, A> + ?Sized,
+        {
+            let state = self.get_state().await;
+            let event_propagation = component
+                .handle_event(
+                    ComponentScopeArgs {
+                        shared_global_data: &self.shared_global_data,
+                        shared_store: &self.shared_store,
+                        state: &state,
+                        component_registry: &mut self.component_registry,
+                        window_size: &self.window_size,
+                    },
+                    input_event,
+                )
+                .await?;
+            tokio::task::yield_now().await;
+            Ok(event_propagation)
+        }
+
+        /// Render the `component` in [current_box](ComponentTestHarness::current_box), w/
+        /// the latest state from the store.
+        pub async fn render<C>(
+            &mut self,
+            component: &mut C,
+        ) -> CommonResult<RenderPipeline>
+        where
+            C: Component<S, A> + ?Sized,
+        {
+            let state = self.get_state().await;
+            component
+                .render(
+                    ComponentScopeArgs {
+                        shared_global_data: &self.shared_global_data,
+                        shared_store: &self.shared_store,
+                        state: &state,
+                        component_registry: &mut self.component_registry,
+                        window_size: &self.window_size,
+                    },
+                    &self.current_box,
+                    self.surface_bounds,
+                )
+                .await
+        }
+
+        /// Pass each of the `input_events` to the `component` (in order), & then render
+        /// it.
+        pub async fn run<C>(
+            &mut self,
+            component: &mut C,
+            input_events: impl IntoIterator<Item = InputEvent>,
+        ) -> CommonResult<ComponentTestOutput>
+        where
+            C: Component<S, A> + ?Sized,
+        {
+            let mut event_propagations = vec![];
+            for input_event in input_events {
+                event_propagations
+                    .push(self.handle_event(component, &input_event).await?);
+            }
+            let render_pipeline = self.render(component).await?;
+            Ok(ComponentTestOutput {
+                event_propagations,
+                render_pipeline,
+            })
+        }
+
+        /// Same as [run](ComponentTestHarness::run), but the input events are parsed from
+        /// a `script` like `"hello<Enter>world<Esc>"`. See [parse_input_events] for the
+        /// grammar.
+        pub async fn run_script<C>(
+            &mut self,
+            component: &mut C,
+            script: &str,
+        ) -> CommonResult<ComponentTestOutput>
+        where
+            C: Component<S, A> + ?Sized,
+        {
+            let input_events = parse_input_events(script)?;
+            self.run(component, input_events).await
+        }
+    }
+
+    impl ComponentTestOutput {
+        /// Returns the text of all the [RenderOp::PaintTextWithAttributes] in the
+        /// `render_pipeline`, in [ZOrder::get_render_order].
+        pub fn get_painted_texts(&self) -> Vec<String> {
+            ZOrder::get_render_order()
+                .into_iter()
+                .filter_map(|z_order| self.render_pipeline.get_all_render_op_in(z_order))
+                .flatten()
+                .filter_map(|render_op| match render_op {
+                    RenderOp::PaintTextWithAttributes(text, _) => Some(text),
+                    _ => None,
+                })
+                .collect()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use async_trait::async_trait;
+    use r3bl_redux::*;
+    use r3bl_rs_utils_core::*;
+
+    use super::*;
+
+    #[derive(Clone, Debug, Default, PartialEq)]
+    struct State {
+        count: usize,
+    }
+
+    #[derive(Clone, Debug, Default, PartialEq)]
+    enum Action {
+        #[default]
+        Noop,
+        Increment,
+    }
+
+    #[derive(Default)]
+    struct Reducer;
+
+    #[async_trait]
+    impl AsyncReducer<State, Action> for Reducer {
+        async fn run(&self, action: &Action, state: &mut State) {
+            if let Action::Increment = action {
+                state.count += 1;
+            }
+        }
+    }
+
+    /// Dispatches [Action::Increment] when `+` is typed, & paints the count.
+    struct CounterComponent;
+
+    #[async_trait]
+    impl Component<State, Action> for CounterComponent {
+        fn reset(&mut self) {}
+
+        fn get_id(&self) -> FlexBoxId { FlexBoxId::from(0) }
+
+        async fn render(
+            &mut self,
+            args: ComponentScopeArgs<'_, State, Action>,
+            current_box: &FlexBox,
+            _surface_bounds: SurfaceBounds,
+        ) -> CommonResult<RenderPipeline> {
+            let mut render_ops = render_ops!();
+            render_ops.push(RenderOp::MoveCursorPositionAbs(
+                current_box.style_adjusted_origin_pos,
+            ));
+            render_ops.push(RenderOp::PaintTextWithAttributes(
+                format!("count: {}", args.state.count),
+                None,
+            ));
+            let mut pipeline = render_pipeline!();
+            pipeline.push(ZOrder::Normal, render_ops);
+            Ok(pipeline)
+        }
+
+        async fn handle_event(
+            &mut self,
+            args: ComponentScopeArgs<'_, State, Action>,
+            input_event: &InputEvent,
+        ) -> CommonResult<EventPropagation> {
+            if input_event.matches_keypress(keypress!(@char '+')) {
+                spawn_dispatch_action!(args.shared_store, Action::Increment);
+                return Ok(EventPropagation::ConsumedRender);
+            }
+            Ok(EventPropagation::Propagate)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_component_test_harness() {
+        let mut harness = ComponentTestHarness::<State, Action>::builder()
+            .set_reducer(Box::new(Reducer))
+            .set_window_size(size!(col_count: 20, row_count: 5))
+            .build()
+            .await;
+        assert!(harness
+            .component_registry
+            .has_focus
+            .does_id_have_focus(FlexBoxId::from(0)));
+        assert_eq2!(
+            harness.current_box.style_adjusted_bounds_size.col_count,
+            ch!(20)
+        );
+
+        let input_events = "++x"
+            .chars()
+            .map(|it| InputEvent::Keyboard(keypress!(@char it)));
+        let output = harness
+            .run(&mut CounterComponent, input_events)
+            .await
+            .unwrap();
+
+        assert_eq2!(
+            output.event_propagations,
+            vec![
+                EventPropagation::ConsumedRender,
+                EventPropagation::ConsumedRender,
+                EventPropagation::Propagate,
+            ]
+        );
+        assert_eq2!(harness.get_state().await, State { count: 2 });
+        assert_eq2!(output.get_painted_texts(), vec!["count: 2".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_component_test_harness_run_script() {
+        let mut harness = ComponentTestHarness::<State, Action>::builder()
+            .set_reducer(Box::new(Reducer))
+            .build()
+            .await;
+        let output = harness
+            .run_script(&mut CounterComponent, "+<Enter>+")
+            .await
+            .unwrap();
+        assert_eq2!(
+            output.event_propagations,
+            vec![
+                EventPropagation::ConsumedRender,
+                EventPropagation::Propagate,
+                EventPropagation::ConsumedRender,
+            ]
+        );
+        assert_eq2!(harness.get_state().await, State { count: 2 });
+    }
+}
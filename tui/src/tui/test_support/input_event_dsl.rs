@@ -0,0 +1,203 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use r3bl_rs_utils_core::*;
+
+use crate::*;
+
+/// Turns a script like `"hello<Enter>world<C-s><Esc>"` into the [InputEvent]s that a user
+/// would generate by typing it. Useful for tests & for reproducing bug reports, eg w/
+/// [ComponentTestHarness::run_script].
+///
+/// # Grammar
+///
+/// ```text
+/// script    = { text | key }
+/// text      = any char other than `<`        -> KeyPress::Plain { Key::Character(char) }
+/// key       = "<" { modifier "-" } key_name ">"
+/// modifier  = "C" (Ctrl) | "A" | "M" (Alt) | "S" (Shift)
+/// key_name  = special | function | "lt" | "Space" | single char
+/// special   = "Enter" | "CR" | "Return" | "Tab" | "BackTab" | "Esc" | "Escape"
+///           | "BS" | "Backspace" | "Del" | "Delete" | "Ins" | "Insert"
+///           | "Home" | "End" | "PageUp" | "PgUp" | "PageDown" | "PgDn"
+///           | "Left" | "Right" | "Up" | "Down"
+/// function  = "F1" .. "F12"
+/// ```
+///
+/// - Modifiers & key names are case insensitive, except for a single char key name, so
+///   `<C-a>` & `<C-A>` are different key presses.
+/// - Use `<lt>` to type a literal `<`. A `>` outside of a key is just text.
+/// - A key w/ no modifiers is a [KeyPress::Plain], eg: `<Enter>`, `<F1>`, `<Space>`.
+///
+/// Returns a [CommonErrorType::ParsingError] if a `<` isn't closed, or a key or modifier
+/// name is unknown.
+pub fn parse_input_events(script: &str) -> CommonResult<Vec<InputEvent>> {
+    let mut input_events = vec![];
+    let mut rest = script;
+
+    while let Some(character) = rest.chars().next() {
+        if character != '<' {
+            input_events.push(InputEvent::Keyboard(keypress!(@char character)));
+            rest = &rest[character.len_utf8()..];
+            continue;
+        }
+
+        let Some(end_index) = rest.find('>') else {
+            return CommonError::new(
+                CommonErrorType::ParsingError,
+                &format!("Unclosed `<` in: {rest:?}"),
+            );
+        };
+        input_events.push(InputEvent::Keyboard(parse_key(&rest[1..end_index])?));
+        rest = &rest[end_index + 1..];
+    }
+
+    Ok(input_events)
+}
+
+/// Parse the text between `<` & `>`, eg: `C-S-Left`.
+fn parse_key(text: &str) -> CommonResult<KeyPress> {
+    let mut mask = ModifierKeysMask::empty();
+    let mut key_name = text;
+
+    // A single char key name can itself be `-`, eg: `<C-->`.
+    while key_name.len() > 2 && key_name.as_bytes()[1] == b'-' {
+        let modifier = match key_name.as_bytes()[0].to_ascii_uppercase() {
+            b'C' => ModifierKeysMask::CTRL,
+            b'A' | b'M' => ModifierKeysMask::ALT,
+            b'S' => ModifierKeysMask::SHIFT,
+            _ => break,
+        };
+        mask.insert(modifier);
+        key_name = &key_name[2..];
+    }
+
+    let Some(key) = parse_key_name(key_name) else {
+        return CommonError::new(
+            CommonErrorType::ParsingError,
+            &format!("Unknown key: <{text}>"),
+        );
+    };
+
+    Ok(if mask.is_empty() {
+        KeyPress::Plain { key }
+    } else {
+        KeyPress::WithModifiers { key, mask }
+    })
+}
+
+fn parse_key_name(key_name: &str) -> Option<Key> {
+    let mut chars = key_name.chars();
+    if let (Some(character), None) = (chars.next(), chars.next()) {
+        return Some(Key::Character(character));
+    }
+
+    let key = match key_name.to_ascii_lowercase().as_str() {
+        "lt" => Key::Character('<'),
+        "space" => Key::Character(' '),
+        "enter" | "cr" | "return" => Key::SpecialKey(SpecialKey::Enter),
+        "tab" => Key::SpecialKey(SpecialKey::Tab),
+        "backtab" => Key::SpecialKey(SpecialKey::BackTab),
+        "esc" | "escape" => Key::SpecialKey(SpecialKey::Esc),
+        "bs" | "backspace" => Key::SpecialKey(SpecialKey::Backspace),
+        "del" | "delete" => Key::SpecialKey(SpecialKey::Delete),
+        "ins" | "insert" => Key::SpecialKey(SpecialKey::Insert),
+        "home" => Key::SpecialKey(SpecialKey::Home),
+        "end" => Key::SpecialKey(SpecialKey::End),
+        "pageup" | "pgup" => Key::SpecialKey(SpecialKey::PageUp),
+        "pagedown" | "pgdn" => Key::SpecialKey(SpecialKey::PageDown),
+        "left" => Key::SpecialKey(SpecialKey::Left),
+        "right" => Key::SpecialKey(SpecialKey::Right),
+        "up" => Key::SpecialKey(SpecialKey::Up),
+        "down" => Key::SpecialKey(SpecialKey::Down),
+        "f1" => Key::FunctionKey(FunctionKey::F1),
+        "f2" => Key::FunctionKey(FunctionKey::F2),
+        "f3" => Key::FunctionKey(FunctionKey::F3),
+        "f4" => Key::FunctionKey(FunctionKey::F4),
+        "f5" => Key::FunctionKey(FunctionKey::F5),
+        "f6" => Key::FunctionKey(FunctionKey::F6),
+        "f7" => Key::FunctionKey(FunctionKey::F7),
+        "f8" => Key::FunctionKey(FunctionKey::F8),
+        "f9" => Key::FunctionKey(FunctionKey::F9),
+        "f10" => Key::FunctionKey(FunctionKey::F10),
+        "f11" => Key::FunctionKey(FunctionKey::F11),
+        "f12" => Key::FunctionKey(FunctionKey::F12),
+        _ => return None,
+    };
+    Some(key)
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_rs_utils_core::*;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_text_and_named_keys() {
+        let input_events = parse_input_events("hi<Enter>x<esc>").unwrap();
+        assert_eq2!(
+            input_events,
+            vec![
+                InputEvent::Keyboard(keypress!(@char 'h')),
+                InputEvent::Keyboard(keypress!(@char 'i')),
+                InputEvent::Keyboard(keypress!(@special SpecialKey::Enter)),
+                InputEvent::Keyboard(keypress!(@char 'x')),
+                InputEvent::Keyboard(keypress!(@special SpecialKey::Esc)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_modifiers() {
+        let input_events = parse_input_events("<C-a><C-S-Left><M-F5><C-->").unwrap();
+        assert_eq2!(
+            input_events,
+            vec![
+                InputEvent::Keyboard(keypress!(@char ModifierKeysMask::CTRL, 'a')),
+                InputEvent::Keyboard(keypress!(
+                    @special ModifierKeysMask::CTRL | ModifierKeysMask::SHIFT,
+                    SpecialKey::Left
+                )),
+                InputEvent::Keyboard(
+                    keypress!(@fn ModifierKeysMask::ALT, FunctionKey::F5)
+                ),
+                InputEvent::Keyboard(keypress!(@char ModifierKeysMask::CTRL, '-')),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_literal_angle_brackets() {
+        let input_events = parse_input_events("<lt>a>").unwrap();
+        assert_eq2!(
+            input_events,
+            vec![
+                InputEvent::Keyboard(keypress!(@char '<')),
+                InputEvent::Keyboard(keypress!(@char 'a')),
+                InputEvent::Keyboard(keypress!(@char '>')),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(parse_input_events("abc<Enter").is_err());
+        assert!(parse_input_events("<Foo>").is_err());
+        assert!(parse_input_events("<X-a>").is_err());
+    }
+}
@@ -0,0 +1,27 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+//! Helpers to unit test [Component]s w/out a terminal. This module is only compiled for
+//! this crate's tests, or when the `test-support` feature is enabled.
+
+// Attach.
+pub mod component_test_harness;
+pub mod input_event_dsl;
+
+// Re-export.
+pub use component_test_harness::*;
+pub use input_event_dsl::*;
@@ -0,0 +1,247 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+#[cfg(test)]
+mod test_text_input {
+    use r3bl_redux::*;
+    use r3bl_rs_utils_core::*;
+
+    use crate::*;
+
+    fn apply(buffer: &mut TextInputBuffer, text_input_event: TextInputEvent) -> bool {
+        buffer.apply_event(&text_input_event, None, &WordCharClass::default())
+    }
+
+    #[test]
+    fn test_try_from_input_event() {
+        assert_eq2!(
+            TextInputEvent::try_from_input_event(&InputEvent::Keyboard(
+                keypress!(@char 'a')
+            )),
+            Some(TextInputEvent::InsertString("a".to_string()))
+        );
+        assert_eq2!(
+            TextInputEvent::try_from_input_event(&InputEvent::Keyboard(
+                keypress!(@special ModifierKeysMask::CTRL, SpecialKey::Left)
+            )),
+            Some(TextInputEvent::PrevWord)
+        );
+        assert_eq2!(
+            TextInputEvent::try_from_input_event(&InputEvent::Keyboard(
+                keypress!(@special SpecialKey::Enter)
+            )),
+            Some(TextInputEvent::Submit)
+        );
+        // Pasted text is kept on one line.
+        assert_eq2!(
+            TextInputEvent::try_from_input_event(&InputEvent::Paste("a\nb".to_string())),
+            Some(TextInputEvent::InsertString("a b".to_string()))
+        );
+        // Other keys are not handled.
+        assert_eq2!(
+            TextInputEvent::try_from_input_event(&InputEvent::Keyboard(
+                keypress!(@special SpecialKey::Up)
+            )),
+            None
+        );
+    }
+
+    #[test]
+    fn test_insert_and_delete() {
+        let mut buffer = TextInputBuffer::default();
+        assert!(apply(
+            &mut buffer,
+            TextInputEvent::InsertString("ab😃".into())
+        ));
+        assert_eq2!(buffer.caret_index, 3);
+
+        // Edit in the middle of the text.
+        apply(&mut buffer, TextInputEvent::Left);
+        assert!(apply(&mut buffer, TextInputEvent::Backspace));
+        assert_eq2!(buffer.text, "a😃");
+        assert!(apply(&mut buffer, TextInputEvent::Delete));
+        assert_eq2!(buffer.text, "a");
+        assert_eq2!(buffer.caret_index, 1);
+
+        // Nothing to delete.
+        assert!(!apply(&mut buffer, TextInputEvent::Delete));
+        apply(&mut buffer, TextInputEvent::Home);
+        assert!(!apply(&mut buffer, TextInputEvent::Backspace));
+
+        // The inserted text is cut short to fit the max length.
+        let mut buffer = TextInputBuffer::new("abc");
+        let word_char_class = WordCharClass::default();
+        assert!(buffer.apply_event(
+            &TextInputEvent::InsertString("def".into()),
+            Some(5),
+            &word_char_class
+        ));
+        assert_eq2!(buffer.text, "abcde");
+        assert!(!buffer.apply_event(
+            &TextInputEvent::InsertString("f".into()),
+            Some(5),
+            &word_char_class
+        ));
+    }
+
+    #[test]
+    fn test_word_motions() {
+        let mut buffer = TextInputBuffer::new("foo_bar, baz");
+
+        apply(&mut buffer, TextInputEvent::PrevWord);
+        assert_eq2!(buffer.caret_index, 9);
+        apply(&mut buffer, TextInputEvent::PrevWord);
+        assert_eq2!(buffer.caret_index, 7);
+        apply(&mut buffer, TextInputEvent::PrevWord);
+        assert_eq2!(buffer.caret_index, 0);
+        apply(&mut buffer, TextInputEvent::PrevWord);
+        assert_eq2!(buffer.caret_index, 0);
+
+        apply(&mut buffer, TextInputEvent::NextWord);
+        assert_eq2!(buffer.caret_index, 7);
+        apply(&mut buffer, TextInputEvent::NextWord);
+        assert_eq2!(buffer.caret_index, 9);
+        apply(&mut buffer, TextInputEvent::NextWord);
+        assert_eq2!(buffer.caret_index, 12);
+    }
+
+    #[test]
+    fn test_scroll_caret_into_view() {
+        let mut buffer = TextInputBuffer::new("0123456789");
+        let display_text = buffer.get_display_text(None);
+
+        // The caret at the end of the text needs a col of its own.
+        buffer.scroll_caret_into_view(&display_text, ch!(5));
+        assert_eq2!(buffer.scroll_offset_col_index, ch!(6));
+
+        // In view, so no need to scroll.
+        buffer.caret_index = 7;
+        buffer.scroll_caret_into_view(&display_text, ch!(5));
+        assert_eq2!(buffer.scroll_offset_col_index, ch!(6));
+
+        // Left of the viewport.
+        buffer.caret_index = 2;
+        buffer.scroll_caret_into_view(&display_text, ch!(5));
+        assert_eq2!(buffer.scroll_offset_col_index, ch!(2));
+
+        // Each grapheme cluster is masked w/ one char.
+        let buffer = TextInputBuffer::new("p😃ss");
+        assert_eq2!(buffer.get_display_text(Some('*')).string, "****");
+    }
+
+    #[derive(Clone, Debug, Default, PartialEq)]
+    struct State {
+        text: String,
+        maybe_submitted_text: Option<String>,
+    }
+
+    #[derive(Clone, Debug, Default, PartialEq)]
+    enum Action {
+        #[default]
+        Noop,
+        Change(String),
+        Submit(String),
+    }
+
+    #[derive(Default)]
+    struct Reducer;
+
+    #[async_trait::async_trait]
+    impl AsyncReducer<State, Action> for Reducer {
+        async fn run(&self, action: &Action, state: &mut State) {
+            match action {
+                Action::Change(text) => state.text = text.clone(),
+                Action::Submit(text) => state.maybe_submitted_text = Some(text.clone()),
+                Action::Noop => {}
+            }
+        }
+    }
+
+    fn make_text_input_component(
+        config: TextInputConfig,
+    ) -> TextInputComponent<State, Action> {
+        TextInputComponent::new(
+            FlexBoxId::from(0),
+            config,
+            |shared_store, _, text| {
+                spawn_dispatch_action!(shared_store, Action::Change(text));
+            },
+            |shared_store, _, text| {
+                spawn_dispatch_action!(shared_store, Action::Submit(text));
+            },
+        )
+    }
+
+    #[tokio::test]
+    async fn test_text_input_component() {
+        let mut harness = ComponentTestHarness::<State, Action>::builder()
+            .set_reducer(Box::new(Reducer))
+            .set_window_size(size!(col_count: 5, row_count: 1))
+            .build()
+            .await;
+        let mut component = make_text_input_component(TextInputConfig::default());
+
+        let output = harness
+            .run_script(&mut component, "hello<C-Left>X<End>!<Up><Enter>")
+            .await
+            .unwrap();
+        assert_eq2!(
+            output.event_propagations[8..].to_vec(),
+            vec![
+                EventPropagation::ConsumedRender,
+                EventPropagation::Propagate,
+                EventPropagation::ConsumedRender,
+            ]
+        );
+        assert_eq2!(
+            harness.get_state().await,
+            State {
+                text: "Xhello!".to_string(),
+                maybe_submitted_text: Some("Xhello!".to_string()),
+            }
+        );
+
+        // Scrolled so that the caret (at the end of the text) is in view.
+        assert_eq2!(
+            output.get_painted_texts(),
+            vec!["llo! ".to_string(), DEFAULT_CURSOR_CHAR.to_string()]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_text_input_component_placeholder_and_mask() {
+        let mut harness = ComponentTestHarness::<State, Action>::builder()
+            .set_window_size(size!(col_count: 8, row_count: 1))
+            .set_has_focus(false)
+            .build()
+            .await;
+        let mut component = make_text_input_component(TextInputConfig {
+            maybe_placeholder: Some("Password".to_string()),
+            maybe_mask_char: Some('*'),
+            ..Default::default()
+        });
+
+        // The caret isn't painted w/out focus.
+        let output = harness.run(&mut component, vec![]).await.unwrap();
+        assert_eq2!(output.get_painted_texts(), vec!["Password".to_string()]);
+
+        component.set_text("abc");
+        let output = harness.run(&mut component, vec![]).await.unwrap();
+        assert_eq2!(output.get_painted_texts(), vec!["***     ".to_string()]);
+        assert_eq2!(component.get_text(), "abc");
+    }
+}
@@ -0,0 +1,306 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use r3bl_rs_utils_core::*;
+
+use crate::*;
+
+/// The text of a [TextInputComponent], and where its caret is. The caret is a grapheme
+/// cluster index, so it is never in the middle of a cluster.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TextInputBuffer {
+    pub text: String,
+    /// Goes from `0` to the number of grapheme clusters in the `text`, ie: the caret is
+    /// past the last cluster when it is at the end of the `text`.
+    pub caret_index: usize,
+    /// How many display cols of the text are scrolled out of view to the left.
+    pub scroll_offset_col_index: ChUnit,
+}
+
+/// The edits & caret movements that a [TextInputComponent] handles.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TextInputEvent {
+    InsertString(String),
+    Backspace,
+    Delete,
+    Left,
+    Right,
+    Home,
+    End,
+    NextWord,
+    PrevWord,
+    Submit,
+}
+
+mod text_input_event_impl {
+    use super::*;
+
+    impl TextInputEvent {
+        /// Typed chars are inserted, and so is pasted text (w/ its line breaks replaced
+        /// by spaces). Ctrl+Left & Ctrl+Right (or Alt+B & Alt+F) move by word, Ctrl+A &
+        /// Ctrl+E are the same as Home & End, and Enter submits.
+        pub fn try_from_input_event(input_event: &InputEvent) -> Option<Self> {
+            let key_press = match input_event {
+                InputEvent::Keyboard(key_press) => key_press,
+                InputEvent::Paste(text) => {
+                    let text = text.lines().collect::<Vec<_>>().join(SPACER);
+                    return Some(TextInputEvent::InsertString(text));
+                }
+                _ => return None,
+            };
+
+            if let KeyPress::Plain {
+                key: Key::Character(character),
+            } = key_press
+            {
+                return Some(TextInputEvent::InsertString(character.to_string()));
+            }
+
+            let ctrl = ModifierKeysMask::CTRL;
+            let alt = ModifierKeysMask::ALT;
+            let key_map = [
+                (
+                    keypress!(@special SpecialKey::Backspace),
+                    TextInputEvent::Backspace,
+                ),
+                (
+                    keypress!(@special SpecialKey::Delete),
+                    TextInputEvent::Delete,
+                ),
+                (keypress!(@special SpecialKey::Left), TextInputEvent::Left),
+                (keypress!(@special SpecialKey::Right), TextInputEvent::Right),
+                (keypress!(@special SpecialKey::Home), TextInputEvent::Home),
+                (keypress!(@special SpecialKey::End), TextInputEvent::End),
+                (
+                    keypress!(@special SpecialKey::Enter),
+                    TextInputEvent::Submit,
+                ),
+                (
+                    keypress!(@special ctrl, SpecialKey::Left),
+                    TextInputEvent::PrevWord,
+                ),
+                (
+                    keypress!(@special ctrl, SpecialKey::Right),
+                    TextInputEvent::NextWord,
+                ),
+                (keypress!(@char alt, 'b'), TextInputEvent::PrevWord),
+                (keypress!(@char alt, 'f'), TextInputEvent::NextWord),
+                (keypress!(@char ctrl, 'a'), TextInputEvent::Home),
+                (keypress!(@char ctrl, 'e'), TextInputEvent::End),
+            ];
+            key_map
+                .into_iter()
+                .find(|(it, _)| it == key_press)
+                .map(|(_, text_input_event)| text_input_event)
+        }
+    }
+}
+
+mod text_input_buffer_impl {
+    use super::*;
+
+    impl TextInputBuffer {
+        /// The caret is placed at the end of the `text`.
+        pub fn new(text: &str) -> Self {
+            let mut it = Self {
+                text: text.to_string(),
+                ..Default::default()
+            };
+            it.caret_index = it.get_segments().len();
+            it
+        }
+
+        pub fn get_segments(&self) -> Vec<GraphemeClusterSegment> {
+            UnicodeString::from(self.text.as_str()).vec_segment
+        }
+
+        /// Apply an edit or caret movement, & return whether the `text` changed. The
+        /// inserted text is cut short so that the `text` has at most `maybe_max_length`
+        /// grapheme clusters. [TextInputEvent::Submit] is handled by the
+        /// [TextInputComponent], so it does nothing here.
+        pub fn apply_event(
+            &mut self,
+            text_input_event: &TextInputEvent,
+            maybe_max_length: Option<usize>,
+            word_char_class: &WordCharClass,
+        ) -> bool {
+            let mut segments = self
+                .get_segments()
+                .into_iter()
+                .map(|segment| segment.string)
+                .collect::<Vec<_>>();
+            self.caret_index = self.caret_index.min(segments.len());
+
+            match text_input_event {
+                TextInputEvent::InsertString(chunk) => {
+                    let mut chunk_segments = UnicodeString::from(chunk.as_str())
+                        .vec_segment
+                        .into_iter()
+                        .map(|segment| segment.string)
+                        .collect::<Vec<_>>();
+                    if let Some(max_length) = maybe_max_length {
+                        chunk_segments
+                            .truncate(max_length.saturating_sub(segments.len()));
+                    }
+                    if chunk_segments.is_empty() {
+                        return false;
+                    }
+                    let caret_index = self.caret_index + chunk_segments.len();
+                    let segments_after_caret = segments.split_off(self.caret_index);
+                    segments.extend(chunk_segments);
+                    segments.extend(segments_after_caret);
+                    self.text = segments.concat();
+                    // A combining char merges w/ the cluster before it.
+                    self.caret_index = caret_index.min(self.get_segments().len());
+                    true
+                }
+                TextInputEvent::Backspace => {
+                    if self.caret_index == 0 {
+                        return false;
+                    }
+                    self.caret_index -= 1;
+                    segments.remove(self.caret_index);
+                    self.text = segments.concat();
+                    true
+                }
+                TextInputEvent::Delete => {
+                    if self.caret_index == segments.len() {
+                        return false;
+                    }
+                    segments.remove(self.caret_index);
+                    self.text = segments.concat();
+                    true
+                }
+                TextInputEvent::Left => {
+                    self.caret_index = self.caret_index.saturating_sub(1);
+                    false
+                }
+                TextInputEvent::Right => {
+                    self.caret_index = (self.caret_index + 1).min(segments.len());
+                    false
+                }
+                TextInputEvent::Home => {
+                    self.caret_index = 0;
+                    false
+                }
+                TextInputEvent::End => {
+                    self.caret_index = segments.len();
+                    false
+                }
+                TextInputEvent::NextWord => {
+                    self.caret_index = self.find_next_word_start(word_char_class);
+                    false
+                }
+                TextInputEvent::PrevWord => {
+                    self.caret_index = self.find_prev_word_start(word_char_class);
+                    false
+                }
+                TextInputEvent::Submit => false,
+            }
+        }
+
+        /// Same as the word motions of the [EditorComponent]: skip the rest of the word
+        /// that the caret is on, then the whitespace after it. Returns the end of the text
+        /// if there is no next word.
+        fn find_next_word_start(&self, word_char_class: &WordCharClass) -> usize {
+            let segments = self.get_segments();
+            let Some(segment_at_caret) = segments.get(self.caret_index) else {
+                return segments.len();
+            };
+            let current_class = word_char_class.get_char_class(segment_at_caret);
+            segments
+                .iter()
+                .skip(self.caret_index)
+                .skip_while(|segment| {
+                    current_class != CharClass::Whitespace
+                        && word_char_class.get_char_class(segment) == current_class
+                })
+                .find(|segment| {
+                    word_char_class.get_char_class(segment) != CharClass::Whitespace
+                })
+                .map_or(segments.len(), |segment| segment.logical_index)
+        }
+
+        /// Skip the whitespace before the caret, then go to the start of the word before
+        /// it. Returns `0` if there is no previous word.
+        fn find_prev_word_start(&self, word_char_class: &WordCharClass) -> usize {
+            let segments = self.get_segments();
+            let mut iter = segments
+                .iter()
+                .take(self.caret_index)
+                .rev()
+                .skip_while(|segment| {
+                    word_char_class.get_char_class(segment) == CharClass::Whitespace
+                })
+                .peekable();
+            let Some(word_class) = iter
+                .peek()
+                .map(|segment| word_char_class.get_char_class(segment))
+            else {
+                return 0;
+            };
+            iter.take_while(|segment| {
+                word_char_class.get_char_class(segment) == word_class
+            })
+            .last()
+            .map_or(0, |segment| segment.logical_index)
+        }
+
+        /// The text that is painted, ie: each grapheme cluster is replaced by the
+        /// `maybe_mask_char` (if any), eg: for passwords.
+        pub fn get_display_text(&self, maybe_mask_char: Option<char>) -> UnicodeString {
+            match maybe_mask_char {
+                Some(mask_char) => {
+                    let cluster_count = self.get_segments().len();
+                    UnicodeString::from(mask_char.to_string().repeat(cluster_count))
+                }
+                None => UnicodeString::from(self.text.as_str()),
+            }
+        }
+
+        /// The display col of the caret in the `display_text` (from
+        /// [get_display_text](TextInputBuffer::get_display_text)), ignoring the scroll
+        /// offset.
+        pub fn get_caret_display_col_index(
+            &self,
+            display_text: &UnicodeString,
+        ) -> ChUnit {
+            match display_text.vec_segment.get(self.caret_index) {
+                Some(segment) => segment.display_col_offset,
+                None => display_text.display_width,
+            }
+        }
+
+        /// Scroll horizontally (just enough) so that the caret is in the `viewport_width`
+        /// cols. When the caret is at the end of the text, it needs a col of its own.
+        pub fn scroll_caret_into_view(
+            &mut self,
+            display_text: &UnicodeString,
+            viewport_width: ChUnit,
+        ) {
+            if viewport_width == ch!(0) {
+                return;
+            }
+            let caret_col_index = self.get_caret_display_col_index(display_text);
+            if caret_col_index < self.scroll_offset_col_index {
+                self.scroll_offset_col_index = caret_col_index;
+            } else if caret_col_index >= self.scroll_offset_col_index + viewport_width {
+                self.scroll_offset_col_index = caret_col_index + ch!(1) - viewport_width;
+            }
+        }
+    }
+}
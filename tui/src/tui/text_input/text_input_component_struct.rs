@@ -0,0 +1,242 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::{fmt::Debug, sync::Arc};
+
+use async_trait::async_trait;
+use r3bl_redux::*;
+use r3bl_rs_utils_core::*;
+use r3bl_rs_utils_macro::style;
+use tokio::sync::RwLock;
+
+use crate::*;
+
+/// This is a reusable [Component] for a single line of text input, eg: in forms & search
+/// bars. It is much lighter than the [EditorComponent], which it is modeled after.
+/// - The text is painted in the first row of its box, & scrolls horizontally to keep the
+///   caret in view. When the text is empty, the placeholder (if any) is painted instead.
+/// - It handles the keys in [TextInputEvent::try_from_input_event]. The other keys are
+///   propagated.
+/// - The `on_change_handler` is called w/ the new text when it changes, and the
+///   `on_submit_handler` is called w/ the text when Enter is pressed. Typically they
+///   dispatch an action to the store.
+#[derive(Clone)]
+pub struct TextInputComponent<S, A>
+where
+    S: Debug + Default + Clone + PartialEq + Sync + Send,
+    A: Debug + Default + Clone + Sync + Send,
+{
+    pub id: FlexBoxId,
+    pub config: TextInputConfig,
+    pub buffer: TextInputBuffer,
+    pub on_change_handler: Option<OnTextInputChangeFn<S, A>>,
+    pub on_submit_handler: Option<OnTextInputSubmitFn<S, A>>,
+}
+
+pub type OnTextInputChangeFn<S, A> = fn(&SharedStore<S, A>, FlexBoxId, String);
+
+pub type OnTextInputSubmitFn<S, A> = fn(&SharedStore<S, A>, FlexBoxId, String);
+
+pub mod text_input_component_impl {
+    use super::*;
+
+    #[async_trait]
+    impl<S, A> Component<S, A> for TextInputComponent<S, A>
+    where
+        S: Debug + Default + Clone + PartialEq + Sync + Send + 'static,
+        A: Debug + Default + Clone + Sync + Send + 'static,
+    {
+        fn reset(&mut self) { self.buffer = TextInputBuffer::default(); }
+
+        fn get_id(&self) -> FlexBoxId { self.id }
+
+        async fn handle_event(
+            &mut self,
+            args: ComponentScopeArgs<'_, S, A>,
+            input_event: &InputEvent,
+        ) -> CommonResult<EventPropagation> {
+            let ComponentScopeArgs { shared_store, .. } = args;
+
+            let Some(text_input_event) =
+                TextInputEvent::try_from_input_event(input_event)
+            else {
+                return Ok(EventPropagation::Propagate);
+            };
+
+            if text_input_event == TextInputEvent::Submit {
+                if let Some(on_submit_handler) = self.on_submit_handler {
+                    on_submit_handler(shared_store, self.id, self.buffer.text.clone());
+                }
+                return Ok(EventPropagation::ConsumedRender);
+            }
+
+            let is_text_changed = self.buffer.apply_event(
+                &text_input_event,
+                self.config.maybe_max_length,
+                &self.config.word_char_class,
+            );
+            if is_text_changed {
+                if let Some(on_change_handler) = self.on_change_handler {
+                    on_change_handler(shared_store, self.id, self.buffer.text.clone());
+                }
+            }
+
+            Ok(EventPropagation::ConsumedRender)
+        }
+
+        async fn render(
+            &mut self,
+            args: ComponentScopeArgs<'_, S, A>,
+            current_box: &FlexBox,
+            _surface_bounds: SurfaceBounds, /* Ignore this. */
+        ) -> CommonResult<RenderPipeline> {
+            let ComponentScopeArgs {
+                component_registry, ..
+            } = args;
+
+            let origin_pos = current_box.style_adjusted_origin_pos;
+            let col_count = current_box.style_adjusted_bounds_size.col_count;
+
+            // The width of the box is only known here, so this is where the text scrolls.
+            let display_text = self.buffer.get_display_text(self.config.maybe_mask_char);
+            self.buffer.scroll_caret_into_view(&display_text, col_count);
+
+            // Paint the text, or the placeholder if there isn't any.
+            let (painted_text, clipped_text, maybe_style) =
+                match (&self.config.maybe_placeholder, self.buffer.text.is_empty()) {
+                    (Some(placeholder), true) => {
+                        let placeholder = UnicodeString::from(placeholder.as_str());
+                        let clipped_text =
+                            placeholder.clip_to_width(ch!(0), col_count).to_string();
+                        let style = match self.config.maybe_style {
+                            Some(style) => style + self.config.placeholder_style,
+                            None => self.config.placeholder_style,
+                        };
+                        (placeholder, clipped_text, Some(style))
+                    }
+                    _ => {
+                        let clipped_text = display_text
+                            .clip_to_width(self.buffer.scroll_offset_col_index, col_count)
+                            .to_string();
+                        (display_text, clipped_text, self.config.maybe_style)
+                    }
+                };
+            let padding_len =
+                col_count - ch!(UnicodeString::str_display_width(&clipped_text));
+            let padded_text = format!(
+                "{clipped_text}{}",
+                SPACER.repeat(ch!(@to_usize padding_len))
+            );
+
+            let mut render_ops = render_ops!();
+            render_ops.push(RenderOp::MoveCursorPositionRelTo(
+                origin_pos,
+                position! { col_index: 0, row_index: 0 },
+            ));
+            render_ops.push(RenderOp::ApplyColors(maybe_style));
+            render_ops.push(RenderOp::PaintTextWithAttributes(padded_text, maybe_style));
+            render_ops.push(RenderOp::ResetColor);
+
+            // Paint the caret (over the placeholder if the text is empty), only if this
+            // component has focus.
+            if component_registry.has_focus.does_id_have_focus(self.id) {
+                let caret_position = position! {
+                    col_index: self.buffer.get_caret_display_col_index(&painted_text)
+                        - self.buffer.scroll_offset_col_index,
+                    row_index: 0
+                };
+                let str_at_caret =
+                    match painted_text.vec_segment.get(self.buffer.caret_index) {
+                        Some(segment) => segment.string.clone(),
+                        None => DEFAULT_CURSOR_CHAR.to_string(),
+                    };
+                render_ops.push(RenderOp::MoveCursorPositionRelTo(
+                    origin_pos,
+                    caret_position,
+                ));
+                render_ops.push(RenderOp::PaintTextWithAttributes(
+                    str_at_caret,
+                    style! { attrib: [reverse] }.into(),
+                ));
+                render_ops.push(RenderOp::MoveCursorPositionRelTo(
+                    origin_pos,
+                    caret_position,
+                ));
+                render_ops.push(RenderOp::ResetColor);
+            }
+
+            let mut render_pipeline = render_pipeline!();
+            render_pipeline.push(ZOrder::Normal, render_ops);
+            Ok(render_pipeline)
+        }
+    }
+
+    impl<S, A> TextInputComponent<S, A>
+    where
+        S: Debug + Default + Clone + PartialEq + Sync + Send,
+        A: Debug + Default + Clone + Sync + Send,
+    {
+        pub fn get_text(&self) -> &str { &self.buffer.text }
+
+        /// Replace the text, & move the caret to the end of it. The `on_change_handler`
+        /// isn't called.
+        pub fn set_text(&mut self, text: &str) {
+            self.buffer = TextInputBuffer::new(text);
+        }
+    }
+}
+pub use text_input_component_impl::*;
+
+mod constructor {
+    use super::*;
+
+    impl<S, A> TextInputComponent<S, A>
+    where
+        S: Debug + Default + Clone + PartialEq + Sync + Send,
+        A: Debug + Default + Clone + Sync + Send,
+    {
+        /// The on_change handler is a lambda that is called w/ the new text each time it
+        /// changes, and the on_submit handler is called w/ the text when Enter is pressed.
+        /// Typically this results in a Redux action being created and then dispatched to
+        /// the given store.
+        pub fn new(
+            id: FlexBoxId,
+            config: TextInputConfig,
+            on_change: OnTextInputChangeFn<S, A>,
+            on_submit: OnTextInputSubmitFn<S, A>,
+        ) -> Self {
+            Self {
+                id,
+                config,
+                buffer: TextInputBuffer::default(),
+                on_change_handler: Some(on_change),
+                on_submit_handler: Some(on_submit),
+            }
+        }
+
+        pub fn new_shared(
+            id: FlexBoxId,
+            config: TextInputConfig,
+            on_change: OnTextInputChangeFn<S, A>,
+            on_submit: OnTextInputSubmitFn<S, A>,
+        ) -> Arc<RwLock<Self>> {
+            Arc::new(RwLock::new(TextInputComponent::new(
+                id, config, on_change, on_submit,
+            )))
+        }
+    }
+}
@@ -0,0 +1,55 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use r3bl_rs_utils_core::*;
+
+use crate::*;
+
+/// Configure what the [TextInputComponent] accepts, & how it renders its text.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TextInputConfig {
+    /// Painted w/ the `placeholder_style` when the text is empty.
+    pub maybe_placeholder: Option<String>,
+    /// The max number of grapheme clusters in the text. Typed or pasted text that doesn't
+    /// fit is cut short.
+    pub maybe_max_length: Option<usize>,
+    /// When set, each grapheme cluster of the text is painted as this char, eg: `'*'`
+    /// for passwords. The text that is passed to the handlers isn't masked.
+    pub maybe_mask_char: Option<char>,
+    /// Which chars are part of a word, for Ctrl+Left & Ctrl+Right.
+    pub word_char_class: WordCharClass,
+    /// This fills the whole row, even past the end of the text.
+    pub maybe_style: Option<Style>,
+    pub placeholder_style: Style,
+}
+
+mod text_input_config_impl {
+    use super::*;
+
+    impl Default for TextInputConfig {
+        fn default() -> Self {
+            Self {
+                maybe_placeholder: None,
+                maybe_max_length: None,
+                maybe_mask_char: None,
+                word_char_class: WordCharClass::default(),
+                maybe_style: None,
+                placeholder_style: get_text_input_placeholder_style(),
+            }
+        }
+    }
+}
@@ -0,0 +1,338 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::{fmt::Debug,
+          sync::{Arc, OnceLock}};
+
+use r3bl_redux::*;
+use r3bl_rs_utils_core::*;
+use r3bl_rs_utils_macro::style;
+use syntect::highlighting::{Theme, ThemeSet};
+
+use crate::*;
+
+pub const BUILTIN_THEME_DARK_ID: &str = "dark";
+pub const BUILTIN_THEME_LIGHT_ID: &str = "light";
+
+/// Well known [Style] ids that the reusable components ([DialogEngine], etc) look up in
+/// the active theme's [Stylesheet]. They are at the end of the `u8` range so that they
+/// don't clash w/ the ids that apps use for their own styles.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ThemeStyleId {
+    DialogBorder = 200,
+    DialogTitle = 201,
+    DialogEditor = 202,
+    DialogResultsPanel = 203,
+    DialogBorderTitle = 204,
+    DialogResultsPanelMatch = 205,
+}
+
+/// A named set of styles for the entire TUI:
+/// - The [Stylesheet] is used by [Surface] layouts, and by the reusable components (using
+///   the [ThemeStyleId]s).
+/// - The syntect [Theme] is used for syntax highlighting by [EditorEngine].
+/// - The [MarkdownStyleConfig] is used to highlight markdown by [EditorEngine] and
+///   [MarkdownViewComponent].
+#[derive(Clone, Debug)]
+pub struct TuiTheme {
+    pub id: String,
+    pub stylesheet: Stylesheet,
+    /// This is a very heavy object to create, so it is shared (and not cloned) between
+    /// renders.
+    pub syntect_theme: Arc<Theme>,
+    pub markdown_style_config: MarkdownStyleConfig,
+}
+
+impl TuiTheme {
+    pub fn new(id: &str, stylesheet: Stylesheet, syntect_theme: Theme) -> Self {
+        Self {
+            id: id.to_string(),
+            stylesheet,
+            syntect_theme: Arc::new(syntect_theme),
+            markdown_style_config: MarkdownStyleConfig::default(),
+        }
+    }
+
+    pub fn with_markdown_style_config(
+        mut self,
+        markdown_style_config: MarkdownStyleConfig,
+    ) -> Self {
+        self.markdown_style_config = markdown_style_config;
+        self
+    }
+
+    pub fn find_style(&self, id: ThemeStyleId) -> Option<Style> {
+        self.stylesheet.find_style_by_id(id as u8)
+    }
+
+    /// The `app_stylesheet` w/ the styles of this theme layered on top of it, ie: the
+    /// styles w/ the same id come from the theme. Use this for the [Surface] that is
+    /// created in [App::app_render], so that the layouts use the active theme, eg:
+    /// `surface!(stylesheet: theme.get_surface_stylesheet(create_stylesheet()?))`.
+    pub fn get_surface_stylesheet(&self, app_stylesheet: Stylesheet) -> Stylesheet {
+        let mut stylesheet = app_stylesheet;
+        for style in &self.stylesheet.styles {
+            stylesheet.styles.retain(|it| it.id != style.id);
+            stylesheet.styles.push(*style);
+        }
+        stylesheet
+    }
+}
+
+/// Holds all the [TuiTheme]s that the user can switch between at runtime, and which one
+/// is active. It lives in [GlobalData] so that the [EditorEngine] & [DialogEngine] can
+/// read their styles through the active theme each time they render, instead of caching
+/// them. This is why switching themes doesn't require the component tree to be rebuilt.
+/// The other places that the active theme is used:
+/// - The app's [Surface], if it is created w/ [TuiTheme::get_surface_stylesheet].
+/// - The styles in [DialogEngineConfigOptions] (if set) take precedence over the theme.
+///
+/// It is populated w/ the [BUILTIN_THEME_DARK_ID] (active by default) and
+/// [BUILTIN_THEME_LIGHT_ID] themes.
+#[derive(Clone, Debug, Default)]
+pub struct ThemeRegistry {
+    /// Creating the builtin themes loads the syntect themes, which is slow. So it is
+    /// deferred until the registry is first used, instead of in [GlobalData::default].
+    lazy_themes: OnceLock<Vec<TuiTheme>>,
+    active_theme_index: usize,
+}
+
+impl ThemeRegistry {
+    fn get_themes(&self) -> &Vec<TuiTheme> {
+        self.lazy_themes.get_or_init(builtin_themes::create_all)
+    }
+
+    fn get_themes_mut(&mut self) -> &mut Vec<TuiTheme> {
+        self.get_themes();
+        self.lazy_themes
+            .get_mut()
+            .expect("The themes are created by get_themes")
+    }
+
+    /// If a theme w/ the same id is already registered, it is replaced.
+    pub fn register(&mut self, theme: TuiTheme) {
+        let themes = self.get_themes_mut();
+        match themes.iter().position(|it| it.id == theme.id) {
+            Some(index) => themes[index] = theme,
+            None => themes.push(theme),
+        }
+    }
+
+    pub fn set_active_theme(&mut self, id: &str) -> CommonResult<()> {
+        match self.get_themes().iter().position(|it| it.id == id) {
+            Some(index) => {
+                self.active_theme_index = index;
+                Ok(())
+            }
+            None => CommonError::new(
+                CommonErrorType::InvalidArguments,
+                &format!("No theme registered w/ id: {id}"),
+            ),
+        }
+    }
+
+    pub fn get_active_theme(&self) -> &TuiTheme {
+        &self.get_themes()[self.active_theme_index]
+    }
+
+    pub fn get_theme_ids(&self) -> Vec<&str> {
+        self.get_themes().iter().map(|it| it.id.as_str()).collect()
+    }
+
+    /// Returns the id of the theme after the active one (wrapping around), which is
+    /// handy for a "switch theme" command.
+    pub fn get_next_theme_id(&self) -> &str {
+        let themes = self.get_themes();
+        let next_index = (self.active_theme_index + 1) % themes.len();
+        &themes[next_index].id
+    }
+}
+
+/// Switch the active theme & re-render the entire app w/ it.
+/// - The saved offscreen buffer is discarded so that every cell is repainted.
+/// - The re-render is triggered by dispatching the default action `A` (since all the
+///   subscribers of the `shared_store` are run after each dispatch).
+pub async fn set_active_theme<S, A>(
+    shared_global_data: &SharedGlobalData,
+    shared_store: &SharedStore<S, A>,
+    id: &str,
+) -> CommonResult<()>
+where
+    S: Default + Clone + PartialEq + Debug + Sync + Send + 'static,
+    A: Debug + Default + Clone + Sync + Send + 'static,
+{
+    shared_global_data.write().await.set_active_theme(id)?;
+    spawn_dispatch_action!(shared_store, A::default());
+    Ok(())
+}
+
+mod builtin_themes {
+    use super::*;
+
+    pub fn create_all() -> Vec<TuiTheme> { vec![dark(), light()] }
+
+    pub fn dark() -> TuiTheme {
+        let syntect_theme =
+            try_load_r3bl_theme().unwrap_or_else(|_| load_default_theme());
+        let stylesheet = Stylesheet {
+            styles: vec![
+                style! {
+                  id: ThemeStyleId::DialogBorder as u8
+                  lolcat: true
+                },
+                style! {
+                  id: ThemeStyleId::DialogTitle as u8
+                  lolcat: true
+                },
+                style! {
+                  id: ThemeStyleId::DialogEditor as u8
+                  attrib: [bold]
+                  color_fg: TuiColor::Basic(ANSIBasicColor::Magenta)
+                },
+                style! {
+                  id: ThemeStyleId::DialogResultsPanel as u8
+                  color_fg: TuiColor::Basic(ANSIBasicColor::Blue)
+                },
+                style! {
+                  id: ThemeStyleId::DialogBorderTitle as u8
+                  attrib: [bold]
+                  color_fg: TuiColor::Basic(ANSIBasicColor::Yellow)
+                },
+                style! {
+                  id: ThemeStyleId::DialogResultsPanelMatch as u8
+                  attrib: [bold]
+                  color_fg: TuiColor::Basic(ANSIBasicColor::Cyan)
+                },
+            ],
+            ..Default::default()
+        };
+        TuiTheme::new(BUILTIN_THEME_DARK_ID, stylesheet, syntect_theme)
+    }
+
+    pub fn light() -> TuiTheme {
+        let syntect_theme = {
+            let mut theme_set = ThemeSet::load_defaults();
+            theme_set
+                .themes
+                .remove("InspiredGitHub")
+                .unwrap_or_else(load_default_theme)
+        };
+        let stylesheet = Stylesheet {
+            styles: vec![
+                style! {
+                  id: ThemeStyleId::DialogBorder as u8
+                  color_fg: TuiColor::Basic(ANSIBasicColor::DarkGrey)
+                },
+                style! {
+                  id: ThemeStyleId::DialogTitle as u8
+                  attrib: [bold]
+                  color_fg: TuiColor::Basic(ANSIBasicColor::DarkBlue)
+                },
+                style! {
+                  id: ThemeStyleId::DialogEditor as u8
+                  color_fg: TuiColor::Basic(ANSIBasicColor::Black)
+                },
+                style! {
+                  id: ThemeStyleId::DialogResultsPanel as u8
+                  color_fg: TuiColor::Basic(ANSIBasicColor::DarkMagenta)
+                },
+                style! {
+                  id: ThemeStyleId::DialogBorderTitle as u8
+                  attrib: [bold]
+                  color_fg: TuiColor::Basic(ANSIBasicColor::DarkRed)
+                },
+                style! {
+                  id: ThemeStyleId::DialogResultsPanelMatch as u8
+                  attrib: [bold]
+                  color_fg: TuiColor::Basic(ANSIBasicColor::DarkBlue)
+                },
+            ],
+            ..Default::default()
+        };
+        TuiTheme::new(BUILTIN_THEME_LIGHT_ID, stylesheet, syntect_theme)
+    }
+}
+
+#[cfg(test)]
+mod test_theme_registry {
+    use super::*;
+
+    #[test]
+    fn test_switch_and_register_themes() {
+        // The builtin themes are created on first use.
+        let mut registry = ThemeRegistry::default();
+        assert!(registry.lazy_themes.get().is_none());
+        assert_eq2!(
+            registry.get_theme_ids(),
+            vec![BUILTIN_THEME_DARK_ID, BUILTIN_THEME_LIGHT_ID]
+        );
+        assert_eq2!(registry.get_active_theme().id, BUILTIN_THEME_DARK_ID);
+        assert_eq2!(registry.get_next_theme_id(), BUILTIN_THEME_LIGHT_ID);
+
+        registry.set_active_theme(BUILTIN_THEME_LIGHT_ID).unwrap();
+        assert_eq2!(registry.get_active_theme().id, BUILTIN_THEME_LIGHT_ID);
+        assert!(registry
+            .get_active_theme()
+            .find_style(ThemeStyleId::DialogBorder)
+            .is_some());
+        assert_eq2!(registry.get_next_theme_id(), BUILTIN_THEME_DARK_ID);
+
+        // Unknown ids are rejected, and the active theme doesn't change.
+        assert!(registry.set_active_theme("does-not-exist").is_err());
+        assert_eq2!(registry.get_active_theme().id, BUILTIN_THEME_LIGHT_ID);
+
+        // Registering a theme w/ an existing id replaces it.
+        registry.register(TuiTheme::new(
+            BUILTIN_THEME_LIGHT_ID,
+            Stylesheet::new(),
+            load_default_theme(),
+        ));
+        assert_eq2!(registry.get_theme_ids().len(), 2);
+        assert!(registry
+            .get_active_theme()
+            .find_style(ThemeStyleId::DialogBorder)
+            .is_none());
+    }
+
+    #[test]
+    fn test_surface_stylesheet() {
+        let app_stylesheet = Stylesheet {
+            styles: vec![
+                style! { id: 1 padding: 1 },
+                style! { id: ThemeStyleId::DialogBorder as u8 padding: 2 },
+            ],
+            ..Default::default()
+        };
+        let registry = ThemeRegistry::default();
+        let theme = registry.get_active_theme();
+
+        // The app's styles are kept, unless the theme has a style w/ the same id.
+        let stylesheet = theme.get_surface_stylesheet(app_stylesheet);
+        assert_eq2!(
+            stylesheet.find_style_by_id(1).map(|it| it.padding),
+            Some(Some(ch!(1)))
+        );
+        assert_eq2!(
+            stylesheet.find_style_by_id(ThemeStyleId::DialogBorder as u8),
+            theme.find_style(ThemeStyleId::DialogBorder)
+        );
+        assert!(stylesheet
+            .find_style_by_id(ThemeStyleId::DialogEditor as u8)
+            .is_some());
+    }
+}